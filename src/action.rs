@@ -1,7 +1,26 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
-use strum::Display;
+use strum::{Display, IntoStaticStr};
+
+use crate::{
+  client::Exchange,
+  fuzz::CaseResult,
+  health::Health,
+  keymap::Keymap,
+  load::Sample,
+  mock::MockHit,
+  pages::home::ParsedSpec,
+  pagination::Page,
+  registry::RegistryApi,
+  report::TestReport,
+  request::HttpRequest,
+  toast::ToastLevel,
+  workflow::StepOutcome,
+  workspace::{Extraction, OperationKey, RequestDraft, ScriptTarget, Scripts},
+};
 
-#[derive(Debug, Clone, PartialEq, Serialize, Display, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Display, IntoStaticStr, Deserialize)]
 pub enum Action {
   Tick,
   Render,
@@ -9,6 +28,8 @@ pub enum Action {
   Suspend,
   Resume,
   Quit,
+  /// Quits despite the unsaved edits or requests in flight, never asking again for the spec when true.
+  ConfirmQuit(bool),
   Refresh,
   Error(String),
   Help,
@@ -16,10 +37,185 @@ pub enum Action {
   FocusPrev,
   Up,
   Down,
+  PageUp,
+  PageDown,
+  /// Moves to the first item of lists.
+  Top,
+  /// Moves to the last item of lists.
+  Bottom,
+  /// Moves to the start of the next section of lists, e.g. the next group of paths.
+  NextSection,
+  PrevSection,
   Submit,
   Update,
   Tab(u32),
   Go,
   Back,
+  /// Goes back to the schema at the depth of the trail of the shown schemas, the root at 0.
+  BackTo(usize),
+  /// Expands the next `oneOf`/`anyOf` branch of the shown schema.
+  NextBranch,
   ToggleFullScreen,
+  /// Grows the focused pane, its column and its height, the sizes being kept in the workspace.
+  GrowPane,
+  ShrinkPane,
+  /// Arranges the panes as the next layout of the config.
+  CycleLayout,
+  /// Arranges the panes as the layout of the given name.
+  SelectLayout(String),
+  /// Switches the navigation keys to the profile.
+  SelectKeymap(Keymap),
+  /// Wraps long lines of response bodies instead of cutting them.
+  ToggleWrap,
+  /// Turns the client-side cache of GET responses on or off.
+  ToggleCache,
+  /// Annotates the timestamps of response bodies with the local time, or stops.
+  ToggleLocalTimes,
+  /// Shows the request of the selected operation as it will be sent next to its inputs, or hides it.
+  TogglePreview,
+  /// Focuses the pane of the given index.
+  FocusPane(usize),
+  /// Opens the command palette, listing every action by title.
+  ShowCommands,
+  /// Lists the keys bound in the focused pane and on the page.
+  ShowHelp,
+  /// Closes the command palette and runs the chosen action.
+  RunCommand(Box<Action>),
+  ShowSnippets,
+  ClosePopup,
+  Copy(String),
+  /// Copies the url of the active request, variables substituted, to the clipboard.
+  CopyUrl,
+  /// Copies the path template of the active operation to the clipboard.
+  CopyPath,
+  /// Copies the `operationId` of the active operation to the clipboard.
+  CopyOperationId,
+  /// Opens the `externalDocs` of the active operation, or else of the spec, in the browser.
+  OpenDocs,
+  OpenUrl(String),
+  ExportHttp,
+  ExportHttpCollection,
+  ExportPostman,
+  ExportPostmanSaved,
+  SendRequest,
+  SendHttpRequest(Box<HttpRequest>, Option<OperationKey>),
+  ShowReplay,
+  ShowEnvironments,
+  SelectEnvironment(Option<String>),
+  ShowServers,
+  SetServerVariables(BTreeMap<String, String>),
+  /// Response to the request of the given id in the queue.
+  ResponseReceived(usize, Box<Exchange>),
+  RequestFailed(usize, String),
+  /// Probes the base url of the selected server, see `health::probe`.
+  ProbeHealth,
+  HealthProbed(Box<Health>),
+  /// Shows a toast, e.g. `Copied to clipboard`.
+  Notify(ToastLevel, String),
+  ExportHar,
+  /// Writes the schema of the given name as a standalone JSON Schema document.
+  ExportSchema(String, Box<serde_json::Value>),
+  /// Generates TypeScript declarations and Rust structs for the schema of the given name.
+  ShowTypes(String, Box<serde_json::Value>),
+  /// Writes generated code as `<name>.<extension>`.
+  ExportTypes(String, String, String),
+  /// Shows the example payload, as JSON, of the webhook of the given name.
+  ShowPayload(String, String),
+  ToggleSecrets,
+  /// Bookmarks the active operation as a favorite, or removes its bookmark.
+  ToggleBookmark,
+  /// Lists only the bookmarked operations, or all of them again.
+  ToggleFavorites,
+  /// Expands every group of the operations grouped by path or by tag.
+  ExpandAll,
+  /// Collapses every group of the operations grouped by path or by tag.
+  CollapseAll,
+  ShowSaveRequest,
+  /// Saves the active request under `folder/name`.
+  SaveRequest(String),
+  ShowExchange(Box<Exchange>),
+  /// Compares the response of the first exchange with the one of the second.
+  CompareExchanges(Box<Exchange>, Box<Exchange>),
+  /// Compares the response of the exchange with the previous response of its operation.
+  ShowResponseDiff(Box<Exchange>),
+  /// Compares the response of the exchange with the example the spec declares for its status and media type.
+  ShowExampleDiff(Box<Exchange>),
+  /// Sends the most recent request of the active operation again.
+  Resend,
+  /// Sends the request of the exchange again with `If-None-Match` and `If-Modified-Since` from its response.
+  Revalidate(Box<Exchange>),
+  ShowExtractions,
+  AddExtraction(Extraction),
+  RemoveExtraction(Extraction),
+  SendSavedRequest(usize),
+  ShowAssertions(usize),
+  SaveAssertions(usize, Vec<String>),
+  /// Edits the note of the active operation.
+  ShowNote,
+  SaveNote(OperationKey, String),
+  ShowScripts(ScriptTarget),
+  /// Edits the scripts of the active operation.
+  ShowOperationScripts,
+  SaveScripts(ScriptTarget, Scripts),
+  ShowWorkflows,
+  MockRequestReceived(Box<MockHit>),
+  RunWorkflow(usize),
+  WorkflowStepFinished(usize, Box<StepOutcome>),
+  /// Fuzzes the active operation with inputs generated from its schemas.
+  ShowFuzz,
+  RunFuzz,
+  FuzzCaseFinished(usize, Box<CaseResult>),
+  /// Fires the request of the active operation many times to measure its throughput and latencies.
+  ShowLoadTest,
+  /// Sends the request of the active operation the given number of times, that many at a time.
+  RunLoadTest(usize, usize),
+  LoadSample(Box<Sample>),
+  /// Follows the pages of the request of the active operation, concatenating their items.
+  FollowPagination,
+  PageFetched(Box<Page>),
+  /// Sends every listed `GET` operation with example inputs to check a deployment at once.
+  ShowSmokeTests,
+  RunSmokeTests,
+  SmokeTestFinished(usize, Box<CaseResult>),
+  /// Writes the results of a test run as JUnit XML and JSON.
+  ExportReport(Box<TestReport>),
+  /// Lists the findings of the spec linter.
+  ShowLint,
+  /// Shows the statistics dashboard of the spec.
+  ShowStats,
+  /// Shows the version, contact and license of the spec along with the documentation of its tags.
+  ShowSpecInfo,
+  /// Lists every operation, selects the given one and focuses the APIs pane.
+  SelectOperation(OperationKey),
+  /// Selects the target operation of a response link, its draft filled with the values taken from the response.
+  FollowLink(OperationKey, Box<RequestDraft>),
+  /// Lists the operations using the component schema of the given name.
+  ShowUsages(String),
+  /// Shows the two operations side by side, their parameters, bodies, responses and security.
+  CompareOperations(OperationKey, OperationKey),
+  /// Asks for the spec to compare the loaded one with.
+  ShowDiff,
+  /// Compares the loaded spec with the previous version at the given path.
+  DiffSpec(String),
+  /// Loads the spec at the given path in place of the page shown, after it failed to load.
+  LoadSpec(String),
+  /// Spec of the home page, parsed on a background task.
+  SpecLoaded(Box<ParsedSpec>),
+  /// Spec of the home page failed to load, with the error and its causes.
+  SpecFailed(String, Vec<String>),
+  /// Spec given by url changed on the server since loaded.
+  SpecChanged,
+  /// Loads the spec again, fetching it again when given by url, and keeps the selection.
+  ReloadSpec,
+  /// Lists the APIs of the configured registries to open one of them.
+  ShowRegistry,
+  /// APIs of the registries, along with the errors of those that could not be listed.
+  RegistryListed(Vec<RegistryApi>, Vec<String>),
+}
+
+impl Action {
+  /// Name of the variant, without the requests, responses and secrets some actions carry, e.g. `SendHttpRequest`.
+  pub fn name(&self) -> &'static str {
+    self.into()
+  }
 }
@@ -6,11 +6,64 @@ use tokio::sync::mpsc;
 
 use crate::{
   action::Action,
-  config::Config,
-  pages::{home::Home, Page},
+  asyncapi,
+  cli::{Cli, Command},
+  config::{normalize_key_event, Config, KeyScope},
+  crash, git, headless, merge,
+  pages::{asyncapi::AsyncApiPage, error::ErrorPage, home::Home, mock::MockPage, Page},
+  remote, spool,
+  theme::{self, Theme},
   tui,
 };
 
+/// The home page set up with the spec, imports, variables, workflows and cassette of the arguments.
+fn home(args: Cli) -> Result<Home> {
+  let home = Home::new(args.openapi_path.clone())?;
+  set_up(home, args)
+}
+
+fn set_up(mut home: Home, args: Cli) -> Result<Home> {
+  home.import(&args.import)?;
+  home.override_base_url(args.base_url);
+  home.override_hosts(&args.resolve)?;
+  home.limit_redirects(args.max_redirects);
+  home.load_env_files(&args.env_file)?;
+  home.load_workflows(&args.workflow)?;
+  home.use_cassette(args.record, args.replay)?;
+  home.watch_remote(args.refresh, args.offline);
+  home.start_at(args.operation, args.tag)?;
+  Ok(home)
+}
+
+/// The page of the command, or the error page when its spec fails to load.
+fn page(args: &Cli) -> Box<dyn Page> {
+  let (openapi_path, page) = match args.command.clone() {
+    Some(Command::Mock { spec, port }) => {
+      let openapi_path = spec.unwrap_or(args.openapi_path.clone());
+      let page = MockPage::new(openapi_path.clone(), port).map(|page| Box::new(page) as Box<dyn Page>);
+      (openapi_path, page)
+    },
+    _ if asyncapi::is_asyncapi(&args.openapi_path) => {
+      let page = AsyncApiPage::new(args.openapi_path.clone()).map(|page| Box::new(page) as Box<dyn Page>);
+      (args.openapi_path.clone(), page)
+    },
+    _ => {
+      // the spec is parsed on a background task, its page shown meanwhile
+      let home = Home::loading(args.openapi_path.clone()).and_then(|home| set_up(home, args.clone()));
+      (args.openapi_path.clone(), home.map(|page| Box::new(page) as Box<dyn Page>))
+    },
+  };
+  page.unwrap_or_else(|error| {
+    log::error!("Failed to load {openapi_path}: {error:?}");
+    Box::new(ErrorPage::new(openapi_path, error.chain().map(|cause| cause.to_string()).collect()))
+  })
+}
+
+/// Whether colors are left out, with `--no-color`, the `no_color` config or a non-empty `NO_COLOR` variable.
+fn no_color(args: &Cli, config: &Config, no_color_variable: Option<&str>) -> bool {
+  args.no_color || config.no_color || no_color_variable.is_some_and(|value| !value.is_empty())
+}
+
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Mode {
   #[default]
@@ -18,33 +71,117 @@ pub enum Mode {
 }
 
 pub struct App {
+  /// Arguments the pages are created from, again when their spec is loaded anew.
+  pub args: Cli,
   pub config: Config,
   pub pages: Vec<Box<dyn Page>>,
   pub should_quit: bool,
   pub should_suspend: bool,
+  /// An event or action may have changed what is shown since the last frame, which is rendered only then.
+  pub should_render: bool,
   pub mode: Mode,
   pub last_tick_key_events: Vec<KeyEvent>,
 }
 
 impl App {
-  pub fn new(openapi_path: String) -> Result<Self> {
-    let home = Home::new(openapi_path)?;
+  pub fn new(args: Cli) -> Result<Self> {
     let config = Config::new()?;
+    // panes take their styles from the theme when they are created
+    theme::set(match no_color(&args, &config, std::env::var("NO_COLOR").ok().as_deref()) {
+      true => Theme::mono(),
+      false => Theme::load(config.theme.as_deref(), &config.config._config_dir)?,
+    });
+    let page = page(&args);
+    crash::set_spec_path(&args.openapi_path);
     let mode = Mode::Home;
     Ok(Self {
-      pages: vec![Box::new(home)],
+      args,
+      pages: vec![page],
       should_quit: false,
       should_suspend: false,
+      should_render: true,
       config,
       mode,
       last_tick_key_events: Vec::new(),
     })
   }
 
+  /// Whether a frame is rendered: after events and actions, or while a page animates.
+  fn frame_due(&self) -> bool {
+    self.should_render || self.pages.iter().any(|page| page.animating())
+  }
+
+  /// Runs the tests of `openapi-tui test` and writes their reports, `false` when a test failed.
+  pub async fn test(args: Cli) -> Result<bool> {
+    let Some(Command::Test { environment, smoke, junit, json }) = args.command.clone() else {
+      return Ok(true);
+    };
+    let mut home = home(args)?;
+    home.register_config_handler(Config::new()?)?;
+    if let Some(environment) = environment {
+      home.use_environment(&environment)?;
+    }
+    let report = home.run_tests(smoke).await;
+    for line in report.summary() {
+      println!("{line}");
+    }
+    if let Some(path) = junit {
+      std::fs::write(path, report.to_junit())?;
+    }
+    if let Some(path) = json {
+      std::fs::write(path, serde_json::to_string_pretty(&report)?)?;
+    }
+    Ok(report.passed())
+  }
+
+  /// Sends the request of `openapi-tui call`, prints the status and headers of its response to stderr and its body
+  /// to stdout, `false` when its status is not 2xx.
+  pub async fn call(mut args: Cli) -> Result<bool> {
+    let Some(Command::Call { spec, operation, parameters, data, environment }) = args.command.clone() else {
+      return Ok(true);
+    };
+    args.openapi_path = spec;
+    let body = data.as_deref().map(headless::read_body).transpose()?;
+    let mut home = home(args)?;
+    home.register_config_handler(Config::new()?)?;
+    if let Some(environment) = environment {
+      home.use_environment(&environment)?;
+    }
+    let response = home.call(&operation, &parameters, body).await?.response;
+    eprintln!("{} {} {}", response.http_version, response.status, response.status_text);
+    for (name, value) in response.headers.iter() {
+      eprintln!("{name}: {value}");
+    }
+    match &response.spooled {
+      Some(spooled) => {
+        std::io::copy(&mut std::fs::File::open(&spooled.path)?, &mut std::io::stdout())?;
+        spool::clean_up();
+      },
+      None => println!("{}", response.body),
+    }
+    Ok((200..300).contains(&response.status))
+  }
+
+  /// Runs the commands of `openapi-tui headless` read from stdin, `false` when one failed.
+  pub async fn headless(mut args: Cli) -> Result<bool> {
+    let Some(Command::Headless { spec, environment }) = args.command.clone() else {
+      return Ok(true);
+    };
+    if let Some(spec) = spec {
+      args.openapi_path = spec;
+    }
+    let mut home = home(args)?;
+    home.register_config_handler(Config::new()?)?;
+    if let Some(environment) = environment {
+      home.use_environment(&environment)?;
+    }
+    headless::run(&mut home, std::io::stdin().lock(), std::io::stdout()).await
+  }
+
   pub async fn run(&mut self) -> Result<()> {
     let (action_tx, mut action_rx) = mpsc::unbounded_channel();
 
-    let mut tui = tui::Tui::new()?;
+    let mut tui = tui::Tui::new()?.mouse(self.config.mouse.unwrap_or(true)).paste(true);
     tui.enter()?;
 
     for component in self.pages.iter_mut() {
@@ -61,6 +198,9 @@ impl App {
 
     loop {
       if let Some(e) = tui.next().await {
+        if !matches!(e, tui::Event::Tick | tui::Event::Render) {
+          self.should_render = true;
+        }
         let mut stop_event_propagation = false;
         for component in self.pages.iter_mut() {
           if let Some(response) = component.handle_events(Some(e.clone()))? {
@@ -80,10 +220,12 @@ impl App {
           match e {
             tui::Event::Quit => action_tx.send(Action::Quit)?,
             tui::Event::Tick => action_tx.send(Action::Tick)?,
-            tui::Event::Render => action_tx.send(Action::Render)?,
+            // frames are skipped while idle
+            tui::Event::Render if self.frame_due() => action_tx.send(Action::Render)?,
             tui::Event::Resize(x, y) => action_tx.send(Action::Resize(x, y))?,
             tui::Event::Key(key) => {
-              if let Some(keymap) = self.config.keybindings.get(&self.mode) {
+              let key = normalize_key_event(key);
+              if let Some(keymap) = self.config.keybindings.get(&KeyScope::from(self.mode)) {
                 if let Some(action) = keymap.get(&vec![key]) {
                   action_tx.send(action.clone())?;
                 } else {
@@ -104,18 +246,59 @@ impl App {
       }
 
       while let Ok(action) = action_rx.try_recv() {
+        // only the names are logged, requests and responses carry secrets the log would hold unmasked
         if action != Action::Tick && action != Action::Render {
-          log::debug!("{action:?}");
+          log::debug!("{}", action.name());
+          crash::record(&action);
+          self.should_render = true;
         }
         match action {
           Action::Tick => {
             self.last_tick_key_events.drain(..);
           },
-          Action::Quit => self.should_quit = true,
+          Action::Quit => self.should_quit = self.pages.iter().all(|page| page.unfinished_work().is_empty()),
+          Action::ConfirmQuit(_) => self.should_quit = true,
+          // the page switches its own bindings
+          Action::SelectKeymap(keymap) => self.config.set_keymap(keymap),
+          Action::LoadSpec(ref path) => {
+            // specs picked from a registry are given by url
+            let resolved = remote::resolve(path, self.args.offline)
+              .await
+              .and_then(|path| git::resolve(&path, None))
+              .and_then(|path| merge::resolve(&path));
+            let path = match resolved {
+              Ok(path) => path,
+              Err(error) => {
+                action_tx
+                  .send(Action::SpecFailed(path.clone(), error.chain().map(|cause| cause.to_string()).collect()))?;
+                continue;
+              },
+            };
+            self.args.openapi_path = path;
+            crash::set_spec_path(&self.args.openapi_path);
+            if let Some(Command::Mock { spec, .. }) = self.args.command.as_mut() {
+              *spec = None;
+            }
+            let mut page = page(&self.args);
+            page.register_action_handler(action_tx.clone())?;
+            page.register_config_handler(self.config.clone())?;
+            page.init()?;
+            self.pages = vec![page];
+            action_tx.send(Action::Render)?;
+          },
+          Action::SpecFailed(ref path, ref messages) => {
+            let mut page = ErrorPage::new(path.clone(), messages.clone());
+            page.register_action_handler(action_tx.clone())?;
+            page.register_config_handler(self.config.clone())?;
+            page.init()?;
+            self.pages = vec![Box::new(page)];
+            action_tx.send(Action::Render)?;
+          },
           Action::Suspend => self.should_suspend = true,
           Action::Resume => self.should_suspend = false,
           Action::Resize(w, h) => {
             tui.resize(Rect::new(0, 0, w, h))?;
+            self.should_render = false;
             tui.draw(|f| {
               for component in self.pages.iter_mut() {
                 let r = component.draw(f, f.size());
@@ -123,9 +306,13 @@ impl App {
                   action_tx.send(Action::Error(format!("Failed to draw: {:?}", e))).unwrap();
                 }
               }
+              if theme::current().monochrome {
+                theme::strip_colors(f.buffer_mut());
+              }
             })?;
           },
           Action::Render => {
+            self.should_render = false;
             tui.draw(|f| {
               for component in self.pages.iter_mut() {
                 let r = component.draw(f, f.size());
@@ -133,6 +320,9 @@ impl App {
                   action_tx.send(Action::Error(format!("Failed to draw: {:?}", e))).unwrap();
                 }
               }
+              if theme::current().monochrome {
+                theme::strip_colors(f.buffer_mut());
+              }
             })?;
           },
           _ => {},
@@ -146,7 +336,7 @@ impl App {
       if self.should_suspend {
         tui.suspend()?;
         action_tx.send(Action::Resume)?;
-        tui = tui::Tui::new()?;
+        tui = tui::Tui::new()?.mouse(self.config.mouse.unwrap_or(true)).paste(true);
         tui.enter()?;
       } else if self.should_quit {
         tui.stop()?;
@@ -154,6 +344,53 @@ impl App {
       }
     }
     tui.exit()?;
+    spool::clean_up();
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use clap::Parser;
+
+  use super::*;
+
+  #[test]
+  fn test_no_color() {
+    let mut args = Cli::parse_from(["openapi-tui"]);
+    let mut config = Config::default();
+    assert!(!no_color(&args, &config, None));
+    // an empty `NO_COLOR` is ignored, as the convention says
+    assert!(!no_color(&args, &config, Some("")));
+    assert!(no_color(&args, &config, Some("1")));
+    config.no_color = true;
+    assert!(no_color(&args, &config, None));
+    config.no_color = false;
+    args = Cli::parse_from(["openapi-tui", "--no-color"]);
+    assert!(no_color(&args, &config, None));
+  }
+
+  #[test]
+  fn test_frame_due() -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("openapi-tui-frames-{}", std::process::id()));
+    let openapi_path = dir.join("petstore.yaml").to_string_lossy().to_string();
+    let mut app = App {
+      args: Cli::parse_from(["openapi-tui"]),
+      config: Config::default(),
+      pages: vec![Box::new(ErrorPage::new(openapi_path.clone(), vec![String::from("No such file")]))],
+      should_quit: false,
+      should_suspend: false,
+      should_render: true,
+      mode: Mode::Home,
+      last_tick_key_events: Vec::new(),
+    };
+    assert!(app.frame_due());
+    // idle, no frame is rendered until something changes
+    app.should_render = false;
+    assert!(!app.frame_due());
+    // a page with a spinner is rendered on every frame
+    app.pages.push(Box::new(Home::loading(openapi_path)?));
+    assert!(app.frame_due());
     Ok(())
   }
 }
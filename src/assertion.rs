@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{client::HttpResponse, extract};
+
+const STATUS_CODE: &str = "$statusCode";
+const LENGTH_SUFFIX: &str = " length";
+const OPERATORS: [&str; 6] = ["==", "!=", "<=", ">=", "<", ">"];
+
+/// Outcome of an assertion of a saved request, checked once its response is received.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TestResult {
+  pub assertion: String,
+  pub passed: bool,
+  /// Value the assertion read from the response, `None` when it is missing.
+  pub actual: Option<String>,
+}
+
+/// Reads `$statusCode`, `header:<name>` or a JSONPath into the body, followed by `length` for the number of items or
+/// characters of the value.
+pub fn value(source: &str, response: &HttpResponse) -> Option<String> {
+  let source = source.trim();
+  if let Some(source) = source.strip_suffix(LENGTH_SUFFIX) {
+    return extract::length(source.trim(), response).map(|length| length.to_string());
+  }
+  match source {
+    STATUS_CODE => Some(response.status.to_string()),
+    source => extract::extract(source, response),
+  }
+}
+
+/// Evaluates `<value> <operator> <expected>`, see `value` for the values. An assertion without an operator only
+/// checks that the value exists.
+pub fn evaluate(assertion: &str, response: &HttpResponse) -> TestResult {
+  let comparison =
+    OPERATORS.iter().find_map(|operator| assertion.split_once(operator).map(|(left, right)| (left, *operator, right)));
+  let source = comparison.map_or(assertion, |(source, ..)| source);
+  let actual = value(source, response);
+  let passed = match (&actual, comparison) {
+    (None, _) => false,
+    (Some(_), None) => true,
+    (Some(actual), Some((_, operator, expected))) => compare(actual, operator, expected),
+  };
+  TestResult { assertion: assertion.trim().to_string(), passed, actual }
+}
+
+pub fn check(assertion: &str, response: &HttpResponse) -> bool {
+  evaluate(assertion, response).passed
+}
+
+/// Compares as numbers when both sides are, as text otherwise.
+fn compare(actual: &str, operator: &str, expected: &str) -> bool {
+  let expected = expected.trim().trim_matches(|c| c == '"' || c == '\'');
+  let ordering = match (actual.parse::<f64>(), expected.parse::<f64>()) {
+    (Ok(actual), Ok(expected)) => actual.partial_cmp(&expected),
+    _ => Some(actual.cmp(expected)),
+  };
+  let Some(ordering) = ordering else {
+    return false;
+  };
+  match operator {
+    "==" => ordering.is_eq(),
+    "!=" => ordering.is_ne(),
+    "<=" => ordering.is_le(),
+    ">=" => ordering.is_ge(),
+    "<" => ordering.is_lt(),
+    _ => ordering.is_gt(),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_evaluate() {
+    let response = HttpResponse {
+      status: 200,
+      headers: vec![(String::from("X-Request-Id"), String::from("abc"))],
+      body: String::from(r#"{ "items": [{ "id": 1 }, { "id": 2 }], "name": "rex" }"#),
+      ..HttpResponse::default()
+    };
+    assert!(check("$statusCode == 200", &response));
+    assert!(check("$.items length > 0", &response));
+    assert!(check("$.name length == 3", &response));
+    assert!(check("header:X-Request-Id", &response));
+    assert!(!check("header:ETag", &response));
+    assert_eq!(evaluate(" $.items[1].id != 2", &response), TestResult {
+      assertion: String::from("$.items[1].id != 2"),
+      passed: false,
+      actual: Some(String::from("2")),
+    });
+  }
+}
@@ -0,0 +1,327 @@
+use std::{collections::BTreeMap, io::Read};
+
+use color_eyre::eyre::{eyre, Result};
+use serde_json::Value;
+
+use crate::mock;
+
+/// Bytes of the spec read to tell whether it is an AsyncAPI document, whose `asyncapi` key comes first by convention.
+const SNIFF_LEN: u64 = 4096;
+
+/// AsyncAPI document, 2.x or 3.x, browsed read-only: the operations on its channels and the messages they carry.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AsyncApi {
+  pub title: String,
+  pub version: String,
+  /// AsyncAPI version of the document, e.g. `2.6.0`.
+  pub asyncapi: String,
+  /// Operations by channel in 2.x and by id in 3.x, channels without operations listed on their own.
+  pub operations: Vec<ChannelOperation>,
+  /// `components.schemas`, the targets of the `$ref`s of the payloads.
+  pub schemas: BTreeMap<String, Value>,
+}
+
+/// Operation of an application on a channel, e.g. `publish` in 2.x or `send` in 3.x.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChannelOperation {
+  /// Address of the channel, e.g. `user/signedup`.
+  pub channel: String,
+  pub channel_description: Option<String>,
+  /// `publish`, `subscribe`, `send` or `receive`, `None` for a channel without operations.
+  pub action: Option<String>,
+  pub id: Option<String>,
+  pub summary: Option<String>,
+  pub description: Option<String>,
+  pub messages: Vec<Message>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Message {
+  pub name: String,
+  pub title: Option<String>,
+  pub summary: Option<String>,
+  pub description: Option<String>,
+  pub content_type: Option<String>,
+  /// Schema of the payload, `Null` when the message has none.
+  pub payload: Value,
+  pub headers: Option<Value>,
+}
+
+/// Whether the spec at the path is an AsyncAPI document, from its first bytes so that OpenAPI specs are not parsed
+/// twice.
+pub fn is_asyncapi(path: &str) -> bool {
+  let mut head = vec![];
+  let read = std::fs::File::open(path).and_then(|file| file.take(SNIFF_LEN).read_to_end(&mut head));
+  if read.is_err() && head.is_empty() {
+    return false;
+  }
+  // the cut may land inside a character
+  sniff(&String::from_utf8_lossy(&head))
+}
+
+/// Whether the head of a spec has a top-level `asyncapi` key, in YAML a line starting with it and in JSON, possibly
+/// minified, the key coming before any `openapi` key.
+fn sniff(head: &str) -> bool {
+  if head.trim_start().starts_with('{') {
+    return match (head.find("\"asyncapi\""), head.find("\"openapi\"")) {
+      (Some(asyncapi), Some(openapi)) => asyncapi < openapi,
+      (asyncapi, _) => asyncapi.is_some(),
+    };
+  }
+  head.lines().any(|line| line.starts_with("asyncapi:") || line.starts_with("\"asyncapi\":"))
+}
+
+impl AsyncApi {
+  pub fn load(path: &str) -> Result<Self> {
+    let document: Value = serde_yaml::from_reader(std::fs::File::open(path)?)?;
+    Self::new(&document)
+  }
+
+  pub fn new(document: &Value) -> Result<Self> {
+    let asyncapi = document.get("asyncapi").and_then(Value::as_str).ok_or_else(|| eyre!("Not an AsyncAPI document"))?;
+    let info = |key| document.get("info").and_then(|info| info.get(key)).and_then(Value::as_str).unwrap_or_default();
+    let operations = match asyncapi.starts_with("2.") {
+      true => operations_v2(document),
+      false => operations_v3(document),
+    };
+    let schemas = document
+      .pointer("/components/schemas")
+      .and_then(Value::as_object)
+      .into_iter()
+      .flatten()
+      .map(|(name, schema)| (name.clone(), schema.clone()))
+      .collect();
+    Ok(Self {
+      title: info("title").to_string(),
+      version: info("version").to_string(),
+      asyncapi: asyncapi.to_string(),
+      operations,
+      schemas,
+    })
+  }
+}
+
+/// Operations of 2.x documents, the `publish` and `subscribe` of each channel, its messages given as one or `oneOf`.
+fn operations_v2(document: &Value) -> Vec<ChannelOperation> {
+  let mut operations = vec![];
+  for (address, channel) in document.get("channels").and_then(Value::as_object).into_iter().flatten() {
+    let channel = mock::resolve(channel, document);
+    let channel_description = text(channel, "description");
+    let mut found = false;
+    for action in ["publish", "subscribe"] {
+      let Some(operation) = channel.get(action).map(|operation| mock::resolve(operation, document)) else {
+        continue;
+      };
+      found = true;
+      let messages = match operation.get("message").map(|message| mock::resolve(message, document)) {
+        Some(message) => {
+          match message.get("oneOf").and_then(Value::as_array) {
+            Some(one_of) => one_of.iter().map(|message| message_of(document, None, message)).collect(),
+            None => vec![message_of(document, operation.get("message").and_then(ref_name), message)],
+          }
+        },
+        None => vec![],
+      };
+      operations.push(ChannelOperation {
+        channel: address.clone(),
+        channel_description: channel_description.clone(),
+        action: Some(action.to_string()),
+        id: text(operation, "operationId"),
+        summary: text(operation, "summary"),
+        description: text(operation, "description"),
+        messages,
+      });
+    }
+    if !found {
+      operations.push(ChannelOperation { channel: address.clone(), channel_description, ..Default::default() });
+    }
+  }
+  operations
+}
+
+/// Operations of 3.x documents, which reference their channel and messages, followed by the channels no operation
+/// references.
+fn operations_v3(document: &Value) -> Vec<ChannelOperation> {
+  let channels = document.get("channels").and_then(Value::as_object);
+  let channel_messages = |channel: &Value| -> Vec<Message> {
+    let messages = channel.get("messages").and_then(Value::as_object).into_iter().flatten();
+    messages.map(|(name, message)| message_of(document, Some(name.as_str()), message)).collect()
+  };
+  let address = |id: &str, channel: &Value| text(channel, "address").unwrap_or(id.to_string());
+  let mut operations = vec![];
+  let mut referenced = vec![];
+  for (id, operation) in document.get("operations").and_then(Value::as_object).into_iter().flatten() {
+    let operation = mock::resolve(operation, document);
+    let channel_ref = operation.get("channel").and_then(|channel| channel.get("$ref")).and_then(Value::as_str);
+    let channel_id = channel_ref.and_then(|reference| reference.strip_prefix("#/channels/")).map(unescape);
+    let channel = operation.get("channel").map(|channel| mock::resolve(channel, document)).unwrap_or(&Value::Null);
+    let messages = match operation.get("messages").and_then(Value::as_array) {
+      Some(messages) => messages.iter().map(|message| message_of(document, ref_name(message), message)).collect(),
+      None => channel_messages(channel),
+    };
+    referenced.extend(channel_id.clone());
+    operations.push(ChannelOperation {
+      channel: address(channel_id.as_deref().unwrap_or_default(), channel),
+      channel_description: text(channel, "description"),
+      action: text(operation, "action"),
+      id: Some(id.clone()),
+      summary: text(operation, "summary"),
+      description: text(operation, "description"),
+      messages,
+    });
+  }
+  for (id, channel) in channels.into_iter().flatten().filter(|(id, _)| !referenced.contains(id)) {
+    let channel = mock::resolve(channel, document);
+    operations.push(ChannelOperation {
+      channel: address(id, channel),
+      channel_description: text(channel, "description"),
+      messages: channel_messages(channel),
+      ..Default::default()
+    });
+  }
+  operations
+}
+
+/// The message, its `$ref` followed, named by its `name`, else by the key or reference it is found under.
+fn message_of(document: &Value, key: Option<&str>, message: &Value) -> Message {
+  let key = key.map(String::from).or_else(|| ref_name(message).map(String::from));
+  let message = mock::resolve(message, document);
+  // schemas are left referenced, the viewer going into them
+  let payload = message.get("payload").cloned().unwrap_or_default();
+  // 3.x multi format schemas wrap the schema along with its format
+  let payload = match (payload.get("schemaFormat"), payload.get("schema")) {
+    (Some(_), Some(schema)) => schema.clone(),
+    _ => payload,
+  };
+  Message {
+    name: text(message, "name").or(key).unwrap_or(String::from("message")),
+    title: text(message, "title"),
+    summary: text(message, "summary"),
+    description: text(message, "description"),
+    content_type: text(message, "contentType")
+      .or_else(|| document.get("defaultContentType").and_then(Value::as_str).map(String::from)),
+    payload,
+    headers: message.get("headers").map(|headers| mock::resolve(headers, document)).cloned(),
+  }
+}
+
+/// Last segment of the `$ref` of the value, e.g. `UserSignedUp` of `#/components/messages/UserSignedUp`.
+fn ref_name(value: &Value) -> Option<&str> {
+  value.get("$ref").and_then(Value::as_str).and_then(|reference| reference.rsplit('/').next())
+}
+
+fn unescape(segment: &str) -> String {
+  segment.replace("~1", "/").replace("~0", "~")
+}
+
+fn text(value: &Value, key: &str) -> Option<String> {
+  value.get(key).and_then(Value::as_str).map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+  use serde_json::json;
+
+  use super::*;
+
+  fn listed(asyncapi: &AsyncApi) -> Vec<(Option<&str>, &str, Vec<&str>)> {
+    let operations = asyncapi.operations.iter();
+    operations
+      .map(|operation| {
+        let messages = operation.messages.iter().map(|message| message.name.as_str()).collect();
+        (operation.action.as_deref(), operation.channel.as_str(), messages)
+      })
+      .collect()
+  }
+
+  #[test]
+  fn test_is_asyncapi() {
+    assert!(sniff("asyncapi: 3.0.0\ninfo:\n  title: Accounts\n"));
+    assert!(sniff("# Accounts\n\"asyncapi\": 2.6.0\n"));
+    assert!(sniff(r#"{"asyncapi":"2.6.0","info":{"title":"Accounts"}}"#));
+    assert!(sniff("{\n  \"asyncapi\": \"3.0.0\",\n  \"info\": {}\n}"));
+    assert!(!sniff(r#"{"openapi":"3.0.0","info":{"description":"Mirrors the \"asyncapi\" events"}}"#));
+    assert!(!sniff("openapi: 3.0.0\ninfo:\n  x-asyncapi: events.yaml\n"));
+
+    // the head is cut inside `é`, which takes two bytes
+    let directory = std::env::temp_dir().join(format!("openapi-tui-asyncapi-{}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+    let path = directory.join("asyncapi.yaml");
+    let description = "é".repeat(SNIFF_LEN as usize);
+    std::fs::write(&path, format!("asyncapi: 2.6.0\ninfo:\n  description: {description}\n")).unwrap();
+    assert!(is_asyncapi(path.to_str().unwrap()));
+    std::fs::remove_dir_all(&directory).unwrap();
+  }
+
+  #[test]
+  fn test_v2() {
+    let document = json!({
+      "asyncapi": "2.6.0",
+      "info": { "title": "Accounts", "version": "1.0.0" },
+      "defaultContentType": "application/json",
+      "channels": {
+        "user/signedup": {
+          "subscribe": { "operationId": "onSignup", "message": { "$ref": "#/components/messages/UserSignedUp" } }
+        },
+        "user/updated": {
+          "publish": { "message": { "oneOf": [
+            { "name": "Renamed", "payload": { "type": "object" } },
+            { "$ref": "#/components/messages/UserSignedUp" }
+          ] } }
+        },
+        "heartbeat": {}
+      },
+      "components": {
+        "messages": { "UserSignedUp": { "payload": { "$ref": "#/components/schemas/User" } } },
+        "schemas": { "User": { "type": "object" } }
+      }
+    });
+    let asyncapi = AsyncApi::new(&document).unwrap();
+    assert_eq!(listed(&asyncapi), vec![
+      (None, "heartbeat", vec![]),
+      (Some("subscribe"), "user/signedup", vec!["UserSignedUp"]),
+      (Some("publish"), "user/updated", vec!["Renamed", "UserSignedUp"]),
+    ]);
+    let message = &asyncapi.operations[1].messages[0];
+    assert_eq!(message.payload, json!({ "$ref": "#/components/schemas/User" }));
+    assert_eq!(message.content_type.as_deref(), Some("application/json"));
+    assert_eq!(asyncapi.schemas.keys().collect::<Vec<_>>(), vec!["User"]);
+  }
+
+  #[test]
+  fn test_v3() {
+    let document = json!({
+      "asyncapi": "3.0.0",
+      "info": { "title": "Accounts", "version": "1.0.0" },
+      "channels": {
+        "userSignedUp": {
+          "address": "user/signedup",
+          "messages": {
+            "signedUp": { "payload": { "schemaFormat": "application/schema+json;version=draft-07", "schema": {
+              "type": "object"
+            } } },
+            "welcomed": { "name": "Welcomed" }
+          }
+        },
+        "audit": { "messages": { "event": {} } }
+      },
+      "operations": {
+        "sendSignedUp": {
+          "action": "send",
+          "channel": { "$ref": "#/channels/userSignedUp" },
+          "messages": [{ "$ref": "#/channels/userSignedUp/messages/signedUp" }]
+        },
+        "receiveSignedUp": { "action": "receive", "channel": { "$ref": "#/channels/userSignedUp" } }
+      }
+    });
+    let asyncapi = AsyncApi::new(&document).unwrap();
+    assert_eq!(listed(&asyncapi), vec![
+      (Some("receive"), "user/signedup", vec!["signedUp", "Welcomed"]),
+      (Some("send"), "user/signedup", vec!["signedUp"]),
+      (None, "audit", vec!["event"]),
+    ]);
+    assert_eq!(asyncapi.operations[1].messages[0].payload, json!({ "type": "object" }));
+    assert!(AsyncApi::new(&json!({ "openapi": "3.0.0" })).is_err());
+  }
+}
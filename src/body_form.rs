@@ -0,0 +1,484 @@
+use std::collections::BTreeSet;
+
+use serde_json::{Map, Value};
+
+use crate::{
+  json_path::{self, Segment},
+  mock,
+};
+
+/// Nesting after which the fields of a body stop following its schema, which keeps recursive schemas finite.
+const MAX_DEPTH: usize = 8;
+
+/// Input a field of a JSON body edited as a form takes.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldKind {
+  Text,
+  Integer,
+  Number,
+  Boolean,
+  /// One of the `enum` values of the schema.
+  Choice(Vec<Value>),
+  Object,
+  Array,
+  /// Any JSON value, the schema telling no type.
+  Any,
+}
+
+impl FieldKind {
+  pub fn name(&self) -> &'static str {
+    match self {
+      FieldKind::Text => "text",
+      FieldKind::Integer => "integer",
+      FieldKind::Number => "number",
+      FieldKind::Boolean => "boolean",
+      FieldKind::Choice(_) => "choice",
+      FieldKind::Object => "object",
+      FieldKind::Array => "array",
+      FieldKind::Any => "any",
+    }
+  }
+}
+
+/// Property, or array item, of a JSON body edited as a form.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+  pub path: Vec<Segment>,
+  /// Name of the property, or index of the item, e.g. `[0]`.
+  pub label: String,
+  pub depth: usize,
+  pub kind: FieldKind,
+  pub required: bool,
+  /// Value the body holds, `None` when it leaves the field out.
+  pub value: Option<Value>,
+  /// Whether the fields of an object, or the items of an array, are listed below it.
+  pub expanded: bool,
+  /// Value an item added to an array starts from, the example of the schema of its items.
+  pub new_item: Option<Value>,
+}
+
+impl Field {
+  /// Key the field is expanded by, its JSONPath.
+  pub fn key(&self) -> String {
+    json_path::json_path(&self.path)
+  }
+
+  pub fn is_container(&self) -> bool {
+    matches!(self.kind, FieldKind::Object | FieldKind::Array)
+  }
+
+  /// Value as shown in the form, e.g. `doggie`, `42`, `{2 fields}` or `[3 items]`.
+  pub fn display(&self) -> Option<String> {
+    self.value.as_ref().map(|value| {
+      match value {
+        Value::String(text) => text.clone(),
+        Value::Object(object) => format!("{{{} fields}}", object.len()),
+        Value::Array(items) => format!("[{} items]", items.len()),
+        value => value.to_string(),
+      }
+    })
+  }
+
+  /// Text the value is edited from: strings as they are, other values as JSON.
+  pub fn text(&self) -> String {
+    match &self.value {
+      Some(Value::String(text)) => text.clone(),
+      Some(value) => value.to_string(),
+      None => String::default(),
+    }
+  }
+
+  /// The value typed for the field, `None` for an empty text, which leaves the field out of the body.
+  pub fn parse(&self, text: &str) -> Result<Option<Value>, String> {
+    if text.is_empty() {
+      return Ok(None);
+    }
+    let json = serde_json::from_str::<Value>(text);
+    let value = match &self.kind {
+      FieldKind::Text => Value::String(text.to_string()),
+      FieldKind::Integer => {
+        text.trim().parse::<i64>().map(Value::from).map_err(|_| format!("`{text}` is not an integer"))?
+      },
+      FieldKind::Number => json.ok().filter(Value::is_number).ok_or_else(|| format!("`{text}` is not a number"))?,
+      FieldKind::Boolean => {
+        match text.trim() {
+          "true" => Value::Bool(true),
+          "false" => Value::Bool(false),
+          _ => return Err(format!("`{text}` is neither true nor false")),
+        }
+      },
+      FieldKind::Choice(options) => {
+        let option = options.iter().find(|option| option.as_str().map_or(option.to_string(), String::from) == text);
+        option.cloned().ok_or_else(|| format!("`{text}` is none of the allowed values"))?
+      },
+      FieldKind::Object => {
+        json.ok().filter(Value::is_object).ok_or_else(|| format!("`{text}` is not a JSON object"))?
+      },
+      FieldKind::Array => json.ok().filter(Value::is_array).ok_or_else(|| format!("`{text}` is not a JSON array"))?,
+      // anything that is not JSON is sent as a string
+      FieldKind::Any => json.unwrap_or_else(|_| Value::String(text.to_string())),
+    };
+    Ok(Some(value))
+  }
+
+  /// Next value of a boolean or a choice, the first one when the field is left out, `None` for other fields.
+  pub fn toggled(&self) -> Option<Value> {
+    match (&self.kind, &self.value) {
+      (FieldKind::Boolean, Some(Value::Bool(value))) => Some(Value::Bool(!value)),
+      (FieldKind::Boolean, _) => Some(Value::Bool(true)),
+      (FieldKind::Choice(options), value) => {
+        let index = value.as_ref().and_then(|value| options.iter().position(|option| option == value));
+        let next = index.map_or(0, |index| (index + 1) % options.len());
+        options.get(next).cloned()
+      },
+      _ => None,
+    }
+  }
+}
+
+/// The schema with its `$ref` followed, the members of an `allOf` merged, and the first member of a `oneOf` or
+/// `anyOf`.
+fn merged(schema: &Value, document: &Value, depth: usize) -> Value {
+  let schema = mock::resolve(schema, document);
+  if depth > MAX_DEPTH {
+    return schema.clone();
+  }
+  if let Some(members) = schema.get("allOf").and_then(Value::as_array) {
+    let mut properties = Map::new();
+    let mut required = vec![];
+    for member in members.iter().map(|member| merged(member, document, depth + 1)) {
+      properties.extend(member.get("properties").and_then(Value::as_object).cloned().unwrap_or_default());
+      required.extend(member.get("required").and_then(Value::as_array).cloned().unwrap_or_default());
+    }
+    let mut merged = schema.clone();
+    if let Some(object) = merged.as_object_mut() {
+      object.remove("allOf");
+      object.insert(String::from("type"), Value::from("object"));
+      object.insert(String::from("properties"), Value::Object(properties));
+      object.insert(String::from("required"), Value::Array(required));
+    }
+    return merged;
+  }
+  for key in ["oneOf", "anyOf"] {
+    if let Some(first) = schema.get(key).and_then(|members| members.get(0)) {
+      return merged(first, document, depth + 1);
+    }
+  }
+  schema.clone()
+}
+
+fn field_kind(schema: &Value) -> FieldKind {
+  if let Some(options) = schema.get("enum").and_then(Value::as_array) {
+    return FieldKind::Choice(options.clone());
+  }
+  match mock::kind(schema) {
+    Some("string") => FieldKind::Text,
+    Some("integer") => FieldKind::Integer,
+    Some("number") => FieldKind::Number,
+    Some("boolean") => FieldKind::Boolean,
+    Some("object") => FieldKind::Object,
+    Some("array") => FieldKind::Array,
+    _ => FieldKind::Any,
+  }
+}
+
+/// Whether the schema of a body is an object, whose properties are edited as a form.
+pub fn is_object(schema: &Value, document: &Value) -> bool {
+  field_kind(&merged(schema, document, 0)) == FieldKind::Object
+}
+
+/// Fields of the body, by the properties of its schema and then those the body holds besides, the fields of the
+/// expanded objects and the items of the expanded arrays listed below them.
+pub fn fields(schema: &Value, document: &Value, body: &Value, expanded: &BTreeSet<String>) -> Vec<Field> {
+  let mut fields = vec![];
+  let mut lister = Lister { document, expanded, fields: &mut fields };
+  lister.children(&merged(schema, document, 0), Some(body), &mut vec![], 0);
+  fields
+}
+
+struct Lister<'a> {
+  document: &'a Value,
+  expanded: &'a BTreeSet<String>,
+  fields: &'a mut Vec<Field>,
+}
+
+impl Lister<'_> {
+  fn children(&mut self, schema: &Value, value: Option<&Value>, path: &mut Vec<Segment>, depth: usize) {
+    if depth > MAX_DEPTH {
+      return;
+    }
+    match field_kind(schema) {
+      FieldKind::Object => {
+        let required: Vec<&str> =
+          schema.get("required").and_then(Value::as_array).into_iter().flatten().filter_map(Value::as_str).collect();
+        let properties = schema.get("properties").and_then(Value::as_object);
+        for (name, property) in properties.into_iter().flatten() {
+          let property = merged(property, self.document, depth);
+          let child = value.and_then(|value| value.get(name));
+          path.push(Segment::Key(name.clone()));
+          self.field(&property, child, path, depth, name.clone(), required.contains(&name.as_str()));
+          path.pop();
+        }
+        // properties the schema does not declare, e.g. of `additionalProperties`
+        let extra = value
+          .and_then(Value::as_object)
+          .into_iter()
+          .flatten()
+          .filter(|(name, _)| properties.is_none_or(|properties| !properties.contains_key(name.as_str())));
+        let additional = schema.get("additionalProperties").filter(|additional| additional.is_object());
+        let additional = additional.map(|additional| merged(additional, self.document, depth)).unwrap_or_default();
+        for (name, child) in extra {
+          path.push(Segment::Key(name.clone()));
+          self.field(&additional, Some(child), path, depth, name.clone(), false);
+          path.pop();
+        }
+      },
+      FieldKind::Array => {
+        let items = schema.get("items").map(|items| merged(items, self.document, depth)).unwrap_or_default();
+        for (index, item) in value.and_then(Value::as_array).into_iter().flatten().enumerate() {
+          path.push(Segment::Index(index));
+          self.field(&items, Some(item), path, depth, format!("[{index}]"), false);
+          path.pop();
+        }
+      },
+      _ => {},
+    }
+  }
+
+  fn field(
+    &mut self,
+    schema: &Value,
+    value: Option<&Value>,
+    path: &mut Vec<Segment>,
+    depth: usize,
+    label: String,
+    required: bool,
+  ) {
+    let kind = match field_kind(schema) {
+      // a value of another type than declared is edited as JSON
+      FieldKind::Object if value.is_some_and(|value| !value.is_object()) => FieldKind::Any,
+      FieldKind::Array if value.is_some_and(|value| !value.is_array()) => FieldKind::Any,
+      kind => kind,
+    };
+    let new_item = (kind == FieldKind::Array).then(|| {
+      let items = schema.get("items").cloned().unwrap_or_default();
+      mock::example(&items, self.document, depth + 1)
+    });
+    let field =
+      Field { path: path.clone(), label, depth, kind, required, value: value.cloned(), expanded: false, new_item };
+    let expanded = field.is_container() && value.is_some() && self.expanded.contains(&field.key());
+    self.fields.push(Field { expanded, ..field });
+    if expanded {
+      self.children(schema, value, path, depth + 1);
+    }
+  }
+}
+
+/// Sets the value at the path of the body, creating the objects and arrays on its way, or with `None` removes it.
+pub fn set(body: &mut Value, path: &[Segment], value: Option<Value>) {
+  let Some((last, parents)) = path.split_last() else {
+    if let Some(value) = value {
+      *body = value;
+    }
+    return;
+  };
+  let mut current = body;
+  for segment in parents {
+    if value.is_none() {
+      // nothing to remove below a value the body leaves out
+      let child = match segment {
+        Segment::Key(key) => current.get_mut(key.as_str()),
+        Segment::Index(index) => current.get_mut(*index),
+      };
+      let Some(child) = child else {
+        return;
+      };
+      current = child;
+      continue;
+    }
+    current = match segment {
+      Segment::Key(key) => {
+        if !current.is_object() {
+          *current = Value::Object(Map::new());
+        }
+        current.as_object_mut().unwrap().entry(key.clone()).or_insert(Value::Null)
+      },
+      Segment::Index(index) => {
+        if !current.is_array() {
+          *current = Value::Array(vec![]);
+        }
+        let items = current.as_array_mut().unwrap();
+        if items.len() <= *index {
+          items.resize(index + 1, Value::Null);
+        }
+        &mut items[*index]
+      },
+    };
+  }
+  match (last, value) {
+    (Segment::Key(key), Some(value)) => {
+      if !current.is_object() {
+        *current = Value::Object(Map::new());
+      }
+      current.as_object_mut().unwrap().insert(key.clone(), value);
+    },
+    (Segment::Key(key), None) => {
+      if let Some(object) = current.as_object_mut() {
+        object.remove(key);
+      }
+    },
+    (Segment::Index(index), Some(value)) => {
+      if !current.is_array() {
+        *current = Value::Array(vec![]);
+      }
+      let items = current.as_array_mut().unwrap();
+      match items.get_mut(*index) {
+        Some(item) => *item = value,
+        None => items.push(value),
+      }
+    },
+    (Segment::Index(index), None) => {
+      if let Some(items) = current.as_array_mut().filter(|items| *index < items.len()) {
+        items.remove(*index);
+      }
+    },
+  }
+}
+
+/// Appends the new item of the array field to it, creating the array when the body leaves it out.
+pub fn add_item(body: &mut Value, field: &Field) {
+  let mut items = field.value.as_ref().and_then(Value::as_array).cloned().unwrap_or_default();
+  items.push(field.new_item.clone().unwrap_or_default());
+  set(body, &field.path, Some(Value::Array(items)));
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+  use serde_json::json;
+
+  use super::*;
+
+  #[test]
+  fn test_fields() {
+    let document = json!({ "components": { "schemas": {
+      "Tag": { "type": "object", "properties": { "name": { "type": "string" } } },
+      "Pet": {
+        "type": "object",
+        "required": ["name"],
+        "properties": {
+          "name": { "type": "string" },
+          "age": { "type": "integer" },
+          "status": { "type": "string", "enum": ["available", "sold"] },
+          "owner": { "type": "object", "properties": { "email": { "type": "string" } } },
+          "tags": { "type": "array", "items": { "$ref": "#/components/schemas/Tag" } }
+        }
+      }
+    } } });
+    let schema = json!({ "$ref": "#/components/schemas/Pet" });
+    assert!(is_object(&schema, &document));
+    let mut body = json!({ "name": "doggie", "tags": [{ "name": "cute" }], "vaccinated": true });
+    let expanded = BTreeSet::from([String::from("$.tags"), String::from("$.tags[0]")]);
+    let listed = fields(&schema, &document, &body, &expanded);
+    let rows: Vec<String> = listed
+      .iter()
+      .map(|field| {
+        format!(
+          "{}{}{} {}",
+          "  ".repeat(field.depth),
+          field.label,
+          if field.required { "*" } else { "" },
+          field.display().unwrap_or_default()
+        )
+      })
+      .collect();
+    assert_eq!(rows, vec![
+      "age ",
+      "name* doggie",
+      "owner ",
+      "status ",
+      "tags [1 items]",
+      "  [0] {1 fields}",
+      "    name cute",
+      "vaccinated true",
+    ]);
+
+    let age = &listed[0];
+    assert_eq!(age.kind, FieldKind::Integer);
+    assert_eq!(age.parse("3"), Ok(Some(json!(3))));
+    assert!(age.parse("three").is_err());
+    set(&mut body, &age.path, age.parse("3").unwrap());
+    let status = &listed[3];
+    assert_eq!(status.toggled(), Some(json!("available")));
+    assert!(status.parse("lost").is_err());
+    set(
+      &mut body,
+      &listed[2].path.iter().cloned().chain([Segment::Key(String::from("email"))]).collect::<Vec<_>>(),
+      Some(json!("ann@example.com")),
+    );
+    add_item(&mut body, &listed[4]);
+    set(&mut body, &listed[1].path, None);
+    assert_eq!(
+      body,
+      json!({
+        "age": 3,
+        "owner": { "email": "ann@example.com" },
+        "tags": [{ "name": "cute" }, { "name": "string" }],
+        "vaccinated": true
+      })
+    );
+    set(&mut body, &[Segment::Key(String::from("tags")), Segment::Index(0)], None);
+    assert_eq!(body["tags"], json!([{ "name": "string" }]));
+  }
+
+  #[test]
+  fn test_edit_nested_paths() {
+    let key = |key: &str| Segment::Key(key.to_string());
+    let mut body = json!({ "owner": { "pets": [{ "name": "rex", "tags": ["cute"] }] } });
+
+    let tags = [key("owner"), key("pets"), Segment::Index(0), key("tags")];
+    set(&mut body, &[&tags[..], &[Segment::Index(0)]].concat(), Some(json!("calm")));
+    set(&mut body, &[key("owner"), key("pets"), Segment::Index(0), key("age")], Some(json!(3)));
+    assert_eq!(body, json!({ "owner": { "pets": [{ "name": "rex", "tags": ["calm"], "age": 3 }] } }));
+
+    // objects and arrays missing on the way are created, though not to remove a value
+    set(&mut body, &[key("owner"), key("address"), key("city")], Some(json!("Oslo")));
+    set(&mut body, &[key("owner"), key("pets"), Segment::Index(2), key("name")], Some(json!("tom")));
+    assert_eq!(body["owner"]["address"], json!({ "city": "Oslo" }));
+    assert_eq!(body["owner"]["pets"][1], Value::Null);
+    assert_eq!(body["owner"]["pets"][2], json!({ "name": "tom" }));
+
+    let field = |path: &[Segment], value, new_item| {
+      Field {
+        path: path.to_vec(),
+        label: String::from("tags"),
+        depth: 3,
+        kind: FieldKind::Array,
+        required: false,
+        value,
+        expanded: false,
+        new_item,
+      }
+    };
+    add_item(&mut body, &field(&tags, Some(json!(["calm"])), Some(json!("new"))));
+    assert_eq!(body["owner"]["pets"][0]["tags"], json!(["calm", "new"]));
+    let missing = [key("owner"), key("pets"), Segment::Index(2), key("tags")];
+    add_item(&mut body, &field(&missing, None, None));
+    assert_eq!(body["owner"]["pets"][2]["tags"], json!([null]));
+
+    set(&mut body, &[&tags[..], &[Segment::Index(0)]].concat(), None);
+    set(&mut body, &[key("owner"), key("pets"), Segment::Index(1)], None);
+    set(&mut body, &[key("owner"), key("address"), key("city")], None);
+    set(&mut body, &[key("owner"), key("pets"), Segment::Index(7)], None);
+    set(&mut body, &[key("owner"), key("nickname"), key("first")], None);
+    assert_eq!(
+      body,
+      json!({
+        "owner": {
+          "address": {},
+          "pets": [{ "name": "rex", "tags": ["new"], "age": 3 }, { "name": "tom", "tags": [null] }]
+        }
+      })
+    );
+  }
+}
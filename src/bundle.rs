@@ -0,0 +1,364 @@
+use std::{
+  collections::{BTreeMap, BTreeSet},
+  path::{Path, PathBuf},
+};
+
+use color_eyre::eyre::{eyre, Result, WrapErr};
+use serde_json::{Map, Value};
+
+use crate::{diff, inventory, mock::METHODS};
+
+/// Kinds of components a local reference points into, e.g. `#/components/responses/NotFound`.
+const COMPONENT_KINDS: [&str; 9] = [
+  "schemas",
+  "responses",
+  "parameters",
+  "examples",
+  "requestBodies",
+  "headers",
+  "securitySchemes",
+  "links",
+  "callbacks",
+];
+
+/// Follows the references of a spec into other files, moving what they point to under its components.
+struct Bundler {
+  /// Files referenced so far, by path.
+  documents: BTreeMap<PathBuf, Value>,
+  /// Components of the bundle, by kind and name.
+  components: Map<String, Value>,
+  /// Local reference given to each external one already followed, by file and pointer.
+  bundled: BTreeMap<(PathBuf, String), String>,
+}
+
+/// Last segment of the JSON pointer, unescaped, e.g. `Pet` for `/components/schemas/Pet`.
+fn last_segment(pointer: &str) -> Option<String> {
+  let segment = pointer.rsplit('/').next().filter(|segment| !segment.is_empty())?;
+  Some(segment.replace("~1", "/").replace("~0", "~"))
+}
+
+/// Name usable as a component key, e.g. `pet_yaml` for `pet.yaml`.
+fn component_name(name: &str) -> String {
+  name.chars().map(|c| if c.is_ascii_alphanumeric() || c == '.' || c == '-' || c == '_' { c } else { '_' }).collect()
+}
+
+impl Bundler {
+  fn document(&mut self, path: &Path) -> Result<&Value> {
+    if !self.documents.contains_key(path) {
+      let document = diff::load(&path.display().to_string()).wrap_err_with(|| format!("{}", path.display()))?;
+      self.documents.insert(path.to_path_buf(), document);
+    }
+    Ok(&self.documents[path])
+  }
+
+  /// File and pointer of a reference made from the file, the file itself when the reference is local to it.
+  fn target(file: &Path, reference: &str) -> (PathBuf, String) {
+    let (relative, pointer) = reference.split_once('#').unwrap_or((reference, ""));
+    let path = match relative.is_empty() {
+      true => file.to_path_buf(),
+      false => file.parent().unwrap_or(Path::new("")).join(relative),
+    };
+    (std::fs::canonicalize(&path).unwrap_or(path), pointer.to_string())
+  }
+
+  fn value(&mut self, file: &Path, pointer: &str) -> Result<Value> {
+    let document = self.document(file)?;
+    document.pointer(pointer).cloned().ok_or_else(|| eyre!("{}#{pointer} points to nothing", file.display()))
+  }
+
+  /// Local reference to the component the external reference is bundled as, named after its key when it points into
+  /// components, or else after its last segment or file, and made unique.
+  fn bundle(&mut self, file: PathBuf, pointer: String) -> Result<String> {
+    if let Some(reference) = self.bundled.get(&(file.clone(), pointer.clone())) {
+      return Ok(reference.clone());
+    }
+    let segments: Vec<&str> = pointer.trim_start_matches('/').split('/').collect();
+    let kind = match segments.as_slice() {
+      ["components", kind, _] if COMPONENT_KINDS.contains(kind) => kind.to_string(),
+      _ => String::from("schemas"),
+    };
+    let name = last_segment(&pointer)
+      .or_else(|| file.file_stem().map(|stem| stem.to_string_lossy().to_string()))
+      .map(|name| component_name(&name))
+      .unwrap_or_else(|| String::from("Bundled"));
+    let components = self.components.entry(kind.clone()).or_insert_with(|| Value::Object(Map::new()));
+    let components = components.as_object_mut().ok_or_else(|| eyre!("components.{kind} is not an object"))?;
+    let mut unique = name.clone();
+    let mut suffix = 2;
+    while components.contains_key(&unique) {
+      unique = format!("{name}_{suffix}");
+      suffix += 1;
+    }
+    // reserved before its own references are followed, which may lead back to it
+    components.insert(unique.clone(), Value::Null);
+    let reference = format!("#/components/{kind}/{}", unique.replace('~', "~0").replace('/', "~1"));
+    self.bundled.insert((file.clone(), pointer.clone()), reference.clone());
+    let mut value = self.value(&file, &pointer)?;
+    self.follow(&mut value, &file, false)?;
+    self.components[kind.as_str()][unique.as_str()] = value;
+    Ok(reference)
+  }
+
+  /// Replaces the references of the value, read from the file, to other files, and to the file itself unless it is the
+  /// root spec, with references to the components they are bundled as.
+  fn follow(&mut self, value: &mut Value, file: &Path, root: bool) -> Result<()> {
+    match value {
+      Value::Object(object) => {
+        if let Some(Value::String(reference)) = object.get("$ref") {
+          if !(root && reference.starts_with('#')) {
+            let (target, pointer) = Self::target(file, reference);
+            let local = self.bundle(target, pointer)?;
+            object.insert(String::from("$ref"), Value::String(local));
+          }
+        }
+        for (key, nested) in object.iter_mut() {
+          if key != "$ref" {
+            self.follow(nested, file, root)?;
+          }
+        }
+      },
+      Value::Array(items) => {
+        for item in items.iter_mut() {
+          self.follow(item, file, root)?;
+        }
+      },
+      _ => {},
+    }
+    Ok(())
+  }
+}
+
+/// The spec at the path with every reference to another file resolved: path items are inlined, and everything else
+/// is moved under the components of the spec, local references pointing to it.
+pub fn bundle(path: &str) -> Result<Value> {
+  let file = std::fs::canonicalize(path).wrap_err_with(|| format!("{path} not found"))?;
+  let mut bundler = Bundler { documents: BTreeMap::new(), components: Map::new(), bundled: BTreeMap::new() };
+  let mut document = bundler.document(&file)?.clone();
+  bundler.components = document.get("components").and_then(Value::as_object).cloned().unwrap_or_default();
+  // path items cannot be components, they are inlined
+  if let Some(paths) = document.get_mut("paths").and_then(Value::as_object_mut) {
+    for item in paths.values_mut() {
+      let Some(reference) = item.get("$ref").and_then(Value::as_str).filter(|reference| !reference.starts_with('#'))
+      else {
+        continue;
+      };
+      let (target, pointer) = Bundler::target(&file, reference);
+      let mut inlined = bundler.value(&target, &pointer)?;
+      bundler.follow(&mut inlined, &target, false)?;
+      *item = inlined;
+    }
+  }
+  let mut root = document.clone();
+  if let Some(object) = root.as_object_mut() {
+    object.remove("components");
+  }
+  bundler.follow(&mut root, &file, true)?;
+  let mut components = Value::Object(bundler.components.clone());
+  bundler.follow(&mut components, &file, true)?;
+  // components reached only through the root components are bundled while following them
+  for (kind, bundled) in bundler.components {
+    if components.get(&kind).is_none() {
+      components[kind.as_str()] = bundled;
+    } else if let (Some(existing), Some(bundled)) = (components[kind.as_str()].as_object_mut(), bundled.as_object()) {
+      for (name, value) in bundled {
+        existing.entry(name.clone()).or_insert_with(|| value.clone());
+      }
+    }
+  }
+  if let Some(object) = root.as_object_mut() {
+    if components.as_object().is_some_and(|components| !components.is_empty()) {
+      object.insert(String::from("components"), components);
+    }
+  }
+  Ok(root)
+}
+
+/// Local references to components in the value, including the schemas named by discriminator mappings.
+fn references(value: &Value, found: &mut BTreeSet<String>) {
+  match value {
+    Value::Object(object) => {
+      for (key, nested) in object {
+        match (key.as_str(), nested) {
+          ("$ref", Value::String(reference)) => {
+            found.insert(reference.clone());
+          },
+          ("mapping", Value::Object(mapping)) => {
+            found.extend(mapping.values().filter_map(Value::as_str).map(String::from));
+          },
+          _ => references(nested, found),
+        }
+      }
+    },
+    Value::Array(items) => items.iter().for_each(|item| references(item, found)),
+    _ => {},
+  }
+}
+
+/// Keeps only the operations of the tags, or tag groups, and the operations named by id or by method and path, e.g.
+/// `POST /pets`, then drops the components, tags and paths left unused. Keeps everything when given neither.
+pub fn filter(document: &mut Value, tags: &[String], operations: &[String]) {
+  if tags.is_empty() && operations.is_empty() {
+    return;
+  }
+  let mut kept = BTreeSet::new();
+  for tag in tags {
+    kept
+      .extend(inventory::operations(document, Some(tag), None).into_iter().map(|listed| (listed.method, listed.path)));
+  }
+  for listed in inventory::operations(document, None, None) {
+    let named = operations.iter().any(|name| {
+      listed.operation_id.as_deref() == Some(name.as_str())
+        || name
+          .split_once(' ')
+          .is_some_and(|(method, path)| method.eq_ignore_ascii_case(&listed.method) && path.trim() == listed.path)
+    });
+    if named {
+      kept.insert((listed.method, listed.path));
+    }
+  }
+  if let Some(paths) = document.get_mut("paths").and_then(Value::as_object_mut) {
+    for (path, item) in paths.iter_mut() {
+      if let Some(item) = item.as_object_mut() {
+        item.retain(|key, _| !METHODS.contains(&key.as_str()) || kept.contains(&(key.to_uppercase(), path.clone())));
+      }
+    }
+    paths.retain(|_, item| METHODS.iter().any(|method| item.get(*method).is_some()));
+  }
+  prune(document);
+}
+
+/// Drops the components no longer referenced, but the security schemes, and the tags no longer used.
+fn prune(document: &mut Value) {
+  let mut outside = document.clone();
+  if let Some(object) = outside.as_object_mut() {
+    object.remove("components");
+  }
+  let mut reached = BTreeSet::new();
+  references(&outside, &mut reached);
+  let mut pending: Vec<String> = reached.iter().cloned().collect();
+  while let Some(reference) = pending.pop() {
+    let Some(component) = reference.strip_prefix('#').and_then(|pointer| document.pointer(pointer)) else {
+      continue;
+    };
+    let mut nested = BTreeSet::new();
+    references(component, &mut nested);
+    for reference in nested {
+      if reached.insert(reference.clone()) {
+        pending.push(reference);
+      }
+    }
+  }
+  if let Some(components) = document.get_mut("components").and_then(Value::as_object_mut) {
+    for (kind, entries) in components.iter_mut().filter(|(kind, _)| kind.as_str() != "securitySchemes") {
+      if let Some(entries) = entries.as_object_mut() {
+        entries.retain(|name, _| {
+          reached.contains(&format!("#/components/{kind}/{}", name.replace('~', "~0").replace('/', "~1")))
+        });
+      }
+    }
+    components.retain(|_, entries| entries.as_object().is_none_or(|entries| !entries.is_empty()));
+  }
+  let used: BTreeSet<String> =
+    inventory::operations(document, None, None).into_iter().flat_map(|listed| listed.tags).collect();
+  if let Some(tags) = document.get_mut("tags").and_then(Value::as_array_mut) {
+    tags.retain(|tag| tag.get("name").and_then(Value::as_str).is_some_and(|name| used.contains(name)));
+  }
+}
+
+/// The spec as YAML when the output path ends with `.yaml` or `.yml`, as pretty printed JSON otherwise.
+pub fn render(document: &Value, output: Option<&str>) -> Result<String> {
+  let yaml = output.is_some_and(|output| output.ends_with(".yaml") || output.ends_with(".yml"));
+  match yaml {
+    true => Ok(serde_yaml::to_string(document)?),
+    false => Ok(serde_json::to_string_pretty(document)? + "\n"),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+  use serde_json::json;
+
+  use super::*;
+
+  #[test]
+  fn test_bundle() {
+    let directory = std::env::temp_dir().join(format!("openapi-tui-bundle-{}", std::process::id()));
+    std::fs::create_dir_all(directory.join("schemas")).unwrap();
+    std::fs::write(
+      directory.join("openapi.yaml"),
+      r##"
+openapi: 3.0.3
+info: { title: Pets, version: "1" }
+tags: [{ name: pets }, { name: admin }]
+paths:
+  /pets:
+    $ref: paths/pets.yaml
+  /admin:
+    get:
+      tags: [admin]
+      responses:
+        "200": { $ref: "#/components/responses/Ok" }
+components:
+  responses:
+    Ok: { description: ok }
+  securitySchemes:
+    key: { type: apiKey, in: header, name: X-Key }
+"##,
+    )
+    .unwrap();
+    std::fs::create_dir_all(directory.join("paths")).unwrap();
+    std::fs::write(
+      directory.join("paths/pets.yaml"),
+      r##"
+get:
+  tags: [pets]
+  operationId: listPets
+  responses:
+    "200":
+      description: pets
+      content:
+        application/json:
+          schema: { type: array, items: { $ref: "../schemas/pet.yaml" } }
+"##,
+    )
+    .unwrap();
+    std::fs::write(
+      directory.join("schemas/pet.yaml"),
+      r##"
+type: object
+properties:
+  owner: { $ref: "#/definitions/Owner" }
+  parent: { $ref: "#" }
+definitions:
+  Owner: { type: object, properties: { name: { type: string } } }
+"##,
+    )
+    .unwrap();
+    let bundled = bundle(&directory.join("openapi.yaml").display().to_string());
+    std::fs::remove_dir_all(&directory).unwrap();
+    let mut bundled = bundled.unwrap();
+
+    assert_eq!(
+      bundled["paths"]["/pets"]["get"]["responses"]["200"]["content"]["application/json"]["schema"],
+      json!({
+        "type": "array",
+        "items": { "$ref": "#/components/schemas/pet" }
+      })
+    );
+    let schemas = &bundled["components"]["schemas"];
+    assert_eq!(schemas["pet"]["properties"]["owner"], json!({ "$ref": "#/components/schemas/Owner" }));
+    assert_eq!(schemas["pet"]["properties"]["parent"], json!({ "$ref": "#/components/schemas/pet" }));
+    assert_eq!(schemas["Owner"]["properties"]["name"], json!({ "type": "string" }));
+    assert_eq!(bundled["components"]["responses"]["Ok"], json!({ "description": "ok" }));
+
+    filter(&mut bundled, &[], &[String::from("listPets")]);
+    assert_eq!(bundled["paths"].as_object().unwrap().keys().collect::<Vec<_>>(), vec!["/pets"]);
+    assert_eq!(bundled["components"].as_object().unwrap().keys().collect::<Vec<_>>(), vec![
+      "schemas",
+      "securitySchemes"
+    ]);
+    assert_eq!(bundled["tags"], json!([{ "name": "pets" }]));
+    assert!(render(&bundled, Some("out.yaml")).unwrap().starts_with("components:"));
+  }
+}
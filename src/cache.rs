@@ -0,0 +1,369 @@
+use std::{
+  collections::HashMap,
+  sync::{Arc, Mutex},
+  time::Instant,
+};
+
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+  client::{self, Exchange, HttpResponse},
+  request::HttpRequest,
+  workspace::OperationKey,
+};
+
+/// Statuses cacheable by default, RFC 9110 section 15.1.
+const CACHEABLE_STATUSES: [u16; 11] = [200, 203, 204, 206, 300, 301, 308, 404, 405, 410, 501];
+
+/// How a response was answered from the client-side cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CacheHit {
+  /// Served while fresh, without sending the request.
+  Fresh,
+  /// Served after the server answered a conditional request with a 304.
+  Revalidated,
+}
+
+/// Directives of the `Cache-Control` header, lowercased, e.g. `("max-age", Some("60"))`.
+pub fn directives(response: &HttpResponse) -> Vec<(String, Option<String>)> {
+  let headers = response.headers.iter().filter(|(name, _)| name.eq_ignore_ascii_case("Cache-Control"));
+  headers
+    .flat_map(|(_, value)| value.split(','))
+    .map(str::trim)
+    .filter(|directive| !directive.is_empty())
+    .map(|directive| {
+      match directive.split_once('=') {
+        Some((name, value)) => (name.trim().to_lowercase(), Some(value.trim().trim_matches('"').to_string())),
+        None => (directive.to_lowercase(), None),
+      }
+    })
+    .collect()
+}
+
+fn directive(directives: &[(String, Option<String>)], name: &str) -> Option<Option<u64>> {
+  directives
+    .iter()
+    .find(|(directive, _)| directive == name)
+    .map(|(_, value)| value.as_deref().and_then(|value| value.parse().ok()))
+}
+
+fn timestamp(value: &str) -> Option<i64> {
+  chrono::DateTime::parse_from_rfc2822(value).ok().map(|date| date.timestamp())
+}
+
+/// Seconds the response was already cached for by the caches on its way, from its `Age` header.
+pub fn age(response: &HttpResponse) -> u64 {
+  response.header("Age").and_then(|age| age.trim().parse().ok()).unwrap_or_default()
+}
+
+/// Seconds the response stays fresh once generated, from `max-age` or else `Expires` and `Date`, `None` when it
+/// tells neither.
+pub fn freshness_lifetime(response: &HttpResponse) -> Option<u64> {
+  let directives = directives(response);
+  if let Some(Some(max_age)) = directive(&directives, "max-age") {
+    return Some(max_age);
+  }
+  let expires = response.header("Expires")?;
+  let date = response.header("Date").and_then(timestamp)?;
+  // an invalid date, e.g. `0`, means already expired
+  Some(timestamp(expires).map_or(0, |expires| expires.saturating_sub(date).max(0) as u64))
+}
+
+/// Whether a client-side cache may keep the response to the request.
+pub fn is_storable(request: &HttpRequest, response: &HttpResponse) -> bool {
+  let directives = directives(response);
+  request.method.eq_ignore_ascii_case("get")
+    && CACHEABLE_STATUSES.contains(&response.status)
+    && directive(&directives, "no-store").is_none()
+    && response.header("Vary").is_none_or(|vary| vary.trim() != "*")
+    && response.spooled.is_none()
+    && (freshness_lifetime(response).is_some()
+      || response.header("ETag").is_some()
+      || response.header("Last-Modified").is_some())
+}
+
+/// Parts of the caching behavior the response headers tell, e.g. `max-age 60 s, fresh for 42 s` or `ETag "33a64df5"`,
+/// along with whether a cache may store it.
+pub fn analysis(request: &HttpRequest, response: &HttpResponse) -> (bool, Vec<String>) {
+  let directives = directives(response);
+  let mut parts = vec![];
+  if !directives.is_empty() {
+    let text = directives.iter().map(|(name, value)| {
+      match value {
+        Some(value) => format!("{name}={value}"),
+        None => name.clone(),
+      }
+    });
+    parts.push(text.collect::<Vec<_>>().join(", "));
+  }
+  if let Some(lifetime) = freshness_lifetime(response) {
+    let age = age(response);
+    match (lifetime.checked_sub(age), directive(&directives, "no-cache").is_some()) {
+      (_, true) => parts.push(String::from("revalidated before each use")),
+      (Some(left), false) if left > 0 => parts.push(format!("fresh for {left} s")),
+      _ => parts.push(String::from("stale")),
+    }
+  }
+  if response.header("Age").is_some() {
+    parts.push(format!("Age {} s", age(response)));
+  }
+  for header in ["ETag", "Last-Modified", "Vary"] {
+    if let Some(value) = response.header(header) {
+      parts.push(format!("{header} {value}"));
+    }
+  }
+  (is_storable(request, response), parts)
+}
+
+/// Conditional headers revalidating the response, `If-None-Match` with its `ETag` and `If-Modified-Since` with its
+/// `Last-Modified`, empty when it has no validator.
+pub fn conditional_headers(response: &HttpResponse) -> Vec<(String, String)> {
+  let mut headers = vec![];
+  if let Some(etag) = response.header("ETag") {
+    headers.push((String::from("If-None-Match"), etag.to_string()));
+  }
+  if let Some(modified) = response.header("Last-Modified") {
+    headers.push((String::from("If-Modified-Since"), modified.to_string()));
+  }
+  headers
+}
+
+fn is_conditional(request: &HttpRequest) -> bool {
+  request
+    .headers
+    .iter()
+    .any(|(name, _)| name.eq_ignore_ascii_case("If-None-Match") || name.eq_ignore_ascii_case("If-Modified-Since"))
+}
+
+/// How the server handled a conditional request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Revalidation {
+  /// Answered with a 304 and no body.
+  NotModified,
+  /// Sent the full response again though its validator still matches the one of the request.
+  Ignored,
+  /// Sent the full response with a new validator, e.g. `ETag "34"`.
+  Modified(String),
+  /// Sent the full response without any validator to compare with.
+  Unvalidated,
+}
+
+/// How the server handled the request, `None` when it is no conditional request or failed.
+pub fn revalidation(request: &HttpRequest, response: &HttpResponse) -> Option<Revalidation> {
+  if !is_conditional(request) {
+    return None;
+  }
+  match response.status {
+    304 => return Some(Revalidation::NotModified),
+    200..=299 => {},
+    _ => return None,
+  }
+  let sent =
+    |name: &str| request.headers.iter().find(|(header, _)| header.eq_ignore_ascii_case(name)).map(|(_, value)| value);
+  let validators = [("If-None-Match", "ETag"), ("If-Modified-Since", "Last-Modified")];
+  let compared = validators.iter().find_map(|(condition, validator)| Some((sent(condition)?, *validator)));
+  let Some((condition, validator)) = compared else {
+    return Some(Revalidation::Unvalidated);
+  };
+  match response.header(validator) {
+    Some(value) if value.trim() == condition.trim() => Some(Revalidation::Ignored),
+    Some(value) => Some(Revalidation::Modified(format!("{validator} {value}"))),
+    None => Some(Revalidation::Unvalidated),
+  }
+}
+
+struct Entry {
+  response: HttpResponse,
+  stored_at: Instant,
+  /// Headers of the request the response varies on, with their values when it was stored.
+  vary: Vec<(String, Option<String>)>,
+}
+
+/// What the cache holds for a request.
+enum Lookup {
+  Fresh(HttpResponse),
+  /// To be revalidated with a conditional request before use.
+  Stale(HttpResponse),
+  Miss,
+}
+
+/// Client-side cache of the responses to GET requests, by method and url, kept for the session.
+#[derive(Clone, Default)]
+pub struct Cache {
+  entries: Arc<Mutex<HashMap<String, Entry>>>,
+}
+
+fn key(request: &HttpRequest) -> String {
+  format!("{} {}", request.method.to_uppercase(), request.full_url())
+}
+
+fn vary(request: &HttpRequest, response: &HttpResponse) -> Vec<(String, Option<String>)> {
+  let names = response.header("Vary").into_iter().flat_map(|vary| vary.split(',')).map(str::trim);
+  names
+    .filter(|name| !name.is_empty())
+    .map(|name| {
+      let value = request.headers.iter().find(|(header, _)| header.eq_ignore_ascii_case(name));
+      (name.to_lowercase(), value.map(|(_, value)| value.clone()))
+    })
+    .collect()
+}
+
+impl Cache {
+  fn lookup(&self, request: &HttpRequest) -> Lookup {
+    let entries = self.entries.lock().unwrap();
+    let Some(entry) = entries.get(&key(request)) else {
+      return Lookup::Miss;
+    };
+    if vary(request, &entry.response) != entry.vary {
+      return Lookup::Miss;
+    }
+    let age = age(&entry.response) + entry.stored_at.elapsed().as_secs();
+    let no_cache = directive(&directives(&entry.response), "no-cache").is_some();
+    match freshness_lifetime(&entry.response) {
+      Some(lifetime) if !no_cache && age < lifetime => Lookup::Fresh(entry.response.clone()),
+      _ => Lookup::Stale(entry.response.clone()),
+    }
+  }
+
+  fn store(&self, request: &HttpRequest, response: &HttpResponse) {
+    let mut entries = self.entries.lock().unwrap();
+    match is_storable(request, response) {
+      true => {
+        let entry = Entry { response: response.clone(), stored_at: Instant::now(), vary: vary(request, response) };
+        entries.insert(key(request), entry);
+      },
+      false => {
+        entries.remove(&key(request));
+      },
+    }
+  }
+
+  /// Answers a GET from the cache while its response is fresh, revalidates it with `If-None-Match` or
+  /// `If-Modified-Since` once stale, and sends any other request, or one already conditional, as is.
+  pub async fn exchange(&self, operation: Option<OperationKey>, request: HttpRequest) -> Result<Exchange> {
+    if !request.method.eq_ignore_ascii_case("get") || is_conditional(&request) {
+      return client::exchange(operation, request).await;
+    }
+    let cached = match self.lookup(&request) {
+      Lookup::Fresh(response) => {
+        let response = HttpResponse { cache_hit: Some(CacheHit::Fresh), ..response };
+        let started_at = chrono::Utc::now().to_rfc3339();
+        return Ok(Exchange { started_at, operation, request, response, ..Exchange::default() });
+      },
+      Lookup::Stale(response) => Some(response),
+      Lookup::Miss => None,
+    };
+    let mut conditional = request.clone();
+    if let Some(cached) = &cached {
+      conditional.headers.extend(conditional_headers(cached));
+    }
+    let mut exchange = client::exchange(operation, conditional).await?;
+    exchange.request = request;
+    match cached.filter(|_| exchange.response.status == 304) {
+      Some(cached) => {
+        // the 304 carries the headers to update, e.g. `Date` and `Cache-Control`
+        let mut headers = cached.headers.clone();
+        for (name, value) in exchange.response.headers.iter() {
+          match headers.iter_mut().find(|(header, _)| header.eq_ignore_ascii_case(name)) {
+            Some(header) => header.1 = value.clone(),
+            None => headers.push((name.clone(), value.clone())),
+          }
+        }
+        exchange.response = HttpResponse { headers, ..cached };
+        self.store(&exchange.request, &exchange.response);
+        exchange.response.cache_hit = Some(CacheHit::Revalidated);
+      },
+      None => self.store(&exchange.request, &exchange.response),
+    }
+    Ok(exchange)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  fn response(headers: &[(&str, &str)]) -> HttpResponse {
+    HttpResponse {
+      status: 200,
+      headers: headers.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect(),
+      ..HttpResponse::default()
+    }
+  }
+
+  #[test]
+  fn test_analysis() {
+    let get = HttpRequest { method: String::from("get"), ..HttpRequest::default() };
+    let cached = response(&[("Cache-Control", "public, max-age=60"), ("Age", "18"), ("ETag", "\"33a64df5\"")]);
+    assert_eq!(freshness_lifetime(&cached), Some(60));
+    assert_eq!(
+      analysis(&get, &cached),
+      (true, vec![
+        String::from("public, max-age=60"),
+        String::from("fresh for 42 s"),
+        String::from("Age 18 s"),
+        String::from("ETag \"33a64df5\""),
+      ])
+    );
+
+    let expires = response(&[("Date", "Wed, 21 Oct 2015 07:28:00 GMT"), ("Expires", "Wed, 21 Oct 2015 07:38:00 GMT")]);
+    assert_eq!(freshness_lifetime(&expires), Some(600));
+    assert_eq!(freshness_lifetime(&response(&[("Date", "Wed, 21 Oct 2015 07:28:00 GMT"), ("Expires", "0")])), Some(0));
+
+    let no_store = response(&[("Cache-Control", "no-store"), ("ETag", "\"1\"")]);
+    assert!(!is_storable(&get, &no_store));
+    assert!(!is_storable(&get, &response(&[])));
+    let post = HttpRequest { method: String::from("post"), ..HttpRequest::default() };
+    assert!(!is_storable(&post, &cached));
+    let (_, parts) = analysis(&get, &response(&[("Cache-Control", "max-age=60, no-cache")]));
+    assert_eq!(parts, vec![String::from("max-age=60, no-cache"), String::from("revalidated before each use")]);
+  }
+
+  #[test]
+  fn test_lookup() {
+    let request = HttpRequest {
+      method: String::from("get"),
+      base_url: String::from("https://petstore.example.com"),
+      path: String::from("/pets"),
+      headers: vec![(String::from("Accept"), String::from("application/json"))],
+      ..HttpRequest::default()
+    };
+    let cache = Cache::default();
+    assert!(matches!(cache.lookup(&request), Lookup::Miss));
+    cache.store(&request, &response(&[("Cache-Control", "max-age=60"), ("Vary", "Accept")]));
+    assert!(matches!(cache.lookup(&request), Lookup::Fresh(_)));
+    let xml =
+      HttpRequest { headers: vec![(String::from("Accept"), String::from("application/xml"))], ..request.clone() };
+    assert!(matches!(cache.lookup(&xml), Lookup::Miss));
+
+    cache.store(&request, &response(&[("Cache-Control", "no-cache"), ("ETag", "\"1\"")]));
+    assert!(matches!(cache.lookup(&request), Lookup::Stale(_)));
+    cache.store(&request, &response(&[("Cache-Control", "no-store")]));
+    assert!(matches!(cache.lookup(&request), Lookup::Miss));
+  }
+
+  #[test]
+  fn test_revalidation() {
+    let validated = response(&[("ETag", "\"33\""), ("Last-Modified", "Wed, 21 Oct 2015 07:28:00 GMT")]);
+    let headers = conditional_headers(&validated);
+    assert_eq!(headers, vec![
+      (String::from("If-None-Match"), String::from("\"33\"")),
+      (String::from("If-Modified-Since"), String::from("Wed, 21 Oct 2015 07:28:00 GMT")),
+    ]);
+    let request = HttpRequest { method: String::from("get"), headers, ..HttpRequest::default() };
+    assert_eq!(revalidation(&HttpRequest::default(), &validated), None);
+    let not_modified = HttpResponse { status: 304, ..HttpResponse::default() };
+    assert_eq!(revalidation(&request, &not_modified), Some(Revalidation::NotModified));
+    assert_eq!(revalidation(&request, &validated), Some(Revalidation::Ignored));
+    assert_eq!(
+      revalidation(&request, &response(&[("ETag", "\"34\"")])),
+      Some(Revalidation::Modified(String::from("ETag \"34\"")))
+    );
+    assert_eq!(revalidation(&request, &response(&[])), Some(Revalidation::Unvalidated));
+    assert_eq!(revalidation(&request, &HttpResponse { status: 500, ..HttpResponse::default() }), None);
+  }
+}
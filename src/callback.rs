@@ -0,0 +1,108 @@
+use serde_json::Value;
+
+use crate::{
+  mock::{self, METHODS},
+  webhook,
+};
+
+/// Operation of the `callbacks` of an operation, a request the API sends to the URL of the runtime expression.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Callback {
+  pub name: String,
+  /// Runtime expression of the URL, e.g. `{$request.body#/callbackUrl}`.
+  pub expression: String,
+  pub method: String,
+  pub summary: Option<String>,
+  pub description: Option<String>,
+  pub media_type: Option<String>,
+  /// Schema of the payload, resolved when it is a reference, `Null` when the callback has no request body.
+  pub schema: Value,
+}
+
+impl Callback {
+  pub fn title(&self) -> String {
+    format!("{} {}", self.name, self.method)
+  }
+
+  /// Markdown description of the callback, starting with its method and expression.
+  pub fn describe(&self) -> String {
+    let expression = format!("**Callback** `{} {}`", self.method, self.expression);
+    [Some(expression), self.summary.clone(), self.description.clone()]
+      .into_iter()
+      .flatten()
+      .collect::<Vec<_>>()
+      .join("\n\n")
+  }
+}
+
+/// Callbacks of the operation, by name, expression and method.
+pub fn callbacks(operation: &Value, document: &Value) -> Vec<Callback> {
+  let mut callbacks = vec![];
+  for (name, callback) in operation.get("callbacks").and_then(Value::as_object).into_iter().flatten() {
+    let callback = mock::resolve(callback, document);
+    for (expression, item) in callback.as_object().into_iter().flatten() {
+      let item = mock::resolve(item, document);
+      for method in METHODS {
+        let Some(operation) = item.get(method) else {
+          continue;
+        };
+        let media = webhook::request_media(operation, document);
+        callbacks.push(Callback {
+          name: name.clone(),
+          expression: expression.clone(),
+          method: method.to_uppercase(),
+          summary: operation.get("summary").and_then(Value::as_str).map(String::from),
+          description: operation.get("description").and_then(Value::as_str).map(String::from),
+          media_type: media.map(|(media_type, _)| media_type.clone()),
+          schema: media
+            .and_then(|(_, media)| media.get("schema"))
+            .map(|schema| mock::resolve(schema, document).clone())
+            .unwrap_or_default(),
+        });
+      }
+    }
+  }
+  callbacks
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+  use serde_json::json;
+
+  use super::*;
+
+  #[test]
+  fn test_callbacks() {
+    let document = json!({
+      "paths": { "/subscriptions": { "post": {
+        "callbacks": {
+          "onEvent": { "{$request.body#/callbackUrl}": {
+            "post": {
+              "summary": "Event happened",
+              "requestBody": { "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Event" } } } }
+            },
+            "delete": {}
+          } },
+          "onClose": { "$ref": "#/components/callbacks/Close" }
+        }
+      } } },
+      "components": {
+        "schemas": { "Event": { "properties": { "id": { "type": "string" } } } },
+        "callbacks": { "Close": { "{$request.body#/closeUrl}": { "put": {} } } }
+      }
+    });
+    let operation = document.pointer("/paths/~1subscriptions/post").unwrap();
+    let callbacks = callbacks(operation, &document);
+    assert_eq!(callbacks.iter().map(Callback::title).collect::<Vec<_>>(), vec![
+      "onClose PUT",
+      "onEvent POST",
+      "onEvent DELETE"
+    ]);
+    assert_eq!(callbacks[0].expression, "{$request.body#/closeUrl}");
+    assert_eq!(callbacks[1].media_type.as_deref(), Some("application/json"));
+    assert_eq!(callbacks[1].schema, json!({ "properties": { "id": { "type": "string" } } }));
+    assert_eq!(callbacks[1].describe(), "**Callback** `POST {$request.body#/callbackUrl}`\n\nEvent happened");
+    assert_eq!(callbacks[2].schema, Value::Null);
+  }
+}
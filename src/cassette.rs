@@ -0,0 +1,152 @@
+use std::{
+  collections::BTreeMap,
+  path::{Path, PathBuf},
+  sync::Mutex,
+};
+
+use color_eyre::eyre::{eyre, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+  client::{Exchange, HttpResponse},
+  request::HttpRequest,
+  workspace::OperationKey,
+};
+
+/// Recorded requests and their responses, in the order they were sent.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cassette {
+  pub interactions: Vec<Interaction>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Interaction {
+  /// Request as sent, with secret variable values written as their `{{name}}` reference.
+  pub request: HttpRequest,
+  pub response: HttpResponse,
+}
+
+impl Cassette {
+  /// An empty cassette when the file does not exist yet.
+  pub fn load(path: &Path) -> Result<Self> {
+    if !path.exists() {
+      return Ok(Self::default());
+    }
+    Ok(serde_yaml::from_reader(std::fs::File::open(path)?)?)
+  }
+
+  pub fn save(&self, path: &Path) -> Result<()> {
+    std::fs::write(path, serde_yaml::to_string(self)?)?;
+    Ok(())
+  }
+}
+
+/// Adds every exchange to the cassette file, started with `--record`.
+#[derive(Debug)]
+pub struct Recorder {
+  path: PathBuf,
+  cassette: Cassette,
+}
+
+impl Recorder {
+  /// Records after the interactions the cassette already holds.
+  pub fn new(path: &str) -> Result<Self> {
+    let path = PathBuf::from(path);
+    Ok(Self { cassette: Cassette::load(&path)?, path })
+  }
+
+  pub fn record(&mut self, request: HttpRequest, response: HttpResponse) -> Result<()> {
+    self.cassette.interactions.push(Interaction { request, response });
+    self.cassette.save(&self.path)
+  }
+}
+
+/// Answers requests from a cassette instead of the network, started with `--replay`.
+///
+/// A request gets the response of the first interaction with the same method and url that was not played yet, or
+/// of the last one once they all were, so that repeated requests replay the responses in the recorded order.
+#[derive(Debug)]
+pub struct Player {
+  cassette: Cassette,
+  played: Mutex<Vec<bool>>,
+}
+
+impl Player {
+  pub fn new(path: &str) -> Result<Self> {
+    let cassette = Cassette::load(Path::new(path))?;
+    if cassette.interactions.is_empty() {
+      return Err(eyre!("The cassette `{path}` has no recorded interactions"));
+    }
+    Ok(Self { played: Mutex::new(vec![false; cassette.interactions.len()]), cassette })
+  }
+
+  /// The exchange of the recorded response, variables resolve the references to secrets of recorded requests.
+  pub fn play(
+    &self,
+    operation: Option<OperationKey>,
+    request: HttpRequest,
+    variables: &BTreeMap<String, String>,
+  ) -> Result<Exchange> {
+    let mut played = self.played.lock().unwrap();
+    let matches: Vec<usize> = self
+      .cassette
+      .interactions
+      .iter()
+      .enumerate()
+      .filter(|(_, interaction)| {
+        let recorded = interaction.request.substitute(variables);
+        recorded.method.eq_ignore_ascii_case(&request.method) && recorded.full_url() == request.full_url()
+      })
+      .map(|(index, _)| index)
+      .collect();
+    let index = matches
+      .iter()
+      .find(|index| !played[**index])
+      .or(matches.last())
+      .copied()
+      .ok_or_else(|| eyre!("No response recorded for {} {} in the cassette", request.method, request.full_url()))?;
+    played[index] = true;
+    Ok(Exchange {
+      started_at: chrono::Utc::now().to_rfc3339(),
+      operation,
+      request,
+      response: self.cassette.interactions[index].response.clone(),
+      ..Exchange::default()
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_play() {
+    let request = |path: &str| {
+      HttpRequest {
+        method: String::from("GET"),
+        base_url: String::from("https://{{host}}"),
+        path: path.to_string(),
+        ..HttpRequest::default()
+      }
+    };
+    let response = |status: u16| HttpResponse { status, ..HttpResponse::default() };
+    let player = Player {
+      cassette: Cassette {
+        interactions: vec![
+          Interaction { request: request("/pets"), response: response(200) },
+          Interaction { request: request("/pets/1"), response: response(404) },
+          Interaction { request: request("/pets"), response: response(503) },
+        ],
+      },
+      played: Mutex::new(vec![false; 3]),
+    };
+    let variables = BTreeMap::from([(String::from("host"), String::from("example.com"))]);
+    let sent = HttpRequest { base_url: String::from("https://example.com"), ..request("/pets") };
+    let status = || player.play(None, sent.clone(), &variables).unwrap().response.status;
+    assert_eq!((status(), status(), status()), (200, 503, 503));
+    assert!(player.play(None, request("/pets"), &variables).is_err());
+  }
+}
@@ -0,0 +1,40 @@
+use encoding_rs::{Encoding, UTF_8};
+
+/// Charsets a body can be decoded with instead of the one its `Content-Type` declares, `windows-1252` standing for
+/// `ISO-8859-1` as browsers do.
+pub const CHARSETS: [&str; 9] =
+  ["UTF-8", "windows-1252", "ISO-8859-2", "Shift_JIS", "EUC-JP", "GBK", "Big5", "EUC-KR", "UTF-16LE"];
+
+/// The `charset` parameter of a `Content-Type` header value.
+pub fn of(content_type: &str) -> Option<&str> {
+  content_type.split(';').skip(1).find_map(|param| {
+    let (name, value) = param.split_once('=')?;
+    name.trim().eq_ignore_ascii_case("charset").then(|| value.trim().trim_matches('"'))
+  })
+}
+
+/// Decodes the body with the charset, UTF-8 when it has none or an unknown one, a byte order mark winning over it, and
+/// returns the text along with the name of the charset it was decoded with.
+pub fn decode(bytes: &[u8], charset: Option<&str>) -> (String, &'static str) {
+  let encoding = charset.and_then(|label| Encoding::for_label(label.trim().as_bytes())).unwrap_or(UTF_8);
+  let (text, used, _) = encoding.decode(bytes);
+  (text.into_owned(), used.name())
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_decode() {
+    assert_eq!(of("text/plain; charset=\"Shift_JIS\""), Some("Shift_JIS"));
+    assert_eq!(of("application/json"), None);
+
+    assert_eq!(decode(b"caf\xe9", of("text/plain; charset=ISO-8859-1")), (String::from("café"), "windows-1252"));
+    assert_eq!(decode(b"\x82\xb1\x82\xf1\x82\xc9\x82\xbf\x82\xcd", Some("Shift_JIS")).0, "こんにちは");
+    assert_eq!(decode("café".as_bytes(), None), (String::from("café"), "UTF-8"));
+    assert_eq!(decode(b"caf\xe9", Some("unknown")).0, "caf\u{FFFD}");
+  }
+}
@@ -1,16 +1,302 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
+use clap_complete::ArgValueCompleter;
 
-use crate::utils::version;
+use crate::{completion, utils::version};
 
-#[derive(Parser, Debug)]
+#[derive(Parser, Debug, Clone)]
 #[command(author, version = version(), about)]
 pub struct Cli {
   #[arg(
     short,
     long,
     value_name = "PATH",
-    help = "Input file, i.e. json or yaml file with openapi specification",
+    help = "Input file, i.e. json or yaml file with openapi specification, or its url, or several files to merge separated by commas or matched by a glob, or git:<REF>:<PATH> read from a git reference",
     default_value_t= String::from("openapi.json")
   )]
   pub openapi_path: String,
+
+  #[arg(
+    short,
+    long,
+    value_name = "PATH",
+    help = "Import requests from a .http file, a Postman collection, an Insomnia export or a HAR capture into the workspace, can be repeated"
+  )]
+  pub import: Vec<String>,
+
+  #[arg(
+    short,
+    long,
+    value_name = "URL",
+    help = "Base url used instead of the spec servers, e.g. http://localhost:8080"
+  )]
+  pub base_url: Option<String>,
+
+  #[arg(
+    long,
+    value_name = "ENTRY",
+    help = "Send requests to a host and port to another address, as host:port:address, e.g. api.example.com:443:127.0.0.1, can be repeated"
+  )]
+  pub resolve: Vec<String>,
+
+  #[arg(
+    long,
+    value_name = "COUNT",
+    help = "Follow at most COUNT redirects of a request, 0 to show the redirect responses themselves [default: 10]"
+  )]
+  pub max_redirects: Option<usize>,
+
+  #[arg(
+    long,
+    value_name = "PATH",
+    help = "Load variables from a .env file, in addition to the .env file next to the spec, can be repeated"
+  )]
+  pub env_file: Vec<String>,
+
+  #[arg(
+    long,
+    value_name = "NAME",
+    help = "Store the secret read from stdin in the OS keyring under NAME, reference it as keyring:NAME in environments"
+  )]
+  pub store_secret: Option<String>,
+
+  #[arg(
+    short,
+    long,
+    value_name = "PATH",
+    help = "Load a workflow, a YAML file listing steps to run in order, into the workflow runner, can be repeated"
+  )]
+  pub workflow: Vec<String>,
+
+  #[arg(
+    long,
+    value_name = "PATH",
+    conflicts_with = "replay",
+    help = "Record sent requests and their responses into a cassette YAML file"
+  )]
+  pub record: Option<String>,
+
+  #[arg(
+    long,
+    value_name = "PATH",
+    help = "Answer sent requests from a cassette recorded with --record, without touching the network"
+  )]
+  pub replay: Option<String>,
+
+  #[arg(
+    long,
+    value_name = "REF",
+    help = "Read the specs given by path from a branch, tag or commit of their git repository, e.g. v1.2.0, without checking it out"
+  )]
+  pub git_ref: Option<String>,
+
+  #[arg(long, help = "Load specs given by url from their cached copies, without fetching them")]
+  pub offline: bool,
+
+  #[arg(
+    long,
+    help = "Draw without colors, telling focus, methods and statuses apart by borders, bold and underlines, also when NO_COLOR is set"
+  )]
+  pub no_color: bool,
+
+  #[arg(
+    long,
+    value_name = "OPERATION",
+    add = ArgValueCompleter::new(completion::operations),
+    help = "Start on this operation, by id or method and path, e.g. \"POST /pet\", with the request pane focused"
+  )]
+  pub operation: Option<String>,
+
+  #[arg(
+    long,
+    value_name = "NAME",
+    add = ArgValueCompleter::new(completion::tags),
+    help = "Start on the operations of this tag, or of the tags of this group"
+  )]
+  pub tag: Option<String>,
+
+  #[arg(
+    long,
+    value_name = "SECONDS",
+    help = "Check the spec given by url for changes every SECONDS seconds, offering to reload it when it changed"
+  )]
+  pub refresh: Option<u64>,
+
+  #[arg(
+    long,
+    value_name = "PATH",
+    help = "Write the logs to this file as JSON lines, instead of the log file of the data directory"
+  )]
+  pub log_file: Option<String>,
+
+  #[arg(
+    short,
+    long,
+    action = clap::ArgAction::Count,
+    help = "Log more, -v for debug logs, -vv for the raw requests and responses too, secrets masked"
+  )]
+  pub verbose: u8,
+
+  #[command(subcommand)]
+  pub command: Option<Command>,
+}
+
+impl Cli {
+  /// Specs named by the arguments, the `--openapi-path` one and those of the subcommand.
+  pub fn specs_mut(&mut self) -> Vec<&mut String> {
+    let mut specs = vec![&mut self.openapi_path];
+    match self.command.as_mut() {
+      Some(Command::Mock { spec: Some(spec), .. })
+      | Some(Command::Headless { spec: Some(spec), .. })
+      | Some(Command::List { spec: Some(spec), .. })
+      | Some(Command::ExportDocs { spec: Some(spec), .. })
+      | Some(Command::Bundle { spec: Some(spec), .. })
+      | Some(Command::Call { spec, .. }) => specs.push(spec),
+      Some(Command::Diff { old, new }) => specs.extend([old, new]),
+      _ => {},
+    }
+    specs
+  }
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum Command {
+  /// Serve example responses for every operation of the spec, and list the requests received
+  Mock {
+    #[arg(value_name = "PATH", help = "Spec to mock, the --openapi-path spec when omitted")]
+    spec: Option<String>,
+
+    #[arg(short, long, default_value_t = 4010, help = "Local port to listen on")]
+    port: u16,
+  },
+  /// Run the --workflow workflows and the saved requests holding assertions without the TUI, exits with 1 when a
+  /// test fails
+  Test {
+    #[arg(short, long, value_name = "NAME", help = "Environment to run against, the active one when omitted")]
+    environment: Option<String>,
+
+    #[arg(long, help = "Also smoke test every GET operation with example inputs")]
+    smoke: bool,
+
+    #[arg(long, value_name = "PATH", help = "Write the results as JUnit XML")]
+    junit: Option<String>,
+
+    #[arg(long, value_name = "PATH", help = "Write the results as JSON")]
+    json: Option<String>,
+  },
+  /// Send the request of an operation as the TUI would, auth, variables and scripts included, and print its response
+  /// body, exits with 1 when its status is not 2xx
+  Call {
+    #[arg(value_name = "PATH", help = "Spec of the operation")]
+    spec: String,
+
+    #[arg(value_name = "OPERATION", add = ArgValueCompleter::new(completion::operations), help = "Operation id, or method and path, e.g. \"POST /pet\"")]
+    operation: String,
+
+    #[arg(
+      short,
+      long = "param",
+      value_name = "NAME=VALUE",
+      value_parser = parse_parameter,
+      help = "Value of a parameter of the operation, can be repeated"
+    )]
+    parameters: Vec<(String, String)>,
+
+    #[arg(
+      short,
+      long,
+      value_name = "BODY",
+      help = "Body to send, read from a file when it starts with @, e.g. @body.json"
+    )]
+    data: Option<String>,
+
+    #[arg(short, long, value_name = "NAME", help = "Environment to send with, the active one when omitted")]
+    environment: Option<String>,
+  },
+  /// Run commands read from stdin, one per line, e.g. `select addPet`, `param name=value`, `body @pet.json`, `send` or
+  /// `assert status 200`, and print the outcome of each as a line of JSON, exits with 1 when one failed
+  Headless {
+    #[arg(value_name = "PATH", help = "Spec of the operations, the --openapi-path spec when omitted")]
+    spec: Option<String>,
+
+    #[arg(short, long, value_name = "NAME", help = "Environment to send with, the active one when omitted")]
+    environment: Option<String>,
+  },
+  /// Print the operations of the spec without the TUI, e.g. to pipe them into scripts or fzf
+  List {
+    #[arg(value_name = "PATH", help = "Spec to list, the --openapi-path spec when omitted")]
+    spec: Option<String>,
+
+    #[arg(
+      short,
+      long,
+      value_name = "NAME",
+      add = ArgValueCompleter::new(completion::tags),
+      help = "Only list the operations of this tag, or of the tags of this group"
+    )]
+    tag: Option<String>,
+
+    #[arg(short, long, value_name = "METHOD", help = "Only list the operations of this method, e.g. get")]
+    method: Option<String>,
+
+    #[arg(short, long, default_value = "table", value_parser = ["table", "json"], help = "Output format")]
+    format: String,
+  },
+  /// List the changes between two versions of a spec, exits with 1 when a change is breaking
+  Diff {
+    #[arg(value_name = "OLD", help = "Previous version of the spec")]
+    old: String,
+
+    #[arg(value_name = "NEW", help = "New version of the spec")]
+    new: String,
+  },
+  /// Write documentation of the spec, its operations by tag and its schemas, as Markdown or HTML
+  ExportDocs {
+    #[arg(value_name = "PATH", help = "Spec to document, the --openapi-path spec when omitted")]
+    spec: Option<String>,
+
+    #[arg(short, long, default_value = "md", value_parser = ["md", "html"], help = "Output format")]
+    format: String,
+
+    #[arg(long, value_name = "PATH", help = "File to write the documentation to, stdout when omitted")]
+    output: Option<String>,
+  },
+  /// Write the spec as a single file, the references to other files resolved, optionally trimmed to some tags and
+  /// operations
+  Bundle {
+    #[arg(value_name = "PATH", help = "Spec to bundle, the --openapi-path spec when omitted")]
+    spec: Option<String>,
+
+    #[arg(
+      short,
+      long,
+      value_name = "PATH",
+      help = "File to write the spec to, as YAML when it ends with .yaml or .yml, stdout as JSON when omitted"
+    )]
+    output: Option<String>,
+
+    #[arg(
+      short,
+      long = "tag",
+      value_name = "NAME",
+      add = ArgValueCompleter::new(completion::tags),
+      help = "Keep the operations of this tag, or of the tags of this group, can be repeated"
+    )]
+    tags: Vec<String>,
+
+    #[arg(
+      long = "operation",
+      value_name = "OPERATION",
+      add = ArgValueCompleter::new(completion::operations),
+      help = "Keep this operation, by id or method and path, e.g. \"POST /pet\", can be repeated"
+    )]
+    operations: Vec<String>,
+  },
+}
+
+/// `name=value` of the `--param` option.
+fn parse_parameter(text: &str) -> Result<(String, String), String> {
+  text
+    .split_once('=')
+    .map(|(name, value)| (name.trim().to_string(), value.to_string()))
+    .ok_or_else(|| format!("`{text}` is not NAME=VALUE"))
 }
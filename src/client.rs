@@ -0,0 +1,166 @@
+use std::{
+  collections::BTreeMap,
+  sync::{Arc, Mutex},
+  time::Instant,
+};
+
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+  assertion::TestResult,
+  cache::CacheHit,
+  charset, digest, hosts, multipart,
+  redirect::{self, Redirect},
+  request::HttpRequest,
+  script::LogLine,
+  spool::{Spool, SpooledBody},
+  workspace::OperationKey,
+};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HttpResponse {
+  pub status: u16,
+  pub status_text: String,
+  pub http_version: String,
+  pub headers: Vec<(String, String)>,
+  pub body: String,
+  /// Body as received, kept when it was not UTF-8 to decode it with another charset, not saved with the response.
+  #[serde(skip)]
+  pub raw_body: Option<Vec<u8>>,
+  /// File the body was written to when it was too large to be kept in memory, `body` holding only its head.
+  #[serde(skip)]
+  pub spooled: Option<SpooledBody>,
+  /// Redirects followed on the way to the response, in order.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub redirects: Vec<Redirect>,
+  /// How the response was answered from the client-side cache, `None` when it came from the server.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub cache_hit: Option<CacheHit>,
+}
+
+impl HttpResponse {
+  pub fn header(&self, name: &str) -> Option<&str> {
+    self.headers.iter().find(|(key, _)| key.eq_ignore_ascii_case(name)).map(|(_, value)| value.as_str())
+  }
+}
+
+/// A request sent during this session along with the response it received.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Exchange {
+  /// RFC 3339 timestamp of when the request was sent.
+  pub started_at: String,
+  pub elapsed_ms: u64,
+  pub operation: Option<OperationKey>,
+  pub request: HttpRequest,
+  pub response: HttpResponse,
+  /// Output and assertions of the scripts run for this request.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub log: Vec<LogLine>,
+  /// Results of the assertions of the saved request.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub tests: Vec<TestResult>,
+  /// Variables set by the scripts, not kept in the history as they may hold tokens.
+  #[serde(skip)]
+  pub variables: BTreeMap<String, String>,
+}
+
+/// Sends the request, and sends it again with an answer to the Digest challenge of the server when it responded with a
+/// 401 and the request has Digest credentials.
+pub async fn send(request: &HttpRequest) -> Result<HttpResponse> {
+  let response = send_once(request, None).await?;
+  let Some(credentials) = request.digest.as_ref().filter(|_| response.status == 401) else {
+    return Ok(response);
+  };
+  let Some(challenge) = response.header("WWW-Authenticate").and_then(digest::Challenge::parse) else {
+    return Ok(response);
+  };
+  let url = reqwest::Url::parse(&request.full_url())?;
+  let uri = match url.query() {
+    Some(query) => format!("{}?{query}", url.path()),
+    None => url.path().to_string(),
+  };
+  let authorization = challenge.authorization(credentials, &request.method, &uri, &digest::cnonce());
+  send_once(request, Some(authorization)).await
+}
+
+async fn send_once(request: &HttpRequest, authorization: Option<String>) -> Result<HttpResponse> {
+  let redirects = Arc::new(Mutex::new(vec![]));
+  let mut client = reqwest::Client::builder().redirect(redirect::policy(redirects.clone()));
+  let url = request.url();
+  let network_override = match &request.network {
+    Some(network) => network.host_override(&url)?,
+    None => None,
+  };
+  if let Some(host_override) = network_override.or_else(|| hosts::find(&url)) {
+    client = client.resolve(&host_override.host, host_override.socket_addr());
+  }
+  if let Some(network) = &request.network {
+    client = network.configure(client)?;
+  }
+  let client = client.build()?;
+  let method = reqwest::Method::from_bytes(request.method.as_bytes())?;
+  let mut builder = client.request(method, request.full_url());
+  let multipart = !request.form.is_empty();
+  for (key, value) in request.headers.iter() {
+    // the content type of a multipart body names the boundary of its parts
+    if !(multipart && key.eq_ignore_ascii_case("Content-Type")) {
+      builder = builder.header(key, value);
+    }
+  }
+  if let Some(authorization) = authorization {
+    builder = builder.header("Authorization", authorization);
+  }
+  if multipart {
+    let boundary = multipart::boundary();
+    builder = builder
+      .header("Content-Type", multipart::content_type(&boundary))
+      .body(multipart::encode(&request.form, &boundary)?);
+  } else if let Some(body) = &request.body {
+    builder = builder.body(body.clone());
+  }
+  let mut response = builder.send().await?;
+  let status = response.status();
+  let http_version = format!("{:?}", response.version());
+  let headers: Vec<(String, String)> = response
+    .headers()
+    .iter()
+    .map(|(key, value)| (key.to_string(), String::from_utf8_lossy(value.as_bytes()).to_string()))
+    .collect();
+  let mut spool = Spool::default();
+  while let Some(chunk) = response.chunk().await? {
+    spool.write(&chunk)?;
+  }
+  let (bytes, spooled) = spool.finish()?;
+  let content_type = headers.iter().find(|(key, _)| key.eq_ignore_ascii_case("Content-Type"));
+  let (body, decoded_as) = charset::decode(&bytes, content_type.and_then(|(_, value)| charset::of(value)));
+  let raw_body =
+    (spooled.is_none() && (decoded_as != "UTF-8" || std::str::from_utf8(&bytes).is_err())).then_some(bytes);
+  let redirects = std::mem::take(&mut *redirects.lock().unwrap());
+  Ok(HttpResponse {
+    status: status.as_u16(),
+    status_text: status.canonical_reason().unwrap_or_default().to_string(),
+    http_version,
+    headers,
+    body,
+    raw_body,
+    spooled,
+    redirects,
+    cache_hit: None,
+  })
+}
+
+/// Sends the request and records it as an exchange.
+pub async fn exchange(operation: Option<OperationKey>, request: HttpRequest) -> Result<Exchange> {
+  let started_at = chrono::Utc::now().to_rfc3339();
+  let start = Instant::now();
+  let response = send(&request).await?;
+  Ok(Exchange {
+    started_at,
+    elapsed_ms: start.elapsed().as_millis() as u64,
+    operation,
+    request,
+    response,
+    ..Exchange::default()
+  })
+}
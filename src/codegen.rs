@@ -0,0 +1,584 @@
+use std::collections::BTreeSet;
+
+use serde_json::Value;
+use strum::{Display, EnumIter};
+
+const SCHEMA_REF_PREFIX: &str = "#/components/schemas/";
+const INDENT: &str = "  ";
+const RUST_INDENT: &str = "    ";
+const RUST_DERIVE: &str = "#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]";
+const RUST_KEYWORDS: [&str; 38] = [
+  "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern", "false", "fn", "for",
+  "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "self", "Self", "static",
+  "struct", "super", "trait", "true", "type", "unsafe", "use", "where", "while",
+];
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Display, EnumIter)]
+pub enum Language {
+  #[default]
+  #[strum(to_string = "TypeScript")]
+  TypeScript,
+  #[strum(to_string = "Rust")]
+  Rust,
+}
+
+impl Language {
+  /// Declarations of the schema, named `name`, and of the component schemas it references.
+  pub fn render(&self, name: &str, schema: &Value, document: &Value) -> String {
+    match self {
+      Language::TypeScript => typescript(name, schema, document),
+      Language::Rust => rust(name, schema, document),
+    }
+  }
+
+  pub fn extension(&self) -> &'static str {
+    match self {
+      Language::TypeScript => "ts",
+      Language::Rust => "rs",
+    }
+  }
+}
+
+/// Definition of the component schema of the given name.
+fn component<'a>(document: &'a Value, name: &str) -> Option<&'a Value> {
+  document.pointer(&format!("/components/schemas/{}", name.replace('~', "~0").replace('/', "~1")))
+}
+
+/// TypeScript declarations of the schema, named `name`, followed by those of the component schemas it references,
+/// transitively.
+pub fn typescript(name: &str, schema: &Value, document: &Value) -> String {
+  let mut pending = vec![];
+  let mut declarations = vec![declaration(&type_name(name), schema, &mut pending)];
+  let mut declared = BTreeSet::from([type_name(name)]);
+  let mut index = 0;
+  while let Some(reference) = pending.get(index).cloned() {
+    index += 1;
+    if !declared.insert(type_name(&reference)) {
+      continue;
+    }
+    let definition = component(document, &reference).cloned().unwrap_or(Value::Null);
+    declarations.push(declaration(&type_name(&reference), &definition, &mut pending));
+  }
+  declarations.join("\n\n") + "\n"
+}
+
+/// An interface for plain objects, a type alias otherwise.
+fn declaration(name: &str, schema: &Value, pending: &mut Vec<String>) -> String {
+  let composed = ["allOf", "oneOf", "anyOf", "enum", "$ref"].iter().any(|keyword| schema.get(keyword).is_some());
+  let nullable = schema.get("nullable").and_then(Value::as_bool).unwrap_or_default();
+  let mut lines = vec![];
+  if let Some(description) = schema.get("description").and_then(Value::as_str) {
+    lines.push(comment(description, ""));
+  }
+  match schema.get("properties").is_some() && !composed && !nullable {
+    true => lines.push(format!("export interface {name} {}", object_type(schema, pending, 0))),
+    false => lines.push(format!("export type {name} = {};", ts_type(schema, pending, 0))),
+  }
+  lines.join("\n")
+}
+
+/// Type of the schema, referenced component schemas are added to `pending`.
+fn ts_type(schema: &Value, pending: &mut Vec<String>, depth: usize) -> String {
+  let kind = base_type(schema, pending, depth);
+  match schema.get("nullable").and_then(Value::as_bool) {
+    Some(true) if kind != "null" => format!("{kind} | null"),
+    _ => kind,
+  }
+}
+
+fn base_type(schema: &Value, pending: &mut Vec<String>, depth: usize) -> String {
+  if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+    let Some(name) = reference.strip_prefix(SCHEMA_REF_PREFIX) else {
+      return String::from("unknown");
+    };
+    pending.push(name.to_string());
+    return type_name(name);
+  }
+  if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+    return values.iter().map(Value::to_string).collect::<Vec<_>>().join(" | ");
+  }
+  if let Some(value) = schema.get("const") {
+    return value.to_string();
+  }
+  for (keyword, separator) in [("allOf", " & "), ("oneOf", " | "), ("anyOf", " | ")] {
+    if let Some(members) = schema.get(keyword).and_then(Value::as_array) {
+      let members: Vec<String> = members.iter().map(|member| group(ts_type(member, pending, depth))).collect();
+      return members.join(separator);
+    }
+  }
+  if let Some(kinds) = schema.get("type").and_then(Value::as_array) {
+    let kinds: Vec<String> = kinds
+      .iter()
+      .filter_map(Value::as_str)
+      .map(|kind| {
+        let mut schema = schema.clone();
+        schema["type"] = Value::from(kind);
+        base_type(&schema, pending, depth)
+      })
+      .collect();
+    return kinds.join(" | ");
+  }
+  match schema.get("type").and_then(Value::as_str) {
+    Some("string") => String::from("string"),
+    Some("integer") | Some("number") => String::from("number"),
+    Some("boolean") => String::from("boolean"),
+    Some("null") => String::from("null"),
+    Some("array") => {
+      let items = schema.get("items").map_or(String::from("unknown"), |items| ts_type(items, pending, depth));
+      match items.contains(' ') {
+        true => format!("Array<{items}>"),
+        false => format!("{items}[]"),
+      }
+    },
+    Some("object") => object_type(schema, pending, depth),
+    _ if schema.get("properties").is_some() || schema.get("additionalProperties").is_some() => {
+      object_type(schema, pending, depth)
+    },
+    _ => String::from("unknown"),
+  }
+}
+
+/// Object literal type, its properties are optional unless required.
+fn object_type(schema: &Value, pending: &mut Vec<String>, depth: usize) -> String {
+  let indent = INDENT.repeat(depth + 1);
+  let required: Vec<&str> = schema
+    .get("required")
+    .and_then(Value::as_array)
+    .map(|names| names.iter().filter_map(Value::as_str).collect())
+    .unwrap_or_default();
+  let mut lines = vec![];
+  for (name, property) in schema.get("properties").and_then(Value::as_object).into_iter().flatten() {
+    if let Some(description) = property.get("description").and_then(Value::as_str) {
+      lines.push(comment(description, &indent));
+    }
+    let optional = if required.contains(&name.as_str()) { "" } else { "?" };
+    let readonly = match property.get("readOnly").and_then(Value::as_bool) {
+      Some(true) => "readonly ",
+      _ => "",
+    };
+    lines.push(format!(
+      "{indent}{readonly}{}{optional}: {};",
+      property_name(name),
+      ts_type(property, pending, depth + 1)
+    ));
+  }
+  match schema.get("additionalProperties") {
+    Some(Value::Bool(true)) => lines.push(format!("{indent}[key: string]: unknown;")),
+    Some(additional @ Value::Object(_)) => {
+      lines.push(format!("{indent}[key: string]: {};", ts_type(additional, pending, depth + 1)))
+    },
+    _ => {},
+  }
+  if lines.is_empty() {
+    return String::from("Record<string, unknown>");
+  }
+  format!("{{\n{}\n{}}}", lines.join("\n"), INDENT.repeat(depth))
+}
+
+fn comment(description: &str, indent: &str) -> String {
+  match description.trim().lines().count() {
+    0 | 1 => format!("{indent}/** {} */", description.trim()),
+    _ => {
+      let lines: Vec<String> =
+        description.trim().lines().map(|line| format!("{indent} * {line}").trim_end().to_string()).collect();
+      format!("{indent}/**\n{}\n{indent} */", lines.join("\n"))
+    },
+  }
+}
+
+/// Wraps unions in parentheses, to intersect or unite them with other types.
+fn group(kind: String) -> String {
+  match kind.contains(" | ") {
+    true => format!("({kind})"),
+    false => kind,
+  }
+}
+
+/// PascalCase identifier, e.g. `pet-status` becomes `PetStatus`.
+fn type_name(name: &str) -> String {
+  let name: String = name
+    .split(|c: char| !c.is_ascii_alphanumeric())
+    .filter(|part| !part.is_empty())
+    .map(|part| {
+      let mut chars = part.chars();
+      chars.next().map(|first| first.to_ascii_uppercase().to_string() + chars.as_str()).unwrap_or_default()
+    })
+    .collect();
+  match name.chars().next() {
+    Some(first) if first.is_ascii_digit() => format!("_{name}"),
+    Some(_) => name,
+    None => String::from("Schema"),
+  }
+}
+
+/// Rust structs and enums of the schema, named `name`, followed by those of the component schemas it references,
+/// transitively, with the serde attributes that (de)serialize them.
+pub fn rust(name: &str, schema: &Value, document: &Value) -> String {
+  let mut generator = RustGenerator { declarations: vec![], declared: BTreeSet::new(), pending: vec![] };
+  generator.declare(&type_name(name), schema);
+  let mut index = 0;
+  while let Some(reference) = generator.pending.get(index).cloned() {
+    index += 1;
+    if generator.declared.contains(&type_name(&reference)) {
+      continue;
+    }
+    let definition = component(document, &reference).cloned().unwrap_or(Value::Null);
+    generator.declare(&type_name(&reference), &definition);
+  }
+  format!("use serde::{{Deserialize, Serialize}};\n\n{}\n", generator.declarations.join("\n\n"))
+}
+
+struct RustGenerator {
+  declarations: Vec<String>,
+  /// Names of the declared types, including inline schemas named after their parent.
+  declared: BTreeSet<String>,
+  /// Referenced component schemas, in the order they were met.
+  pending: Vec<String>,
+}
+
+impl RustGenerator {
+  fn declare(&mut self, name: &str, schema: &Value) {
+    if !self.declared.insert(name.to_string()) {
+      return;
+    }
+    // inline schemas are declared while this one is, after it
+    let slot = self.declarations.len();
+    self.declarations.push(String::new());
+    let mut lines = vec![];
+    if let Some(description) = schema.get("description").and_then(Value::as_str) {
+      lines.extend(doc_comment(description, ""));
+    }
+    let members = schema.get("oneOf").or_else(|| schema.get("anyOf")).and_then(Value::as_array);
+    if let Some(members) = members {
+      lines.extend(self.union(name, schema, members));
+    } else if schema
+      .get("enum")
+      .is_some_and(|values| values.as_array().is_some_and(|values| values.iter().all(Value::is_string)))
+    {
+      lines.extend(string_enum(name, schema));
+    } else if schema.get("allOf").is_some() || schema.get("properties").is_some() {
+      lines.extend(self.structure(name, schema));
+    } else {
+      let kind = self.rust_type(schema, &format!("{name}Value"));
+      lines.push(format!("pub type {name} = {kind};"));
+    }
+    self.declarations[slot] = lines.join("\n");
+  }
+
+  /// Struct of the properties, `allOf` references are flattened fields.
+  fn structure(&mut self, name: &str, schema: &Value) -> Vec<String> {
+    let mut fields = vec![];
+    let mut objects = vec![schema.clone()];
+    for member in schema.get("allOf").and_then(Value::as_array).into_iter().flatten() {
+      match member.get("$ref").and_then(Value::as_str).and_then(|reference| reference.strip_prefix(SCHEMA_REF_PREFIX)) {
+        Some(reference) => {
+          self.pending.push(reference.to_string());
+          fields.push(format!("{RUST_INDENT}#[serde(flatten)]"));
+          fields.push(format!("{RUST_INDENT}pub {}: {},", field_name(reference), type_name(reference)));
+        },
+        None => objects.push(member.clone()),
+      }
+    }
+    for object in objects.iter() {
+      let required: Vec<&str> = object
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|names| names.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+      for (property, definition) in object.get("properties").and_then(Value::as_object).into_iter().flatten() {
+        if let Some(description) = definition.get("description").and_then(Value::as_str) {
+          fields.extend(doc_comment(description, RUST_INDENT));
+        }
+        let field = field_name(property);
+        let mut kind = self.rust_type(definition, &format!("{name}{}", type_name(property)));
+        if kind == name {
+          kind = format!("Box<{kind}>");
+        }
+        let required = required.contains(&property.as_str());
+        let mut attributes = vec![];
+        if field.trim_start_matches("r#") != property {
+          attributes.push(format!("rename = {}", serde_json::to_string(property).unwrap_or_default()));
+        }
+        if !required {
+          attributes.push(String::from("default, skip_serializing_if = \"Option::is_none\""));
+        }
+        if !attributes.is_empty() {
+          fields.push(format!("{RUST_INDENT}#[serde({})]", attributes.join(", ")));
+        }
+        if !required || nullable(definition) {
+          kind = format!("Option<{kind}>");
+        }
+        fields.push(format!("{RUST_INDENT}pub {field}: {kind},"));
+      }
+    }
+    let mut lines = vec![RUST_DERIVE.to_string(), format!("pub struct {name} {{")];
+    lines.extend(fields);
+    lines.push(String::from("}"));
+    lines
+  }
+
+  /// Enum of the `oneOf`/`anyOf` branches, tagged by the discriminator property when there is one.
+  fn union(&mut self, name: &str, schema: &Value, members: &[Value]) -> Vec<String> {
+    let discriminator = schema.get("discriminator");
+    let property = discriminator.and_then(|discriminator| discriminator.get("propertyName")).and_then(Value::as_str);
+    let mapping = discriminator.and_then(|discriminator| discriminator.get("mapping")).and_then(Value::as_object);
+    let mut lines = vec![RUST_DERIVE.to_string()];
+    match property {
+      Some(property) => lines.push(format!("#[serde(tag = {})]", serde_json::to_string(property).unwrap_or_default())),
+      None => lines.push(String::from("#[serde(untagged)]")),
+    }
+    lines.push(format!("pub enum {name} {{"));
+    for (index, member) in members.iter().enumerate() {
+      if member.get("type").and_then(Value::as_str) == Some("null") {
+        continue;
+      }
+      let reference = member.get("$ref").and_then(Value::as_str);
+      let variant = match reference.and_then(|reference| reference.strip_prefix(SCHEMA_REF_PREFIX)) {
+        Some(reference) => type_name(reference),
+        None => format!("Variant{}", index + 1),
+      };
+      let kind = self.rust_type(member, &format!("{name}{variant}"));
+      if property.is_some() {
+        let value = mapping
+          .and_then(|mapping| mapping.iter().find(|(_, target)| target.as_str() == reference))
+          .map(|(value, _)| value.clone())
+          .or_else(|| reference.and_then(|reference| reference.strip_prefix(SCHEMA_REF_PREFIX)).map(String::from));
+        if let Some(value) = value.filter(|value| *value != variant) {
+          lines.push(format!("{RUST_INDENT}#[serde(rename = {})]", serde_json::to_string(&value).unwrap_or_default()));
+        }
+      }
+      lines.push(format!("{RUST_INDENT}{variant}({kind}),"));
+    }
+    lines.push(String::from("}"));
+    lines
+  }
+
+  /// Type of the schema, inline structs and enums are declared as `context`.
+  fn rust_type(&mut self, schema: &Value, context: &str) -> String {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+      let Some(name) = reference.strip_prefix(SCHEMA_REF_PREFIX) else {
+        return String::from("serde_json::Value");
+      };
+      self.pending.push(name.to_string());
+      return type_name(name);
+    }
+    let declared = ["oneOf", "anyOf", "allOf", "properties"].iter().any(|keyword| schema.get(keyword).is_some())
+      || schema.get("enum").and_then(Value::as_array).is_some_and(|values| values.iter().all(Value::is_string));
+    if declared {
+      self.declare(context, schema);
+      return context.to_string();
+    }
+    let kinds: Vec<&str> = match schema.get("type") {
+      Some(Value::Array(kinds)) => kinds.iter().filter_map(Value::as_str).filter(|kind| *kind != "null").collect(),
+      Some(Value::String(kind)) => vec![kind.as_str()],
+      _ => vec![],
+    };
+    let format = schema.get("format").and_then(Value::as_str);
+    match (kinds.as_slice(), format) {
+      (["string"], _) => String::from("String"),
+      (["integer"], Some("int32")) => String::from("i32"),
+      (["integer"], _) => String::from("i64"),
+      (["number"], Some("float")) => String::from("f32"),
+      (["number"], _) => String::from("f64"),
+      (["boolean"], _) => String::from("bool"),
+      (["array"], _) => {
+        let items = schema.get("items").map(|items| self.rust_type(items, &format!("{context}Item")));
+        format!("Vec<{}>", items.unwrap_or_else(|| String::from("serde_json::Value")))
+      },
+      (["object"], _) | ([], _) if schema.get("additionalProperties").is_some_and(Value::is_object) => {
+        let values = self.rust_type(&schema["additionalProperties"], &format!("{context}Value"));
+        format!("std::collections::BTreeMap<String, {values}>")
+      },
+      _ => String::from("serde_json::Value"),
+    }
+  }
+}
+
+/// Enum of the string values, renamed to the values they (de)serialize as.
+fn string_enum(name: &str, schema: &Value) -> Vec<String> {
+  let mut lines = vec![RUST_DERIVE.to_string(), format!("pub enum {name} {{")];
+  for value in schema.get("enum").and_then(Value::as_array).into_iter().flatten().filter_map(Value::as_str) {
+    let variant = match value.is_empty() {
+      true => String::from("Empty"),
+      false => type_name(value),
+    };
+    if variant != value {
+      lines.push(format!("{RUST_INDENT}#[serde(rename = {})]", serde_json::to_string(value).unwrap_or_default()));
+    }
+    lines.push(format!("{RUST_INDENT}{variant},"));
+  }
+  lines.push(String::from("}"));
+  lines
+}
+
+fn nullable(schema: &Value) -> bool {
+  schema.get("nullable").and_then(Value::as_bool).unwrap_or_default()
+    || schema.get("type").and_then(Value::as_array).is_some_and(|kinds| kinds.contains(&Value::from("null")))
+}
+
+fn doc_comment(description: &str, indent: &str) -> Vec<String> {
+  description.trim().lines().map(|line| format!("{indent}/// {line}").trim_end().to_string()).collect()
+}
+
+/// snake_case field name, e.g. `petId` becomes `pet_id`, keywords are raw identifiers.
+fn field_name(name: &str) -> String {
+  let mut field = String::new();
+  let mut previous_lowercase = false;
+  for c in name.chars() {
+    match c {
+      c if c.is_ascii_uppercase() => {
+        if previous_lowercase {
+          field.push('_');
+        }
+        field.push(c.to_ascii_lowercase());
+        previous_lowercase = false;
+      },
+      c if c.is_ascii_alphanumeric() => {
+        field.push(c);
+        previous_lowercase = c.is_ascii_lowercase() || c.is_ascii_digit();
+      },
+      _ => {
+        if !field.is_empty() && !field.ends_with('_') {
+          field.push('_');
+        }
+        previous_lowercase = false;
+      },
+    }
+  }
+  let field = field.trim_end_matches('_').to_string();
+  match field.chars().next() {
+    None => String::from("value"),
+    Some(first) if first.is_ascii_digit() => format!("_{field}"),
+    Some(_) if RUST_KEYWORDS.contains(&field.as_str()) => format!("r#{field}"),
+    Some(_) => field,
+  }
+}
+
+/// Property name, quoted when it is not a valid identifier.
+fn property_name(name: &str) -> String {
+  let identifier = name.chars().next().is_some_and(|first| first.is_ascii_alphabetic() || first == '_' || first == '$')
+    && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+  match identifier {
+    true => name.to_string(),
+    false => serde_json::to_string(name).unwrap_or_default(),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+  use serde_json::json;
+
+  use super::*;
+
+  #[test]
+  fn test_rust() {
+    let document = json!({
+      "components": { "schemas": {
+        "Pet": {
+          "description": "A pet of the store.",
+          "required": ["id", "type"],
+          "properties": {
+            "id": { "type": "integer", "format": "int32" },
+            "type": { "type": "string", "enum": ["cat", "in-stock"] },
+            "ownerId": { "type": ["string", "null"], "description": "Owner, if any." },
+            "parent": { "$ref": "#/components/schemas/Pet" }
+          }
+        },
+        "Animal": {
+          "oneOf": [{ "$ref": "#/components/schemas/Pet" }, { "$ref": "#/components/schemas/Wild" }],
+          "discriminator": { "propertyName": "kind", "mapping": { "pet": "#/components/schemas/Pet" } }
+        },
+        "Wild": { "allOf": [{ "$ref": "#/components/schemas/Pet" }, { "properties": { "tags": { "type": "array", "items": { "type": "string" } } } }] }
+      } }
+    });
+    let schema = json!({ "$ref": "#/components/schemas/Animal" });
+    assert_eq!(
+      rust("Animal", &document["components"]["schemas"]["Animal"], &document),
+      r#"use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum Animal {
+    #[serde(rename = "pet")]
+    Pet(Pet),
+    Wild(Wild),
+}
+
+/// A pet of the store.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Pet {
+    pub id: i32,
+    /// Owner, if any.
+    #[serde(rename = "ownerId", default, skip_serializing_if = "Option::is_none")]
+    pub owner_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<Box<Pet>>,
+    pub r#type: PetType,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PetType {
+    #[serde(rename = "cat")]
+    Cat,
+    #[serde(rename = "in-stock")]
+    InStock,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Wild {
+    #[serde(flatten)]
+    pub pet: Pet,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+}
+"#
+    );
+    assert!(rust("animal ref", &schema, &document).contains("pub type AnimalRef = Animal;"));
+  }
+
+  #[test]
+  fn test_typescript() {
+    let document = json!({
+      "components": { "schemas": {
+        "Pet": {
+          "description": "A pet of the store.",
+          "required": ["id", "name"],
+          "properties": {
+            "id": { "type": "integer", "readOnly": true },
+            "name": { "type": "string", "description": "Name given by the owner." },
+            "status": { "type": "string", "enum": ["available", "sold"] },
+            "owner": { "$ref": "#/components/schemas/Owner", "nullable": true },
+            "x-tags": { "type": "array", "items": { "type": ["string", "integer"] } }
+          }
+        },
+        "Owner": { "allOf": [{ "$ref": "#/components/schemas/Person" }, { "properties": { "pets": { "type": "integer" } } }] },
+        "Person": { "type": "object", "additionalProperties": { "type": "string" } }
+      } }
+    });
+    let schema = json!({ "type": "array", "items": { "$ref": "#/components/schemas/Pet" } });
+    assert_eq!(
+      typescript("pet list", &schema, &document),
+      r#"export type PetList = Pet[];
+
+/** A pet of the store. */
+export interface Pet {
+  readonly id: number;
+  /** Name given by the owner. */
+  name: string;
+  owner?: Owner | null;
+  status?: "available" | "sold";
+  "x-tags"?: Array<string | number>;
+}
+
+export type Owner = Person & {
+  pets?: number;
+};
+
+export type Person = {
+  [key: string]: string;
+};
+"#
+    );
+  }
+}
@@ -0,0 +1,92 @@
+use std::ffi::OsStr;
+
+use clap_complete::CompletionCandidate;
+use serde_json::Value;
+
+use crate::{diff, inventory, tags::TagNode};
+
+/// Spec read when the command line being completed names none.
+const DEFAULT_SPEC: &str = "openapi.json";
+
+/// Operation ids of the spec on the command line being completed, starting with the typed text.
+pub fn operations(current: &OsStr) -> Vec<CompletionCandidate> {
+  let Some(document) = document() else {
+    return vec![];
+  };
+  let current = current.to_string_lossy();
+  inventory::operations(&document, None, None)
+    .into_iter()
+    .filter_map(|operation| {
+      let id = operation.operation_id.filter(|id| id.starts_with(current.as_ref()))?;
+      Some(CompletionCandidate::new(id).help(operation.summary.map(Into::into)))
+    })
+    .collect()
+}
+
+/// Tags and groups of tags of the spec on the command line being completed, starting with the typed text.
+pub fn tags(current: &OsStr) -> Vec<CompletionCandidate> {
+  let Some(document) = document() else {
+    return vec![];
+  };
+  let current = current.to_string_lossy();
+  let mut candidates = vec![];
+  collect_tags(&crate::tags::tree(&document), current.as_ref(), &mut candidates);
+  candidates
+}
+
+fn collect_tags(nodes: &[TagNode], current: &str, candidates: &mut Vec<CompletionCandidate>) {
+  for node in nodes {
+    let name = node.name();
+    if name.starts_with(current) {
+      candidates.push(CompletionCandidate::new(name).help(node.description.clone().map(Into::into)));
+    }
+    collect_tags(&node.children, current, candidates);
+  }
+}
+
+/// The spec named on the command line being completed, which the shell passes after `--`.
+fn document() -> Option<Value> {
+  let words: Vec<String> = std::env::args().skip_while(|word| word != "--").skip(1).collect();
+  diff::load(&spec_path(&words)).ok()
+}
+
+/// Spec of the words typed: the spec argument of the subcommand, else the `--openapi-path` one, else the default.
+fn spec_path(words: &[String]) -> String {
+  let subcommand = words.iter().position(|word| ["bundle", "call", "list", "mock"].contains(&word.as_str()));
+  // `-o` of a subcommand names its output
+  let output = |index: usize| index > 0 && ["-o", "--output"].contains(&words[index - 1].as_str());
+  let argument = subcommand.and_then(|index| {
+    words.iter().enumerate().skip(index + 1).find_map(|(index, word)| {
+      let extension = word.rsplit_once('.').map(|(_, extension)| extension.to_lowercase()).unwrap_or_default();
+      (!word.starts_with('-') && !output(index) && ["json", "yaml", "yml"].contains(&extension.as_str()))
+        .then_some(word)
+    })
+  });
+  let options = &words[..subcommand.unwrap_or(words.len())];
+  let option = options.iter().enumerate().find_map(|(index, word)| {
+    match word.as_str() {
+      "-o" | "--openapi-path" => words.get(index + 1).cloned(),
+      word => word.strip_prefix("--openapi-path=").map(String::from),
+    }
+  });
+  argument.cloned().or(option).unwrap_or_else(|| String::from(DEFAULT_SPEC))
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_spec_path() {
+    let spec = |line: &str| spec_path(&line.split(' ').map(String::from).collect::<Vec<_>>());
+    assert_eq!(spec("openapi-tui call petstore.yaml add"), "petstore.yaml");
+    assert_eq!(spec("openapi-tui call -e staging specs/petstore.JSON -p id=1 "), "specs/petstore.JSON");
+    assert_eq!(spec("openapi-tui -o petstore.yml list --tag "), "petstore.yml");
+    assert_eq!(spec("openapi-tui --openapi-path=petstore.yaml list --tag "), "petstore.yaml");
+    assert_eq!(spec("openapi-tui list --tag "), DEFAULT_SPEC);
+    assert_eq!(spec("openapi-tui bundle -o out.yaml --tag "), DEFAULT_SPEC);
+    assert_eq!(spec("openapi-tui bundle -o out.yaml petstore.yaml --tag "), "petstore.yaml");
+  }
+}
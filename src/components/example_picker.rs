@@ -0,0 +1,73 @@
+use ratatui::{prelude::*, widgets::*};
+
+use crate::{markdown, mock::NamedExample};
+
+/// Named examples of a media type, one of them shown at a time with its summary and description.
+#[derive(Default)]
+pub struct ExamplePicker {
+  examples: Vec<NamedExample>,
+  index: usize,
+}
+
+impl ExamplePicker {
+  pub fn set(&mut self, examples: Vec<NamedExample>) {
+    self.examples = examples;
+    self.index = 0;
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.examples.is_empty()
+  }
+
+  pub fn selected(&self) -> Option<&NamedExample> {
+    self.examples.get(self.index)
+  }
+
+  pub fn next(&mut self) {
+    if !self.examples.is_empty() {
+      self.index = (self.index + 1) % self.examples.len();
+    }
+  }
+
+  pub fn previous(&mut self) {
+    if !self.examples.is_empty() {
+      self.index = (self.index + self.examples.len() - 1) % self.examples.len();
+    }
+  }
+
+  fn lines(&self) -> Vec<Line<'_>> {
+    let Some(example) = self.selected() else {
+      return vec![];
+    };
+    let mut title = vec![
+      Span::styled(format!("Example {}/{} ", self.index + 1, self.examples.len()), Style::default().dim()),
+      Span::styled(example.name.as_str(), Style::default().bold()),
+    ];
+    if let Some(summary) = &example.summary {
+      title.push(Span::raw(format!(" — {summary}")));
+    }
+    let mut lines = vec![Line::from(title)];
+    for line in example.description.as_deref().map(markdown::render).unwrap_or_default() {
+      lines.push(Line::from(
+        line.spans.into_iter().map(|(style, text)| Span::styled(text, style.dim())).collect::<Vec<_>>(),
+      ));
+    }
+    lines.extend(
+      example.text().lines().map(|line| Line::styled(line.to_string(), Style::default().fg(Color::LightGreen))),
+    );
+    lines
+  }
+
+  /// Height of the example, at most `max` lines along with the bottom border, none without examples.
+  pub fn height(&self, max: u16) -> u16 {
+    if self.examples.is_empty() {
+      return 0;
+    }
+    u16::try_from(self.lines().len()).unwrap_or(u16::MAX).saturating_add(1).min(max)
+  }
+
+  pub fn render_widget(&self, frame: &mut Frame<'_>, area: Rect) {
+    let block = Block::default().borders(Borders::BOTTOM).border_style(Style::default().dim());
+    frame.render_widget(Paragraph::new(self.lines()).block(block), area);
+  }
+}
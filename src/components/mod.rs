@@ -1 +1,2 @@
+pub mod example_picker;
 pub mod schema_viewer;
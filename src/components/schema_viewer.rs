@@ -6,17 +6,42 @@ use std::{
 use color_eyre::eyre::Result;
 use oas3::Schema;
 use ratatui::{prelude::*, widgets::*};
+use serde::Serialize;
+use serde_yaml::{Mapping, Value};
 use syntect::{easy::HighlightLines, highlighting::ThemeSet, parsing::SyntaxSet, util::LinesWithEndings};
 
-use crate::pages::home::State;
+use crate::{
+  action::Action,
+  extensions,
+  markdown::{self, MarkdownLine},
+  pages::home::State,
+  panes::{jumped_index, render_scrollbar},
+  theme,
+};
 
-const SYNTAX_THEME: &str = "Solarized (dark)";
+const SCHEMA_REF_PREFIX: &str = "#/components/schemas/";
+/// Nesting after which compositions are no longer expanded, which keeps recursive schemas finite.
+const MAX_DEPTH: usize = 8;
+/// Stands for a description in the YAML of the shown definition, which is rendered as Markdown instead.
+const DESCRIPTION_MARKER: &str = "__description_";
 
 #[derive(Default)]
 pub struct SchemaViewer {
   components: BTreeMap<String, Schema>,
   styles: Vec<Vec<(Style, String)>>,
+  /// Targets of the links shown on each line.
+  links: BTreeMap<usize, Vec<String>>,
   line_offset: usize,
+  /// Lines shown at once when last drawn, the lines a page moves by.
+  page_height: usize,
+  /// Description of the parameter, body or response shown above its schema.
+  description: Option<String>,
+  /// Shown definition, kept to render it again when another branch is selected.
+  shown: Value,
+  /// Expanded branch of the `oneOf` and `anyOf` compositions.
+  branch_index: usize,
+  /// Shows the values of the vendor extensions of the shown definition, listed above it, rather than only their count.
+  extensions_expanded: bool,
 
   name_history: Vec<String>,
   line_offset_history: Vec<usize>,
@@ -30,7 +55,13 @@ impl SchemaViewer {
     Self {
       components,
       styles: Vec::default(),
+      links: BTreeMap::default(),
       line_offset: 0,
+      page_height: 0,
+      description: None,
+      shown: Value::Null,
+      branch_index: 0,
+      extensions_expanded: false,
       name_history: Vec::default(),
       line_offset_history: Vec::default(),
       highlighter_syntax_set: SyntaxSet::load_defaults_newlines(),
@@ -44,10 +75,8 @@ impl SchemaViewer {
       state_reader
         .openapi_spec
         .components
-        .as_ref()
-        .unwrap()
-        .schemas
         .iter()
+        .flat_map(|components| components.schemas.iter())
         .filter_map(|(key, value)| value.resolve(&state_reader.openapi_spec).ok().map(|schema| (key.clone(), schema))),
     ))
   }
@@ -57,15 +86,47 @@ impl SchemaViewer {
     self.name_history = vec![];
     self.line_offset_history = vec![];
     self.styles = vec![];
+    self.links = BTreeMap::default();
+    self.shown = Value::Null;
+    self.description = None;
+  }
+
+  /// Sets the Markdown description shown above the schemas set next.
+  pub fn set_description(&mut self, description: Option<String>) {
+    self.description = description.filter(|description| !description.trim().is_empty());
   }
 
-  pub fn set(&mut self, schema: Schema) -> Result<()> {
+  /// Shows the schema, or any other definition of the spec whose schema references can be followed.
+  pub fn set(&mut self, schema: impl Serialize) -> Result<()> {
     self.line_offset = 0;
     self.name_history = vec![];
     self.line_offset_history = vec![];
+    self.branch_index = 0;
     self.set_styles(schema)
   }
 
+  pub fn extensions_expanded(&self) -> bool {
+    self.extensions_expanded
+  }
+
+  /// Expands or collapses the vendor extensions of the shown definition.
+  pub fn toggle_extensions(&mut self) -> Result<()> {
+    self.extensions_expanded = !self.extensions_expanded;
+    self.set_styles(self.shown.clone())
+  }
+
+  /// Expands the next branch of the `oneOf` and `anyOf` compositions.
+  pub fn next_branch(&mut self) -> Result<()> {
+    if self.shown.is_null() {
+      return Ok(());
+    }
+    self.branch_index = self.branch_index.saturating_add(1);
+    let line_offset = self.line_offset;
+    self.set_styles(self.shown.clone())?;
+    self.line_offset = line_offset.min(self.styles.len().saturating_sub(1));
+    Ok(())
+  }
+
   pub fn go(&mut self) -> Result<()> {
     if let Some(line_styles) = self.styles.get(self.line_offset) {
       let line: Vec<String> = line_styles
@@ -88,6 +149,7 @@ impl SchemaViewer {
 
       self.line_offset_history.push(self.line_offset);
       self.line_offset = 0;
+      self.branch_index = 0;
       self.name_history.push(schema_name.to_string());
 
       self.set_styles_by_name(schema_name.to_string())
@@ -96,7 +158,7 @@ impl SchemaViewer {
     }
   }
 
-  pub fn back(&mut self, schema: Schema) -> Result<()> {
+  pub fn back(&mut self, schema: impl Serialize) -> Result<()> {
     if let Some(line_offset) = self.line_offset_history.pop() {
       self.line_offset = line_offset;
     } else {
@@ -115,6 +177,21 @@ impl SchemaViewer {
     }
   }
 
+  /// Goes back to the schema at the depth of the trail, the root at 0, on the line it was left from.
+  pub fn back_to(&mut self, depth: usize, schema: impl Serialize) -> Result<()> {
+    if depth >= self.name_history.len() {
+      return Ok(());
+    }
+    self.line_offset_history.truncate(depth + 1);
+    self.line_offset = self.line_offset_history.pop().unwrap_or_default();
+    self.name_history.truncate(depth);
+    self.branch_index = 0;
+    match self.name_history.last().cloned() {
+      Some(schema_name) => self.set_styles_by_name(schema_name),
+      None => self.set_styles(schema),
+    }
+  }
+
   pub fn down(&mut self) {
     self.line_offset = self.line_offset.saturating_add(1).min(self.styles.len() - 1);
   }
@@ -123,11 +200,59 @@ impl SchemaViewer {
     self.line_offset = self.line_offset.saturating_sub(1);
   }
 
+  /// Moves a page down or up, or to the first or last line.
+  pub fn jump(&mut self, action: &Action) {
+    if let Some(line_offset) = jumped_index(action, self.line_offset, self.styles.len(), self.page_height) {
+      self.line_offset = line_offset;
+    }
+  }
+
+  /// Selected line and lines of the shown definition, empty when they all fit.
+  pub fn position_line(&self) -> Line<'static> {
+    match self.styles.len() > self.page_height {
+      true => Line::from(format!("{} of {}", self.line_offset + 1, self.styles.len())),
+      false => Line::default(),
+    }
+  }
+
+  /// Name and definition of the shown schema, the component gone to or else the root, named `root`.
+  pub fn shown(&self, root: &str) -> Option<(String, serde_json::Value)> {
+    if self.shown.is_null() {
+      return None;
+    }
+    let name = self.name_history.last().cloned().unwrap_or_else(|| root.to_string());
+    Some((name, serde_json::to_value(&self.shown).ok()?))
+  }
+
+  /// First link target of the selected line.
+  pub fn link(&self) -> Option<String> {
+    self.links.get(&self.line_offset).and_then(|links| links.first()).cloned()
+  }
+
   pub fn schema_path(&self) -> Vec<String> {
     self.name_history.clone()
   }
 
-  pub fn render_widget(&self, frame: &mut Frame<'_>, area: Rect) {
+  /// Trail from the root to the schema gone to, each level numbered as `BackTo` goes back to it, empty at the root.
+  pub fn trail(&self, root: &str) -> Line<'static> {
+    if self.name_history.is_empty() {
+      return Line::default();
+    }
+    let names = std::iter::once(root.to_string()).chain(self.name_history.iter().cloned());
+    let mut spans = vec![Span::raw("[ ")];
+    for (depth, name) in names.enumerate() {
+      if depth > 0 {
+        spans.push(Span::raw(" > "));
+      }
+      spans.push(Span::styled(format!("{}:", depth + 1), Style::default().fg(Color::LightCyan)));
+      spans.push(Span::raw(name));
+    }
+    spans.push(Span::raw(" ]"));
+    Line::from(spans)
+  }
+
+  pub fn render_widget(&mut self, frame: &mut Frame<'_>, area: Rect) {
+    self.page_height = usize::from(area.height);
     let lines = self.styles.iter().map(|items| {
       return Line::from(
         items
@@ -145,17 +270,89 @@ impl SchemaViewer {
       area,
       &mut list_state,
     );
+    render_scrollbar(frame, area, self.styles.len(), self.line_offset, self.page_height);
   }
 
-  fn set_styles(&mut self, schema: Schema) -> Result<()> {
+  fn set_styles(&mut self, schema: impl Serialize) -> Result<()> {
     self.styles = vec![];
-    let yaml_schema = serde_yaml::to_string(&schema)?;
+    self.links = BTreeMap::default();
+    self.shown = serde_yaml::to_value(schema)?;
+    if let Some(description) = self.description.clone().filter(|_| self.name_history.is_empty()) {
+      for line in markdown::render(&description) {
+        push_markdown_line(&mut self.styles, &mut self.links, line, " ");
+      }
+      self.styles.push(vec![]);
+    }
+    let components = |name: &str| self.components.get(name).and_then(|schema| serde_yaml::to_value(schema).ok());
+    let mut composed = compose(&self.shown, &components, self.branch_index, 0);
+    // the extensions of the shown definition are listed apart, those of its properties stay in place
+    if let Value::Mapping(mapping) = &mut composed {
+      let names: Vec<Value> =
+        mapping.keys().filter(|key| key.as_str().is_some_and(|key| key.starts_with("x-"))).cloned().collect();
+      let extensions: serde_json::Map<String, serde_json::Value> = names
+        .iter()
+        .filter_map(|name| {
+          let value = mapping.remove(name)?;
+          Some((name.as_str()?.to_string(), serde_json::to_value(value).ok()?))
+        })
+        .collect();
+      let lines = extensions::lines(&extensions::of(&serde_json::Value::Object(extensions)), self.extensions_expanded);
+      if !lines.is_empty() {
+        self.styles.extend(lines.into_iter().map(|mut line| {
+          line.insert(0, (Style::default(), String::from(" ")));
+          line
+        }));
+        self.styles.push(vec![]);
+      }
+    }
+    let mut descriptions = vec![];
+    let composed = mark_descriptions(composed, &mut descriptions);
+    let yaml_schema = serde_yaml::to_string(&composed)?;
     let mut highlighter = HighlightLines::new(
       self.highlighter_syntax_set.find_syntax_by_extension("yaml").expect("yaml syntax highlighter not found"),
-      &self.highlighter_theme_set.themes[SYNTAX_THEME],
+      &self.highlighter_theme_set.themes[theme::current().syntax.as_str()],
     );
     for (line_num, line) in LinesWithEndings::from(yaml_schema.as_str()).enumerate() {
-      let mut line_styles: Vec<(Style, String)> = highlighter
+      let description =
+        line.trim_end().split_once(&format!("description: {DESCRIPTION_MARKER}")).and_then(|(prefix, index)| {
+          Some((prefix, descriptions.get(index.strip_suffix("__")?.parse::<usize>().ok()?)?))
+        });
+      if let Some((prefix, description)) = description {
+        // the key is highlighted as it is in any other line, the description follows as Markdown
+        let key = format!("{prefix}description:\n");
+        let mut line_styles = self.highlight(&mut highlighter, &key)?;
+        line_styles.insert(0, (Style::default().dim(), format!(" {:<3} ", line_num + 1)));
+        let mut rendered = markdown::render(description).into_iter();
+        let indent = " ".repeat(prefix.chars().count() + 2);
+        match rendered.len() {
+          1 => {
+            let rendered = rendered.next().unwrap_or_default();
+            line_styles.push((Style::default(), String::from(" ")));
+            line_styles.extend(rendered.spans);
+            if !rendered.links.is_empty() {
+              self.links.insert(self.styles.len(), rendered.links);
+            }
+            self.styles.push(line_styles);
+          },
+          _ => {
+            self.styles.push(line_styles);
+            for rendered in rendered {
+              push_markdown_line(&mut self.styles, &mut self.links, rendered, &indent);
+            }
+          },
+        }
+        continue;
+      }
+      let mut line_styles = self.highlight(&mut highlighter, line)?;
+      line_styles.insert(0, (Style::default().dim(), format!(" {:<3} ", line_num + 1)));
+      self.styles.push(line_styles);
+    }
+    Ok(())
+  }
+
+  fn highlight(&self, highlighter: &mut HighlightLines<'_>, line: &str) -> Result<Vec<(Style, String)>> {
+    Ok(
+      highlighter
         .highlight_line(line, &self.highlighter_syntax_set)?
         .into_iter()
         .map(|segment| {
@@ -168,11 +365,8 @@ impl SchemaViewer {
             segment.1.to_string(),
           )
         })
-        .collect();
-      line_styles.insert(0, (Style::default().dim(), format!(" {:<3} ", line_num + 1)));
-      self.styles.push(line_styles);
-    }
-    Ok(())
+        .collect(),
+    )
   }
 
   fn set_styles_by_name(&mut self, schema_name: String) -> Result<()> {
@@ -180,3 +374,295 @@ impl SchemaViewer {
     self.set_styles(schema.clone())
   }
 }
+
+/// Adds a line of rendered Markdown, without a line number, indented under its key.
+fn push_markdown_line(
+  styles: &mut Vec<Vec<(Style, String)>>,
+  links: &mut BTreeMap<usize, Vec<String>>,
+  line: MarkdownLine,
+  indent: &str,
+) {
+  let mut line_styles = vec![(Style::default().dim(), format!(" {:<3} {indent}", ""))];
+  line_styles.extend(line.spans);
+  if !line.links.is_empty() {
+    links.insert(styles.len(), line.links);
+  }
+  styles.push(line_styles);
+}
+
+/// Replaces the descriptions of the definition with markers indexing them, to render them apart from the YAML.
+fn mark_descriptions(value: Value, descriptions: &mut Vec<String>) -> Value {
+  match value {
+    Value::Mapping(mapping) => {
+      Value::Mapping(
+        mapping
+          .into_iter()
+          .map(|(key, value)| {
+            match (key.as_str(), value) {
+              (Some("description"), Value::String(description)) => {
+                descriptions.push(description);
+                let marker = format!("{DESCRIPTION_MARKER}{}__", descriptions.len() - 1);
+                (key, Value::String(marker))
+              },
+              // examples are values, whatever properties they have
+              (Some("example" | "examples" | "default" | "enum" | "const"), value) => (key, value),
+              (_, value) => (key, mark_descriptions(value, descriptions)),
+            }
+          })
+          .collect(),
+      )
+    },
+    Value::Sequence(values) => {
+      Value::Sequence(values.into_iter().map(|value| mark_descriptions(value, descriptions)).collect())
+    },
+    value => value,
+  }
+}
+
+/// View of a schema with its composition spelled out: `allOf` members merged, the selected `oneOf`/`anyOf` branch
+/// expanded under its discriminator name, `null` types turned into `nullable` and required properties marked.
+fn compose(schema: &Value, components: &dyn Fn(&str) -> Option<Value>, branch: usize, depth: usize) -> Value {
+  let Value::Mapping(mapping) = schema else {
+    return schema.clone();
+  };
+  if depth > MAX_DEPTH {
+    return schema.clone();
+  }
+  let mut mapping = merge_all_of(mapping.clone(), components, depth);
+  if let Some(Value::Sequence(kinds)) = mapping.get("type") {
+    let kinds: Vec<Value> = kinds.iter().filter(|kind| kind.as_str() != Some("null")).cloned().collect();
+    let nullable = mapping.get("type").and_then(Value::as_sequence).is_some_and(|all| all.len() > kinds.len());
+    let kind = match kinds.len() {
+      1 => kinds[0].clone(),
+      _ => Value::Sequence(kinds),
+    };
+    mapping.insert("type".into(), kind);
+    if nullable {
+      mapping.insert("nullable".into(), Value::Bool(true));
+    }
+  }
+  for key in ["oneOf", "anyOf"] {
+    let Some(Value::Sequence(members)) = mapping.get(key).cloned() else {
+      continue;
+    };
+    let branches: Vec<Value> =
+      members.iter().filter(|member| member.get("type").and_then(Value::as_str) != Some("null")).cloned().collect();
+    if branches.len() < members.len() {
+      mapping.insert("nullable".into(), Value::Bool(true));
+      mapping.insert(key.into(), Value::Sequence(branches.clone()));
+    }
+    if branches.is_empty() {
+      continue;
+    }
+    let names = branch_names(&mut mapping, &branches);
+    let selected = branch % branches.len();
+    let title = format!("{key} branch {} of {} ({})", selected + 1, branches.len(), names[selected]);
+    let expanded = compose(&resolve(&branches[selected], components), components, branch, depth + 1);
+    mapping.insert(title.into(), expanded);
+  }
+  let required: Vec<String> = mapping
+    .get("required")
+    .and_then(Value::as_sequence)
+    .map(|names| names.iter().filter_map(Value::as_str).map(String::from).collect())
+    .unwrap_or_default();
+  if let Some(Value::Mapping(properties)) = mapping.get("properties") {
+    let properties = properties
+      .iter()
+      .map(|(name, property)| {
+        let property = compose(property, components, branch, depth + 1);
+        match name.as_str().filter(|name| required.iter().any(|required| required == name)) {
+          Some(name) => (Value::String(format!("{name} (required)")), property),
+          None => (name.clone(), property),
+        }
+      })
+      .collect();
+    mapping.insert("properties".into(), Value::Mapping(properties));
+  }
+  for key in ["items", "additionalProperties", "not"] {
+    if let Some(nested) = mapping.get(key).filter(|nested| nested.is_mapping()) {
+      let nested = compose(nested, components, branch, depth + 1);
+      mapping.insert(key.into(), nested);
+    }
+  }
+  Value::Mapping(mapping)
+}
+
+/// Merges the `allOf` members into the schema, keeping their references under `mergedFrom` to go to them.
+fn merge_all_of(mut mapping: Mapping, components: &dyn Fn(&str) -> Option<Value>, depth: usize) -> Mapping {
+  let Some(Value::Sequence(members)) = mapping.remove("allOf") else {
+    return mapping;
+  };
+  let mut merged = Mapping::new();
+  let references: Vec<Value> = members.iter().filter(|member| member.get("$ref").is_some()).cloned().collect();
+  for member in members.iter() {
+    if let Value::Mapping(member) = resolve(member, components) {
+      let member = match depth < MAX_DEPTH {
+        true => merge_all_of(member, components, depth + 1),
+        false => member,
+      };
+      merge(&mut merged, member);
+    }
+  }
+  merge(&mut merged, mapping);
+  if !references.is_empty() {
+    merged.insert("mergedFrom".into(), Value::Sequence(references));
+  }
+  merged
+}
+
+/// Adds the keywords of the source, joining properties and required properties.
+fn merge(target: &mut Mapping, source: Mapping) {
+  for (key, value) in source {
+    match (target.get_mut(&key), value) {
+      (Some(Value::Mapping(properties)), Value::Mapping(added)) if key.as_str() == Some("properties") => {
+        properties.extend(added);
+      },
+      (Some(Value::Sequence(required)), Value::Sequence(added)) if key.as_str() == Some("required") => {
+        required.extend(added.into_iter().filter(|name| !required.contains(name)).collect::<Vec<_>>());
+      },
+      (_, value) => {
+        target.insert(key, value);
+      },
+    }
+  }
+}
+
+/// Names of the branches: their discriminator values, else the names of the schemas they reference, else their
+/// titles. Discriminators without a mapping get the implicit one, by schema name.
+fn branch_names(mapping: &mut Mapping, branches: &[Value]) -> Vec<String> {
+  let references: Vec<Option<String>> =
+    branches.iter().map(|branch| branch.get("$ref").and_then(Value::as_str).map(String::from)).collect();
+  if let Some(Value::Mapping(discriminator)) = mapping.get_mut("discriminator") {
+    if !discriminator.contains_key("mapping") && references.iter().all(Option::is_some) {
+      let implicit = references
+        .iter()
+        .flatten()
+        .map(|reference| {
+          (Value::from(reference.trim_start_matches(SCHEMA_REF_PREFIX)), Value::from(reference.as_str()))
+        })
+        .collect();
+      discriminator.insert("mapping".into(), Value::Mapping(implicit));
+    }
+  }
+  let discriminated = mapping.get("discriminator").and_then(|discriminator| discriminator.get("mapping"));
+  branches
+    .iter()
+    .zip(references.iter())
+    .enumerate()
+    .map(|(index, (branch, reference))| {
+      let discriminator_value = discriminated.and_then(Value::as_mapping).and_then(|mapping| {
+        mapping.iter().find(|(_, target)| target.as_str() == reference.as_deref()).and_then(|(name, _)| name.as_str())
+      });
+      discriminator_value
+        .map(String::from)
+        .or_else(|| reference.as_ref().map(|reference| reference.trim_start_matches(SCHEMA_REF_PREFIX).to_string()))
+        .or_else(|| branch.get("title").and_then(Value::as_str).map(String::from))
+        .unwrap_or_else(|| format!("branch {}", index + 1))
+    })
+    .collect()
+}
+
+/// The component schema a `$ref` points to, other values as they are.
+fn resolve(value: &Value, components: &dyn Fn(&str) -> Option<Value>) -> Value {
+  value
+    .get("$ref")
+    .and_then(Value::as_str)
+    .and_then(|reference| reference.strip_prefix(SCHEMA_REF_PREFIX))
+    .and_then(components)
+    .unwrap_or_else(|| value.clone())
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_trail() -> Result<()> {
+    let components = [
+      ("Pet", "{type: object, properties: {tag: {$ref: '#/components/schemas/Tag'}}}"),
+      ("Tag", "{type: object, properties: {category: {$ref: '#/components/schemas/Category'}}}"),
+      ("Category", "{type: object, properties: {name: {type: string}}}"),
+    ];
+    let components = components.map(|(name, schema)| (name.to_string(), serde_yaml::from_str(schema).unwrap()));
+    let root: Value = serde_yaml::from_str("{type: object, properties: {pet: {$ref: '#/components/schemas/Pet'}}}")?;
+    let mut viewer = SchemaViewer::new(BTreeMap::from(components));
+    let go_to = |viewer: &mut SchemaViewer, name: &str| {
+      let target = format!("#/components/schemas/{name}");
+      viewer.line_offset = viewer.styles.iter().position(|line| line.iter().any(|(_, text)| *text == target)).unwrap();
+      viewer.go()
+    };
+    let trail = |viewer: &SchemaViewer| viewer.trail("body").to_string();
+    viewer.set(&root)?;
+    assert_eq!(trail(&viewer), "");
+    go_to(&mut viewer, "Pet")?;
+    let pet_line = viewer.line_offset_history[0];
+    go_to(&mut viewer, "Tag")?;
+    go_to(&mut viewer, "Category")?;
+    let category_line = viewer.line_offset_history[2];
+    assert_eq!(trail(&viewer), "[ 1:body > 2:Pet > 3:Tag > 4:Category ]");
+
+    // back to a level on the line it was left from, deeper levels dropped
+    viewer.back_to(2, &root)?;
+    assert_eq!((trail(&viewer), viewer.line_offset), (String::from("[ 1:body > 2:Pet > 3:Tag ]"), category_line));
+    viewer.back_to(2, &root)?;
+    assert_eq!(trail(&viewer), "[ 1:body > 2:Pet > 3:Tag ]");
+    go_to(&mut viewer, "Category")?;
+    viewer.back_to(0, &root)?;
+    assert_eq!((trail(&viewer), viewer.line_offset), (String::new(), pet_line));
+    assert_eq!(viewer.shown("body").map(|(name, _)| name), Some(String::from("body")));
+    Ok(())
+  }
+
+  #[test]
+  fn test_compose() {
+    let components = |name: &str| {
+      let schema = match name {
+        "Pet" => "{type: object, required: [name], properties: {name: {type: string}}}",
+        "Cat" => "{allOf: [{$ref: '#/components/schemas/Pet'}, {properties: {lives: {type: integer}}}]}",
+        "Dog" => "{title: Dog, properties: {bark: {type: [string, 'null']}}}",
+        _ => return None,
+      };
+      serde_yaml::from_str(schema).ok()
+    };
+    let schema: Value = serde_yaml::from_str(
+      "{oneOf: [{$ref: '#/components/schemas/Cat'}, {$ref: '#/components/schemas/Dog'}], discriminator: {propertyName: kind}}",
+    )
+    .unwrap();
+    let view = |branch| serde_yaml::to_string(&compose(&schema, &components, branch, 0)).unwrap();
+    assert_eq!(
+      view(0),
+      r"oneOf:
+- $ref: '#/components/schemas/Cat'
+- $ref: '#/components/schemas/Dog'
+discriminator:
+  propertyName: kind
+  mapping:
+    Cat: '#/components/schemas/Cat'
+    Dog: '#/components/schemas/Dog'
+oneOf branch 1 of 2 (Cat):
+  type: object
+  required:
+  - name
+  properties:
+    name (required):
+      type: string
+    lives:
+      type: integer
+  mergedFrom:
+  - $ref: '#/components/schemas/Pet'
+"
+    );
+    assert_eq!(
+      view(1).split_once("oneOf branch").unwrap().1,
+      r" 2 of 2 (Dog):
+  title: Dog
+  properties:
+    bark:
+      type: string
+      nullable: true
+"
+    );
+  }
+}
@@ -1,4 +1,7 @@
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+  collections::{BTreeMap, HashMap},
+  path::PathBuf,
+};
 
 use color_eyre::eyre::Result;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
@@ -6,7 +9,10 @@ use derive_deref::{Deref, DerefMut};
 use ratatui::style::{Color, Modifier, Style};
 use serde::{de::Deserializer, Deserialize};
 
-use crate::{action::Action, app::Mode};
+use crate::{
+  action::Action, app::Mode, health::HealthConfig, keymap::Keymap, network::Network, redaction::RedactionConfig,
+  registry::RegistryKind, signing::Signing,
+};
 
 const CONFIG: &str = include_str!("../.config/config.json5");
 
@@ -24,8 +30,116 @@ pub struct Config {
   pub config: AppConfig,
   #[serde(default)]
   pub keybindings: KeyBindings,
+  /// Bindings of the config files alone, applied again over the defaults as the keymap changes.
+  #[serde(skip)]
+  user_keybindings: KeyBindings,
+  /// Profile of the navigation keys, `vim`, `emacs` or `arrows`, see `keymap::Keymap`.
+  #[serde(default)]
+  pub keymap: Keymap,
   #[serde(default)]
   pub styles: Styles,
+  /// Built-in theme, `dark`, `light` or `mono`, or TOML theme file relative to the config directory, see
+  /// `theme::Theme`.
+  #[serde(default)]
+  pub theme: Option<String>,
+  /// Whether the `mono` theme is used whatever the `theme`, like `--no-color`.
+  #[serde(default)]
+  pub no_color: bool,
+  /// Named sets of variables, e.g. `{ dev: { baseUrl: "http://localhost:8080" } }`.
+  #[serde(default)]
+  pub environments: BTreeMap<String, EnvironmentConfig>,
+  /// WebAssembly plugins by name, see `plugin::Plugin`.
+  #[serde(default)]
+  pub plugins: BTreeMap<String, PluginConfig>,
+  /// Registries of specs by name, browsed to open one of their APIs, see `registry`.
+  #[serde(default)]
+  pub registries: BTreeMap<String, RegistryConfig>,
+  /// Arrangements of the home panes, the first one used until another is chosen, see `layout`.
+  #[serde(default)]
+  pub layouts: Vec<LayoutConfig>,
+  /// Addresses requests to a host and port are sent to, as `host:port:address`, after those of `--resolve`.
+  #[serde(default)]
+  pub resolve: Vec<String>,
+  /// Redirects followed by a request, unless `--max-redirects` is given, see `redirect::DEFAULT_MAX_REDIRECTS`.
+  #[serde(default)]
+  pub max_redirects: Option<usize>,
+  /// Whether quitting with unsaved edits or requests in flight asks first, true when unset.
+  #[serde(default)]
+  pub confirm_quit: Option<bool>,
+  /// Whether the mouse is captured to click and scroll the panes, true when unset. Off leaves the mouse to the
+  /// terminal, e.g. to select text.
+  #[serde(default)]
+  pub mouse: Option<bool>,
+  /// Headers, fields and patterns redacted from the history, exports, logs and snippets.
+  #[serde(default)]
+  pub redaction: RedactionConfig,
+  /// Whether repeated GETs are answered from a client-side cache honoring `Cache-Control`, see `cache::Cache`.
+  #[serde(default)]
+  pub cache: bool,
+  /// Whether the timestamps of response bodies are annotated with the local time, see `timestamps`.
+  #[serde(default)]
+  pub local_times: bool,
+  /// Probing of the base url of the selected server in the background, off when unset, see `health::HealthConfig`.
+  #[serde(default)]
+  pub health: Option<HealthConfig>,
+}
+
+/// Variables of an environment, along with the headers sent with every request under the `headers` key.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct EnvironmentConfig {
+  #[serde(default)]
+  pub headers: BTreeMap<String, String>,
+  #[serde(default)]
+  pub signing: Option<Signing>,
+  #[serde(default)]
+  pub network: Option<Network>,
+  #[serde(flatten)]
+  pub variables: BTreeMap<String, String>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct PluginConfig {
+  /// `.wasm` or `.wat` module, relative to the config directory.
+  pub path: PathBuf,
+  /// Security schemes whose requests are signed by the `sign` export.
+  #[serde(default)]
+  pub auth_schemes: Vec<String>,
+  /// Response content types, or their prefixes, rendered by the `render` export.
+  #[serde(default)]
+  pub content_types: Vec<String>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct RegistryConfig {
+  /// `index`, `swaggerhub` or `apicurio`, see `registry::RegistryKind`.
+  #[serde(default)]
+  pub kind: RegistryKind,
+  /// Url of the index, of the SwaggerHub listing of an owner or of the Apicurio Registry.
+  pub url: String,
+}
+
+/// Groups of panes side by side in columns, or stacked in rows when `vertical`. Panes left out are hidden.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+pub struct LayoutConfig {
+  pub name: String,
+  #[serde(default)]
+  pub vertical: bool,
+  pub groups: Vec<PaneGroupConfig>,
+}
+
+/// Panes stacked in a column, or side by side in a row, named as their key scopes.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+pub struct PaneGroupConfig {
+  /// Share of the screen of the group, relative to the other groups.
+  #[serde(default = "PaneGroupConfig::default_size")]
+  pub size: u16,
+  pub panes: Vec<KeyScope>,
+}
+
+impl PaneGroupConfig {
+  fn default_size() -> u16 {
+    1
+  }
 }
 
 impl Config {
@@ -57,11 +171,10 @@ impl Config {
 
     let mut cfg: Self = builder.build()?.try_deserialize()?;
 
-    for (mode, default_bindings) in default_config.keybindings.iter() {
-      let user_bindings = cfg.keybindings.entry(*mode).or_default();
-      for (key, cmd) in default_bindings.iter() {
-        user_bindings.entry(key.clone()).or_insert_with(|| cmd.clone());
-      }
+    cfg.user_keybindings = std::mem::take(&mut cfg.keybindings);
+    cfg.set_keymap(cfg.keymap);
+    if cfg.layouts.is_empty() {
+      cfg.layouts = default_config.layouts;
     }
     for (mode, default_styles) in default_config.styles.iter() {
       let user_styles = cfg.styles.entry(*mode).or_default();
@@ -72,35 +185,144 @@ impl Config {
 
     Ok(cfg)
   }
+
+  /// Switches to the keymap profile, its bindings over the default ones and those of the config files over both.
+  pub fn set_keymap(&mut self, keymap: Keymap) {
+    let default_config: Config = json5::from_str(CONFIG).unwrap();
+    let mut keybindings = default_config.keybindings;
+    keymap.apply(&mut keybindings);
+    for (scope, user_bindings) in self.user_keybindings.iter() {
+      keybindings.entry(*scope).or_default().extend(user_bindings.clone());
+    }
+    self.keybindings = keybindings;
+    self.keymap = keymap;
+  }
+}
+
+/// Where key bindings apply: the whole page, or a pane while it is focused, before the bindings of the page.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Deserialize)]
+pub enum KeyScope {
+  Home,
+  Apis,
+  Tags,
+  Collections,
+  History,
+  Components,
+  Address,
+  Request,
+  Response,
+}
+
+impl KeyScope {
+  /// Scopes of the panes of the home page, in the order of the panes.
+  pub const PANES: [KeyScope; 8] = [
+    KeyScope::Apis,
+    KeyScope::Tags,
+    KeyScope::Collections,
+    KeyScope::History,
+    KeyScope::Components,
+    KeyScope::Address,
+    KeyScope::Request,
+    KeyScope::Response,
+  ];
+}
+
+impl From<Mode> for KeyScope {
+  fn from(mode: Mode) -> Self {
+    match mode {
+      Mode::Home => KeyScope::Home,
+    }
+  }
 }
 
 #[derive(Clone, Debug, Default, Deref, DerefMut)]
-pub struct KeyBindings(pub HashMap<Mode, HashMap<Vec<KeyEvent>, Action>>);
+pub struct KeyBindings(pub HashMap<KeyScope, HashMap<Vec<KeyEvent>, Action>>);
+
+impl KeyBindings {
+  /// Action bound to the key in the scope.
+  pub fn action(&self, scope: KeyScope, key: KeyEvent) -> Option<&Action> {
+    self.sequence_action(scope, &[key])
+  }
+
+  /// Action bound to the key sequence in the scope, e.g. `gg`.
+  pub fn sequence_action(&self, scope: KeyScope, keys: &[KeyEvent]) -> Option<&Action> {
+    self.get(&scope)?.get(&keys.iter().copied().map(normalize_key_event).collect::<Vec<_>>())
+  }
+
+  /// Whether a longer key sequence bound in the scope starts with the keys, e.g. `gg` with `g`.
+  pub fn starts_sequence(&self, scope: KeyScope, keys: &[KeyEvent]) -> bool {
+    let keys: Vec<KeyEvent> = keys.iter().copied().map(normalize_key_event).collect();
+    self.get(&scope).is_some_and(|bindings| {
+      bindings.keys().any(|sequence| sequence.len() > keys.len() && sequence.starts_with(&keys))
+    })
+  }
+
+  /// Shortest key sequence bound to the action in the scope, e.g. `ctrl-e`, `None` when it is not bound.
+  pub fn key_of(&self, scope: KeyScope, action: &Action) -> Option<String> {
+    let keys = self.get(&scope)?.iter().filter(|(_, bound)| *bound == action);
+    let keys = keys.map(|(keys, _)| keys.iter().map(key_event_to_string).collect::<Vec<_>>().join(" "));
+    keys.min_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)))
+  }
+
+  /// Actions bound in the scope, with their key sequences shortest first.
+  pub fn bindings(&self, scope: KeyScope) -> Vec<(Action, Vec<String>)> {
+    let mut bindings: Vec<(Action, Vec<String>)> = vec![];
+    for (keys, action) in self.get(&scope).into_iter().flatten() {
+      let keys = keys.iter().map(key_event_to_string).collect::<Vec<_>>().join(" ");
+      match bindings.iter_mut().find(|(bound, _)| bound == action) {
+        Some((_, bound_keys)) => bound_keys.push(keys),
+        None => bindings.push((action.clone(), vec![keys])),
+      }
+    }
+    for (_, keys) in bindings.iter_mut() {
+      keys.sort_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+    }
+    bindings
+  }
+}
 
 impl<'de> Deserialize<'de> for KeyBindings {
   fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
   where
     D: Deserializer<'de>,
   {
-    let parsed_map = HashMap::<Mode, HashMap<String, Action>>::deserialize(deserializer)?;
-
-    let keybindings = parsed_map
-      .into_iter()
-      .map(|(mode, inner_map)| {
-        let converted_inner_map =
-          inner_map.into_iter().map(|(key_str, cmd)| (parse_key_sequence(&key_str).unwrap(), cmd)).collect();
-        (mode, converted_inner_map)
-      })
-      .collect();
+    let parsed_map = HashMap::<KeyScope, HashMap<String, Action>>::deserialize(deserializer)?;
+
+    let mut keybindings = HashMap::new();
+    for (scope, inner_map) in parsed_map {
+      let mut converted_inner_map = HashMap::new();
+      for (key_str, cmd) in inner_map {
+        let keys = parse_key_sequence(&key_str)
+          .map_err(|error| serde::de::Error::custom(format!("{error} in the {scope:?} keybindings")))?;
+        converted_inner_map.insert(keys, cmd);
+      }
+      keybindings.insert(scope, converted_inner_map);
+    }
 
     Ok(KeyBindings(keybindings))
   }
 }
 
-fn parse_key_event(raw: &str) -> Result<KeyEvent, String> {
+/// Key event without the `shift` of characters, which their case already tells, so that `<E>` and `<shift-e>` both
+/// match `E` whether or not the terminal reports `shift`.
+pub fn normalize_key_event(key: KeyEvent) -> KeyEvent {
+  match key.code {
+    KeyCode::Char(c) if key.modifiers.contains(KeyModifiers::SHIFT) => {
+      KeyEvent::new(KeyCode::Char(c.to_ascii_uppercase()), key.modifiers - KeyModifiers::SHIFT)
+    },
+    code => KeyEvent::new(code, key.modifiers),
+  }
+}
+
+pub fn parse_key_event(raw: &str) -> Result<KeyEvent, String> {
   let raw_lower = raw.to_ascii_lowercase();
   let (remaining, modifiers) = extract_modifiers(&raw_lower);
-  parse_key_code_with_modifiers(remaining, modifiers)
+  // characters without modifiers keep their case, e.g. `<E>`
+  let remaining = match remaining.len() == 1 && modifiers.is_empty() {
+    true => &raw[raw.len() - 1..],
+    false => remaining,
+  };
+  parse_key_code_with_modifiers(remaining, modifiers).map(normalize_key_event)
 }
 
 fn extract_modifiers(raw: &str) -> (&str, KeyModifiers) {
@@ -387,6 +609,27 @@ mod tests {
 
   use super::*;
 
+  #[test]
+  fn test_environments() {
+    let config: Config = json5::from_str(
+      r#"{ "environments": { "dev": { "baseUrl": "http://localhost:8080", "headers": { "X-Tenant-Id": "acme" } } } }"#,
+    )
+    .unwrap();
+    assert_eq!(config.environments["dev"].variables["baseUrl"], "http://localhost:8080");
+    assert_eq!(config.environments["dev"].headers["X-Tenant-Id"], "acme");
+    assert!(!config.environments["dev"].variables.contains_key("headers"));
+  }
+
+  #[test]
+  fn test_plugins() {
+    let config: Config =
+      json5::from_str(r#"{ "plugins": { "signer": { "path": "signer.wasm", "auth_schemes": ["orgSignature"] } } }"#)
+        .unwrap();
+    assert_eq!(config.plugins["signer"].path, PathBuf::from("signer.wasm"));
+    assert_eq!(config.plugins["signer"].auth_schemes, vec![String::from("orgSignature")]);
+    assert!(config.plugins["signer"].content_types.is_empty());
+  }
+
   #[test]
   fn test_parse_style_default() {
     let style = parse_style("");
@@ -438,12 +681,43 @@ mod tests {
   fn test_config() -> Result<()> {
     let c = Config::new()?;
     assert_eq!(
-      c.keybindings.get(&Mode::Home).unwrap().get(&parse_key_sequence("<q>").unwrap_or_default()).unwrap(),
+      c.keybindings.get(&KeyScope::Home).unwrap().get(&parse_key_sequence("<q>").unwrap_or_default()).unwrap(),
       &Action::Quit
     );
     Ok(())
   }
 
+  #[test]
+  fn test_keybindings() {
+    let config: Config = json5::from_str(
+      r#"{ "keybindings": {
+        "Home": { "<ctrl-n>": "Down", "<E>": "ExportHttpCollection", "<3>": { "Tab": 2 } },
+        "Address": { "<o>": "SendRequest" }
+      } }"#,
+    )
+    .unwrap();
+    let bindings = &config.keybindings;
+    let key = |code, modifiers| KeyEvent::new(code, modifiers);
+    assert_eq!(bindings.action(KeyScope::Home, key(KeyCode::Char('n'), KeyModifiers::CONTROL)), Some(&Action::Down));
+    assert_eq!(
+      bindings.action(KeyScope::Home, key(KeyCode::Char('E'), KeyModifiers::SHIFT)),
+      Some(&Action::ExportHttpCollection)
+    );
+    assert_eq!(bindings.action(KeyScope::Home, key(KeyCode::Char('e'), KeyModifiers::NONE)), None);
+    assert_eq!(bindings.action(KeyScope::Home, key(KeyCode::Char('3'), KeyModifiers::NONE)), Some(&Action::Tab(2)));
+    assert_eq!(
+      bindings.action(KeyScope::Address, key(KeyCode::Char('o'), KeyModifiers::NONE)),
+      Some(&Action::SendRequest)
+    );
+    assert_eq!(bindings.key_of(KeyScope::Home, &Action::Down).as_deref(), Some("ctrl-n"));
+    assert_eq!(bindings.bindings(KeyScope::Address), vec![(Action::SendRequest, vec![String::from("o")])]);
+
+    let invalid = |config: &str| json5::from_str::<Config>(config).is_err();
+    assert!(invalid(r#"{ "keybindings": { "Home": { "<ctrl-unknown>": "Down" } } }"#));
+    assert!(invalid(r#"{ "keybindings": { "Home": { "<x>": "NoSuchAction" } } }"#));
+    assert!(invalid(r#"{ "keybindings": { "Sidebar": { "<x>": "Down" } } }"#));
+  }
+
   #[test]
   fn test_simple_keys() {
     assert_eq!(parse_key_event("a").unwrap(), KeyEvent::new(KeyCode::Char('a'), KeyModifiers::empty()));
@@ -0,0 +1,88 @@
+use std::{
+  collections::VecDeque,
+  path::PathBuf,
+  sync::{Mutex, RwLock},
+};
+
+use color_eyre::eyre::Result;
+use lazy_static::lazy_static;
+
+use crate::{action::Action, utils};
+
+/// Actions kept to be written in a crash report, the oldest forgotten past it.
+const LIMIT: usize = 50;
+
+lazy_static! {
+  static ref LAST_ACTIONS: Mutex<VecDeque<&'static str>> = Mutex::new(VecDeque::new());
+  static ref SPEC_PATH: RwLock<Option<String>> = RwLock::new(None);
+}
+
+/// Records the name of the action as one of the last ones handled, leaving out what it carries since requests and
+/// responses hold credentials, and crash reports are meant to be attached to public bug reports.
+pub fn record(action: &Action) {
+  let mut actions = LAST_ACTIONS.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+  actions.push_back(action.name());
+  if actions.len() > LIMIT {
+    actions.pop_front();
+  }
+}
+
+pub fn set_spec_path(path: &str) {
+  *SPEC_PATH.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(path.to_string());
+}
+
+/// Crash report of the error, with the spec in use and the last actions handled before it.
+pub fn report(error: &str, backtrace: &str) -> String {
+  let spec_path = SPEC_PATH.read().unwrap_or_else(|poisoned| poisoned.into_inner()).clone();
+  let actions = LAST_ACTIONS.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+  let mut report = vec![
+    format!("{} {}", env!("CARGO_PKG_NAME"), utils::version().lines().next().unwrap_or_default()),
+    format!("Spec: {}", spec_path.as_deref().unwrap_or("none")),
+    String::default(),
+    String::from("Error:"),
+    error.trim_end().to_string(),
+    String::default(),
+    format!("Last actions, oldest first ({}):", actions.len()),
+  ];
+  report.extend(actions.iter().map(|action| format!("  {action}")));
+  report.extend([String::default(), String::from("Backtrace:"), backtrace.trim_end().to_string()]);
+  report.join("\n") + "\n"
+}
+
+/// Writes the crash report of the error under `crash-reports` in the config directory, returning its path.
+pub fn write(error: &str) -> Result<PathBuf> {
+  let backtrace = std::backtrace::Backtrace::force_capture();
+  let directory = utils::get_config_dir().join("crash-reports");
+  std::fs::create_dir_all(&directory)?;
+  let path = directory.join(format!("crash-{}.txt", chrono::Local::now().format("%Y%m%d-%H%M%S")));
+  std::fs::write(&path, report(error, &backtrace.to_string()))?;
+  Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+  use crate::request::HttpRequest;
+
+  #[test]
+  fn test_report() {
+    set_spec_path("petstore.yaml");
+    for index in 0..=LIMIT {
+      record(&Action::Tab(index as u32));
+    }
+    let request = HttpRequest {
+      headers: vec![(String::from("Authorization"), String::from("Bearer s3cr3t-token"))],
+      ..HttpRequest::default()
+    };
+    record(&Action::SendHttpRequest(Box::new(request), None));
+    let report = report("attempt to subtract with overflow\n", "0: main");
+    assert!(report.contains("\nSpec: petstore.yaml\n"));
+    assert!(report.contains("\nError:\nattempt to subtract with overflow\n\n"));
+    assert!(report.contains("  Tab\n"));
+    assert!(!report.contains("s3cr3t-token"));
+    assert!(report.ends_with("  SendHttpRequest\n\nBacktrace:\n0: main\n"));
+    assert_eq!(LAST_ACTIONS.lock().unwrap().len(), LIMIT);
+  }
+}
@@ -0,0 +1,392 @@
+use std::collections::BTreeMap;
+
+use color_eyre::eyre::Result;
+use serde_json::Value;
+
+use crate::{
+  mock::{kind, resolve, METHODS},
+  workspace::OperationKey,
+};
+
+/// Nesting after which schemas are no longer compared, which keeps recursive schemas finite.
+const MAX_DEPTH: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+  Added,
+  Removed,
+  Changed,
+}
+
+/// Difference between two versions of a spec.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+  pub kind: ChangeKind,
+  /// Clients written against the old version may fail against the new one.
+  pub breaking: bool,
+  /// Operation and the part of it that changed, e.g. `GET /pets response 200 $.items[].status`.
+  pub location: String,
+  pub message: String,
+}
+
+impl Change {
+  fn new(kind: ChangeKind, breaking: bool, location: impl Into<String>, message: impl Into<String>) -> Self {
+    Self { kind, breaking, location: location.into(), message: message.into() }
+  }
+}
+
+impl std::fmt::Display for Change {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let symbol = match self.kind {
+      ChangeKind::Added => '+',
+      ChangeKind::Removed => '-',
+      ChangeKind::Changed => '~',
+    };
+    write!(f, "{symbol} {}: {}", self.location, self.message)
+  }
+}
+
+/// Whether a schema describes what clients send or what they receive, which decides what breaks them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+  Request,
+  Response,
+}
+
+/// Both documents, to resolve the references of each side.
+struct Documents<'a> {
+  old: &'a Value,
+  new: &'a Value,
+}
+
+/// Reads a JSON or YAML spec.
+pub fn load(path: &str) -> Result<Value> {
+  Ok(serde_yaml::from_reader(std::fs::File::open(path)?)?)
+}
+
+/// Changes from the old spec to the new one, breaking changes first.
+pub fn diff(old: &Value, new: &Value) -> Vec<Change> {
+  let documents = Documents { old, new };
+  let old_operations = operations(old);
+  let new_operations = operations(new);
+  let mut changes = vec![];
+  for (key, old_operation) in old_operations.iter() {
+    match new_operations.get(key) {
+      Some(new_operation) => operation_changes(key, *old_operation, *new_operation, &documents, &mut changes),
+      None => changes.push(Change::new(ChangeKind::Removed, true, key.to_string(), "operation removed")),
+    }
+  }
+  for key in new_operations.keys().filter(|key| !old_operations.contains_key(key)) {
+    changes.push(Change::new(ChangeKind::Added, false, key.to_string(), "operation added"));
+  }
+  let schemas =
+    |document: &Value| document.pointer("/components/schemas").and_then(Value::as_object).cloned().unwrap_or_default();
+  let (old_schemas, new_schemas) = (schemas(old), schemas(new));
+  for name in old_schemas.keys().filter(|name| !new_schemas.contains_key(*name)) {
+    changes.push(Change::new(ChangeKind::Removed, false, format!("schema {name}"), "component schema removed"));
+  }
+  for name in new_schemas.keys().filter(|name| !old_schemas.contains_key(*name)) {
+    changes.push(Change::new(ChangeKind::Added, false, format!("schema {name}"), "component schema added"));
+  }
+  changes.sort_by_key(|change| !change.breaking);
+  changes
+}
+
+/// Counts followed by the breaking changes and then the others, as printed by `openapi-tui diff`.
+pub fn summary(changes: &[Change]) -> Vec<String> {
+  let breaking = changes.iter().filter(|change| change.breaking).count();
+  let mut lines = vec![format!("{} changes, {breaking} breaking", changes.len())];
+  for (title, breaking) in [("Breaking changes", true), ("Other changes", false)] {
+    let listed: Vec<String> =
+      changes.iter().filter(|change| change.breaking == breaking).map(|change| format!("  {change}")).collect();
+    if !listed.is_empty() {
+      lines.push(format!("{title}:"));
+      lines.extend(listed);
+    }
+  }
+  lines
+}
+
+/// Path items and operations by method and path.
+fn operations(document: &Value) -> BTreeMap<OperationKey, (&Value, &Value)> {
+  let paths = document.get("paths").and_then(Value::as_object).into_iter().flatten();
+  paths
+    .flat_map(|(path, item)| {
+      METHODS.iter().filter_map(move |method| {
+        item.get(*method).map(|operation| (OperationKey::new(method, path), (item, operation)))
+      })
+    })
+    .collect()
+}
+
+/// Parameters of the path item and the operation, by location and name.
+fn parameters<'a>(item: &'a Value, operation: &'a Value, document: &'a Value) -> BTreeMap<(String, String), &'a Value> {
+  [item, operation]
+    .into_iter()
+    .filter_map(|value| value.get("parameters").and_then(Value::as_array))
+    .flatten()
+    .map(|parameter| resolve(parameter, document))
+    .filter_map(|parameter| {
+      let location = parameter.get("in").and_then(Value::as_str)?;
+      let name = parameter.get("name").and_then(Value::as_str)?;
+      Some(((location.to_string(), name.to_string()), parameter))
+    })
+    .collect()
+}
+
+fn required(value: &Value) -> bool {
+  value.get("required").and_then(Value::as_bool).unwrap_or_default()
+}
+
+fn request_body<'a>(operation: &'a Value, document: &'a Value) -> Option<&'a Value> {
+  operation.get("requestBody").map(|body| resolve(body, document))
+}
+
+/// Schema of the JSON content, or else of the first content type.
+fn content_schema<'a>(value: &'a Value, document: &'a Value) -> Option<&'a Value> {
+  let content = resolve(value, document).get("content")?.as_object()?;
+  content.get("application/json").or_else(|| content.values().next())?.get("schema")
+}
+
+fn operation_changes(
+  key: &OperationKey,
+  (old_item, old_operation): (&Value, &Value),
+  (new_item, new_operation): (&Value, &Value),
+  documents: &Documents<'_>,
+  changes: &mut Vec<Change>,
+) {
+  let old_parameters = parameters(old_item, old_operation, documents.old);
+  let new_parameters = parameters(new_item, new_operation, documents.new);
+  for ((location, name), old_parameter) in old_parameters.iter() {
+    let at = format!("{key} {location} parameter {name}");
+    let Some(new_parameter) = new_parameters.get(&(location.clone(), name.clone())) else {
+      changes.push(Change::new(ChangeKind::Removed, false, at, "parameter removed"));
+      continue;
+    };
+    if !required(old_parameter) && required(new_parameter) {
+      changes.push(Change::new(ChangeKind::Changed, true, at.clone(), "parameter became required"));
+    }
+    if let (Some(old_schema), Some(new_schema)) = (old_parameter.get("schema"), new_parameter.get("schema")) {
+      schema_changes(old_schema, new_schema, documents, &at, "$", Direction::Request, 0, changes);
+    }
+  }
+  for ((location, name), new_parameter) in new_parameters.iter() {
+    if !old_parameters.contains_key(&(location.clone(), name.clone())) {
+      let message = match required(new_parameter) {
+        true => "required parameter added",
+        false => "optional parameter added",
+      };
+      changes.push(Change::new(
+        ChangeKind::Added,
+        required(new_parameter),
+        format!("{key} {location} parameter {name}"),
+        message,
+      ));
+    }
+  }
+
+  let at = format!("{key} request body");
+  match (request_body(old_operation, documents.old), request_body(new_operation, documents.new)) {
+    (None, Some(new_body)) if required(new_body) => {
+      changes.push(Change::new(ChangeKind::Added, true, at, "required request body added"))
+    },
+    (None, Some(_)) => changes.push(Change::new(ChangeKind::Added, false, at, "optional request body added")),
+    (Some(_), None) => changes.push(Change::new(ChangeKind::Removed, true, at, "request body removed")),
+    (Some(old_body), Some(new_body)) => {
+      if !required(old_body) && required(new_body) {
+        changes.push(Change::new(ChangeKind::Changed, true, at.clone(), "request body became required"));
+      }
+      let old_schema = content_schema(old_body, documents.old);
+      if let (Some(old_schema), Some(new_schema)) = (old_schema, content_schema(new_body, documents.new)) {
+        schema_changes(old_schema, new_schema, documents, &at, "$", Direction::Request, 0, changes);
+      }
+    },
+    (None, None) => {},
+  }
+
+  let responses =
+    |operation: &Value| operation.get("responses").and_then(Value::as_object).cloned().unwrap_or_default();
+  let (old_responses, new_responses) = (responses(old_operation), responses(new_operation));
+  for (status, old_response) in old_responses.iter() {
+    let at = format!("{key} response {status}");
+    let Some(new_response) = new_responses.get(status) else {
+      changes.push(Change::new(ChangeKind::Removed, true, at, "response removed"));
+      continue;
+    };
+    let old_schema = content_schema(old_response, documents.old);
+    if let (Some(old_schema), Some(new_schema)) = (old_schema, content_schema(new_response, documents.new)) {
+      schema_changes(old_schema, new_schema, documents, &at, "$", Direction::Response, 0, changes);
+    }
+  }
+  for status in new_responses.keys().filter(|status| !old_responses.contains_key(*status)) {
+    changes.push(Change::new(ChangeKind::Added, false, format!("{key} response {status}"), "response added"));
+  }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn schema_changes(
+  old: &Value,
+  new: &Value,
+  documents: &Documents<'_>,
+  location: &str,
+  at: &str,
+  direction: Direction,
+  depth: usize,
+  changes: &mut Vec<Change>,
+) {
+  if depth > MAX_DEPTH {
+    return;
+  }
+  let (old, new) = (resolve(old, documents.old), resolve(new, documents.new));
+  let place = match at {
+    "$" => location.to_string(),
+    _ => format!("{location} {at}"),
+  };
+  if let (Some(old_kind), Some(new_kind)) = (kind(old), kind(new)) {
+    if old_kind != new_kind {
+      changes.push(Change::new(
+        ChangeKind::Changed,
+        true,
+        place,
+        format!("type changed from {old_kind} to {new_kind}"),
+      ));
+      return;
+    }
+  }
+
+  let values = |schema: &Value| schema.get("enum").and_then(Value::as_array).cloned().unwrap_or_default();
+  let (old_values, new_values) = (values(old), values(new));
+  if !old_values.is_empty() && !new_values.is_empty() {
+    let list = |values: Vec<&Value>| values.iter().map(|value| value.to_string()).collect::<Vec<_>>().join(", ");
+    let removed: Vec<&Value> = old_values.iter().filter(|value| !new_values.contains(value)).collect();
+    if !removed.is_empty() {
+      let message = format!("enum narrowed, {} removed", list(removed));
+      changes.push(Change::new(ChangeKind::Changed, direction == Direction::Request, place.clone(), message));
+    }
+    let added: Vec<&Value> = new_values.iter().filter(|value| !old_values.contains(value)).collect();
+    if !added.is_empty() {
+      let message = format!("enum widened, {} added", list(added));
+      changes.push(Change::new(ChangeKind::Changed, direction == Direction::Response, place.clone(), message));
+    }
+  }
+
+  let properties = |schema: &Value| schema.get("properties").and_then(Value::as_object).cloned().unwrap_or_default();
+  let required_properties = |schema: &Value| {
+    schema
+      .get("required")
+      .and_then(Value::as_array)
+      .map(|names| names.iter().filter_map(Value::as_str).map(String::from).collect::<Vec<_>>())
+      .unwrap_or_default()
+  };
+  let (old_properties, new_properties) = (properties(old), properties(new));
+  let (old_required, new_required) = (required_properties(old), required_properties(new));
+  for (name, old_property) in old_properties.iter() {
+    let property_at = format!("{at}.{name}");
+    let property_place = format!("{location} {property_at}");
+    let Some(new_property) = new_properties.get(name) else {
+      let breaking = direction == Direction::Response;
+      changes.push(Change::new(ChangeKind::Removed, breaking, property_place, "property removed"));
+      continue;
+    };
+    match (old_required.contains(name), new_required.contains(name), direction) {
+      (false, true, Direction::Request) => {
+        changes.push(Change::new(ChangeKind::Changed, true, property_place.clone(), "property became required"))
+      },
+      (true, false, Direction::Response) => {
+        changes.push(Change::new(ChangeKind::Changed, true, property_place.clone(), "property became optional"))
+      },
+      _ => {},
+    }
+    schema_changes(old_property, new_property, documents, location, &property_at, direction, depth + 1, changes);
+  }
+  for name in new_properties.keys().filter(|name| !old_properties.contains_key(*name)) {
+    let required = new_required.contains(name);
+    let (breaking, message) = match (direction, required) {
+      (Direction::Request, true) => (true, "required property added"),
+      _ => (false, "property added"),
+    };
+    changes.push(Change::new(ChangeKind::Added, breaking, format!("{location} {at}.{name}"), message));
+  }
+
+  if let (Some(old_items), Some(new_items)) = (old.get("items"), new.get("items")) {
+    schema_changes(old_items, new_items, documents, location, &format!("{at}[]"), direction, depth + 1, changes);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+  use serde_json::json;
+
+  use super::*;
+
+  #[test]
+  fn test_diff() {
+    let old = json!({
+      "paths": {
+        "/pets": {
+          "get": {
+            "parameters": [{ "in": "query", "name": "limit", "schema": { "type": "integer" } }],
+            "responses": {
+              "200": { "content": { "application/json": { "schema": {
+                "type": "array",
+                "items": { "$ref": "#/components/schemas/Pet" }
+              } } } }
+            }
+          },
+          "post": {
+            "requestBody": { "content": { "application/json": { "schema": {
+              "type": "object",
+              "properties": { "status": { "type": "string", "enum": ["available", "sold"] } }
+            } } } },
+            "responses": { "201": {} }
+          }
+        },
+        "/pets/{id}": { "delete": { "responses": { "204": {} } } }
+      },
+      "components": { "schemas": { "Pet": {
+        "type": "object",
+        "required": ["name"],
+        "properties": { "name": { "type": "string" }, "age": { "type": "integer" } }
+      } } }
+    });
+    let new = json!({
+      "paths": {
+        "/pets": {
+          "get": {
+            "parameters": [{ "in": "query", "name": "limit", "required": true, "schema": { "type": "string" } }],
+            "responses": {
+              "200": { "content": { "application/json": { "schema": {
+                "type": "array",
+                "items": { "$ref": "#/components/schemas/Pet" }
+              } } } },
+              "400": {}
+            }
+          },
+          "post": {
+            "requestBody": { "content": { "application/json": { "schema": {
+              "type": "object",
+              "properties": { "status": { "type": "string", "enum": ["available"] } }
+            } } } },
+            "responses": { "201": {} }
+          }
+        }
+      },
+      "components": { "schemas": { "Pet": {
+        "type": "object",
+        "properties": { "name": { "type": "string" }, "tag": { "type": "string" } }
+      } } }
+    });
+    let changes: Vec<String> = diff(&old, &new).iter().map(ToString::to_string).collect();
+    assert_eq!(changes, vec![
+      "- DELETE /pets/{id}: operation removed",
+      "~ GET /pets query parameter limit: parameter became required",
+      "~ GET /pets query parameter limit: type changed from integer to string",
+      "- GET /pets response 200 $[].age: property removed",
+      "~ GET /pets response 200 $[].name: property became optional",
+      "~ POST /pets request body $.status: enum narrowed, \"sold\" removed",
+      "+ GET /pets response 200 $[].tag: property added",
+      "+ GET /pets response 400: response added",
+    ]);
+  }
+}
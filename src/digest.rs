@@ -0,0 +1,174 @@
+use md5::Md5;
+use sha2::{Digest as _, Sha256};
+
+/// Username and password answering the Digest challenge of a server, RFC 7616.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Credentials {
+  pub username: String,
+  pub password: String,
+}
+
+/// Digest challenge of a `WWW-Authenticate` header.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Challenge {
+  pub realm: String,
+  pub nonce: String,
+  pub opaque: Option<String>,
+  /// `MD5`, `SHA-256`, or their `-sess` variants.
+  pub algorithm: String,
+  /// Whether the server offers the `auth` quality of protection, the only one answered.
+  pub qop_auth: bool,
+}
+
+impl Challenge {
+  /// The Digest challenge of a `WWW-Authenticate` header value, which may list the challenges of other schemes too,
+  /// `None` when there is none or its algorithm is not supported.
+  pub fn parse(header: &str) -> Option<Self> {
+    let challenges = challenges(header);
+    let mut digests = challenges.iter().filter(|(scheme, _)| scheme.eq_ignore_ascii_case("digest"));
+    digests.find_map(|(_, params)| Self::from_params(params))
+  }
+
+  fn from_params(params: &[(String, String)]) -> Option<Self> {
+    let param = |name: &str| params.iter().find(|(key, _)| key.eq_ignore_ascii_case(name)).map(|(_, value)| value);
+    let algorithm = param("algorithm").cloned().unwrap_or(String::from("MD5")).to_uppercase();
+    if !["MD5", "MD5-SESS", "SHA-256", "SHA-256-SESS"].contains(&algorithm.as_str()) {
+      return None;
+    }
+    Some(Self {
+      realm: param("realm").cloned().unwrap_or_default(),
+      nonce: param("nonce")?.clone(),
+      opaque: param("opaque").cloned(),
+      algorithm,
+      qop_auth: param("qop").is_some_and(|qop| qop.split(',').any(|qop| qop.trim().eq_ignore_ascii_case("auth"))),
+    })
+  }
+
+  /// `Authorization` header value answering the challenge for a request of the method to the uri, its path and query.
+  pub fn authorization(&self, credentials: &Credentials, method: &str, uri: &str, cnonce: &str) -> String {
+    const NONCE_COUNT: &str = "00000001";
+    let hash = |text: String| {
+      match self.algorithm.starts_with("SHA-256") {
+        true => hex::encode(Sha256::digest(text)),
+        false => hex::encode(Md5::digest(text)),
+      }
+    };
+    let mut ha1 = hash(format!("{}:{}:{}", credentials.username, self.realm, credentials.password));
+    if self.algorithm.ends_with("-SESS") {
+      ha1 = hash(format!("{ha1}:{}:{cnonce}", self.nonce));
+    }
+    let ha2 = hash(format!("{}:{uri}", method.to_uppercase()));
+    let response = match self.qop_auth {
+      true => hash(format!("{ha1}:{}:{NONCE_COUNT}:{cnonce}:auth:{ha2}", self.nonce)),
+      false => hash(format!("{ha1}:{}:{ha2}", self.nonce)),
+    };
+    let mut fields = vec![
+      format!("username=\"{}\"", quote(&credentials.username)),
+      format!("realm=\"{}\"", quote(&self.realm)),
+      format!("nonce=\"{}\"", quote(&self.nonce)),
+      format!("uri=\"{}\"", quote(uri)),
+      format!("algorithm={}", self.algorithm),
+      format!("response=\"{response}\""),
+    ];
+    if self.qop_auth {
+      fields.extend([String::from("qop=auth"), format!("nc={NONCE_COUNT}"), format!("cnonce=\"{cnonce}\"")]);
+    }
+    if let Some(opaque) = &self.opaque {
+      fields.push(format!("opaque=\"{}\"", quote(opaque)));
+    }
+    format!("Digest {}", fields.join(", "))
+  }
+}
+
+/// Client nonce of an answer, unique enough for the server to tell answers apart.
+pub fn cnonce() -> String {
+  let now = chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default();
+  hex::encode(Sha256::digest(now.to_string()))[..16].to_string()
+}
+
+/// Schemes of the challenges of a `WWW-Authenticate` header with their `key=value` and `key="quoted, value"`
+/// parameters, quoted values being left alone so that they can't be mistaken for a scheme.
+fn challenges(header: &str) -> Vec<(String, Vec<(String, String)>)> {
+  let mut challenges: Vec<(String, Vec<(String, String)>)> = vec![];
+  let mut chars = header.chars().peekable();
+  loop {
+    while chars.next_if(|char| *char == ',' || char.is_whitespace()).is_some() {}
+    let key: String = std::iter::from_fn(|| chars.next_if(|char| *char != '=' && *char != ',')).collect();
+    let key = key.trim();
+    if key.is_empty() {
+      break;
+    }
+    // a scheme is followed by its first parameter, or by nothing
+    let key = match key.split_once(char::is_whitespace) {
+      Some((scheme, key)) => {
+        challenges.push((scheme.to_string(), vec![]));
+        key.trim().to_string()
+      },
+      None if chars.peek() != Some(&'=') => {
+        challenges.push((key.to_string(), vec![]));
+        continue;
+      },
+      None => key.to_string(),
+    };
+    if chars.next_if_eq(&'=').is_none() {
+      continue;
+    }
+    let mut value = String::new();
+    if chars.next_if_eq(&'"').is_some() {
+      while let Some(char) = chars.next() {
+        match char {
+          '\\' => value.extend(chars.next()),
+          '"' => break,
+          char => value.push(char),
+        }
+      }
+    } else {
+      value = std::iter::from_fn(|| chars.next_if(|char| *char != ',')).collect::<String>().trim().to_string();
+    }
+    if let Some((_, params)) = challenges.last_mut() {
+      params.push((key, value));
+    }
+  }
+  challenges
+}
+
+fn quote(text: &str) -> String {
+  text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_authorization() {
+    // example of RFC 2617, section 3.5
+    let header = r#"Basic realm="x", Digest realm="testrealm@host.com", qop="auth,auth-int", nonce="dcd98b7102dd2f0e8b11d0f600bfb0c093", opaque="5ccc069c403ebaf9f0171e9517f40e41""#;
+    let challenge = Challenge::parse(header).unwrap();
+    assert_eq!(challenge, Challenge {
+      realm: String::from("testrealm@host.com"),
+      nonce: String::from("dcd98b7102dd2f0e8b11d0f600bfb0c093"),
+      opaque: Some(String::from("5ccc069c403ebaf9f0171e9517f40e41")),
+      algorithm: String::from("MD5"),
+      qop_auth: true,
+    });
+    let credentials = Credentials { username: String::from("Mufasa"), password: String::from("Circle Of Life") };
+    let authorization = challenge.authorization(&credentials, "GET", "/dir/index.html", "0a4f113b");
+    assert!(authorization.contains(r#"response="6629fae49393a05397450978507c4ef1""#));
+    assert!(authorization.starts_with(r#"Digest username="Mufasa", realm="testrealm@host.com""#));
+    assert_eq!(Challenge::parse(r#"Basic realm="x""#), None);
+    assert_eq!(Challenge::parse(r#"Digest nonce="n", algorithm=SHA-512-256"#), None);
+
+    // schemes named in quoted values are not challenges
+    let header = r#"Basic realm="digest x", Digest realm="api", nonce="abc", qop="auth""#;
+    let challenge = Challenge::parse(header).unwrap();
+    assert_eq!((challenge.realm.as_str(), challenge.nonce.as_str()), ("api", "abc"));
+    assert_eq!(Challenge::parse(r#"Basic realm="Digest nonce=x""#), None);
+
+    // the first challenge of a supported algorithm is answered
+    let header = r#"Digest realm="api", nonce="a", algorithm=SHA-512-256, Bearer, Digest realm="api", nonce="b""#;
+    assert_eq!(Challenge::parse(header).map(|challenge| challenge.nonce), Some(String::from("b")));
+  }
+}
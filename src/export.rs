@@ -0,0 +1,15 @@
+use std::path::PathBuf;
+
+pub mod docs;
+pub mod har;
+pub mod http;
+pub mod json_schema;
+pub mod postman;
+
+/// File name in the current directory derived from `name`, keeping it safe for any file system.
+pub fn export_path(name: &str, extension: &str) -> PathBuf {
+  let stem: String =
+    name.chars().map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.' { c } else { '_' }).collect();
+  let stem = stem.trim_matches('_');
+  PathBuf::from(format!("{}.{extension}", if stem.is_empty() { "openapi" } else { stem }))
+}
@@ -0,0 +1,407 @@
+use serde_json::Value;
+
+use crate::{
+  mock::{self, kind},
+  operation_index::OperationIndex,
+  pages::home::ParsedSpec,
+  parameter,
+  tags::{self, TagNode},
+  xml::escape,
+};
+
+/// Part of the documentation, rendered as Markdown or HTML.
+#[derive(Debug, Clone, PartialEq)]
+enum Block {
+  Heading(usize, String),
+  Paragraph(String),
+  /// Links to headings, by their level below the top one and their text.
+  Contents(Vec<(usize, String)>),
+  Table(Vec<&'static str>, Vec<Vec<String>>),
+  /// JSON example.
+  Code(String),
+}
+
+/// Documentation of the spec as Markdown: its operations by tag, their parameters, bodies and responses with
+/// examples, then its component schemas.
+pub fn markdown(parsed: &ParsedSpec) -> String {
+  let mut text = String::new();
+  for block in blocks(parsed) {
+    match block {
+      Block::Heading(level, heading) => text.push_str(&format!("{} {heading}\n\n", "#".repeat(level))),
+      Block::Paragraph(paragraph) => text.push_str(&format!("{paragraph}\n\n")),
+      Block::Contents(headings) => {
+        for (depth, heading) in headings {
+          text.push_str(&format!("{}- [{heading}](#{})\n", "  ".repeat(depth), anchor(&heading)));
+        }
+        text.push('\n');
+      },
+      Block::Table(header, rows) => {
+        text.push_str(&format!("| {} |\n", header.join(" | ")));
+        text.push_str(&format!("|{}\n", " --- |".repeat(header.len())));
+        for row in rows {
+          let cells: Vec<String> = row.iter().map(|cell| cell.replace('|', "\\|").replace('\n', " ")).collect();
+          text.push_str(&format!("| {} |\n", cells.join(" | ")));
+        }
+        text.push('\n');
+      },
+      Block::Code(code) => text.push_str(&format!("```json\n{code}\n```\n\n")),
+    }
+  }
+  text.trim_end().to_string() + "\n"
+}
+
+/// Documentation of the spec as a standalone HTML page, see [`markdown`].
+pub fn html(parsed: &ParsedSpec) -> String {
+  let title = escape(&parsed.spec.info.title);
+  let mut body = String::new();
+  for block in blocks(parsed) {
+    match block {
+      Block::Heading(level, heading) => {
+        body.push_str(&format!("<h{level} id=\"{}\">{}</h{level}>\n", anchor(&heading), escape(&heading)))
+      },
+      Block::Paragraph(paragraph) => body.push_str(&format!("<p>{}</p>\n", escape(&paragraph).replace('\n', "<br>"))),
+      Block::Contents(headings) => {
+        body.push_str("<ul class=\"contents\">\n");
+        for (depth, heading) in headings {
+          body.push_str(&format!(
+            "<li class=\"depth-{depth}\"><a href=\"#{}\">{}</a></li>\n",
+            anchor(&heading),
+            escape(&heading)
+          ));
+        }
+        body.push_str("</ul>\n");
+      },
+      Block::Table(header, rows) => {
+        body.push_str("<table>\n<tr>");
+        for cell in header {
+          body.push_str(&format!("<th>{}</th>", escape(cell)));
+        }
+        body.push_str("</tr>\n");
+        for row in rows {
+          body.push_str("<tr>");
+          for cell in row {
+            body.push_str(&format!("<td>{}</td>", escape(&cell)));
+          }
+          body.push_str("</tr>\n");
+        }
+        body.push_str("</table>\n");
+      },
+      Block::Code(code) => body.push_str(&format!("<pre><code>{}</code></pre>\n", escape(&code))),
+    }
+  }
+  format!(
+    "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n<style>\n{STYLE}</style>\n</head>\n<body>\n{body}</body>\n</html>\n"
+  )
+}
+
+const STYLE: &str = "body { font-family: sans-serif; max-width: 60rem; margin: auto; padding: 1rem; line-height: 1.5; }
+table { border-collapse: collapse; margin-bottom: 1rem; }
+th, td { border: 1px solid #ccc; padding: 0.25rem 0.5rem; text-align: left; vertical-align: top; }
+pre { background: #f5f5f5; padding: 0.5rem; overflow-x: auto; }
+.contents { list-style: none; padding-left: 0; }
+.contents .depth-1 { padding-left: 1.5rem; }
+";
+
+fn blocks(parsed: &ParsedSpec) -> Vec<Block> {
+  let document = &parsed.document;
+  let info = &parsed.spec.info;
+  let mut blocks = vec![Block::Heading(1, format!("{} {}", info.title, info.version))];
+  blocks.extend(info.description.clone().map(Block::Paragraph));
+  if !parsed.spec.servers.is_empty() {
+    let servers: Vec<String> = parsed.spec.servers.iter().map(|server| server.url.clone()).collect();
+    blocks.push(Block::Paragraph(format!("Servers: {}", servers.join(", "))));
+  }
+
+  let index = OperationIndex::new(&parsed.spec);
+  let tag_tree = tags::tree(document);
+  let mut sections = vec![];
+  flatten(&tag_tree, &mut sections);
+  let sections: Vec<(&TagNode, Vec<usize>)> = sections
+    .into_iter()
+    .map(|node| (node, index.tagged(std::slice::from_ref(node.tag.as_ref().unwrap_or(&node.key)))))
+    .filter(|(_, operations)| !operations.is_empty())
+    .collect();
+  let schemas = document.pointer("/components/schemas").and_then(Value::as_object);
+
+  let mut contents = vec![];
+  for (node, operations) in sections.iter() {
+    contents.push((0, node.name()));
+    contents.extend(
+      operations
+        .iter()
+        .filter_map(|position| index.get(*position))
+        .map(|operation| (1, operation_heading(&operation.method, &operation.path))),
+    );
+  }
+  if schemas.is_some_and(|schemas| !schemas.is_empty()) {
+    contents.push((0, String::from("Schemas")));
+  }
+  blocks.push(Block::Contents(contents));
+
+  for (node, operations) in sections {
+    blocks.push(Block::Heading(2, node.name()));
+    blocks.extend(node.description.clone().map(Block::Paragraph));
+    for operation in operations.iter().filter_map(|position| index.get(*position)) {
+      blocks.extend(operation_blocks(document, &operation.path, &operation.method));
+    }
+  }
+
+  if let Some(schemas) = schemas.filter(|schemas| !schemas.is_empty()) {
+    blocks.push(Block::Heading(2, String::from("Schemas")));
+    for (name, schema) in schemas {
+      blocks.push(Block::Heading(3, name.clone()));
+      blocks.extend(schema_blocks(schema));
+    }
+  }
+  blocks
+}
+
+/// Tags of the tree, depth first, the groups that are not tags themselves left out.
+fn flatten<'a>(nodes: &'a [TagNode], flattened: &mut Vec<&'a TagNode>) {
+  for node in nodes {
+    if node.tag.is_some() || node.children.is_empty() {
+      flattened.push(node);
+    }
+    flatten(&node.children, flattened);
+  }
+}
+
+fn operation_heading(method: &str, path: &str) -> String {
+  format!("{} {path}", method.to_uppercase())
+}
+
+fn operation_blocks(document: &Value, path: &str, method: &str) -> Vec<Block> {
+  let operation =
+    document.get("paths").and_then(|paths| paths.get(path)).and_then(|item| item.get(method.to_lowercase()));
+  let Some(operation) = operation else {
+    return vec![];
+  };
+  let text = |key: &str| operation.get(key).and_then(Value::as_str).map(String::from);
+  let mut blocks = vec![Block::Heading(3, operation_heading(method, path))];
+  blocks.extend(text("summary").map(Block::Paragraph));
+  if operation.get("deprecated").and_then(Value::as_bool) == Some(true) {
+    blocks.push(Block::Paragraph(String::from("Deprecated.")));
+  }
+  blocks.extend(text("operationId").map(|id| Block::Paragraph(format!("Operation id: {id}"))));
+  blocks.extend(text("description").map(Block::Paragraph));
+
+  let parameters = parameter::parameters(document, path, method);
+  if !parameters.is_empty() {
+    blocks.push(Block::Heading(4, String::from("Parameters")));
+    let rows = parameters
+      .into_iter()
+      .map(|((location, name), parameter)| {
+        let schema = parameter.get("schema").unwrap_or(&Value::Null);
+        vec![
+          name,
+          location,
+          type_name(schema, document),
+          yes_no(parameter.get("required")),
+          description(parameter).unwrap_or_default(),
+        ]
+      })
+      .collect();
+    blocks.push(Block::Table(vec!["Name", "In", "Type", "Required", "Description"], rows));
+  }
+
+  if let Some(body) = operation.get("requestBody").map(|body| mock::resolve(body, document)) {
+    blocks.push(Block::Heading(4, String::from("Request body")));
+    blocks.extend(description(body).map(Block::Paragraph));
+    let contents = body.get("content").and_then(Value::as_object).into_iter().flatten();
+    let rows: Vec<Vec<String>> = contents
+      .clone()
+      .map(|(content_type, media)| {
+        let schema = media.get("schema").unwrap_or(&Value::Null);
+        vec![content_type.clone(), type_name(schema, document), yes_no(body.get("required"))]
+      })
+      .collect();
+    blocks.push(Block::Table(vec!["Content type", "Type", "Required"], rows));
+    blocks.extend(example_block(document, contents));
+  }
+
+  let responses = operation.get("responses").and_then(Value::as_object);
+  if let Some(responses) = responses.filter(|responses| !responses.is_empty()) {
+    blocks.push(Block::Heading(4, String::from("Responses")));
+    let rows = responses
+      .iter()
+      .map(|(status, response)| {
+        let response = mock::resolve(response, document);
+        let contents = response.get("content").and_then(Value::as_object).into_iter().flatten();
+        let contents: Vec<String> = contents
+          .map(|(content_type, media)| {
+            format!("{content_type}: {}", type_name(media.get("schema").unwrap_or(&Value::Null), document))
+          })
+          .collect();
+        vec![status.clone(), description(response).unwrap_or_default(), contents.join(", ")]
+      })
+      .collect();
+    blocks.push(Block::Table(vec!["Status", "Description", "Content"], rows));
+    let success = responses.iter().find(|(status, _)| status.starts_with('2'));
+    if let Some((status, response)) = success {
+      let contents = mock::resolve(response, document).get("content").and_then(Value::as_object);
+      if let Some(example) = example_block(document, contents.into_iter().flatten()) {
+        blocks.push(Block::Paragraph(format!("Example {status} response:")));
+        blocks.push(example);
+      }
+    }
+  }
+  blocks
+}
+
+/// Example of the first JSON content, none for other content types.
+fn example_block<'a>(document: &Value, mut contents: impl Iterator<Item = (&'a String, &'a Value)>) -> Option<Block> {
+  let (_, media) = contents.find(|(content_type, _)| content_type.contains("json"))?;
+  let example = mock::media_example(media, document);
+  (!example.is_null()).then(|| Block::Code(serde_json::to_string_pretty(&example).unwrap_or_default()))
+}
+
+fn schema_blocks(schema: &Value) -> Vec<Block> {
+  let mut blocks: Vec<Block> = description(schema).map(Block::Paragraph).into_iter().collect();
+  let Some(properties) = schema.get("properties").and_then(Value::as_object) else {
+    blocks.push(Block::Paragraph(format!("Type: {}", type_name(schema, &Value::Null))));
+    return blocks;
+  };
+  let required: Vec<&str> =
+    schema.get("required").and_then(Value::as_array).into_iter().flatten().filter_map(Value::as_str).collect();
+  let rows = properties
+    .iter()
+    .map(|(name, property)| {
+      vec![
+        name.clone(),
+        type_name(property, &Value::Null),
+        yes_no(Some(&Value::Bool(required.contains(&name.as_str())))),
+        description(property).unwrap_or_default(),
+      ]
+    })
+    .collect();
+  blocks.push(Block::Table(vec!["Property", "Type", "Required", "Description"], rows));
+  blocks
+}
+
+/// Readable type of the schema: the component it references, `array of` its items, its compositions, or its type and
+/// format.
+fn type_name(schema: &Value, document: &Value) -> String {
+  if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+    let name = reference.rsplit('/').next().unwrap_or(reference).to_string();
+    // references to parameters or bodies stand for the schema they hold
+    return match reference.starts_with("#/components/schemas/") || document.is_null() {
+      true => name,
+      false => type_name(mock::resolve(schema, document), document),
+    };
+  }
+  for (key, separator) in [("oneOf", " | "), ("anyOf", " | "), ("allOf", " & ")] {
+    if let Some(schemas) = schema.get(key).and_then(Value::as_array) {
+      return schemas.iter().map(|schema| type_name(schema, document)).collect::<Vec<_>>().join(separator);
+    }
+  }
+  match kind(schema) {
+    Some("array") => format!("array of {}", type_name(schema.get("items").unwrap_or(&Value::Null), document)),
+    Some(kind) => {
+      match schema.get("format").and_then(Value::as_str) {
+        Some(format) => format!("{kind} ({format})"),
+        None => kind.to_string(),
+      }
+    },
+    None => String::from("any"),
+  }
+}
+
+fn description(value: &Value) -> Option<String> {
+  value.get("description").and_then(Value::as_str).map(String::from)
+}
+
+fn yes_no(value: Option<&Value>) -> String {
+  match value.and_then(Value::as_bool).unwrap_or_default() {
+    true => String::from("yes"),
+    false => String::from("no"),
+  }
+}
+
+/// Anchor of the heading, as Markdown renderers such as GitHub's derive it.
+fn anchor(heading: &str) -> String {
+  heading
+    .to_lowercase()
+    .chars()
+    .filter_map(|c| {
+      match c {
+        ' ' => Some('-'),
+        c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+        _ => None,
+      }
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+  use serde_json::json;
+
+  use super::*;
+
+  fn parsed() -> ParsedSpec {
+    let document = json!({
+      "openapi": "3.0.0",
+      "info": { "title": "Petstore", "version": "1.0.0" },
+      "tags": [{ "name": "pets", "description": "Everything about pets" }],
+      "paths": {
+        "/pets/{petId}": {
+          "parameters": [{ "name": "petId", "in": "path", "required": true, "schema": { "type": "integer" } }],
+          "get": {
+            "tags": ["pets"],
+            "summary": "Find a pet",
+            "operationId": "getPet",
+            "responses": {
+              "200": {
+                "description": "The pet",
+                "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Pet" } } }
+              },
+              "404": { "description": "Not found" }
+            }
+          }
+        }
+      },
+      "components": {
+        "schemas": {
+          "Pet": {
+            "type": "object",
+            "required": ["name"],
+            "properties": {
+              "name": { "type": "string", "example": "Rex" },
+              "tags": { "type": "array", "items": { "type": "string" } }
+            }
+          }
+        }
+      }
+    });
+    ParsedSpec { spec: serde_json::from_value(document.clone()).unwrap(), document }
+  }
+
+  #[test]
+  fn test_markdown() {
+    let markdown = markdown(&parsed());
+    let expected = [
+      "# Petstore 1.0.0",
+      "- [pets](#pets)\n  - [GET /pets/{petId}](#get-petspetid)\n- [Schemas](#schemas)",
+      "## pets\n\nEverything about pets",
+      "### GET /pets/{petId}\n\nFind a pet\n\nOperation id: getPet",
+      "| petId | path | integer | yes |  |",
+      "| 200 | The pet | application/json: Pet |",
+      "Example 200 response:\n\n```json\n{\n  \"name\": \"Rex\",",
+      "### Pet",
+      "| tags | array of string | no |  |",
+    ];
+    for expected in expected {
+      assert!(markdown.contains(expected), "{expected:?} missing from:\n{markdown}");
+    }
+  }
+
+  #[test]
+  fn test_html() {
+    let html = html(&parsed());
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<h3 id=\"get-petspetid\">GET /pets/{petId}</h3>"));
+    assert!(html.contains("<td>application/json: Pet</td>"));
+    assert_eq!(escape("<a href=\"x\">&</a>"), "&lt;a href=&quot;x&quot;&gt;&amp;&lt;/a&gt;");
+  }
+}
@@ -0,0 +1,99 @@
+use serde_json::{json, Value};
+
+use crate::client::Exchange;
+
+const HTTP_VERSION: &str = "HTTP/1.1";
+
+fn name_values(pairs: &[(String, String)]) -> Vec<Value> {
+  pairs.iter().map(|(name, value)| json!({ "name": name, "value": value })).collect()
+}
+
+fn entry(exchange: &Exchange) -> Value {
+  let request = &exchange.request;
+  let response = &exchange.response;
+  let mut har_request = json!({
+    "method": request.method,
+    "url": request.full_url(),
+    "httpVersion": HTTP_VERSION,
+    "cookies": [],
+    "headers": name_values(&request.headers),
+    "queryString": name_values(&request.query),
+    "headersSize": -1,
+    "bodySize": request.body.as_ref().map_or(0, String::len),
+  });
+  if let Some(body) = &request.body {
+    har_request["postData"] = json!({ "mimeType": request.header("Content-Type").unwrap_or_default(), "text": body });
+  }
+  json!({
+    "startedDateTime": exchange.started_at,
+    "time": exchange.elapsed_ms,
+    "request": har_request,
+    "response": {
+      "status": response.status,
+      "statusText": response.status_text,
+      "httpVersion": response.http_version,
+      "cookies": [],
+      "headers": name_values(&response.headers),
+      "content": {
+        "size": response.body.len(),
+        "mimeType": response.header("Content-Type").unwrap_or_default(),
+        "text": response.body,
+      },
+      "redirectURL": response.header("Location").unwrap_or_default(),
+      "headersSize": -1,
+      "bodySize": response.body.len(),
+    },
+    "cache": {},
+    "timings": { "send": 0, "wait": exchange.elapsed_ms, "receive": 0 },
+  })
+}
+
+/// Renders the exchanges as a HAR 1.2 log.
+pub fn render(exchanges: &[Exchange]) -> Value {
+  json!({
+    "log": {
+      "version": "1.2",
+      "creator": { "name": env!("CARGO_PKG_NAME"), "version": env!("CARGO_PKG_VERSION") },
+      "entries": exchanges.iter().map(entry).collect::<Vec<_>>(),
+    }
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+  use crate::{client::HttpResponse, request::HttpRequest};
+
+  #[test]
+  fn test_render() {
+    let exchange = Exchange {
+      started_at: String::from("2024-05-01T10:00:00+00:00"),
+      elapsed_ms: 42,
+      operation: None,
+      request: HttpRequest {
+        method: String::from("POST"),
+        base_url: String::from("https://petstore.swagger.io/v2"),
+        path: String::from("/pet"),
+        headers: vec![(String::from("Content-Type"), String::from("application/json"))],
+        body: Some(String::from("{}")),
+        ..HttpRequest::default()
+      },
+      response: HttpResponse {
+        status: 201,
+        status_text: String::from("Created"),
+        http_version: String::from("HTTP/1.1"),
+        body: String::from("{\"id\":1}"),
+        ..HttpResponse::default()
+      },
+      ..Exchange::default()
+    };
+    let har = render(&[exchange]);
+    let entry = &har["log"]["entries"][0];
+    assert_eq!(entry["request"]["url"], "https://petstore.swagger.io/v2/pet");
+    assert_eq!(entry["request"]["postData"], json!({ "mimeType": "application/json", "text": "{}" }));
+    assert_eq!(entry["response"]["status"], 201);
+    assert_eq!(entry["response"]["content"]["text"], "{\"id\":1}");
+  }
+}
@@ -0,0 +1,96 @@
+use std::collections::BTreeSet;
+
+use crate::request::HttpRequest;
+
+pub const BASE_URL_VARIABLE: &str = "baseUrl";
+pub const AUTH_VARIABLE: &str = "authToken";
+
+/// A request written to a `.http` file, as understood by VS Code REST Client and JetBrains HTTP Client.
+pub struct HttpFileEntry {
+  pub name: String,
+  pub request: HttpRequest,
+  /// Header name and value template carrying the credential, e.g. `("Authorization", "Bearer {{authToken}}")`.
+  pub auth_header: Option<(String, String)>,
+}
+
+pub fn render(entries: &[HttpFileEntry]) -> String {
+  let base_url = entries.first().map(|entry| entry.request.base_url.clone()).unwrap_or_default();
+  let mut variables = BTreeSet::new();
+  let mut blocks = vec![];
+
+  for entry in entries {
+    let request = &entry.request;
+    let mut path = String::new();
+    let mut rest = request.path.as_str();
+    while let Some(start) = rest.find('{') {
+      let Some(end) = rest[start..].find('}').map(|end| start + end) else {
+        break;
+      };
+      let name = &rest[start + 1..end];
+      path.push_str(&rest[..start]);
+      path.push_str(format!("{{{{{name}}}}}").as_str());
+      variables.insert(name.to_string());
+      rest = &rest[end + 1..];
+    }
+    path.push_str(rest);
+
+    let url = if request.base_url == base_url {
+      format!("{{{{{BASE_URL_VARIABLE}}}}}{path}")
+    } else {
+      format!("{}{path}", request.base_url)
+    };
+    let mut block = format!("### {}\n{} {url}{}\n", entry.name, request.method, request.query_string());
+    for (key, value) in request.headers.iter() {
+      block.push_str(format!("{key}: {value}\n").as_str());
+    }
+    if let Some((key, value)) = &entry.auth_header {
+      if request.header(key).is_none() {
+        block.push_str(format!("{key}: {value}\n").as_str());
+      }
+    }
+    if let Some(body) = &request.body {
+      block.push('\n');
+      block.push_str(body.trim_end());
+      block.push('\n');
+    }
+    blocks.push(block);
+  }
+
+  let mut content = format!("@{BASE_URL_VARIABLE} = {base_url}\n");
+  if entries.iter().any(|entry| entry.auth_header.is_some()) {
+    content.push_str(format!("@{AUTH_VARIABLE} =\n").as_str());
+  }
+  for variable in variables {
+    content.push_str(format!("@{variable} =\n").as_str());
+  }
+  content.push('\n');
+  content.push_str(blocks.join("\n").as_str());
+  content
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_render() {
+    let entries = vec![HttpFileEntry {
+      name: String::from("getPetById"),
+      request: HttpRequest {
+        method: String::from("GET"),
+        base_url: String::from("https://petstore.swagger.io/v2"),
+        path: String::from("/pet/{petId}"),
+        query: vec![(String::from("verbose"), String::from("true"))],
+        ..HttpRequest::default()
+      },
+      auth_header: Some((String::from("api_key"), format!("{{{{{AUTH_VARIABLE}}}}}"))),
+    }];
+    assert_eq!(
+      render(&entries),
+      "@baseUrl = https://petstore.swagger.io/v2\n@authToken =\n@petId =\n\n### getPetById\nGET \
+       {{baseUrl}}/pet/{{petId}}?verbose=true\napi_key: {{authToken}}\n"
+    );
+  }
+}
@@ -0,0 +1,137 @@
+use serde_json::{json, Map, Value};
+
+const DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+const SCHEMA_REF_PREFIX: &str = "#/components/schemas/";
+/// Keywords whose values are data rather than schemas, copied as they are.
+const DATA_KEYWORDS: [&str; 5] = ["example", "examples", "default", "enum", "const"];
+/// OpenAPI keywords that JSON Schema validators do not know.
+const OPENAPI_KEYWORDS: [&str; 3] = ["discriminator", "xml", "externalDocs"];
+
+/// Standalone JSON Schema of a spec schema, the component schemas it references, transitively, are bundled under
+/// `$defs`.
+pub fn bundle(name: &str, schema: &Value, document: &Value) -> Value {
+  let mut pending = vec![];
+  let mut root = convert(schema, &mut pending);
+  let mut definitions = Map::new();
+  while let Some(reference) = pending.pop() {
+    if definitions.contains_key(&reference) {
+      continue;
+    }
+    let pointer = format!("/components/schemas/{}", reference.replace('~', "~0").replace('/', "~1"));
+    let definition = document.pointer(&pointer).cloned().unwrap_or_else(|| json!({}));
+    definitions.insert(reference, convert(&definition, &mut pending));
+  }
+  let Value::Object(object) = &mut root else {
+    return root;
+  };
+  let mut bundled = Map::from_iter([(String::from("$schema"), Value::from(DIALECT))]);
+  if !object.contains_key("title") {
+    bundled.insert(String::from("title"), Value::from(name));
+  }
+  bundled.append(object);
+  if !definitions.is_empty() {
+    bundled.insert(String::from("$defs"), Value::Object(definitions));
+  }
+  Value::Object(bundled)
+}
+
+/// The schema in JSON Schema terms: component references point to `$defs`, `nullable` becomes a `null` type and
+/// `example` becomes `examples`. Referenced component names are added to `pending`.
+fn convert(schema: &Value, pending: &mut Vec<String>) -> Value {
+  let Value::Object(object) = schema else {
+    return match schema {
+      Value::Array(items) => Value::Array(items.iter().map(|item| convert(item, pending)).collect()),
+      _ => schema.clone(),
+    };
+  };
+  let mut converted = Map::new();
+  for (key, value) in object {
+    match key.as_str() {
+      "$ref" => {
+        let reference = value.as_str().unwrap_or_default();
+        match reference.strip_prefix(SCHEMA_REF_PREFIX) {
+          Some(name) => {
+            pending.push(name.to_string());
+            converted.insert(key.clone(), Value::from(format!("#/$defs/{name}")));
+          },
+          None => {
+            converted.insert(key.clone(), value.clone());
+          },
+        }
+      },
+      "properties" | "patternProperties" => {
+        let properties = value.as_object().into_iter().flatten();
+        let properties = properties.map(|(name, property)| (name.clone(), convert(property, pending))).collect();
+        converted.insert(key.clone(), Value::Object(properties));
+      },
+      "example" if !object.contains_key("examples") => {
+        converted.insert(String::from("examples"), json!([value]));
+      },
+      "nullable" => {},
+      key if OPENAPI_KEYWORDS.contains(&key) => {},
+      key if DATA_KEYWORDS.contains(&key) => {
+        converted.insert(key.to_string(), value.clone());
+      },
+      _ => {
+        converted.insert(key.clone(), convert(value, pending));
+      },
+    }
+  }
+  if object.get("nullable").and_then(Value::as_bool) != Some(true) {
+    return Value::Object(converted);
+  }
+  match converted.get_mut("type") {
+    Some(Value::String(kind)) => {
+      let kind = std::mem::take(kind);
+      converted.insert(String::from("type"), json!([kind, "null"]));
+    },
+    Some(Value::Array(kinds)) if !kinds.contains(&Value::from("null")) => kinds.push(Value::from("null")),
+    Some(_) => {},
+    None => return json!({ "anyOf": [converted, { "type": "null" }] }),
+  }
+  Value::Object(converted)
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_bundle() {
+    let document = json!({
+      "components": { "schemas": {
+        "Pet": {
+          "type": "object",
+          "discriminator": { "propertyName": "kind" },
+          "properties": {
+            "owner": { "$ref": "#/components/schemas/Owner", "nullable": true },
+            "example": { "type": "string", "nullable": true, "example": "rex" }
+          }
+        },
+        "Owner": { "type": "object", "properties": { "pets": { "items": { "$ref": "#/components/schemas/Pet" } } } }
+      } }
+    });
+    let schema = json!({ "type": "array", "items": { "$ref": "#/components/schemas/Pet" } });
+    assert_eq!(
+      bundle("pets", &schema, &document),
+      json!({
+        "$schema": DIALECT,
+        "title": "pets",
+        "type": "array",
+        "items": { "$ref": "#/$defs/Pet" },
+        "$defs": {
+          "Pet": {
+            "type": "object",
+            "properties": {
+              "owner": { "anyOf": [{ "$ref": "#/$defs/Owner" }, { "type": "null" }] },
+              "example": { "type": ["string", "null"], "examples": ["rex"] }
+            }
+          },
+          "Owner": { "type": "object", "properties": { "pets": { "items": { "$ref": "#/$defs/Pet" } } } }
+        }
+      })
+    );
+  }
+}
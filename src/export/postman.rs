@@ -0,0 +1,232 @@
+use serde_json::{json, Value};
+
+use crate::{
+  export::http::{AUTH_VARIABLE, BASE_URL_VARIABLE},
+  request::{decode_component, Auth, HttpRequest},
+  security::SecurityScheme,
+  workspace::Environment,
+};
+
+const COLLECTION_SCHEMA: &str = "https://schema.getpostman.com/json/collection/v2.1.0/collection.json";
+
+/// A request written to a Postman v2.1 collection.
+pub struct PostmanEntry {
+  pub name: String,
+  /// Folder path, nested folders are separated by `/`.
+  pub folder: Option<String>,
+  pub request: HttpRequest,
+  pub auth: Option<Auth>,
+}
+
+/// Auth referencing the `authToken` collection variable, or `username`/`password` for basic and digest auth.
+pub fn scheme_auth(scheme: &SecurityScheme) -> Option<Auth> {
+  let token = format!("{{{{{AUTH_VARIABLE}}}}}");
+  match scheme {
+    SecurityScheme::ApiKey { name, location } if location == "header" || location == "query" => {
+      Some(Auth::ApiKey { name: name.clone(), value: token, location: location.clone() })
+    },
+    SecurityScheme::ApiKey { .. } => None,
+    SecurityScheme::Http { scheme } if scheme == "basic" => {
+      Some(Auth::Basic { username: String::from("{{username}}"), password: String::from("{{password}}") })
+    },
+    SecurityScheme::Http { scheme } if scheme == "digest" => {
+      Some(Auth::Digest { username: String::from("{{username}}"), password: String::from("{{password}}") })
+    },
+    SecurityScheme::Http { .. } | SecurityScheme::OAuth2 | SecurityScheme::OpenIdConnect => {
+      Some(Auth::Bearer { token })
+    },
+  }
+}
+
+fn auth_value(auth: &Auth) -> Value {
+  let params = |params: &[(&str, &str)]| {
+    params.iter().map(|(key, value)| json!({ "key": key, "value": value, "type": "string" })).collect::<Vec<_>>()
+  };
+  match auth {
+    Auth::Bearer { token } => json!({ "type": "bearer", "bearer": params(&[("token", token)]) }),
+    Auth::Basic { username, password } => {
+      json!({ "type": "basic", "basic": params(&[("username", username), ("password", password)]) })
+    },
+    Auth::ApiKey { name, value, location } => {
+      json!({ "type": "apikey", "apikey": params(&[("key", name), ("value", value), ("in", location)]) })
+    },
+    Auth::Digest { username, password } => {
+      json!({ "type": "digest", "digest": params(&[("username", username), ("password", password)]) })
+    },
+  }
+}
+
+fn url_value(request: &HttpRequest, base_url: &str) -> Value {
+  let host =
+    if request.base_url == base_url { format!("{{{{{BASE_URL_VARIABLE}}}}}") } else { request.base_url.clone() };
+  let mut variables = vec![];
+  let path: Vec<String> = request
+    .path
+    .split('/')
+    .filter(|segment| !segment.is_empty())
+    .map(|segment| {
+      // `{{name}}` segments, e.g. of imported requests, are variables Postman substitutes already
+      let parameter = segment.strip_prefix('{').and_then(|segment| segment.strip_suffix('}'));
+      match parameter.filter(|name| !name.starts_with('{') && !name.ends_with('}')) {
+        Some(name) => {
+          variables.push(json!({ "key": name, "value": "" }));
+          format!(":{name}")
+        },
+        None => segment.to_string(),
+      }
+    })
+    .collect();
+  let query: Vec<Value> = request.query.iter().map(|(key, value)| json!({ "key": key, "value": value })).collect();
+  let raw = format!("{host}/{}{}", path.join("/"), request.query_string());
+  let mut url = json!({ "raw": raw, "host": [host], "path": path });
+  if !query.is_empty() {
+    url["query"] = json!(query);
+  }
+  if !variables.is_empty() {
+    url["variable"] = json!(variables);
+  }
+  url
+}
+
+fn body_value(request: &HttpRequest) -> Option<Value> {
+  let body = request.body.as_ref()?;
+  let content_type = request.header("Content-Type").unwrap_or_default();
+  if content_type.contains("x-www-form-urlencoded") {
+    let pairs: Vec<Value> = body
+      .split('&')
+      .filter(|pair| !pair.is_empty())
+      .map(|pair| {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        json!({ "key": decode_component(key), "value": decode_component(value) })
+      })
+      .collect();
+    return Some(json!({ "mode": "urlencoded", "urlencoded": pairs }));
+  }
+  let mut body = json!({ "mode": "raw", "raw": body });
+  if content_type.contains("json") {
+    body["options"] = json!({ "raw": { "language": "json" } });
+  }
+  Some(body)
+}
+
+fn request_item(entry: &PostmanEntry, base_url: &str) -> Value {
+  let request = &entry.request;
+  let headers: Vec<Value> =
+    request.headers.iter().map(|(key, value)| json!({ "key": key, "value": value, "type": "text" })).collect();
+  let mut item = json!({
+    "name": entry.name,
+    "request": { "method": request.method, "header": headers, "url": url_value(request, base_url) },
+  });
+  if let Some(body) = body_value(request) {
+    item["request"]["body"] = body;
+  }
+  if let Some(auth) = &entry.auth {
+    item["request"]["auth"] = auth_value(auth);
+  }
+  item
+}
+
+fn insert_item(items: &mut Vec<Value>, folders: &[&str], item: Value) {
+  let Some((folder, rest)) = folders.split_first() else {
+    items.push(item);
+    return;
+  };
+  let index = match items.iter().position(|existing| existing.get("item").is_some() && existing["name"] == *folder) {
+    Some(index) => index,
+    None => {
+      items.push(json!({ "name": folder, "item": [] }));
+      items.len() - 1
+    },
+  };
+  if let Some(children) = items[index]["item"].as_array_mut() {
+    insert_item(children, rest, item);
+  }
+}
+
+/// Renders a Postman v2.1 collection, exposing the base url of the first entry and the auth token as
+/// collection variables.
+pub fn render_collection(name: &str, entries: &[PostmanEntry]) -> Value {
+  let base_url = entries.first().map(|entry| entry.request.base_url.clone()).unwrap_or_default();
+  let mut items = vec![];
+  for entry in entries {
+    let folders: Vec<&str> = entry.folder.iter().flat_map(|folder| folder.split('/')).collect();
+    insert_item(&mut items, &folders, request_item(entry, &base_url));
+  }
+  let mut variables = vec![json!({ "key": BASE_URL_VARIABLE, "value": base_url })];
+  let uses_auth_variable = entries.iter().filter_map(|entry| entry.auth.as_ref()).any(|auth| {
+    serde_json::to_string(auth).is_ok_and(|auth| auth.contains(format!("{{{{{AUTH_VARIABLE}}}}}").as_str()))
+  });
+  if uses_auth_variable {
+    variables.push(json!({ "key": AUTH_VARIABLE, "value": "" }));
+  }
+  json!({
+    "info": { "name": name, "schema": COLLECTION_SCHEMA },
+    "item": items,
+    "variable": variables,
+  })
+}
+
+/// Renders an environment as a Postman environment file.
+pub fn render_environment(environment: &Environment) -> Value {
+  let values: Vec<Value> =
+    environment.variables.iter().map(|(key, value)| json!({ "key": key, "value": value, "enabled": true })).collect();
+  json!({ "name": environment.name, "values": values, "_postman_variable_scope": "environment" })
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+  use crate::import::{postman, ImportedRequest};
+
+  #[test]
+  fn test_render_collection() {
+    let entries = vec![PostmanEntry {
+      name: String::from("getPetById"),
+      folder: Some(String::from("pet")),
+      request: HttpRequest {
+        method: String::from("GET"),
+        base_url: String::from("https://petstore.swagger.io/v2"),
+        path: String::from("/pet/{petId}"),
+        query: vec![(String::from("verbose"), String::from("true"))],
+        ..HttpRequest::default()
+      },
+      auth: scheme_auth(&SecurityScheme::ApiKey { name: String::from("api_key"), location: String::from("header") }),
+    }];
+    let collection = render_collection("Petstore", &entries);
+    assert_eq!(collection["item"][0]["item"][0]["request"]["url"]["raw"], "{{baseUrl}}/pet/:petId?verbose=true");
+    assert_eq!(
+      collection["variable"],
+      json!([{ "key": "baseUrl", "value": "https://petstore.swagger.io/v2" }, { "key": "authToken", "value": "" }])
+    );
+
+    let (requests, _) = postman::parse(&collection).unwrap();
+    assert_eq!(requests, vec![ImportedRequest {
+      name: String::from("getPetById"),
+      method: String::from("GET"),
+      url: String::from("{{baseUrl}}/pet/{{petId}}?verbose=true"),
+      folder: Some(String::from("pet")),
+      auth: Some(Auth::ApiKey {
+        name: String::from("api_key"),
+        value: String::from("{{authToken}}"),
+        location: String::from("header")
+      }),
+      ..ImportedRequest::default()
+    }]);
+  }
+
+  #[test]
+  fn test_url_value_keeps_variables() {
+    let request = HttpRequest {
+      method: String::from("GET"),
+      base_url: String::from("https://petstore.swagger.io/v2"),
+      path: String::from("/pet/{{petId}}/photos/{photoId}"),
+      ..HttpRequest::default()
+    };
+    let url = url_value(&request, "https://petstore.swagger.io/v2");
+    assert_eq!(url["raw"], "{{baseUrl}}/pet/{{petId}}/photos/:photoId");
+    assert_eq!(url["path"], json!(["pet", "{{petId}}", "photos", ":photoId"]));
+    assert_eq!(url["variable"], json!([{ "key": "photoId", "value": "" }]));
+  }
+}
@@ -0,0 +1,81 @@
+use ratatui::prelude::*;
+use serde_json::Value;
+
+/// Vendor extensions of an object of the spec, its `x-` keys, e.g. `x-rate-limit` or `x-owner`, in order.
+pub fn of(value: &Value) -> Vec<(String, Value)> {
+  value
+    .as_object()
+    .map(|object| {
+      object.iter().filter(|(key, _)| key.starts_with("x-")).map(|(key, value)| (key.clone(), value.clone())).collect()
+    })
+    .unwrap_or_default()
+}
+
+/// Lines of the section listing the extensions: a header with their count, followed once `expanded` by each
+/// extension, scalars next to their name and objects and arrays pretty printed as JSON below it. No lines when there
+/// are no extensions.
+pub fn lines(extensions: &[(String, Value)], expanded: bool) -> Vec<Vec<(Style, String)>> {
+  if extensions.is_empty() {
+    return vec![];
+  }
+  let marker = match expanded {
+    true => "▾",
+    false => "▸",
+  };
+  let mut lines = vec![vec![(Style::default().bold(), format!("{marker} Extensions ({})", extensions.len()))]];
+  if !expanded {
+    return lines;
+  }
+  let name_style = Style::default().fg(Color::LightMagenta);
+  let value_style = Style::default().fg(Color::LightGreen);
+  for (name, value) in extensions {
+    match value {
+      Value::Object(_) | Value::Array(_) => {
+        lines.push(vec![(name_style, format!("  {name}:"))]);
+        let pretty = serde_json::to_string_pretty(value).unwrap_or_default();
+        lines.extend(pretty.lines().map(|line| vec![(value_style, format!("    {line}"))]));
+      },
+      Value::String(text) => lines.push(vec![(name_style, format!("  {name}: ")), (value_style, text.clone())]),
+      scalar => lines.push(vec![(name_style, format!("  {name}: ")), (value_style, scalar.to_string())]),
+    }
+  }
+  lines
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+  use serde_json::json;
+
+  use super::*;
+
+  #[test]
+  fn test_lines() {
+    let operation = json!({
+      "summary": "List pets",
+      "x-rate-limit": 100,
+      "x-owner": { "team": "payments" },
+      "x-feature-flag": "pets-v2",
+    });
+    let extensions = of(&operation);
+    assert_eq!(extensions.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>(), vec![
+      "x-feature-flag",
+      "x-owner",
+      "x-rate-limit"
+    ]);
+    let text = |lines: Vec<Vec<(Style, String)>>| {
+      lines.into_iter().map(|line| line.into_iter().map(|(_, text)| text).collect::<String>()).collect::<Vec<_>>()
+    };
+    assert_eq!(text(lines(&extensions, false)), vec!["▸ Extensions (3)"]);
+    assert_eq!(text(lines(&extensions, true)), vec![
+      "▾ Extensions (3)",
+      "  x-feature-flag: pets-v2",
+      "  x-owner:",
+      "    {",
+      "      \"team\": \"payments\"",
+      "    }",
+      "  x-rate-limit: 100",
+    ]);
+    assert!(lines(&of(&json!({ "summary": "List pets" })), true).is_empty());
+  }
+}
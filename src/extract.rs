@@ -0,0 +1,70 @@
+use serde_json::Value;
+
+use crate::client::HttpResponse;
+
+const HEADER_PREFIX: &str = "header:";
+
+/// Reads the value an extraction source points at: `header:<name>` for a response header, or a JSONPath such as
+/// `$.items[0].id` into the JSON body. Strings are returned as is, other JSON values serialized.
+pub fn extract(source: &str, response: &HttpResponse) -> Option<String> {
+  if let Some(name) = source.strip_prefix(HEADER_PREFIX) {
+    return response.header(name.trim()).map(String::from);
+  }
+  let body: Value = serde_json::from_str(&response.body).ok()?;
+  match json_path(&body, source)? {
+    Value::String(text) => Some(text.clone()),
+    value => Some(value.to_string()),
+  }
+}
+
+/// Number of items of the array or object, or of characters of the text, the source points at.
+pub fn length(source: &str, response: &HttpResponse) -> Option<usize> {
+  if let Some(name) = source.strip_prefix(HEADER_PREFIX) {
+    return response.header(name.trim()).map(|value| value.chars().count());
+  }
+  let body: Value = serde_json::from_str(&response.body).ok()?;
+  match json_path(&body, source)? {
+    Value::Array(items) => Some(items.len()),
+    Value::Object(members) => Some(members.len()),
+    Value::String(text) => Some(text.chars().count()),
+    _ => None,
+  }
+}
+
+/// Follows the member names and array indexes of a JSONPath, filters and wildcards are not supported.
+fn json_path<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+  let path = path.trim();
+  let path = path.strip_prefix('$').unwrap_or(path);
+  let mut current = value;
+  for segment in path.split('.').filter(|segment| !segment.is_empty()) {
+    let (name, indexes) = segment.split_at(segment.find('[').unwrap_or(segment.len()));
+    if !name.is_empty() {
+      current = current.get(name)?;
+    }
+    for index in indexes.split('[').filter(|index| !index.is_empty()) {
+      current = current.get(index.trim_end_matches(']').parse::<usize>().ok()?)?;
+    }
+  }
+  Some(current)
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_extract() {
+    let response = HttpResponse {
+      headers: vec![(String::from("Location"), String::from("/pet/42"))],
+      body: String::from(r#"{ "id": 42, "tags": [{ "name": "dog" }], "name": "rex" }"#),
+      ..HttpResponse::default()
+    };
+    assert_eq!(extract("$.id", &response).as_deref(), Some("42"));
+    assert_eq!(extract("$.name", &response).as_deref(), Some("rex"));
+    assert_eq!(extract("$.tags[0].name", &response).as_deref(), Some("dog"));
+    assert_eq!(extract("header:location", &response).as_deref(), Some("/pet/42"));
+    assert_eq!(extract("$.missing", &response), None);
+  }
+}
@@ -0,0 +1,611 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use crate::{
+  client::{Exchange, HttpResponse},
+  mock::{example, kind, resolve},
+  rate_limit,
+  request::HttpRequest,
+  script::Hooks,
+  workspace::{DraftParameter, OperationKey, RequestDraft},
+};
+
+/// Cases with random schema-valid inputs, in addition to the example, boundary and invalid ones.
+const RANDOM_CASES: usize = 20;
+const LONG_TEXT_LENGTH: i64 = 1024;
+/// Characters of the texts generated, whatever the `minLength` and `maxLength`, as specs often declare a
+/// `maxLength` of `2147483647`.
+const MAX_TEXT_LENGTH: i64 = 64 * 1024;
+/// Nesting after which random values stop following schemas.
+const MAX_DEPTH: usize = 6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CaseKind {
+  Valid,
+  Boundary,
+  Invalid,
+}
+
+/// Inputs sent to the operation, as a draft applied on top of its request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Case {
+  pub kind: CaseKind,
+  pub description: String,
+  pub draft: RequestDraft,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CaseResult {
+  pub exchange: Option<Exchange>,
+  /// Server errors and responses that do not match the spec.
+  pub problems: Vec<String>,
+  /// Why the request could not be sent.
+  pub error: Option<String>,
+}
+
+impl CaseResult {
+  pub fn failed(&self) -> bool {
+    self.error.is_some() || !self.problems.is_empty()
+  }
+
+  pub fn server_error(&self) -> bool {
+    self.exchange.as_ref().is_some_and(|exchange| exchange.response.status >= 500)
+  }
+}
+
+/// Parameter or body of an operation.
+struct Input {
+  location: String,
+  name: String,
+  schema: Value,
+  required: bool,
+}
+
+/// Small xorshift generator, seeded so that runs are repeatable.
+struct Rng(u64);
+
+impl Rng {
+  fn next(&mut self) -> u64 {
+    self.0 ^= self.0 << 13;
+    self.0 ^= self.0 >> 7;
+    self.0 ^= self.0 << 17;
+    self.0
+  }
+
+  fn below(&mut self, bound: usize) -> usize {
+    (self.next() % bound.max(1) as u64) as usize
+  }
+
+  fn between(&mut self, min: i64, max: i64) -> i64 {
+    min.saturating_add((self.next() % (max.saturating_sub(min).unsigned_abs().saturating_add(1)).max(1)) as i64)
+  }
+}
+
+/// Path item and operation at `paths.<path>.<method>` of the document.
+fn operation<'a>(document: &'a Value, path: &str, method: &str) -> Option<(&'a Value, &'a Value)> {
+  let item = document.get("paths")?.get(path)?;
+  Some((item, item.get(method.to_lowercase())?))
+}
+
+/// Draft with the example of every input of the operation.
+pub fn example_draft(document: &Value, path: &str, method: &str) -> RequestDraft {
+  let Some((item, operation)) = operation(document, path, method) else {
+    return RequestDraft::default();
+  };
+  let inputs = inputs(item, operation, document);
+  draft(&inputs.iter().map(|input| (input, example(&input.schema, document, 0))).collect::<Vec<_>>())
+}
+
+/// Cases for the operation: its examples, random valid inputs, then boundary and invalid values of every input, one
+/// input at a time.
+pub fn cases(document: &Value, path: &str, method: &str) -> Vec<Case> {
+  let Some((item, operation)) = operation(document, path, method) else {
+    return vec![];
+  };
+  let inputs = inputs(item, operation, document);
+  let base: Vec<(&Input, Value)> = inputs.iter().map(|input| (input, example(&input.schema, document, 0))).collect();
+  let case = |kind, description: String, values: &[(&Input, Value)]| Case { kind, description, draft: draft(values) };
+
+  let mut cases = vec![case(CaseKind::Valid, String::from("examples"), &base)];
+  let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+  for index in 0..RANDOM_CASES {
+    let values: Vec<_> = inputs.iter().map(|input| (input, random(&input.schema, document, &mut rng, 0))).collect();
+    cases.push(case(CaseKind::Valid, format!("random #{}", index + 1), &values));
+  }
+  for (index, (input, _)) in base.iter().enumerate() {
+    let schema = resolve(&input.schema, document);
+    let replaced = |value: Value| {
+      let mut values = base.clone();
+      values[index].1 = value;
+      values
+    };
+    for (value, note) in boundary_values(schema) {
+      cases.push(case(CaseKind::Boundary, format!("{} = {}", input.name, described(&value, note)), &replaced(value)));
+    }
+    for (value, note) in invalid_values(schema) {
+      cases.push(case(CaseKind::Invalid, format!("{} = {}", input.name, described(&value, note)), &replaced(value)));
+    }
+    if input.required {
+      let mut values = base.clone();
+      values.remove(index);
+      cases.push(case(CaseKind::Invalid, format!("{} missing", input.name), &values));
+    }
+    if input.location == "body" {
+      cases.extend(property_cases(input, &base, index, document));
+    }
+  }
+  cases
+}
+
+/// Boundary and invalid values of the body properties, along with the body without each required property.
+fn property_cases(input: &Input, base: &[(&Input, Value)], index: usize, document: &Value) -> Vec<Case> {
+  let schema = resolve(&input.schema, document);
+  let Some(Value::Object(body)) = base.get(index).map(|(_, value)| value) else {
+    return vec![];
+  };
+  let required: Vec<&str> =
+    schema.get("required").and_then(Value::as_array).into_iter().flatten().filter_map(Value::as_str).collect();
+  let with_body = |body: Map<String, Value>| {
+    let mut values = base.to_vec();
+    values[index].1 = Value::Object(body);
+    draft(&values)
+  };
+  let mut cases = vec![];
+  let properties = schema.get("properties").and_then(Value::as_object).into_iter().flatten();
+  for (name, property) in properties {
+    let property = resolve(property, document);
+    let kinds = [(CaseKind::Boundary, boundary_values(property)), (CaseKind::Invalid, invalid_values(property))];
+    for (kind, values) in kinds {
+      for (value, note) in values {
+        let description = format!("{}.{name} = {}", input.name, described(&value, note));
+        let mut body = body.clone();
+        body.insert(name.clone(), value);
+        cases.push(Case { kind, description, draft: with_body(body) });
+      }
+    }
+    if required.contains(&name.as_str()) {
+      let mut body = body.clone();
+      body.remove(name);
+      cases.push(Case {
+        kind: CaseKind::Invalid,
+        description: format!("{}.{name} missing", input.name),
+        draft: with_body(body),
+      });
+    }
+  }
+  let malformed = RequestDraft { body: Some(String::from("{ \"malformed\": ")), ..draft(base) };
+  cases.push(Case { kind: CaseKind::Invalid, description: String::from("malformed JSON body"), draft: malformed });
+  cases
+}
+
+fn inputs(item: &Value, operation: &Value, document: &Value) -> Vec<Input> {
+  let parameters = [item, operation]
+    .into_iter()
+    .filter_map(|owner| owner.get("parameters").and_then(Value::as_array))
+    .flatten()
+    .map(|parameter| resolve(parameter, document));
+  let mut inputs: Vec<Input> = vec![];
+  for parameter in parameters {
+    let (Some(location), Some(name)) =
+      (parameter.get("in").and_then(Value::as_str), parameter.get("name").and_then(Value::as_str))
+    else {
+      continue;
+    };
+    if !["path", "query", "header"].contains(&location) {
+      continue;
+    }
+    // Operation parameters override the path item ones.
+    inputs.retain(|input| input.location != location || input.name != name);
+    let mut schema = parameter.get("schema").cloned().unwrap_or(json!({ "type": "string" }));
+    if let (Some(example), Value::Object(schema)) = (parameter.get("example"), &mut schema) {
+      schema.insert(String::from("example"), example.clone());
+    }
+    let required = location == "path" || parameter.get("required").and_then(Value::as_bool).unwrap_or_default();
+    inputs.push(Input { location: location.to_string(), name: name.to_string(), schema, required });
+  }
+  let body = operation.get("requestBody").map(|body| resolve(body, document));
+  let media = body
+    .and_then(|body| body.get("content"))
+    .and_then(Value::as_object)
+    .and_then(|content| content.iter().find(|(media_type, _)| media_type.contains("json")));
+  if let Some((_, media)) = media {
+    let mut schema = media.get("schema").cloned().unwrap_or(json!({}));
+    if let (Some(example), Value::Object(schema)) = (media.get("example"), &mut schema) {
+      schema.insert(String::from("example"), example.clone());
+    }
+    let required = body.and_then(|body| body.get("required")).and_then(Value::as_bool).unwrap_or_default();
+    inputs.push(Input { location: String::from("body"), name: String::from("body"), schema, required });
+  }
+  inputs
+}
+
+fn draft(values: &[(&Input, Value)]) -> RequestDraft {
+  let mut draft = RequestDraft::default();
+  for (input, value) in values {
+    match input.location.as_str() {
+      "body" => draft.body = serde_json::to_string_pretty(value).ok(),
+      location => {
+        draft.parameters.push(DraftParameter {
+          location: location.to_string(),
+          name: input.name.clone(),
+          value: text(value),
+        })
+      },
+    }
+  }
+  draft
+}
+
+/// Parameter value as sent, arrays are joined with commas.
+fn text(value: &Value) -> String {
+  match value {
+    Value::String(text) => text.clone(),
+    Value::Array(values) => values.iter().map(text).collect::<Vec<_>>().join(","),
+    Value::Null => String::default(),
+    value => value.to_string(),
+  }
+}
+
+fn preview(value: &Value) -> String {
+  let text = value.to_string();
+  match text.chars().count() > 24 {
+    true => format!("{}… ({} chars)", text.chars().take(24).collect::<String>(), text.chars().count()),
+    false => text,
+  }
+}
+
+/// Preview of the value along with why it differs from what the schema asks for, e.g. a text capped in length.
+fn described(value: &Value, note: Option<String>) -> String {
+  match note {
+    Some(note) => format!("{} ({note})", preview(value)),
+    None => preview(value),
+  }
+}
+
+/// The character repeated `length` times, at most [`MAX_TEXT_LENGTH`], with a note when the length was capped.
+fn text_of_length(character: &str, length: i64) -> (Value, Option<String>) {
+  let capped = length.clamp(0, MAX_TEXT_LENGTH);
+  let note = (capped < length).then(|| format!("{length} chars capped at {MAX_TEXT_LENGTH}"));
+  (json!(character.repeat(capped as usize)), note)
+}
+
+fn number(schema: &Value, key: &str) -> Option<i64> {
+  schema.get(key).and_then(|value| value.as_i64().or(value.as_f64().map(|value| value as i64)))
+}
+
+fn random(schema: &Value, document: &Value, rng: &mut Rng, depth: usize) -> Value {
+  let schema = resolve(schema, document);
+  if depth > MAX_DEPTH {
+    return Value::Null;
+  }
+  if let Some(values) = schema.get("enum").and_then(Value::as_array).filter(|values| !values.is_empty()) {
+    return values[rng.below(values.len())].clone();
+  }
+  for key in ["oneOf", "anyOf"] {
+    if let Some(schemas) = schema.get(key).and_then(Value::as_array).filter(|schemas| !schemas.is_empty()) {
+      return random(&schemas[rng.below(schemas.len())], document, rng, depth + 1);
+    }
+  }
+  if schema.get("allOf").is_some() || schema.get("format").is_some() {
+    return example(schema, document, depth);
+  }
+  match kind(schema) {
+    Some("object") => {
+      let required: Vec<&str> =
+        schema.get("required").and_then(Value::as_array).into_iter().flatten().filter_map(Value::as_str).collect();
+      let mut object = Map::new();
+      for (name, property) in schema.get("properties").and_then(Value::as_object).into_iter().flatten() {
+        if required.contains(&name.as_str()) || rng.below(2) == 0 {
+          object.insert(name.clone(), random(property, document, rng, depth + 1));
+        }
+      }
+      Value::Object(object)
+    },
+    Some("array") => {
+      let length = rng.between(number(schema, "minItems").unwrap_or(0), number(schema, "maxItems").unwrap_or(3).min(3));
+      let items = schema.get("items").unwrap_or(&Value::Null);
+      Value::Array((0..length).map(|_| random(items, document, rng, depth + 1)).collect())
+    },
+    Some("string") => {
+      const CHARACTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+      let min = number(schema, "minLength").unwrap_or(0).clamp(0, MAX_TEXT_LENGTH);
+      let length = rng.between(min, number(schema, "maxLength").unwrap_or(i64::MAX).clamp(min, min.max(16)));
+      Value::String((0..length).map(|_| CHARACTERS[rng.below(CHARACTERS.len())] as char).collect())
+    },
+    Some("integer") | Some("number") => {
+      json!(rng.between(number(schema, "minimum").unwrap_or(-1000), number(schema, "maximum").unwrap_or(1000)))
+    },
+    Some("boolean") => json!(rng.below(2) == 0),
+    _ => example(schema, document, depth),
+  }
+}
+
+/// Valid values at the edges of what the schema accepts, each with a note when it had to be cut short.
+fn boundary_values(schema: &Value) -> Vec<(Value, Option<String>)> {
+  if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+    return values.iter().map(|value| (value.clone(), None)).collect();
+  }
+  let (minimum, maximum) = (number(schema, "minimum"), number(schema, "maximum"));
+  match kind(schema) {
+    Some("integer") | Some("number") => {
+      let mut values: Vec<Value> = [minimum, maximum].into_iter().flatten().map(|value| json!(value)).collect();
+      let zero = minimum.unwrap_or(0) <= 0 && maximum.unwrap_or(0) >= 0;
+      if zero && !values.contains(&json!(0)) {
+        values.push(json!(0));
+      }
+      if maximum.is_none() {
+        values.push(json!(i32::MAX));
+      }
+      values.into_iter().map(|value| (value, None)).collect()
+    },
+    Some("string") if schema.get("format").is_none() => {
+      let (min, max) = (number(schema, "minLength").unwrap_or(0), number(schema, "maxLength"));
+      vec![
+        text_of_length("a", min),
+        text_of_length("x", max.unwrap_or(LONG_TEXT_LENGTH)),
+        (json!("ünïcødé ✓ 😀"), None),
+      ]
+    },
+    Some("array") => vec![(json!([]), None)],
+    _ => vec![],
+  }
+}
+
+/// Values the schema rejects, of the wrong type or out of its bounds, each with a note when it had to be cut short.
+///
+/// Bounds at the ends of `i64` and a `maxLength` the capped texts cannot exceed have no value beyond them.
+fn invalid_values(schema: &Value) -> Vec<(Value, Option<String>)> {
+  let (minimum, maximum) = (number(schema, "minimum"), number(schema, "maximum"));
+  let mut values = match kind(schema) {
+    Some("integer") | Some("number") => {
+      let mut values = vec![json!("not-a-number")];
+      values.extend(minimum.and_then(|minimum| minimum.checked_sub(1)).map(|below| json!(below)));
+      values.extend(maximum.and_then(|maximum| maximum.checked_add(1)).map(|above| json!(above)));
+      if kind(schema) == Some("integer") {
+        values.push(json!(1.5));
+      }
+      values.into_iter().map(|value| (value, None)).collect()
+    },
+    Some("string") => {
+      let mut values = vec![(json!(12345), None)];
+      let min = number(schema, "minLength").filter(|min| *min > 0);
+      values.extend(min.map(|min| text_of_length("a", min - 1)));
+      let max = number(schema, "maxLength").filter(|max| (0..MAX_TEXT_LENGTH).contains(max));
+      values.extend(max.map(|max| text_of_length("x", max + 1)));
+      values
+    },
+    Some("boolean") => vec![(json!("maybe"), None)],
+    Some("array") => vec![(json!("not-an-array"), None)],
+    Some("object") => vec![(json!([]), None), (Value::Null, None)],
+    _ => vec![],
+  };
+  if schema.get("enum").is_some() {
+    values.push((json!("not-in-enum"), None));
+  }
+  values
+}
+
+/// Sends the request of a case, pacing it by the rate limit of the server, and checks its response against the
+/// operation responses.
+pub async fn run_case(
+  document: &Value,
+  operation: OperationKey,
+  request: HttpRequest,
+  hooks: &Hooks,
+  variables: &BTreeMap<String, String>,
+) -> CaseResult {
+  let (path, method) = (operation.path.clone(), operation.method.clone());
+  match rate_limit::exchange(Some(operation), request, hooks, variables).await {
+    Ok(exchange) => {
+      CaseResult {
+        problems: problems(document, &path, &method, &exchange.response),
+        exchange: Some(exchange),
+        error: None,
+      }
+    },
+    Err(error) => CaseResult { error: Some(error.to_string()), ..CaseResult::default() },
+  }
+}
+
+/// Server errors and mismatches between the response and the responses the operation documents.
+pub fn problems(document: &Value, path: &str, method: &str, response: &HttpResponse) -> Vec<String> {
+  let mut problems = vec![];
+  if response.status >= 500 {
+    problems.push(format!("server error {}", response.status));
+  }
+  let Some(responses) = operation(document, path, method).and_then(|(_, operation)| operation.get("responses")) else {
+    return problems;
+  };
+  let status = response.status.to_string();
+  let range = format!("{}XX", &status[..1]);
+  let documented = [status.as_str(), range.as_str(), "default"].into_iter().find_map(|key| responses.get(key));
+  let Some(documented) = documented.map(|documented| resolve(documented, document)) else {
+    problems.push(format!("status {status} is not documented"));
+    return problems;
+  };
+  let content_type = response.header("Content-Type").unwrap_or_default();
+  let media_type = content_type.split(';').next().unwrap_or_default().trim();
+  let schema = documented
+    .get("content")
+    .and_then(|content| content.get(media_type))
+    .and_then(|media| media.get("schema"))
+    .filter(|_| media_type.contains("json") && !response.body.is_empty());
+  if let Some(schema) = schema {
+    match serde_json::from_str::<Value>(&response.body) {
+      Ok(body) => problems.extend(violations(schema, &body, document, "$", 0)),
+      Err(_) => problems.push(String::from("the body is not valid JSON")),
+    }
+  }
+  problems
+}
+
+/// Where the value does not match the schema, a lightweight take on JSON Schema validation.
+pub fn violations(schema: &Value, value: &Value, document: &Value, at: &str, depth: usize) -> Vec<String> {
+  let schema = resolve(schema, document);
+  if depth > MAX_DEPTH * 2 {
+    return vec![];
+  }
+  let nullable = schema.get("nullable").and_then(Value::as_bool).unwrap_or_default()
+    || schema.get("type").and_then(Value::as_array).is_some_and(|kinds| kinds.contains(&json!("null")));
+  if value.is_null() && nullable {
+    return vec![];
+  }
+  if let Some(schemas) = schema.get("allOf").and_then(Value::as_array) {
+    return schemas.iter().flat_map(|schema| violations(schema, value, document, at, depth + 1)).collect();
+  }
+  for key in ["oneOf", "anyOf"] {
+    if let Some(schemas) = schema.get(key).and_then(Value::as_array) {
+      if schemas.iter().any(|schema| violations(schema, value, document, at, depth + 1).is_empty()) {
+        return vec![];
+      }
+      return vec![format!("{at}: matches none of the {key} schemas")];
+    }
+  }
+  if let Some(values) = schema.get("enum").and_then(Value::as_array).filter(|values| !values.contains(value)) {
+    return vec![format!("{at}: {} is not one of {}", preview(value), Value::Array(values.clone()))];
+  }
+  let expected = kind(schema);
+  let matches = match expected {
+    Some("object") => value.is_object(),
+    Some("array") => value.is_array(),
+    Some("string") => value.is_string(),
+    Some("integer") => value.is_i64() || value.is_u64(),
+    Some("number") => value.is_number(),
+    Some("boolean") => value.is_boolean(),
+    _ => true,
+  };
+  if !matches {
+    return vec![format!("{at}: expected {}, got {}", expected.unwrap_or_default(), preview(value))];
+  }
+  let mut found = vec![];
+  if let Some(number) = value.as_f64() {
+    if schema.get("minimum").and_then(Value::as_f64).is_some_and(|minimum| number < minimum) {
+      found.push(format!("{at}: {number} is below the minimum"));
+    }
+    if schema.get("maximum").and_then(Value::as_f64).is_some_and(|maximum| number > maximum) {
+      found.push(format!("{at}: {number} is above the maximum"));
+    }
+  }
+  if let Some(text) = value.as_str() {
+    let length = text.chars().count() as i64;
+    if number(schema, "minLength").is_some_and(|min| length < min)
+      || number(schema, "maxLength").is_some_and(|max| length > max)
+    {
+      found.push(format!("{at}: length {length} is out of bounds"));
+    }
+  }
+  if let Value::Object(object) = value {
+    let required = schema.get("required").and_then(Value::as_array).into_iter().flatten().filter_map(Value::as_str);
+    found.extend(required.filter(|name| !object.contains_key(*name)).map(|name| format!("{at}.{name}: is missing")));
+    for (name, property) in schema.get("properties").and_then(Value::as_object).into_iter().flatten() {
+      if let Some(value) = object.get(name) {
+        found.extend(violations(property, value, document, &format!("{at}.{name}"), depth + 1));
+      }
+    }
+  }
+  if let (Value::Array(items), Some(schema)) = (value, schema.get("items")) {
+    for (index, item) in items.iter().enumerate() {
+      found.extend(violations(schema, item, document, &format!("{at}[{index}]"), depth + 1));
+    }
+  }
+  found
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_cases() {
+    let document = json!({
+      "paths": {
+        "/pet/{petId}": {
+          "parameters": [{ "name": "petId", "in": "path", "schema": { "type": "integer", "minimum": 1 } }],
+          "put": {
+            "requestBody": {
+              "required": true,
+              "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Pet" } } }
+            },
+            "responses": {
+              "200": { "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Pet" } } } }
+            }
+          }
+        }
+      },
+      "components": {
+        "schemas": {
+          "Pet": {
+            "required": ["name"],
+            "properties": { "name": { "type": "string", "maxLength": 8 }, "tags": { "type": "array", "items": { "type": "string" } } }
+          }
+        }
+      }
+    });
+    let cases = cases(&document, "/pet/{petId}", "PUT");
+    assert_eq!(example_draft(&document, "/pet/{petId}", "put"), cases[0].draft);
+    assert_eq!(cases[0].draft.parameter("path", "petId"), Some("1"));
+    assert_eq!(
+      cases.len(),
+      cases.iter().map(|case| &case.description).collect::<std::collections::BTreeSet<_>>().len()
+    );
+    let descriptions: Vec<&str> = cases.iter().map(|case| case.description.as_str()).collect();
+    for description in
+      ["petId = 0", "petId = \"not-a-number\"", "body.name = \"xxxxxxxxx\"", "body.name missing", "body missing"]
+    {
+      assert!(descriptions.contains(&description), "{description} in {descriptions:?}");
+    }
+    assert!(cases.iter().filter(|case| case.kind == CaseKind::Valid).all(|case| {
+      let body: Value = serde_json::from_str(case.draft.body.as_deref().unwrap()).unwrap();
+      violations(&json!({ "$ref": "#/components/schemas/Pet" }), &body, &document, "$", 0).is_empty()
+    }));
+
+    let response = |status: u16, body: &str| {
+      HttpResponse {
+        status,
+        headers: vec![(String::from("Content-Type"), String::from("application/json; charset=utf-8"))],
+        body: body.to_string(),
+        ..HttpResponse::default()
+      }
+    };
+    assert_eq!(
+      problems(&document, "/pet/{petId}", "put", &response(200, r#"{ "name": "rex" }"#)),
+      Vec::<String>::new()
+    );
+    assert_eq!(problems(&document, "/pet/{petId}", "put", &response(200, r#"{ "tags": [1] }"#)), vec![
+      String::from("$.name: is missing"),
+      String::from("$.tags[0]: expected string, got 1"),
+    ]);
+    assert_eq!(problems(&document, "/pet/{petId}", "put", &response(500, "")), vec![
+      String::from("server error 500"),
+      String::from("status 500 is not documented"),
+    ]);
+  }
+
+  #[test]
+  fn test_extreme_bounds() {
+    let text = json!({ "type": "string", "minLength": -3, "maxLength": 2147483647 });
+    let lengths = |values: Vec<(Value, Option<String>)>| {
+      values
+        .into_iter()
+        .map(|(value, note)| (value.as_str().map_or(0, |text| text.chars().count()), note))
+        .collect::<Vec<_>>()
+    };
+    assert_eq!(lengths(boundary_values(&text)), vec![
+      (0, None),
+      (MAX_TEXT_LENGTH as usize, Some(format!("2147483647 chars capped at {MAX_TEXT_LENGTH}"))),
+      (11, None),
+    ]);
+    assert_eq!(lengths(invalid_values(&text)), vec![(0, None)]);
+    let mut rng = Rng(1);
+    let random = random(&json!({ "type": "string", "minLength": 2147483647 }), &Value::Null, &mut rng, 0);
+    assert_eq!(random.as_str().unwrap().len(), MAX_TEXT_LENGTH as usize);
+
+    let integer = json!({ "type": "integer", "minimum": i64::MIN, "maximum": i64::MAX });
+    let values: Vec<Value> = invalid_values(&integer).into_iter().map(|(value, _)| value).collect();
+    assert_eq!(values, vec![json!("not-a-number"), json!(1.5)]);
+  }
+}
@@ -0,0 +1,121 @@
+use std::{
+  path::{Path, PathBuf},
+  process,
+};
+
+use color_eyre::eyre::{eyre, Result};
+
+use crate::{
+  cli::{Cli, Command},
+  merge, remote,
+};
+
+/// Prefix of the specs read from a git reference, `git:<reference>:<path>`, e.g. `git:v1.2.0:openapi.yaml`.
+pub const PREFIX: &str = "git:";
+
+/// Reference and path of a spec given as `git:<reference>:<path>`.
+pub fn parse(path: &str) -> Option<(&str, &str)> {
+  let (reference, path) = path.strip_prefix(PREFIX)?.split_once(':')?;
+  (!reference.is_empty() && !path.is_empty()).then_some((reference, path))
+}
+
+/// Replaces the specs of the arguments read from a git reference, those given as `git:<reference>:<path>` or with
+/// `--git-ref` all those given by path, with the paths of their copies.
+pub fn resolve_args(args: &mut Cli) -> Result<()> {
+  let reference = args.git_ref.clone();
+  // the default --openapi-path, listed first, is left alone when the command is given its own spec
+  let own_spec = matches!(
+    args.command,
+    Some(
+      Command::Mock { spec: Some(_), .. }
+        | Command::Headless { spec: Some(_), .. }
+        | Command::List { spec: Some(_), .. }
+        | Command::ExportDocs { spec: Some(_), .. }
+        | Command::Call { .. }
+        | Command::Diff { .. }
+    )
+  );
+  for (index, spec) in args.specs_mut().into_iter().enumerate() {
+    *spec = resolve(spec, reference.as_deref().filter(|_| index > 0 || !own_spec))?;
+  }
+  Ok(())
+}
+
+/// Path of the spec: the path itself, or for a spec read from a git reference its copy written under the cache
+/// directory. Without `git:`, the spec is read from the reference when given one, unless given by url.
+pub fn resolve(spec: &str, reference: Option<&str>) -> Result<String> {
+  let (reference, path) = match (parse(spec), reference) {
+    (Some((reference, path)), _) => (reference, path),
+    (None, Some(reference)) if !remote::is_url(spec) => {
+      if merge::is_merge(spec) {
+        return Err(eyre!("--git-ref reads a single spec, give each of {spec} as git:{reference}:<path> instead"));
+      }
+      (reference, spec)
+    },
+    _ => return Ok(spec.to_string()),
+  };
+  let text = show(reference, Path::new(path))?;
+  let copy = remote::cache_dir().join(copy_name(reference, &std::env::current_dir()?.join(path)));
+  std::fs::create_dir_all(remote::cache_dir())?;
+  std::fs::write(&copy, text)?;
+  log::info!("Read {path} at {reference} into {}", copy.display());
+  Ok(copy.display().to_string())
+}
+
+/// Contents of the file at the reference of the repository it is in, even when since removed from the working tree.
+fn show(reference: &str, path: &Path) -> Result<Vec<u8>> {
+  let mut command = process::Command::new("git");
+  match path.parent().filter(|parent| path.is_absolute() && parent.is_dir()) {
+    // paths starting with `./` are relative to the directory git runs in, not to the root of the repository
+    Some(parent) => {
+      command
+        .arg("-C")
+        .arg(parent)
+        .arg("show")
+        .arg(format!("{reference}:./{}", path.file_name().unwrap_or_default().to_string_lossy()))
+    },
+    None => command.arg("show").arg(format!("{reference}:./{}", path.display())),
+  };
+  let output = command.output().map_err(|error| eyre!("Failed to run git: {error}"))?;
+  if !output.status.success() {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    return Err(eyre!("Failed to read {} at {reference}: {}", path.display(), stderr.trim()));
+  }
+  Ok(output.stdout)
+}
+
+/// File name of the copy of the spec at the reference, keeping its extension, e.g.
+/// `git_v1.2.0_home_me_api_openapi.yaml`.
+fn copy_name(reference: &str, absolute: &Path) -> PathBuf {
+  let name: String = format!("git_{reference}{}", absolute.display())
+    .chars()
+    .map(|c| if c.is_ascii_alphanumeric() || c == '.' || c == '-' { c } else { '_' })
+    .collect();
+  PathBuf::from(name)
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_parse() {
+    assert_eq!(parse("git:v1.2.0:openapi.yaml"), Some(("v1.2.0", "openapi.yaml")));
+    assert_eq!(parse("git:main:specs/openapi.json"), Some(("main", "specs/openapi.json")));
+    assert_eq!(parse("git::openapi.yaml"), None);
+    assert_eq!(parse("openapi.yaml"), None);
+    assert_eq!(
+      copy_name("feature/auth", Path::new("/home/me/api/openapi.yaml")),
+      PathBuf::from("git_feature_auth_home_me_api_openapi.yaml")
+    );
+  }
+
+  #[test]
+  fn test_resolve_untouched() {
+    assert_eq!(resolve("openapi.yaml", None).unwrap(), "openapi.yaml");
+    assert_eq!(resolve("https://example.com/openapi.yaml", Some("v1")).unwrap(), "https://example.com/openapi.yaml");
+    assert!(resolve("users.yaml,billing.yaml", Some("v1")).is_err());
+  }
+}
@@ -0,0 +1,176 @@
+use std::io::{BufRead, Write};
+
+use color_eyre::eyre::{eyre, Result};
+use serde::Serialize;
+
+use crate::{
+  assertion::{self, TestResult},
+  client::Exchange,
+  pages::home::Home,
+};
+
+/// Command of `openapi-tui headless`, one per line of its input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+  /// `select <operation>`, by id or method and path, forgetting the parameters and body set for the previous one.
+  Select(String),
+  /// `param <name>=<value>`
+  Param(String, String),
+  /// `body <text>`, or `body @<path>` to read it from a file.
+  Body(String),
+  /// `env <name>`
+  Environment(String),
+  /// `send`
+  Send,
+  /// `assert <assertion>` against the last response, as the assertions of saved requests, e.g. `$.name == doggie`.
+  /// `assert status <code>` is short for `assert $statusCode == <code>`.
+  Assert(String),
+}
+
+impl Command {
+  /// The command of the line, `None` for blank lines and `#` comments.
+  pub fn parse(line: &str) -> Result<Option<Self>> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+      return Ok(None);
+    }
+    let (name, argument) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+    let argument = argument.trim();
+    let required = |usage: &str| {
+      match argument.is_empty() {
+        true => Err(eyre!("usage: {usage}")),
+        false => Ok(argument.to_string()),
+      }
+    };
+    let command = match name {
+      "select" => Self::Select(required("select <operation>")?),
+      "param" => {
+        let (name, value) = argument.split_once('=').ok_or_else(|| eyre!("usage: param <name>=<value>"))?;
+        Self::Param(name.trim().to_string(), value.to_string())
+      },
+      "body" => Self::Body(required("body <text>")?),
+      "env" => Self::Environment(required("env <name>")?),
+      "send" => Self::Send,
+      "assert" => {
+        let assertion = required("assert <assertion>")?;
+        match assertion.strip_prefix("status ") {
+          Some(status) => Self::Assert(format!("$statusCode == {}", status.trim())),
+          None => Self::Assert(assertion),
+        }
+      },
+      name => return Err(eyre!("unknown command `{name}`")),
+    };
+    Ok(Some(command))
+  }
+}
+
+/// Outcome of a command, written as a line of JSON.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Outcome {
+  /// Line of the command.
+  pub command: String,
+  pub ok: bool,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub error: Option<String>,
+  /// Request sent by `send` along with its response.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub exchange: Option<Exchange>,
+  /// Result of `assert`.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub test: Option<TestResult>,
+}
+
+/// Body of the argument, read from the file it names when it starts with `@`.
+pub fn read_body(argument: &str) -> Result<String> {
+  match argument.strip_prefix('@') {
+    Some(path) => Ok(std::fs::read_to_string(path)?),
+    None => Ok(argument.to_string()),
+  }
+}
+
+/// Operation selected by the commands along with the inputs set for it, and the last exchange.
+#[derive(Debug, Default)]
+struct Session {
+  operation: Option<String>,
+  parameters: Vec<(String, String)>,
+  body: Option<String>,
+  exchange: Option<Exchange>,
+}
+
+/// Runs the commands of the input one after the other, writing the outcome of each to the output as it finishes,
+/// `false` when one failed.
+pub async fn run(home: &mut Home, input: impl BufRead, mut output: impl Write) -> Result<bool> {
+  let mut session = Session::default();
+  let mut passed = true;
+  for line in input.lines() {
+    let line = line?;
+    let outcome = match Command::parse(&line) {
+      Ok(Some(command)) => execute(home, &mut session, command).await,
+      Ok(None) => continue,
+      Err(error) => Err(error),
+    };
+    let outcome = match outcome {
+      Ok(outcome) => Outcome { command: line.trim().to_string(), ..outcome },
+      Err(error) => Outcome { command: line.trim().to_string(), error: Some(error.to_string()), ..Outcome::default() },
+    };
+    passed &= outcome.ok;
+    writeln!(output, "{}", serde_json::to_string(&outcome)?)?;
+    output.flush()?;
+  }
+  Ok(passed)
+}
+
+async fn execute(home: &mut Home, session: &mut Session, command: Command) -> Result<Outcome> {
+  let ok = Outcome { ok: true, ..Outcome::default() };
+  match command {
+    Command::Select(operation) => {
+      *session = Session { operation: Some(operation), exchange: session.exchange.take(), ..Session::default() };
+    },
+    Command::Param(name, value) => {
+      session.parameters.retain(|(existing, _)| existing != &name);
+      session.parameters.push((name, value));
+    },
+    Command::Body(body) => session.body = Some(read_body(&body)?),
+    Command::Environment(name) => home.use_environment(&name)?,
+    Command::Send => {
+      let operation = session.operation.as_deref().ok_or_else(|| eyre!("no operation selected"))?;
+      let exchange = home.call(operation, &session.parameters, session.body.clone()).await?;
+      session.exchange = Some(exchange.clone());
+      return Ok(Outcome { exchange: Some(exchange), ..ok });
+    },
+    Command::Assert(assertion) => {
+      let exchange = session.exchange.as_ref().ok_or_else(|| eyre!("no response received yet"))?;
+      let test = assertion::evaluate(&assertion, &exchange.response);
+      return Ok(Outcome { ok: test.passed, test: Some(test), ..ok });
+    },
+  }
+  Ok(ok)
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_parse() {
+    let parse = |line: &str| Command::parse(line).map_err(|error| error.to_string());
+    assert_eq!(parse("select getPetById").unwrap(), Some(Command::Select(String::from("getPetById"))));
+    assert_eq!(parse("  select POST /pet ").unwrap(), Some(Command::Select(String::from("POST /pet"))));
+    assert_eq!(parse("param petId=10").unwrap(), Some(Command::Param(String::from("petId"), String::from("10"))));
+    assert_eq!(parse("param q=a=b").unwrap(), Some(Command::Param(String::from("q"), String::from("a=b"))));
+    assert_eq!(
+      parse("body {\"name\": \"doggie\"}").unwrap(),
+      Some(Command::Body(String::from("{\"name\": \"doggie\"}")))
+    );
+    assert_eq!(parse("send").unwrap(), Some(Command::Send));
+    assert_eq!(parse("assert status 200").unwrap(), Some(Command::Assert(String::from("$statusCode == 200"))));
+    assert_eq!(parse("assert $.name == doggie").unwrap(), Some(Command::Assert(String::from("$.name == doggie"))));
+    assert_eq!(parse("# comment").unwrap(), None);
+    assert_eq!(parse("").unwrap(), None);
+    assert_eq!(parse("param petId").unwrap_err(), "usage: param <name>=<value>");
+    assert_eq!(parse("env").unwrap_err(), "usage: env <name>");
+    assert_eq!(parse("delete").unwrap_err(), "unknown command `delete`");
+  }
+}
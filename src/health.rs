@@ -0,0 +1,183 @@
+use std::time::{Duration, Instant};
+
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::{hosts, network::Network};
+
+/// Seconds between probes when `interval_secs` is unset.
+pub const DEFAULT_INTERVAL_SECS: u64 = 60;
+/// Seconds a probe may take before the server is deemed down, unless the network of the environment sets a timeout.
+const TIMEOUT_SECS: u64 = 5;
+
+/// The `health` section of the config, probing the base url of the selected server in the background, e.g.
+/// `{ path: "/healthz", interval_secs: 30 }`.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct HealthConfig {
+  /// Path probed below the base url, the base url itself when unset.
+  #[serde(default)]
+  pub path: Option<String>,
+  /// Seconds between probes of the same base url, see [`DEFAULT_INTERVAL_SECS`].
+  #[serde(default)]
+  pub interval_secs: Option<u64>,
+}
+
+impl HealthConfig {
+  /// Whether the base url is to be probed, it was not yet or the last probe, of the given base url and time, is older
+  /// than the interval.
+  pub fn due(&self, last: Option<&(String, Instant)>, base_url: &str, now: Instant) -> bool {
+    let interval = Duration::from_secs(self.interval_secs.unwrap_or(DEFAULT_INTERVAL_SECS));
+    match last {
+      Some((probed, at)) => probed != base_url || now.duration_since(*at) >= interval,
+      None => true,
+    }
+  }
+}
+
+/// Whether the certificate of the server is trusted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Tls {
+  /// Plain HTTP.
+  Plain,
+  Valid,
+  /// The server answered once its certificate was accepted, with why it is not trusted.
+  Invalid(String),
+}
+
+/// Result of probing a base url with a `HEAD`, or a `GET` when the server does not allow it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Health {
+  /// Base url as shown in the address pane, before its variables are substituted.
+  pub base_url: String,
+  /// Url probed.
+  pub url: String,
+  /// Status the server answered with, `None` when it could not be reached.
+  pub status: Option<u16>,
+  pub latency_ms: u64,
+  pub tls: Tls,
+  /// Why the server could not be reached.
+  pub error: Option<String>,
+}
+
+impl Health {
+  pub fn reachable(&self) -> bool {
+    self.status.is_some()
+  }
+
+  /// Whether the server is reachable, trusted and not failing, a 404 of a base url without a route still telling it
+  /// is up.
+  pub fn healthy(&self) -> bool {
+    self.status.is_some_and(|status| status < 500) && !matches!(self.tls, Tls::Invalid(_))
+  }
+
+  /// Summary shown in the address pane, e.g. `● up 200 · 42 ms · TLS ✓`.
+  pub fn label(&self) -> String {
+    let Some(status) = self.status else {
+      return format!("● down · {}", self.error.as_deref().unwrap_or("unreachable"));
+    };
+    let state = match status < 500 {
+      true => "up",
+      false => "failing",
+    };
+    let tls = match &self.tls {
+      Tls::Plain => "",
+      Tls::Valid => " · TLS ✓",
+      Tls::Invalid(_) => " · TLS ✗",
+    };
+    format!("● {state} {status} · {} ms{tls}", self.latency_ms)
+  }
+}
+
+/// The url probed for the base url, the path of the config below it.
+pub fn url(base_url: &str, path: Option<&str>) -> String {
+  match path.filter(|path| !path.is_empty()) {
+    Some(path) => format!("{}/{}", base_url.trim_end_matches('/'), path.trim_start_matches('/')),
+    None => base_url.to_string(),
+  }
+}
+
+/// Probes the url with the network settings of the environment, telling an untrusted certificate from a server down
+/// by probing an https url again with any certificate accepted.
+pub async fn probe(base_url: String, url: String, network: Option<Network>) -> Health {
+  let mut started = Instant::now();
+  let (status, tls, error) = match request(&url, network.as_ref(), false).await {
+    Ok(status) if url.starts_with("https://") => (Some(status), Tls::Valid, None),
+    Ok(status) => (Some(status), Tls::Plain, None),
+    Err(error) if url.starts_with("https://") => {
+      let cause = error.root_cause().to_string();
+      started = Instant::now();
+      match request(&url, network.as_ref(), true).await {
+        Ok(status) => (Some(status), Tls::Invalid(cause), None),
+        Err(_) => (None, Tls::Valid, Some(cause)),
+      }
+    },
+    Err(error) => (None, Tls::Plain, Some(error.root_cause().to_string())),
+  };
+  let latency_ms = started.elapsed().as_millis() as u64;
+  Health { base_url, url, status, latency_ms, tls, error }
+}
+
+async fn request(url: &str, network: Option<&Network>, accept_invalid_certs: bool) -> Result<u16> {
+  let mut client = reqwest::Client::builder()
+    .timeout(Duration::from_secs(TIMEOUT_SECS))
+    .danger_accept_invalid_certs(accept_invalid_certs);
+  let network_override = match network {
+    Some(network) => network.host_override(url)?,
+    None => None,
+  };
+  if let Some(host_override) = network_override.or_else(|| hosts::find(url)) {
+    client = client.resolve(&host_override.host, host_override.socket_addr());
+  }
+  if let Some(network) = network {
+    client = network.configure(client)?;
+  }
+  let client = client.build()?;
+  let status = client.head(url).send().await?.status();
+  match status {
+    reqwest::StatusCode::METHOD_NOT_ALLOWED | reqwest::StatusCode::NOT_IMPLEMENTED => {
+      Ok(client.get(url).send().await?.status().as_u16())
+    },
+    status => Ok(status.as_u16()),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_health() {
+    assert_eq!(url("https://api.example.com/v1/", Some("/healthz")), "https://api.example.com/v1/healthz");
+    assert_eq!(url("http://localhost:8080", Some("")), "http://localhost:8080");
+    assert_eq!(url("http://localhost:8080", None), "http://localhost:8080");
+
+    let config = HealthConfig { path: None, interval_secs: Some(30) };
+    let now = Instant::now();
+    let last = (String::from("http://localhost:8080"), now);
+    assert!(config.due(None, "http://localhost:8080", now));
+    assert!(!config.due(Some(&last), "http://localhost:8080", now + Duration::from_secs(10)));
+    assert!(config.due(Some(&last), "http://localhost:8080", now + Duration::from_secs(30)));
+    assert!(config.due(Some(&last), "https://staging.example.com", now));
+
+    let health = Health {
+      base_url: String::from("https://api.example.com"),
+      url: String::from("https://api.example.com"),
+      status: Some(404),
+      latency_ms: 42,
+      tls: Tls::Valid,
+      error: None,
+    };
+    assert!(health.healthy());
+    assert_eq!(health.label(), "● up 404 · 42 ms · TLS ✓");
+    let invalid = Health { tls: Tls::Invalid(String::from("certificate expired")), ..health.clone() };
+    assert!(!invalid.healthy());
+    assert_eq!(invalid.label(), "● up 404 · 42 ms · TLS ✗");
+    let failing = Health { status: Some(503), tls: Tls::Plain, ..health.clone() };
+    assert_eq!(failing.label(), "● failing 503 · 42 ms");
+    let down = Health { status: None, error: Some(String::from("Connection refused")), ..health };
+    assert!(!down.reachable());
+    assert_eq!(down.label(), "● down · Connection refused");
+  }
+}
@@ -0,0 +1,107 @@
+use std::{
+  collections::HashMap,
+  path::PathBuf,
+  sync::{
+    atomic::{AtomicU64, Ordering},
+    Mutex,
+  },
+};
+
+use color_eyre::eyre::Result;
+use lazy_static::lazy_static;
+
+use crate::{client::Exchange, workspace::Workspace};
+
+/// Oldest entries are dropped beyond this count.
+const MAX_ENTRIES: usize = 500;
+
+/// Number of the last save started, across specs.
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+lazy_static! {
+  /// Number of the last save written for each spec, so that a save finishing late does not overwrite a newer one.
+  static ref WRITTEN: Mutex<HashMap<String, u64>> = Mutex::new(HashMap::new());
+}
+
+/// Requests sent for a spec, oldest first, kept next to the workspace in
+/// `.openapi-tui/<spec file name>.history.json`.
+pub fn path(openapi_path: &str) -> PathBuf {
+  Workspace::path(openapi_path).with_extension("history.json")
+}
+
+pub fn load(openapi_path: &str) -> Result<Vec<Exchange>> {
+  let path = path(openapi_path);
+  if !path.exists() {
+    return Ok(vec![]);
+  }
+  Ok(serde_json::from_reader(std::fs::File::open(path)?)?)
+}
+
+pub fn save(openapi_path: &str, history: &[Exchange]) -> Result<()> {
+  let path = path(openapi_path);
+  if let Some(parent) = path.parent() {
+    std::fs::create_dir_all(parent)?;
+  }
+  std::fs::write(path, serde_json::to_string(recent(history))?)?;
+  Ok(())
+}
+
+/// Saves the history on a blocking thread when there is a runtime, so that writing up to `MAX_ENTRIES` exchanges does
+/// not hold up the UI, and right away otherwise.
+pub fn save_in_background(openapi_path: &str, history: &[Exchange]) -> Result<()> {
+  let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+    return save(openapi_path, history);
+  };
+  let generation = GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+  let openapi_path = openapi_path.to_string();
+  let history = recent(history).to_vec();
+  runtime.spawn_blocking(move || {
+    let mut written = WRITTEN.lock().unwrap();
+    if written.get(&openapi_path).is_some_and(|written| *written > generation) {
+      return;
+    }
+    if let Err(error) = save(&openapi_path, &history) {
+      log::error!("Failed to save the history of {openapi_path}: {error:?}");
+    }
+    written.insert(openapi_path, generation);
+  });
+  Ok(())
+}
+
+fn recent(history: &[Exchange]) -> &[Exchange] {
+  &history[history.len().saturating_sub(MAX_ENTRIES)..]
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_path() {
+    assert_eq!(path("specs/petstore.yaml"), PathBuf::from("specs/.openapi-tui/petstore.yaml.history.json"));
+    let history = vec![Exchange::default(); MAX_ENTRIES + 2];
+    assert_eq!(recent(&history).len(), MAX_ENTRIES);
+  }
+
+  #[test]
+  fn test_save_in_background() -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("openapi-tui-history-{}", std::process::id()));
+    let openapi_path = dir.join("petstore.yaml").to_string_lossy().to_string();
+    let runtime = tokio::runtime::Runtime::new()?;
+    let mut history = vec![Exchange::default()];
+    {
+      let _guard = runtime.enter();
+      save_in_background(&openapi_path, &history)?;
+      history.push(Exchange::default());
+      save_in_background(&openapi_path, &history)?;
+    }
+    // dropping the runtime waits for the saves
+    drop(runtime);
+    let saved = load(&openapi_path)?;
+    std::fs::remove_dir_all(&dir)?;
+    assert_eq!(saved.len(), 2);
+    Ok(())
+  }
+}
@@ -0,0 +1,88 @@
+use std::{
+  net::{IpAddr, SocketAddr},
+  str::FromStr,
+  sync::RwLock,
+};
+
+use color_eyre::eyre::{eyre, Report, Result};
+use lazy_static::lazy_static;
+
+lazy_static! {
+  static ref OVERRIDES: RwLock<Vec<HostOverride>> = RwLock::new(Vec::new());
+}
+
+/// Address the requests to a host and port are sent to instead of the one DNS resolves, as `curl --resolve`, e.g.
+/// `api.example.com:443:127.0.0.1` to reach a local container without editing `/etc/hosts`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostOverride {
+  pub host: String,
+  pub port: u16,
+  pub address: IpAddr,
+}
+
+impl FromStr for HostOverride {
+  type Err = Report;
+
+  fn from_str(text: &str) -> Result<Self> {
+    let invalid = || eyre!("Invalid host override `{text}`, expected `host:port:address`");
+    let mut parts = text.splitn(3, ':');
+    let (Some(host), Some(port), Some(address)) = (parts.next(), parts.next(), parts.next()) else {
+      return Err(invalid());
+    };
+    let address = address.trim_start_matches('[').trim_end_matches(']');
+    Ok(Self {
+      host: host.to_lowercase(),
+      port: port.parse().map_err(|_| invalid())?,
+      address: address.parse().map_err(|_| invalid())?,
+    })
+  }
+}
+
+impl HostOverride {
+  pub fn socket_addr(&self) -> SocketAddr {
+    SocketAddr::new(self.address, self.port)
+  }
+}
+
+/// Overrides in use, the first one of a host and port winning.
+pub fn set(overrides: Vec<HostOverride>) {
+  *OVERRIDES.write().unwrap() = overrides;
+}
+
+/// The override of the host and port of the url, its scheme telling the port when it has none.
+pub fn find(url: &str) -> Option<HostOverride> {
+  matching(&OVERRIDES.read().unwrap(), url)
+}
+
+/// The first of the overrides for the host and port of the url.
+pub fn matching(overrides: &[HostOverride], url: &str) -> Option<HostOverride> {
+  let url = reqwest::Url::parse(url).ok()?;
+  let (host, port) = (url.host_str()?, url.port_or_known_default()?);
+  overrides.iter().find(|other| other.host == host && other.port == port).cloned()
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_host_override() {
+    let host_override: HostOverride = "API.example.com:443:127.0.0.1".parse().unwrap();
+    assert_eq!(host_override, HostOverride {
+      host: String::from("api.example.com"),
+      port: 443,
+      address: IpAddr::from([127, 0, 0, 1]),
+    });
+    let ipv6: HostOverride = "api.example.com:8080:[::1]".parse().unwrap();
+    assert_eq!(ipv6.socket_addr().to_string(), "[::1]:8080");
+    assert!("api.example.com:127.0.0.1".parse::<HostOverride>().is_err());
+
+    set(vec![host_override.clone(), ipv6]);
+    assert_eq!(find("https://api.example.com/v1/pets"), Some(host_override));
+    assert_eq!(find("http://api.example.com:8080").map(|found| found.port), Some(8080));
+    assert_eq!(find("http://api.example.com/v1"), None);
+    set(vec![]);
+  }
+}
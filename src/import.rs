@@ -0,0 +1,165 @@
+use std::path::Path;
+
+use color_eyre::eyre::Result;
+use oas3::Spec;
+
+use crate::{
+  request::{decode_component, Auth, HttpRequest},
+  workspace::{Environment, OperationKey, SavedRequest},
+};
+
+pub mod har;
+pub mod http;
+pub mod insomnia;
+pub mod postman;
+
+/// A request read from a foreign file format, before it is matched against the spec.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportedRequest {
+  pub name: String,
+  pub method: String,
+  pub url: String,
+  pub headers: Vec<(String, String)>,
+  pub body: Option<String>,
+  pub folder: Option<String>,
+  pub auth: Option<Auth>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Imported {
+  pub saved_requests: Vec<SavedRequest>,
+  pub environments: Vec<Environment>,
+}
+
+/// Folder of the requests imported from HAR files, each file gets its own sub folder.
+pub const HAR_FOLDER: &str = "HAR";
+
+/// Imports a `.http` file, a Postman collection, an Insomnia export or a HAR capture, detected from the
+/// file content.
+pub fn import_file(spec: &Spec, path: &Path) -> Result<Imported> {
+  let content = std::fs::read_to_string(path)?;
+  let (requests, environments) = match serde_yaml::from_str::<serde_json::Value>(&content) {
+    Ok(document) if postman::is_collection(&document) => postman::parse(&document)?,
+    Ok(document) if insomnia::is_export(&document) => insomnia::parse(&document)?,
+    Ok(document) if har::is_archive(&document) => {
+      let name = path.file_stem().unwrap_or_default().to_string_lossy();
+      (har::parse(&document, &format!("{HAR_FOLDER}/{name}")), vec![])
+    },
+    _ => (http::parse(&content), vec![]),
+  };
+  Ok(Imported {
+    saved_requests: requests.into_iter().map(|request| saved_request(spec, request)).collect(),
+    environments,
+  })
+}
+
+/// Splits an url into its origin, path and decoded query pairs. Urls without a scheme are treated as
+/// `host/path`, unless they start with `/`.
+pub fn split_url(url: &str) -> (String, String, Vec<(String, String)>) {
+  let url = url.split('#').next().unwrap_or_default();
+  let (url, query) = url.split_once('?').unwrap_or((url, ""));
+  let authority_start = url.find("://").map(|index| index + 3).unwrap_or_default();
+  let (origin, path) = match url[authority_start..].find('/') {
+    Some(index) => url.split_at(authority_start + index),
+    None => (url, ""),
+  };
+  let query = query
+    .split('&')
+    .filter(|pair| !pair.is_empty())
+    .map(|pair| {
+      let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+      (decode_component(key), decode_component(value))
+    })
+    .collect();
+  (origin.to_string(), path.to_string(), query)
+}
+
+fn is_template(segment: &str) -> bool {
+  segment.contains('{')
+}
+
+/// Finds the spec path template matching the end of `path` for `method`. Literal segments must match
+/// exactly, templated segments match any non-empty segment; templates with more literal segments win.
+/// Returns the template along with the length of the `path` prefix that precedes it, i.e. the server
+/// base path.
+pub fn match_operation(spec: &Spec, method: &str, path: &str) -> Option<(String, usize)> {
+  let segments: Vec<&str> = path.trim_end_matches('/').split('/').skip(1).collect();
+  spec
+    .operations()
+    .filter(|(_, operation_method, _)| operation_method.as_str().eq_ignore_ascii_case(method))
+    .filter_map(|(template, ..)| {
+      let template_segments: Vec<&str> = template.trim_end_matches('/').split('/').skip(1).collect();
+      if template_segments.len() > segments.len() {
+        return None;
+      }
+      let prefix_len = segments.len() - template_segments.len();
+      let matched = template_segments.iter().zip(&segments[prefix_len..]).all(|(template_segment, segment)| {
+        if is_template(template_segment) {
+          !segment.is_empty()
+        } else {
+          template_segment == segment
+        }
+      });
+      if !matched {
+        return None;
+      }
+      let literals = template_segments.iter().filter(|segment| !is_template(segment)).count();
+      let prefix = segments[..prefix_len].iter().map(|segment| segment.len() + 1).sum::<usize>();
+      Some((literals, std::cmp::Reverse(prefix_len), template, prefix))
+    })
+    .max_by(|a, b| (a.0, a.1).cmp(&(b.0, b.1)))
+    .map(|(_, _, template, prefix)| (template, prefix))
+}
+
+pub fn saved_request(spec: &Spec, imported: ImportedRequest) -> SavedRequest {
+  let (origin, path, query) = split_url(&imported.url);
+  let method = imported.method.to_uppercase();
+  let (operation, base_url, path) = match match_operation(spec, &method, &path) {
+    Some((template, prefix)) => {
+      let (base_path, path) = path.split_at(prefix);
+      (Some(OperationKey::new(&method, &template)), format!("{origin}{base_path}"), path.to_string())
+    },
+    None => (None, origin, path),
+  };
+  SavedRequest {
+    name: imported.name,
+    operation,
+    request: HttpRequest {
+      method,
+      base_url,
+      path,
+      query,
+      headers: imported.headers,
+      body: imported.body,
+      form: vec![],
+      digest: None,
+      network: None,
+    },
+    folder: imported.folder,
+    auth: imported.auth,
+    assertions: Vec::default(),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_split_url() {
+    assert_eq!(
+      split_url("https://example.com:8080/api/v3/pet/findByStatus?status=sold%20out&limit=1#top"),
+      (String::from("https://example.com:8080"), String::from("/api/v3/pet/findByStatus"), vec![
+        (String::from("status"), String::from("sold out")),
+        (String::from("limit"), String::from("1"))
+      ])
+    );
+    assert_eq!(
+      split_url("{{baseUrl}}/pet/{{petId}}"),
+      (String::from("{{baseUrl}}"), String::from("/pet/{{petId}}"), vec![])
+    );
+    assert_eq!(split_url("/pet"), (String::default(), String::from("/pet"), vec![]));
+  }
+}
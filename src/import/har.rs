@@ -0,0 +1,85 @@
+use serde_json::Value;
+
+use crate::import::ImportedRequest;
+
+/// Headers set by the browser or the transport, they are recomputed when the request is replayed.
+const SKIPPED_HEADERS: [&str; 4] = ["host", "content-length", "connection", "accept-encoding"];
+
+pub fn is_archive(document: &Value) -> bool {
+  document.pointer("/log/entries").is_some_and(Value::is_array)
+}
+
+/// Reads the requests captured in a HAR file, they are all placed in `folder`.
+pub fn parse(document: &Value, folder: &str) -> Vec<ImportedRequest> {
+  let text = |value: &Value, key: &str| value.get(key).and_then(Value::as_str).unwrap_or_default().to_string();
+  document
+    .pointer("/log/entries")
+    .and_then(Value::as_array)
+    .into_iter()
+    .flatten()
+    .filter_map(|entry| entry.get("request"))
+    .map(|request| {
+      let method = text(request, "method").to_uppercase();
+      let url = text(request, "url");
+      let headers = request
+        .get("headers")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .map(|header| (text(header, "name"), text(header, "value")))
+        .filter(|(name, _)| !name.starts_with(':') && !SKIPPED_HEADERS.contains(&name.to_lowercase().as_str()))
+        .collect();
+      let body = request.get("postData").map(|post_data| text(post_data, "text")).filter(|body| !body.is_empty());
+      let path =
+        url.split_once("://").map_or(url.as_str(), |(_, rest)| rest.find('/').map_or("/", |index| &rest[index..]));
+      ImportedRequest {
+        name: format!("{method} {}", path.split('?').next().unwrap_or_default()),
+        method,
+        url: url.clone(),
+        headers,
+        body,
+        folder: Some(folder.to_string()),
+        auth: None,
+      }
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_parse() {
+    let document = serde_json::json!({
+      "log": {
+        "version": "1.2",
+        "entries": [{
+          "request": {
+            "method": "POST",
+            "url": "https://petstore.swagger.io/v2/pet?dryRun=true",
+            "headers": [
+              { "name": ":authority", "value": "petstore.swagger.io" },
+              { "name": "Content-Length", "value": "2" },
+              { "name": "Content-Type", "value": "application/json" }
+            ],
+            "postData": { "mimeType": "application/json", "text": "{}" }
+          },
+          "response": { "status": 200 }
+        }]
+      }
+    });
+    assert!(is_archive(&document));
+    assert_eq!(parse(&document, "HAR/capture"), vec![ImportedRequest {
+      name: String::from("POST /v2/pet"),
+      method: String::from("POST"),
+      url: String::from("https://petstore.swagger.io/v2/pet?dryRun=true"),
+      headers: vec![(String::from("Content-Type"), String::from("application/json"))],
+      body: Some(String::from("{}")),
+      folder: Some(String::from("HAR/capture")),
+      auth: None,
+    }]);
+  }
+}
@@ -0,0 +1,124 @@
+use std::collections::BTreeMap;
+
+use crate::{import::ImportedRequest, request::substitute};
+
+const METHODS: [&str; 9] = ["GET", "POST", "PUT", "DELETE", "PATCH", "HEAD", "OPTIONS", "TRACE", "CONNECT"];
+
+/// Parses a VS Code REST Client / JetBrains HTTP Client file. Requests are separated by `###` lines,
+/// file variables (`@name = value`) with a value are substituted, empty ones are kept as `{{name}}`.
+pub fn parse(content: &str) -> Vec<ImportedRequest> {
+  let mut variables = BTreeMap::new();
+  let mut requests = vec![];
+
+  for block in split_blocks(content) {
+    let mut name = block.title.clone();
+    let mut lines = block.lines.iter().map(|line| line.trim_end()).peekable();
+    let mut request_line = None;
+
+    for line in lines.by_ref() {
+      let trimmed = line.trim();
+      if let Some(variable) = trimmed.strip_prefix('@') {
+        if let Some((key, value)) = variable.split_once('=') {
+          if !value.trim().is_empty() {
+            variables.insert(key.trim().to_string(), value.trim().to_string());
+          }
+        }
+        continue;
+      }
+      if let Some(comment) = trimmed.strip_prefix('#').or_else(|| trimmed.strip_prefix("//")) {
+        if let Some(request_name) = comment.trim().strip_prefix("@name") {
+          name = request_name.trim().to_string();
+        }
+        continue;
+      }
+      if !trimmed.is_empty() {
+        request_line = Some(trimmed.to_string());
+        break;
+      }
+    }
+    let Some(request_line) = request_line else {
+      continue;
+    };
+
+    let mut tokens = request_line.split_whitespace();
+    let first = tokens.next().unwrap_or_default();
+    let (method, mut url) = if METHODS.contains(&first.to_uppercase().as_str()) {
+      (first.to_uppercase(), tokens.next().unwrap_or_default().to_string())
+    } else {
+      (String::from("GET"), first.to_string())
+    };
+    while let Some(line) = lines.next_if(|line| line.trim_start().starts_with(['?', '&'])) {
+      url.push_str(line.trim());
+    }
+
+    let mut headers = vec![];
+    for line in lines.by_ref() {
+      if line.trim().is_empty() {
+        break;
+      }
+      if let Some((key, value)) = line.split_once(':') {
+        headers.push((key.trim().to_string(), substitute(value.trim(), &variables)));
+      }
+    }
+
+    let body = lines.collect::<Vec<_>>().join("\n").trim().to_string();
+    requests.push(ImportedRequest {
+      name: if name.is_empty() { format!("{method} {url}") } else { name },
+      method,
+      url: substitute(&url, &variables),
+      headers,
+      body: if body.is_empty() { None } else { Some(substitute(&body, &variables)) },
+      ..ImportedRequest::default()
+    });
+  }
+  requests
+}
+
+struct Block {
+  title: String,
+  lines: Vec<String>,
+}
+
+fn split_blocks(content: &str) -> Vec<Block> {
+  let mut blocks = vec![Block { title: String::default(), lines: vec![] }];
+  for line in content.lines() {
+    if let Some(title) = line.trim_start().strip_prefix("###") {
+      blocks.push(Block { title: title.trim().to_string(), lines: vec![] });
+    } else if let Some(block) = blocks.last_mut() {
+      block.lines.push(line.to_string());
+    }
+  }
+  blocks
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_parse() {
+    let content = "@baseUrl = https://petstore.swagger.io/v2\n@petId =\n\n### getPetById\nGET \
+                   {{baseUrl}}/pet/{{petId}} HTTP/1.1\napi_key: secret\n\n###\n# @name addPet\nPOST \
+                   {{baseUrl}}/pet\n  ?dryRun=true\nContent-Type: application/json\n\n{\n  \"name\": \"doggie\"\n}\n";
+    assert_eq!(parse(content), vec![
+      ImportedRequest {
+        name: String::from("getPetById"),
+        method: String::from("GET"),
+        url: String::from("https://petstore.swagger.io/v2/pet/{{petId}}"),
+        headers: vec![(String::from("api_key"), String::from("secret"))],
+        body: None,
+        ..ImportedRequest::default()
+      },
+      ImportedRequest {
+        name: String::from("addPet"),
+        method: String::from("POST"),
+        url: String::from("https://petstore.swagger.io/v2/pet?dryRun=true"),
+        headers: vec![(String::from("Content-Type"), String::from("application/json"))],
+        body: Some(String::from("{\n  \"name\": \"doggie\"\n}")),
+        ..ImportedRequest::default()
+      },
+    ]);
+  }
+}
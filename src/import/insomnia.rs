@@ -0,0 +1,292 @@
+use std::collections::{BTreeMap, HashMap};
+
+use color_eyre::eyre::Result;
+use serde_json::Value;
+
+use crate::{
+  import::ImportedRequest,
+  request::{encode_component, Auth},
+  workspace::Environment,
+};
+
+/// Matches Insomnia v4 exports (`__export_format: 4`) and v5 collections (`type: collection.insomnia.rest/5.0`).
+pub fn is_export(document: &Value) -> bool {
+  document.get("__export_format").is_some()
+    || text(document.get("type")).is_some_and(|kind| kind.starts_with("collection.insomnia.rest"))
+}
+
+/// Converts an Insomnia export into requests and environments, rewriting Insomnia templates such as
+/// `{{ _.baseUrl }}` to `{{baseUrl}}`.
+pub fn parse(document: &Value) -> Result<(Vec<ImportedRequest>, Vec<Environment>)> {
+  let mut requests = vec![];
+  let mut environments = vec![];
+  if document.get("resources").is_some() {
+    parse_resources(document, &mut requests, &mut environments);
+  } else {
+    collect_items(document.get("collection"), None, &None, &mut requests);
+    if let Some(base) = document.get("environments") {
+      push_environments(base, &BTreeMap::new(), &mut environments);
+    }
+  }
+  Ok((requests, environments))
+}
+
+fn text(value: Option<&Value>) -> Option<&str> {
+  value.and_then(Value::as_str)
+}
+
+fn is_disabled(value: &Value) -> bool {
+  value.get("disabled").and_then(Value::as_bool).unwrap_or_default()
+}
+
+/// Rewrites `{{ _.name }}` and `{{ name }}` to `{{name}}`, leaving template tags such as `{% ... %}` untouched.
+pub fn normalize_template(text: &str) -> String {
+  let mut normalized = String::with_capacity(text.len());
+  let mut rest = text;
+  while let Some(start) = rest.find("{{") {
+    let Some(end) = rest[start..].find("}}").map(|end| start + end) else {
+      break;
+    };
+    let name = rest[start + 2..end].trim();
+    let name = name.strip_prefix("_.").unwrap_or(name);
+    normalized.push_str(&rest[..start]);
+    normalized.push_str(format!("{{{{{name}}}}}").as_str());
+    rest = &rest[end + 2..];
+  }
+  normalized.push_str(rest);
+  normalized
+}
+
+/// Flattens nested environment data, nested keys are joined with `.` the same way Insomnia references them.
+fn flatten_data(prefix: &str, data: &Value, variables: &mut BTreeMap<String, String>) {
+  match data {
+    Value::Object(object) => {
+      for (key, value) in object {
+        let key = if prefix.is_empty() { key.clone() } else { format!("{prefix}.{key}") };
+        flatten_data(&key, value, variables);
+      }
+    },
+    Value::String(value) => {
+      variables.insert(prefix.to_string(), normalize_template(value));
+    },
+    Value::Null => {},
+    value => {
+      variables.insert(prefix.to_string(), value.to_string());
+    },
+  }
+}
+
+/// Adds the environment and its sub environments, which inherit the variables of their parent.
+fn push_environments(environment: &Value, inherited: &BTreeMap<String, String>, environments: &mut Vec<Environment>) {
+  let mut variables = inherited.clone();
+  flatten_data("", environment.get("data").unwrap_or(&Value::Null), &mut variables);
+  let name = text(environment.get("name")).unwrap_or("Base Environment").to_string();
+  let children = environment.get("subEnvironments").and_then(Value::as_array);
+  for child in children.into_iter().flatten() {
+    push_environments(child, &variables, environments);
+  }
+  if !variables.is_empty() {
+    environments.push(Environment { name, variables, ..Environment::default() });
+  }
+}
+
+fn parse_resources(document: &Value, requests: &mut Vec<ImportedRequest>, environments: &mut Vec<Environment>) {
+  let resources: Vec<&Value> = document["resources"].as_array().into_iter().flatten().collect();
+  let by_id: HashMap<&str, &Value> =
+    resources.iter().filter_map(|resource| Some((text(resource.get("_id"))?, *resource))).collect();
+  let kind = |resource: &Value| text(resource.get("_type")).unwrap_or_default().to_string();
+  let parent = |resource: &Value| text(resource.get("parentId")).and_then(|id| by_id.get(id).copied());
+
+  for resource in resources.iter().filter(|resource| kind(resource) == "request") {
+    let mut folders = vec![];
+    let mut auth = None;
+    let mut current = parent(resource);
+    while let Some(group) = current.filter(|group| kind(group) == "request_group") {
+      folders.push(text(group.get("name")).unwrap_or_default());
+      if auth.is_none() {
+        auth = parse_auth(group.get("authentication"));
+      }
+      current = parent(group);
+    }
+    folders.reverse();
+    let folder = if folders.is_empty() { None } else { Some(folders.join("/")) };
+    let inherited = auth.flatten();
+    requests.push(parse_request(resource, folder, &inherited));
+  }
+
+  // Sub environments are children of the base environment, which is a child of the workspace.
+  let is_environment = |resource: &&&Value| kind(resource) == "environment";
+  for base in resources.iter().filter(is_environment).filter(|resource| {
+    match parent(resource) {
+      Some(parent) => kind(parent) != "environment",
+      None => true,
+    }
+  }) {
+    let mut base = (*base).clone();
+    let base_id = text(base.get("_id")).unwrap_or_default().to_string();
+    let children: Vec<Value> = resources
+      .iter()
+      .filter(is_environment)
+      .filter(|resource| text(resource.get("parentId")) == Some(base_id.as_str()))
+      .map(|resource| (*resource).clone())
+      .collect();
+    base["subEnvironments"] = Value::Array(children);
+    push_environments(&base, &BTreeMap::new(), environments);
+  }
+}
+
+fn collect_items(
+  items: Option<&Value>,
+  folder: Option<&str>,
+  auth_inherited: &Option<Auth>,
+  requests: &mut Vec<ImportedRequest>,
+) {
+  for item in items.and_then(Value::as_array).into_iter().flatten() {
+    let name = text(item.get("name")).unwrap_or_default();
+    if item.get("children").is_some() {
+      let folder = match folder {
+        Some(folder) => format!("{folder}/{name}"),
+        None => name.to_string(),
+      };
+      let auth = parse_auth(item.get("authentication")).unwrap_or_else(|| auth_inherited.clone());
+      collect_items(item.get("children"), Some(&folder), &auth, requests);
+    } else if item.get("url").is_some() {
+      requests.push(parse_request(item, folder.map(String::from), auth_inherited));
+    }
+  }
+}
+
+/// Reads an Insomnia authentication object, `None` means the auth is inherited and `Some(None)` means no auth.
+fn parse_auth(value: Option<&Value>) -> Option<Option<Auth>> {
+  let value = value?;
+  let field = |key: &str| normalize_template(text(value.get(key)).unwrap_or_default());
+  let kind = text(value.get("type"))?;
+  if is_disabled(value) {
+    return Some(None);
+  }
+  match kind {
+    "bearer" => Some(Some(Auth::Bearer { token: field("token") })),
+    "basic" => Some(Some(Auth::Basic { username: field("username"), password: field("password") })),
+    "digest" => Some(Some(Auth::Digest { username: field("username"), password: field("password") })),
+    "apikey" => {
+      let location = if field("addTo") == "queryParams" { "query" } else { "header" };
+      Some(Some(Auth::ApiKey { name: field("key"), value: field("value"), location: location.to_string() }))
+    },
+    "inherit" => None,
+    _ => Some(None),
+  }
+}
+
+fn name_values(values: Option<&Value>) -> Vec<(String, String)> {
+  values
+    .and_then(Value::as_array)
+    .into_iter()
+    .flatten()
+    .filter(|value| !is_disabled(value))
+    .filter_map(|value| {
+      Some((
+        normalize_template(text(value.get("name"))?),
+        normalize_template(text(value.get("value")).unwrap_or_default()),
+      ))
+    })
+    .collect()
+}
+
+fn parse_request(request: &Value, folder: Option<String>, auth_inherited: &Option<Auth>) -> ImportedRequest {
+  let mut url = normalize_template(text(request.get("url")).unwrap_or_default());
+  let parameters = name_values(request.get("parameters"));
+  if !parameters.is_empty() {
+    let query = parameters
+      .iter()
+      .map(|(key, value)| format!("{}={}", encode_component(key), encode_component(value)))
+      .collect::<Vec<_>>()
+      .join("&");
+    url.push(if url.contains('?') { '&' } else { '?' });
+    url.push_str(&query);
+  }
+
+  let mut headers = name_values(request.get("headers"));
+  let body = request.get("body").and_then(|body| {
+    let mime_type = text(body.get("mimeType"))?;
+    let content = match mime_type {
+      "application/x-www-form-urlencoded" => {
+        Some(
+          name_values(body.get("params"))
+            .iter()
+            .map(|(key, value)| format!("{}={}", encode_component(key), encode_component(value)))
+            .collect::<Vec<_>>()
+            .join("&"),
+        )
+      },
+      _ => text(body.get("text")).map(normalize_template),
+    }?;
+    if !mime_type.starts_with("multipart/") && !headers.iter().any(|(key, _)| key.eq_ignore_ascii_case("content-type"))
+    {
+      headers.push((String::from("Content-Type"), mime_type.to_string()));
+    }
+    Some(content)
+  });
+
+  ImportedRequest {
+    name: text(request.get("name")).unwrap_or_default().to_string(),
+    method: text(request.get("method")).unwrap_or("GET").to_uppercase(),
+    url,
+    headers,
+    body,
+    folder,
+    auth: parse_auth(request.get("authentication")).unwrap_or_else(|| auth_inherited.clone()),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_parse_v4() {
+    let document: Value = serde_yaml::from_str(
+      r#"
+_type: export
+__export_format: 4
+resources:
+  - { _id: wrk_1, _type: workspace, name: Petstore }
+  - { _id: fld_1, _type: request_group, parentId: wrk_1, name: pet }
+  - _id: req_1
+    _type: request
+    parentId: fld_1
+    name: Add pet
+    method: post
+    url: "{{ _.baseUrl }}/pet"
+    parameters: [{ name: verbose, value: "true" }, { name: debug, value: "1", disabled: true }]
+    headers: [{ name: Accept, value: application/json }]
+    body: { mimeType: application/json, text: '{"name": "{{ name }}"}' }
+    authentication: { type: bearer, token: "{{ _.token }}" }
+  - { _id: env_1, _type: environment, parentId: wrk_1, name: Base Environment, data: { baseUrl: "http://localhost" } }
+  - { _id: env_2, _type: environment, parentId: env_1, name: staging, data: { token: abc, retries: 3 } }
+"#,
+    )
+    .unwrap();
+    assert!(is_export(&document));
+    let (requests, environments) = parse(&document).unwrap();
+    assert_eq!(requests, vec![ImportedRequest {
+      name: String::from("Add pet"),
+      method: String::from("POST"),
+      url: String::from("{{baseUrl}}/pet?verbose=true"),
+      headers: vec![
+        (String::from("Accept"), String::from("application/json")),
+        (String::from("Content-Type"), String::from("application/json"))
+      ],
+      body: Some(String::from("{\"name\": \"{{name}}\"}")),
+      folder: Some(String::from("pet")),
+      auth: Some(Auth::Bearer { token: String::from("{{token}}") }),
+    }]);
+    assert_eq!(environments.iter().map(|environment| environment.name.as_str()).collect::<Vec<_>>(), vec![
+      "staging",
+      "Base Environment"
+    ]);
+    assert_eq!(environments[0].variables.get("baseUrl").map(String::as_str), Some("http://localhost"));
+    assert_eq!(environments[0].variables.get("retries").map(String::as_str), Some("3"));
+  }
+}
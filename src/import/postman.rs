@@ -0,0 +1,268 @@
+use std::collections::BTreeMap;
+
+use color_eyre::eyre::Result;
+use serde_json::Value;
+
+use crate::{
+  import::ImportedRequest,
+  request::{encode_component, Auth},
+  workspace::Environment,
+};
+
+pub fn is_collection(document: &Value) -> bool {
+  document
+    .pointer("/info/schema")
+    .and_then(Value::as_str)
+    .is_some_and(|schema| schema.contains("schema.getpostman.com"))
+}
+
+/// Converts a Postman v2.1 collection into requests, and its collection variables into an environment
+/// named after the collection. Folder and collection level auth is inherited by the requests.
+pub fn parse(document: &Value) -> Result<(Vec<ImportedRequest>, Vec<Environment>)> {
+  let name = text(document.pointer("/info/name")).unwrap_or("Postman");
+  let mut requests = vec![];
+  let collection_auth = auth(document.get("auth")).flatten();
+  collect_items(document.get("item"), None, &collection_auth, &mut requests);
+
+  let variables: BTreeMap<String, String> = document
+    .get("variable")
+    .and_then(Value::as_array)
+    .map(|variables| {
+      variables
+        .iter()
+        .filter(|variable| !variable.get("disabled").and_then(Value::as_bool).unwrap_or_default())
+        .filter_map(|variable| Some((text(variable.get("key"))?.to_string(), value_text(variable.get("value")))))
+        .collect()
+    })
+    .unwrap_or_default();
+  let environments = if variables.is_empty() {
+    vec![]
+  } else {
+    vec![Environment { name: name.to_string(), variables, ..Environment::default() }]
+  };
+  Ok((requests, environments))
+}
+
+fn text(value: Option<&Value>) -> Option<&str> {
+  value.and_then(Value::as_str)
+}
+
+fn value_text(value: Option<&Value>) -> String {
+  match value {
+    Some(Value::String(value)) => value.clone(),
+    Some(Value::Null) | None => String::default(),
+    Some(value) => value.to_string(),
+  }
+}
+
+fn collect_items(
+  items: Option<&Value>,
+  folder: Option<&str>,
+  auth_inherited: &Option<Auth>,
+  requests: &mut Vec<ImportedRequest>,
+) {
+  for item in items.and_then(Value::as_array).into_iter().flatten() {
+    let name = text(item.get("name")).unwrap_or_default();
+    let item_auth = auth(item.get("auth")).unwrap_or_else(|| auth_inherited.clone());
+    if item.get("item").is_some() {
+      let folder = match folder {
+        Some(folder) => format!("{folder}/{name}"),
+        None => name.to_string(),
+      };
+      collect_items(item.get("item"), Some(&folder), &item_auth, requests);
+    } else if let Some(request) = item.get("request") {
+      let mut imported = parse_request(request);
+      imported.name = name.to_string();
+      imported.folder = folder.map(String::from);
+      imported.auth = auth(request.get("auth")).unwrap_or(item_auth);
+      requests.push(imported);
+    }
+  }
+}
+
+/// Reads a Postman auth object, `None` means the auth is inherited and `Some(None)` means no auth.
+fn auth(value: Option<&Value>) -> Option<Option<Auth>> {
+  let value = value?;
+  let kind = text(value.get("type"))?;
+  let param = |key: &str| {
+    value
+      .get(kind)
+      .and_then(Value::as_array)
+      .and_then(|params| params.iter().find(|param| text(param.get("key")) == Some(key)))
+      .map(|param| value_text(param.get("value")))
+      .unwrap_or_default()
+  };
+  match kind {
+    "inherit" => None,
+    "bearer" => Some(Some(Auth::Bearer { token: param("token") })),
+    "basic" => Some(Some(Auth::Basic { username: param("username"), password: param("password") })),
+    "digest" => Some(Some(Auth::Digest { username: param("username"), password: param("password") })),
+    "apikey" => {
+      let location = param("in");
+      Some(Some(Auth::ApiKey {
+        name: param("key"),
+        value: param("value"),
+        location: if location.is_empty() { String::from("header") } else { location },
+      }))
+    },
+    _ => Some(None),
+  }
+}
+
+fn parse_request(request: &Value) -> ImportedRequest {
+  if let Some(url) = request.as_str() {
+    return ImportedRequest { method: String::from("GET"), url: url.to_string(), ..ImportedRequest::default() };
+  }
+
+  let mut headers: Vec<(String, String)> = request
+    .get("header")
+    .and_then(Value::as_array)
+    .into_iter()
+    .flatten()
+    .filter(|header| !header.get("disabled").and_then(Value::as_bool).unwrap_or_default())
+    .filter_map(|header| Some((text(header.get("key"))?.to_string(), value_text(header.get("value")))))
+    .collect();
+
+  let body = request.get("body").and_then(|body| {
+    let mut content_type = None;
+    let body = match text(body.get("mode"))? {
+      "raw" => {
+        if text(body.pointer("/options/raw/language")) == Some("json") {
+          content_type = Some("application/json");
+        }
+        text(body.get("raw")).map(String::from)
+      },
+      "urlencoded" => {
+        content_type = Some("application/x-www-form-urlencoded");
+        let pairs = body.get("urlencoded").and_then(Value::as_array)?;
+        Some(
+          pairs
+            .iter()
+            .filter(|pair| !pair.get("disabled").and_then(Value::as_bool).unwrap_or_default())
+            .map(|pair| {
+              format!(
+                "{}={}",
+                encode_component(text(pair.get("key")).unwrap_or_default()),
+                encode_component(&value_text(pair.get("value")))
+              )
+            })
+            .collect::<Vec<_>>()
+            .join("&"),
+        )
+      },
+      "graphql" => {
+        content_type = Some("application/json");
+        let query = text(body.pointer("/graphql/query")).unwrap_or_default();
+        let variables = text(body.pointer("/graphql/variables"))
+          .and_then(|variables| serde_json::from_str::<Value>(variables).ok())
+          .unwrap_or(Value::Null);
+        Some(serde_json::json!({ "query": query, "variables": variables }).to_string())
+      },
+      _ => None,
+    };
+    if let Some(content_type) = content_type {
+      if !headers.iter().any(|(key, _)| key.eq_ignore_ascii_case("content-type")) {
+        headers.push((String::from("Content-Type"), content_type.to_string()));
+      }
+    }
+    body
+  });
+
+  ImportedRequest {
+    method: text(request.get("method")).unwrap_or("GET").to_uppercase(),
+    url: parse_url(request.get("url")),
+    headers,
+    body,
+    ..ImportedRequest::default()
+  }
+}
+
+/// Builds the url, replacing Postman `:name` path variables with their value or a `{{name}}` reference.
+fn parse_url(url: Option<&Value>) -> String {
+  let Some(url) = url else {
+    return String::default();
+  };
+  if let Some(raw) = url.as_str() {
+    return raw.to_string();
+  }
+  let raw = text(url.get("raw")).unwrap_or_default();
+  let variables: BTreeMap<&str, String> = url
+    .get("variable")
+    .and_then(Value::as_array)
+    .into_iter()
+    .flatten()
+    .filter_map(|variable| Some((text(variable.get("key"))?, value_text(variable.get("value")))))
+    .collect();
+
+  let (path, query) = raw.split_once('?').map_or((raw, None), |(path, query)| (path, Some(query)));
+  let path = path
+    .split('/')
+    .map(|segment| {
+      match segment.strip_prefix(':') {
+        Some(name) if !name.is_empty() => {
+          match variables.get(name) {
+            Some(value) if !value.is_empty() => value.clone(),
+            _ => format!("{{{{{name}}}}}"),
+          }
+        },
+        _ => segment.to_string(),
+      }
+    })
+    .collect::<Vec<_>>()
+    .join("/");
+  match query {
+    Some(query) => format!("{path}?{query}"),
+    None => path,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_parse() {
+    let document = serde_json::json!({
+      "info": { "name": "Petstore", "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json" },
+      "auth": { "type": "bearer", "bearer": [{ "key": "token", "value": "{{token}}" }] },
+      "variable": [{ "key": "baseUrl", "value": "https://petstore.swagger.io/v2" }],
+      "item": [{
+        "name": "pet",
+        "item": [{
+          "name": "Find pet by ID",
+          "request": {
+            "method": "GET",
+            "header": [{ "key": "Accept", "value": "application/json" }, { "key": "X-Debug", "value": "1", "disabled": true }],
+            "url": { "raw": "{{baseUrl}}/pet/:petId", "variable": [{ "key": "petId", "value": "" }] }
+          }
+        }, {
+          "name": "Public",
+          "request": { "method": "GET", "url": "{{baseUrl}}/pet/findByStatus?status=sold", "auth": { "type": "noauth" } }
+        }]
+      }]
+    });
+    assert!(is_collection(&document));
+    let (requests, environments) = parse(&document).unwrap();
+    assert_eq!(requests, vec![
+      ImportedRequest {
+        name: String::from("Find pet by ID"),
+        method: String::from("GET"),
+        url: String::from("{{baseUrl}}/pet/{{petId}}"),
+        headers: vec![(String::from("Accept"), String::from("application/json"))],
+        body: None,
+        folder: Some(String::from("pet")),
+        auth: Some(Auth::Bearer { token: String::from("{{token}}") }),
+      },
+      ImportedRequest {
+        name: String::from("Public"),
+        method: String::from("GET"),
+        url: String::from("{{baseUrl}}/pet/findByStatus?status=sold"),
+        folder: Some(String::from("pet")),
+        ..ImportedRequest::default()
+      },
+    ]);
+    assert_eq!(environments[0].variables.get("baseUrl").map(String::as_str), Some("https://petstore.swagger.io/v2"));
+  }
+}
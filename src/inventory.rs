@@ -0,0 +1,117 @@
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{mock::METHODS, tags};
+
+/// Operation of the spec, as printed by the `list` command.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListedOperation {
+  pub method: String,
+  pub path: String,
+  pub operation_id: Option<String>,
+  pub summary: Option<String>,
+  pub tags: Vec<String>,
+}
+
+/// Operations of the spec by path, of the tag or group of tags and of the method when given.
+pub fn operations(document: &Value, tag: Option<&str>, method: Option<&str>) -> Vec<ListedOperation> {
+  let tag_tree = tags::tree(document);
+  let tags =
+    tag.map(|name| tags::find(&tag_tree, name).map_or_else(|| vec![name.to_string()], |node| node.tags.clone()));
+  let text = |operation: &Value, key: &str| operation.get(key).and_then(Value::as_str).map(String::from);
+  let paths = document.get("paths").and_then(Value::as_object).into_iter().flatten();
+  paths
+    .flat_map(|(path, item)| {
+      METHODS.iter().filter_map(move |method| item.get(*method).map(|operation| (path, *method, operation)))
+    })
+    .filter(|(_, operation_method, _)| method.is_none_or(|method| method.eq_ignore_ascii_case(operation_method)))
+    .map(|(path, method, operation)| {
+      let operation_tags = operation.get("tags").and_then(Value::as_array).into_iter().flatten();
+      ListedOperation {
+        method: method.to_uppercase(),
+        path: path.clone(),
+        operation_id: text(operation, "operationId"),
+        summary: text(operation, "summary"),
+        tags: operation_tags.filter_map(Value::as_str).map(String::from).collect(),
+      }
+    })
+    .filter(|operation| tags.as_ref().is_none_or(|tags| tags::is_tagged_with(&operation.tags, tags)))
+    .collect()
+}
+
+/// Lines of a table of the operations, its columns aligned under a header.
+pub fn table(operations: &[ListedOperation]) -> Vec<String> {
+  let header = [String::from("METHOD"), String::from("PATH"), String::from("OPERATION ID"), String::from("SUMMARY")];
+  let rows: Vec<[String; 4]> = std::iter::once(header)
+    .chain(operations.iter().map(|operation| {
+      [
+        operation.method.clone(),
+        operation.path.clone(),
+        operation.operation_id.clone().unwrap_or_default(),
+        operation.summary.clone().unwrap_or_default(),
+      ]
+    }))
+    .collect();
+  let widths: Vec<usize> =
+    (0..3).map(|column| rows.iter().map(|row| row[column].chars().count()).max().unwrap_or_default()).collect();
+  rows
+    .iter()
+    .map(|row| {
+      let line = format!(
+        "{:w0$}  {:w1$}  {:w2$}  {}",
+        row[0],
+        row[1],
+        row[2],
+        row[3],
+        w0 = widths[0],
+        w1 = widths[1],
+        w2 = widths[2]
+      );
+      line.trim_end().to_string()
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+  use serde_json::json;
+
+  use super::*;
+
+  #[test]
+  fn test_operations() {
+    let document = json!({
+      "tags": [{ "name": "pets" }, { "name": "store" }],
+      "paths": {
+        "/pets": {
+          "get": { "operationId": "listPets", "summary": "List all pets", "tags": ["pets"] },
+          "post": { "operationId": "createPet", "tags": ["pets"] }
+        },
+        "/store/orders": { "get": { "summary": "List orders", "tags": ["store"] } },
+        "/health": { "get": {} }
+      }
+    });
+    let listed = |tag, method| {
+      operations(&document, tag, method)
+        .into_iter()
+        .map(|operation| format!("{} {}", operation.method, operation.path))
+        .collect::<Vec<_>>()
+    };
+    assert_eq!(listed(None, None), vec!["GET /health", "GET /pets", "POST /pets", "GET /store/orders"]);
+    assert_eq!(listed(Some("pets"), None), vec!["GET /pets", "POST /pets"]);
+    assert_eq!(listed(None, Some("get")), vec!["GET /health", "GET /pets", "GET /store/orders"]);
+    assert_eq!(listed(Some(tags::UNTAGGED), None), vec!["GET /health"]);
+
+    assert_eq!(table(&operations(&document, Some("pets"), None)), vec![
+      "METHOD  PATH   OPERATION ID  SUMMARY",
+      "GET     /pets  listPets      List all pets",
+      "POST    /pets  createPet",
+    ]);
+    assert_eq!(
+      serde_json::to_value(operations(&document, None, Some("POST"))).unwrap(),
+      json!([{ "method": "POST", "path": "/pets", "operationId": "createPet", "summary": null, "tags": ["pets"] }])
+    );
+  }
+}
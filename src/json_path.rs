@@ -0,0 +1,102 @@
+use serde_json::Value;
+
+/// Step from a JSON value to one of its members or items.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Segment {
+  Key(String),
+  Index(usize),
+}
+
+/// Path of the node each line of the value pretty printed by `serde_json::to_string_pretty` starts, or closes, e.g.
+/// `[]` for the first line and `[Key("items"), Index(0)]` for the line of the first item of `items`.
+pub fn line_paths(value: &Value) -> Vec<Vec<Segment>> {
+  let mut lines = vec![vec![]];
+  walk(value, &mut vec![], &mut lines);
+  lines
+}
+
+fn walk(value: &Value, path: &mut Vec<Segment>, lines: &mut Vec<Vec<Segment>>) {
+  let children: Vec<(Segment, &Value)> = match value {
+    Value::Object(object) => object.iter().map(|(key, value)| (Segment::Key(key.clone()), value)).collect(),
+    Value::Array(items) => items.iter().enumerate().map(|(index, item)| (Segment::Index(index), item)).collect(),
+    _ => return,
+  };
+  // empty objects and arrays are printed on the line of their key
+  if children.is_empty() {
+    return;
+  }
+  for (segment, child) in children {
+    path.push(segment);
+    lines.push(path.clone());
+    walk(child, path, lines);
+    path.pop();
+  }
+  lines.push(path.clone());
+}
+
+fn is_identifier(key: &str) -> bool {
+  let mut chars = key.chars();
+  chars.next().is_some_and(|first| first.is_alphabetic() || first == '_')
+    && chars.all(|char| char.is_alphanumeric() || char == '_')
+}
+
+/// JSONPath of the node, e.g. `$.items[0].name` or `$['content-type']`, as extractions and assertions read them.
+pub fn json_path(path: &[Segment]) -> String {
+  path.iter().fold(String::from("$"), |text, segment| {
+    match segment {
+      Segment::Key(key) if is_identifier(key) => format!("{text}.{key}"),
+      Segment::Key(key) => format!("{text}['{}']", key.replace('\\', "\\\\").replace('\'', "\\'")),
+      Segment::Index(index) => format!("{text}[{index}]"),
+    }
+  })
+}
+
+/// jq path of the node, e.g. `.items[0].name` or `.headers["content-type"]`.
+pub fn jq_path(path: &[Segment]) -> String {
+  let text = path.iter().fold(String::default(), |text, segment| {
+    match segment {
+      Segment::Key(key) if is_identifier(key) => format!("{text}.{key}"),
+      Segment::Key(key) => format!("{text}[{}]", Value::String(key.clone())),
+      Segment::Index(index) => format!("{text}[{index}]"),
+    }
+  });
+  // paths into arrays and odd keys at the root start with a bracket, e.g. `.[0]`
+  match text.starts_with('.') {
+    true => text,
+    false => format!(".{text}"),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+  use serde_json::json;
+
+  use super::*;
+
+  #[test]
+  fn test_line_paths() {
+    let value = json!({ "id": 1, "tags": [{ "content-type": "dog" }, []], "owner": {} });
+    let pretty = serde_json::to_string_pretty(&value).unwrap();
+    let paths = line_paths(&value);
+    assert_eq!(paths.len(), pretty.lines().count());
+    let lines = pretty
+      .lines()
+      .zip(&paths)
+      .map(|(line, path)| format!("{:<28}{} {}", line, json_path(path), jq_path(path)))
+      .collect::<Vec<_>>();
+    assert_eq!(lines, vec![
+      "{                           $ .",
+      "  \"id\": 1,                  $.id .id",
+      "  \"owner\": {},              $.owner .owner",
+      "  \"tags\": [                 $.tags .tags",
+      "    {                       $.tags[0] .tags[0]",
+      "      \"content-type\": \"dog\" $.tags[0]['content-type'] .tags[0][\"content-type\"]",
+      "    },                      $.tags[0] .tags[0]",
+      "    []                      $.tags[1] .tags[1]",
+      "  ]                         $.tags .tags",
+      "}                           $ .",
+    ]);
+    assert_eq!(jq_path(&[Segment::Index(2), Segment::Key(String::from("id"))]), ".[2].id");
+  }
+}
@@ -0,0 +1,122 @@
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumIter};
+
+use crate::{
+  action::Action,
+  config::{parse_key_event, parse_key_sequence, KeyBindings, KeyScope},
+};
+
+/// Profile of the navigation keys, chosen with `keymap` in the config and switched from the command palette. The
+/// keybindings of the config file apply over the profile.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display, EnumIter)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum Keymap {
+  /// The default keys, `h`, `j`, `k` and `l` moving along with the arrows.
+  #[default]
+  Vim,
+  /// `ctrl-n`, `ctrl-p`, `ctrl-f` and `ctrl-b` moving, `alt-<` and `alt->` to the top and bottom.
+  Emacs,
+  /// The arrows and function keys, `tab` focusing the next pane.
+  Arrows,
+}
+
+impl Keymap {
+  /// Default key sequences the profile frees, in every scope.
+  fn unbound(self) -> &'static [&'static str] {
+    match self {
+      Keymap::Vim => &[],
+      Keymap::Emacs | Keymap::Arrows => &["<h>", "<H>", "<j>", "<J>", "<k>", "<K>", "<l>", "<L>", "<G>", "<g><g>"],
+    }
+  }
+
+  /// Keys the profile binds on the page, over the defaults.
+  fn bindings(self) -> Vec<(&'static str, Action)> {
+    match self {
+      Keymap::Vim => vec![],
+      Keymap::Emacs => {
+        vec![
+          ("ctrl-n", Action::Down),
+          ("ctrl-p", Action::Up),
+          ("ctrl-f", Action::FocusNext),
+          ("ctrl-b", Action::FocusPrev),
+          ("ctrl-v", Action::PageDown),
+          ("alt-v", Action::PageUp),
+          ("alt-<", Action::Top),
+          ("alt->", Action::Bottom),
+          ("ctrl-g", Action::Back),
+          // the defaults bound to the keys taken
+          ("alt-n", Action::FollowPagination),
+          ("alt-p", Action::TogglePreview),
+          ("alt-g", Action::ProbeHealth),
+        ]
+      },
+      Keymap::Arrows => {
+        vec![
+          ("tab", Action::FocusNext),
+          ("backtab", Action::FocusPrev),
+          ("f1", Action::ShowHelp),
+          ("f2", Action::ShowCommands),
+          ("f5", Action::ReloadSpec),
+          ("f10", Action::Quit),
+          ("f11", Action::ToggleFullScreen),
+        ]
+      },
+    }
+  }
+
+  /// Unbinds the keys the profile frees from the default bindings and binds its own.
+  pub fn apply(self, keybindings: &mut KeyBindings) {
+    let unbound: Vec<_> = self.unbound().iter().filter_map(|keys| parse_key_sequence(keys).ok()).collect();
+    for bindings in keybindings.values_mut() {
+      bindings.retain(|keys, _| !unbound.contains(keys));
+    }
+    let home = keybindings.entry(KeyScope::Home).or_default();
+    for (key, action) in self.bindings() {
+      if let Ok(key) = parse_key_event(key) {
+        home.insert(vec![key], action);
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_apply() {
+    let keybindings = || {
+      json5::from_str::<KeyBindings>(
+        r#"{ "Home": { "<j>": "Down", "<down>": "Down", "<ctrl-p>": "TogglePreview" }, "Apis": { "<g><g>": "Top" } }"#,
+      )
+      .unwrap()
+    };
+    let key = |code, modifiers| KeyEvent::new(code, modifiers);
+
+    let mut vim = keybindings();
+    Keymap::Vim.apply(&mut vim);
+    assert_eq!(vim.action(KeyScope::Home, key(KeyCode::Char('j'), KeyModifiers::NONE)), Some(&Action::Down));
+
+    let mut emacs = keybindings();
+    Keymap::Emacs.apply(&mut emacs);
+    assert_eq!(emacs.action(KeyScope::Home, key(KeyCode::Char('j'), KeyModifiers::NONE)), None);
+    assert_eq!(emacs.action(KeyScope::Home, key(KeyCode::Down, KeyModifiers::NONE)), Some(&Action::Down));
+    assert_eq!(emacs.action(KeyScope::Home, key(KeyCode::Char('p'), KeyModifiers::CONTROL)), Some(&Action::Up));
+    assert_eq!(emacs.key_of(KeyScope::Home, &Action::TogglePreview).as_deref(), Some("alt-p"));
+    assert_eq!(emacs.action(KeyScope::Home, key(KeyCode::Char('<'), KeyModifiers::ALT)), Some(&Action::Top));
+    assert!(emacs.get(&KeyScope::Apis).unwrap().is_empty());
+
+    let mut arrows = keybindings();
+    Keymap::Arrows.apply(&mut arrows);
+    assert_eq!(arrows.action(KeyScope::Home, key(KeyCode::Char('j'), KeyModifiers::NONE)), None);
+    assert_eq!(arrows.action(KeyScope::Home, key(KeyCode::Tab, KeyModifiers::NONE)), Some(&Action::FocusNext));
+    assert_eq!(arrows.action(KeyScope::Home, key(KeyCode::BackTab, KeyModifiers::SHIFT)), Some(&Action::FocusPrev));
+
+    assert_eq!(json5::from_str::<Keymap>(r#""emacs""#).unwrap(), Keymap::Emacs);
+    assert_eq!(Keymap::Arrows.to_string(), "arrows");
+  }
+}
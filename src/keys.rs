@@ -0,0 +1,156 @@
+use std::time::{Duration, Instant};
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::{
+  action::Action,
+  config::{KeyBindings, KeyScope},
+};
+
+/// Keys typed before the action they make: a vim-style count, e.g. `15` of `15j`, or the start of a key sequence
+/// bound in the config, e.g. `g` of `gg`.
+#[derive(Debug, Default)]
+pub struct KeyResolver {
+  count: Option<usize>,
+  keys: Vec<KeyEvent>,
+  /// When the sequence was started.
+  typed_at: Option<Instant>,
+}
+
+impl KeyResolver {
+  const MAX_COUNT: usize = 9999;
+  /// How long the start of a sequence waits for its next key before making its own action.
+  const TIMEOUT: Duration = Duration::from_millis(500);
+
+  /// Actions of the key typed, bound in the first of the scopes binding it, and digits counting the next action when
+  /// `counting`. Empty while the key starts a count or a sequence, `None` when it is not bound.
+  pub fn key(
+    &mut self,
+    bindings: &KeyBindings,
+    scopes: &[KeyScope],
+    key: KeyEvent,
+    counting: bool,
+  ) -> Option<Vec<Action>> {
+    self.key_at(bindings, scopes, key, counting, Instant::now())
+  }
+
+  fn key_at(
+    &mut self,
+    bindings: &KeyBindings,
+    scopes: &[KeyScope],
+    key: KeyEvent,
+    counting: bool,
+    now: Instant,
+  ) -> Option<Vec<Action>> {
+    let digit = match key.code {
+      KeyCode::Char(c) if key.modifiers.is_empty() && counting && self.keys.is_empty() => c.to_digit(10),
+      _ => None,
+    };
+    // a leading 0 is no count
+    if let Some(digit) = digit.filter(|digit| *digit > 0 || self.count.is_some()) {
+      let count = self.count.unwrap_or_default().saturating_mul(10).saturating_add(digit as usize);
+      self.count = Some(count.min(Self::MAX_COUNT));
+      return Some(vec![]);
+    }
+    self.keys.push(key);
+    if scopes.iter().any(|scope| bindings.starts_sequence(*scope, &self.keys)) {
+      self.typed_at.get_or_insert(now);
+      return Some(vec![]);
+    }
+    let keys = std::mem::take(&mut self.keys);
+    self.typed_at = None;
+    if let Some(action) = find(bindings, scopes, &keys) {
+      return Some(self.counted(action));
+    }
+    // keys starting a sequence this one does not follow make their own action first
+    let Some((last, first)) = keys.split_last().filter(|(_, first)| !first.is_empty()) else {
+      self.count = None;
+      return None;
+    };
+    let mut actions = find(bindings, scopes, first).map(|action| self.counted(action)).unwrap_or_default();
+    actions.extend(self.key_at(bindings, scopes, *last, counting, now).unwrap_or_default());
+    Some(actions)
+  }
+
+  /// Action of the sequence started long enough ago, as if it was not started.
+  pub fn expire(&mut self, bindings: &KeyBindings, scopes: &[KeyScope]) -> Vec<Action> {
+    self.expire_at(bindings, scopes, Instant::now())
+  }
+
+  fn expire_at(&mut self, bindings: &KeyBindings, scopes: &[KeyScope], now: Instant) -> Vec<Action> {
+    if self.typed_at.is_none_or(|typed_at| now.duration_since(typed_at) < Self::TIMEOUT) {
+      return vec![];
+    }
+    self.typed_at = None;
+    let keys = std::mem::take(&mut self.keys);
+    match find(bindings, scopes, &keys) {
+      Some(action) => self.counted(action),
+      None => {
+        self.count = None;
+        vec![]
+      },
+    }
+  }
+
+  /// The action repeated by the count typed, for the moves in lists, and the count forgotten.
+  fn counted(&mut self, action: Action) -> Vec<Action> {
+    let count = self.count.take().unwrap_or(1);
+    match action {
+      Action::Down | Action::Up | Action::PageDown | Action::PageUp | Action::NextSection | Action::PrevSection => {
+        vec![action; count]
+      },
+      action => vec![action],
+    }
+  }
+}
+
+fn find(bindings: &KeyBindings, scopes: &[KeyScope], keys: &[KeyEvent]) -> Option<Action> {
+  scopes.iter().find_map(|scope| bindings.sequence_action(*scope, keys)).cloned()
+}
+
+#[cfg(test)]
+mod tests {
+  use std::collections::HashMap;
+
+  use crossterm::event::KeyModifiers;
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_key_resolver() {
+    let key = |c: char| KeyEvent::new(KeyCode::Char(c), KeyModifiers::empty());
+    let bindings = KeyBindings(HashMap::from([
+      (
+        KeyScope::Home,
+        HashMap::from([
+          (vec![key('j')], Action::Down),
+          (vec![key('g')], Action::Go),
+          (vec![key('G')], Action::Bottom),
+          (vec![key('1')], Action::Tab(0)),
+        ]),
+      ),
+      (KeyScope::Apis, HashMap::from([(vec![key('g'), key('g')], Action::Top)])),
+    ]));
+    let scopes = [KeyScope::Apis, KeyScope::Home];
+    let mut resolver = KeyResolver::default();
+    let now = Instant::now();
+    let typed = |resolver: &mut KeyResolver, keys: &str, counting: bool| {
+      keys.chars().map(|c| resolver.key_at(&bindings, &scopes, key(c), counting, now)).collect::<Vec<_>>()
+    };
+
+    assert_eq!(typed(&mut resolver, "15j", true), vec![Some(vec![]), Some(vec![]), Some(vec![Action::Down; 15])]);
+    assert_eq!(typed(&mut resolver, "1", false), vec![Some(vec![Action::Tab(0)])]);
+    assert_eq!(typed(&mut resolver, "0j", true), vec![None, Some(vec![Action::Down])]);
+    assert_eq!(typed(&mut resolver, "gg", true), vec![Some(vec![]), Some(vec![Action::Top])]);
+    assert_eq!(typed(&mut resolver, "3G", true), vec![Some(vec![]), Some(vec![Action::Bottom])]);
+    assert_eq!(typed(&mut resolver, "gj", true), vec![Some(vec![]), Some(vec![Action::Go, Action::Down])]);
+    assert_eq!(typed(&mut resolver, "2x", true), vec![Some(vec![]), None]);
+    assert_eq!(typed(&mut resolver, "j", true), vec![Some(vec![Action::Down])]);
+
+    assert_eq!(typed(&mut resolver, "g", true), vec![Some(vec![])]);
+    assert_eq!(resolver.expire_at(&bindings, &scopes, now + Duration::from_millis(100)), vec![]);
+    assert_eq!(resolver.expire_at(&bindings, &scopes, now + Duration::from_secs(1)), vec![Action::Go]);
+    assert_eq!(resolver.expire_at(&bindings, &scopes, now + Duration::from_secs(2)), vec![]);
+  }
+}
@@ -0,0 +1,150 @@
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+
+use crate::{
+  config::{KeyScope, LayoutConfig, PaneGroupConfig},
+  workspace::PaneSizes,
+};
+
+/// Indexes of the panes of the group, in its order.
+fn group_panes(panes: &[KeyScope]) -> Vec<usize> {
+  panes.iter().filter_map(|scope| KeyScope::PANES.iter().position(|pane| pane == scope)).collect()
+}
+
+/// Indexes of the panes shown by the layout, in its order.
+pub fn panes(layout: &LayoutConfig) -> Vec<usize> {
+  let mut panes: Vec<usize> = vec![];
+  for index in layout.groups.iter().flat_map(|group| group_panes(&group.panes)) {
+    if !panes.contains(&index) {
+      panes.push(index);
+    }
+  }
+  panes
+}
+
+/// Group of the layout showing the pane.
+pub fn group_of(layout: &LayoutConfig, index: usize) -> Option<usize> {
+  layout.groups.iter().position(|group| group_panes(&group.panes).contains(&index))
+}
+
+/// The default layout, for configs without layouts: the lists on the left, the request and response on the right.
+pub fn fallback() -> LayoutConfig {
+  LayoutConfig {
+    name: String::from("default"),
+    vertical: false,
+    groups: vec![PaneGroupConfig { size: 1, panes: KeyScope::PANES[..5].to_vec() }, PaneGroupConfig {
+      size: 3,
+      panes: KeyScope::PANES[5..].to_vec(),
+    }],
+  }
+}
+
+/// Length of a constraint keeping a fixed size, `None` for the ones filling the space left.
+fn fixed_length(constraint: Constraint) -> Option<u16> {
+  match constraint {
+    Constraint::Length(length) | Constraint::Max(length) => Some(length),
+    _ => None,
+  }
+}
+
+/// Areas of the panes shown by the layout, by index. Stacked in columns, panes take the heights they ask for, and
+/// rows of panes of fixed heights keep them.
+pub fn areas(layout: &LayoutConfig, heights: &[Constraint], sizes: &PaneSizes, area: Rect) -> Vec<(usize, Rect)> {
+  // a pane named several times is shown once, in the first group naming it
+  let shown = panes(layout);
+  let groups: Vec<Vec<usize>> = (0..layout.groups.len())
+    .map(|group| shown.iter().copied().filter(|index| group_of(layout, *index) == Some(group)).collect())
+    .collect();
+  let (direction, pane_direction) = match layout.vertical {
+    true => (Direction::Vertical, Direction::Horizontal),
+    false => (Direction::Horizontal, Direction::Vertical),
+  };
+  let group_constraints: Vec<Constraint> = layout
+    .groups
+    .iter()
+    .zip(groups.iter())
+    .enumerate()
+    .map(|(group_index, (group, panes))| {
+      let fixed = panes.iter().map(|index| heights.get(*index).copied().and_then(fixed_length));
+      match layout.vertical && !panes.is_empty() {
+        true => fixed.collect::<Option<Vec<u16>>>().and_then(|lengths| lengths.into_iter().max()),
+        false => None,
+      }
+      .map_or(Constraint::Fill(sizes.group(&layout.name, group_index, group.size)), Constraint::Length)
+    })
+    .collect();
+  let group_areas = Layout::default().direction(direction).constraints(group_constraints).split(area);
+  groups
+    .iter()
+    .zip(group_areas.iter())
+    .flat_map(|(panes, group_area)| {
+      let constraints = panes.iter().map(|index| {
+        match (layout.vertical, heights.get(*index).copied().unwrap_or(Constraint::Fill(1))) {
+          (false, Constraint::Fill(weight)) => Constraint::Fill(sizes.height(*index, weight)),
+          (false, constraint) => constraint,
+          (true, _) => Constraint::Fill(sizes.height(*index, 1)),
+        }
+      });
+      let pane_areas = Layout::default().direction(pane_direction).constraints(constraints).split(*group_area);
+      panes.iter().copied().zip(pane_areas.iter().copied()).collect::<Vec<_>>()
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_areas() {
+    let group = |size: u16, panes: Vec<KeyScope>| PaneGroupConfig { size, panes };
+    let heights = [
+      Constraint::Fill(3),
+      Constraint::Fill(1),
+      Constraint::Fill(1),
+      Constraint::Fill(1),
+      Constraint::Fill(1),
+      Constraint::Max(3),
+      Constraint::Fill(1),
+      Constraint::Fill(1),
+    ];
+    let default = LayoutConfig {
+      name: String::from("default"),
+      vertical: false,
+      groups: vec![
+        group(1, vec![KeyScope::Apis, KeyScope::Tags]),
+        group(3, vec![KeyScope::Address, KeyScope::Response, KeyScope::Tags]),
+      ],
+    };
+    assert_eq!(panes(&default), vec![0, 1, 5, 7]);
+    assert_eq!(group_of(&default, 7), Some(1));
+    assert_eq!(group_of(&default, 4), None);
+    let area = Rect::new(0, 0, 100, 40);
+    assert_eq!(areas(&default, &heights, &PaneSizes::default(), area), vec![
+      (0, Rect::new(0, 0, 25, 30)),
+      (1, Rect::new(0, 30, 25, 10)),
+      (5, Rect::new(25, 0, 75, 3)),
+      (7, Rect::new(25, 3, 75, 37)),
+    ]);
+
+    let testing = LayoutConfig {
+      name: String::from("testing"),
+      vertical: true,
+      groups: vec![
+        group(1, vec![KeyScope::Apis, KeyScope::History]),
+        group(1, vec![KeyScope::Address]),
+        group(3, vec![KeyScope::Request, KeyScope::Response]),
+      ],
+    };
+    let mut sizes = PaneSizes::default();
+    sizes.resize("testing", 2, 7, true);
+    assert_eq!(areas(&testing, &heights, &sizes, area), vec![
+      (0, Rect::new(0, 0, 50, 9)),
+      (3, Rect::new(50, 0, 50, 9)),
+      (5, Rect::new(0, 9, 100, 3)),
+      (6, Rect::new(0, 12, 33, 28)),
+      (7, Rect::new(33, 12, 67, 28)),
+    ]);
+  }
+}
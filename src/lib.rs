@@ -0,0 +1,117 @@
+//! Browse, call and test APIs described by OpenAPI specs, in the terminal.
+//!
+//! Besides the `openapi-tui` binary, the crate lets other ratatui apps reuse its parts:
+//! - [`State`] holds a loaded spec, its workspace and the requests sent, and builds the requests of its operations,
+//!   e.g. [`State::call_request`].
+//! - The panes of [`panes`], e.g. the operation list of [`panes::apis::ApisPane`], and the schema tree of
+//!   [`SchemaViewer`], draw themselves from a shared [`State`] and react to [`Action`]s, see [`Pane`].
+//! - [`client::send`] sends a request, and [`script::exchange`] sends it through the scripts and plugins of its
+//!   operation.
+//!
+//! ```no_run
+//! use std::sync::{Arc, RwLock};
+//!
+//! use openapi_tui::{panes::apis::ApisPane, Pane, State};
+//! use ratatui::{prelude::*, Terminal};
+//!
+//! # fn main() -> color_eyre::eyre::Result<()> {
+//! let state = Arc::new(RwLock::new(State::load("petstore.yaml")?));
+//! let mut operations = ApisPane::new(state.clone(), true, Style::default().fg(Color::LightGreen));
+//! let mut terminal = Terminal::new(CrosstermBackend::new(std::io::stdout()))?;
+//! terminal.draw(|frame| {
+//!   operations.draw(frame, frame.size()).unwrap();
+//! })?;
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod action;
+pub mod app;
+pub mod assertion;
+pub mod asyncapi;
+pub mod body_form;
+pub mod bundle;
+pub mod cache;
+pub mod callback;
+pub mod cassette;
+pub mod charset;
+pub mod cli;
+pub mod client;
+pub mod codegen;
+pub mod completion;
+pub mod components;
+pub mod config;
+pub mod crash;
+pub mod diff;
+pub mod digest;
+pub mod export;
+pub mod extensions;
+pub mod extract;
+pub mod fuzz;
+pub mod git;
+pub mod headless;
+pub mod health;
+pub mod history;
+pub mod hosts;
+pub mod import;
+pub mod inventory;
+pub mod json_path;
+pub mod keymap;
+pub mod keys;
+pub mod layout;
+pub mod link;
+pub mod lint;
+pub mod load;
+pub mod markdown;
+pub mod merge;
+pub mod mock;
+pub mod multipart;
+pub mod negotiation;
+pub mod network;
+pub mod operation_compare;
+pub mod operation_filter;
+pub mod operation_index;
+pub mod pages;
+pub mod pagination;
+pub mod palette;
+pub mod panes;
+pub mod parameter;
+pub mod path_tree;
+pub mod plugin;
+pub mod popups;
+pub mod queue;
+pub mod rate_limit;
+pub mod redaction;
+pub mod redirect;
+pub mod registry;
+pub mod remote;
+pub mod report;
+pub mod request;
+pub mod response_diff;
+pub mod script;
+pub mod secrets;
+pub mod security;
+pub mod server;
+pub mod signing;
+pub mod snippets;
+pub mod spec_info;
+pub mod spool;
+pub mod stats;
+pub mod tags;
+pub mod theme;
+pub mod timestamps;
+pub mod toast;
+pub mod tui;
+pub mod undo;
+pub mod usage;
+pub mod utils;
+pub mod webhook;
+pub mod wire;
+pub mod workflow;
+pub mod workspace;
+pub mod xml;
+
+pub use action::Action;
+pub use components::schema_viewer::SchemaViewer;
+pub use pages::home::State;
+pub use panes::Pane;
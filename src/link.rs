@@ -0,0 +1,262 @@
+use serde_json::Value;
+
+use crate::{
+  client::Exchange,
+  mock::{self, METHODS},
+  workspace::{OperationKey, RequestDraft},
+};
+
+/// Locations a link parameter name can be qualified with, e.g. `path.id`.
+const LOCATIONS: [&str; 4] = ["path", "query", "header", "cookie"];
+
+/// Link of a response of an operation to another operation, whose inputs it takes from the response.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Link {
+  /// Status of the response defining the link.
+  pub status: String,
+  pub name: String,
+  pub description: Option<String>,
+  /// Operation of the `operationId` or `operationRef` of the link, `None` when the spec has no such operation.
+  pub target: Option<OperationKey>,
+  /// Parameters of the target by name, with the runtime expressions or constants of their values.
+  pub parameters: Vec<(String, Value)>,
+  pub request_body: Option<Value>,
+}
+
+/// Links of the responses of the operation.
+pub fn links(operation: &Value, document: &Value) -> Vec<Link> {
+  let mut links = vec![];
+  for (status, response) in operation.get("responses").and_then(Value::as_object).into_iter().flatten() {
+    let response = mock::resolve(response, document);
+    for (name, link) in response.get("links").and_then(Value::as_object).into_iter().flatten() {
+      let link = mock::resolve(link, document);
+      links.push(Link {
+        status: status.clone(),
+        name: name.clone(),
+        description: link.get("description").and_then(Value::as_str).map(String::from),
+        target: target(link, document),
+        parameters: link
+          .get("parameters")
+          .and_then(Value::as_object)
+          .into_iter()
+          .flatten()
+          .map(|(name, value)| (name.clone(), value.clone()))
+          .collect(),
+        request_body: link.get("requestBody").cloned(),
+      });
+    }
+  }
+  links
+}
+
+/// Operation the link points at, by `operationId` or by an `operationRef` into the paths of the document.
+fn target(link: &Value, document: &Value) -> Option<OperationKey> {
+  if let Some(operation_id) = link.get("operationId").and_then(Value::as_str) {
+    return document.get("paths").and_then(Value::as_object)?.iter().find_map(|(path, item)| {
+      METHODS
+        .iter()
+        .find(|method| {
+          item.get(**method).and_then(|operation| operation.get("operationId")) == Some(&operation_id.into())
+        })
+        .map(|method| OperationKey::new(method, path))
+    });
+  }
+  let reference = link.get("operationRef").and_then(Value::as_str)?;
+  let pointer = reference.split_once('#').map_or(reference, |(_, pointer)| pointer);
+  let (path, method) = pointer.strip_prefix("/paths/")?.rsplit_once('/')?;
+  let path = path.replace("~1", "/").replace("~0", "~");
+  document.pointer(pointer)?;
+  Some(OperationKey::new(method, &path))
+}
+
+/// Value of a runtime expression, e.g. `$response.body#/id`, or of the `{expression}` parts of a string, the exchange
+/// being the request and response of the operation defining the link. Constants are their own value.
+pub fn evaluate(value: &Value, exchange: &Exchange) -> Option<Value> {
+  let Value::String(text) = value else {
+    return Some(value.clone());
+  };
+  if text.starts_with('$') {
+    return expression(text, exchange);
+  }
+  if !text.contains("{$") {
+    return Some(value.clone());
+  }
+  let mut result = String::new();
+  let mut rest = text.as_str();
+  while let Some(start) = rest.find("{$") {
+    let end = rest[start..].find('}')? + start;
+    result.push_str(&rest[..start]);
+    result.push_str(&text_of(expression(&rest[start + 1..end], exchange)?));
+    rest = &rest[end + 1..];
+  }
+  result.push_str(rest);
+  Some(Value::String(result))
+}
+
+/// Strings as is, other values serialized.
+pub fn text_of(value: Value) -> String {
+  match value {
+    Value::String(text) => text,
+    value => value.to_string(),
+  }
+}
+
+fn expression(expression: &str, exchange: &Exchange) -> Option<Value> {
+  let text = |value: &str| Some(Value::String(value.to_string()));
+  match expression {
+    "$url" => return text(&exchange.request.full_url()),
+    "$method" => return text(&exchange.request.method),
+    "$statusCode" => return Some(Value::from(exchange.response.status)),
+    _ => {},
+  }
+  let (source, rest) = expression
+    .strip_prefix("$request.")
+    .map(|rest| ("request", rest))
+    .or_else(|| expression.strip_prefix("$response.").map(|rest| ("response", rest)))?;
+  if let Some(pointer) = rest.strip_prefix("body") {
+    let body = match source {
+      "request" => exchange.request.body.clone().unwrap_or_default(),
+      _ => exchange.response.body.clone(),
+    };
+    let body: Value = serde_json::from_str(&body).unwrap_or(Value::String(body));
+    return match pointer.strip_prefix('#') {
+      Some(pointer) => body.pointer(pointer).cloned(),
+      None => Some(body),
+    };
+  }
+  let (location, name) = rest.split_once('.')?;
+  match (source, location) {
+    ("request", "header") => {
+      exchange.request.headers.iter().find(|(key, _)| key.eq_ignore_ascii_case(name)).and_then(|(_, value)| text(value))
+    },
+    ("request", "query") => {
+      exchange.request.query.iter().find(|(key, _)| key == name).and_then(|(_, value)| text(value))
+    },
+    ("request", "path") => {
+      let template = &exchange.operation.as_ref()?.path;
+      let placeholder = format!("{{{name}}}");
+      let index = template.split('/').position(|segment| segment == placeholder)?;
+      exchange.request.path.split('/').nth(index).and_then(text)
+    },
+    ("response", "header") => exchange.response.header(name).and_then(text),
+    _ => None,
+  }
+}
+
+/// Draft of the target of the link, filled with the values of its parameters and request body for the exchange.
+/// Unqualified parameter names are located by the parameters of the target, values that cannot be evaluated are
+/// left out.
+pub fn draft(link: &Link, exchange: Option<&Exchange>, document: &Value) -> RequestDraft {
+  let mut draft = RequestDraft::default();
+  let Some(exchange) = exchange else {
+    return draft;
+  };
+  for (name, value) in link.parameters.iter() {
+    let Some(value) = evaluate(value, exchange) else {
+      continue;
+    };
+    let (location, name) = match name.split_once('.') {
+      Some((location, name)) if LOCATIONS.contains(&location) => (location.to_string(), name),
+      _ => (location(link.target.as_ref(), name, document), name.as_str()),
+    };
+    draft.set_parameter(&location, name, text_of(value));
+  }
+  if let Some(body) = link.request_body.as_ref().and_then(|body| evaluate(body, exchange)) {
+    draft.body = Some(match body {
+      Value::String(text) => text,
+      body => serde_json::to_string_pretty(&body).unwrap_or_default(),
+    });
+  }
+  draft
+}
+
+/// Location of the parameter of the target operation, `path` for a `{name}` segment and `query` when unknown.
+fn location(target: Option<&OperationKey>, name: &str, document: &Value) -> String {
+  let Some(target) = target else {
+    return String::from("query");
+  };
+  let item = document.get("paths").and_then(|paths| paths.get(&target.path));
+  let operation = item.and_then(|item| item.get(target.method.to_lowercase()));
+  let parameters = [operation, item]
+    .into_iter()
+    .flatten()
+    .filter_map(|value| value.get("parameters").and_then(Value::as_array))
+    .flatten()
+    .map(|parameter| mock::resolve(parameter, document));
+  for parameter in parameters {
+    if parameter.get("name").and_then(Value::as_str) == Some(name) {
+      if let Some(location) = parameter.get("in").and_then(Value::as_str) {
+        return location.to_string();
+      }
+    }
+  }
+  match target.path.contains(&format!("{{{name}}}")) {
+    true => String::from("path"),
+    false => String::from("query"),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+  use serde_json::json;
+
+  use super::*;
+  use crate::{client::HttpResponse, request::HttpRequest};
+
+  #[test]
+  fn test_links() {
+    let document = json!({
+      "paths": {
+        "/users": { "post": { "responses": { "201": {
+          "description": "Created",
+          "links": {
+            "GetUser": { "operationId": "getUser", "parameters": { "id": "$response.body#/id", "query.fields": "name" } },
+            "UserPets": { "$ref": "#/components/links/UserPets" }
+          }
+        } } } },
+        "/users/{id}": {
+          "parameters": [{ "name": "id", "in": "path" }],
+          "get": { "operationId": "getUser" }
+        },
+        "/users/{id}/pets": { "get": {} }
+      },
+      "components": { "links": { "UserPets": {
+        "operationRef": "#/paths/~1users~1{id}~1pets/get",
+        "parameters": { "id": "$response.body#/id", "X-Trace": "{$response.header.X-Trace}-{$statusCode}" },
+        "requestBody": { "owner": "$request.body#/name" }
+      } } }
+    });
+    let links = links(document.pointer("/paths/~1users/post").unwrap(), &document);
+    assert_eq!(links.iter().map(|link| link.name.as_str()).collect::<Vec<_>>(), vec!["GetUser", "UserPets"]);
+    assert_eq!(links[0].target, Some(OperationKey::new("get", "/users/{id}")));
+    assert_eq!(links[1].target, Some(OperationKey::new("get", "/users/{id}/pets")));
+
+    let exchange = Exchange {
+      operation: Some(OperationKey::new("post", "/users")),
+      request: HttpRequest {
+        method: String::from("POST"),
+        path: String::from("/users"),
+        body: Some(String::from(r#"{ "name": "Ann" }"#)),
+        ..HttpRequest::default()
+      },
+      response: HttpResponse {
+        status: 201,
+        headers: vec![(String::from("X-Trace"), String::from("abc"))],
+        body: String::from(r#"{ "id": 7 }"#),
+        ..HttpResponse::default()
+      },
+      ..Exchange::default()
+    };
+    let draft = draft(&links[0], Some(&exchange), &document);
+    assert_eq!(draft.parameter("path", "id"), Some("7"));
+    assert_eq!(draft.parameter("query", "fields"), Some("name"));
+    assert_eq!(draft.body, None);
+    assert_eq!(evaluate(&json!("{$response.header.X-Trace}-{$statusCode}"), &exchange), Some(json!("abc-201")));
+    assert_eq!(evaluate(&json!("$request.body#/name"), &exchange), Some(json!("Ann")));
+    let draft = super::draft(&links[1], Some(&exchange), &document);
+    assert_eq!(draft.parameter("query", "X-Trace"), Some("abc-201"));
+    assert_eq!(draft.body.as_deref(), Some("{\n  \"owner\": \"$request.body#/name\"\n}"));
+    assert_eq!(evaluate(&json!("$response.body#/missing"), &exchange), None);
+  }
+}
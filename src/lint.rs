@@ -0,0 +1,267 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde_json::Value;
+
+use crate::{merge, mock::METHODS, workspace::OperationKey};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+  Error,
+  Warning,
+  Info,
+}
+
+/// Rule broken by the spec, at the operation it concerns when there is one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+  pub rule: &'static str,
+  pub severity: Severity,
+  pub message: String,
+  pub operation: Option<OperationKey>,
+}
+
+impl Finding {
+  fn new(rule: &'static str, severity: Severity, message: String, operation: Option<&OperationKey>) -> Self {
+    Self { rule, severity, message, operation: operation.cloned() }
+  }
+}
+
+/// Checks the spec against Spectral-style rules, most severe findings first.
+pub fn lint(document: &Value) -> Vec<Finding> {
+  let mut findings = vec![];
+  for (key, operation) in operations(document) {
+    findings.extend(lint_operation(&key, operation));
+  }
+  findings.extend(conflicts(document));
+  findings.extend(unused_schemas(document));
+  findings.sort_by_key(|finding| finding.severity);
+  findings
+}
+
+fn operations(document: &Value) -> Vec<(OperationKey, &Value)> {
+  let paths = document.get("paths").and_then(Value::as_object).into_iter().flatten();
+  paths
+    .flat_map(|(path, item)| {
+      METHODS
+        .iter()
+        .filter_map(|method| item.get(*method).map(|operation| (OperationKey::new(method, path), operation)))
+    })
+    .collect()
+}
+
+/// Operations clashing with others, which break code generators downstream: those sharing an `operationId`, paths
+/// differing only by the names of their templates, e.g. `/pets/{id}` and `/pets/{petId}`, and the operations a merge
+/// left out, another spec having the same method and path.
+pub fn conflicts(document: &Value) -> Vec<Finding> {
+  let operations = operations(document);
+  let mut findings = vec![];
+  let mut operation_ids: BTreeMap<&str, Vec<&OperationKey>> = BTreeMap::new();
+  for (key, operation) in operations.iter() {
+    if let Some(operation_id) = operation.get("operationId").and_then(Value::as_str) {
+      operation_ids.entry(operation_id).or_default().push(key);
+    }
+  }
+  for (operation_id, operations) in operation_ids.iter().filter(|(_, operations)| operations.len() > 1) {
+    for operation in operations {
+      findings.push(Finding::new(
+        "operation-operationId-unique",
+        Severity::Error,
+        format!("operationId `{operation_id}` is shared by {} operations", operations.len()),
+        Some(operation),
+      ));
+    }
+  }
+  // paths by their form with the templates left unnamed, e.g. `/pets/{}`
+  let mut templates: BTreeMap<String, Vec<&str>> = BTreeMap::new();
+  for path in document.get("paths").and_then(Value::as_object).into_iter().flat_map(|paths| paths.keys()) {
+    templates.entry(unnamed_templates(path)).or_default().push(path);
+  }
+  for paths in templates.values().filter(|paths| paths.len() > 1) {
+    for path in &paths[1..] {
+      let operation = operations.iter().find(|(key, _)| key.path == *path).map(|(key, _)| key);
+      findings.push(Finding::new(
+        "no-ambiguous-paths",
+        Severity::Error,
+        format!("{path} is the same path as {}, only its templates are named differently", paths[0]),
+        operation,
+      ));
+    }
+  }
+  let left_out = document.get(merge::CONFLICTS_KEY).and_then(Value::as_array).into_iter().flatten();
+  for conflict in left_out.filter_map(Value::as_str) {
+    // e.g. `GET /pets of billing, already in users`, at the operation kept
+    let operation = conflict
+      .split_once(' ')
+      .and_then(|(method, rest)| rest.split_once(" of ").map(|(path, _)| OperationKey::new(method, path)));
+    findings.push(Finding::new("merge-conflict", Severity::Error, format!("left out {conflict}"), operation.as_ref()));
+  }
+  findings
+}
+
+fn unnamed_templates(path: &str) -> String {
+  let mut unnamed = String::new();
+  let mut in_template = false;
+  for c in path.chars() {
+    match c {
+      '{' => in_template = true,
+      '}' => in_template = false,
+      _ if in_template => continue,
+      _ => {},
+    }
+    unnamed.push(c);
+  }
+  unnamed
+}
+
+fn lint_operation(key: &OperationKey, operation: &Value) -> Vec<Finding> {
+  let mut findings = vec![];
+  let text = |name: &str| operation.get(name).and_then(Value::as_str).is_some_and(|text| !text.trim().is_empty());
+  if !text("description") && !text("summary") {
+    findings.push(Finding::new(
+      "operation-description",
+      Severity::Warning,
+      format!("{key} has no description or summary"),
+      Some(key),
+    ));
+  }
+  if !text("operationId") {
+    findings.push(Finding::new(
+      "operation-operationId",
+      Severity::Warning,
+      format!("{key} has no operationId"),
+      Some(key),
+    ));
+  }
+  let responses = operation.get("responses").and_then(Value::as_object);
+  match responses.filter(|responses| !responses.is_empty()) {
+    None => {
+      findings.push(Finding::new(
+        "operation-responses",
+        Severity::Error,
+        format!("{key} defines no responses"),
+        Some(key),
+      ))
+    },
+    Some(responses) if !responses.keys().any(|status| status.starts_with('2') || status.starts_with('3')) => {
+      findings.push(Finding::new(
+        "operation-success-response",
+        Severity::Warning,
+        format!("{key} defines no 2xx or 3xx response"),
+        Some(key),
+      ))
+    },
+    Some(_) => {},
+  }
+  findings
+}
+
+/// Component schemas that no `$ref` of the spec points to.
+fn unused_schemas(document: &Value) -> Vec<Finding> {
+  let Some(schemas) = document.get("components").and_then(|components| components.get("schemas")) else {
+    return vec![];
+  };
+  let mut references = BTreeSet::new();
+  collect_references(document, &mut references);
+  schemas
+    .as_object()
+    .into_iter()
+    .flatten()
+    .map(|(name, _)| name)
+    .filter(|name| !references.contains(format!("#/components/schemas/{name}").as_str()))
+    .map(|name| {
+      Finding::new("oas3-unused-component", Severity::Info, format!("schema `{name}` is never referenced"), None)
+    })
+    .collect()
+}
+
+fn collect_references<'a>(value: &'a Value, references: &mut BTreeSet<&'a str>) {
+  match value {
+    Value::Object(object) => {
+      for (name, value) in object {
+        match (name.as_str(), value) {
+          ("$ref", Value::String(reference)) => {
+            references.insert(reference);
+          },
+          _ => collect_references(value, references),
+        }
+      }
+    },
+    Value::Array(items) => items.iter().for_each(|item| collect_references(item, references)),
+    _ => {},
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+  use serde_json::json;
+
+  use super::*;
+
+  #[test]
+  fn test_lint() {
+    let document = json!({
+      "paths": {
+        "/pets": {
+          "get": {
+            "operationId": "listPets",
+            "summary": "List pets",
+            "responses": { "200": { "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Pet" } } } } }
+          },
+          "post": { "operationId": "listPets", "description": "Add a pet", "responses": { "400": {} } }
+        },
+        "/pets/{id}": { "delete": {} }
+      },
+      "components": { "schemas": { "Pet": {}, "Owner": {} } }
+    });
+    let delete = OperationKey::new("delete", "/pets/{id}");
+    let post = OperationKey::new("post", "/pets");
+    let found: Vec<(&str, Option<OperationKey>)> =
+      lint(&document).into_iter().map(|finding| (finding.rule, finding.operation)).collect();
+    assert_eq!(found, vec![
+      ("operation-responses", Some(delete.clone())),
+      ("operation-operationId-unique", Some(OperationKey::new("get", "/pets"))),
+      ("operation-operationId-unique", Some(post.clone())),
+      ("operation-success-response", Some(post)),
+      ("operation-description", Some(delete.clone())),
+      ("operation-operationId", Some(delete)),
+      ("oas3-unused-component", None),
+    ]);
+  }
+
+  #[test]
+  fn test_conflicts() {
+    let document = json!({
+      "paths": {
+        "/pets/{id}": { "get": { "operationId": "getPet" } },
+        "/pets/{petId}": { "delete": { "operationId": "getPet" } },
+        "/pets": { "get": {} }
+      },
+      "x-merge-conflicts": ["GET /pets of billing, already in users"]
+    });
+    let found: Vec<(&str, String, Option<OperationKey>)> =
+      conflicts(&document).into_iter().map(|finding| (finding.rule, finding.message, finding.operation)).collect();
+    assert_eq!(found, vec![
+      (
+        "operation-operationId-unique",
+        String::from("operationId `getPet` is shared by 2 operations"),
+        Some(OperationKey::new("get", "/pets/{id}"))
+      ),
+      (
+        "operation-operationId-unique",
+        String::from("operationId `getPet` is shared by 2 operations"),
+        Some(OperationKey::new("delete", "/pets/{petId}"))
+      ),
+      (
+        "no-ambiguous-paths",
+        String::from("/pets/{petId} is the same path as /pets/{id}, only its templates are named differently"),
+        Some(OperationKey::new("delete", "/pets/{petId}"))
+      ),
+      (
+        "merge-conflict",
+        String::from("left out GET /pets of billing, already in users"),
+        Some(OperationKey::new("get", "/pets"))
+      ),
+    ]);
+  }
+}
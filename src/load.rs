@@ -0,0 +1,158 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+  request::HttpRequest,
+  script::{self, Hooks},
+  workspace::OperationKey,
+};
+
+pub const DEFAULT_REQUESTS: usize = 100;
+pub const DEFAULT_CONCURRENCY: usize = 10;
+
+/// Outcome of one request of a load test.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Sample {
+  pub elapsed_ms: u64,
+  /// `None` when the request failed before a response was received.
+  pub status: Option<u16>,
+  pub error: Option<String>,
+}
+
+impl Sample {
+  /// Failed requests and responses with a 4xx or 5xx status.
+  pub fn failed(&self) -> bool {
+    self.status.is_none_or(|status| status >= 400)
+  }
+}
+
+/// Throughput, latencies and errors of the samples of a load test.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Summary {
+  pub sent: usize,
+  pub errors: usize,
+  /// Share of failed samples, from 0 to 1.
+  pub error_rate: f64,
+  /// Requests per second over the elapsed time.
+  pub rps: f64,
+  pub min_ms: u64,
+  pub mean_ms: u64,
+  pub p50_ms: u64,
+  pub p90_ms: u64,
+  pub p95_ms: u64,
+  pub p99_ms: u64,
+  pub max_ms: u64,
+  /// Responses by status, failed requests left out.
+  pub statuses: BTreeMap<u16, usize>,
+}
+
+impl Summary {
+  pub fn of(samples: &[Sample], elapsed: Duration) -> Self {
+    let mut latencies: Vec<u64> = samples.iter().map(|sample| sample.elapsed_ms).collect();
+    latencies.sort_unstable();
+    let errors = samples.iter().filter(|sample| sample.failed()).count();
+    let mut statuses = BTreeMap::new();
+    for status in samples.iter().filter_map(|sample| sample.status) {
+      *statuses.entry(status).or_default() += 1;
+    }
+    let sent = samples.len();
+    Self {
+      sent,
+      errors,
+      error_rate: if sent == 0 { 0.0 } else { errors as f64 / sent as f64 },
+      rps: if elapsed.is_zero() { 0.0 } else { sent as f64 / elapsed.as_secs_f64() },
+      min_ms: latencies.first().copied().unwrap_or_default(),
+      mean_ms: latencies.iter().sum::<u64>().checked_div(sent as u64).unwrap_or_default(),
+      p50_ms: percentile(&latencies, 50),
+      p90_ms: percentile(&latencies, 90),
+      p95_ms: percentile(&latencies, 95),
+      p99_ms: percentile(&latencies, 99),
+      max_ms: latencies.last().copied().unwrap_or_default(),
+      statuses,
+    }
+  }
+}
+
+/// Nearest-rank percentile of the sorted latencies.
+fn percentile(sorted: &[u64], percent: usize) -> u64 {
+  if sorted.is_empty() {
+    return 0;
+  }
+  let rank = (percent * sorted.len()).div_ceil(100).max(1);
+  sorted[rank - 1]
+}
+
+/// Request fired many times by a load test.
+pub struct LoadTest {
+  pub operation: Option<OperationKey>,
+  pub request: HttpRequest,
+  pub hooks: Hooks,
+  pub variables: BTreeMap<String, String>,
+  pub requests: usize,
+  pub concurrency: usize,
+}
+
+impl LoadTest {
+  /// Sends the request `requests` times, `concurrency` at a time, through the scripts and plugins of its operation,
+  /// and hands each sample to `sampled` as soon as it is received. Stops sending once cancelled.
+  pub async fn run(self, cancellation: CancellationToken, sampled: impl Fn(Sample)) {
+    let Self { operation, request, hooks, variables, requests, concurrency } = self;
+    let samples = futures::stream::iter(0..requests)
+      .map(|_| {
+        let (operation, request) = (operation.clone(), request.clone());
+        let (hooks, variables) = (&hooks, &variables);
+        async move {
+          let started = std::time::Instant::now();
+          match script::exchange(operation, request, hooks, variables).await {
+            Ok(exchange) => {
+              Sample { elapsed_ms: exchange.elapsed_ms, status: Some(exchange.response.status), error: None }
+            },
+            Err(error) => {
+              let elapsed_ms = started.elapsed().as_millis().try_into().unwrap_or(u64::MAX);
+              Sample { elapsed_ms, status: None, error: Some(error.to_string()) }
+            },
+          }
+        }
+      })
+      .buffer_unordered(concurrency.max(1))
+      .take_until(cancellation.cancelled());
+    futures::pin_mut!(samples);
+    while let Some(sample) = samples.next().await {
+      sampled(sample);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_summary() {
+    let sample = |elapsed_ms, status| Sample { elapsed_ms, status, error: None };
+    let mut samples: Vec<Sample> = (1..=18).map(|elapsed_ms| sample(elapsed_ms * 10, Some(200))).collect();
+    samples.push(sample(500, Some(503)));
+    samples.push(sample(1000, None));
+    let summary = Summary::of(&samples, Duration::from_secs(4));
+    assert_eq!(summary, Summary {
+      sent: 20,
+      errors: 2,
+      error_rate: 0.1,
+      rps: 5.0,
+      min_ms: 10,
+      mean_ms: 160,
+      p50_ms: 100,
+      p90_ms: 180,
+      p95_ms: 500,
+      p99_ms: 1000,
+      max_ms: 1000,
+      statuses: BTreeMap::from([(200, 18), (503, 1)]),
+    });
+    assert_eq!(Summary::of(&[], Duration::ZERO), Summary::default());
+  }
+}
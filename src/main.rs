@@ -1,29 +1,103 @@
-pub mod action;
-pub mod app;
-pub mod cli;
-pub mod components;
-pub mod config;
-pub mod pages;
-pub mod panes;
-pub mod tui;
-pub mod utils;
-
-use clap::Parser;
-use cli::Cli;
+use clap::{CommandFactory, Parser};
+use clap_complete::CompleteEnv;
 use color_eyre::eyre::Result;
-
-use crate::{
+use openapi_tui::{
   app::App,
-  utils::{initialize_logging, initialize_panic_handler},
+  bundle,
+  cli::{self, Cli},
+  diff,
+  export::docs,
+  git, inventory, merge,
+  pages::home::ParsedSpec,
+  remote, secrets,
+  utils::{initialize_logging, initialize_panic_handler, restore_terminal, write_crash_report},
 };
 
 async fn tokio_main() -> Result<()> {
-  initialize_logging()?;
+  // answers the shell completing a command line, when run with `COMPLETE=<shell>`
+  CompleteEnv::with_factory(Cli::command).complete();
+
+  let mut args = Cli::parse();
+
+  initialize_logging(args.log_file.as_deref(), args.verbose)?;
 
   initialize_panic_handler()?;
 
-  let args = Cli::parse();
-  let mut app = App::new(args.openapi_path)?;
+  remote::resolve_args(&mut args).await?;
+  git::resolve_args(&mut args)?;
+  for spec in args.specs_mut() {
+    *spec = merge::resolve(spec)?;
+  }
+
+  if let Some(name) = &args.store_secret {
+    secrets::store_from_stdin(name)?;
+    println!("Stored `{name}` in the keyring, reference it as `{}{name}`", secrets::KEYRING_PREFIX);
+    return Ok(());
+  }
+  if let Some(cli::Command::Diff { old, new }) = &args.command {
+    let changes = diff::diff(&diff::load(old)?, &diff::load(new)?);
+    for line in diff::summary(&changes) {
+      println!("{line}");
+    }
+    if changes.iter().any(|change| change.breaking) {
+      std::process::exit(1);
+    }
+    return Ok(());
+  }
+  if let Some(cli::Command::List { spec, tag, method, format }) = &args.command {
+    let document = diff::load(spec.as_ref().unwrap_or(&args.openapi_path))?;
+    let operations = inventory::operations(&document, tag.as_deref(), method.as_deref());
+    match format.as_str() {
+      "json" => println!("{}", serde_json::to_string_pretty(&operations)?),
+      _ => {
+        for line in inventory::table(&operations) {
+          println!("{line}");
+        }
+      },
+    }
+    return Ok(());
+  }
+  if let Some(cli::Command::ExportDocs { spec, format, output }) = &args.command {
+    let parsed = ParsedSpec::parse(spec.as_ref().unwrap_or(&args.openapi_path))?;
+    let docs = match format.as_str() {
+      "html" => docs::html(&parsed),
+      _ => docs::markdown(&parsed),
+    };
+    match output {
+      Some(output) => std::fs::write(output, docs)?,
+      None => print!("{docs}"),
+    }
+    return Ok(());
+  }
+  if let Some(cli::Command::Bundle { spec, output, tags, operations }) = &args.command {
+    let mut document = bundle::bundle(spec.as_ref().unwrap_or(&args.openapi_path))?;
+    bundle::filter(&mut document, tags, operations);
+    let bundled = bundle::render(&document, output.as_deref())?;
+    match output {
+      Some(output) => std::fs::write(output, bundled)?,
+      None => print!("{bundled}"),
+    }
+    return Ok(());
+  }
+  if let Some(cli::Command::Call { .. }) = &args.command {
+    if !App::call(args).await? {
+      std::process::exit(1);
+    }
+    return Ok(());
+  }
+  if let Some(cli::Command::Headless { .. }) = &args.command {
+    if !App::headless(args).await? {
+      std::process::exit(1);
+    }
+    return Ok(());
+  }
+  if let Some(cli::Command::Test { .. }) = &args.command {
+    if !App::test(args).await? {
+      std::process::exit(1);
+    }
+    return Ok(());
+  }
+  let mut app = App::new(args)?;
   app.run().await?;
 
   Ok(())
@@ -32,7 +106,9 @@ async fn tokio_main() -> Result<()> {
 #[tokio::main]
 async fn main() -> Result<()> {
   if let Err(e) = tokio_main().await {
+    restore_terminal();
     eprintln!("{} error: Something went wrong", env!("CARGO_PKG_NAME"));
+    write_crash_report(&format!("{e:?}"));
     Err(e)
   } else {
     Ok(())
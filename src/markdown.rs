@@ -0,0 +1,224 @@
+use ratatui::prelude::*;
+
+/// Line of rendered Markdown, with the targets of the links it shows.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MarkdownLine {
+  pub spans: Vec<(Style, String)>,
+  pub links: Vec<String>,
+}
+
+impl MarkdownLine {
+  fn push(&mut self, style: Style, text: impl Into<String>) {
+    let text = text.into();
+    if !text.is_empty() {
+      self.spans.push((style, text));
+    }
+  }
+}
+
+/// Renders the CommonMark of descriptions line by line: headings, emphasis, inline code, fenced code blocks, lists,
+/// block quotes and links, whose targets are kept to be copied.
+pub fn render(text: &str) -> Vec<MarkdownLine> {
+  let mut lines = vec![];
+  let mut fence: Option<&str> = None;
+  for source in text.trim_end().lines() {
+    let mut line = MarkdownLine::default();
+    let trimmed = source.trim_start();
+    let indent = &source[..source.len() - trimmed.len()];
+    if let Some(marker) = fence {
+      if trimmed.starts_with(marker) {
+        fence = None;
+      } else {
+        line.push(code_style(), format!("  {source}"));
+        lines.push(line);
+      }
+      continue;
+    }
+    if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+      fence = Some(&trimmed[..3]);
+      continue;
+    }
+    let heading = trimmed.chars().take_while(|c| *c == '#').count();
+    if (1..=6).contains(&heading) && trimmed[heading..].starts_with(' ') {
+      let style = match heading {
+        1 => Style::default().bold().underlined(),
+        _ => Style::default().bold(),
+      };
+      inline(trimmed[heading..].trim().trim_end_matches('#').trim_end(), style, &mut line);
+    } else if trimmed.len() >= 3 && ["-", "*", "_"].iter().any(|rule| trimmed.replace(' ', "") == rule.repeat(3)) {
+      line.push(Style::default().dim(), "─".repeat(24));
+    } else if let Some(quote) = trimmed.strip_prefix('>') {
+      line.push(Style::default().dim(), format!("{indent}│ "));
+      inline(quote.trim_start(), Style::default().dim().italic(), &mut line);
+    } else if let Some(item) = ["- ", "* ", "+ "].iter().find_map(|bullet| trimmed.strip_prefix(bullet)) {
+      line.push(Style::default(), format!("{indent}• "));
+      inline(item, Style::default(), &mut line);
+    } else if let Some((number, item)) = ordered_item(trimmed) {
+      line.push(Style::default(), format!("{indent}{number}. "));
+      inline(item, Style::default(), &mut line);
+    } else {
+      line.push(Style::default(), indent);
+      inline(trimmed, Style::default(), &mut line);
+    }
+    lines.push(line);
+  }
+  lines
+}
+
+fn code_style() -> Style {
+  Style::default().fg(Color::LightYellow)
+}
+
+fn link_style() -> Style {
+  Style::default().fg(Color::LightBlue).underlined()
+}
+
+/// Number and text of an ordered list item, e.g. `1. text` or `1) text`.
+fn ordered_item(text: &str) -> Option<(&str, &str)> {
+  let digits = text.chars().take_while(char::is_ascii_digit).count();
+  if digits == 0 || digits > 9 {
+    return None;
+  }
+  let rest = text[digits..].strip_prefix(". ").or_else(|| text[digits..].strip_prefix(") "))?;
+  Some((&text[..digits], rest))
+}
+
+/// Spans of the inline Markdown of the text: `**strong**`, `*emphasis*`, `` `code` ``, `[label](url)` and `<url>`.
+fn inline(text: &str, base: Style, line: &mut MarkdownLine) {
+  let chars: Vec<char> = text.chars().collect();
+  let (mut strong, mut emphasis) = (false, false);
+  let mut buffer = String::new();
+  let style = |strong: bool, emphasis: bool| {
+    let style = if strong { base.bold() } else { base };
+    if emphasis {
+      style.italic()
+    } else {
+      style
+    }
+  };
+  let mut index = 0;
+  while index < chars.len() {
+    let c = chars[index];
+    match c {
+      '\\' if chars.get(index + 1).is_some_and(char::is_ascii_punctuation) => {
+        buffer.push(chars[index + 1]);
+        index += 2;
+        continue;
+      },
+      '`' => {
+        if let Some(end) = find(&chars, index + 1, "`") {
+          line.push(style(strong, emphasis), std::mem::take(&mut buffer));
+          line.push(code_style(), chars[index + 1..end].iter().collect::<String>());
+          index = end + 1;
+          continue;
+        }
+      },
+      '[' => {
+        if let Some((label, url, next)) = link(&chars, index) {
+          line.push(style(strong, emphasis), std::mem::take(&mut buffer));
+          line.push(link_style(), label);
+          line.links.push(url);
+          index = next;
+          continue;
+        }
+      },
+      '<' => {
+        if let Some(end) = find(&chars, index + 1, ">") {
+          let url: String = chars[index + 1..end].iter().collect();
+          if ["http://", "https://", "mailto:"].iter().any(|scheme| url.starts_with(scheme)) && !url.contains(' ') {
+            line.push(style(strong, emphasis), std::mem::take(&mut buffer));
+            line.push(link_style(), url.clone());
+            line.links.push(url);
+            index = end + 1;
+            continue;
+          }
+        }
+      },
+      '*' | '_' => {
+        let double = chars.get(index + 1) == Some(&c);
+        let delimiter = c.to_string().repeat(if double { 2 } else { 1 });
+        let after = index + delimiter.len();
+        let previous = index.checked_sub(1).map(|previous| chars[previous]);
+        let next = chars.get(after).copied();
+        let open = if double { strong } else { emphasis };
+        let toggles = match open {
+          true => previous.is_some_and(|previous| !previous.is_whitespace()),
+          false => next.is_some_and(|next| !next.is_whitespace()) && find(&chars, after + 1, &delimiter).is_some(),
+        };
+        // underscores inside words, as in snake_case names, are not emphasis
+        let intraword = c == '_'
+          && match open {
+            true => next.is_some_and(char::is_alphanumeric),
+            false => previous.is_some_and(char::is_alphanumeric),
+          };
+        if toggles && !intraword {
+          line.push(style(strong, emphasis), std::mem::take(&mut buffer));
+          match double {
+            true => strong = !strong,
+            false => emphasis = !emphasis,
+          }
+          index = after;
+          continue;
+        }
+      },
+      _ => {},
+    }
+    buffer.push(c);
+    index += 1;
+  }
+  line.push(style(strong, emphasis), buffer);
+}
+
+/// Index of the next occurrence of the delimiter from `start`.
+fn find(chars: &[char], start: usize, delimiter: &str) -> Option<usize> {
+  let delimiter: Vec<char> = delimiter.chars().collect();
+  (start..chars.len()).find(|index| chars[*index..].starts_with(&delimiter))
+}
+
+/// Label, url and next index of the `[label](url)` link at `start`.
+fn link(chars: &[char], start: usize) -> Option<(String, String, usize)> {
+  let close = find(chars, start + 1, "]")?;
+  if chars.get(close + 1) != Some(&'(') {
+    return None;
+  }
+  let end = find(chars, close + 2, ")")?;
+  let label: String = chars[start + 1..close].iter().collect();
+  let target: String = chars[close + 2..end].iter().collect();
+  // a link title, as in `[label](url "title")`, is not part of the url
+  let url = target.split_whitespace().next()?.trim_matches(|c| c == '<' || c == '>').to_string();
+  Some((label, url, end + 1))
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_render() {
+    let text = "# Pets\n\nLists **all** the *pets*, see [the guide](https://example.com/guide \"Guide\").\n\n- by \
+                `status`\n  - or snake_case_names\n1. <https://example.com>\n```json\n{ \"id\": 1 }\n```\n> 2 * 3";
+    let lines = render(text);
+    let texts: Vec<String> =
+      lines.iter().map(|line| line.spans.iter().map(|(_, text)| text.as_str()).collect()).collect();
+    assert_eq!(texts, vec![
+      "Pets",
+      "",
+      "Lists all the pets, see the guide.",
+      "",
+      "• by status",
+      "  • or snake_case_names",
+      "1. https://example.com",
+      "  { \"id\": 1 }",
+      "│ 2 * 3",
+    ]);
+    assert_eq!(lines[0].spans, vec![(Style::default().bold().underlined(), String::from("Pets"))]);
+    assert_eq!(lines[2].spans[1], (Style::default().bold(), String::from("all")));
+    assert_eq!(lines[2].spans[3], (Style::default().italic(), String::from("pets")));
+    assert_eq!(lines[2].spans[5], (link_style(), String::from("the guide")));
+    assert_eq!(lines[2].links, vec![String::from("https://example.com/guide")]);
+    assert_eq!(lines[4].spans[2], (code_style(), String::from("status")));
+    assert_eq!(lines[6].links, vec![String::from("https://example.com")]);
+  }
+}
@@ -0,0 +1,386 @@
+use std::{
+  collections::BTreeMap,
+  path::{Path, PathBuf},
+};
+
+use color_eyre::eyre::{eyre, Result};
+use serde_json::{json, Map, Value};
+
+use crate::{diff, mock::METHODS, remote};
+
+/// Key of the operations of a merged spec naming the spec they come from.
+pub const SOURCE_KEY: &str = "x-source";
+/// Key of the merged spec listing the operations left out, another spec having one of the same method and path.
+pub const CONFLICTS_KEY: &str = "x-merge-conflicts";
+
+/// Whether the spec path names several specs to merge, separated by commas or matched by `*` and `?` in file names.
+pub fn is_merge(openapi_path: &str) -> bool {
+  !remote::is_url(openapi_path) && openapi_path.contains([',', '*', '?'])
+}
+
+/// Path of the spec: the path itself, or for several specs the spec merging them, written under the cache directory.
+pub fn resolve(openapi_path: &str) -> Result<String> {
+  if !is_merge(openapi_path) {
+    return Ok(openapi_path.to_string());
+  }
+  let paths = spec_paths(openapi_path)?;
+  if paths.is_empty() {
+    return Err(eyre!("No spec matches {openapi_path}"));
+  }
+  let mut specs = vec![];
+  for path in paths {
+    let document = diff::load(&path.display().to_string()).map_err(|error| eyre!("{}: {error}", path.display()))?;
+    specs.push((source_name(&path), document));
+  }
+  let document = merge(&specs);
+  for conflict in document.get(CONFLICTS_KEY).and_then(Value::as_array).into_iter().flatten() {
+    log::warn!("Merge conflict: {}", conflict.as_str().unwrap_or_default());
+  }
+  let absolute = std::env::current_dir()?.join(openapi_path);
+  let name: String = absolute
+    .display()
+    .to_string()
+    .chars()
+    .map(|c| if c.is_ascii_alphanumeric() || c == '.' || c == '-' { c } else { '_' })
+    .collect();
+  let merged = remote::cache_dir().join(format!("merged{}.json", name.trim_end_matches(".json")));
+  std::fs::create_dir_all(remote::cache_dir())?;
+  std::fs::write(&merged, serde_json::to_string_pretty(&document)?)?;
+  Ok(merged.display().to_string())
+}
+
+/// Specs of the comma separated paths, those whose file name has wildcards standing for the specs they match, sorted.
+fn spec_paths(pattern: &str) -> Result<Vec<PathBuf>> {
+  let mut paths: Vec<PathBuf> = vec![];
+  for part in pattern.split(',').map(str::trim).filter(|part| !part.is_empty()) {
+    let path = Path::new(part);
+    let name = path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+    if !name.contains(['*', '?']) {
+      paths.push(path.to_path_buf());
+      continue;
+    }
+    let directory = path.parent().filter(|parent| !parent.as_os_str().is_empty()).unwrap_or(Path::new("."));
+    let mut matched: Vec<PathBuf> = std::fs::read_dir(directory)?
+      .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+      .filter(|path| {
+        path.is_file() && path.file_name().and_then(|name| name.to_str()).is_some_and(|file| wildcard(name, file))
+      })
+      .collect();
+    matched.sort();
+    paths.extend(matched);
+  }
+  let mut unique = vec![];
+  for path in paths {
+    if !unique.contains(&path) {
+      unique.push(path);
+    }
+  }
+  Ok(unique)
+}
+
+/// Whether the name matches the pattern, `*` standing for any characters and `?` for one.
+fn wildcard(pattern: &str, name: &str) -> bool {
+  let (pattern, name): (Vec<char>, Vec<char>) = (pattern.chars().collect(), name.chars().collect());
+  let (mut p, mut n) = (0, 0);
+  // position of the last `*` in the pattern and of the name it was tried against
+  let mut star: Option<(usize, usize)> = None;
+  while n < name.len() {
+    match pattern.get(p) {
+      Some('*') => {
+        star = Some((p, n));
+        p += 1;
+      },
+      Some(c) if *c == '?' || *c == name[n] => {
+        p += 1;
+        n += 1;
+      },
+      _ => {
+        let Some((star_p, star_n)) = star else {
+          return false;
+        };
+        p = star_p + 1;
+        n = star_n + 1;
+        star = Some((star_p, star_n + 1));
+      },
+    }
+  }
+  pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Name of the spec shown next to its operations, its file name without extension.
+fn source_name(path: &Path) -> String {
+  let stem = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or("spec");
+  stem.chars().map(|c| if c.is_ascii_alphanumeric() || c == '.' || c == '-' { c } else { '_' }).collect()
+}
+
+/// One spec of the named specs, in order. Operations note their spec under [`SOURCE_KEY`] and take along the servers,
+/// security and path parameters they inherited from it. An operation of the method and path of an earlier spec's is
+/// left out and listed under [`CONFLICTS_KEY`]. Components differing from an earlier spec's of the same name are
+/// renamed with the name of their spec appended, e.g. `Error_billing`, along with the references to them.
+pub fn merge(specs: &[(String, Value)]) -> Value {
+  let sources: Vec<&str> = specs.iter().map(|(source, _)| source.as_str()).collect();
+  let openapi = specs.first().and_then(|(_, document)| document.get("openapi").cloned()).unwrap_or(json!("3.0.3"));
+  let mut merged = json!({
+    "openapi": openapi,
+    "info": { "title": format!("Merged {}", sources.join(", ")), "version": "" },
+    "servers": [],
+    "tags": [],
+    "paths": {},
+    "components": {},
+  });
+  let mut conflicts: Vec<String> = vec![];
+  for (source, document) in specs {
+    let mut document = document.clone();
+    rename_components(&merged, &mut document, source);
+    let servers = document.get("servers").cloned().unwrap_or(json!([]));
+    let security = document.get("security").cloned();
+    let paths = document.get("paths").and_then(Value::as_object).cloned().unwrap_or_default();
+    for (path, item) in paths {
+      let merged_item = merged["paths"].as_object_mut().unwrap().entry(path.clone()).or_insert_with(|| {
+        let mut item = item.clone();
+        if let Some(item) = item.as_object_mut() {
+          item.retain(|key, _| !METHODS.contains(&key.as_str()) && key != "servers" && key != "parameters");
+        }
+        item
+      });
+      for method in METHODS {
+        let Some(mut operation) = item.get(method).cloned() else {
+          continue;
+        };
+        if let Some(existing) = merged_item.get(method) {
+          let kept = existing.get(SOURCE_KEY).and_then(Value::as_str).unwrap_or_default();
+          conflicts.push(format!("{} {path} of {source}, already in {kept}", method.to_uppercase()));
+          continue;
+        }
+        if let Some(operation) = operation.as_object_mut() {
+          operation.insert(String::from(SOURCE_KEY), json!(source));
+          inherit(operation, &item, &servers, security.as_ref());
+        }
+        merged_item[method] = operation;
+      }
+    }
+    for tag in document.get("tags").and_then(Value::as_array).into_iter().flatten() {
+      let tags = merged["tags"].as_array_mut().unwrap();
+      if !tags.iter().any(|merged_tag| merged_tag.get("name") == tag.get("name")) {
+        tags.push(tag.clone());
+      }
+    }
+    for server in servers.as_array().into_iter().flatten() {
+      let merged_servers = merged["servers"].as_array_mut().unwrap();
+      if !merged_servers.contains(server) {
+        merged_servers.push(server.clone());
+      }
+    }
+    for (kind, definitions) in document.get("components").and_then(Value::as_object).into_iter().flatten() {
+      let merged_definitions = merged["components"].as_object_mut().unwrap().entry(kind.clone()).or_insert(json!({}));
+      for (name, definition) in definitions.as_object().into_iter().flatten() {
+        merged_definitions.as_object_mut().unwrap().entry(name.clone()).or_insert(definition.clone());
+      }
+    }
+  }
+  if !conflicts.is_empty() {
+    merged[CONFLICTS_KEY] = json!(conflicts);
+  }
+  merged
+}
+
+/// Moves what the operation inherits from its path item and its spec onto it, as the operations of a path may come from
+/// different specs once merged.
+fn inherit(operation: &mut Map<String, Value>, item: &Value, servers: &Value, security: Option<&Value>) {
+  if !operation.contains_key("servers") {
+    let servers = item.get("servers").unwrap_or(servers);
+    if servers.as_array().is_some_and(|servers| !servers.is_empty()) {
+      operation.insert(String::from("servers"), servers.clone());
+    }
+  }
+  if let Some(security) = security.filter(|_| !operation.contains_key("security")) {
+    operation.insert(String::from("security"), security.clone());
+  }
+  let path_parameters = item.get("parameters").and_then(Value::as_array).cloned().unwrap_or_default();
+  if path_parameters.is_empty() {
+    return;
+  }
+  let parameters = operation.entry("parameters").or_insert(json!([])).as_array_mut();
+  let Some(parameters) = parameters else {
+    return;
+  };
+  let identity = |parameter: &Value| (parameter.get("name").cloned(), parameter.get("in").cloned());
+  for parameter in path_parameters {
+    let overridden = parameters.iter().any(|existing| {
+      existing == &parameter || (existing.get("name").is_some() && identity(existing) == identity(&parameter))
+    });
+    if !overridden {
+      parameters.push(parameter);
+    }
+  }
+}
+
+/// Renames the components of the document that the merged spec defines otherwise, along with the references to them
+/// and, for security schemes, the security requirements naming them.
+fn rename_components(merged: &Value, document: &mut Value, source: &str) {
+  let mut renamed: Vec<(String, String, String)> = vec![];
+  for (kind, definitions) in document.get("components").and_then(Value::as_object).into_iter().flatten() {
+    for (name, definition) in definitions.as_object().into_iter().flatten() {
+      let existing = merged.get("components").and_then(|components| components.get(kind)?.get(name));
+      if existing.is_some_and(|existing| existing != definition) {
+        renamed.push((kind.clone(), name.clone(), format!("{name}_{source}")));
+      }
+    }
+  }
+  if renamed.is_empty() {
+    return;
+  }
+  for (kind, old, new) in renamed.iter() {
+    if let Some(definitions) = document["components"][kind].as_object_mut() {
+      if let Some(definition) = definitions.remove(old) {
+        definitions.insert(new.clone(), definition);
+      }
+    }
+  }
+  let references: BTreeMap<String, String> = renamed
+    .iter()
+    .map(|(kind, old, new)| (format!("#/components/{kind}/{old}"), format!("#/components/{kind}/{new}")))
+    .collect();
+  rename_references(document, &references);
+  let schemes: BTreeMap<&str, &str> = renamed
+    .iter()
+    .filter(|(kind, ..)| kind == "securitySchemes")
+    .map(|(_, old, new)| (old.as_str(), new.as_str()))
+    .collect();
+  if !schemes.is_empty() {
+    rename_schemes(document.get_mut("security"), &schemes);
+    for item in
+      document.get_mut("paths").and_then(Value::as_object_mut).into_iter().flat_map(|paths| paths.values_mut())
+    {
+      for method in METHODS {
+        rename_schemes(item.get_mut(method).and_then(|operation| operation.get_mut("security")), &schemes);
+      }
+    }
+  }
+}
+
+fn rename_references(value: &mut Value, references: &BTreeMap<String, String>) {
+  match value {
+    Value::Object(object) => {
+      for (key, value) in object.iter_mut() {
+        match (key.as_str(), value) {
+          ("$ref", Value::String(reference)) => {
+            let renamed = references.iter().find_map(|(old, new)| {
+              let rest = reference.strip_prefix(old.as_str())?;
+              (rest.is_empty() || rest.starts_with('/')).then(|| format!("{new}{rest}"))
+            });
+            if let Some(renamed) = renamed {
+              *reference = renamed;
+            }
+          },
+          (_, value) => rename_references(value, references),
+        }
+      }
+    },
+    Value::Array(values) => values.iter_mut().for_each(|value| rename_references(value, references)),
+    _ => {},
+  }
+}
+
+/// Renames the schemes named by the security requirements.
+fn rename_schemes(security: Option<&mut Value>, schemes: &BTreeMap<&str, &str>) {
+  for requirement in security.and_then(Value::as_array_mut).into_iter().flatten() {
+    if let Some(requirement) = requirement.as_object_mut() {
+      *requirement = std::mem::take(requirement)
+        .into_iter()
+        .map(|(name, scopes)| (schemes.get(name.as_str()).map_or(name, |new| new.to_string()), scopes))
+        .collect();
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_wildcard() {
+    assert!(wildcard("*.yaml", "billing.yaml"));
+    assert!(wildcard("*-api.y?ml", "users-api.yaml"));
+    assert!(!wildcard("*-api.y?ml", "users-api.yml"));
+    assert!(wildcard("*", ""));
+    assert!(!wildcard("*.yaml", "billing.json"));
+    assert!(!wildcard("b?", "billing"));
+  }
+
+  #[test]
+  fn test_merge() {
+    let users = json!({
+      "openapi": "3.0.3",
+      "servers": [{ "url": "https://users.example.com" }],
+      "security": [{ "token": [] }],
+      "tags": [{ "name": "users" }],
+      "paths": {
+        "/users/{id}": {
+          "parameters": [{ "name": "id", "in": "path", "required": true }],
+          "get": { "operationId": "getUser", "tags": ["users"], "responses": {
+            "404": { "$ref": "#/components/responses/NotFound" },
+            "default": { "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Error" } } } }
+          } }
+        },
+        "/health": { "get": { "operationId": "usersHealth" } }
+      },
+      "components": {
+        "schemas": { "Error": { "type": "string" } },
+        "securitySchemes": { "token": { "type": "http", "scheme": "bearer" } }
+      }
+    });
+    let billing = json!({
+      "openapi": "3.1.0",
+      "servers": [{ "url": "https://billing.example.com" }],
+      "security": [{ "token": [] }],
+      "paths": {
+        "/invoices": { "get": { "operationId": "listInvoices", "responses": {
+          "default": { "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Error" } } } }
+        } } },
+        "/health": { "get": { "operationId": "billingHealth" }, "head": {} }
+      },
+      "components": {
+        "schemas": { "Error": { "type": "object" } },
+        "securitySchemes": { "token": { "type": "apiKey", "in": "header", "name": "X-Key" } }
+      }
+    });
+    let merged = merge(&[(String::from("users"), users), (String::from("billing"), billing)]);
+    assert_eq!(merged["info"]["title"], "Merged users, billing");
+    assert_eq!(merged["openapi"], "3.0.3");
+    assert_eq!(
+      merged["paths"]["/users/{id}"]["get"],
+      json!({
+        "operationId": "getUser",
+        "tags": ["users"],
+        "responses": {
+          "404": { "$ref": "#/components/responses/NotFound" },
+          "default": { "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Error" } } } }
+        },
+        "x-source": "users",
+        "servers": [{ "url": "https://users.example.com" }],
+        "security": [{ "token": [] }],
+        "parameters": [{ "name": "id", "in": "path", "required": true }]
+      })
+    );
+    assert_eq!(merged["paths"]["/users/{id}"].get("parameters"), None);
+    let invoices = &merged["paths"]["/invoices"]["get"];
+    assert_eq!(invoices["x-source"], "billing");
+    assert_eq!(
+      invoices["responses"]["default"]["content"]["application/json"]["schema"]["$ref"],
+      "#/components/schemas/Error_billing"
+    );
+    assert_eq!(invoices["security"], json!([{ "token_billing": [] }]));
+    assert_eq!(
+      merged["components"]["schemas"],
+      json!({ "Error": { "type": "string" }, "Error_billing": { "type": "object" } })
+    );
+    assert_eq!(merged["paths"]["/health"]["get"]["operationId"], "usersHealth");
+    assert_eq!(merged["paths"]["/health"]["head"]["x-source"], "billing");
+    assert_eq!(merged[CONFLICTS_KEY], json!(["GET /health of billing, already in users"]));
+    assert_eq!(merged["servers"].as_array().unwrap().len(), 2);
+    assert_eq!(merged["tags"], json!([{ "name": "users" }]));
+  }
+}
@@ -0,0 +1,457 @@
+use std::sync::Arc;
+
+use color_eyre::eyre::{bail, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use tokio::{
+  io::{AsyncReadExt, AsyncWriteExt},
+  net::{TcpListener, TcpStream},
+  sync::mpsc::UnboundedSender,
+};
+
+use crate::{action::Action, workspace::OperationKey, xml};
+
+pub const METHODS: [&str; 8] = ["get", "put", "post", "delete", "options", "head", "patch", "trace"];
+/// Nesting after which generated examples stop following schemas, which keeps recursive schemas finite.
+const MAX_DEPTH: usize = 8;
+const MAX_HEAD_BYTES: usize = 64 * 1024;
+
+/// Canned response of an operation.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Route {
+  pub operation: OperationKey,
+  pub status: u16,
+  pub content_type: Option<String>,
+  pub body: String,
+}
+
+impl Route {
+  /// Whether the request path fits the operation path, `{name}` segments matching any value.
+  fn matches(&self, method: &str, path: &str) -> bool {
+    let template: Vec<&str> = self.operation.path.trim_matches('/').split('/').collect();
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    self.operation.method.eq_ignore_ascii_case(method)
+      && template.len() == segments.len()
+      && template.iter().zip(segments).all(|(expected, segment)| {
+        expected == &segment || (expected.starts_with('{') && expected.ends_with('}') && !segment.is_empty())
+      })
+  }
+}
+
+/// Named example of a media type object, from its `examples`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NamedExample {
+  pub name: String,
+  pub summary: Option<String>,
+  pub description: Option<String>,
+  /// Value of the example, its `externalValue` URL as a string when it has no value.
+  pub value: Value,
+}
+
+impl NamedExample {
+  /// Value as it is entered in a body editor: strings as is, other values as pretty JSON.
+  pub fn text(&self) -> String {
+    match &self.value {
+      Value::String(text) => text.clone(),
+      value => serde_json::to_string_pretty(value).unwrap_or_default(),
+    }
+  }
+}
+
+/// Request received by the mock server, along with the status it was answered with.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MockHit {
+  /// Local time the request was received at, e.g. `14:03:27`.
+  pub received_at: String,
+  pub method: String,
+  /// Path along with the query string.
+  pub target: String,
+  pub headers: Vec<(String, String)>,
+  pub body: String,
+  pub status: u16,
+  /// Operation that answered, `None` when no operation matches.
+  pub operation: Option<OperationKey>,
+}
+
+/// Responses of every operation of the spec, built from the examples of their success response, or generated from
+/// its schema.
+pub fn routes(document: &Value) -> Vec<Route> {
+  let Some(paths) = document.get("paths").and_then(Value::as_object) else {
+    return vec![];
+  };
+  let mut routes = vec![];
+  for (path, item) in paths {
+    for method in METHODS {
+      let Some(operation) = item.get(method) else {
+        continue;
+      };
+      let (status, response) = success_response(operation, document);
+      let media =
+        response.and_then(|response| response.get("content")).and_then(Value::as_object).and_then(|content| {
+          content.iter().find(|(media_type, _)| media_type.contains("json")).or(content.iter().next())
+        });
+      let (content_type, body) = match media {
+        Some((media_type, media)) => {
+          let body = match media_example(media, document) {
+            Value::Null => String::default(),
+            Value::String(text) if !media_type.contains("json") => text,
+            _ if xml::is_xml(media_type) && media.get("schema").is_some() => xml::example(&media["schema"], document),
+            example => serde_json::to_string_pretty(&example).unwrap_or_default(),
+          };
+          (Some(media_type.clone()), body)
+        },
+        None => (None, String::default()),
+      };
+      routes.push(Route { operation: OperationKey::new(method, path), status, content_type, body });
+    }
+  }
+  // Static segments win over parameters, e.g. `/pet/findByStatus` over `/pet/{petId}`.
+  routes.sort_by_key(|route| route.operation.path.matches('{').count());
+  routes
+}
+
+pub fn route<'a>(routes: &'a [Route], method: &str, target: &str) -> Option<&'a Route> {
+  let path = target.split('?').next().unwrap_or_default();
+  routes.iter().find(|route| route.matches(method, path))
+}
+
+/// The lowest 2xx response, or the default one.
+fn success_response<'a>(operation: &'a Value, document: &'a Value) -> (u16, Option<&'a Value>) {
+  let Some(responses) = operation.get("responses").and_then(Value::as_object) else {
+    return (200, None);
+  };
+  let success = responses
+    .iter()
+    .filter_map(|(status, response)| status.parse::<u16>().ok().map(|status| (status, response)))
+    .filter(|(status, _)| (200..300).contains(status))
+    .min_by_key(|(status, _)| *status);
+  match success {
+    Some((status, response)) => (status, Some(resolve(response, document))),
+    None => (200, responses.get("default").map(|response| resolve(response, document))),
+  }
+}
+
+/// Example of the media type object, its own `example` or first of its `examples` when it has one.
+pub fn media_example(media: &Value, document: &Value) -> Value {
+  if let Some(example) = media.get("example") {
+    return example.clone();
+  }
+  let first_example = media.get("examples").and_then(Value::as_object).and_then(|examples| examples.values().next());
+  if let Some(example) = first_example.and_then(|example| resolve(example, document).get("value")) {
+    return example.clone();
+  }
+  media.get("schema").map_or(Value::Null, |schema| example(schema, document, 0))
+}
+
+/// Named `examples` of the media type object, references to `components/examples` followed.
+pub fn media_examples(media: &Value, document: &Value) -> Vec<NamedExample> {
+  let examples = media.get("examples").and_then(Value::as_object).into_iter().flatten();
+  examples
+    .map(|(name, example)| {
+      let example = resolve(example, document);
+      let text = |key: &str| example.get(key).and_then(Value::as_str).map(String::from);
+      NamedExample {
+        name: name.clone(),
+        summary: text("summary"),
+        description: text("description"),
+        value: example.get("value").cloned().or_else(|| text("externalValue").map(Value::String)).unwrap_or_default(),
+      }
+    })
+    .collect()
+}
+
+/// Examples previewing a response of the media type: its named `examples`, then its own `example`, then one generated
+/// from its schema, so that responses declaring no example still show what they look like.
+pub fn preview_examples(media: &Value, document: &Value) -> Vec<NamedExample> {
+  let mut examples = media_examples(media, document);
+  if let Some(example) = media.get("example") {
+    examples.push(NamedExample { name: String::from("example"), value: example.clone(), ..NamedExample::default() });
+  }
+  if let Some(schema) = media.get("schema") {
+    examples.push(NamedExample {
+      name: String::from("generated"),
+      summary: Some(String::from("from the schema")),
+      value: example(schema, document, 0),
+      ..NamedExample::default()
+    });
+  }
+  examples
+}
+
+/// Follows `$ref`s to components of the document.
+pub fn resolve<'a>(mut value: &'a Value, document: &'a Value) -> &'a Value {
+  for _ in 0..MAX_DEPTH {
+    if value.get("$ref").is_none() {
+      break;
+    }
+    value = target(value, document).unwrap_or(&Value::Null);
+  }
+  value
+}
+
+/// Value the local `$ref` of the value points to, one reference deep, `None` when it has none or it dangles.
+pub fn target<'a>(value: &Value, document: &'a Value) -> Option<&'a Value> {
+  let pointer = value.get("$ref").and_then(Value::as_str)?.strip_prefix('#')?;
+  document.pointer(pointer)
+}
+
+/// Type of the schema, other than `null`, guessed from its keywords when it has none.
+pub fn kind(schema: &Value) -> Option<&str> {
+  match schema.get("type") {
+    Some(Value::Array(kinds)) => kinds.iter().filter_map(Value::as_str).find(|kind| *kind != "null"),
+    Some(kind) => kind.as_str(),
+    None if schema.get("properties").is_some() => Some("object"),
+    None if schema.get("items").is_some() => Some("array"),
+    None => None,
+  }
+}
+
+/// Example value of the schema, its own `example` when it has one.
+pub fn example(schema: &Value, document: &Value, depth: usize) -> Value {
+  let schema = resolve(schema, document);
+  if depth > MAX_DEPTH {
+    return Value::Null;
+  }
+  for key in ["example", "default"] {
+    if let Some(example) = schema.get(key) {
+      return example.clone();
+    }
+  }
+  if let Some(first) = schema.get("enum").and_then(|values| values.get(0)) {
+    return first.clone();
+  }
+  if let Some(schemas) = schema.get("allOf").and_then(Value::as_array) {
+    let mut merged = Map::new();
+    for schema in schemas {
+      if let Value::Object(properties) = example(schema, document, depth + 1) {
+        merged.extend(properties);
+      }
+    }
+    return Value::Object(merged);
+  }
+  for key in ["oneOf", "anyOf"] {
+    if let Some(schema) = schema.get(key).and_then(|schemas| schemas.get(0)) {
+      return example(schema, document, depth + 1);
+    }
+  }
+  match kind(schema) {
+    Some("object") => {
+      let properties = schema.get("properties").and_then(Value::as_object);
+      Value::Object(
+        properties
+          .into_iter()
+          .flatten()
+          .map(|(name, property)| (name.clone(), example(property, document, depth + 1)))
+          .collect(),
+      )
+    },
+    Some("array") => {
+      json!([schema.get("items").map_or(Value::Null, |items| example(items, document, depth + 1))])
+    },
+    Some("string") => {
+      let text = match schema.get("format").and_then(Value::as_str) {
+        Some("date-time") => "2024-01-01T00:00:00Z",
+        Some("date") => "2024-01-01",
+        Some("uuid") => "3fa85f64-5717-4562-b3fc-2c963f66afa6",
+        Some("email") => "user@example.com",
+        Some("uri") | Some("url") => "https://example.com",
+        _ => "string",
+      };
+      json!(text)
+    },
+    Some("integer") => json!(schema.get("minimum").and_then(Value::as_i64).unwrap_or(0)),
+    Some("number") => json!(schema.get("minimum").and_then(Value::as_f64).unwrap_or(0.0)),
+    Some("boolean") => json!(true),
+    _ => Value::Null,
+  }
+}
+
+/// Answers the connections of the listener until the app exits, reporting every request as it is received.
+pub async fn serve(listener: TcpListener, routes: Arc<Vec<Route>>, action_tx: UnboundedSender<Action>) {
+  loop {
+    match listener.accept().await {
+      Ok((stream, _)) => {
+        let routes = routes.clone();
+        let action_tx = action_tx.clone();
+        tokio::spawn(async move {
+          if let Err(error) = answer(stream, &routes, &action_tx).await {
+            log::error!("Failed to answer a mock request: {error:?}");
+          }
+        });
+      },
+      Err(error) => log::error!("Failed to accept a mock connection: {error:?}"),
+    }
+  }
+}
+
+/// Reads a single HTTP/1.1 request from the connection and answers it, closing the connection.
+async fn answer(mut stream: TcpStream, routes: &[Route], action_tx: &UnboundedSender<Action>) -> Result<()> {
+  let mut buffer = vec![];
+  let mut chunk = [0; 4096];
+  let head_end = loop {
+    if let Some(end) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+      break end;
+    }
+    if buffer.len() > MAX_HEAD_BYTES {
+      bail!("request head is larger than {MAX_HEAD_BYTES} bytes");
+    }
+    let read = stream.read(&mut chunk).await?;
+    if read == 0 {
+      return Ok(());
+    }
+    buffer.extend_from_slice(&chunk[..read]);
+  };
+  let head = String::from_utf8_lossy(&buffer[..head_end]).to_string();
+  let mut lines = head.split("\r\n");
+  let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+  let method = request_line.next().unwrap_or_default().to_uppercase();
+  let target = request_line.next().unwrap_or("/").to_string();
+  let headers: Vec<(String, String)> = lines
+    .filter_map(|line| line.split_once(':'))
+    .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+    .collect();
+  let length = headers
+    .iter()
+    .find(|(name, _)| name.eq_ignore_ascii_case("Content-Length"))
+    .and_then(|(_, value)| value.parse::<usize>().ok())
+    .unwrap_or_default();
+  let mut body = buffer[head_end + 4..].to_vec();
+  while body.len() < length {
+    let read = stream.read(&mut chunk).await?;
+    if read == 0 {
+      break;
+    }
+    body.extend_from_slice(&chunk[..read]);
+  }
+  body.truncate(length);
+
+  let route = route(routes, &method, &target);
+  let (status, content_type, response_body) = match route {
+    Some(route) => (route.status, route.content_type.clone(), route.body.clone()),
+    // Lets browsers send cross-origin requests.
+    None if method == "OPTIONS" => (204, None, String::default()),
+    None => {
+      let message = json!({ "message": format!("No operation matches {method} {target}") });
+      (404, Some(String::from("application/json")), message.to_string())
+    },
+  };
+  let reason = reqwest::StatusCode::from_u16(status).ok().and_then(|status| status.canonical_reason());
+  let mut response = format!(
+    "HTTP/1.1 {status} {}\r\nContent-Length: {}\r\nConnection: close\r\nAccess-Control-Allow-Origin: *\r\n\
+     Access-Control-Allow-Methods: *\r\nAccess-Control-Allow-Headers: *\r\n",
+    reason.unwrap_or_default(),
+    response_body.len()
+  );
+  if let Some(content_type) = content_type {
+    response.push_str(&format!("Content-Type: {content_type}\r\n"));
+  }
+  response.push_str("\r\n");
+  response.push_str(&response_body);
+  stream.write_all(response.as_bytes()).await?;
+  stream.shutdown().await?;
+
+  let hit = MockHit {
+    received_at: chrono::Local::now().format("%H:%M:%S").to_string(),
+    method,
+    target,
+    headers,
+    body: String::from_utf8_lossy(&body).to_string(),
+    status,
+    operation: route.map(|route| route.operation.clone()),
+  };
+  action_tx.send(Action::MockRequestReceived(Box::new(hit)))?;
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_media_examples() {
+    let document =
+      json!({ "components": { "examples": { "cat": { "summary": "A cat", "value": { "name": "Tom" } } } } });
+    let media = json!({ "examples": {
+      "cat": { "$ref": "#/components/examples/cat" },
+      "remote": { "description": "Hosted", "externalValue": "https://example.com/dog.json" }
+    } });
+    let examples = media_examples(&media, &document);
+    assert_eq!(examples.iter().map(|example| example.name.as_str()).collect::<Vec<_>>(), vec!["cat", "remote"]);
+    assert_eq!(examples[0].summary.as_deref(), Some("A cat"));
+    assert_eq!(examples[0].text(), "{\n  \"name\": \"Tom\"\n}");
+    assert_eq!(examples[1].description.as_deref(), Some("Hosted"));
+    assert_eq!(examples[1].text(), "https://example.com/dog.json");
+  }
+
+  #[test]
+  fn test_preview_examples() {
+    let document = json!({ "components": { "schemas": { "Pet": {
+      "type": "object",
+      "properties": { "name": { "type": "string", "example": "Tom" } }
+    } } } });
+    let media = json!({ "example": { "name": "Felix" }, "schema": { "$ref": "#/components/schemas/Pet" } });
+    let examples = preview_examples(&media, &document);
+    assert_eq!(examples.iter().map(|example| example.name.as_str()).collect::<Vec<_>>(), vec!["example", "generated"]);
+    assert_eq!(examples[0].value, json!({ "name": "Felix" }));
+    assert_eq!(examples[1].summary.as_deref(), Some("from the schema"));
+    assert_eq!(examples[1].value, json!({ "name": "Tom" }));
+    assert!(preview_examples(&json!({}), &document).is_empty());
+  }
+
+  #[test]
+  fn test_routes() {
+    let document = json!({
+      "paths": {
+        "/pet/{petId}": {
+          "get": {
+            "responses": {
+              "404": { "description": "Not found" },
+              "200": { "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Pet" } } } }
+            }
+          }
+        },
+        "/pet/findByStatus": {
+          "get": {
+            "responses": {
+              "default": {
+                "content": { "application/json": { "schema": { "type": "array", "items": { "type": "integer" } } } }
+              }
+            }
+          }
+        },
+        "/pet": { "post": { "responses": { "201": { "description": "Created" } } } }
+      },
+      "components": {
+        "schemas": {
+          "Pet": {
+            "properties": {
+              "id": { "type": "integer", "example": 7 },
+              "name": { "type": "string" },
+              "status": { "type": "string", "enum": ["available", "sold"] },
+              "parent": { "$ref": "#/components/schemas/Pet" }
+            }
+          }
+        }
+      }
+    });
+    let routes = routes(&document);
+    let pet = route(&routes, "GET", "/pet/7?fields=name").unwrap();
+    assert_eq!(pet.status, 200);
+    assert_eq!(pet.content_type.as_deref(), Some("application/json"));
+    let body: Value = serde_json::from_str(&pet.body).unwrap();
+    assert_eq!(body["id"], 7);
+    assert_eq!(body["name"], "string");
+    assert_eq!(body["status"], "available");
+    assert_eq!(body["parent"]["parent"]["id"], 7);
+
+    let find = route(&routes, "get", "/pet/findByStatus").unwrap();
+    assert_eq!(find.operation, OperationKey::new("get", "/pet/findByStatus"));
+    assert_eq!(serde_json::from_str::<Value>(&find.body).unwrap(), json!([0]));
+
+    assert_eq!(route(&routes, "POST", "/pet").map(|route| (route.status, route.body.as_str())), Some((201, "")));
+    assert_eq!(route(&routes, "DELETE", "/pet"), None);
+    assert_eq!(route(&routes, "GET", "/pet/7/photos"), None);
+  }
+}
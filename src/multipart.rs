@@ -0,0 +1,202 @@
+use std::{
+  path::{Path, PathBuf},
+  time::{SystemTime, UNIX_EPOCH},
+};
+
+use color_eyre::eyre::{Result, WrapErr};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::request::encode_component;
+
+const MEDIA_TYPE: &str = "multipart/form-data";
+const URLENCODED_MEDIA_TYPE: &str = "application/x-www-form-urlencoded";
+
+/// Part of a `multipart/form-data` body, a text field or a file read when the request is sent, or field of an
+/// `application/x-www-form-urlencoded` one.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FormPart {
+  pub name: String,
+  /// Text of the field, or path of the file.
+  pub value: String,
+  #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+  pub file: bool,
+}
+
+pub fn is_multipart(media_type: &str) -> bool {
+  media_type.starts_with(MEDIA_TYPE)
+}
+
+pub fn is_urlencoded(media_type: &str) -> bool {
+  media_type.starts_with(URLENCODED_MEDIA_TYPE)
+}
+
+/// Whether bodies of the media type are edited as a form, field by field.
+pub fn is_form(media_type: &str) -> bool {
+  is_multipart(media_type) || is_urlencoded(media_type)
+}
+
+/// Fields of the properties of the schema of a form body, binary strings being files in multipart ones.
+pub fn parts(media_type: &str, schema: &Value) -> Vec<FormPart> {
+  let properties = schema.get("properties").and_then(Value::as_object).into_iter().flatten();
+  properties
+    .map(|(name, property)| {
+      let property = property.get("items").unwrap_or(property);
+      let format = property.get("format").and_then(Value::as_str);
+      let binary = matches!(format, Some("binary") | Some("base64")) || property.get("contentMediaType").is_some();
+      let file = binary && is_multipart(media_type);
+      FormPart { name: name.clone(), value: String::default(), file }
+    })
+    .collect()
+}
+
+/// Boundary between the parts, unlikely to appear in them.
+pub fn boundary() -> String {
+  let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_nanos());
+  format!("openapi-tui-{nanos:x}")
+}
+
+/// `Content-Type` of a body encoded with the boundary.
+pub fn content_type(boundary: &str) -> String {
+  format!("{MEDIA_TYPE}; boundary={boundary}")
+}
+
+/// Body of the parts, files read from disk. Parts left empty are not sent.
+pub fn encode(parts: &[FormPart], boundary: &str) -> Result<Vec<u8>> {
+  let mut body = vec![];
+  for part in parts.iter().filter(|part| !part.value.is_empty()) {
+    body.extend(format!("--{boundary}\r\n").as_bytes());
+    match part.file {
+      true => {
+        let path = expand_home(&part.value);
+        let file_name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+        let content = std::fs::read(&path).wrap_err_with(|| format!("Could not read `{}`", part.value))?;
+        body.extend(
+          format!(
+            "Content-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\nContent-Type: \
+             application/octet-stream\r\n\r\n",
+            quote_param(&part.name),
+            quote_param(&file_name)
+          )
+          .as_bytes(),
+        );
+        body.extend(content);
+      },
+      false => {
+        body.extend(format!("Content-Disposition: form-data; name=\"{}\"\r\n\r\n", quote_param(&part.name)).as_bytes());
+        body.extend(part.value.as_bytes());
+      },
+    }
+    body.extend(b"\r\n");
+  }
+  body.extend(format!("--{boundary}--\r\n").as_bytes());
+  Ok(body)
+}
+
+/// Body of the fields of an urlencoded form. Fields left empty are not sent.
+pub fn urlencode(parts: &[FormPart]) -> String {
+  let fields = parts.iter().filter(|part| !part.value.is_empty());
+  fields
+    .map(|part| format!("{}={}", encode_component(&part.name), encode_component(&part.value)))
+    .collect::<Vec<_>>()
+    .join("&")
+}
+
+/// The name or file name percent-encoded for a quoted `Content-Disposition` parameter, as browsers do, markup
+/// escaping has no meaning there.
+fn quote_param(text: &str) -> String {
+  text.replace('"', "%22").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+fn expand_home(path: &str) -> PathBuf {
+  match (path.strip_prefix("~/"), std::env::var_os("HOME")) {
+    (Some(rest), Some(home)) => Path::new(&home).join(rest),
+    _ => PathBuf::from(path),
+  }
+}
+
+/// Completes the path being typed to the longest prefix shared by the entries it matches, directories ending with
+/// a separator.
+pub fn complete_path(input: &str) -> String {
+  let (directory, prefix) = match input.rfind('/') {
+    Some(index) => (&input[..=index], &input[index + 1..]),
+    None => ("", input),
+  };
+  let search = match directory {
+    "" => PathBuf::from("."),
+    directory => expand_home(directory),
+  };
+  let Ok(entries) = std::fs::read_dir(search) else {
+    return input.to_string();
+  };
+  let mut matches: Vec<String> = entries
+    .filter_map(|entry| entry.ok())
+    .filter_map(|entry| {
+      let name = entry.file_name().to_string_lossy().to_string();
+      let directory = entry.file_type().is_ok_and(|kind| kind.is_dir());
+      name.starts_with(prefix).then(|| if directory { format!("{name}/") } else { name })
+    })
+    .collect();
+  matches.sort();
+  let Some(first) = matches.first() else {
+    return input.to_string();
+  };
+  let common = matches.iter().fold(first.as_str(), |common, name| {
+    let length = common.chars().zip(name.chars()).take_while(|(a, b)| a == b).map(|(a, _)| a.len_utf8()).sum();
+    &common[..length]
+  });
+  format!("{directory}{common}")
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+  use serde_json::json;
+
+  use super::*;
+
+  #[test]
+  fn test_multipart() {
+    let schema = json!({ "properties": {
+      "file": { "type": "string", "format": "binary" },
+      "name": { "type": "string" },
+      "photos": { "type": "array", "items": { "type": "string", "format": "binary" } }
+    } });
+    let files = |media_type: &str| {
+      let parts = parts(media_type, &schema);
+      parts.into_iter().map(|part| (part.name, part.file)).collect::<Vec<_>>()
+    };
+    assert_eq!(files(MEDIA_TYPE), vec![
+      (String::from("file"), true),
+      (String::from("name"), false),
+      (String::from("photos"), true)
+    ]);
+    assert!(files(URLENCODED_MEDIA_TYPE).iter().all(|(_, file)| !file));
+    let fields = vec![
+      FormPart { name: String::from("name"), value: String::from("Rex & co"), file: false },
+      FormPart { name: String::from("tag"), value: String::default(), file: false },
+      FormPart { name: String::from("status"), value: String::from("available"), file: false },
+    ];
+    assert_eq!(urlencode(&fields), "name=Rex%20%26%20co&status=available");
+
+    let directory = std::env::temp_dir().join(format!("openapi-tui-multipart-{}", std::process::id()));
+    std::fs::create_dir_all(directory.join("photos")).unwrap();
+    std::fs::write(directory.join("photo.png"), b"PNG").unwrap();
+    let input = format!("{}/pho", directory.display());
+    assert_eq!(complete_path(&input), format!("{}/photo", directory.display()));
+    assert_eq!(complete_path(&format!("{input}tos")), format!("{}/photos/", directory.display()));
+
+    let parts = vec![
+      FormPart { name: String::from("name"), value: String::from("Rex"), file: false },
+      FormPart { name: String::from("file"), value: format!("{}/photo.png", directory.display()), file: true },
+      FormPart { name: String::from("photos"), value: String::default(), file: true },
+    ];
+    let body = String::from_utf8(encode(&parts, "XYZ").unwrap()).unwrap();
+    std::fs::remove_dir_all(&directory).unwrap();
+    assert_eq!(
+      body,
+      "--XYZ\r\nContent-Disposition: form-data; name=\"name\"\r\n\r\nRex\r\n--XYZ\r\nContent-Disposition: form-data; \
+       name=\"file\"; filename=\"photo.png\"\r\nContent-Type: application/octet-stream\r\n\r\nPNG\r\n--XYZ--\r\n"
+    );
+  }
+}
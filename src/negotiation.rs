@@ -0,0 +1,78 @@
+use serde_json::Value;
+
+use crate::mock;
+
+/// Media types an operation can send and be answered with.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContentTypes {
+  /// Media types of the request body, the first one sent unless another is chosen.
+  pub request: Vec<String>,
+  /// Media types of the responses, of every status, that can be asked for with `Accept`.
+  pub response: Vec<String>,
+}
+
+impl ContentTypes {
+  pub fn of(operation: &Value, document: &Value) -> Self {
+    let keys = |value: &Value| -> Vec<String> {
+      mock::resolve(value, document)
+        .get("content")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+        .map(|(key, _)| key.clone())
+        .collect()
+    };
+    let request = operation.get("requestBody").map(keys).unwrap_or_default();
+    let mut response: Vec<String> = vec![];
+    for media_type in
+      operation.get("responses").and_then(Value::as_object).into_iter().flatten().flat_map(|(_, value)| keys(value))
+    {
+      if !response.contains(&media_type) {
+        response.push(media_type);
+      }
+    }
+    Self { request, response }
+  }
+}
+
+/// Media type after the current one, the first one following none. When `optional`, none follows the last one, so
+/// that the choice can be left to the defaults again.
+pub fn next(media_types: &[String], current: Option<&str>, optional: bool) -> Option<String> {
+  let index = current.and_then(|current| media_types.iter().position(|media_type| media_type == current));
+  match index {
+    Some(index) if index + 1 < media_types.len() => Some(media_types[index + 1].clone()),
+    Some(_) if optional => None,
+    _ => media_types.first().cloned(),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+  use serde_json::json;
+
+  use super::*;
+
+  #[test]
+  fn test_content_types() {
+    let document =
+      json!({ "components": { "responses": { "Error": { "content": { "application/problem+json": {} } } } } });
+    let operation = json!({
+      "requestBody": { "content": { "application/json": {}, "application/xml": {} } },
+      "responses": {
+        "200": { "content": { "application/json": {}, "text/csv": {} } },
+        "400": { "$ref": "#/components/responses/Error" },
+        "default": { "content": { "application/json": {} } }
+      }
+    });
+    let content_types = ContentTypes::of(&operation, &document);
+    assert_eq!(content_types.request, vec!["application/json", "application/xml"]);
+    assert_eq!(content_types.response, vec!["application/json", "text/csv", "application/problem+json"]);
+
+    let request = &content_types.request;
+    assert_eq!(next(request, None, false).as_deref(), Some("application/json"));
+    assert_eq!(next(request, Some("application/json"), false).as_deref(), Some("application/xml"));
+    assert_eq!(next(request, Some("application/xml"), false).as_deref(), Some("application/json"));
+    assert_eq!(next(request, Some("application/xml"), true), None);
+  }
+}
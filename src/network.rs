@@ -0,0 +1,138 @@
+use std::{path::PathBuf, time::Duration};
+
+use color_eyre::eyre::{Result, WrapErr};
+use serde::{Deserialize, Serialize};
+
+use crate::hosts::{self, HostOverride};
+
+/// Network settings of the requests sent with an environment, e.g. a proxy and the certificate authority of a
+/// staging stack behind a VPN, while production is reached directly.
+///
+/// Paths are relative to the working directory.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Network {
+  /// Proxy every request goes through, e.g. `http://proxy.internal:3128`.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub proxy: Option<String>,
+  /// PEM file of a certificate authority trusted on top of the built-in roots.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub ca_cert: Option<PathBuf>,
+  /// PEM file holding the client certificate along with its private key, for mutual TLS.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub client_cert: Option<PathBuf>,
+  /// Seconds a request may take before it fails, without limit when unset.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub timeout_secs: Option<u64>,
+  /// Addresses requests to a host and port are sent to, as `host:port:address`, before those of `--resolve` and the
+  /// config file.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub resolve: Vec<String>,
+}
+
+impl Network {
+  /// The override of the host and port of the url among the `resolve` entries.
+  pub fn host_override(&self, url: &str) -> Result<Option<HostOverride>> {
+    let overrides = self.resolve.iter().map(|entry| entry.parse()).collect::<Result<Vec<HostOverride>>>()?;
+    Ok(hosts::matching(&overrides, url))
+  }
+
+  /// Sets the proxy, the certificates and the timeout on the client, reading the certificate files.
+  pub fn configure(&self, mut client: reqwest::ClientBuilder) -> Result<reqwest::ClientBuilder> {
+    if let Some(proxy) = &self.proxy {
+      client = client.proxy(reqwest::Proxy::all(proxy).wrap_err_with(|| format!("Invalid proxy `{proxy}`"))?);
+    }
+    if let Some(path) = &self.ca_cert {
+      let pem =
+        std::fs::read(path).wrap_err_with(|| format!("Failed to read the CA certificate {}", path.display()))?;
+      client = client.add_root_certificate(reqwest::Certificate::from_pem(&pem)?);
+    }
+    if let Some(path) = &self.client_cert {
+      let pem =
+        std::fs::read(path).wrap_err_with(|| format!("Failed to read the client certificate {}", path.display()))?;
+      client = client.identity(reqwest::Identity::from_pem(&pem)?);
+    }
+    if let Some(timeout) = self.timeout_secs {
+      client = client.timeout(Duration::from_secs(timeout));
+    }
+    Ok(client)
+  }
+
+  /// The settings that are set, by name, e.g. `("timeout", "30 s")`.
+  pub fn settings(&self) -> Vec<(&'static str, String)> {
+    let mut settings = vec![];
+    if let Some(proxy) = &self.proxy {
+      settings.push(("proxy", proxy.clone()));
+    }
+    if let Some(path) = &self.ca_cert {
+      settings.push(("CA certificate", path.display().to_string()));
+    }
+    if let Some(path) = &self.client_cert {
+      settings.push(("client certificate", path.display().to_string()));
+    }
+    if let Some(timeout) = self.timeout_secs {
+      settings.push(("timeout", format!("{timeout} s")));
+    }
+    settings.extend(self.resolve.iter().map(|entry| ("resolve", entry.clone())));
+    settings
+  }
+
+  /// The settings of the other network, where it sets them, over these.
+  pub fn merge(&mut self, other: &Network) {
+    if other.proxy.is_some() {
+      self.proxy.clone_from(&other.proxy);
+    }
+    if other.ca_cert.is_some() {
+      self.ca_cert.clone_from(&other.ca_cert);
+    }
+    if other.client_cert.is_some() {
+      self.client_cert.clone_from(&other.client_cert);
+    }
+    if other.timeout_secs.is_some() {
+      self.timeout_secs = other.timeout_secs;
+    }
+    let mut resolve = other.resolve.clone();
+    resolve.append(&mut self.resolve);
+    self.resolve = resolve;
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_network() {
+    let mut network: Network = serde_json::from_str(
+      r#"{ "proxy": "http://proxy.internal:3128", "timeout_secs": 30, "resolve": ["api.staging.example.com:443:10.0.0.7"] }"#,
+    )
+    .unwrap();
+    assert_eq!(
+      network.host_override("https://api.staging.example.com/v1/pets").unwrap().map(|found| found.socket_addr()),
+      Some("10.0.0.7:443".parse().unwrap())
+    );
+    assert_eq!(network.host_override("https://api.example.com/v1/pets").unwrap(), None);
+    assert!(network.configure(reqwest::Client::builder()).is_ok());
+
+    network.merge(&Network {
+      timeout_secs: Some(5),
+      resolve: vec![String::from("api.staging.example.com:443:127.0.0.1")],
+      ..Network::default()
+    });
+    assert_eq!(network.settings(), vec![
+      ("proxy", String::from("http://proxy.internal:3128")),
+      ("timeout", String::from("5 s")),
+      ("resolve", String::from("api.staging.example.com:443:127.0.0.1")),
+      ("resolve", String::from("api.staging.example.com:443:10.0.0.7")),
+    ]);
+    assert_eq!(
+      network.host_override("https://api.staging.example.com").unwrap().map(|found| found.socket_addr()),
+      Some("127.0.0.1:443".parse().unwrap())
+    );
+
+    let missing = Network { ca_cert: Some(PathBuf::from("missing-ca.pem")), ..Network::default() };
+    let error = missing.configure(reqwest::Client::builder()).err().unwrap();
+    assert_eq!(error.to_string(), "Failed to read the CA certificate missing-ca.pem");
+  }
+}
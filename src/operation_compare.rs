@@ -0,0 +1,224 @@
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+use crate::{
+  mock::{kind, resolve},
+  parameter, security,
+  workspace::OperationKey,
+};
+
+/// Nesting of the schema properties compared, which keeps recursive schemas finite.
+const MAX_DEPTH: usize = 8;
+
+/// Sections of the comparison, in the order they are listed.
+pub const SECTIONS: [&str; 4] = ["Parameters", "Request body", "Responses", "Security"];
+const PARAMETERS: usize = 0;
+const REQUEST_BODY: usize = 1;
+const RESPONSES: usize = 2;
+const SECURITY: usize = 3;
+
+/// What two operations are compared on, e.g. a parameter or a property of a response, with how each one has it,
+/// `None` when it lacks it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+  /// Index in [`SECTIONS`].
+  pub section: usize,
+  pub name: String,
+  pub left: Option<String>,
+  pub right: Option<String>,
+}
+
+impl Row {
+  pub fn differs(&self) -> bool {
+    self.left != self.right
+  }
+}
+
+/// The type of the schema with its format and enum, e.g. `string (email)` or `string: active|banned`.
+fn schema_type(schema: &Value) -> String {
+  let mut text = kind(schema).unwrap_or("any").to_string();
+  if let Some(format) = schema.get("format").and_then(Value::as_str) {
+    text.push_str(&format!(" ({format})"));
+  }
+  if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+    let values: Vec<String> =
+      values.iter().map(|value| value.as_str().map_or(value.to_string(), String::from)).collect();
+    text.push_str(&format!(": {}", values.join("|")));
+  }
+  if schema.get("nullable").and_then(Value::as_bool) == Some(true) {
+    text.push_str(", nullable");
+  }
+  text
+}
+
+/// Properties of the schema, and of the members of its `allOf`, with the names of the required ones.
+fn properties<'a>(schema: &'a Value, document: &'a Value) -> (Vec<(&'a String, &'a Value)>, Vec<&'a str>) {
+  let mut properties = vec![];
+  let mut required = vec![];
+  let members = schema.get("allOf").and_then(Value::as_array).into_iter().flatten();
+  for schema in std::iter::once(schema).chain(members.map(|member| resolve(member, document))) {
+    properties.extend(schema.get("properties").and_then(Value::as_object).into_iter().flatten());
+    required.extend(schema.get("required").and_then(Value::as_array).into_iter().flatten().filter_map(Value::as_str));
+  }
+  (properties, required)
+}
+
+/// The fields of the schema by JSONPath, e.g. `$.tags[].name`, each with its type and whether it is required.
+fn schema_fields(schema: &Value, document: &Value, at: &str, depth: usize, fields: &mut BTreeMap<String, String>) {
+  let schema = resolve(schema, document);
+  if depth > MAX_DEPTH {
+    return;
+  }
+  let (properties, required) = properties(schema, document);
+  for (name, property) in properties {
+    let property = resolve(property, document);
+    let property_at = format!("{at}.{name}");
+    let mut text = schema_type(property);
+    if required.contains(&name.as_str()) {
+      text.push_str(", required");
+    }
+    fields.insert(property_at.clone(), text);
+    schema_fields(property, document, &property_at, depth + 1, fields);
+  }
+  if let Some(items) = schema.get("items") {
+    schema_fields(items, document, &format!("{at}[]"), depth + 1, fields);
+  }
+}
+
+/// Media types of the content along with the fields of the schema of its JSON media type, or else of its first one.
+fn content_facts(value: &Value, document: &Value, prefix: &str, facts: &mut BTreeMap<String, String>) -> String {
+  let Some(content) = value.get("content").and_then(Value::as_object).filter(|content| !content.is_empty()) else {
+    return String::from("no content");
+  };
+  let media = content.iter().find(|(media_type, _)| media_type.contains("json")).or(content.iter().next());
+  if let Some(schema) = media.and_then(|(_, media)| media.get("schema")) {
+    let mut fields = BTreeMap::new();
+    schema_fields(schema, document, "$", 0, &mut fields);
+    if fields.is_empty() {
+      fields.insert(String::from("$"), schema_type(resolve(schema, document)));
+    }
+    facts.extend(fields.into_iter().map(|(at, text)| (format!("{prefix}{at}"), text)));
+  }
+  content.keys().cloned().collect::<Vec<_>>().join(", ")
+}
+
+/// What the operation has of each section, by section and name.
+fn facts(document: &Value, operation: &OperationKey) -> BTreeMap<(usize, String), String> {
+  let mut facts = BTreeMap::new();
+  let (path, method) = (operation.path.as_str(), operation.method.as_str());
+  for ((location, name), parameter) in parameter::parameters(document, path, method) {
+    let schema = parameter.get("schema").map_or(&Value::Null, |schema| resolve(schema, document));
+    let mut text = schema_type(schema);
+    if parameter.get("required").and_then(Value::as_bool) == Some(true) {
+      text.push_str(", required");
+    }
+    if parameter.get("deprecated").and_then(Value::as_bool) == Some(true) {
+      text.push_str(", deprecated");
+    }
+    facts.insert((PARAMETERS, format!("{location} {name}")), text);
+  }
+  let value = document.get("paths").and_then(|paths| paths.get(path)).and_then(|item| item.get(method.to_lowercase()));
+  let value = value.unwrap_or(&Value::Null);
+
+  if let Some(body) = value.get("requestBody").map(|body| resolve(body, document)) {
+    let mut fields = BTreeMap::new();
+    let mut text = content_facts(body, document, "body ", &mut fields);
+    if body.get("required").and_then(Value::as_bool) == Some(true) {
+      text.push_str(", required");
+    }
+    facts.insert((REQUEST_BODY, String::from("body")), text);
+    facts.extend(fields.into_iter().map(|(at, text)| ((REQUEST_BODY, at), text)));
+  }
+
+  let responses = value.get("responses").and_then(Value::as_object).into_iter().flatten();
+  for (status, response) in responses {
+    let mut fields = BTreeMap::new();
+    let text = content_facts(resolve(response, document), document, &format!("{status} "), &mut fields);
+    facts.insert((RESPONSES, status.clone()), text);
+    facts.extend(fields.into_iter().map(|(at, text)| ((RESPONSES, at), text)));
+  }
+
+  let requirements: Vec<String> = security::operation_security(document, path, method)
+    .into_iter()
+    .map(|requirement| {
+      match requirement.is_empty() {
+        true => String::from("anonymous"),
+        false => requirement.into_keys().collect::<Vec<_>>().join(" + "),
+      }
+    })
+    .collect();
+  let security = match requirements.is_empty() {
+    true => String::from("none"),
+    false => requirements.join(" or "),
+  };
+  facts.insert((SECURITY, String::from("security")), security);
+  facts
+}
+
+/// The parameters, request body, responses and security of the two operations side by side, what only one of them
+/// has included.
+pub fn compare(document: &Value, left: &OperationKey, right: &OperationKey) -> Vec<Row> {
+  let (mut left, mut right) = (facts(document, left), facts(document, right));
+  let mut keys: Vec<(usize, String)> = left.keys().chain(right.keys()).cloned().collect();
+  keys.sort();
+  keys.dedup();
+  keys
+    .into_iter()
+    .map(|key| Row { left: left.remove(&key), right: right.remove(&key), section: key.0, name: key.1 })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+  use serde_json::json;
+
+  use super::*;
+
+  #[test]
+  fn test_compare() {
+    let document = json!({
+      "security": [{ "bearer": [] }],
+      "paths": {
+        "/users": { "post": {
+          "requestBody": { "required": true, "content": { "application/json": { "schema": { "$ref": "#/components/schemas/User" } } } },
+          "responses": { "201": { "content": { "application/json": { "schema": { "$ref": "#/components/schemas/User" } } } } }
+        } },
+        "/users/{id}": {
+          "parameters": [{ "name": "id", "in": "path", "required": true, "schema": { "type": "integer" } }],
+          "patch": {
+            "requestBody": { "content": { "application/json": { "schema": {
+              "type": "object",
+              "properties": { "email": { "type": "string" }, "role": { "type": "string", "enum": ["admin", "member"] } }
+            } } } },
+            "responses": { "204": {} }
+          }
+        }
+      },
+      "components": { "schemas": { "User": {
+        "type": "object",
+        "required": ["email"],
+        "properties": { "email": { "type": "string", "format": "email" }, "name": { "type": "string" } }
+      } } }
+    });
+    let rows = compare(&document, &OperationKey::new("post", "/users"), &OperationKey::new("patch", "/users/{id}"));
+    let shown: Vec<(&str, &str, Option<&str>, Option<&str>)> = rows
+      .iter()
+      .map(|row| (SECTIONS[row.section], row.name.as_str(), row.left.as_deref(), row.right.as_deref()))
+      .collect();
+    assert_eq!(shown, vec![
+      ("Parameters", "path id", None, Some("integer, required")),
+      ("Request body", "body", Some("application/json, required"), Some("application/json")),
+      ("Request body", "body $.email", Some("string (email), required"), Some("string")),
+      ("Request body", "body $.name", Some("string"), None),
+      ("Request body", "body $.role", None, Some("string: admin|member")),
+      ("Responses", "201", Some("application/json"), None),
+      ("Responses", "201 $.email", Some("string (email), required"), None),
+      ("Responses", "201 $.name", Some("string"), None),
+      ("Responses", "204", None, Some("no content")),
+      ("Security", "security", Some("bearer"), Some("bearer")),
+    ]);
+    assert_eq!(rows.iter().filter(|row| row.differs()).count(), 9);
+  }
+}
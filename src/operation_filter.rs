@@ -0,0 +1,199 @@
+use std::str::FromStr;
+
+use color_eyre::eyre::{eyre, Report, Result};
+use regex::Regex;
+
+use crate::operation_index::IndexedOperation;
+
+/// Fields of an operation a filter term tests, e.g. `tag:users`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+  Method,
+  Tag,
+  Path,
+  Id,
+  Summary,
+  Deprecated,
+}
+
+impl FromStr for Field {
+  type Err = Report;
+
+  fn from_str(name: &str) -> Result<Self> {
+    match name.to_lowercase().as_str() {
+      "method" => Ok(Field::Method),
+      "tag" => Ok(Field::Tag),
+      "path" => Ok(Field::Path),
+      "id" | "operationid" => Ok(Field::Id),
+      "summary" => Ok(Field::Summary),
+      "deprecated" => Ok(Field::Deprecated),
+      _ => Err(eyre!("Unknown field `{name}`, expected method, tag, path, id, summary or deprecated")),
+    }
+  }
+}
+
+#[derive(Debug, Clone)]
+enum Pattern {
+  /// Lowercase text, equal to a method or tag, or contained in the other fields.
+  Text(String),
+  /// Regex after `~`, e.g. `path:~^/admin/`.
+  Regex(Regex),
+  Flag(bool),
+}
+
+impl Pattern {
+  fn matches(&self, field: Option<Field>, value: &str) -> bool {
+    match self {
+      Pattern::Text(text) if matches!(field, Some(Field::Method | Field::Tag)) => value.eq_ignore_ascii_case(text),
+      Pattern::Text(text) => value.to_lowercase().contains(text.as_str()),
+      Pattern::Regex(regex) => regex.is_match(value),
+      Pattern::Flag(_) => false,
+    }
+  }
+}
+
+/// Term of a filter, the operation matching when one of its alternatives does, or none of them when negated.
+#[derive(Debug, Clone)]
+struct Term {
+  /// `None` for a plain word, searched in the method, path, id and summary.
+  field: Option<Field>,
+  alternatives: Vec<Pattern>,
+  negated: bool,
+}
+
+impl FromStr for Term {
+  type Err = Report;
+
+  fn from_str(text: &str) -> Result<Self> {
+    let (negated, text) = match text.strip_prefix('-') {
+      Some(rest) if !rest.is_empty() => (true, rest),
+      _ => (false, text),
+    };
+    let (field, value) = match text.split_once(':') {
+      Some((name, value)) if name.chars().all(|c| c.is_ascii_alphabetic()) => (Some(name.parse::<Field>()?), value),
+      _ => (None, text),
+    };
+    if value.is_empty() {
+      return Err(eyre!("Missing value in `{text}`"));
+    }
+    let alternatives = value
+      .split(',')
+      .filter(|alternative| !alternative.is_empty())
+      .map(|alternative| {
+        match (field, alternative.strip_prefix('~')) {
+          (Some(Field::Deprecated), _) => {
+            match alternative.to_lowercase().as_str() {
+              "true" | "yes" => Ok(Pattern::Flag(true)),
+              "false" | "no" => Ok(Pattern::Flag(false)),
+              _ => Err(eyre!("Invalid `deprecated:{alternative}`, expected true or false")),
+            }
+          },
+          (_, Some(regex)) => {
+            Regex::new(regex).map(Pattern::Regex).map_err(|error| eyre!("Invalid regex `{regex}`: {error}"))
+          },
+          (_, None) => Ok(Pattern::Text(alternative.to_lowercase())),
+        }
+      })
+      .collect::<Result<Vec<Pattern>>>()?;
+    Ok(Self { field, alternatives, negated })
+  }
+}
+
+impl Term {
+  fn matches(&self, operation: &IndexedOperation) -> bool {
+    let values: Vec<&str> = match self.field {
+      Some(Field::Method) => vec![&operation.method],
+      Some(Field::Tag) => operation.tags.iter().map(String::as_str).collect(),
+      Some(Field::Path) => vec![&operation.path],
+      Some(Field::Id) => operation.operation_id.iter().map(String::as_str).collect(),
+      Some(Field::Summary) => operation.summary.iter().map(String::as_str).collect(),
+      Some(Field::Deprecated) => vec![],
+      None => vec![operation.text()],
+    };
+    let matched = self.alternatives.iter().any(|pattern| {
+      match pattern {
+        Pattern::Flag(flag) => operation.deprecated == *flag,
+        pattern => values.iter().any(|value| pattern.matches(self.field, value)),
+      }
+    });
+    matched != self.negated
+  }
+}
+
+/// Filter of the listed operations, terms separated by spaces that must all match, e.g.
+/// `method:post tag:users deprecated:false path:~/admin/`.
+///
+/// A term is a plain word searched in the method, path, id and summary, or a `field:value` of the method, a tag, the
+/// path, the `operationId` (`id`), the summary or `deprecated`. Methods and tags are matched whole, the other fields
+/// when they contain the value, ignoring case, or a regex after `~`. Values separated by `,` are alternatives, and
+/// `-` in front of a term excludes the operations it matches.
+#[derive(Debug, Clone)]
+pub struct OperationFilter {
+  terms: Vec<Term>,
+}
+
+impl FromStr for OperationFilter {
+  type Err = Report;
+
+  fn from_str(text: &str) -> Result<Self> {
+    let terms = text.split_whitespace().map(str::parse).collect::<Result<Vec<Term>>>()?;
+    Ok(Self { terms })
+  }
+}
+
+impl OperationFilter {
+  pub fn matches(&self, operation: &IndexedOperation) -> bool {
+    self.terms.iter().all(|term| term.matches(operation))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use oas3::Spec;
+  use pretty_assertions::assert_eq;
+  use serde_json::json;
+
+  use super::*;
+  use crate::operation_index::OperationIndex;
+
+  #[test]
+  fn test_filter() {
+    let spec: Spec = serde_json::from_value(json!({
+      "openapi": "3.0.0",
+      "info": { "title": "Users", "version": "1.0.0" },
+      "paths": {
+        "/users": {
+          "get": { "operationId": "listUsers", "summary": "List users", "tags": ["users"] },
+          "post": { "operationId": "createUser", "tags": ["users"] }
+        },
+        "/admin/users": {
+          "post": { "operationId": "importUsers", "tags": ["users", "admin"], "deprecated": true },
+          "delete": { "operationId": "purgeUsers", "tags": ["admin"] }
+        },
+        "/health": { "get": {} }
+      }
+    }))
+    .unwrap();
+    let index = OperationIndex::new(&spec);
+    let filtered = |query: &str| {
+      let filter: OperationFilter = query.parse().unwrap();
+      (0..index.len())
+        .filter_map(|position| index.get(position))
+        .filter(|operation| filter.matches(operation))
+        .map(|operation| operation.key.to_string())
+        .collect::<Vec<_>>()
+    };
+    assert_eq!(filtered("method:post tag:users deprecated:false"), vec!["POST /users"]);
+    assert_eq!(filtered("method:POST path:~/admin/"), vec!["POST /admin/users"]);
+    assert_eq!(filtered("method:get,delete -tag:admin"), vec!["GET /health", "GET /users"]);
+    assert_eq!(filtered("id:~^(list|purge) list"), vec!["GET /users"]);
+    assert_eq!(filtered("summary:users"), vec!["GET /users"]);
+    assert_eq!(filtered("").len(), 5);
+    assert_eq!(
+      "verb:get".parse::<OperationFilter>().err().unwrap().to_string(),
+      "Unknown field `verb`, expected method, tag, path, id, summary or deprecated"
+    );
+    assert!("path:~(".parse::<OperationFilter>().is_err());
+    assert!("deprecated:maybe".parse::<OperationFilter>().is_err());
+  }
+}
@@ -0,0 +1,179 @@
+use std::collections::{BTreeMap, HashMap};
+
+use oas3::{
+  spec::{Operation, PathItem},
+  Spec,
+};
+
+use crate::{tags::UNTAGGED, workspace::OperationKey};
+
+/// Operation of the spec, as indexed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexedOperation {
+  pub path: String,
+  pub method: String,
+  pub key: OperationKey,
+  pub tags: Vec<String>,
+  pub operation_id: Option<String>,
+  pub summary: Option<String>,
+  pub deprecated: bool,
+  /// Method, path, id and summary, lowercase, searched by [`OperationIndex::search`].
+  text: String,
+}
+
+impl IndexedOperation {
+  /// Method, path, id and summary, lowercase.
+  pub fn text(&self) -> &str {
+    &self.text
+  }
+}
+
+/// Operations of the spec in its order, indexed by tag, method and name, built once when the spec is loaded so that
+/// specs of thousands of operations are filtered without walking the spec again.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OperationIndex {
+  operations: Vec<IndexedOperation>,
+  /// Operations by tag, those without tags under [`UNTAGGED`].
+  by_tag: BTreeMap<String, Vec<usize>>,
+  by_method: BTreeMap<String, Vec<usize>>,
+  /// Operations by id and by method and path, e.g. `POST /pet`.
+  by_name: HashMap<String, usize>,
+}
+
+impl OperationIndex {
+  pub fn new(spec: &Spec) -> Self {
+    let mut index = Self::default();
+    for (path, method, operation) in spec.operations() {
+      let position = index.operations.len();
+      let method = method.to_string();
+      let key = OperationKey::new(&method, &path);
+      let tags = match operation.tags.is_empty() {
+        true => vec![UNTAGGED.to_string()],
+        false => operation.tags.clone(),
+      };
+      for tag in tags {
+        index.by_tag.entry(tag).or_default().push(position);
+      }
+      index.by_method.entry(method.to_uppercase()).or_default().push(position);
+      if let Some(id) = &operation.operation_id {
+        index.by_name.entry(id.clone()).or_insert(position);
+      }
+      index.by_name.insert(key.to_string(), position);
+      let text = [
+        method.as_str(),
+        path.as_str(),
+        operation.operation_id.as_deref().unwrap_or_default(),
+        operation.summary.as_deref().unwrap_or_default(),
+      ]
+      .join(" ")
+      .to_lowercase();
+      index.operations.push(IndexedOperation {
+        path,
+        method,
+        key,
+        tags: operation.tags.clone(),
+        operation_id: operation.operation_id.clone(),
+        summary: operation.summary.clone(),
+        deprecated: operation.deprecated.unwrap_or_default(),
+        text,
+      });
+    }
+    index
+  }
+
+  pub fn len(&self) -> usize {
+    self.operations.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.operations.is_empty()
+  }
+
+  pub fn get(&self, position: usize) -> Option<&IndexedOperation> {
+    self.operations.get(position)
+  }
+
+  /// Operations listed under one of the tags, in the order of the spec.
+  pub fn tagged(&self, tags: &[String]) -> Vec<usize> {
+    let mut positions: Vec<usize> = tags.iter().filter_map(|tag| self.by_tag.get(tag)).flatten().copied().collect();
+    positions.sort_unstable();
+    positions.dedup();
+    positions
+  }
+
+  /// Operations of the method, in the order of the spec.
+  pub fn with_method(&self, method: &str) -> &[usize] {
+    self.by_method.get(&method.to_uppercase()).map_or(&[], Vec::as_slice)
+  }
+
+  /// The operation of the id, or of the method and path, e.g. `POST /pet`.
+  pub fn find(&self, name: &str) -> Option<usize> {
+    let key = name.split_once(' ').map(|(method, path)| OperationKey::new(method.trim(), path.trim()).to_string());
+    self.by_name.get(name).or_else(|| key.and_then(|key| self.by_name.get(&key))).copied()
+  }
+
+  /// Operations whose method, path, id or summary contain every word of the query, ignoring case.
+  pub fn search(&self, query: &str) -> Vec<usize> {
+    let words: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+    (0..self.operations.len())
+      .filter(|position| words.iter().all(|word| self.operations[*position].text.contains(word.as_str())))
+      .collect()
+  }
+}
+
+/// Operation of the method on the path item.
+pub fn operation_of<'a>(item: &'a PathItem, method: &str) -> Option<&'a Operation> {
+  let operation = match method.to_lowercase().as_str() {
+    "get" => &item.get,
+    "put" => &item.put,
+    "post" => &item.post,
+    "delete" => &item.delete,
+    "options" => &item.options,
+    "head" => &item.head,
+    "patch" => &item.patch,
+    "trace" => &item.trace,
+    _ => return None,
+  };
+  operation.as_ref()
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+  use serde_json::json;
+
+  use super::*;
+
+  #[test]
+  fn test_index() {
+    let spec: Spec = serde_json::from_value(json!({
+      "openapi": "3.0.0",
+      "info": { "title": "Petstore", "version": "1.0.0" },
+      "paths": {
+        "/pets": {
+          "get": { "operationId": "listPets", "summary": "List all pets", "tags": ["pets"] },
+          "post": { "operationId": "createPet", "tags": ["pets", "admin"] }
+        },
+        "/store/orders": { "get": { "summary": "List orders", "tags": ["store"] } },
+        "/health": { "get": {} }
+      }
+    }))
+    .unwrap();
+    let index = OperationIndex::new(&spec);
+    let listed = |positions: &[usize]| {
+      positions.iter().map(|position| index.get(*position).unwrap().key.to_string()).collect::<Vec<_>>()
+    };
+    assert_eq!(index.len(), 4);
+    assert_eq!(listed(&index.tagged(&[String::from("pets"), String::from("admin")])), vec!["GET /pets", "POST /pets"]);
+    assert_eq!(listed(&index.tagged(&[UNTAGGED.to_string()])), vec!["GET /health"]);
+    assert_eq!(listed(index.with_method("get")), vec!["GET /health", "GET /pets", "GET /store/orders"]);
+    assert_eq!(index.find("createPet"), index.find("post /pets"));
+    assert_eq!(index.find("DELETE /pets"), None);
+    assert_eq!(listed(&index.search("list ORDERS")), vec!["GET /store/orders"]);
+    assert_eq!(listed(&index.search("pets")), vec!["GET /pets", "POST /pets"]);
+    assert_eq!(
+      operation_of(&spec.paths["/pets"], "POST").and_then(|operation| operation.operation_id.clone()),
+      Some(String::from("createPet"))
+    );
+  }
+}
@@ -9,7 +9,10 @@ use crate::{
   tui::{Event, EventResponse, Frame},
 };
 
+pub mod asyncapi;
+pub mod error;
 pub mod home;
+pub mod mock;
 
 pub trait Page {
   #[allow(unused_variables)]
@@ -30,6 +33,7 @@ pub trait Page {
     let r = match event {
       Some(Event::Key(key_event)) => self.handle_key_events(key_event)?,
       Some(Event::Mouse(mouse_event)) => self.handle_mouse_events(mouse_event)?,
+      Some(Event::Paste(text)) => self.handle_paste_events(text)?,
       _ => None,
     };
     Ok(r)
@@ -45,10 +49,27 @@ pub trait Page {
     Ok(None)
   }
 
+  /// Text pasted with bracketed paste, inserted as is by editors instead of being handled key by key.
+  #[allow(unused_variables)]
+  fn handle_paste_events(&mut self, text: String) -> Result<Option<EventResponse<Action>>> {
+    Ok(None)
+  }
+
   #[allow(unused_variables)]
   fn update(&mut self, action: Action) -> Result<Option<Action>> {
     Ok(None)
   }
 
+  /// Unsaved edits and requests in flight that quitting would discard, asked to be confirmed first.
+  fn unfinished_work(&self) -> Vec<String> {
+    vec![]
+  }
+
+  /// Whether the page changes on its own, e.g. with a spinner, and is rendered on every frame rather than only after
+  /// events and actions.
+  fn animating(&self) -> bool {
+    false
+  }
+
   fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<()>;
 }
@@ -0,0 +1,136 @@
+use std::sync::{Arc, RwLock};
+
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::{layout::Position, prelude::*};
+
+use crate::{
+  action::Action,
+  asyncapi::{AsyncApi, ChannelOperation},
+  pages::Page,
+  panes::{channels::ChannelsPane, messages::MessagesPane, Pane},
+  theme,
+  tui::{EventResponse, Frame},
+};
+
+/// AsyncAPI document browsed by the page, shared by its panes.
+#[derive(Debug, Default)]
+pub struct AsyncApiState {
+  pub openapi_path: String,
+  pub spec: AsyncApi,
+  /// Selected operation, into `spec.operations`.
+  pub operation_index: usize,
+}
+
+impl AsyncApiState {
+  pub fn active_operation(&self) -> Option<&ChannelOperation> {
+    self.spec.operations.get(self.operation_index)
+  }
+}
+
+/// Browses an AsyncAPI document read-only, its channels and operations beside the messages of the selected one.
+pub struct AsyncApiPage {
+  panes: Vec<Box<dyn Pane>>,
+  focused_pane_index: usize,
+  /// Areas the panes were last drawn in, to find the pane clicked.
+  pane_areas: Vec<Rect>,
+}
+
+impl AsyncApiPage {
+  pub fn new(openapi_path: String) -> Result<Self> {
+    let spec = AsyncApi::load(&openapi_path)?;
+    let state = Arc::new(RwLock::new(AsyncApiState { openapi_path, spec, operation_index: 0 }));
+    let focused_border_style = theme::current().focused_border;
+    Ok(Self {
+      panes: vec![
+        Box::new(ChannelsPane::new(state.clone(), true, focused_border_style)),
+        Box::new(MessagesPane::new(state, false, focused_border_style)),
+      ],
+      focused_pane_index: 0,
+      pane_areas: Vec::default(),
+    })
+  }
+
+  fn focus_pane(&mut self, index: usize) -> Result<()> {
+    if index != self.focused_pane_index {
+      self.panes[self.focused_pane_index].unfocus()?;
+      self.focused_pane_index = index;
+      self.panes[index].focus()?;
+    }
+    Ok(())
+  }
+}
+
+impl Page for AsyncApiPage {
+  fn init(&mut self) -> Result<()> {
+    for pane in self.panes.iter_mut() {
+      pane.init()?;
+    }
+    Ok(())
+  }
+
+  fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
+    self.panes[self.focused_pane_index].handle_key_events(key)
+  }
+
+  fn handle_mouse_events(&mut self, mouse: MouseEvent) -> Result<Option<EventResponse<Action>>> {
+    let position = Position { x: mouse.column, y: mouse.row };
+    let Some(index) = self.pane_areas.iter().position(|area| area.contains(position)) else {
+      return Ok(None);
+    };
+    let scroll = match mouse.kind {
+      MouseEventKind::ScrollDown => Action::Down,
+      MouseEventKind::ScrollUp => Action::Up,
+      MouseEventKind::Down(MouseButton::Left) => {
+        self.focus_pane(index)?;
+        return self.panes[index].handle_mouse_events(mouse);
+      },
+      _ => return Ok(None),
+    };
+    let action = self.panes[index].update(scroll)?;
+    Ok(Some(EventResponse::Stop(action.unwrap_or(Action::Render))))
+  }
+
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    match action {
+      Action::FocusNext | Action::FocusPrev => {
+        let index = (self.focused_pane_index + 1) % self.panes.len();
+        self.focus_pane(index)?;
+      },
+      Action::Update => {
+        for pane in self.panes.iter_mut() {
+          pane.update(Action::Update)?;
+        }
+      },
+      // messages are picked by tab wherever the focus is
+      Action::Tab(_) => return self.panes[1].update(action),
+      action => return self.panes[self.focused_pane_index].update(action),
+    }
+    Ok(Some(Action::Render))
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    let vertical_layout = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints([Constraint::Fill(1), Constraint::Max(1)])
+      .split(area);
+    let layout = Layout::default()
+      .direction(Direction::Horizontal)
+      .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+      .split(vertical_layout[0]);
+    self.pane_areas = layout.to_vec();
+    for (pane, area) in self.panes.iter_mut().zip(layout.iter()) {
+      pane.draw(frame, *area)?;
+    }
+
+    const ARROW: &str = symbols::scrollbar::HORIZONTAL.end;
+    frame.render_widget(
+      Line::from(format!(
+        "[l {ARROW} next pane] [j/k {ARROW} item] [1-9 {ARROW} message] [g/b {ARROW} go in/back] [i {ARROW} headers/payload] [q {ARROW} quit]"
+      ))
+      .style(Style::default().fg(Color::DarkGray)),
+      vertical_layout[1],
+    );
+    Ok(())
+  }
+}
@@ -0,0 +1,232 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+  prelude::*,
+  widgets::{block::*, *},
+};
+use tui_textarea::TextArea;
+
+use crate::{
+  action::Action,
+  pages::Page,
+  tui::{self, EventResponse, Frame},
+};
+
+/// Lines of the spec shown around the line of the error.
+const SNIPPET_CONTEXT: usize = 3;
+
+/// Shown instead of the page of the spec when it fails to load, to load it again once fixed or to load another one.
+pub struct ErrorPage {
+  openapi_path: String,
+  /// Error and its causes, outermost first.
+  messages: Vec<String>,
+  /// Line and column of the error in the spec, 1-based.
+  location: Option<(usize, usize)>,
+  /// Numbered lines of the spec around the error.
+  snippet: Vec<(usize, String)>,
+  /// Path of the spec to load instead, while it is typed.
+  path_input: Option<TextArea<'static>>,
+}
+
+impl ErrorPage {
+  /// The page of the error, its messages outermost first.
+  pub fn new(openapi_path: String, messages: Vec<String>) -> Self {
+    let location = messages.iter().find_map(|message| location(message));
+    let snippet = match (location, std::fs::read_to_string(&openapi_path)) {
+      (Some((line, _)), Ok(text)) => {
+        let first = line.saturating_sub(SNIPPET_CONTEXT + 1);
+        text
+          .lines()
+          .enumerate()
+          .skip(first)
+          .take(SNIPPET_CONTEXT * 2 + 1)
+          .map(|(index, text)| (index + 1, text.to_string()))
+          .collect()
+      },
+      _ => vec![],
+    };
+    Self { openapi_path, messages, location, snippet, path_input: None }
+  }
+
+  fn lines(&self) -> Vec<Line<'_>> {
+    let mut lines = vec![Line::styled(format!("Failed to load {}", self.openapi_path), Style::default().bold())];
+    if let Some((line, column)) = self.location {
+      lines.push(Line::styled(format!("at line {line}, column {column}"), Style::default().dim()));
+    }
+    lines.push(Line::default());
+    lines
+      .extend(self.messages.iter().map(|message| Line::styled(message.as_str(), Style::default().fg(Color::LightRed))));
+    if self.snippet.is_empty() {
+      return lines;
+    }
+    lines.push(Line::default());
+    let width = self.snippet.last().map_or(1, |(number, _)| number.to_string().len());
+    for (number, text) in self.snippet.iter() {
+      let error_line = self.location.is_some_and(|(line, _)| line == *number);
+      let gutter = format!("{}{number:>width$} │ ", if error_line { "▶ " } else { "  " });
+      let style = if error_line { Style::default().bold() } else { Style::default().dim() };
+      lines.push(Line::from(vec![Span::styled(gutter, Style::default().dim()), Span::styled(text.as_str(), style)]));
+      if let Some((_, column)) = self.location.filter(|_| error_line) {
+        let indent = 2 + width + 3 + column.saturating_sub(1);
+        lines.push(Line::styled(format!("{}^", " ".repeat(indent)), Style::default().fg(Color::LightRed)));
+      }
+    }
+    lines
+  }
+}
+
+/// Line and column of a parse error message, e.g. `... at line 3 column 5`.
+fn location(message: &str) -> Option<(usize, usize)> {
+  let (_, position) = message.rsplit_once("at line ")?;
+  let mut numbers = position.split(|c: char| !c.is_ascii_digit()).filter(|part| !part.is_empty());
+  let line = numbers.next()?.parse().ok()?;
+  let column = numbers.next().and_then(|column| column.parse().ok()).unwrap_or(1);
+  Some((line, column))
+}
+
+impl Page for ErrorPage {
+  fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
+    if let Some(input) = self.path_input.as_mut() {
+      let response = match key.code {
+        KeyCode::Enter => {
+          let path = input.lines().join("").trim().to_string();
+          self.path_input = None;
+          match path.is_empty() {
+            true => Action::Render,
+            false => Action::LoadSpec(path),
+          }
+        },
+        KeyCode::Esc => {
+          self.path_input = None;
+          Action::Render
+        },
+        _ => {
+          input.input(key);
+          Action::Render
+        },
+      };
+      return Ok(Some(EventResponse::Stop(response)));
+    }
+    let response = match key.code {
+      KeyCode::Char('r') => Action::LoadSpec(self.openapi_path.clone()),
+      KeyCode::Char('o') => {
+        let mut input = TextArea::new(vec![self.openapi_path.clone()]);
+        input.set_cursor_line_style(Style::default());
+        input.move_cursor(tui_textarea::CursorMove::End);
+        self.path_input = Some(input);
+        Action::Render
+      },
+      _ => return Ok(None),
+    };
+    Ok(Some(EventResponse::Stop(response)))
+  }
+
+  fn handle_paste_events(&mut self, text: String) -> Result<Option<EventResponse<Action>>> {
+    let Some(input) = self.path_input.as_mut() else {
+      return Ok(None);
+    };
+    input.insert_str(tui::pasted(&text, false));
+    Ok(Some(EventResponse::Stop(Action::Render)))
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    const ARROW: &str = symbols::scrollbar::HORIZONTAL.end;
+    let layout = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints([Constraint::Fill(1), Constraint::Max(if self.path_input.is_some() { 3 } else { 0 })])
+      .split(area);
+    frame.render_widget(
+      Paragraph::new(self.lines()).wrap(Wrap { trim: false }).block(
+        Block::default()
+          .title("Error")
+          .borders(Borders::ALL)
+          .border_type(BorderType::Thick)
+          .border_style(Style::default().fg(Color::LightRed))
+          .padding(Padding::horizontal(1))
+          .title_bottom(
+            Line::from(format!("[r {ARROW} retry] [o {ARROW} open another spec] [q {ARROW} quit]"))
+              .style(Style::default().dim())
+              .right_aligned(),
+          ),
+      ),
+      layout[0],
+    );
+    if let Some(path_input) = self.path_input.as_mut() {
+      path_input.set_block(
+        Block::default().title("Open").borders(Borders::ALL).border_type(BorderType::Double).title_bottom(
+          Line::from(format!("[enter {ARROW} open] [esc {ARROW} cancel]"))
+            .style(Style::default().dim())
+            .right_aligned(),
+        ),
+      );
+      frame.render_widget(path_input.widget(), layout[1]);
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_location() {
+    assert_eq!(location("paths.`/pets`: invalid type at line 12 column 5"), Some((12, 5)));
+    assert_eq!(location("did not find expected key at line 3, column 14, while parsing"), Some((3, 14)));
+    assert_eq!(location("unexpected end of input at line 7"), Some((7, 1)));
+    assert_eq!(location("No such file or directory (os error 2)"), None);
+  }
+
+  #[test]
+  fn test_snippet() -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("openapi-tui-error-{}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    let openapi_path = dir.join("petstore.yaml").to_string_lossy().to_string();
+    let spec = (1..=20).map(|number| format!("line{number}")).collect::<Vec<_>>().join("\n");
+    std::fs::write(&openapi_path, spec)?;
+    let page = ErrorPage::new(openapi_path.clone(), vec![
+      String::from("Failed to parse the spec"),
+      String::from("mapping values are not allowed at line 10 column 3"),
+    ]);
+    let near_start = ErrorPage::new(openapi_path.clone(), vec![String::from("invalid at line 2 column 1")]);
+    std::fs::remove_dir_all(&dir)?;
+    assert_eq!(page.location, Some((10, 3)));
+    assert_eq!(page.snippet.first(), Some(&(7, String::from("line7"))));
+    assert_eq!(page.snippet.last(), Some(&(13, String::from("line13"))));
+    let lines: Vec<String> = page.lines().iter().map(ToString::to_string).collect();
+    let error_line = lines.iter().position(|line| line.starts_with("▶ 10 │ line10")).unwrap();
+    assert_eq!(lines[error_line + 1], "         ^");
+    assert_eq!(near_start.snippet.iter().map(|(number, _)| *number).collect::<Vec<_>>(), (1..=7).collect::<Vec<_>>());
+    Ok(())
+  }
+
+  #[test]
+  fn test_retry_and_open() -> Result<()> {
+    let mut page = ErrorPage::new(String::from("missing.yaml"), vec![String::from("No such file")]);
+    assert!(page.snippet.is_empty());
+    let mut press = |code: KeyCode| -> Result<Option<Action>> {
+      Ok(page.handle_key_events(KeyEvent::from(code))?.map(|response| {
+        match response {
+          EventResponse::Stop(action) | EventResponse::Continue(action) => action,
+        }
+      }))
+    };
+    assert_eq!(press(KeyCode::Char('r'))?, Some(Action::LoadSpec(String::from("missing.yaml"))));
+    assert_eq!(press(KeyCode::Char('o'))?, Some(Action::Render));
+    for _ in 0.."missing.yaml".len() {
+      press(KeyCode::Backspace)?;
+    }
+    press(KeyCode::Char('a'))?;
+    assert_eq!(press(KeyCode::Enter)?, Some(Action::LoadSpec(String::from("a"))));
+    // a blank path opens nothing
+    press(KeyCode::Char('o'))?;
+    for _ in 0.."missing.yaml".len() {
+      press(KeyCode::Backspace)?;
+    }
+    assert_eq!(press(KeyCode::Enter)?, Some(Action::Render));
+    assert_eq!(press(KeyCode::Char('x'))?, None);
+    Ok(())
+  }
+}
@@ -1,50 +1,991 @@
-use std::sync::{Arc, RwLock};
+use std::{
+  collections::{BTreeMap, BTreeSet},
+  path::{Path, PathBuf},
+  sync::{Arc, Mutex, RwLock},
+  time::{Duration, Instant},
+};
 
-use color_eyre::eyre::Result;
-use crossterm::event::{KeyCode, KeyEvent};
+use color_eyre::eyre::{eyre, Result};
+use crossterm::event::{KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 use oas3::{spec::Operation, Spec};
-use ratatui::prelude::*;
+use ratatui::{
+  layout::Position,
+  prelude::*,
+  widgets::{Block, BorderType, Borders, Clear, Padding, Paragraph, Wrap},
+};
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
 use tokio::sync::mpsc::UnboundedSender;
+use tokio_util::sync::{CancellationToken, DropGuard};
 
 use crate::{
   action::Action,
-  config::Config,
+  cache::{self, Cache},
+  cassette::{Player, Recorder},
+  client::Exchange,
+  codegen,
+  config::{Config, KeyScope, LayoutConfig},
+  diff,
+  export::{
+    self, export_path,
+    http::{HttpFileEntry, AUTH_VARIABLE},
+    postman::{scheme_auth, PostmanEntry},
+  },
+  extract,
+  fuzz::{self, Case, CaseResult},
+  git,
+  health::{self, Health},
+  history,
+  hosts::{self, HostOverride},
+  import,
+  keymap::Keymap,
+  keys::KeyResolver,
+  layout, lint,
+  load::LoadTest,
+  merge, mock,
+  network::Network,
+  operation_compare,
+  operation_filter::OperationFilter,
+  operation_index::{self, OperationIndex},
   pages::Page,
-  panes::{address::AddressPane, apis::ApisPane, request::RequestPane, response::ResponsePane, tags::TagsPane, Pane},
+  pagination::Walk,
+  palette::Command,
+  panes::{
+    address::AddressPane, apis::ApisPane, collections::CollectionsPane, components::ComponentsPane,
+    history::HistoryPane, request::RequestPane, response::ResponsePane, tags::TagsPane, Pane,
+  },
+  parameter,
+  plugin::Plugins,
+  popups::{
+    assertion::AssertionPopup,
+    centered_rect,
+    diff::DiffPopup,
+    environment::EnvironmentPopup,
+    extraction::ExtractionPopup,
+    fuzz::FuzzPopup,
+    help::{HelpPopup, HelpSection},
+    lint::LintPopup,
+    load::LoadPopup,
+    message::MessagePopup,
+    note::NotePopup,
+    operation_compare::OperationComparePopup,
+    pagination::PaginationPopup,
+    palette::PalettePopup,
+    payload::PayloadPopup,
+    quit::QuitPopup,
+    registry::RegistryPopup,
+    replay::ReplayPopup,
+    response::ResponsePopup,
+    response_diff::ResponseDiffPopup,
+    save::SavePopup,
+    script::ScriptPopup,
+    server::ServerPopup,
+    smoke::SmokePopup,
+    snippet::SnippetPopup,
+    spec_info::SpecInfoPopup,
+    stats::StatsPopup,
+    types::TypesPopup,
+    usage::UsagePopup,
+    workflow::WorkflowPopup,
+    Popup,
+  },
+  queue::Queue,
+  rate_limit,
+  redaction::{self, Redactor},
+  redirect, registry, remote,
+  report::{TestCase, TestReport, TestSuite},
+  request::{self, HttpRequest},
+  response_diff::{self, DocumentedStatus},
+  script::{self, Hooks},
+  secrets, security,
+  security::operation_auth_scheme,
+  server::{self, Server},
+  spec_info::SpecInfo,
+  stats::Stats,
+  tags::{self, TagNode},
+  theme,
+  toast::{ToastLevel, Toasts},
   tui::EventResponse,
+  usage,
+  workflow::{self, PreparedStep, Step, Workflow},
+  workspace::{Environment, OperationKey, RequestDraft, SavedRequest, ScriptTarget, Session, Workspace},
+  xml,
 };
 
+/// The loaded spec along with its workspace and the requests sent, shared by the panes of the home page.
 #[derive(Default)]
 pub struct State {
   pub openapi_path: String,
   pub openapi_spec: Spec,
+  pub openapi_document: serde_json::Value,
   pub active_operation_index: usize,
+  /// Selected tag, or group of tags, whose operations are listed.
   pub active_tag_name: Option<String>,
+  pub tag_tree: Vec<TagNode>,
+  pub workspace: Workspace,
+  /// Requests sent during this session, oldest first.
+  pub exchanges: Vec<Exchange>,
+  /// Requests sent for this spec across sessions, oldest first, with secrets referenced by variable.
+  pub history: Vec<Exchange>,
+  /// Environments defined in the config file, workspace environments with the same name override them.
+  pub config_environments: Vec<Environment>,
+  /// Base url set on the command line or edited in the address pane, used instead of the spec servers.
+  pub base_url_override: Option<String>,
+  /// Environment set on the command line, used instead of the workspace one and never saved.
+  pub environment_override: Option<String>,
+  /// Variables loaded from `.env` files, available whatever the active environment.
+  pub dotenv_variables: BTreeMap<String, String>,
+  /// Workflows loaded with `--workflow`.
+  pub workflows: Vec<Workflow>,
+  /// Values extracted from responses during this session, they override the environment variables.
+  pub extracted_variables: BTreeMap<String, String>,
+  /// Shows secret values in popups and writes them to exports instead of masking them.
+  pub reveal_secrets: bool,
+  /// Wraps long lines of response bodies instead of cutting them.
+  pub wrap_lines: bool,
+  /// WebAssembly plugins of the config.
+  pub plugins: Plugins,
+  /// Redaction rules of the config, applied to the history, exports, logs and snippets.
+  pub redactor: Redactor,
+  /// Client-side cache of GET responses, `None` while it is off.
+  pub cache: Option<Cache>,
+  /// Annotates the timestamps of response bodies with the local time.
+  pub local_times: bool,
+  /// Shows the request of the selected operation as it will be sent, in the request pane.
+  pub request_preview: bool,
+  /// Last probe of the base url of the selected server, shown in the address pane.
+  pub health: Option<Health>,
+  /// Cassette the exchanges are recorded into, with `--record`.
+  pub recorder: Option<Recorder>,
+  /// Cassette answering the requests, with `--replay`.
+  pub player: Option<Arc<Player>>,
+  /// Lists only the operations no request was sent for yet.
+  pub untested_only: bool,
+  /// Filter expression the listed operations match, typed after `/` in the APIs pane.
+  pub operation_query: String,
+  /// Lists only the bookmarked operations.
+  pub favorites_only: bool,
+  /// Requests sent during this session, pending or finished, listed in the queue tab of the responses pane.
+  pub queue: Queue,
+  /// The spec is still being parsed on a background task, see `Home::loading`.
+  pub loading: bool,
+  pub operation_index: OperationIndex,
+  /// Operations listed, by position in the index, along with the filters they were listed with.
+  listed: Mutex<Option<(ListFilter, Arc<[usize]>)>>,
+  /// Variables with their keyring references resolved, read from the keyring again only once the variables change.
+  resolved_variables: Mutex<secrets::ResolvedVariables>,
+}
+
+/// Filters of the operations listed, the exercised and bookmarked operations only when they filter them.
+#[derive(Debug, Default)]
+struct ListFilter {
+  tag: Option<String>,
+  query: String,
+  exercised: Option<BTreeSet<String>>,
+  bookmarked: Option<BTreeSet<String>>,
+}
+
+impl ListFilter {
+  fn of(state: &State) -> Self {
+    Self {
+      tag: state.active_tag_name.clone(),
+      query: state.operation_query.clone(),
+      exercised: state.untested_only.then(|| state.workspace.exercised_operations.clone()),
+      bookmarked: state.favorites_only.then(|| state.workspace.bookmarked_operations.clone()),
+    }
+  }
+
+  fn is_of(&self, state: &State) -> bool {
+    self.tag == state.active_tag_name
+      && self.query == state.operation_query
+      && self.exercised.as_ref() == state.untested_only.then_some(&state.workspace.exercised_operations)
+      && self.bookmarked.as_ref() == state.favorites_only.then_some(&state.workspace.bookmarked_operations)
+  }
+}
+
+/// Spec parsed along with its raw document, sent back by the task loading it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ParsedSpec {
+  pub spec: Spec,
+  pub document: serde_json::Value,
+}
+
+impl ParsedSpec {
+  pub fn parse(openapi_path: &str) -> Result<Self> {
+    // parsed as YAML first, its errors tell where the spec is malformed
+    let document = serde_yaml::from_reader(std::fs::File::open(openapi_path)?)?;
+    let spec = oas3::from_path(openapi_path)?;
+    Ok(Self { spec, document })
+  }
 }
 
 impl State {
+  /// The spec of the path along with its workspace and history, kept next to it.
+  pub fn load(openapi_path: &str) -> Result<Self> {
+    let mut state = Self::unparsed(openapi_path)?;
+    state.set_spec(ParsedSpec::parse(openapi_path)?);
+    Ok(state)
+  }
+
+  /// The workspace and history of the spec of the path, its spec left empty and `loading` until `set_spec`.
+  pub fn unparsed(openapi_path: &str) -> Result<Self> {
+    Ok(Self {
+      openapi_spec: Spec::default(),
+      openapi_document: serde_json::Value::Null,
+      openapi_path: openapi_path.to_string(),
+      active_operation_index: 0,
+      active_tag_name: None,
+      tag_tree: Vec::default(),
+      workspace: Workspace::load(openapi_path)?,
+      exchanges: Vec::default(),
+      queue: Queue::default(),
+      history: history::load(openapi_path)?,
+      config_environments: Vec::default(),
+      base_url_override: None,
+      environment_override: None,
+      dotenv_variables: BTreeMap::default(),
+      workflows: Vec::default(),
+      extracted_variables: BTreeMap::default(),
+      reveal_secrets: false,
+      wrap_lines: false,
+      plugins: Plugins::default(),
+      redactor: Redactor::default(),
+      cache: None,
+      local_times: false,
+      request_preview: false,
+      health: None,
+      recorder: None,
+      player: None,
+      untested_only: false,
+      operation_query: String::default(),
+      favorites_only: false,
+      loading: true,
+      operation_index: OperationIndex::default(),
+      listed: Mutex::default(),
+      resolved_variables: Mutex::default(),
+    })
+  }
+
+  pub fn set_spec(&mut self, parsed: ParsedSpec) {
+    self.tag_tree = tags::tree(&parsed.document);
+    self.operation_index = OperationIndex::new(&parsed.spec);
+    self.listed = Mutex::default();
+    self.openapi_spec = parsed.spec;
+    self.openapi_document = parsed.document;
+    self.loading = false;
+  }
+
+  pub fn operations(&self) -> impl Iterator<Item = (String, String, &Operation)> {
+    let listed = self.listed();
+    (0..listed.len()).filter_map(move |index| self.indexed_operation(listed[index]))
+  }
+
+  /// Positions in the operation index of the operations listed, listed anew only once the filters change.
+  fn listed(&self) -> Arc<[usize]> {
+    let mut listed = self.listed.lock().unwrap();
+    if let Some((_, positions)) = listed.as_ref().filter(|(filter, _)| filter.is_of(self)) {
+      return positions.clone();
+    }
+    let positions: Vec<usize> = match &self.active_tag_name {
+      Some(name) => self.operation_index.tagged(&self.tags_of(name)),
+      None => (0..self.operation_index.len()).collect(),
+    };
+    // a query that does not parse lists the operations as if there was none
+    let filter = self.operation_query.parse::<OperationFilter>().ok();
+    let positions: Arc<[usize]> = positions
+      .into_iter()
+      .filter(|position| {
+        let indexed = self.operation_index.get(*position).unwrap();
+        let operation = &indexed.key;
+        (!self.untested_only || !self.workspace.is_exercised(operation))
+          && (!self.favorites_only || self.workspace.is_bookmarked(operation))
+          && filter.as_ref().is_none_or(|filter| filter.matches(indexed))
+      })
+      .collect();
+    *listed = Some((ListFilter::of(self), positions.clone()));
+    positions
+  }
+
+  /// The operation at the position in the operation index.
+  fn indexed_operation(&self, position: usize) -> Option<(String, String, &Operation)> {
+    let indexed = self.operation_index.get(position)?;
+    let operation = operation_index::operation_of(self.openapi_spec.paths.get(&indexed.path)?, &indexed.method)?;
+    Some((indexed.path.clone(), indexed.method.clone(), operation))
+  }
+
+  /// The tag and the tags grouped under it, or those of the group of the name.
+  pub fn tags_of(&self, name: &str) -> Vec<String> {
+    tags::find(&self.tag_tree, name).map_or_else(|| vec![name.to_string()], |node| node.tags.clone())
+  }
+
+  /// Exercised and total operations, of the tag or group of tags when given.
+  pub fn coverage(&self, tag: Option<&str>) -> (usize, usize) {
+    let positions: Vec<usize> = match tag {
+      Some(name) => self.operation_index.tagged(&self.tags_of(name)),
+      None => (0..self.operation_index.len()).collect(),
+    };
+    let exercised = positions
+      .iter()
+      .filter_map(|position| self.operation_index.get(*position))
+      .filter(|operation| self.workspace.is_exercised(&operation.key))
+      .count();
+    (exercised, positions.len())
+  }
+
+  /// Counts the operation as covered, saving the workspace the first time.
+  pub fn exercise(&mut self, operation: &OperationKey) -> Result<()> {
+    if self.workspace.exercise(operation) {
+      self.workspace.save(&self.openapi_path)?;
+    }
+    Ok(())
+  }
+
   pub fn active_operation(&self) -> Option<(String, String, &Operation)> {
-    if let Some(active_tag) = &self.active_tag_name {
-      if let Some((path, method, operation)) =
-        self.openapi_spec.operations().filter(|item| item.2.tags.contains(active_tag)).nth(self.active_operation_index)
-      {
-        return Some((path, method.to_string(), operation));
+    let position = *self.listed().get(self.active_operation_index)?;
+    self.indexed_operation(position)
+  }
+
+  /// Raw definition of the active operation in the document, for what the parsed spec leaves out.
+  pub fn active_operation_value(&self) -> Option<&serde_json::Value> {
+    let (path, method, _) = self.active_operation()?;
+    self.operation_value(&path, &method)
+  }
+
+  /// Spec the operation comes from, when the spec merges several.
+  pub fn operation_source(&self, path: &str, method: &str) -> Option<&str> {
+    self.operation_value(path, method)?.get(merge::SOURCE_KEY)?.as_str()
+  }
+
+  /// How the operation of the exchange documents the status of its response.
+  pub fn documented_status(&self, exchange: &Exchange) -> Option<DocumentedStatus> {
+    let operation = exchange.operation.as_ref()?;
+    let operation = self.operation_value(&operation.path, &operation.method)?;
+    Some(response_diff::documented_status(operation, &self.openapi_document, exchange.response.status))
+  }
+
+  fn operation_value(&self, path: &str, method: &str) -> Option<&serde_json::Value> {
+    let pointer = format!("/paths/{}/{}", path.replace('~', "~0").replace('/', "~1"), method.to_lowercase());
+    self.openapi_document.pointer(&pointer)
+  }
+
+  /// The operation of the id, or of the method and path, e.g. `POST /pet`.
+  fn find_operation(&self, name: &str) -> Option<(String, String, &Operation)> {
+    self.indexed_operation(self.operation_index.find(name)?)
+  }
+
+  pub fn servers(&self) -> Vec<Server> {
+    server::servers(self.openapi_document.get("servers"))
+  }
+
+  pub fn active_server(&self) -> Option<Server> {
+    let mut servers = self.servers();
+    let index = self.workspace.active_server.min(servers.len().saturating_sub(1));
+    (!servers.is_empty()).then(|| servers.swap_remove(index))
+  }
+
+  /// Servers declared on the operation, or else on its path, which replace the root servers.
+  pub fn operation_server(&self, path: &str, method: &str) -> Option<Server> {
+    let path_item = self.openapi_document.get("paths").and_then(|paths| paths.get(path))?;
+    let operation_servers = server::servers(path_item.get(method.to_lowercase()).and_then(|item| item.get("servers")));
+    let servers =
+      if operation_servers.is_empty() { server::servers(path_item.get("servers")) } else { operation_servers };
+    servers.into_iter().next()
+  }
+
+  /// Base url of an operation, honoring the base url override and operation or path servers.
+  pub fn operation_base_url(&self, path: &str, method: &str) -> String {
+    match self.operation_server(path, method) {
+      Some(server) if self.base_url_override.is_none() => server.expand(&self.workspace.server_variables),
+      _ => self.base_url(),
+    }
+  }
+
+  /// Base url of the selected operation, or of the active server when none is.
+  pub fn active_base_url(&self) -> String {
+    match self.active_operation() {
+      Some((path, method, _)) => self.operation_base_url(&path, &method),
+      None => self.base_url(),
+    }
+  }
+
+  pub fn base_url(&self) -> String {
+    if let Some(base_url) = &self.base_url_override {
+      base_url.clone()
+    } else if let Some(server) = self.active_server() {
+      server.expand(&self.workspace.server_variables)
+    } else {
+      String::from("http://localhost")
+    }
+  }
+
+  /// The request of the active operation, filled with its draft.
+  pub fn active_request(&self) -> Option<HttpRequest> {
+    self
+      .active_operation()
+      .map(|(path, method, operation)| self.operation_request(&path, &method, operation, self.active_draft()))
+  }
+
+  /// The request of the operation, filled with the draft.
+  fn operation_request(
+    &self,
+    path: &str,
+    method: &str,
+    operation: &Operation,
+    draft: Option<&RequestDraft>,
+  ) -> HttpRequest {
+    let mut request =
+      HttpRequest::from_operation(&self.openapi_spec, &self.operation_base_url(path, method), path, method, operation);
+    if request.body.as_deref().unwrap_or_default().is_empty() {
+      if let Some(body) = self.xml_example(path, method, request.header("Content-Type")) {
+        request.body = Some(body);
+      }
+    }
+    // parameters left out of the draft start from the values of the spec
+    let mut draft = draft.cloned().unwrap_or_default();
+    for ((location, name), value) in parameter::seeds(&self.openapi_document, path, method) {
+      if draft.parameter(&location, &name).is_none() {
+        draft.set_parameter(&location, &name, value);
+      }
+    }
+    draft.apply(&mut request, &parameter::styles(&self.openapi_document, path, method));
+    request
+  }
+
+  /// The request of `openapi-tui call` for the operation of the id, or of the method and path, sent as from the TUI:
+  /// its draft with the given parameters and body, default headers and variables included.
+  pub fn call_request(
+    &self,
+    name: &str,
+    parameters: &[(String, String)],
+    body: Option<String>,
+  ) -> Option<(HttpRequest, OperationKey)> {
+    let (path, method, operation) = self.find_operation(name)?;
+    let key = OperationKey::new(&method, &path);
+    let mut draft = self.draft_of(&path, &method, operation).cloned().unwrap_or_default();
+    for (name, value) in parameters {
+      draft.set_parameter(&self.parameter_location(operation, name), name, value.clone());
+    }
+    if body.is_some() {
+      draft.body = body;
+    }
+    let request = self.operation_request(&path, &method, operation, Some(&draft));
+    Some((self.resolve_request(&request, Some(&key)), key))
+  }
+
+  /// Where the parameter of the operation goes, in the query when the operation has none of that name.
+  fn parameter_location(&self, operation: &Operation, name: &str) -> String {
+    operation
+      .parameters
+      .iter()
+      .filter_map(|parameter| parameter.resolve(&self.openapi_spec).ok())
+      .find(|parameter| parameter.name == name)
+      .map_or(String::from("query"), |parameter| parameter.location)
+  }
+
+  /// Value the parameter of the active operation starts from when the draft has none, see `parameter::seeds`.
+  pub fn parameter_seed(&self, location: &str, name: &str) -> Option<String> {
+    let (path, method, _) = self.active_operation()?;
+    let mut seeds = parameter::seeds(&self.openapi_document, &path, &method);
+    seeds.remove(&(location.to_string(), name.to_string()))
+  }
+
+  /// Example body of the operation for an XML media type, generated from the schema of its request body.
+  fn xml_example(&self, path: &str, method: &str, media_type: Option<&str>) -> Option<String> {
+    let media_type = media_type.filter(|media_type| xml::is_xml(media_type))?;
+    let request_body = self.operation_value(path, method)?.get("requestBody")?;
+    let media = mock::resolve(request_body, &self.openapi_document).get("content")?.get(media_type)?;
+    Some(xml::example(media.get("schema")?, &self.openapi_document))
+  }
+
+  /// Key of the active operation draft, its operation id or its method and path when it has none.
+  pub fn draft_key(&self) -> Option<String> {
+    self.active_operation().map(|(path, method, operation)| draft_key(&path, &method, operation))
+  }
+
+  pub fn active_draft(&self) -> Option<&RequestDraft> {
+    self.draft_key().and_then(|key| self.workspace.drafts.get(&key))
+  }
+
+  /// Draft of the operation, the one the request pane edits when it is active.
+  fn draft_of(&self, path: &str, method: &str, operation: &Operation) -> Option<&RequestDraft> {
+    self.workspace.drafts.get(&draft_key(path, method, operation))
+  }
+
+  /// Config and workspace environments, variables of the workspace win when both define an environment.
+  pub fn environments(&self) -> Vec<Environment> {
+    let mut environments = self.config_environments.clone();
+    for environment in self.workspace.environments.iter() {
+      match environments.iter_mut().find(|existing| existing.name == environment.name) {
+        Some(existing) => {
+          existing.variables.extend(environment.variables.clone());
+          existing.headers.extend(environment.headers.clone());
+          if environment.signing.is_some() {
+            existing.signing.clone_from(&environment.signing);
+          }
+          if let Some(network) = &environment.network {
+            existing.network.get_or_insert_with(Network::default).merge(network);
+          }
+        },
+        None => environments.push(environment.clone()),
+      }
+    }
+    environments
+  }
+
+  /// Name of the active environment, the one set on the command line if any.
+  pub fn active_environment_name(&self) -> Option<&str> {
+    self.environment_override.as_deref().or(self.workspace.active_environment.as_deref())
+  }
+
+  pub fn active_environment(&self) -> Option<Environment> {
+    let active = self.active_environment_name()?;
+    self.environments().into_iter().find(|environment| environment.name == active)
+  }
+
+  /// Variables available for substitution, the active environment overrides the `.env` variables and
+  /// keyring references are replaced with the stored secrets.
+  pub fn variables(&self) -> BTreeMap<String, String> {
+    self.resolved_variables.lock().unwrap().resolve(self.unresolved_variables(), secrets::load).clone()
+  }
+
+  fn unresolved_variables(&self) -> BTreeMap<String, String> {
+    let mut variables = self.dotenv_variables.clone();
+    if let Some(environment) = self.active_environment() {
+      variables.extend(environment.variables);
+    }
+    variables.extend(self.extracted_variables.clone());
+    variables
+  }
+
+  /// Runs the extraction rules of the exchange operation against its response.
+  pub fn extract_variables(&mut self, exchange: &Exchange) {
+    let Some(operation) = &exchange.operation else {
+      return;
+    };
+    for extraction in self.workspace.extractions.iter().filter(|extraction| &extraction.operation == operation) {
+      match extract::extract(&extraction.source, &exchange.response) {
+        Some(value) => {
+          self.extracted_variables.insert(extraction.variable.clone(), value);
+        },
+        None => log::warn!("`{}` not found in the response of {operation}", extraction.source),
+      }
+    }
+  }
+
+  /// Values of the keyring sourced variables and of the variables named like credentials, masked wherever
+  /// they show up until secrets are revealed.
+  pub fn secret_values(&self) -> Vec<String> {
+    self.secret_variables().into_values().collect()
+  }
+
+  /// Keyring sourced variables and variables named like credentials, with their resolved values.
+  pub fn secret_variables(&self) -> BTreeMap<String, String> {
+    let unresolved = self.unresolved_variables();
+    let secret_names: Vec<String> = unresolved
+      .iter()
+      .filter(|(name, value)| secrets::is_keyring_reference(value) || secrets::is_secret_name(name))
+      .map(|(name, _)| name.clone())
+      .collect();
+    let mut resolved_variables = self.resolved_variables.lock().unwrap();
+    let resolved = resolved_variables.resolve(unresolved, secrets::load);
+    secret_names
+      .into_iter()
+      .filter_map(|name| {
+        let value = resolved.get(&name)?.clone();
+        (!value.is_empty() && !secrets::is_keyring_reference(&value)).then_some((name, value))
+      })
+      .collect()
+  }
+
+  /// The request as it should be displayed or exported, masked unless secrets are revealed.
+  pub fn display_request(&self, request: &HttpRequest) -> HttpRequest {
+    match self.reveal_secrets {
+      true => request.clone(),
+      false => secrets::mask_request(request, &self.secret_values()),
+    }
+  }
+
+  /// The request of the active operation as it will be sent, default headers and variables applied, masked unless
+  /// secrets are revealed.
+  pub fn preview_request(&self) -> Option<HttpRequest> {
+    let request = self.active_request()?;
+    Some(self.display_request(&self.resolve_request(&request, self.active_operation_key().as_ref())))
+  }
+
+  /// The request of the exchange as it was sent, taken from the session, or else resolved from the history, which
+  /// holds it with its secrets referenced and its redacted values replaced. `Err` tells why it cannot be sent again,
+  /// since sending a redacted value, e.g. `Authorization: [redacted]`, would only fail.
+  pub fn sent_request(&self, exchange: &Exchange) -> std::result::Result<HttpRequest, String> {
+    if let Some(sent) = self.exchanges.iter().rev().find(|sent| sent.started_at == exchange.started_at) {
+      return Ok(sent.request.clone());
+    }
+    let redacted = serde_json::to_string(&exchange.request).is_ok_and(|request| request.contains(redaction::REDACTED));
+    match redacted {
+      true => {
+        Err(format!(
+          "{} {} was sent in an earlier session and values of it were redacted from the history, send it from the \
+           request pane instead",
+          exchange.request.method, exchange.request.path
+        ))
+      },
+      false => Ok(self.resolve_request(&exchange.request, None)),
+    }
+  }
+
+  /// The request last sent for the selected operation, see `sent_request`, `None` when none was sent yet.
+  pub fn last_request(&self) -> Option<std::result::Result<HttpRequest, String>> {
+    let operation = self.active_operation_key()?;
+    let last = self.history.iter().rev().find(|exchange| exchange.operation.as_ref() == Some(&operation))?;
+    Some(self.sent_request(last))
+  }
+
+  /// The exchange of the history sent for the same operation, or the same request when it has none, before the given
+  /// one.
+  pub fn previous_exchange(&self, exchange: &Exchange) -> Option<&Exchange> {
+    let same = |other: &Exchange| {
+      match &exchange.operation {
+        Some(operation) => other.operation.as_ref() == Some(operation),
+        None => other.request.method == exchange.request.method && other.request.path == exchange.request.path,
+      }
+    };
+    self.history.iter().rev().find(|other| other.started_at < exchange.started_at && same(other))
+  }
+
+  pub fn display_exchange(&self, exchange: &Exchange) -> Exchange {
+    match self.reveal_secrets {
+      true => exchange.clone(),
+      false => secrets::mask_exchange(exchange, &self.secret_values()),
+    }
+  }
+
+  pub fn display_environments(&self) -> Vec<Environment> {
+    let mut environments = self.environments();
+    if !self.reveal_secrets {
+      for environment in environments.iter_mut() {
+        environment.variables = secrets::mask_variables(&environment.variables);
+      }
+    }
+    environments
+  }
+
+  /// Default headers of the active environment with the value sent for the operation, `None` when the
+  /// operation removes the header.
+  pub fn default_headers(&self, operation: &OperationKey) -> Vec<(String, Option<String>)> {
+    let Some(environment) = self.active_environment() else {
+      return vec![];
+    };
+    environment
+      .headers
+      .into_iter()
+      .map(|(name, value)| {
+        let value = match self.workspace.header_override(operation, &name) {
+          Some(header_override) => header_override.value.clone(),
+          None => Some(value),
+        };
+        (name, value)
+      })
+      .collect()
+  }
+
+  /// Adds the default headers of the active environment, as overridden for the operation, then substitutes
+  /// `{{var}}` references with the variables of the active environment and `.env` files.
+  pub fn resolve_request(&self, request: &HttpRequest, operation: Option<&OperationKey>) -> HttpRequest {
+    let mut request = request.clone();
+    self.apply_default_headers(&mut request, operation);
+    request.substitute(&self.variables())
+  }
+
+  fn apply_default_headers(&self, request: &mut HttpRequest, operation: Option<&OperationKey>) {
+    if let Some(operation) = operation {
+      for (name, value) in self.default_headers(operation) {
+        if let Some(value) = value.filter(|_| request.header(&name).is_none()) {
+          request.set_header(&name, value);
+        }
       }
-    } else if let Some((path, method, operation)) = self.openapi_spec.operations().nth(self.active_operation_index) {
-      return Some((path, method.to_string(), operation));
+    } else if let Some(environment) = self.active_environment() {
+      environment.apply_headers(request);
+    }
+  }
+
+  /// The request of a workflow step with its parameters, body and default headers, variables are left for the
+  /// runner to substitute as the previous steps extract them.
+  pub fn workflow_request(&self, step: &Step) -> Option<(HttpRequest, OperationKey)> {
+    let (path, method, operation) = self.find_operation(&step.operation)?;
+    let mut request = HttpRequest::from_operation(
+      &self.openapi_spec,
+      &self.operation_base_url(&path, &method),
+      &path,
+      &method,
+      operation,
+    );
+    let mut draft = RequestDraft { body: step.body_text(), ..RequestDraft::default() };
+    for (name, value) in step.parameters.iter() {
+      draft.set_parameter(&self.parameter_location(operation, name), name, value.clone());
+    }
+    draft.apply(&mut request, &parameter::styles(&self.openapi_document, &path, &method));
+    let operation = OperationKey::new(&method, &path);
+    self.apply_default_headers(&mut request, Some(&operation));
+    Some((request, operation))
+  }
+
+  /// The request of the operation with only the inputs of the generated draft, default headers and variables
+  /// included.
+  fn generated_request(&self, path: &str, method: &str, operation: &Operation, draft: &RequestDraft) -> HttpRequest {
+    let mut request =
+      HttpRequest::from_operation(&self.openapi_spec, &self.operation_base_url(path, method), path, method, operation);
+    // Generated drafts carry every input they send, leaving one out is a fuzz case of its own.
+    request.query.clear();
+    request.headers.retain(|(_, value)| !value.is_empty());
+    request.body = None;
+    draft.apply(&mut request, &parameter::styles(&self.openapi_document, path, method));
+    self.resolve_request(&request, Some(&OperationKey::new(method, path)))
+  }
+
+  /// The fuzz cases of the active operation with their requests.
+  pub fn fuzz_requests(&self) -> Option<(OperationKey, Vec<(Case, HttpRequest)>)> {
+    let (path, method, operation) = self.active_operation()?;
+    let requests = fuzz::cases(&self.openapi_document, &path, &method)
+      .into_iter()
+      .map(|case| {
+        let request = self.generated_request(&path, &method, operation, &case.draft);
+        (case, request)
+      })
+      .collect();
+    Some((OperationKey::new(&method, &path), requests))
+  }
+
+  /// Requests of the listed `GET` operations, of the active tag when one is selected, filled with examples.
+  pub fn smoke_requests(&self) -> Vec<(OperationKey, HttpRequest)> {
+    self
+      .operations()
+      .filter(|(_, method, _)| method.eq_ignore_ascii_case("get"))
+      .map(|(path, method, operation)| {
+        let draft = fuzz::example_draft(&self.openapi_document, &path, &method);
+        (OperationKey::new(&method, &path), self.generated_request(&path, &method, operation, &draft))
+      })
+      .collect()
+  }
+
+  /// Steps of the workflow along with their requests and hooks, to be run away from the state.
+  pub fn workflow_steps(&self, workflow: &Workflow) -> Vec<PreparedStep> {
+    workflow
+      .steps
+      .iter()
+      .map(|step| {
+        let request = self.workflow_request(step);
+        let hooks = self.hooks(request.as_ref().map(|(_, operation)| operation), None);
+        (step.clone(), request, hooks)
+      })
+      .collect()
+  }
+
+  /// The saved request as sent with the active environment, `.env` variables and keyring secrets included.
+  pub fn resolve_saved(&self, saved: &SavedRequest) -> HttpRequest {
+    let environment = Environment {
+      name: self.active_environment_name().unwrap_or_default().to_string(),
+      variables: self.variables(),
+      headers: self.active_environment().map(|environment| environment.headers).unwrap_or_default(),
+      signing: None,
+      network: None,
+    };
+    saved.resolve(Some(&environment))
+  }
+
+  /// Saves the active request, draft and auth included, under `folder/name`, replacing a saved request with the
+  /// same folder and name but keeping its assertions.
+  pub fn save_active_request(&mut self, path: &str) -> Result<()> {
+    let Some((operation_path, method, _)) = self.active_operation() else {
+      return Ok(());
+    };
+    let auth = operation_auth_scheme(&self.openapi_document, &operation_path, &method)
+      .and_then(|(_, scheme)| scheme_auth(&scheme));
+    let Some(request) = self.active_request() else {
+      return Ok(());
+    };
+    let (folder, name) = match path.rsplit_once('/') {
+      Some((folder, name)) => (Some(folder.to_string()), name.to_string()),
+      None => (None, path.to_string()),
+    };
+    let saved =
+      SavedRequest { name, operation: self.active_operation_key(), request, folder, auth, assertions: Vec::default() };
+    let saved_requests = &mut self.workspace.saved_requests;
+    match saved_requests.iter_mut().find(|existing| existing.folder == saved.folder && existing.name == saved.name) {
+      Some(existing) => *existing = SavedRequest { assertions: std::mem::take(&mut existing.assertions), ..saved },
+      None => saved_requests.push(saved),
+    }
+    self.workspace.save(&self.openapi_path)
+  }
+
+  /// Adds the exchange to the session and to the persisted history.
+  pub fn record(&mut self, exchange: Exchange) -> Result<()> {
+    let mut entry = exchange.clone();
+    entry.request = secrets::reference_secrets(&exchange.request, &self.secret_variables());
+    let entry = self.redactor.exchange(&entry);
+    if let Some(recorder) = self.recorder.as_mut() {
+      recorder.record(entry.request.clone(), entry.response.clone())?;
+    }
+    if let Some(operation) = &exchange.operation {
+      self.exercise(operation)?;
     }
-    None
+    self.exchanges.push(exchange);
+    self.history.push(entry);
+    history::save_in_background(&self.openapi_path, &self.history)
+  }
+
+  /// Scripts, plugins, assertions and secrets to mask in the logs when sending a request of the operation, sent from the saved request when
+  /// given.
+  pub fn hooks(&self, operation: Option<&OperationKey>, saved: Option<&SavedRequest>) -> Hooks {
+    let auth_schemes = operation
+      .map(|operation| security::operation_security(&self.openapi_document, &operation.path, &operation.method))
+      .unwrap_or_default()
+      .into_iter()
+      .flat_map(|requirement| requirement.into_keys())
+      .collect();
+    let environment = self.active_environment();
+    Hooks {
+      scripts: self.workspace.request_scripts(operation, saved.and_then(|saved| saved.folder.as_deref())),
+      plugins: self.plugins.clone(),
+      auth_schemes,
+      player: self.player.clone(),
+      assertions: saved.map(|saved| saved.assertions.clone()).unwrap_or_default(),
+      secret_values: self.secret_values(),
+      redactor: self.redactor.clone(),
+      cache: self.cache.clone(),
+      signing: environment.as_ref().and_then(|environment| environment.signing.clone()),
+      network: environment.and_then(|environment| environment.network),
+    }
+  }
+
+  pub fn active_operation_key(&self) -> Option<OperationKey> {
+    self.active_operation().map(|(path, method, _)| OperationKey::new(&method, &path))
   }
 
   pub fn operations_len(&self) -> usize {
-    if let Some(active_tag) = &self.active_tag_name {
-      self.openapi_spec.operations().filter(|item| item.2.tags.contains(active_tag)).count()
+    self.listed().len()
+  }
+
+  fn http_file_entry(&self, path: &str, method: &str, operation: &Operation) -> HttpFileEntry {
+    let auth_header = operation_auth_scheme(&self.openapi_document, path, method)
+      .and_then(|(_, scheme)| scheme.header(format!("{{{{{AUTH_VARIABLE}}}}}").as_str()));
+    HttpFileEntry {
+      name: operation.operation_id.clone().unwrap_or(format!("{method} {path}")),
+      request: self.display_request(&self.operation_request(
+        path,
+        method,
+        operation,
+        self.draft_of(path, method, operation),
+      )),
+      auth_header,
+    }
+  }
+
+  /// Writes the request of the active operation, or of every listed operation when `collection` is set, as a `.http`
+  /// file, each filled with its draft as sent.
+  pub fn export_http(&self, collection: bool) -> Result<Option<PathBuf>> {
+    let (name, entries) = if collection {
+      let name = Path::new(&self.openapi_path).file_stem().unwrap_or_default().to_string_lossy().to_string();
+      let name = self.active_tag_name.as_ref().map_or(name.clone(), |tag| format!("{name}-{tag}"));
+      (
+        name,
+        self.operations().map(|(path, method, operation)| self.http_file_entry(&path, &method, operation)).collect(),
+      )
+    } else if let Some((path, method, operation)) = self.active_operation() {
+      let entry = self.http_file_entry(&path, &method, operation);
+      (entry.name.clone(), vec![entry])
+    } else {
+      return Ok(None);
+    };
+    let export_path = export_path(&name, "http");
+    std::fs::write(&export_path, export::http::render(&entries))?;
+    Ok(Some(export_path))
+  }
+
+  fn postman_entry(&self, path: &str, method: &str, operation: &Operation) -> PostmanEntry {
+    PostmanEntry {
+      name: operation.operation_id.clone().unwrap_or(format!("{method} {path}")),
+      folder: operation.tags.first().cloned(),
+      request: self.display_request(&self.operation_request(
+        path,
+        method,
+        operation,
+        self.draft_of(path, method, operation),
+      )),
+      auth: operation_auth_scheme(&self.openapi_document, path, method).and_then(|(_, scheme)| scheme_auth(&scheme)),
+    }
+  }
+
+  /// Writes every listed operation, filled with its draft, or only the saved requests when `saved` is set, as a
+  /// Postman collection along with a Postman environment file per workspace environment.
+  pub fn export_postman(&self, saved: bool) -> Result<Vec<PathBuf>> {
+    let name = Path::new(&self.openapi_path).file_stem().unwrap_or_default().to_string_lossy().to_string();
+    let entries: Vec<PostmanEntry> = if saved {
+      self
+        .workspace
+        .saved_requests
+        .iter()
+        .map(|saved| {
+          PostmanEntry {
+            name: saved.name.clone(),
+            folder: saved.folder.clone(),
+            request: self.display_request(&saved.request),
+            auth: saved
+              .auth
+              .as_ref()
+              .map(|auth| if self.reveal_secrets { auth.clone() } else { secrets::mask_auth(auth) }),
+          }
+        })
+        .collect()
     } else {
-      self.openapi_spec.operations().count()
+      self.operations().map(|(path, method, operation)| self.postman_entry(&path, &method, operation)).collect()
+    };
+    let mut paths = vec![];
+    let collection_path = export_path(&name, "postman_collection.json");
+    std::fs::write(
+      &collection_path,
+      serde_json::to_string_pretty(&export::postman::render_collection(&name, &entries))?,
+    )?;
+    paths.push(collection_path);
+    for mut environment in self.workspace.environments.iter().cloned() {
+      if !self.reveal_secrets {
+        environment.variables = secrets::mask_variables(&environment.variables);
+      }
+      let environment_path = export_path(&environment.name, "postman_environment.json");
+      std::fs::write(
+        &environment_path,
+        serde_json::to_string_pretty(&export::postman::render_environment(&environment))?,
+      )?;
+      paths.push(environment_path);
+    }
+    Ok(paths)
+  }
+
+  /// Writes the requests sent during this session as a HAR file, `None` when nothing was sent yet.
+  pub fn export_har(&self) -> Result<Option<PathBuf>> {
+    if self.exchanges.is_empty() {
+      return Ok(None);
     }
+    let name = Path::new(&self.openapi_path).file_stem().unwrap_or_default().to_string_lossy().to_string();
+    let export_path = export_path(&name, "har");
+    let exchanges: Vec<Exchange> =
+      self.exchanges.iter().map(|exchange| self.redactor.exchange(&self.display_exchange(exchange))).collect();
+    std::fs::write(&export_path, serde_json::to_string_pretty(&export::har::render(&exchanges))?)?;
+    Ok(Some(export_path))
+  }
+
+  /// Writes the schema, with the component schemas it references, as a standalone JSON Schema document.
+  pub fn export_schema(&self, name: &str, schema: &serde_json::Value) -> Result<PathBuf> {
+    let export_path = export_path(name, "schema.json");
+    let bundled = export::json_schema::bundle(name, schema, &self.openapi_document);
+    std::fs::write(&export_path, serde_json::to_string_pretty(&bundled)?)?;
+    Ok(export_path)
   }
 }
 
+const PANE_TITLES: [&str; 8] =
+  ["APIs", "Tags", "Collections", "History", "Components", "Address", "Request", "Response"];
+/// Panes whose lists take counts, e.g. `15j`, digits select the tabs of the others.
+const COUNTED_PANES: [usize; 4] = [0, 1, 2, 3];
+
 #[derive(Default)]
 pub struct Home {
   command_tx: Option<UnboundedSender<Action>>,
@@ -54,150 +995,2632 @@ pub struct Home {
   #[allow(dead_code)]
   state: Arc<RwLock<State>>,
   fullscreen_pane_index: Option<usize>,
+  popup: Option<Box<dyn Popup>>,
+  clipboard: Option<arboard::Clipboard>,
+  /// Stops the running fuzz cases or smoke tests once their popup is closed.
+  cases_cancellation: Option<CancellationToken>,
+  /// Areas the panes were last drawn in, empty for the panes hidden by a fullscreen one.
+  pane_areas: Vec<Rect>,
+  /// Time and pane of the last click, a second one on the same pane soon after toggling it fullscreen.
+  last_click: Option<(Instant, usize)>,
+  toasts: Toasts,
+  /// Requests sent whose response was not received yet.
+  /// Count or key sequence being typed.
+  keys: KeyResolver,
+  /// Files to import once the spec, loading, is parsed.
+  pending_imports: Vec<String>,
+  /// Seconds between checks of the spec given by url for changes, see `watch_remote`.
+  refresh_interval: Option<Duration>,
+  /// Specs given by url are not fetched again, with `--offline`.
+  offline: bool,
+  /// Stops checking the spec for changes once the page is dropped.
+  refresh_guard: Option<DropGuard>,
+  /// The spec given by url changed on the server since loaded.
+  spec_changed: bool,
+  /// Host overrides of `--resolve`, ahead of those of the config file.
+  host_overrides: Vec<HostOverride>,
+  /// Redirects followed with `--max-redirects`, over those of the config file.
+  max_redirects: Option<usize>,
+  /// Operation, an id or method and path, and tag of `--operation` and `--tag`, selected once the spec is parsed.
+  start_operation: Option<String>,
+  start_tag: Option<String>,
+  /// Base url last probed and when, see `health::HealthConfig::due`.
+  health_probed_at: Option<(String, Instant)>,
 }
 
 impl Home {
   pub fn new(openapi_path: String) -> Result<Self> {
-    let openapi_spec = oas3::from_path(openapi_path.clone())?;
-    let state =
-      Arc::new(RwLock::new(State { openapi_spec, openapi_path, active_operation_index: 0, active_tag_name: None }));
-    let focused_border_style = Style::default().fg(Color::LightGreen);
+    Ok(Self::with_state(State::load(&openapi_path)?))
+  }
 
-    Ok(Self {
+  /// The page shown while its spec is parsed on a background task, started once it is given the action sender and
+  /// sending `Action::SpecLoaded` when done.
+  pub fn loading(openapi_path: String) -> Result<Self> {
+    Ok(Self::with_state(State::unparsed(&openapi_path)?))
+  }
+
+  fn with_state(state: State) -> Self {
+    let state = Arc::new(RwLock::new(state));
+    Self {
       command_tx: None,
       config: Config::default(),
-      panes: vec![
-        Box::new(ApisPane::new(state.clone(), true, focused_border_style)),
-        Box::new(TagsPane::new(state.clone(), false, focused_border_style)),
-        Box::new(AddressPane::new(state.clone(), false, focused_border_style)),
-        Box::new(RequestPane::new(state.clone(), false, focused_border_style)),
-        Box::new(ResponsePane::new(state.clone(), false, focused_border_style)),
-      ],
+      panes: panes(&state),
       focused_pane_index: 0,
       state,
       fullscreen_pane_index: None,
-    })
+      popup: None,
+      clipboard: None,
+      cases_cancellation: None,
+      pane_areas: Vec::default(),
+      last_click: None,
+      toasts: Toasts::default(),
+      keys: KeyResolver::default(),
+      pending_imports: Vec::default(),
+      refresh_interval: None,
+      offline: false,
+      refresh_guard: None,
+      spec_changed: false,
+      host_overrides: Vec::default(),
+      max_redirects: None,
+      start_operation: None,
+      start_tag: None,
+      health_probed_at: None,
+    }
   }
-}
 
-impl Page for Home {
-  fn init(&mut self) -> Result<()> {
-    for pane in self.panes.iter_mut() {
-      pane.init()?;
+  /// Starts on the operation with the request pane focused, or on the operations of the tag, once the spec is parsed.
+  pub fn start_at(&mut self, operation: Option<String>, tag: Option<String>) -> Result<()> {
+    self.start_operation = operation;
+    self.start_tag = tag;
+    let loading = self.state.read().unwrap().loading;
+    match loading {
+      true => Ok(()),
+      false => self.go_to_start(),
     }
-    Ok(())
   }
 
-  fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
-    self.command_tx = Some(tx);
+  /// Selects the operation and tag to start on over the restored session, telling which of them the spec lacks.
+  fn go_to_start(&mut self) -> Result<()> {
+    let (operation, tag) = (self.start_operation.take(), self.start_tag.take());
+    if operation.is_none() && tag.is_none() {
+      return Ok(());
+    }
+    let (found, unknown) = {
+      let mut state = self.state.write().unwrap();
+      let mut unknown = vec![];
+      state.untested_only = false;
+      state.favorites_only = false;
+      state.active_tag_name = None;
+      if let Some(tag) = tag {
+        match state.operation_index.tagged(&state.tags_of(&tag)).is_empty() {
+          true => unknown.push(format!("No operation is tagged `{tag}`.")),
+          false => state.active_tag_name = Some(tag),
+        }
+      }
+      state.active_operation_index = 0;
+      let key = operation.as_ref().and_then(|name| {
+        let key = state.find_operation(name).map(|(path, method, _)| OperationKey::new(&method, &path));
+        if key.is_none() {
+          unknown
+            .push(format!("No operation `{name}`, expected an operationId or a method and path, e.g. `GET /pets`."));
+        }
+        key
+      });
+      if let Some(key) = &key {
+        // an operation the tag does not list is selected among all of them
+        let position =
+          |state: &State| state.operations().position(|(path, method, _)| &OperationKey::new(&method, &path) == key);
+        if position(&state).is_none() {
+          state.active_tag_name = None;
+        }
+        state.active_operation_index = position(&state).unwrap_or_default();
+      }
+      (key.is_some(), unknown)
+    };
+    // the request pane once the operation is found, the APIs pane listing the operations of the tag otherwise
+    let pane = if found { 6 } else { 0 };
+    self.focus_pane(pane)?;
+    self.fullscreen_pane_index = (!layout::panes(&self.layout()).contains(&pane)).then_some(pane);
+    if !unknown.is_empty() && self.popup.is_none() {
+      self.popup = Some(Box::new(MessagePopup::new("Start", unknown.join("\n"))));
+    }
+    if let Some(command_tx) = &self.command_tx {
+      command_tx.send(Action::Update)?;
+    }
     Ok(())
   }
 
-  fn register_config_handler(&mut self, config: Config) -> Result<()> {
-    self.config = config;
-    Ok(())
+  /// Checks the spec, when given by url, for changes every `interval` seconds once the page runs, unless offline.
+  pub fn watch_remote(&mut self, interval: Option<u64>, offline: bool) {
+    self.refresh_interval = interval.filter(|_| !offline).map(|seconds| Duration::from_secs(seconds.max(1)));
+    self.offline = offline;
   }
 
-  fn update(&mut self, action: Action) -> Result<Option<Action>> {
-    match action {
-      Action::Tick => {},
-      Action::FocusNext => {
-        let next_index = self.focused_pane_index.saturating_add(1) % self.panes.len();
-        if let Some(pane) = self.panes.get_mut(self.focused_pane_index) {
-          pane.unfocus()?;
-        }
-        self.focused_pane_index = next_index;
-        if let Some(pane) = self.panes.get_mut(self.focused_pane_index) {
-          pane.focus()?;
-        }
-      },
-      Action::FocusPrev => {
-        let prev_index = self.focused_pane_index.saturating_add(self.panes.len() - 1) % self.panes.len();
-        if let Some(pane) = self.panes.get_mut(self.focused_pane_index) {
-          pane.unfocus()?;
-        }
-        self.focused_pane_index = prev_index;
-        if let Some(pane) = self.panes.get_mut(self.focused_pane_index) {
-          pane.focus()?;
-        }
-      },
-      Action::Update => {
-        for pane in self.panes.iter_mut() {
-          pane.update(action.clone())?;
+  /// Fetches the spec again every interval, telling with `Action::SpecChanged` when it changed.
+  fn poll_spec(&mut self, command_tx: UnboundedSender<Action>, interval: Duration) {
+    let openapi_path = self.state.read().unwrap().openapi_path.clone();
+    if remote::source_url(&openapi_path).is_none() {
+      return;
+    }
+    let cancellation = CancellationToken::new();
+    self.refresh_guard = Some(cancellation.clone().drop_guard());
+    tokio::spawn(async move {
+      loop {
+        tokio::select! {
+          _ = cancellation.cancelled() => break,
+          _ = tokio::time::sleep(interval) => {},
         }
-      },
-      Action::ToggleFullScreen => {
-        self.fullscreen_pane_index = self.fullscreen_pane_index.map_or(Some(self.focused_pane_index), |_| None);
-      },
-      _ => {
-        if let Some(pane) = self.panes.get_mut(self.focused_pane_index) {
-          return pane.update(action);
+        match remote::refresh(&openapi_path).await {
+          Ok(true) => {
+            if command_tx.send(Action::SpecChanged).is_err() {
+              break;
+            }
+          },
+          Ok(false) => {},
+          Err(error) => log::debug!("Failed to check {openapi_path} for changes: {error:?}"),
         }
-      },
+      }
+    });
+  }
+
+  /// The spec parsed in the background, the panes are created anew as they read their schemas when created.
+  fn spec_loaded(&mut self, parsed: ParsedSpec) -> Result<()> {
+    self.state.write().unwrap().set_spec(parsed);
+    self.panes = panes(&self.state);
+    self.focused_pane_index = 0;
+    for pane in self.panes.iter_mut() {
+      pane.init()?;
     }
-    Ok(None)
+    let imports = std::mem::take(&mut self.pending_imports);
+    if let Err(error) = self.import(&imports) {
+      self.popup = Some(Box::new(MessagePopup::new("Import Failed", error.to_string())));
+    }
+    // duplicate operations break code generators, they are listed as soon as the spec loads
+    let conflicts = lint::conflicts(&self.state.read().unwrap().openapi_document);
+    if self.popup.is_none() && !conflicts.is_empty() {
+      self.popup = Some(Box::new(LintPopup::new(conflicts)));
+    }
+    self.restore_session()?;
+    self.go_to_start()
   }
 
-  fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
-    let response = match key.code {
-      KeyCode::Right | KeyCode::Char('l') | KeyCode::Char('L') => EventResponse::Stop(Action::FocusNext),
-      KeyCode::Left | KeyCode::Char('h') | KeyCode::Char('H') => EventResponse::Stop(Action::FocusPrev),
-      KeyCode::Down | KeyCode::Char('j') | KeyCode::Char('J') => EventResponse::Stop(Action::Down),
-      KeyCode::Up | KeyCode::Char('k') | KeyCode::Char('K') => EventResponse::Stop(Action::Up),
-      KeyCode::Char('g') | KeyCode::Char('G') => EventResponse::Stop(Action::Go),
-      KeyCode::Backspace | KeyCode::Char('b') | KeyCode::Char('B') => EventResponse::Stop(Action::Back),
-      KeyCode::Enter => EventResponse::Stop(Action::Submit),
-      KeyCode::Char('f') | KeyCode::Char('F') => EventResponse::Stop(Action::ToggleFullScreen),
-      KeyCode::Char(c) if ('1'..='9').contains(&c) => EventResponse::Stop(Action::Tab(c.to_digit(10).unwrap_or(0) - 1)),
-      _ => {
-        return Ok(None);
-      },
+  /// Lists the APIs of the configured registries on a background task, picked in the popup once listed.
+  fn show_registry(&mut self) {
+    let registries = self.config.registries.clone();
+    if registries.is_empty() {
+      self.popup = Some(Box::new(MessagePopup::new(
+        "Registries",
+        "No registries are configured, add them under `registries` in the config file.",
+      )));
+      return;
+    }
+    self.popup = Some(Box::new(RegistryPopup::new()));
+    let Some(command_tx) = self.command_tx.clone() else {
+      return;
     };
-    Ok(Some(response))
+    tokio::spawn(async move {
+      let (apis, errors) = registry::list(&registries).await;
+      command_tx.send(Action::RegistryListed(apis, errors)).ok();
+    });
   }
 
-  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+  /// Parses the spec on a blocking task, sending it back or the error and its causes. When `refresh`, the spec given by
+  /// url is fetched again first.
+  fn load_spec(&self, command_tx: UnboundedSender<Action>, refresh: bool) {
+    let openapi_path = self.state.read().unwrap().openapi_path.clone();
+    tokio::spawn(async move {
+      if refresh {
+        if let Err(error) = remote::refresh(&openapi_path).await {
+          log::warn!("Failed to fetch {openapi_path} again, reloading the cached copy: {error:?}");
+        }
+      }
+      let path = openapi_path.clone();
+      let parsed = match tokio::task::spawn_blocking(move || ParsedSpec::parse(&path)).await {
+        Ok(parsed) => parsed,
+        Err(error) => Err(error.into()),
+      };
+      let action = match parsed {
+        Ok(parsed) => Action::SpecLoaded(Box::new(parsed)),
+        Err(error) => {
+          log::error!("Failed to load {openapi_path}: {error:?}");
+          Action::SpecFailed(openapi_path, error.chain().map(|cause| cause.to_string()).collect())
+        },
+      };
+      command_tx.send(action).ok();
+    });
+  }
+
+  /// Loads the spec again, keeping the selected tag and operation and the focused panes.
+  fn reload_spec(&mut self) {
+    let Some(command_tx) = self.command_tx.clone() else {
+      return;
+    };
+    let session = self.session();
+    self.state.write().unwrap().workspace.session = session;
+    self.spec_changed = false;
+    self.load_spec(command_tx, !self.offline);
+  }
+
+  /// Imports requests from the given files into the workspace and reports what was imported.
+  pub fn import(&mut self, paths: &[String]) -> Result<()> {
+    if paths.is_empty() {
+      return Ok(());
+    }
+    let mut state = self.state.write().unwrap();
+    if state.loading {
+      self.pending_imports.extend_from_slice(paths);
+      return Ok(());
+    }
+    let mut report = vec![];
+    for path in paths {
+      let imported = import::import_file(&state.openapi_spec, Path::new(path))?;
+      let saved_requests = imported.saved_requests;
+      let unmatched: Vec<String> = saved_requests
+        .iter()
+        .filter(|saved| saved.operation.is_none())
+        .map(|saved| format!("  {} {}", saved.request.method, saved.request.url()))
+        .collect();
+      report.push(format!(
+        "{path}: {} requests imported, {} matched to operations",
+        saved_requests.len(),
+        saved_requests.len() - unmatched.len()
+      ));
+      let replaced = state.workspace.import_requests(saved_requests);
+      if replaced > 0 {
+        report.push(format!("{replaced} of them replacing the requests of the same name and url imported before"));
+      }
+      if !unmatched.is_empty() {
+        report.push(String::from("Unmatched requests:"));
+        report.extend(unmatched);
+      }
+      for environment in imported.environments {
+        report.push(format!("Environment `{}` with {} variables", environment.name, environment.variables.len()));
+        state.workspace.merge_environment(environment);
+      }
+    }
+    state.workspace.save(&state.openapi_path)?;
+    self.popup = Some(Box::new(MessagePopup::new("Import", report.join("\n"))));
+    Ok(())
+  }
+
+  pub fn override_base_url(&mut self, base_url: Option<String>) {
+    self.state.write().unwrap().base_url_override = base_url.map(|base_url| base_url.trim_end_matches('/').to_string());
+  }
+
+  /// Sends the requests to the hosts of the `--resolve` entries to their addresses, the entries of the config file
+  /// are added once it is registered.
+  pub fn override_hosts(&mut self, entries: &[String]) -> Result<()> {
+    self.host_overrides = entries.iter().map(|entry| entry.parse()).collect::<Result<_>>()?;
+    hosts::set(self.host_overrides.clone());
+    Ok(())
+  }
+
+  pub fn limit_redirects(&mut self, max_redirects: Option<usize>) {
+    self.max_redirects = max_redirects;
+    redirect::set_max(max_redirects.unwrap_or(redirect::DEFAULT_MAX_REDIRECTS));
+  }
+
+  /// Loads the `.env` file next to the spec, if any, then the given files, later files win.
+  pub fn load_env_files(&mut self, paths: &[String]) -> Result<()> {
+    let mut state = self.state.write().unwrap();
+    let dotenv_path = Workspace::dotenv_path(&state.openapi_path);
+    if dotenv_path.exists() {
+      let variables = Workspace::load_dotenv(&dotenv_path)?;
+      state.dotenv_variables.extend(variables);
+    }
+    for path in paths {
+      let variables = Workspace::load_dotenv(Path::new(path))?;
+      state.dotenv_variables.extend(variables);
+    }
+    Ok(())
+  }
+
+  /// Activates the environment for this session only, the workspace keeps its active environment.
+  pub fn use_environment(&mut self, name: &str) -> Result<()> {
+    let mut state = self.state.write().unwrap();
+    if !state.environments().iter().any(|environment| environment.name == name) {
+      return Err(eyre!("No environment named `{name}`"));
+    }
+    state.environment_override = Some(name.to_string());
+    Ok(())
+  }
+
+  pub fn load_workflows(&mut self, paths: &[String]) -> Result<()> {
+    let mut state = self.state.write().unwrap();
+    for path in paths {
+      state.workflows.push(workflow::load(Path::new(path))?);
+    }
+    Ok(())
+  }
+
+  pub fn use_cassette(&mut self, record: Option<String>, replay: Option<String>) -> Result<()> {
+    let mut state = self.state.write().unwrap();
+    state.recorder = record.map(|path| Recorder::new(&path)).transpose()?;
+    state.player = replay.map(|path| Player::new(&path).map(Arc::new)).transpose()?;
+    Ok(())
+  }
+
+  /// Runs the steps of the workflow one after the other, stopping at the first one that fails.
+  fn run_workflow(&self, index: usize) {
+    let Some(command_tx) = self.command_tx.clone() else {
+      return;
+    };
+    let state = self.state.read().unwrap();
+    let Some(workflow) = state.workflows.get(index) else {
+      return;
+    };
+    let steps = state.workflow_steps(workflow);
+    let mut variables = state.variables();
+    tokio::spawn(async move {
+      for (index, (step, request, hooks)) in steps.into_iter().enumerate() {
+        let outcome = workflow::run_step(&step, request, &hooks, &mut variables).await;
+        let passed = outcome.passed();
+        if let Err(error) = command_tx.send(Action::WorkflowStepFinished(index, Box::new(outcome))) {
+          log::error!("Failed to send action: {error:?}");
+        }
+        if !passed {
+          break;
+        }
+      }
+    });
+  }
+
+  /// Sends the request of `openapi-tui call` through the scripts of its operation and the plugins, and records the
+  /// exchange as the TUI does.
+  pub async fn call(&self, operation: &str, parameters: &[(String, String)], body: Option<String>) -> Result<Exchange> {
+    let (request, operation, hooks, variables) = {
+      let state = self.state.read().unwrap();
+      let (request, key) = state
+        .call_request(operation, parameters, body)
+        .ok_or_else(|| eyre!("No operation `{operation}` in the spec"))?;
+      let hooks = state.hooks(Some(&key), None);
+      (request, key, hooks, state.variables())
+    };
+    let exchange = script::exchange(Some(operation), request, &hooks, &variables).await?;
+    self.state.write().unwrap().record(exchange.clone())?;
+    Ok(exchange)
+  }
+
+  /// Runs the loaded workflows, the saved requests holding assertions and, with `smoke`, the smoke tests, one after
+  /// the other and away from the TUI.
+  pub async fn run_tests(&self, smoke: bool) -> TestReport {
+    let (workflows, saved_requests, smoke_requests, variables, document) = {
+      let state = self.state.read().unwrap();
+      let workflows: Vec<_> =
+        state.workflows.iter().map(|workflow| (workflow.clone(), state.workflow_steps(workflow))).collect();
+      let saved_requests: Vec<_> = state
+        .workspace
+        .saved_requests
+        .iter()
+        .filter(|saved| !saved.assertions.is_empty())
+        .map(|saved| {
+          let name = match &saved.folder {
+            Some(folder) => format!("{folder}/{}", saved.name),
+            None => saved.name.clone(),
+          };
+          (
+            name,
+            saved.operation.clone(),
+            state.resolve_saved(saved),
+            state.hooks(saved.operation.as_ref(), Some(saved)),
+          )
+        })
+        .collect();
+      let smoke_requests: Vec<_> = match smoke {
+        true => {
+          state
+            .smoke_requests()
+            .into_iter()
+            .map(|(operation, request)| {
+              let hooks = state.hooks(Some(&operation), None);
+              (operation, request, hooks)
+            })
+            .collect()
+        },
+        false => vec![],
+      };
+      (workflows, saved_requests, smoke_requests, state.variables(), state.openapi_document.clone())
+    };
+
+    let mut suites = vec![];
+    let mut exercised = vec![];
+    for (workflow, steps) in workflows {
+      let mut variables = variables.clone();
+      let mut outcomes = vec![None; steps.len()];
+      for (index, (step, request, hooks)) in steps.into_iter().enumerate() {
+        let outcome = workflow::run_step(&step, request, &hooks, &mut variables).await;
+        exercised.extend(outcome.exchange.as_ref().and_then(|exchange| exchange.operation.clone()));
+        let passed = outcome.passed();
+        outcomes[index] = Some(outcome);
+        if !passed {
+          break;
+        }
+      }
+      suites.push(TestSuite::workflow(&workflow, &outcomes));
+    }
+    if !saved_requests.is_empty() {
+      let mut cases = vec![];
+      for (name, operation, request, hooks) in saved_requests {
+        let exchange =
+          rate_limit::exchange(operation, request, &hooks, &variables).await.map_err(|error| error.to_string());
+        exercised.extend(exchange.as_ref().ok().and_then(|exchange| exchange.operation.clone()));
+        cases.push(TestCase::from_exchange(name, &exchange));
+      }
+      suites.push(TestSuite { name: String::from("assertions"), cases });
+    }
+    if !smoke_requests.is_empty() {
+      let mut cases = vec![];
+      for (operation, request, hooks) in smoke_requests {
+        let name = format!("{} {}", operation.method, operation.path);
+        let result = fuzz::run_case(&document, operation, request, &hooks, &variables).await;
+        exercised.extend(result.exchange.as_ref().and_then(|exchange| exchange.operation.clone()));
+        cases.push(TestCase::from_case(name, Some(&result), true));
+      }
+      suites.push(TestSuite { name: String::from("smoke tests"), cases });
+    }
+    let mut state = self.state.write().unwrap();
+    for operation in exercised {
+      if let Err(error) = state.exercise(&operation) {
+        log::error!("Failed to save the workspace: {error:?}");
+      }
+    }
+    TestReport { name: String::from("openapi-tui-tests"), suites }
+  }
+
+  /// Sends the requests one after the other, each result is sent back with the action made by `finished`.
+  fn run_cases(&mut self, requests: Vec<(OperationKey, HttpRequest)>, finished: fn(usize, Box<CaseResult>) -> Action) {
+    let Some(command_tx) = self.command_tx.clone() else {
+      return;
+    };
+    let state = self.state.read().unwrap();
+    let requests: Vec<_> = requests
+      .into_iter()
+      .map(|(operation, request)| {
+        let hooks = state.hooks(Some(&operation), None);
+        (operation, request, hooks)
+      })
+      .collect();
+    let variables = state.variables();
+    let document = state.openapi_document.clone();
+    drop(state);
+    let cancellation = CancellationToken::new();
+    if let Some(previous) = self.cases_cancellation.replace(cancellation.clone()) {
+      previous.cancel();
+    }
+    tokio::spawn(async move {
+      for (index, (operation, request, hooks)) in requests.into_iter().enumerate() {
+        let result = tokio::select! {
+          _ = cancellation.cancelled() => break,
+          result = fuzz::run_case(&document, operation, request, &hooks, &variables) => result,
+        };
+        if let Err(error) = command_tx.send(finished(index, Box::new(result))) {
+          log::error!("Failed to send action: {error:?}");
+        }
+      }
+    });
+  }
+
+  /// Fires the request of the active operation, each sample is sent back as it is received.
+  fn run_load_test(&mut self, requests: usize, concurrency: usize) {
+    let Some(command_tx) = self.command_tx.clone() else {
+      return;
+    };
+    let state = self.state.read().unwrap();
+    let Some(request) = state.active_request() else {
+      return;
+    };
+    let operation = state.active_operation_key();
+    let request = state.resolve_request(&request, operation.as_ref());
+    let hooks = state.hooks(operation.as_ref(), None);
+    let load_test = LoadTest { operation, request, hooks, variables: state.variables(), requests, concurrency };
+    drop(state);
+    let cancellation = CancellationToken::new();
+    if let Some(previous) = self.cases_cancellation.replace(cancellation.clone()) {
+      previous.cancel();
+    }
+    tokio::spawn(load_test.run(cancellation, move |sample| {
+      if let Err(error) = command_tx.send(Action::LoadSample(Box::new(sample))) {
+        log::error!("Failed to send action: {error:?}");
+      }
+    }));
+  }
+
+  /// Follows the pages of the request of the active operation, each page is sent back as it is received.
+  fn follow_pagination(&mut self) {
+    let Some(command_tx) = self.command_tx.clone() else {
+      return;
+    };
+    let state = self.state.read().unwrap();
+    let (Some(request), Some((path, method, _))) = (state.active_request(), state.active_operation()) else {
+      return;
+    };
+    let target = format!("{method} {path}");
+    let operation = state.active_operation_key();
+    let request = state.resolve_request(&request, operation.as_ref());
+    let hooks = state.hooks(operation.as_ref(), None);
+    let walk = Walk { operation, request, hooks, variables: state.variables() };
+    drop(state);
+    self.popup = Some(Box::new(PaginationPopup::new(target)));
+    let cancellation = CancellationToken::new();
+    if let Some(previous) = self.cases_cancellation.replace(cancellation.clone()) {
+      previous.cancel();
+    }
+    tokio::spawn(walk.run(cancellation, move |page| {
+      if let Err(error) = command_tx.send(Action::PageFetched(Box::new(page))) {
+        log::error!("Failed to send action: {error:?}");
+      }
+    }));
+  }
+
+  fn send_request(&mut self) {
+    let state = self.state.read().unwrap();
+    if let Some(request) = state.active_request() {
+      let operation = state.active_operation_key();
+      let request = state.resolve_request(&request, operation.as_ref());
+      drop(state);
+      let xml_body = request.header("Content-Type").is_some_and(xml::is_xml);
+      if let Some(Err(error)) = request.body.as_deref().filter(|_| xml_body).map(xml::check) {
+        let message = format!("The body is not well-formed XML, {error}.");
+        self.popup = Some(Box::new(MessagePopup::new("Invalid XML", message)));
+        return;
+      }
+      self.spawn_exchange(request, operation, None);
+    }
+  }
+
+  fn resend(&mut self) {
+    let state = self.state.read().unwrap();
+    let operation = state.active_operation_key();
+    let request = state.last_request();
+    drop(state);
+    match request {
+      Some(Ok(request)) => self.spawn_exchange(request, operation, None),
+      Some(Err(error)) => self.popup = Some(Box::new(MessagePopup::new("Resend", error))),
+      None => {
+        self.popup = Some(Box::new(MessagePopup::new("Resend", "No request was sent for this operation yet.")));
+      },
+    }
+  }
+
+  /// Sends the request of the exchange again, conditional on the validators of its response, to show how the server
+  /// handles `If-None-Match` and `If-Modified-Since`.
+  fn revalidate(&mut self, exchange: &Exchange) {
+    // the exchange shown has its secrets masked, the one of the session holds them
+    let request = self.state.read().unwrap().sent_request(exchange);
+    let mut request = match request {
+      Ok(request) => request,
+      Err(error) => {
+        self.toasts.push(ToastLevel::Error, error);
+        return;
+      },
+    };
+    let conditional = cache::conditional_headers(&exchange.response);
+    request.headers.retain(|(name, _)| !conditional.iter().any(|(header, _)| header.eq_ignore_ascii_case(name)));
+    request.headers.extend(conditional);
+    self.spawn_exchange(request, exchange.operation.clone(), None);
+  }
+
+  /// Sends the request through the plugins and the scripts of its operation, along with the scripts of the
+  /// collection folder and the assertions of the saved request it was sent from.
+  fn spawn_exchange(&mut self, request: HttpRequest, operation: Option<OperationKey>, saved: Option<&SavedRequest>) {
+    let Some(command_tx) = self.command_tx.clone() else {
+      return;
+    };
+    let mut state = self.state.write().unwrap();
+    let id = state.queue.push(&request.method, &request.path);
+    let hooks = state.hooks(operation.as_ref(), saved);
+    let variables = state.variables();
+    tokio::spawn(async move {
+      let action = match script::exchange(operation, request, &hooks, &variables).await {
+        Ok(exchange) => Action::ResponseReceived(id, Box::new(exchange)),
+        Err(error) => Action::RequestFailed(id, error.to_string()),
+      };
+      command_tx.send(action).ok();
+    });
+  }
+
+  /// Probes the base url of the selected server on a background task, with the network settings of the active
+  /// environment, sending `Action::HealthProbed`.
+  fn probe_health(&mut self) {
+    let Some(command_tx) = self.command_tx.clone() else {
+      return;
+    };
+    let state = self.state.read().unwrap();
+    let base_url = state.active_base_url();
+    let path = self.config.health.as_ref().and_then(|health| health.path.as_deref());
+    let url = health::url(&request::substitute(&base_url, &state.variables()), path);
+    let network = state.active_environment().and_then(|environment| environment.network);
+    drop(state);
+    self.health_probed_at = Some((base_url.clone(), Instant::now()));
+    tokio::spawn(async move {
+      let health = health::probe(base_url, url, network).await;
+      command_tx.send(Action::HealthProbed(Box::new(health))).ok();
+    });
+  }
+
+  fn copy_to_clipboard(&mut self, text: String) -> Result<()> {
+    if self.clipboard.is_none() {
+      self.clipboard = Some(arboard::Clipboard::new()?);
+    }
+    if let Some(clipboard) = self.clipboard.as_mut() {
+      clipboard.set_text(text)?;
+    }
+    Ok(())
+  }
+
+  /// Lists every operation, selects the given one and focuses the APIs pane.
+  fn select_operation(&mut self, operation: &OperationKey) -> Result<()> {
+    {
+      let mut state = self.state.write().unwrap();
+      state.active_tag_name = None;
+      state.untested_only = false;
+      state.favorites_only = false;
+      let index = state
+        .operations()
+        .position(|(path, method, _)| &OperationKey::new(&method, &path) == operation)
+        .unwrap_or_default();
+      state.active_operation_index = index;
+    }
+    self.popup = None;
+    self.focus_pane(0)
+  }
+
+  /// Keeps the selected tag and operation and the focused and fullscreen panes in the workspace, along with the
+  /// active environment.
+  fn save_session(&mut self) -> Result<()> {
+    // the session restored when the spec is loaded is kept when quitting before
+    if self.state.read().unwrap().loading {
+      return Ok(());
+    }
+    let session = self.session();
+    let mut state = self.state.write().unwrap();
+    state.workspace.session = session;
+    state.workspace.save(&state.openapi_path)
+  }
+
+  /// Selected tag and operation and focused panes, to resume from.
+  fn session(&self) -> Session {
+    let state = self.state.read().unwrap();
+    Session {
+      tag: state.active_tag_name.clone(),
+      favorites_only: state.favorites_only,
+      operation: state.active_operation().map(|(path, method, _)| OperationKey::new(&method, &path)),
+      focused_pane: self.focused_pane_index,
+      fullscreen_pane: self.fullscreen_pane_index,
+    }
+  }
+
+  /// Resumes where the spec was left on exit, ignoring the tag, operation and panes it no longer has.
+  fn restore_session(&mut self) -> Result<()> {
+    let session = {
+      let mut state = self.state.write().unwrap();
+      let session = state.workspace.session.clone();
+      state.active_tag_name = session.tag.clone();
+      state.favorites_only = session.favorites_only;
+      if state.operations_len() == 0 {
+        state.active_tag_name = None;
+        state.favorites_only = false;
+      }
+      if let Some(operation) = &session.operation {
+        let index = state.operations().position(|(path, method, _)| &OperationKey::new(&method, &path) == operation);
+        state.active_operation_index = index.unwrap_or_default();
+      }
+      session
+    };
+    if session.focused_pane < self.panes.len() {
+      self.focus_pane(session.focused_pane)?;
+    }
+    self.fullscreen_pane_index = session.fullscreen_pane.filter(|index| *index < self.panes.len());
+    // panes the layout hides are shown fullscreen
+    if self.fullscreen_pane_index.is_none() && !layout::panes(&self.layout()).contains(&self.focused_pane_index) {
+      self.fullscreen_pane_index = Some(self.focused_pane_index);
+    }
+    if let Some(command_tx) = &self.command_tx {
+      command_tx.send(Action::Update)?;
+    }
+    Ok(())
+  }
+
+  fn focus_pane(&mut self, index: usize) -> Result<()> {
+    if let Some(pane) = self.panes.get_mut(self.focused_pane_index) {
+      pane.unfocus()?;
+    }
+    self.focused_pane_index = index;
+    if let Some(pane) = self.panes.get_mut(self.focused_pane_index) {
+      pane.focus()?;
+    }
+    Ok(())
+  }
+
+  /// Keys of the focused pane and of the page, as configured, each action described by the title of its command
+  /// when it has one.
+  fn help(&self) -> Vec<HelpSection> {
+    let commands = self.commands();
+    let describe = |action: &Action| {
+      let description = match action {
+        Action::Down => "Move down in lists",
+        Action::Up => "Move up in lists",
+        Action::PageDown => "Move a page down in lists",
+        Action::PageUp => "Move a page up in lists",
+        Action::Top => "Move to the top of lists",
+        Action::Bottom => "Move to the bottom of lists",
+        Action::NextSection => "Move to the next section of lists",
+        Action::PrevSection => "Move to the previous section of lists",
+        Action::FocusNext => "Focus the next pane",
+        Action::FocusPrev => "Focus the previous pane",
+        Action::Go => "Go in nested items",
+        Action::Back => "Get out of nested items",
+        Action::NextBranch => "Expand the next oneOf/anyOf branch",
+        Action::Submit => "Open or run the selected item",
+        Action::Suspend => "Suspend the application",
+        Action::ShowHelp => "Show the keys",
+        Action::Tab(index) => return format!("Select tab {}", index + 1),
+        Action::BackTo(depth) => return format!("Go back to level {} of the schema trail", depth + 1),
+        action => {
+          let command = commands.iter().find(|command| command.action == *action);
+          return command.map_or_else(|| action.to_string(), |command| command.title.clone());
+        },
+      };
+      description.to_string()
+    };
+    let index = self.focused_pane_index;
+    let pane = KeyScope::PANES.get(index).zip(PANE_TITLES.get(index));
+    let pane = pane.map(|(scope, title)| (*scope, format!("{title} pane")));
+    pane
+      .into_iter()
+      .chain([(KeyScope::Home, String::from("Everywhere"))])
+      .map(|(scope, title)| {
+        let mut bindings: Vec<(Vec<String>, String)> = self
+          .config
+          .keybindings
+          .bindings(scope)
+          .iter()
+          .map(|(action, keys)| (keys.clone(), describe(action)))
+          .collect();
+        bindings.sort_by(|a, b| a.1.cmp(&b.1));
+        HelpSection { title, bindings }
+      })
+      .collect()
+  }
+
+  /// Toasts stacked in the bottom right corner of the area, the newest at the bottom.
+  fn draw_toasts(&self, frame: &mut Frame<'_>, area: Rect) {
+    const MAX_WIDTH: u16 = 48;
+    let mut bottom = area.bottom();
+    for toast in self.toasts.shown().iter().rev() {
+      let color = match toast.level {
+        ToastLevel::Info => Color::LightBlue,
+        ToastLevel::Success => Color::LightGreen,
+        ToastLevel::Error => Color::LightRed,
+      };
+      let message_width = u16::try_from(toast.message.chars().count()).unwrap_or(u16::MAX);
+      let width = message_width.saturating_add(4).min(MAX_WIDTH).min(area.width);
+      let lines = message_width.div_ceil(width.saturating_sub(4).max(1));
+      let height = lines.saturating_add(2);
+      if bottom < area.y + height {
+        break;
+      }
+      bottom -= height;
+      let toast_area = Rect::new(area.right().saturating_sub(width), bottom, width, height);
+      frame.render_widget(Clear, toast_area);
+      frame.render_widget(
+        Paragraph::new(toast.message.as_str()).wrap(Wrap { trim: true }).block(
+          Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(color))
+            .padding(Padding::horizontal(1)),
+        ),
+        toast_area,
+      );
+    }
+  }
+
+  /// Scopes of the key bindings applying, those of the focused pane before those of the page.
+  fn key_scopes(&self) -> Vec<KeyScope> {
+    match self.popup.is_some() {
+      true => vec![KeyScope::Home],
+      false => KeyScope::PANES.get(self.focused_pane_index).copied().into_iter().chain([KeyScope::Home]).collect(),
+    }
+  }
+
+  fn send_actions(&self, actions: Vec<Action>) -> Result<()> {
+    if let Some(command_tx) = &self.command_tx {
+      for action in actions {
+        command_tx.send(action)?;
+      }
+    }
+    Ok(())
+  }
+
+  /// Layout of the config the panes are arranged as, the first one unless another was chosen.
+  fn layout(&self) -> LayoutConfig {
+    let active = self.state.read().unwrap().workspace.active_layout.clone();
+    let layouts = &self.config.layouts;
+    let layout = layouts.iter().find(|layout| Some(&layout.name) == active.as_ref()).or(layouts.first());
+    layout.cloned().unwrap_or_else(layout::fallback)
+  }
+
+  /// Focuses the next or previous pane shown, in the order of the layout.
+  fn focus_shown_pane(&mut self, forward: bool) -> Result<()> {
+    let shown = layout::panes(&self.layout());
+    let Some(position) = shown.iter().position(|index| *index == self.focused_pane_index) else {
+      return match shown.first() {
+        Some(index) => self.focus_pane(*index),
+        None => Ok(()),
+      };
+    };
+    let next = match forward {
+      true => (position + 1) % shown.len(),
+      false => (position + shown.len() - 1) % shown.len(),
+    };
+    self.focus_pane(shown[next])
+  }
+
+  /// Commands of the palette: the actions of the page keys, focusing each pane, switching environments and going
+  /// to each operation.
+  fn commands(&self) -> Vec<Command> {
+    let mut commands = vec![
+      Command::new("Send request", Action::SendRequest),
+      Command::new("Resend last request", Action::Resend),
+      Command::new("Show code snippets, e.g. curl", Action::ShowSnippets),
+      Command::new("Export operation as .http", Action::ExportHttp),
+      Command::new("Export listed operations as .http", Action::ExportHttpCollection),
+      Command::new("Export Postman collection", Action::ExportPostman),
+      Command::new("Export saved requests as Postman collection", Action::ExportPostmanSaved),
+      Command::new("Export HAR", Action::ExportHar),
+      Command::new("Save request", Action::ShowSaveRequest),
+      Command::new("Copy request url", Action::CopyUrl),
+      Command::new("Copy path template", Action::CopyPath),
+      Command::new("Copy operationId", Action::CopyOperationId),
+      Command::new("Switch environment", Action::ShowEnvironments),
+      Command::new("Choose server variables", Action::ShowServers),
+      Command::new("Define extractions", Action::ShowExtractions),
+      Command::new("Run workflows", Action::ShowWorkflows),
+      Command::new("Fuzz operation", Action::ShowFuzz),
+      Command::new("Load test operation", Action::ShowLoadTest),
+      Command::new("Follow pagination", Action::FollowPagination),
+      Command::new("Smoke test GET operations", Action::ShowSmokeTests),
+      Command::new("Lint spec", Action::ShowLint),
+      Command::new("Show spec statistics", Action::ShowStats),
+      Command::new("Show spec info and owners", Action::ShowSpecInfo),
+      Command::new("Diff spec with previous version", Action::ShowDiff),
+      Command::new("Reload spec", Action::ReloadSpec),
+      Command::new("Open spec from registry", Action::ShowRegistry),
+      Command::new("Replay HAR captures", Action::ShowReplay),
+      Command::new("Open docs", Action::OpenDocs),
+      Command::new("Toggle secrets", Action::ToggleSecrets),
+      Command::new("Bookmark operation", Action::ToggleBookmark),
+      Command::new("Toggle favorites only", Action::ToggleFavorites),
+      Command::new("Expand all groups of operations", Action::ExpandAll),
+      Command::new("Collapse all groups of operations", Action::CollapseAll),
+      Command::new("Toggle fullscreen pane", Action::ToggleFullScreen),
+      Command::new("Show keys", Action::ShowHelp),
+      Command::new("Grow focused pane", Action::GrowPane),
+      Command::new("Shrink focused pane", Action::ShrinkPane),
+      Command::new("Toggle line wrap", Action::ToggleWrap),
+      Command::new("Toggle response cache", Action::ToggleCache),
+      Command::new("Toggle local times in responses", Action::ToggleLocalTimes),
+      Command::new("Toggle request preview", Action::TogglePreview),
+      Command::new("Probe server health", Action::ProbeHealth),
+      Command::new("Edit operation scripts", Action::ShowOperationScripts),
+      Command::new("Edit operation note", Action::ShowNote),
+      Command::new("Quit", Action::Quit),
+    ];
+    let state = self.state.read().unwrap();
+    commands.extend(
+      PANE_TITLES
+        .iter()
+        .enumerate()
+        .map(|(index, pane)| Command::new(format!("Open {pane} pane"), Action::FocusPane(index))),
+    );
+    commands.extend(state.environments().into_iter().map(|environment| {
+      Command::new(
+        format!("Switch environment: {}", environment.name),
+        Action::SelectEnvironment(Some(environment.name)),
+      )
+    }));
+    commands.push(Command::new("Switch environment: none", Action::SelectEnvironment(None)));
+    commands.push(Command::new("Switch to the next layout", Action::CycleLayout));
+    commands.extend(self.config.layouts.iter().map(|layout| {
+      Command::new(format!("Switch layout: {}", layout.name), Action::SelectLayout(layout.name.clone()))
+    }));
+    commands.extend(
+      Keymap::iter().map(|keymap| Command::new(format!("Switch keymap: {keymap}"), Action::SelectKeymap(keymap))),
+    );
+    // bookmarked operations first
+    let mut operations: Vec<Command> = state
+      .openapi_spec
+      .operations()
+      .map(|(path, method, operation)| {
+        let method = method.to_string();
+        let key = OperationKey::new(&method, &path);
+        let mut title = match &operation.operation_id {
+          Some(operation_id) => format!("Go to {operation_id}  {} {path}", method.to_uppercase()),
+          None => format!("Go to {} {path}", method.to_uppercase()),
+        };
+        if state.workspace.is_bookmarked(&key) {
+          title.push_str("  ★");
+        }
+        Command::new(title, Action::SelectOperation(key))
+      })
+      .collect();
+    operations.sort_by_key(|command| !command.title.ends_with('★'));
+    commands.extend(operations);
+    // keys as configured
+    for command in commands.iter_mut() {
+      command.key = self.config.keybindings.key_of(KeyScope::Home, &command.action);
+    }
+    commands
+  }
+}
+
+/// Key of the draft of the operation, its operation id or its method and path when it has none.
+fn draft_key(path: &str, method: &str, operation: &Operation) -> String {
+  operation.operation_id.clone().unwrap_or_else(|| OperationKey::new(method, path).to_string())
+}
+
+/// Panes of the home page, the APIs pane focused.
+fn panes(state: &Arc<RwLock<State>>) -> Vec<Box<dyn Pane>> {
+  let focused_border_style = theme::current().focused_border;
+  vec![
+    Box::new(ApisPane::new(state.clone(), true, focused_border_style)),
+    Box::new(TagsPane::new(state.clone(), false, focused_border_style)),
+    Box::new(CollectionsPane::new(state.clone(), false, focused_border_style)),
+    Box::new(HistoryPane::new(state.clone(), false, focused_border_style)),
+    Box::new(ComponentsPane::new(state.clone(), false, focused_border_style)),
+    Box::new(AddressPane::new(state.clone(), false, focused_border_style)),
+    Box::new(RequestPane::new(state.clone(), false, focused_border_style)),
+    Box::new(ResponsePane::new(state.clone(), false, focused_border_style)),
+  ]
+}
+
+impl Page for Home {
+  fn init(&mut self) -> Result<()> {
+    for pane in self.panes.iter_mut() {
+      pane.init()?;
+    }
+    // the session is restored once the spec is loaded otherwise
+    let loading = self.state.read().unwrap().loading;
+    match loading {
+      true => Ok(()),
+      false => self.restore_session(),
+    }
+  }
+
+  fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+    if self.state.read().unwrap().loading {
+      self.load_spec(tx.clone(), false);
+    }
+    if let Some(interval) = self.refresh_interval {
+      self.poll_spec(tx.clone(), interval);
+    }
+    self.command_tx = Some(tx);
+    Ok(())
+  }
+
+  fn register_config_handler(&mut self, config: Config) -> Result<()> {
+    self.state.write().unwrap().config_environments = config
+      .environments
+      .iter()
+      .map(|(name, environment)| {
+        Environment {
+          name: name.clone(),
+          variables: environment.variables.clone(),
+          headers: environment.headers.clone(),
+          signing: environment.signing.clone(),
+          network: environment.network.clone(),
+        }
+      })
+      .collect();
+    self.state.write().unwrap().plugins = Plugins::load(&config.plugins, &crate::utils::get_config_dir());
+    self.state.write().unwrap().redactor = Redactor::new(&config.redaction)?;
+    self.state.write().unwrap().cache = config.cache.then(Cache::default);
+    self.state.write().unwrap().local_times = config.local_times;
+    let mut host_overrides = self.host_overrides.clone();
+    for entry in config.resolve.iter() {
+      host_overrides.push(entry.parse()?);
+    }
+    hosts::set(host_overrides);
+    redirect::set_max(self.max_redirects.or(config.max_redirects).unwrap_or(redirect::DEFAULT_MAX_REDIRECTS));
+    self.config = config;
+    Ok(())
+  }
+
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    match action {
+      Action::Tick => {
+        let scopes = self.key_scopes();
+        let actions = self.keys.expire(&self.config.keybindings, &scopes);
+        self.send_actions(actions)?;
+        let due = self.config.health.as_ref().is_some_and(|health| {
+          health.due(self.health_probed_at.as_ref(), &self.state.read().unwrap().active_base_url(), Instant::now())
+        });
+        if due {
+          self.probe_health();
+        }
+        if self.toasts.expire(Instant::now()) {
+          return Ok(Some(Action::Render));
+        }
+      },
+      Action::Notify(level, message) => self.toasts.push(level, message),
+      Action::SpecLoaded(parsed) => self.spec_loaded(*parsed)?,
+      Action::SpecChanged => {
+        self.spec_changed = true;
+        let key = self.config.keybindings.key_of(KeyScope::Home, &Action::ReloadSpec).unwrap_or_default();
+        self.toasts.push(ToastLevel::Info, format!("The spec changed on the server, {key} reloads it"));
+      },
+      Action::ReloadSpec => self.reload_spec(),
+      Action::ShowRegistry => self.show_registry(),
+      Action::Quit => {
+        let work = self.unfinished_work();
+        match work.is_empty() {
+          true => self.save_session()?,
+          false => self.popup = Some(Box::new(QuitPopup::new(work))),
+        }
+      },
+      Action::ConfirmQuit(always) => {
+        if always {
+          self.state.write().unwrap().workspace.skip_quit_confirmation = true;
+        }
+        self.save_session()?;
+      },
+      Action::ShowSnippets => {
+        let state = self.state.read().unwrap();
+        if let Some(request) = state.active_request() {
+          let request = state.redactor.request(&state.display_request(&request));
+          drop(state);
+          self.popup = Some(Box::new(SnippetPopup::new(request)));
+        }
+      },
+      Action::ExportHttp | Action::ExportHttpCollection => {
+        let exported = self.state.read().unwrap().export_http(action == Action::ExportHttpCollection);
+        self.popup = match exported {
+          Ok(Some(path)) => Some(Box::new(MessagePopup::new("Export", format!("Exported to {}", path.display())))),
+          Ok(None) => None,
+          Err(error) => Some(Box::new(MessagePopup::new("Export Failed", error.to_string()))),
+        };
+      },
+      Action::ExportPostman | Action::ExportPostmanSaved => {
+        let exported = self.state.read().unwrap().export_postman(action == Action::ExportPostmanSaved);
+        self.popup = match exported {
+          Ok(paths) => {
+            let paths = paths.iter().map(|path| path.display().to_string()).collect::<Vec<_>>().join("\n");
+            Some(Box::new(MessagePopup::new("Export", format!("Exported to\n{paths}"))))
+          },
+          Err(error) => Some(Box::new(MessagePopup::new("Export Failed", error.to_string()))),
+        };
+      },
+      Action::SendRequest => self.send_request(),
+      Action::Resend => self.resend(),
+      Action::ShowWorkflows => {
+        let workflows = self.state.read().unwrap().workflows.clone();
+        self.popup = Some(match workflows.is_empty() {
+          true => Box::new(MessagePopup::new("Workflows", "No workflows loaded yet, use `--workflow <file.yaml>`.")),
+          false => Box::new(WorkflowPopup::new(workflows)),
+        });
+      },
+      Action::RunWorkflow(index) => self.run_workflow(index),
+      Action::ShowFuzz => {
+        let Some((operation, requests)) = self.state.read().unwrap().fuzz_requests() else {
+          return Ok(None);
+        };
+        let cases = requests.into_iter().map(|(case, _)| case).collect();
+        self.popup = Some(Box::new(FuzzPopup::new(operation, cases)));
+      },
+      Action::RunFuzz => {
+        let requests = self.state.read().unwrap().fuzz_requests();
+        if let Some((operation, requests)) = requests {
+          let requests = requests.into_iter().map(|(_, request)| (operation.clone(), request)).collect();
+          self.run_cases(requests, Action::FuzzCaseFinished);
+        }
+      },
+      Action::ShowLoadTest => {
+        let state = self.state.read().unwrap();
+        let Some((path, method, _)) = state.active_operation() else {
+          return Ok(None);
+        };
+        let target = format!("{method} {path}");
+        drop(state);
+        self.popup = Some(Box::new(LoadPopup::new(target)));
+      },
+      Action::RunLoadTest(requests, concurrency) => self.run_load_test(requests, concurrency),
+      Action::FollowPagination => self.follow_pagination(),
+      Action::ShowSmokeTests => {
+        let state = self.state.read().unwrap();
+        let operations = state.smoke_requests().into_iter().map(|(operation, _)| operation).collect();
+        let popup =
+          SmokePopup::new(operations, state.active_tag_name.clone(), state.active_environment_name().map(String::from));
+        drop(state);
+        self.popup = Some(Box::new(popup));
+      },
+      Action::RunSmokeTests => {
+        let requests = self.state.read().unwrap().smoke_requests();
+        self.run_cases(requests, Action::SmokeTestFinished);
+      },
+      Action::ShowLint => {
+        let findings = lint::lint(&self.state.read().unwrap().openapi_document);
+        self.popup = Some(Box::new(LintPopup::new(findings)));
+      },
+      Action::CopyUrl | Action::CopyPath | Action::CopyOperationId => {
+        let text = {
+          let state = self.state.read().unwrap();
+          state.active_operation().and_then(|(path, method, operation)| {
+            match action {
+              Action::CopyUrl => {
+                let request = state.operation_request(&path, &method, operation, state.active_draft());
+                let key = OperationKey::new(&method, &path);
+                Some(state.display_request(&state.resolve_request(&request, Some(&key))).full_url())
+              },
+              Action::CopyPath => Some(path),
+              _ => operation.operation_id.clone(),
+            }
+          })
+        };
+        match text {
+          Some(text) => return Ok(Some(Action::Copy(text))),
+          None if action == Action::CopyOperationId => {
+            self.toasts.push(ToastLevel::Info, "The operation has no operationId")
+          },
+          None => {},
+        }
+      },
+      Action::ShowSpecInfo => {
+        let state = self.state.read().unwrap();
+        let info = SpecInfo::new(&state.openapi_document);
+        let operation_tags =
+          state.active_operation().map(|(_, _, operation)| operation.tags.clone()).unwrap_or_default();
+        drop(state);
+        self.popup = Some(Box::new(SpecInfoPopup::new(info, operation_tags)));
+      },
+      Action::ShowStats => {
+        let stats = Stats::new(&self.state.read().unwrap().openapi_document);
+        self.popup = Some(Box::new(StatsPopup::new(stats)));
+      },
+      Action::ShowUsages(schema) => {
+        let usages = usage::usages(&self.state.read().unwrap().openapi_document, &schema);
+        self.popup = Some(Box::new(UsagePopup::new(schema, usages)));
+      },
+      Action::CompareOperations(left, right) => {
+        let rows = operation_compare::compare(&self.state.read().unwrap().openapi_document, &left, &right);
+        self.popup = Some(Box::new(OperationComparePopup::new(left, right, rows)));
+      },
+      Action::ShowDiff => self.popup = Some(Box::new(DiffPopup::new())),
+      Action::DiffSpec(path) => {
+        let document = self.state.read().unwrap().openapi_document.clone();
+        self.popup = Some(match git::resolve(&path, None).and_then(|copy| diff::load(&copy)) {
+          Ok(old) => Box::new(DiffPopup::with_changes(path, diff::diff(&old, &document))),
+          Err(error) => Box::new(MessagePopup::new("Diff", format!("Could not read `{path}`: {error}"))),
+        });
+      },
+      Action::SelectOperation(operation) => {
+        self.select_operation(&operation)?;
+        return Ok(Some(Action::Update));
+      },
+      Action::FollowLink(operation, draft) => {
+        self.select_operation(&operation)?;
+        let mut state = self.state.write().unwrap();
+        if let Some(key) = state.draft_key().filter(|_| !draft.is_empty()) {
+          let existing = state.workspace.drafts.entry(key).or_default();
+          for parameter in draft.parameters {
+            existing.set_parameter(&parameter.location, &parameter.name, parameter.value);
+          }
+          if draft.body.is_some() {
+            existing.body = draft.body;
+          }
+          state.workspace.save(&state.openapi_path)?;
+        }
+        return Ok(Some(Action::Update));
+      },
+      Action::FuzzCaseFinished(_, ref result) | Action::SmokeTestFinished(_, ref result) => {
+        if let Some(operation) = result.exchange.as_ref().and_then(|exchange| exchange.operation.as_ref()) {
+          if let Err(error) = self.state.write().unwrap().exercise(operation) {
+            log::error!("Failed to save the workspace: {error:?}");
+          }
+        }
+        if let Some(popup) = self.popup.as_mut() {
+          return popup.update(action);
+        }
+      },
+      Action::WorkflowStepFinished(index, outcome) => {
+        let mut state = self.state.write().unwrap();
+        state.extracted_variables.extend(outcome.extracted.clone());
+        if let Some(exchange) = outcome.exchange.clone() {
+          if let Err(error) = state.record(exchange) {
+            log::error!("Failed to save the history: {error:?}");
+          }
+        }
+        drop(state);
+        if let Some(popup) = self.popup.as_mut() {
+          return popup.update(Action::WorkflowStepFinished(index, outcome));
+        }
+      },
+      Action::ShowExtractions => {
+        let state = self.state.read().unwrap();
+        let Some(operation) = state.active_operation_key() else {
+          return Ok(None);
+        };
+        let extractions =
+          state.workspace.extractions.iter().filter(|extraction| extraction.operation == operation).cloned().collect();
+        let popup = ExtractionPopup::new(operation, extractions, state.extracted_variables.clone());
+        drop(state);
+        self.popup = Some(Box::new(popup));
+      },
+      Action::AddExtraction(extraction) => {
+        let mut state = self.state.write().unwrap();
+        state
+          .workspace
+          .extractions
+          .retain(|existing| existing.operation != extraction.operation || existing.variable != extraction.variable);
+        state.workspace.extractions.push(extraction);
+        state.workspace.save(&state.openapi_path)?;
+        drop(state);
+        return Ok(Some(Action::ShowExtractions));
+      },
+      Action::RemoveExtraction(extraction) => {
+        let mut state = self.state.write().unwrap();
+        state.workspace.extractions.retain(|existing| existing != &extraction);
+        state.workspace.save(&state.openapi_path)?;
+        drop(state);
+        return Ok(Some(Action::ShowExtractions));
+      },
+      Action::SendHttpRequest(request, operation) => self.spawn_exchange(*request, operation, None),
+      Action::Revalidate(exchange) => self.revalidate(&exchange),
+      Action::SendSavedRequest(index) => {
+        let state = self.state.read().unwrap();
+        let Some(saved) = state.workspace.saved_requests.get(index).cloned() else {
+          return Ok(None);
+        };
+        let request = state.resolve_saved(&saved);
+        drop(state);
+        self.spawn_exchange(request, saved.operation.clone(), Some(&saved));
+      },
+      Action::ShowAssertions(index) => {
+        let state = self.state.read().unwrap();
+        let Some(saved) = state.workspace.saved_requests.get(index) else {
+          return Ok(None);
+        };
+        let popup = AssertionPopup::new(index, saved.name.clone(), &saved.assertions);
+        drop(state);
+        self.popup = Some(Box::new(popup));
+      },
+      Action::SaveAssertions(index, assertions) => {
+        let mut state = self.state.write().unwrap();
+        if let Some(saved) = state.workspace.saved_requests.get_mut(index) {
+          saved.assertions = assertions;
+        }
+        state.workspace.save(&state.openapi_path)?;
+        drop(state);
+        self.popup = None;
+      },
+      Action::ShowScripts(target) => {
+        let scripts = self.state.read().unwrap().workspace.scripts(&target);
+        self.popup = Some(Box::new(ScriptPopup::new(target, scripts)));
+      },
+      Action::SaveScripts(target, scripts) => {
+        let mut state = self.state.write().unwrap();
+        state.workspace.set_scripts(&target, scripts);
+        state.workspace.save(&state.openapi_path)?;
+        drop(state);
+        self.popup = None;
+      },
+      Action::ShowReplay => {
+        let state = self.state.read().unwrap();
+        let har_folder = format!("{}/", import::HAR_FOLDER);
+        let entries: Vec<SavedRequest> = state
+          .workspace
+          .saved_requests
+          .iter()
+          .filter(|saved| saved.folder.as_ref().is_some_and(|folder| folder.starts_with(&har_folder)))
+          .cloned()
+          .collect();
+        let popup: Box<dyn Popup> = if entries.is_empty() {
+          Box::new(MessagePopup::new("Replay HAR", "No HAR captures imported yet, use `--import <file.har>`."))
+        } else {
+          let environments = state
+            .environments()
+            .into_iter()
+            .map(|environment| {
+              let mut variables = state.dotenv_variables.clone();
+              variables.extend(environment.variables);
+              Environment { variables, ..environment }
+            })
+            .collect();
+          Box::new(ReplayPopup::new(entries, environments, state.reveal_secrets))
+        };
+        drop(state);
+        self.popup = Some(popup);
+      },
+      Action::ResponseReceived(id, exchange) => {
+        let mut state = self.state.write().unwrap();
+        state.queue.receive(id, (*exchange).clone());
+        let in_flight = state.queue.pending();
+        let summary = format!(
+          "{} {} {} {}",
+          exchange.response.status,
+          exchange.response.status_text,
+          exchange.request.method.to_uppercase(),
+          exchange.request.path
+        );
+        let popup = ResponsePopup::new(
+          state.display_exchange(&exchange),
+          state.plugins.render(&exchange.response),
+          state.wrap_lines,
+        )
+        .with_documented_status(state.documented_status(&exchange))
+        .with_local_times(state.local_times);
+        state.extract_variables(&exchange);
+        state.extracted_variables.extend(exchange.variables.clone());
+        if let Err(error) = state.record(*exchange) {
+          log::error!("Failed to save the history: {error:?}");
+        }
+        drop(state);
+        // the popup would hide the responses still to come, they are all in the queue tab
+        match in_flight {
+          0 => self.popup = Some(Box::new(popup)),
+          in_flight => self.toasts.push(ToastLevel::Info, format!("{summary}, {in_flight} still in flight")),
+        }
+      },
+      Action::ShowExchange(exchange) => {
+        let state = self.state.read().unwrap();
+        let popup = ResponsePopup::new(
+          state.display_exchange(&exchange),
+          state.plugins.render(&exchange.response),
+          state.wrap_lines,
+        )
+        .with_documented_status(state.documented_status(&exchange))
+        .with_local_times(state.local_times);
+        drop(state);
+        self.popup = Some(Box::new(popup));
+      },
+      Action::CompareExchanges(first, second) => {
+        let state = self.state.read().unwrap();
+        let popup = ResponseDiffPopup::new(state.display_exchange(&first), state.display_exchange(&second));
+        drop(state);
+        self.popup = Some(Box::new(popup));
+      },
+      Action::ShowResponseDiff(exchange) => {
+        let state = self.state.read().unwrap();
+        let popup: Box<dyn Popup> = match state.previous_exchange(&exchange) {
+          Some(previous) => {
+            Box::new(ResponseDiffPopup::new(state.display_exchange(previous), state.display_exchange(&exchange)))
+          },
+          None => {
+            Box::new(MessagePopup::new("Response Diff", "No previous response of this operation to compare with."))
+          },
+        };
+        drop(state);
+        self.popup = Some(popup);
+      },
+      Action::ShowExampleDiff(exchange) => {
+        let state = self.state.read().unwrap();
+        let operation =
+          exchange.operation.as_ref().and_then(|operation| state.operation_value(&operation.path, &operation.method));
+        let response = &exchange.response;
+        let example = operation.and_then(|operation| {
+          response_diff::declared_example(
+            operation,
+            &state.openapi_document,
+            response.status,
+            response.header("Content-Type"),
+          )
+        });
+        let popup: Box<dyn Popup> = match example {
+          Some((media_type, example)) => Box::new(ResponseDiffPopup::with_example(&media_type, &example, &exchange)),
+          None => {
+            Box::new(MessagePopup::new(
+              "Response vs Example",
+              format!("The spec declares no example for a {} response of this operation.", response.status),
+            ))
+          },
+        };
+        drop(state);
+        self.popup = Some(popup);
+      },
+      Action::RequestFailed(id, error) => {
+        self.state.write().unwrap().queue.fail(id, error.clone());
+        self.toasts.push(ToastLevel::Error, format!("Request failed: {error}"));
+      },
+      Action::ExportReport(report) => {
+        self.popup = match report.export() {
+          Ok(paths) => {
+            let paths = paths.iter().map(|path| path.display().to_string()).collect::<Vec<_>>().join("\n");
+            Some(Box::new(MessagePopup::new("Export", format!("Exported to\n{paths}"))))
+          },
+          Err(error) => Some(Box::new(MessagePopup::new("Export Failed", error.to_string()))),
+        };
+      },
+      Action::ExportSchema(name, schema) => {
+        let exported = self.state.read().unwrap().export_schema(&name, &schema);
+        self.popup = match exported {
+          Ok(path) => Some(Box::new(MessagePopup::new("Export", format!("Exported to {}", path.display())))),
+          Err(error) => Some(Box::new(MessagePopup::new("Export Failed", error.to_string()))),
+        };
+      },
+      Action::ShowTypes(name, schema) => {
+        let document = &self.state.read().unwrap().openapi_document;
+        let codes =
+          codegen::Language::iter().map(|language| (language, language.render(&name, &schema, document))).collect();
+        self.popup = Some(Box::new(TypesPopup::new(name, codes)));
+      },
+      Action::ShowPayload(name, payload) => {
+        self.popup = Some(Box::new(PayloadPopup::new(name, payload)));
+      },
+      Action::ExportTypes(name, extension, code) => {
+        let path = export_path(&name, &extension);
+        self.popup = match std::fs::write(&path, code) {
+          Ok(()) => Some(Box::new(MessagePopup::new("Export", format!("Exported to {}", path.display())))),
+          Err(error) => Some(Box::new(MessagePopup::new("Export Failed", error.to_string()))),
+        };
+      },
+      Action::ExportHar => {
+        let exported = self.state.read().unwrap().export_har();
+        self.popup = match exported {
+          Ok(Some(path)) => Some(Box::new(MessagePopup::new("Export", format!("Exported to {}", path.display())))),
+          Ok(None) => Some(Box::new(MessagePopup::new("Export", "No requests were sent in this session yet."))),
+          Err(error) => Some(Box::new(MessagePopup::new("Export Failed", error.to_string()))),
+        };
+      },
+      Action::ShowEnvironments => {
+        let state = self.state.read().unwrap();
+        let popup = EnvironmentPopup::new(state.display_environments(), state.active_environment_name());
+        drop(state);
+        self.popup = Some(Box::new(popup));
+      },
+      Action::SelectEnvironment(name) => {
+        let mut state = self.state.write().unwrap();
+        // picking an environment ends the command line override
+        state.environment_override = None;
+        state.workspace.active_environment = name;
+        state.workspace.save(&state.openapi_path)?;
+        drop(state);
+        self.popup = None;
+      },
+      Action::ShowServers => {
+        let state = self.state.read().unwrap();
+        let popup: Option<Box<dyn Popup>> = state
+          .active_server()
+          .filter(|server| !server.variables.is_empty())
+          .map(|server| Box::new(ServerPopup::new(server, state.workspace.server_variables.clone())) as Box<dyn Popup>);
+        drop(state);
+        self.popup = popup;
+      },
+      Action::SetServerVariables(values) => {
+        let mut state = self.state.write().unwrap();
+        state.workspace.server_variables.extend(values);
+        state.workspace.save(&state.openapi_path)?;
+        drop(state);
+        self.popup = None;
+      },
+      Action::ShowNote => {
+        let state = self.state.read().unwrap();
+        let Some(operation) = state.active_operation_key() else {
+          return Ok(None);
+        };
+        let popup = NotePopup::new(operation.clone(), state.workspace.note(&operation));
+        drop(state);
+        self.popup = Some(Box::new(popup));
+      },
+      Action::SaveNote(operation, note) => {
+        let mut state = self.state.write().unwrap();
+        state.workspace.set_note(&operation, &note);
+        state.workspace.save(&state.openapi_path)?;
+        drop(state);
+        self.popup = None;
+        return Ok(Some(Action::Update));
+      },
+      Action::ShowOperationScripts => {
+        if let Some(operation) = self.state.read().unwrap().active_operation_key() {
+          return Ok(Some(Action::ShowScripts(ScriptTarget::Operation(operation))));
+        }
+      },
+      Action::ShowCommands => self.popup = Some(Box::new(PalettePopup::new(self.commands()))),
+      Action::ShowHelp => self.popup = Some(Box::new(HelpPopup::new(self.help()))),
+      Action::RunCommand(action) => {
+        self.popup = None;
+        return Ok(Some(*action));
+      },
+      Action::ToggleWrap => {
+        let mut state = self.state.write().unwrap();
+        state.wrap_lines = !state.wrap_lines;
+      },
+      Action::ToggleCache => {
+        let mut state = self.state.write().unwrap();
+        state.cache = match state.cache {
+          Some(_) => None,
+          None => Some(Cache::default()),
+        };
+        let message = match state.cache {
+          Some(_) => "Response cache on, repeated GETs are answered from it while fresh",
+          None => "Response cache off and emptied",
+        };
+        drop(state);
+        self.toasts.push(ToastLevel::Info, message);
+      },
+      Action::ToggleLocalTimes => {
+        let mut state = self.state.write().unwrap();
+        state.local_times = !state.local_times;
+        let message = match state.local_times {
+          true => "Timestamps of responses annotated with the local time",
+          false => "Timestamps of responses shown as sent",
+        };
+        drop(state);
+        self.toasts.push(ToastLevel::Info, message);
+      },
+      Action::TogglePreview => {
+        let mut state = self.state.write().unwrap();
+        state.request_preview = !state.request_preview;
+      },
+      Action::ProbeHealth => self.probe_health(),
+      Action::HealthProbed(health) => self.state.write().unwrap().health = Some(*health),
+      Action::ClosePopup => {
+        self.popup = None;
+        if let Some(cancellation) = self.cases_cancellation.take() {
+          cancellation.cancel();
+        }
+      },
+      Action::ShowSaveRequest => {
+        let state = self.state.read().unwrap();
+        let Some((_, _, operation)) = state.active_operation() else {
+          return Ok(None);
+        };
+        let name = operation.operation_id.clone().unwrap_or_else(|| String::from("request"));
+        let name = match operation.tags.first() {
+          Some(tag) => format!("{tag}/{name}"),
+          None => name,
+        };
+        let folders = state
+          .workspace
+          .saved_requests
+          .iter()
+          .filter_map(|saved| saved.folder.clone())
+          .collect::<std::collections::BTreeSet<_>>()
+          .into_iter()
+          .collect();
+        drop(state);
+        self.popup = Some(Box::new(SavePopup::new(name, folders)));
+      },
+      Action::SaveRequest(path) => {
+        self.state.write().unwrap().save_active_request(&path)?;
+        self.popup = None;
+        self.toasts.push(ToastLevel::Success, format!("Saved to {path}"));
+      },
+      Action::ToggleBookmark => {
+        let mut state = self.state.write().unwrap();
+        let Some(operation) = state.active_operation().map(|(path, method, _)| OperationKey::new(&method, &path))
+        else {
+          return Ok(None);
+        };
+        let message = match state.workspace.toggle_bookmark(&operation) {
+          true => format!("Bookmarked {operation}"),
+          false => format!("Removed the bookmark of {operation}"),
+        };
+        state.workspace.save(&state.openapi_path)?;
+        // the operation leaves the favorites when they are the only ones listed
+        if state.favorites_only {
+          state.active_operation_index = state.active_operation_index.min(state.operations_len().saturating_sub(1));
+        }
+        self.toasts.push(ToastLevel::Info, message);
+        return Ok(Some(Action::Update));
+      },
+      Action::ToggleFavorites => {
+        let mut state = self.state.write().unwrap();
+        state.favorites_only = !state.favorites_only;
+        state.active_operation_index = 0;
+        return Ok(Some(Action::Update));
+      },
+      Action::ToggleSecrets => {
+        let mut state = self.state.write().unwrap();
+        state.reveal_secrets = !state.reveal_secrets;
+        let message = if state.reveal_secrets { "Secrets revealed" } else { "Secrets masked" };
+        self.toasts.push(ToastLevel::Info, message);
+      },
+      Action::Copy(text) => {
+        match self.copy_to_clipboard(text) {
+          Ok(()) => self.toasts.push(ToastLevel::Success, "Copied to clipboard"),
+          Err(error) => {
+            log::error!("Failed to copy to clipboard: {error:?}");
+            self.toasts.push(ToastLevel::Error, format!("Could not copy to clipboard: {error}"));
+          },
+        }
+      },
+      Action::OpenDocs => {
+        let url = {
+          let state = self.state.read().unwrap();
+          let operation_docs = state.active_operation().and_then(|(_, _, operation)| operation.external_docs.clone());
+          operation_docs.or_else(|| state.openapi_spec.external_docs.clone()).map(|docs| docs.url.to_string())
+        };
+        match url {
+          Some(url) => return Ok(Some(Action::OpenUrl(url))),
+          None => {
+            self.popup = Some(Box::new(MessagePopup::new(
+              "Documentation",
+              "Neither the operation nor the spec has externalDocs.",
+            )));
+          },
+        }
+      },
+      Action::OpenUrl(url) => {
+        if let Err(error) = crate::utils::open_in_browser(&url) {
+          self.popup = Some(Box::new(MessagePopup::new("Open Failed", error.to_string())));
+        }
+      },
+      _ if self.popup.is_some() => {
+        if let Some(popup) = self.popup.as_mut() {
+          return popup.update(action);
+        }
+      },
+      Action::FocusNext => self.focus_shown_pane(true)?,
+      Action::FocusPrev => self.focus_shown_pane(false)?,
+      Action::FocusPane(index) if index < self.panes.len() => {
+        // panes the layout hides are shown fullscreen
+        if !layout::panes(&self.layout()).contains(&index) {
+          self.fullscreen_pane_index = Some(index);
+        }
+        self.focus_pane(index)?
+      },
+      Action::Update => {
+        for pane in self.panes.iter_mut() {
+          pane.update(action.clone())?;
+        }
+      },
+      // the groups of the APIs pane, whichever pane is focused
+      Action::ExpandAll | Action::CollapseAll => {
+        if let Some(pane) = self.panes.first_mut() {
+          return pane.update(action);
+        }
+      },
+      Action::ToggleFullScreen => {
+        self.fullscreen_pane_index = self.fullscreen_pane_index.map_or(Some(self.focused_pane_index), |_| None);
+      },
+      Action::GrowPane | Action::ShrinkPane => {
+        let layout = self.layout();
+        if let Some(group) = layout::group_of(&layout, self.focused_pane_index) {
+          let mut state = self.state.write().unwrap();
+          let grow = action == Action::GrowPane;
+          state.workspace.pane_sizes.resize(&layout.name, group, self.focused_pane_index, grow);
+          state.workspace.save(&state.openapi_path)?;
+        }
+      },
+      Action::CycleLayout => {
+        let layouts = &self.config.layouts;
+        let active = layouts.iter().position(|layout| layout.name == self.layout().name);
+        if let Some(layout) = layouts.get(active.map_or(0, |index| (index + 1) % layouts.len())) {
+          return Ok(Some(Action::SelectLayout(layout.name.clone())));
+        }
+      },
+      Action::SelectKeymap(keymap) => {
+        self.config.set_keymap(keymap);
+        self.toasts.push(ToastLevel::Info, format!("Switched to the {keymap} keymap"));
+      },
+      Action::SelectLayout(name) => {
+        let mut state = self.state.write().unwrap();
+        state.workspace.active_layout = Some(name);
+        state.workspace.save(&state.openapi_path)?;
+        drop(state);
+        self.fullscreen_pane_index = None;
+        let shown = layout::panes(&self.layout());
+        if let Some(index) = shown.first().filter(|_| !shown.contains(&self.focused_pane_index)) {
+          self.focus_pane(*index)?;
+        }
+      },
+      _ => {
+        if let Some(pane) = self.panes.get_mut(self.focused_pane_index) {
+          return pane.update(action);
+        }
+      },
+    }
+    Ok(None)
+  }
+
+  fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
+    if let Some(popup) = self.popup.as_mut() {
+      if let Some(response) = popup.handle_key_events(key)? {
+        return Ok(Some(response));
+      }
+    } else if let Some(pane) = self.panes.get_mut(self.focused_pane_index) {
+      if let Some(response) = pane.handle_key_events(key)? {
+        return Ok(Some(response));
+      }
+    }
+    let counting = self.popup.is_none() && COUNTED_PANES.contains(&self.focused_pane_index);
+    let scopes = self.key_scopes();
+    let Some(mut actions) = self.keys.key(&self.config.keybindings, &scopes, key, counting) else {
+      return Ok(None);
+    };
+    let last = actions.pop();
+    self.send_actions(actions)?;
+    Ok(Some(EventResponse::Stop(last.unwrap_or(Action::Render))))
+  }
+
+  fn handle_paste_events(&mut self, text: String) -> Result<Option<EventResponse<Action>>> {
+    match self.popup.as_mut() {
+      Some(popup) => popup.handle_paste_events(text),
+      None => {
+        match self.panes.get_mut(self.focused_pane_index) {
+          Some(pane) => pane.handle_paste_events(text),
+          None => Ok(None),
+        }
+      },
+    }
+  }
+
+  fn handle_mouse_events(&mut self, mouse: MouseEvent) -> Result<Option<EventResponse<Action>>> {
+    const DOUBLE_CLICK: Duration = Duration::from_millis(400);
+    let scroll = match mouse.kind {
+      MouseEventKind::ScrollDown => Some(Action::Down),
+      MouseEventKind::ScrollUp => Some(Action::Up),
+      _ => None,
+    };
+    // popups only scroll, wherever the mouse is
+    if self.popup.is_some() {
+      return Ok(scroll.map(EventResponse::Stop));
+    }
+    let position = Position { x: mouse.column, y: mouse.row };
+    let Some(index) = self.pane_areas.iter().position(|area| area.contains(position)) else {
+      return Ok(None);
+    };
+    // the wheel scrolls the pane under the mouse, focused or not
+    if let Some(action) = scroll {
+      let action = self.panes[index].update(action)?;
+      return Ok(Some(EventResponse::Stop(action.unwrap_or(Action::Render))));
+    }
+    if mouse.kind != MouseEventKind::Down(MouseButton::Left) {
+      return Ok(None);
+    }
+    let now = Instant::now();
+    let double_click =
+      self.last_click.is_some_and(|(time, pane)| pane == index && now.duration_since(time) < DOUBLE_CLICK);
+    self.last_click = (!double_click).then_some((now, index));
+    if index != self.focused_pane_index {
+      self.focus_pane(index)?;
+    }
+    if double_click {
+      return Ok(Some(EventResponse::Stop(Action::ToggleFullScreen)));
+    }
+    Ok(Some(self.panes[index].handle_mouse_events(mouse)?.unwrap_or(EventResponse::Stop(Action::Render))))
+  }
+
+  fn unfinished_work(&self) -> Vec<String> {
+    if self.config.confirm_quit == Some(false) || self.state.read().unwrap().workspace.skip_quit_confirmation {
+      return vec![];
+    }
+    let mut work: Vec<String> = self.panes.iter().filter_map(|pane| pane.unsaved_edit()).collect();
+    match self.state.read().unwrap().queue.pending() {
+      0 => {},
+      1 => work.push(String::from("A request in flight")),
+      count => work.push(format!("{count} requests in flight")),
+    }
+    work
+  }
+
+  fn animating(&self) -> bool {
+    // the apis pane turns a spinner while the spec loads, the queue counts the time of the requests in flight
+    let state = self.state.read().unwrap();
+    state.loading || state.queue.pending() > 0 || self.popup.as_ref().is_some_and(|popup| popup.animating())
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
     let verical_layout = Layout::default()
       .direction(Direction::Vertical)
       .constraints(vec![Constraint::Fill(1), Constraint::Max(1)])
       .split(area);
+    let state = self.state.read().unwrap();
+    let cassette = match (&state.recorder, &state.player) {
+      (Some(_), _) => Span::styled("● recording  ", Style::default().fg(Color::LightRed)),
+      (_, Some(_)) => Span::styled("▶ replaying  ", Style::default().fg(Color::LightCyan)),
+      _ => Span::default(),
+    };
+    let cache = match state.cache {
+      Some(_) => Span::styled("⚡ cache  ", Style::default().fg(Color::LightCyan)),
+      None => Span::default(),
+    };
+    let environment = state.active_environment_name().map(String::from);
+    let info = &state.openapi_spec.info;
+    let spec = Span::styled(format!("{} {}  ", info.title, info.version), Style::default().fg(Color::Gray));
+    drop(state);
+    let spec_changed = match self.spec_changed {
+      true => Span::styled("⟳ spec changed  ", Style::default().fg(Color::LightYellow)),
+      false => Span::default(),
+    };
+    let in_flight = match self.state.read().unwrap().queue.pending() {
+      0 => Span::default(),
+      requests => Span::styled(format!("⇅ {requests} in flight  "), Style::default().fg(Color::LightYellow)),
+    };
+    let environment = Line::from(vec![
+      cassette,
+      cache,
+      spec_changed,
+      in_flight,
+      spec,
+      Span::styled("env: ", Style::default().fg(Color::DarkGray)),
+      match environment {
+        Some(environment) => Span::styled(environment, Style::default().fg(Color::LightGreen)),
+        None => Span::styled("none", Style::default().fg(Color::DarkGray)),
+      },
+    ]);
+    let status_layout = Layout::default()
+      .direction(Direction::Horizontal)
+      .constraints(vec![Constraint::Fill(1), Constraint::Length(environment.width().try_into()?)])
+      .split(verical_layout[1]);
     const ARROW: &str = symbols::scrollbar::HORIZONTAL.end;
-    frame.render_widget(
-      Line::from(vec![
-        Span::styled(format!("[l/h {ARROW} next/prev pane] [j/k {ARROW} next/prev item] [1-9 {ARROW} select tab] [g/b {ARROW} go/back definitions] [q {ARROW} quit]"), Style::default()),
-      ])
-      .style(Style::default().fg(Color::DarkGray)),
-      verical_layout[1],
-    );
+    // hints of the keys as configured, as many as fit
+    let hinted = [
+      (Action::ShowHelp, "help"),
+      (Action::ShowCommands, "commands"),
+      (Action::FocusNext, "next pane"),
+      (Action::Down, "next item"),
+      (Action::ShowEnvironments, "environment"),
+      (Action::Quit, "quit"),
+    ];
+    let mut hints = String::default();
+    for (action, label) in hinted {
+      let Some(key) = self.config.keybindings.key_of(KeyScope::Home, &action) else {
+        continue;
+      };
+      let hint = format!("[{key} {ARROW} {label}] ");
+      if hints.chars().count() + hint.chars().count() > usize::from(status_layout[0].width) {
+        break;
+      }
+      hints.push_str(&hint);
+    }
+    frame.render_widget(Line::from(hints).style(Style::default().fg(Color::DarkGray)), status_layout[0]);
+    frame.render_widget(environment, status_layout[1]);
 
+    self.pane_areas = vec![Rect::default(); self.panes.len()];
     if let Some(fullscreen_pane_index) = self.fullscreen_pane_index {
+      self.pane_areas[fullscreen_pane_index] = verical_layout[0];
       self.panes[fullscreen_pane_index].draw(frame, verical_layout[0])?;
     } else {
-      let outer_layout = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints(vec![Constraint::Fill(1), Constraint::Fill(3)])
-        .split(verical_layout[0]);
-
-      let left_panes = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints(vec![self.panes[0].height_constraint(), self.panes[1].height_constraint()])
-        .split(outer_layout[0]);
-
-      let right_panes = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints(vec![
-          self.panes[2].height_constraint(),
-          self.panes[3].height_constraint(),
-          self.panes[4].height_constraint(),
-        ])
-        .split(outer_layout[1]);
-
-      self.panes[0].draw(frame, left_panes[0])?;
-      self.panes[1].draw(frame, left_panes[1])?;
-      self.panes[2].draw(frame, right_panes[0])?;
-      self.panes[3].draw(frame, right_panes[1])?;
-      self.panes[4].draw(frame, right_panes[2])?;
+      let sizes = self.state.read().unwrap().workspace.pane_sizes.clone();
+      let heights: Vec<Constraint> = self.panes.iter().map(|pane| pane.height_constraint()).collect();
+      for (index, area) in layout::areas(&self.layout(), &heights, &sizes, verical_layout[0]) {
+        self.pane_areas[index] = area;
+        self.panes[index].draw(frame, area)?;
+      }
+    }
+
+    if let Some(popup) = self.popup.as_mut() {
+      let popup_area = centered_rect(80, 80, verical_layout[0]);
+      frame.render_widget(Clear, popup_area);
+      popup.draw(frame, popup_area)?;
+    }
+    self.draw_toasts(frame, verical_layout[0]);
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+  use serde_json::json;
+
+  use super::*;
+
+  #[test]
+  fn test_sent_request() {
+    let mut state = State {
+      dotenv_variables: BTreeMap::from([(String::from("token"), String::from("s3cr3t-token"))]),
+      ..State::default()
+    };
+    let exchange = |started_at: &str, authorization: &str| {
+      Exchange {
+        started_at: started_at.to_string(),
+        request: HttpRequest {
+          method: String::from("GET"),
+          path: String::from("/pets"),
+          headers: vec![(String::from("Authorization"), authorization.to_string())],
+          ..HttpRequest::default()
+        },
+        ..Exchange::default()
+      }
+    };
+    // sent during the session, the history holding it redacted
+    state.exchanges.push(exchange("2024-05-01T10:00:00Z", "Bearer s3cr3t-token"));
+    let redacted = exchange("2024-05-01T10:00:00Z", redaction::REDACTED);
+    assert_eq!(state.sent_request(&redacted).unwrap().header("Authorization"), Some("Bearer s3cr3t-token"));
+    // sent in an earlier session
+    let earlier = exchange("2024-04-01T10:00:00Z", redaction::REDACTED);
+    assert!(state.sent_request(&earlier).unwrap_err().contains("redacted"));
+    let referenced = exchange("2024-04-01T10:00:00Z", "Bearer {{token}}");
+    assert_eq!(state.sent_request(&referenced).unwrap().header("Authorization"), Some("Bearer s3cr3t-token"));
+  }
+
+  #[test]
+  fn test_record_short_secrets() -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("openapi-tui-record-{}", std::process::id()));
+    let openapi_path = dir.join("petstore.yaml").to_string_lossy().to_string();
+    let mut state = State { openapi_path: openapi_path.clone(), ..State::default() };
+    state.workspace.environments.push(Environment {
+      name: String::from("dev"),
+      variables: BTreeMap::from([(String::from("pin_secret"), String::from("4711"))]),
+      ..Environment::default()
+    });
+    state.workspace.active_environment = Some(String::from("dev"));
+    let exchange = Exchange {
+      request: HttpRequest {
+        method: String::from("POST"),
+        path: String::from("/cards/unlock"),
+        headers: vec![(String::from("X-Pin"), String::from("4711"))],
+        body: Some(String::from(r#"{"pin":"4711"}"#)),
+        ..HttpRequest::default()
+      },
+      ..Exchange::default()
+    };
+    state.record(exchange)?;
+    let saved = std::fs::read_to_string(history::path(&openapi_path))?;
+    std::fs::remove_dir_all(&dir)?;
+    assert!(!saved.contains("4711"));
+    assert!(saved.contains("{{pin_secret}}"));
+    assert_eq!(state.exchanges[0].request.header("X-Pin"), Some("4711"));
+    Ok(())
+  }
+
+  #[test]
+  fn test_use_environment() -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("openapi-tui-environment-{}", std::process::id()));
+    let openapi_path = dir.join("petstore.yaml").to_string_lossy().to_string();
+    let mut state = State { openapi_path: openapi_path.clone(), ..State::default() };
+    for name in ["dev", "prod"] {
+      state.workspace.environments.push(Environment { name: name.to_string(), ..Environment::default() });
     }
+    state.workspace.active_environment = Some(String::from("prod"));
+    let mut expected = state.workspace.clone();
+    expected.exercise(&OperationKey::new("get", "/pets"));
+
+    let mut home = Home::with_state(state);
+    assert!(home.use_environment("staging").is_err());
+    home.use_environment("dev")?;
+    let mut state = home.state.write().unwrap();
+    assert_eq!(state.active_environment().map(|environment| environment.name), Some(String::from("dev")));
+    state.exercise(&OperationKey::new("get", "/pets"))?;
+    let saved = Workspace::load(&openapi_path)?;
+    std::fs::remove_dir_all(&dir)?;
+    assert_eq!(saved, expected);
+    Ok(())
+  }
+
+  #[test]
+  fn test_load_env_files() -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("openapi-tui-dotenv-{}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    std::fs::write(dir.join(".env"), "# next to the spec\nTOKEN=from-dotenv\nTENANT=acme\n")?;
+    std::fs::write(dir.join("staging.env"), "TOKEN=\"from staging\"\n")?;
+    let openapi_path = dir.join("petstore.yaml").to_string_lossy().to_string();
+    let mut state = State { openapi_path, ..State::default() };
+    state.workspace.environments.push(Environment {
+      name: String::from("dev"),
+      variables: BTreeMap::from([(String::from("TENANT"), String::from("initech"))]),
+      ..Environment::default()
+    });
+    let mut home = Home::with_state(state);
+    let missing = home.load_env_files(&[dir.join("missing.env").to_string_lossy().to_string()]);
+    let loaded = home.load_env_files(&[dir.join("staging.env").to_string_lossy().to_string()]);
+    std::fs::remove_dir_all(&dir)?;
+    loaded?;
+    assert!(missing.is_err());
+
+    let mut state = home.state.write().unwrap();
+    let variables = state.variables();
+    assert_eq!(variables.get("TOKEN").map(String::as_str), Some("from staging"));
+    assert_eq!(variables.get("TENANT").map(String::as_str), Some("acme"));
+    // the active environment overrides the `.env` variables
+    state.workspace.active_environment = Some(String::from("dev"));
+    assert_eq!(state.variables().get("TENANT").map(String::as_str), Some("initech"));
+    Ok(())
+  }
+
+  #[test]
+  fn test_last_request() {
+    let document = json!({
+      "openapi": "3.0.0",
+      "info": { "title": "Petstore", "version": "1.0.0" },
+      "paths": {
+        "/pets": { "get": { "responses": {} } },
+        "/users": { "get": { "responses": {} } }
+      }
+    });
+    let mut state = State::default();
+    state.set_spec(ParsedSpec { spec: serde_json::from_value(document.clone()).unwrap(), document });
+    let exchange = |started_at: &str, path: &str, query: &str| {
+      Exchange {
+        started_at: started_at.to_string(),
+        operation: Some(OperationKey::new("get", path)),
+        request: HttpRequest {
+          method: String::from("GET"),
+          path: path.to_string(),
+          query: vec![(String::from("q"), query.to_string())],
+          ..HttpRequest::default()
+        },
+        ..Exchange::default()
+      }
+    };
+    let mut home = Home::with_state(state);
+    home.resend();
+    assert!(home.popup.is_some());
+
+    let mut state = home.state.write().unwrap();
+    let path = state.active_operation_key().unwrap().path;
+    let other = if path == "/pets" { "/users" } else { "/pets" };
+    state.history = vec![
+      exchange("2024-04-01T10:00:00Z", &path, "first"),
+      exchange("2024-04-01T10:01:00Z", &path, "last"),
+      exchange("2024-04-01T10:02:00Z", other, "other"),
+    ];
+    let last = state.last_request().unwrap().unwrap();
+    assert_eq!((last.path.as_str(), last.query[0].1.as_str()), (path.as_str(), "last"));
+  }
+
+  #[test]
+  fn test_coverage() {
+    let document = json!({
+      "openapi": "3.0.0",
+      "info": { "title": "Petstore", "version": "1.0.0" },
+      "paths": {
+        "/pets": {
+          "get": { "tags": ["pets"], "responses": {} },
+          "post": { "tags": ["pets"], "responses": {} }
+        },
+        "/users": { "get": { "tags": ["users"], "responses": {} } }
+      }
+    });
+    let mut state = State::default();
+    state.set_spec(ParsedSpec { spec: serde_json::from_value(document.clone()).unwrap(), document });
+    assert_eq!(state.coverage(None), (0, 3));
+    state.workspace.exercise(&OperationKey::new("post", "/pets"));
+    assert_eq!(state.coverage(None), (1, 3));
+    assert_eq!(state.coverage(Some("pets")), (1, 2));
+    assert_eq!(state.coverage(Some("users")), (0, 1));
+
+    state.untested_only = true;
+    let listed = |state: &State| {
+      let mut operations: Vec<String> =
+        state.operations().map(|(path, method, _)| format!("{method} {path}")).collect();
+      operations.sort();
+      operations
+    };
+    assert_eq!(listed(&state), vec!["GET /pets", "GET /users"]);
+    state.workspace.exercise(&OperationKey::new("get", "/users"));
+    assert_eq!(listed(&state), vec!["GET /pets"]);
+    state.untested_only = false;
+    assert_eq!(listed(&state).len(), 3);
+  }
+
+  #[test]
+  fn test_untagged_operations() {
+    let document = json!({
+      "openapi": "3.0.0",
+      "info": { "title": "Petstore", "version": "1.0.0" },
+      "tags": [{ "name": "pets" }],
+      "paths": {
+        "/pets": { "get": { "tags": ["pets"], "responses": {} } },
+        "/health": { "get": { "responses": {} }, "head": { "tags": [], "responses": {} } }
+      }
+    });
+    let mut state = State::default();
+    state.set_spec(ParsedSpec { spec: serde_json::from_value(document.clone()).unwrap(), document });
+    let labels: Vec<&str> = state.tag_tree.iter().map(|node| node.label.as_str()).collect();
+    assert_eq!(labels, vec!["pets", tags::UNTAGGED]);
+    state.active_tag_name = Some(tags::UNTAGGED.to_string());
+    let mut listed: Vec<String> = state.operations().map(|(path, method, _)| format!("{method} {path}")).collect();
+    listed.sort();
+    assert_eq!(listed, vec!["GET /health", "HEAD /health"]);
+    assert_eq!(state.coverage(Some(tags::UNTAGGED)), (0, 2));
+
+    let document = json!({ "paths": { "/pets": { "get": { "tags": ["pets"] } } } });
+    assert!(tags::tree(&document).iter().all(|node| node.label != tags::UNTAGGED));
+  }
+
+  #[test]
+  fn test_restore_session() -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("openapi-tui-session-{}", std::process::id()));
+    let openapi_path = dir.join("petstore.yaml").to_string_lossy().to_string();
+    let document = json!({
+      "openapi": "3.0.0",
+      "info": { "title": "Petstore", "version": "1.0.0" },
+      "tags": [{ "name": "pets" }, { "name": "store" }],
+      "paths": {
+        "/pets": {
+          "get": { "tags": ["pets"], "responses": {} },
+          "post": { "tags": ["pets"], "responses": {} }
+        },
+        "/store/inventory": { "get": { "tags": ["store"], "responses": {} } }
+      }
+    });
+    let spec = || ParsedSpec { spec: serde_json::from_value(document.clone()).unwrap(), document: document.clone() };
+    let mut state = State { openapi_path: openapi_path.clone(), ..State::default() };
+    state.set_spec(spec());
+    state.active_tag_name = Some(String::from("pets"));
+    state.active_operation_index = 1;
+    let selected = state.active_operation().map(|(path, method, _)| format!("{method} {path}"));
+    let mut home = Home::with_state(state);
+    home.focus_pane(2)?;
+    home.fullscreen_pane_index = Some(2);
+    home.save_session()?;
+
+    let mut state =
+      State { openapi_path: openapi_path.clone(), workspace: Workspace::load(&openapi_path)?, ..State::default() };
+    state.set_spec(spec());
+    let mut restored = Home::with_state(state);
+    restored.restore_session()?;
+    assert_eq!(restored.focused_pane_index, 2);
+    assert_eq!(restored.fullscreen_pane_index, Some(2));
+    let state = restored.state.read().unwrap();
+    assert_eq!(state.active_tag_name.as_deref(), Some("pets"));
+    assert_eq!(state.active_operation().map(|(path, method, _)| format!("{method} {path}")), selected);
+    drop(state);
+
+    // a tag, operation and panes the spec no longer has are left alone
+    std::fs::remove_dir_all(&dir)?;
+    let mut state = State { openapi_path, ..State::default() };
+    state.workspace.session = Session {
+      tag: Some(String::from("users")),
+      operation: Some(OperationKey::new("delete", "/users")),
+      focused_pane: 99,
+      fullscreen_pane: Some(99),
+      ..Session::default()
+    };
+    state.set_spec(spec());
+    let mut stale = Home::with_state(state);
+    stale.restore_session()?;
+    assert_eq!(stale.focused_pane_index, 0);
+    assert_eq!(stale.fullscreen_pane_index, None);
+    let state = stale.state.read().unwrap();
+    assert_eq!(state.active_tag_name, None);
+    assert_eq!(state.active_operation_index, 0);
     Ok(())
   }
+
+  #[test]
+  fn test_save_note() -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("openapi-tui-note-{}", std::process::id()));
+    let openapi_path = dir.join("petstore.yaml").to_string_lossy().to_string();
+    let operation = OperationKey::new("get", "/pets");
+    let mut home = Home::with_state(State { openapi_path: openapi_path.clone(), ..State::default() });
+    home.popup = Some(Box::new(NotePopup::new(operation.clone(), None)));
+    let action = home.update(Action::SaveNote(operation.clone(), String::from("returns 200 on failure\n")));
+    let saved = Workspace::load(&openapi_path);
+    std::fs::remove_dir_all(&dir)?;
+    assert_eq!(action?, Some(Action::Update));
+    assert!(home.popup.is_none());
+    assert_eq!(saved?.note(&operation), Some("returns 200 on failure"));
+
+    // saving a blank note removes it
+    home.update(Action::SaveNote(operation.clone(), String::from(" ")))?;
+    let saved = Workspace::load(&openapi_path);
+    std::fs::remove_dir_all(&dir)?;
+    assert_eq!(saved?.note(&operation), None);
+    assert_eq!(home.state.read().unwrap().workspace.notes, BTreeMap::new());
+    Ok(())
+  }
+
+  #[test]
+  fn test_call_request() {
+    let document = json!({
+      "openapi": "3.0.0",
+      "info": { "title": "Petstore", "version": "1.0.0" },
+      "servers": [{ "url": "https://petstore.example.com" }],
+      "paths": {
+        "/pets/{petId}": {
+          "get": {
+            "operationId": "getPet",
+            "parameters": [
+              { "name": "petId", "in": "path", "required": true, "schema": { "type": "integer" } },
+              { "name": "X-Tenant", "in": "header", "schema": { "type": "string" } }
+            ],
+            "responses": {}
+          }
+        },
+        "/pets": {
+          "post": {
+            "requestBody": { "content": { "application/json": { "schema": { "type": "object" } } } },
+            "responses": {}
+          }
+        }
+      }
+    });
+    let mut state = State::default();
+    state.set_spec(ParsedSpec { spec: serde_json::from_value(document.clone()).unwrap(), document });
+    state.workspace.environments.push(Environment {
+      name: String::from("dev"),
+      variables: BTreeMap::from([(String::from("tenant"), String::from("acme"))]),
+      headers: BTreeMap::from([(String::from("Authorization"), String::from("Bearer dev-token"))]),
+      ..Environment::default()
+    });
+    state.workspace.active_environment = Some(String::from("dev"));
+    let draft = state.workspace.drafts.entry(String::from("getPet")).or_default();
+    draft.set_parameter("path", "petId", String::from("1"));
+    draft.set_parameter("query", "verbose", String::from("true"));
+
+    let parameters = [
+      (String::from("petId"), String::from("42")),
+      (String::from("X-Tenant"), String::from("{{tenant}}")),
+      (String::from("fields"), String::from("name")),
+    ];
+    let (request, key) = state.call_request("getPet", &parameters, None).unwrap();
+    assert_eq!(key, OperationKey::new("get", "/pets/{petId}"));
+    assert_eq!(request.path, "/pets/42");
+    assert_eq!(request.header("X-Tenant"), Some("acme"));
+    assert_eq!(request.header("Authorization"), Some("Bearer dev-token"));
+    // the draft keeps its other values, unknown parameters go to the query
+    assert_eq!(request.query, vec![
+      (String::from("verbose"), String::from("true")),
+      (String::from("fields"), String::from("name"))
+    ]);
+
+    let (request, key) = state.call_request("POST /pets", &[], Some(String::from(r#"{"name":"Rex"}"#))).unwrap();
+    assert_eq!((request.method.as_str(), key), ("POST", OperationKey::new("post", "/pets")));
+    assert_eq!(request.body.as_deref(), Some(r#"{"name":"Rex"}"#));
+    assert!(state.call_request("deletePet", &[], None).is_none());
+  }
+
+  #[test]
+  fn test_spec_loaded() -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("openapi-tui-loading-{}", std::process::id()));
+    let openapi_path = dir.join("petstore.yaml").to_string_lossy().to_string();
+    let session = Session { tag: Some(String::from("store")), focused_pane: 6, ..Session::default() };
+    Workspace { session: session.clone(), ..Workspace::default() }.save(&openapi_path)?;
+    let document = json!({
+      "openapi": "3.0.0",
+      "info": { "title": "Petstore", "version": "1.0.0" },
+      "tags": [{ "name": "pets" }, { "name": "store" }],
+      "paths": {
+        "/pets": { "get": { "tags": ["pets"], "responses": {} } },
+        "/store/inventory": { "get": { "tags": ["store"], "responses": {} } }
+      }
+    });
+
+    let mut home = Home::loading(openapi_path.clone())?;
+    home.init()?;
+    assert!(home.state.read().unwrap().loading);
+    assert_eq!(home.state.read().unwrap().active_tag_name, None);
+    // imports wait for the spec, quitting before it loads keeps the saved session
+    home.import(&[dir.join("missing.http").to_string_lossy().to_string()])?;
+    assert!(home.popup.is_none());
+    home.update(Action::Quit)?;
+    assert_eq!(Workspace::load(&openapi_path)?.session, session);
+
+    let parsed = ParsedSpec { spec: serde_json::from_value(document.clone()).unwrap(), document };
+    let loaded = home.update(Action::SpecLoaded(Box::new(parsed)));
+    std::fs::remove_dir_all(&dir)?;
+    loaded?;
+    let state = home.state.read().unwrap();
+    assert!(!state.loading);
+    assert_eq!(state.tag_tree.iter().map(|node| node.label.as_str()).collect::<Vec<_>>(), vec!["pets", "store"]);
+    assert_eq!(state.active_tag_name.as_deref(), Some("store"));
+    assert_eq!(home.focused_pane_index, 6);
+    // the import waiting for the spec ran, and failed on the missing file
+    assert!(home.popup.is_some());
+    Ok(())
+  }
+
+  #[test]
+  fn test_reload_spec() -> Result<()> {
+    let openapi_path = std::env::temp_dir().join(format!("openapi-tui-reload-{}.yaml", std::process::id()));
+    let document = json!({
+      "openapi": "3.0.0",
+      "info": { "title": "Petstore", "version": "1.0.0" },
+      "tags": [{ "name": "pets" }],
+      "paths": { "/pets": { "get": { "tags": ["pets"], "responses": {} } } }
+    });
+    let mut state = State { openapi_path: openapi_path.to_string_lossy().to_string(), ..State::default() };
+    state.set_spec(ParsedSpec { spec: serde_json::from_value(document.clone()).unwrap(), document });
+    state.active_tag_name = Some(String::from("pets"));
+    let mut home = Home::with_state(state);
+    home.watch_remote(Some(0), false);
+    assert_eq!(home.refresh_interval, Some(Duration::from_secs(1)));
+    home.watch_remote(Some(30), true);
+    assert_eq!(home.refresh_interval, None);
+
+    home.update(Action::SpecChanged)?;
+    assert!(home.spec_changed);
+    assert!(home.toasts.shown()[0].message.starts_with("The spec changed on the server"));
+
+    // reloaded from the cached copy offline, resuming where it was left
+    let runtime = tokio::runtime::Runtime::new()?;
+    let _guard = runtime.enter();
+    let (command_tx, mut command_rx) = tokio::sync::mpsc::unbounded_channel();
+    home.register_action_handler(command_tx)?;
+    home.focus_pane(6)?;
+    home.update(Action::ReloadSpec)?;
+    assert!(!home.spec_changed);
+    let session = home.state.read().unwrap().workspace.session.clone();
+    assert_eq!((session.tag.as_deref(), session.focused_pane), (Some("pets"), 6));
+    let loaded = runtime.block_on(command_rx.recv());
+    assert!(matches!(loaded, Some(Action::SpecFailed(path, _)) if path == openapi_path.to_string_lossy()));
+    Ok(())
+  }
+
+  #[test]
+  fn test_copy() -> Result<()> {
+    let document = json!({
+      "openapi": "3.0.0",
+      "info": { "title": "Petstore", "version": "1.0.0" },
+      "servers": [{ "url": "https://petstore.example.com/v1" }],
+      "paths": {
+        "/pets/{petId}": {
+          "get": {
+            "operationId": "getPet",
+            "parameters": [
+              { "name": "petId", "in": "path", "required": true, "schema": { "type": "integer" } },
+              { "name": "expand", "in": "query", "schema": { "type": "string" } }
+            ],
+            "responses": {}
+          },
+          "delete": { "responses": {} }
+        }
+      }
+    });
+    let mut state = State::default();
+    state.set_spec(ParsedSpec { spec: serde_json::from_value(document.clone()).unwrap(), document });
+    state.workspace.environments.push(Environment {
+      name: String::from("dev"),
+      variables: BTreeMap::from([(String::from("petId"), String::from("42"))]),
+      ..Environment::default()
+    });
+    state.workspace.active_environment = Some(String::from("dev"));
+    let draft = state.workspace.drafts.entry(String::from("getPet")).or_default();
+    draft.set_parameter("path", "petId", String::from("{{petId}}"));
+    draft.set_parameter("query", "expand", String::from("owner"));
+    let index = state.operations().position(|(_, _, operation)| operation.operation_id.is_some());
+    state.active_operation_index = index.unwrap();
+    let mut home = Home::with_state(state);
+
+    let copied = |url: &str| Some(Action::Copy(url.to_string()));
+    assert_eq!(home.update(Action::CopyUrl)?, copied("https://petstore.example.com/v1/pets/42?expand=owner"));
+    assert_eq!(home.update(Action::CopyPath)?, copied("/pets/{petId}"));
+    assert_eq!(home.update(Action::CopyOperationId)?, copied("getPet"));
+
+    let index = home.state.read().unwrap().operations().position(|(_, method, _)| method == "DELETE").unwrap();
+    home.state.write().unwrap().active_operation_index = index;
+    assert_eq!(home.update(Action::CopyOperationId)?, None);
+    assert_eq!(home.toasts.shown()[0].message, "The operation has no operationId");
+    Ok(())
+  }
+
+  #[test]
+  fn test_confirm_quit() -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("openapi-tui-quit-{}", std::process::id()));
+    let openapi_path = dir.join("petstore.yaml").to_string_lossy().to_string();
+    let document = json!({
+      "openapi": "3.0.0",
+      "info": { "title": "Petstore", "version": "1.0.0" },
+      "paths": {
+        "/pets": {
+          "get": {
+            "parameters": [{ "name": "limit", "in": "query", "schema": { "type": "integer" } }],
+            "responses": {}
+          }
+        }
+      }
+    });
+    let mut state = State { openapi_path: openapi_path.clone(), ..State::default() };
+    state.set_spec(ParsedSpec { spec: serde_json::from_value(document.clone()).unwrap(), document });
+    let mut home = Home::with_state(state);
+    assert_eq!(home.unfinished_work(), Vec::<String>::new());
+    home.focus_pane(6)?;
+    home.panes[6].update(Action::Update)?;
+    home.panes[6].handle_key_events(KeyEvent::from(crossterm::event::KeyCode::Char('i')))?;
+    assert_eq!(home.unfinished_work(), vec![String::from("The value being edited in the request pane")]);
+
+    // asked first, the session is saved once confirmed
+    home.update(Action::Quit)?;
+    assert!(home.popup.is_some());
+    assert!(!Workspace::path(&openapi_path).exists());
+    home.config.confirm_quit = Some(false);
+    assert_eq!(home.unfinished_work(), Vec::<String>::new());
+    home.config.confirm_quit = None;
+    home.update(Action::ConfirmQuit(true))?;
+    let saved = Workspace::load(&openapi_path);
+    std::fs::remove_dir_all(&dir)?;
+    let saved = saved?;
+    assert!(saved.skip_quit_confirmation);
+    assert_eq!(saved.session.focused_pane, 6);
+    assert_eq!(home.unfinished_work(), Vec::<String>::new());
+    Ok(())
+  }
+
+  #[test]
+  fn test_start_at() -> Result<()> {
+    let document = json!({
+      "openapi": "3.0.0",
+      "info": { "title": "Petstore", "version": "1.0.0" },
+      "tags": [{ "name": "pets" }, { "name": "store" }],
+      "paths": {
+        "/pets": {
+          "get": { "operationId": "listPets", "tags": ["pets"], "responses": {} },
+          "post": { "operationId": "addPet", "tags": ["pets"], "responses": {} }
+        },
+        "/store/inventory": { "get": { "operationId": "getInventory", "tags": ["store"], "responses": {} } }
+      }
+    });
+    let parsed = || ParsedSpec { spec: serde_json::from_value(document.clone()).unwrap(), document: document.clone() };
+    let selected = |home: &Home| {
+      let state = home.state.read().unwrap();
+      let operation_id = state.active_operation().and_then(|(_, _, operation)| operation.operation_id.clone());
+      (state.active_tag_name.clone(), operation_id, home.focused_pane_index)
+    };
+    let started = |operation: Option<&str>, tag: Option<&str>| -> Result<Home> {
+      let mut state = State::default();
+      state.set_spec(parsed());
+      let mut home = Home::with_state(state);
+      home.start_at(operation.map(String::from), tag.map(String::from))?;
+      Ok(home)
+    };
+
+    let home = started(Some("addPet"), Some("pets"))?;
+    assert_eq!(selected(&home), (Some(String::from("pets")), Some(String::from("addPet")), 6));
+    assert!(home.popup.is_none());
+    // an operation the tag does not list is selected among all of them
+    let home = started(Some("getInventory"), Some("pets"))?;
+    assert_eq!(selected(&home), (None, Some(String::from("getInventory")), 6));
+    let home = started(Some("GET /pets"), None)?;
+    assert_eq!(selected(&home), (None, Some(String::from("listPets")), 6));
+    let home = started(None, Some("store"))?;
+    assert_eq!(selected(&home), (Some(String::from("store")), Some(String::from("getInventory")), 0));
+    let home = started(Some("deletePet"), Some("users"))?;
+    assert_eq!(selected(&home), (None, Some(String::from("listPets")), 0));
+    assert!(home.popup.is_some());
+
+    // started once the spec loads, over the restored session
+    let openapi_path = std::env::temp_dir().join(format!("openapi-tui-start-{}.yaml", std::process::id()));
+    let mut home = Home::loading(openapi_path.to_string_lossy().to_string())?;
+    home.start_at(Some(String::from("addPet")), None)?;
+    assert_eq!(selected(&home), (None, None, 0));
+    home.update(Action::SpecLoaded(Box::new(parsed())))?;
+    assert_eq!(selected(&home), (None, Some(String::from("addPet")), 6));
+    Ok(())
+  }
+
+  #[test]
+  fn test_export_entries() {
+    let document = json!({
+      "openapi": "3.0.0",
+      "info": { "title": "Petstore", "version": "1.0.0" },
+      "servers": [{ "url": "https://petstore.example.com" }],
+      "paths": {
+        "/pets/{petId}": {
+          "put": {
+            "operationId": "updatePet",
+            "parameters": [
+              { "name": "petId", "in": "path", "required": true, "schema": { "type": "integer" } },
+              { "name": "dryRun", "in": "query", "schema": { "type": "boolean" } }
+            ],
+            "requestBody": { "content": { "application/json": { "schema": { "type": "object" } } } },
+            "responses": {}
+          }
+        }
+      }
+    });
+    let mut state = State::default();
+    state.set_spec(ParsedSpec { spec: serde_json::from_value(document.clone()).unwrap(), document });
+    let draft = state.workspace.drafts.entry(String::from("updatePet")).or_default();
+    draft.set_parameter("path", "petId", String::from("{{petId}}"));
+    draft.set_parameter("query", "dryRun", String::from("true"));
+    draft.set_parameter("header", "X-Request-Id", String::from("42"));
+    draft.body = Some(String::from(r#"{"name":"Rex"}"#));
+
+    let (path, method, operation) = state.active_operation().unwrap();
+    let entry = state.http_file_entry(&path, &method, operation);
+    assert_eq!(entry.name, "updatePet");
+    assert_eq!(entry.request.path, "/pets/{{petId}}");
+    assert_eq!(entry.request.query, vec![(String::from("dryRun"), String::from("true"))]);
+    assert_eq!(entry.request.header("X-Request-Id"), Some("42"));
+    assert_eq!(entry.request.body.as_deref(), Some(r#"{"name":"Rex"}"#));
+
+    let entry = state.postman_entry(&path, &method, operation);
+    assert_eq!(entry.name, "updatePet");
+    assert_eq!(entry.request.path, "/pets/{{petId}}");
+    assert_eq!(entry.request.query, vec![(String::from("dryRun"), String::from("true"))]);
+    assert_eq!(entry.request.header("X-Request-Id"), Some("42"));
+    assert_eq!(entry.request.body.as_deref(), Some(r#"{"name":"Rex"}"#));
+  }
+
+  #[test]
+  fn test_help() {
+    let mut home = Home::with_state(State::default());
+    home.config = json5::from_str(
+      r#"{ "keybindings": {
+        "Home": { "<j>": "Down", "<ctrl-n>": "Down", "<?>": "ShowHelp", "<3>": { "Tab": 2 } },
+        "Address": { "<o>": "SendRequest" }
+      } }"#,
+    )
+    .unwrap();
+    home.focused_pane_index = KeyScope::PANES.iter().position(|scope| *scope == KeyScope::Address).unwrap();
+    let sections = home.help();
+    let lines = |section: &HelpSection| {
+      let bindings = section.bindings.iter().map(|(keys, description)| format!("{}: {description}", keys.join(", ")));
+      std::iter::once(section.title.clone()).chain(bindings).collect::<Vec<_>>()
+    };
+    assert_eq!(sections.iter().map(lines).collect::<Vec<_>>(), vec![vec!["Address pane", "o: Send request"], vec![
+      "Everywhere",
+      "j, ctrl-n: Move down in lists",
+      "3: Select tab 3",
+      "?: Show the keys"
+    ],]);
+  }
+
+  #[test]
+  fn test_override_base_url() {
+    let document = json!({
+      "servers": [{ "url": "https://petstore.example.com" }],
+      "paths": { "/pets": { "servers": [{ "url": "https://pets.example.com" }], "get": {} } }
+    });
+    let mut home = Home::with_state(State { openapi_document: document, ..State::default() });
+    home.override_base_url(Some(String::from("http://localhost:8080/")));
+    let state = home.state.read().unwrap();
+    assert_eq!(state.base_url(), "http://localhost:8080");
+    assert_eq!(state.operation_base_url("/pets", "GET"), "http://localhost:8080");
+    drop(state);
+    home.override_base_url(None);
+    assert_eq!(home.state.read().unwrap().operation_base_url("/pets", "GET"), "https://pets.example.com");
+  }
+
+  #[test]
+  fn test_operation_server() {
+    let document = json!({
+      "servers": [{ "url": "https://{region}.example.com", "variables": { "region": { "default": "eu" } } }],
+      "paths": {
+        "/pets": {
+          "servers": [{ "url": "https://pets.example.com" }],
+          "get": { "servers": [{ "url": "https://read.pets.example.com" }] },
+          "post": {}
+        },
+        "/users": { "get": {} }
+      }
+    });
+    let mut state = State { openapi_document: document, ..State::default() };
+    assert_eq!(state.operation_base_url("/pets", "GET"), "https://read.pets.example.com");
+    assert_eq!(state.operation_base_url("/pets", "POST"), "https://pets.example.com");
+    assert_eq!(state.operation_base_url("/users", "GET"), "https://eu.example.com");
+    state.workspace.server_variables.insert(String::from("region"), String::from("us"));
+    assert_eq!(state.operation_base_url("/users", "GET"), "https://us.example.com");
+  }
+
+  #[test]
+  fn test_preview_request() {
+    let document = json!({
+      "openapi": "3.0.0",
+      "info": { "title": "Petstore", "version": "1.0.0" },
+      "servers": [{ "url": "https://petstore.example.com" }],
+      "paths": {
+        "/pets/{petId}": {
+          "get": {
+            "operationId": "getPet",
+            "parameters": [
+              { "name": "petId", "in": "path", "required": true, "schema": { "type": "integer" } },
+              { "name": "note", "in": "query", "schema": { "type": "string" } }
+            ],
+            "responses": { "200": { "description": "The pet" } }
+          }
+        }
+      }
+    });
+    let mut state = State::default();
+    state.set_spec(ParsedSpec { spec: serde_json::from_value(document.clone()).unwrap(), document });
+    state.workspace.environments.push(Environment {
+      name: String::from("dev"),
+      variables: BTreeMap::from([
+        (String::from("petId"), String::from("42")),
+        (String::from("tenant"), String::from("acme")),
+        (String::from("authToken"), String::from("s3cr3t-token")),
+      ]),
+      headers: BTreeMap::from([(String::from("X-Tenant"), String::from("{{tenant}}"))]),
+      signing: None,
+      network: None,
+    });
+    state.workspace.active_environment = Some(String::from("dev"));
+    let draft = state.workspace.drafts.entry(String::from("getPet")).or_default();
+    draft.set_parameter("path", "petId", String::from("{{petId}}"));
+    draft.set_parameter("query", "note", String::from("{{authToken}}"));
+
+    let preview = state.preview_request().unwrap();
+    assert_eq!(preview.path, "/pets/42");
+    assert_eq!(preview.header("X-Tenant"), Some("acme"));
+    assert_eq!(preview.query, vec![(String::from("note"), secrets::MASK.to_string())]);
+
+    state.reveal_secrets = true;
+    let revealed = state.preview_request().unwrap();
+    assert_eq!(revealed.query, vec![(String::from("note"), String::from("s3cr3t-token"))]);
+  }
 }
@@ -0,0 +1,157 @@
+use std::sync::Arc;
+
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+  prelude::*,
+  widgets::{block::*, *},
+};
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::{
+  action::Action,
+  mock::{self, MockHit, Route},
+  pages::Page,
+  theme,
+  tui::{EventResponse, Frame},
+};
+
+/// Runs the mock server of `openapi-tui mock` and lists the requests it receives as they come.
+pub struct MockPage {
+  openapi_path: String,
+  address: String,
+  routes: Arc<Vec<Route>>,
+  /// Bound when the page is created so that a busy port fails before the TUI starts, served once actions can be
+  /// sent.
+  listener: Option<std::net::TcpListener>,
+  /// Newest first.
+  hits: Vec<MockHit>,
+  hit_index: usize,
+}
+
+impl MockPage {
+  pub fn new(openapi_path: String, port: u16) -> Result<Self> {
+    let document = serde_yaml::from_reader(std::fs::File::open(&openapi_path)?)?;
+    let listener = std::net::TcpListener::bind(("127.0.0.1", port))?;
+    listener.set_nonblocking(true)?;
+    Ok(Self {
+      openapi_path,
+      address: format!("http://{}", listener.local_addr()?),
+      routes: Arc::new(mock::routes(&document)),
+      listener: Some(listener),
+      hits: Vec::default(),
+      hit_index: 0,
+    })
+  }
+
+  fn detail_lines(&self) -> Vec<Line<'_>> {
+    let Some(hit) = self.hits.get(self.hit_index) else {
+      return vec![Line::styled(format!("Waiting for requests on {}", self.address), Style::default().dim())];
+    };
+    let operation = match &hit.operation {
+      Some(operation) => format!("answered by {operation}"),
+      None => String::from("no matching operation"),
+    };
+    let mut lines = vec![
+      Line::from(vec![
+        Span::styled(format!("{} {}", hit.method, hit.target), Style::default().bold()),
+        Span::styled(format!("  {operation}"), Style::default().dim()),
+      ]),
+      Line::default(),
+    ];
+    lines.extend(hit.headers.iter().map(|(name, value)| {
+      Line::from(vec![Span::styled(format!("{name}: "), Style::default().dim()), Span::raw(value.as_str())])
+    }));
+    if !hit.body.is_empty() {
+      lines.push(Line::default());
+      let body = match serde_json::from_str::<serde_json::Value>(&hit.body) {
+        Ok(json) => serde_json::to_string_pretty(&json).unwrap_or(hit.body.clone()),
+        Err(_) => hit.body.clone(),
+      };
+      lines.extend(body.lines().map(|line| Line::raw(line.to_string())));
+    }
+    lines
+  }
+}
+
+impl Page for MockPage {
+  fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+    if let Some(listener) = self.listener.take() {
+      tokio::spawn(mock::serve(tokio::net::TcpListener::from_std(listener)?, self.routes.clone(), tx));
+    }
+    Ok(())
+  }
+
+  fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
+    let response = match key.code {
+      KeyCode::Down | KeyCode::Char('j') | KeyCode::Char('J') => EventResponse::Stop(Action::Down),
+      KeyCode::Up | KeyCode::Char('k') | KeyCode::Char('K') => EventResponse::Stop(Action::Up),
+      _ => return Ok(None),
+    };
+    Ok(Some(response))
+  }
+
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    let hits_len = self.hits.len().max(1);
+    match action {
+      Action::Down => self.hit_index = self.hit_index.saturating_add(1) % hits_len,
+      Action::Up => self.hit_index = self.hit_index.saturating_add(hits_len - 1) % hits_len,
+      Action::MockRequestReceived(hit) => {
+        // Keeps the selected request selected, unless it is the newest one.
+        if self.hit_index > 0 {
+          self.hit_index += 1;
+        }
+        self.hits.insert(0, *hit);
+        return Ok(Some(Action::Render));
+      },
+      _ => {},
+    }
+    Ok(None)
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    let layout = Layout::default()
+      .direction(Direction::Horizontal)
+      .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+      .split(area);
+
+    let items = self.hits.iter().map(|hit| {
+      Line::from(vec![
+        Span::styled(format!("{} ", hit.received_at), Style::default().dim()),
+        Span::styled(format!("{} ", hit.status), theme::current().status_code(hit.status)),
+        Span::styled(format!("{:7}", hit.method), Style::default().bold()),
+        Span::raw(hit.target.as_str()),
+      ])
+    });
+    let mut list_state = ListState::default().with_selected((!self.hits.is_empty()).then_some(self.hit_index));
+    frame.render_stateful_widget(
+      List::new(items)
+        .block(
+          Block::default()
+            .title(format!("Mock Server {}", self.address))
+            .borders(Borders::ALL)
+            .border_style(theme::current().focused_border)
+            .border_type(BorderType::Thick)
+            .title_bottom(
+              Line::from(format!("{} operations of {}", self.routes.len(), self.openapi_path)).right_aligned(),
+            ),
+        )
+        .highlight_symbol(symbols::scrollbar::HORIZONTAL.end)
+        .highlight_spacing(HighlightSpacing::Always)
+        .highlight_style(theme::current().selection),
+      layout[0],
+      &mut list_state,
+    );
+
+    const ARROW: &str = symbols::scrollbar::HORIZONTAL.end;
+    frame.render_widget(
+      Paragraph::new(self.detail_lines()).wrap(Wrap { trim: false }).block(
+        Block::default().title("Request").borders(Borders::ALL).padding(Padding::horizontal(1)).title_bottom(
+          Line::from(format!("[j/k {ARROW} request] [q {ARROW} quit]")).style(Style::default().dim()).right_aligned(),
+        ),
+      ),
+      layout[1],
+    );
+    Ok(())
+  }
+}
@@ -0,0 +1,215 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+  client::HttpResponse,
+  request::HttpRequest,
+  script::{self, Hooks},
+  workspace::OperationKey,
+};
+
+/// Pages followed at most, in case the API keeps pointing at new pages.
+pub const MAX_PAGES: usize = 100;
+
+/// Body fields the url of the next page is commonly found at, as JSON pointers.
+const NEXT_POINTERS: [&str; 9] = [
+  "/next",
+  "/links/next",
+  "/links/next/href",
+  "/_links/next/href",
+  "/paging/next",
+  "/pagination/next",
+  "/meta/next",
+  "/next_page_url",
+  "/@odata.nextLink",
+];
+
+/// Body fields the items of a page are commonly listed in.
+const ITEM_FIELDS: [&str; 8] = ["data", "items", "results", "content", "records", "entries", "values", "value"];
+
+/// One page of a followed list, with the items it lists.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Page {
+  pub url: String,
+  /// `None` when the request failed before a response was received.
+  pub status: Option<u16>,
+  pub elapsed_ms: u64,
+  pub items: Vec<Value>,
+  /// Why no page follows this one, `None` while the walk goes on.
+  pub end: Option<String>,
+}
+
+/// Url the `Link` header of the response points at with `rel="next"`, e.g.
+/// `<https://api.example.com/pets?page=2>; rel="next", <...>; rel="last"`.
+fn link_header(response: &HttpResponse) -> Option<String> {
+  let header = response.header("Link")?;
+  header.split('<').skip(1).find_map(|link| {
+    let (url, params) = link.split_once('>')?;
+    // the parameters run up to the comma before the next link
+    let params = params.split(',').next().unwrap_or_default();
+    let next = params
+      .split(';')
+      .filter_map(|param| param.trim().strip_prefix("rel="))
+      .any(|rel| rel.trim_matches('"').split_whitespace().any(|rel| rel.eq_ignore_ascii_case("next")));
+    next.then(|| url.trim().to_string())
+  })
+}
+
+/// Whether the value looks like a url or a path, rather than an opaque cursor.
+fn is_reference(value: &str) -> bool {
+  value.contains("://") || value.starts_with('/') || value.starts_with('?')
+}
+
+/// Url of the next page, from the `Link` header of the response or else a `next` field of its JSON body, resolved
+/// against the url of the page.
+pub fn next_url(url: &str, response: &HttpResponse) -> Option<String> {
+  let next = link_header(response).or_else(|| {
+    let body = serde_json::from_str::<Value>(&response.body).ok()?;
+    NEXT_POINTERS
+      .iter()
+      .filter_map(|pointer| body.pointer(pointer).and_then(Value::as_str))
+      .find(|next| is_reference(next))
+      .map(String::from)
+  })?;
+  Some(resolve(url, &next))
+}
+
+/// Resolves a reference to another page against the url of the current one.
+pub fn resolve(url: &str, reference: &str) -> String {
+  if reference.contains("://") {
+    return reference.to_string();
+  }
+  let without_query = url.split_once('?').map_or(url, |(url, _)| url);
+  if reference.starts_with('?') {
+    return format!("{without_query}{reference}");
+  }
+  let origin_end =
+    url.find("://").map_or(0, |scheme| url[scheme + 3..].find('/').map_or(url.len(), |end| scheme + 3 + end));
+  let origin = &url[..origin_end.min(without_query.len())];
+  match reference.strip_prefix('/') {
+    Some(_) => format!("{origin}{reference}"),
+    None => {
+      let directory = without_query.rfind('/').filter(|end| *end >= origin_end).map_or(origin, |end| &url[..end]);
+      format!("{directory}/{reference}")
+    },
+  }
+}
+
+/// Items a page lists: the body itself when it is an array, or else its first array under a common name, or its only
+/// array field.
+pub fn items(body: &Value) -> Option<Vec<Value>> {
+  if let Value::Array(items) = body {
+    return Some(items.clone());
+  }
+  let object = body.as_object()?;
+  let named = ITEM_FIELDS.iter().find_map(|field| object.get(*field).and_then(Value::as_array));
+  let arrays: Vec<&Vec<Value>> = object.values().filter_map(Value::as_array).collect();
+  let only = match arrays.as_slice() {
+    [only] => Some(*only),
+    _ => None,
+  };
+  named.or(only).cloned()
+}
+
+/// List request whose pages are followed one after the other.
+pub struct Walk {
+  pub operation: Option<OperationKey>,
+  pub request: HttpRequest,
+  pub hooks: Hooks,
+  pub variables: BTreeMap<String, String>,
+}
+
+impl Walk {
+  /// Sends the request, then the request of each next page, through the scripts and plugins of its operation, and
+  /// hands each page to `fetched` as soon as it is received. Stops at the last page, a page seen before, a failed
+  /// request, after `MAX_PAGES` pages, or once cancelled.
+  pub async fn run(self, cancellation: CancellationToken, fetched: impl Fn(Page)) {
+    let Self { operation, mut request, hooks, variables } = self;
+    let mut seen = BTreeSet::new();
+    for index in 1..=MAX_PAGES {
+      let url = request.full_url();
+      seen.insert(url.clone());
+      let exchange = tokio::select! {
+        _ = cancellation.cancelled() => return,
+        exchange = script::exchange(operation.clone(), request.clone(), &hooks, &variables) => exchange,
+      };
+      let exchange = match exchange {
+        Ok(exchange) => exchange,
+        Err(error) => {
+          fetched(Page { url, end: Some(format!("request failed: {error}")), ..Page::default() });
+          return;
+        },
+      };
+      let response = &exchange.response;
+      let body = serde_json::from_str::<Value>(&response.body).unwrap_or_default();
+      let mut page = Page {
+        url: url.clone(),
+        status: Some(response.status),
+        elapsed_ms: exchange.elapsed_ms,
+        items: items(&body).unwrap_or_default(),
+        end: None,
+      };
+      let next = next_url(&url, response);
+      page.end = match next {
+        _ if !(200..300).contains(&response.status) => Some(format!("stopped at a {} response", response.status)),
+        None => Some(String::from("last page")),
+        Some(ref next) if seen.contains(next) => Some(String::from("the next page was already followed")),
+        Some(_) if index == MAX_PAGES => Some(format!("stopped after {MAX_PAGES} pages")),
+        Some(_) => None,
+      };
+      let end = page.end.is_some();
+      fetched(page);
+      match next {
+        Some(next) if !end => {
+          // the url of the next page carries its query
+          request = HttpRequest { base_url: next, path: String::default(), query: vec![], ..request };
+        },
+        _ => return,
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+  use serde_json::json;
+
+  use super::*;
+
+  #[test]
+  fn test_next_url() {
+    let url = "https://api.example.com/v1/pets?page=1";
+    let link = HttpResponse {
+      headers: vec![(
+        String::from("Link"),
+        String::from(r#"<https://api.example.com/v1/pets?page=2>; rel="next", </v1/pets?page=9>; rel="last""#),
+      )],
+      ..HttpResponse::default()
+    };
+    assert_eq!(next_url(url, &link).as_deref(), Some("https://api.example.com/v1/pets?page=2"));
+    let body = |body: Value| HttpResponse { body: body.to_string(), ..HttpResponse::default() };
+    assert_eq!(
+      next_url(url, &body(json!({ "links": { "next": "/v1/pets?page=2" } }))).as_deref(),
+      Some("https://api.example.com/v1/pets?page=2")
+    );
+    assert_eq!(
+      next_url(url, &body(json!({ "next": "?page=2" }))).as_deref(),
+      Some("https://api.example.com/v1/pets?page=2")
+    );
+    assert_eq!(next_url(url, &body(json!({ "next": "c2Vjb25k" }))), None);
+    assert_eq!(next_url(url, &body(json!({ "next": null }))), None);
+    assert_eq!(resolve(url, "cats?page=2"), "https://api.example.com/v1/cats?page=2");
+  }
+
+  #[test]
+  fn test_items() {
+    assert_eq!(items(&json!([1, 2])), Some(vec![json!(1), json!(2)]));
+    assert_eq!(items(&json!({ "data": [1], "errors": [] })), Some(vec![json!(1)]));
+    assert_eq!(items(&json!({ "pets": [1], "total": 1 })), Some(vec![json!(1)]));
+    assert_eq!(items(&json!({ "pets": [1], "cats": [2] })), None);
+  }
+}
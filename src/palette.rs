@@ -0,0 +1,77 @@
+use crate::action::Action;
+
+/// Entry of the command palette, an action found by typing part of its title.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Command {
+  pub title: String,
+  /// Key running the action outside of the palette, if any.
+  pub key: Option<String>,
+  pub action: Action,
+}
+
+impl Command {
+  pub fn new(title: impl Into<String>, action: Action) -> Self {
+    Self { title: title.into(), key: None, action }
+  }
+}
+
+/// Score of the text for the query, its characters found in order ignoring case, `None` when they are not.
+/// Consecutive characters and characters starting words score higher, characters skipped lower.
+pub fn score(query: &str, text: &str) -> Option<i64> {
+  let text: Vec<char> = text.chars().collect();
+  let mut score = 0;
+  let mut position = 0;
+  let mut previous: Option<usize> = None;
+  for wanted in query.chars().filter(|c| !c.is_whitespace()).flat_map(char::to_lowercase) {
+    let found = (position..text.len()).find(|index| text[*index].to_lowercase().eq([wanted]))?;
+    score += 1;
+    if previous.is_some_and(|previous| previous + 1 == found) {
+      score += 5;
+    }
+    let starts_word = match found.checked_sub(1).map(|before| text[before]) {
+      None => true,
+      Some(before) => !before.is_alphanumeric() || (before.is_lowercase() && text[found].is_uppercase()),
+    };
+    if starts_word {
+      score += 3;
+    }
+    score -= i64::try_from(found - position).unwrap_or(i64::MAX).min(5);
+    previous = Some(found);
+    position = found + 1;
+  }
+  Some(score)
+}
+
+/// Commands whose title matches the query, best first, all of them in order when it is empty.
+pub fn matches<'a>(commands: &'a [Command], query: &str) -> Vec<&'a Command> {
+  let mut scored: Vec<(i64, &Command)> =
+    commands.iter().filter_map(|command| score(query, &command.title).map(|score| (score, command))).collect();
+  scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+  scored.into_iter().map(|(_, command)| command).collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_matches() {
+    assert_eq!(score("xyz", "Export curl"), None);
+    assert!(score("ec", "Export curl") > score("ec", "Select environment"));
+    assert!(score("getpet", "Go to getPetById") > score("getpet", "Go to getUserPets"));
+    let commands = vec![
+      Command::new("Show code snippets, e.g. curl", Action::ShowSnippets),
+      Command::new("Switch environment: staging", Action::SelectEnvironment(Some(String::from("staging")))),
+      Command::new("Toggle line wrap", Action::ToggleWrap),
+      Command::new("Switch environment: none", Action::SelectEnvironment(None)),
+    ];
+    let titles = |query: &str| matches(&commands, query).into_iter().map(|command| command.title.as_str()).collect();
+    let all: Vec<&str> = titles("");
+    assert_eq!(all.len(), 4);
+    assert_eq!(titles("env stag"), vec!["Switch environment: staging"]);
+    assert_eq!(titles("curl"), vec!["Show code snippets, e.g. curl"]);
+    assert_eq!(titles("wrap"), vec!["Toggle line wrap"]);
+  }
+}
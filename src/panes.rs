@@ -1,6 +1,9 @@
 use color_eyre::eyre::Result;
 use crossterm::event::{KeyEvent, MouseEvent};
-use ratatui::layout::{Constraint, Rect};
+use ratatui::{
+  layout::{Constraint, Position, Rect},
+  widgets::{Scrollbar, ScrollbarOrientation, ScrollbarState},
+};
 
 use crate::{
   action::Action,
@@ -9,10 +12,71 @@ use crate::{
 
 pub mod address;
 pub mod apis;
+pub mod channels;
+pub mod collections;
+pub mod components;
+pub mod history;
+pub mod messages;
 pub mod request;
 pub mod response;
 pub mod tags;
 
+/// Index of the list item clicked, the items drawn in the area from the one at the offset.
+pub fn list_item_at(items_area: Rect, offset: usize, mouse: MouseEvent) -> Option<usize> {
+  let position = Position { x: mouse.column, y: mouse.row };
+  items_area.contains(position).then(|| offset + usize::from(mouse.row - items_area.y))
+}
+
+/// Item a page or jump action selects, from the selected one in a list of `len` items showing `page` of them, `None`
+/// for other actions or an empty list.
+pub fn jumped_index(action: &Action, index: usize, len: usize, page: usize) -> Option<usize> {
+  let last = len.checked_sub(1)?;
+  match action {
+    Action::PageDown => Some(index.saturating_add(page.max(1)).min(last)),
+    Action::PageUp => Some(index.saturating_sub(page.max(1)).min(last)),
+    Action::Top => Some(0),
+    Action::Bottom => Some(last),
+    _ => None,
+  }
+}
+
+/// Item a section action selects, from the selected one in a list whose items starting sections are flagged: the
+/// start of the next or previous section, or else the last or first item. `None` for other actions or an empty list.
+pub fn section_index(action: &Action, index: usize, starts: &[bool]) -> Option<usize> {
+  let last = starts.len().checked_sub(1)?;
+  match action {
+    Action::NextSection => Some((index + 1..starts.len()).find(|index| starts[*index]).unwrap_or(last)),
+    Action::PrevSection => Some((0..index.min(starts.len())).rev().find(|index| starts[*index]).unwrap_or(0)),
+    _ => None,
+  }
+}
+
+/// First of the `len` items shown `page` at a time that keeps the selected one in view, scrolled as little as possible
+/// from the offset they were last shown at, for lists too long to hand all their items to the `List` widget.
+pub fn window_offset(offset: usize, selected: usize, len: usize, page: usize) -> usize {
+  let page = page.max(1);
+  let selected = selected.min(len.saturating_sub(1));
+  let offset = offset.min(len.saturating_sub(page));
+  match selected {
+    selected if selected < offset => selected,
+    selected if selected >= offset + page => selected + 1 - page,
+    _ => offset,
+  }
+}
+
+/// Draws a scrollbar in the right column of the area, when the `len` items do not fit in the `page` shown.
+pub fn render_scrollbar(frame: &mut Frame<'_>, area: Rect, len: usize, position: usize, page: usize) {
+  if len <= page {
+    return;
+  }
+  let mut scrollbar_state = ScrollbarState::new(len).position(position).viewport_content_length(page);
+  frame.render_stateful_widget(
+    Scrollbar::new(ScrollbarOrientation::VerticalRight).begin_symbol(None).end_symbol(None),
+    area,
+    &mut scrollbar_state,
+  );
+}
+
 pub trait Pane {
   fn init(&mut self) -> Result<()> {
     Ok(())
@@ -38,10 +102,63 @@ pub trait Pane {
     Ok(None)
   }
 
+  #[allow(unused_variables)]
+  fn handle_paste_events(&mut self, text: String) -> Result<Option<EventResponse<Action>>> {
+    Ok(None)
+  }
+
   #[allow(unused_variables)]
   fn update(&mut self, action: Action) -> Result<Option<Action>> {
     Ok(None)
   }
 
+  /// Edit being made that quitting would discard, asked to be confirmed first.
+  fn unsaved_edit(&self) -> Option<String> {
+    None
+  }
+
   fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<()>;
 }
+
+#[cfg(test)]
+mod tests {
+  use crossterm::event::{KeyModifiers, MouseButton, MouseEventKind};
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_jumped_index() {
+    assert_eq!(jumped_index(&Action::PageDown, 3, 50, 10), Some(13));
+    assert_eq!(jumped_index(&Action::PageDown, 45, 50, 10), Some(49));
+    assert_eq!(jumped_index(&Action::PageUp, 13, 50, 10), Some(3));
+    assert_eq!(jumped_index(&Action::PageUp, 3, 50, 10), Some(0));
+    assert_eq!(jumped_index(&Action::Top, 30, 50, 10), Some(0));
+    assert_eq!(jumped_index(&Action::Bottom, 3, 50, 10), Some(49));
+    // a list not drawn yet moves by one item
+    assert_eq!(jumped_index(&Action::PageDown, 3, 50, 0), Some(4));
+    // an index past the end of a shrunk list is brought back into it
+    assert_eq!(jumped_index(&Action::PageUp, 40, 5, 10), Some(4));
+
+    assert_eq!(jumped_index(&Action::Bottom, 0, 0, 10), None);
+    assert_eq!(jumped_index(&Action::Down, 3, 50, 10), None);
+  }
+
+  #[test]
+  fn test_list_item_at() {
+    let click = |column, row| {
+      MouseEvent { kind: MouseEventKind::Down(MouseButton::Left), column, row, modifiers: KeyModifiers::NONE }
+    };
+    let items_area = Rect::new(2, 5, 20, 4);
+    assert_eq!(list_item_at(items_area, 0, click(2, 5)), Some(0));
+    assert_eq!(list_item_at(items_area, 0, click(21, 8)), Some(3));
+    // the items are drawn from the one at the offset
+    assert_eq!(list_item_at(items_area, 10, click(10, 6)), Some(11));
+
+    assert_eq!(list_item_at(items_area, 10, click(10, 4)), None);
+    assert_eq!(list_item_at(items_area, 10, click(10, 9)), None);
+    assert_eq!(list_item_at(items_area, 10, click(1, 6)), None);
+    assert_eq!(list_item_at(items_area, 10, click(22, 6)), None);
+    assert_eq!(list_item_at(Rect::default(), 0, click(0, 0)), None);
+  }
+}
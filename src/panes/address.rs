@@ -1,17 +1,20 @@
 use std::sync::{Arc, RwLock};
 
 use color_eyre::eyre::Result;
-use crossterm::event::{KeyEvent, MouseEvent};
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent};
 use ratatui::{
   prelude::*,
   widgets::{block::*, *},
 };
+use tui_textarea::{CursorMove, TextArea};
 
 use crate::{
   action::Action,
+  hosts,
   pages::home::State,
   panes::Pane,
-  tui::{EventResponse, Frame},
+  theme,
+  tui::{self, EventResponse, Frame},
 };
 
 #[derive(Default)]
@@ -19,17 +22,34 @@ pub struct AddressPane {
   focused: bool,
   focused_border_style: Style,
   state: Arc<RwLock<State>>,
+  /// Base url being edited, `None` when not editing.
+  editor: Option<TextArea<'static>>,
 }
 
 impl AddressPane {
   pub fn new(state: Arc<RwLock<State>>, focused: bool, focused_border_style: Style) -> Self {
-    Self { state, focused, focused_border_style }
+    Self { state, focused, focused_border_style, editor: None }
+  }
+
+  fn start_editing(&mut self) {
+    let mut editor = TextArea::new(vec![self.state.read().unwrap().base_url()]);
+    editor.set_cursor_line_style(Style::default());
+    editor.move_cursor(CursorMove::End);
+    self.editor = Some(editor);
+  }
+
+  /// Applies the edited base url, an empty one goes back to the spec servers.
+  fn finish_editing(&mut self) {
+    if let Some(editor) = self.editor.take() {
+      let base_url = editor.lines().concat().trim().trim_end_matches('/').to_string();
+      self.state.write().unwrap().base_url_override = if base_url.is_empty() { None } else { Some(base_url) };
+    }
   }
 
   fn border_style(&self) -> Style {
     match self.focused {
       true => self.focused_border_style,
-      false => Style::default(),
+      false => theme::current().border,
     }
   }
 
@@ -40,14 +60,15 @@ impl AddressPane {
     }
   }
 
-  fn method_color(method: &str) -> Color {
-    match method {
-      "GET" => Color::LightCyan,
-      "POST" => Color::LightBlue,
-      "PUT" => Color::LightYellow,
-      "DELETE" => Color::LightRed,
-      _ => Color::Gray,
+  fn select_server(&mut self, index: usize) -> Result<()> {
+    let mut state = self.state.write().unwrap();
+    let servers_len = state.servers().len();
+    if servers_len > 0 {
+      state.base_url_override = None;
+      state.workspace.active_server = index % servers_len;
+      state.workspace.save(&state.openapi_path)?;
     }
+    Ok(())
   }
 }
 impl Pane for AddressPane {
@@ -62,6 +83,7 @@ impl Pane for AddressPane {
 
   fn unfocus(&mut self) -> Result<()> {
     self.focused = false;
+    self.editor = None;
     Ok(())
   }
 
@@ -69,8 +91,22 @@ impl Pane for AddressPane {
     Constraint::Max(3)
   }
 
-  fn handle_key_events(&mut self, _key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
-    Ok(None)
+  fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
+    let Some(editor) = self.editor.as_mut() else {
+      if key.code == KeyCode::Char('i') {
+        self.start_editing();
+        return Ok(Some(EventResponse::Stop(Action::Render)));
+      }
+      return Ok(None);
+    };
+    match key.code {
+      KeyCode::Enter => self.finish_editing(),
+      KeyCode::Esc => self.editor = None,
+      _ => {
+        editor.input(key);
+      },
+    }
+    Ok(Some(EventResponse::Stop(Action::Render)))
   }
 
   #[allow(unused_variables)]
@@ -78,10 +114,31 @@ impl Pane for AddressPane {
     Ok(None)
   }
 
+  fn handle_paste_events(&mut self, text: String) -> Result<Option<EventResponse<Action>>> {
+    let Some(editor) = self.editor.as_mut() else {
+      return Ok(None);
+    };
+    editor.insert_str(tui::pasted(&text, false));
+    Ok(Some(EventResponse::Stop(Action::Render)))
+  }
+
   fn update(&mut self, action: Action) -> Result<Option<Action>> {
     match action {
       Action::Update => {},
-      Action::Submit => {},
+      Action::Submit => return Ok(Some(Action::SendRequest)),
+      Action::Down => {
+        let active_server = self.state.read().unwrap().workspace.active_server;
+        self.select_server(active_server.saturating_add(1))?;
+      },
+      Action::Up => {
+        let (active_server, servers_len) = {
+          let state = self.state.read().unwrap();
+          (state.workspace.active_server, state.servers().len())
+        };
+        self.select_server(active_server.saturating_add(servers_len.saturating_sub(1)))?;
+      },
+      Action::Tab(index) => self.select_server(index.try_into()?)?,
+      Action::Go => return Ok(Some(Action::ShowServers)),
       _ => {},
     }
     Ok(None)
@@ -90,27 +147,79 @@ impl Pane for AddressPane {
   fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
     let state = self.state.read().unwrap();
     if let Some((path, method, operation)) = state.active_operation() {
-      let base_url = if let Some(server) = state.openapi_spec.primary_server() {
-        server.to_owned().url
-      } else {
-        String::from("http://localhost")
-      };
-      let title = operation.summary.clone().unwrap_or_default();
+      let base_url = state.operation_base_url(&path, &method);
+      let operation_server = state.operation_server(&path, &method);
+      let mut title = operation.summary.clone().unwrap_or_default();
+      if state.active_operation_key().is_some_and(|key| state.workspace.note(&key).is_some()) {
+        title.push_str(" ✎");
+      }
       const INNER_MARGIN: Margin = Margin { horizontal: 1, vertical: 1 };
 
       let inner = area.inner(&INNER_MARGIN);
-      frame.render_widget(
-        Paragraph::new(Line::from(vec![
-          Span::styled(format!("{:7}", method.as_str()), Style::default().fg(Self::method_color(method.as_str()))),
-          Span::styled(base_url, Style::default().fg(Color::DarkGray)),
+      if let Some(editor) = self.editor.as_ref() {
+        let layout = Layout::default()
+          .direction(Direction::Horizontal)
+          .constraints([Constraint::Length(7), Constraint::Fill(1)])
+          .split(inner);
+        frame.render_widget(
+          Span::styled(format!("{:7}", method.as_str()), theme::current().method(method.as_str())),
+          layout[0],
+        );
+        frame.render_widget(editor.widget(), layout[1]);
+      } else {
+        let base_url_style = match state.base_url_override {
+          Some(_) => Style::default().fg(Color::LightMagenta),
+          None => Style::default().fg(Color::DarkGray),
+        };
+        // the address the host is resolved to with `--resolve`
+        let host_override = hosts::find(&base_url).map(|host_override| {
+          Span::styled(format!("  → {}", host_override.socket_addr()), Style::default().fg(Color::LightMagenta))
+        });
+        let mut spans = vec![
+          Span::styled(format!("{:7}", method.as_str()), theme::current().method(method.as_str())),
+          Span::styled(base_url.as_str(), base_url_style),
           Span::styled(path, Style::default().fg(Color::White)),
-        ])),
-        inner,
-      );
+        ];
+        spans.extend(host_override);
+        frame.render_widget(Paragraph::new(Line::from(spans)), inner);
+      }
+
+      let servers_len = state.servers().len();
+      let server = match state.active_server() {
+        _ if self.editor.is_some() => {
+          const ARROW: &str = symbols::scrollbar::HORIZONTAL.end;
+          format!("[enter {ARROW} apply] [esc {ARROW} cancel]")
+        },
+        _ if state.base_url_override.is_some() => String::from("base url override"),
+        _ if operation_server.is_some() => {
+          let description = operation_server
+            .and_then(|server| server.description)
+            .map(|description| format!(": {description}"))
+            .unwrap_or_default();
+          format!("operation server{description}")
+        },
+        Some(server) if servers_len > 1 => {
+          let description = server.description.map(|description| format!(": {description}")).unwrap_or_default();
+          format!("server {}/{servers_len}{description}", state.workspace.active_server.min(servers_len - 1) + 1)
+        },
+        _ => String::default(),
+      };
+
+      // the last probe of the base url shown, when it was probed
+      let health = state.health.as_ref().filter(|health| health.base_url == base_url).map(|health| {
+        let color = match (health.reachable(), health.healthy()) {
+          (_, true) => Color::LightGreen,
+          (true, false) => Color::LightYellow,
+          (false, _) => Color::LightRed,
+        };
+        Line::from(health.label()).style(Style::default().fg(color)).right_aligned()
+      });
 
       frame.render_widget(
         Block::default()
           .title(title)
+          .title_top(Line::from(server).style(Style::default().dim()).right_aligned())
+          .title_bottom(health.unwrap_or_default())
           .borders(Borders::ALL)
           .border_style(self.border_style())
           .border_type(self.border_type()),
@@ -121,3 +230,39 @@ impl Pane for AddressPane {
     Ok(())
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_edit_base_url() -> Result<()> {
+    let state = Arc::new(RwLock::new(State::default()));
+    let mut pane = AddressPane::new(state.clone(), true, Style::default());
+    let mut edit = |keys: &[KeyCode], pasted: &str, done: KeyCode| -> Result<()> {
+      pane.handle_key_events(KeyEvent::from(KeyCode::Char('i')))?;
+      for key in keys {
+        pane.handle_key_events(KeyEvent::from(*key))?;
+      }
+      pane.handle_paste_events(pasted.to_string())?;
+      pane.handle_key_events(KeyEvent::from(done))?;
+      Ok(())
+    };
+    let clear = vec![KeyCode::Backspace; "http://localhost".len()];
+    edit(&clear, "https://staging.example.com/v2/", KeyCode::Enter)?;
+    assert_eq!(state.read().unwrap().base_url_override.as_deref(), Some("https://staging.example.com/v2"));
+    assert_eq!(state.read().unwrap().base_url(), "https://staging.example.com/v2");
+
+    // Esc leaves the base url as it was
+    edit(&[], "/v3", KeyCode::Esc)?;
+    assert_eq!(state.read().unwrap().base_url(), "https://staging.example.com/v2");
+
+    // an empty base url goes back to the spec servers
+    edit(&vec![KeyCode::Backspace; "https://staging.example.com/v2".len()], "", KeyCode::Enter)?;
+    assert_eq!(state.read().unwrap().base_url_override, None);
+    assert_eq!(state.read().unwrap().base_url(), "http://localhost");
+    Ok(())
+  }
+}
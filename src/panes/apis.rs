@@ -1,17 +1,26 @@
-use std::sync::{Arc, RwLock};
+use std::{
+  collections::BTreeSet,
+  sync::{Arc, RwLock},
+};
 
 use color_eyre::eyre::Result;
-use crossterm::event::{KeyEvent, MouseEvent};
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent};
 use ratatui::{
   prelude::*,
   widgets::{block::*, *},
 };
+use tui_textarea::{CursorMove, TextArea};
 
 use crate::{
   action::Action,
+  operation_filter::OperationFilter,
   pages::home::State,
-  panes::Pane,
+  panes::{jumped_index, list_item_at, render_scrollbar, section_index, window_offset, Pane},
+  path_tree::{self, PathNode},
+  tags, theme,
+  toast::ToastLevel,
   tui::{EventResponse, Frame},
+  workspace::OperationKey,
 };
 
 pub struct ApisPane {
@@ -19,17 +28,278 @@ pub struct ApisPane {
   focused_border_style: Style,
   state: Arc<RwLock<State>>,
   current_operation_index: usize,
+  view: View,
+  /// Keys of the path and tag groups whose operations are listed.
+  expanded: BTreeSet<String>,
+  /// Selected row, a group or an operation, when grouping the operations.
+  row_index: usize,
+  /// Area of the last drawn operations, whose height is also the page of page up and down, and the first operation
+  /// shown in it, for clicks.
+  list_area: Rect,
+  list_offset: usize,
+  /// Filter expression being typed after `/`, `None` when not editing.
+  filter_editor: Option<TextArea<'static>>,
+  /// Operation marked with `Space` to be compared with the selected one.
+  marked: Option<OperationKey>,
+}
+
+/// How the operations are listed, `d` cycling through them.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum View {
+  #[default]
+  List,
+  /// In groups of their path segments.
+  ByPath,
+  /// Under their tags, nested as in the tags pane.
+  Outline,
+}
+
+/// Row of the grouped operations, with its depth.
+enum Row<'a> {
+  Group(usize, &'a PathNode),
+  /// Index of the operation among the listed ones.
+  Operation(usize, usize),
 }
 
 impl ApisPane {
   pub fn new(state: Arc<RwLock<State>>, focused: bool, focused_border_style: Style) -> Self {
-    Self { focused, focused_border_style, state, current_operation_index: 0 }
+    Self {
+      focused,
+      focused_border_style,
+      state,
+      current_operation_index: 0,
+      view: View::default(),
+      expanded: BTreeSet::default(),
+      row_index: 0,
+      list_area: Rect::default(),
+      list_offset: 0,
+      filter_editor: None,
+      marked: None,
+    }
+  }
+
+  fn grouped(&self) -> bool {
+    self.view != View::List
+  }
+
+  /// Groups of the listed operations, by path or by tag.
+  fn tree(&self, state: &State) -> Vec<PathNode> {
+    match self.view {
+      View::Outline => {
+        let operation_tags: Vec<Vec<String>> =
+          state.operations().map(|(_, _, operation)| operation.tags.clone()).collect();
+        tags::outline(&state.tag_tree, &operation_tags)
+      },
+      _ => {
+        let paths: Vec<String> = state.operations().map(|(path, _, _)| path).collect();
+        path_tree::tree(&paths)
+      },
+    }
+  }
+
+  /// Listed rows, the operations of collapsed groups left out. A path group of a single operation is listed as it.
+  fn rows<'a>(&self, nodes: &'a [PathNode], depth: usize) -> Vec<Row<'a>> {
+    let mut rows = vec![];
+    for node in nodes {
+      if self.view == View::ByPath && node.children.is_empty() && node.operations.len() == 1 {
+        rows.push(Row::Operation(depth, node.operations[0]));
+        continue;
+      }
+      rows.push(Row::Group(depth, node));
+      if self.expanded.contains(&node.key) {
+        rows.extend(node.operations.iter().map(|operation| Row::Operation(depth + 1, *operation)));
+        rows.extend(self.rows(&node.children, depth + 1));
+      }
+    }
+    rows
+  }
+
+  /// Selects the row of the active operation, expanding the groups it is listed in.
+  fn sync_rows(&mut self) {
+    let state = self.state.read().unwrap();
+    let nodes = self.tree(&state);
+    if let Some(groups) = groups_of(&nodes, state.active_operation_index) {
+      self.expanded.extend(groups);
+    }
+    let rows = self.rows(&nodes, 0);
+    let row =
+      rows.iter().position(|row| matches!(row, Row::Operation(_, index) if *index == state.active_operation_index));
+    self.row_index = row.unwrap_or(0);
+  }
+
+  fn start_editing_filter(&mut self) {
+    let mut editor = TextArea::new(vec![self.state.read().unwrap().operation_query.clone()]);
+    editor.set_cursor_line_style(Style::default());
+    editor.move_cursor(CursorMove::End);
+    self.filter_editor = Some(editor);
+  }
+
+  /// Lists the operations matching the filter as it is typed, from the first one.
+  fn set_filter(&mut self, query: String) {
+    let mut state = self.state.write().unwrap();
+    if state.operation_query != query {
+      state.operation_query = query;
+      state.active_operation_index = 0;
+    }
+  }
+
+  /// Moves to the row, making the operation on it the active one.
+  fn select_row(&mut self, offset: usize) -> Option<Action> {
+    let mut state = self.state.write().unwrap();
+    let nodes = self.tree(&state);
+    let rows = self.rows(&nodes, 0);
+    if rows.is_empty() {
+      return None;
+    }
+    self.row_index = self.row_index.saturating_add(offset) % rows.len();
+    let Row::Operation(_, operation) = rows[self.row_index] else {
+      return None;
+    };
+    self.current_operation_index = operation;
+    state.active_operation_index = operation;
+    Some(Action::Update)
+  }
+
+  /// Lists the operations of every tag, keeping the active operation selected.
+  fn list_every_tag(&mut self) {
+    let mut state = self.state.write().unwrap();
+    if state.active_tag_name.is_none() {
+      return;
+    }
+    let active = state.active_operation().map(|(path, method, _)| OperationKey::new(&method, &path));
+    state.active_tag_name = None;
+    let index = active
+      .and_then(|active| state.operations().position(|(path, method, _)| OperationKey::new(&method, &path) == active));
+    state.active_operation_index = index.unwrap_or_default();
+  }
+
+  /// Expands every group, or collapses them all selecting the top one of the active operation.
+  fn expand_all(&mut self, expand: bool) {
+    if !self.grouped() {
+      return;
+    }
+    if expand {
+      let state = self.state.read().unwrap();
+      let mut keys = vec![];
+      collect_keys(&self.tree(&state), &mut keys);
+      drop(state);
+      self.expanded.extend(keys);
+      self.sync_rows();
+      return;
+    }
+    self.expanded.clear();
+    let state = self.state.read().unwrap();
+    let nodes = self.tree(&state);
+    let top = groups_of(&nodes, state.active_operation_index).and_then(|groups| groups.into_iter().next());
+    let rows = self.rows(&nodes, 0);
+    self.row_index = rows
+      .iter()
+      .position(|row| {
+        match row {
+          Row::Group(_, node) => top.as_ref() == Some(&node.key),
+          Row::Operation(_, index) => *index == state.active_operation_index,
+        }
+      })
+      .unwrap_or(0);
+  }
+
+  /// Selects the operation, or the row when grouping by path, a page or a section away or at either end of the list.
+  /// Sections are the path groups, or the operations sharing the first segment of their path.
+  fn jump(&mut self, action: &Action) -> Option<Action> {
+    let page = usize::from(self.list_area.height);
+    if self.grouped() {
+      let starts: Vec<bool> = {
+        let state = self.state.read().unwrap();
+        self.rows(&self.tree(&state), 0).iter().map(|row| matches!(row, Row::Group(..))).collect()
+      };
+      let row = jumped_index(action, self.row_index, starts.len(), page)
+        .or_else(|| section_index(action, self.row_index, &starts))?;
+      return self.select_row((row + starts.len() - self.row_index) % starts.len());
+    }
+    let mut state = self.state.write().unwrap();
+    let segments: Vec<String> = state
+      .operations()
+      .map(|(path, _, _)| path.trim_start_matches('/').split('/').next().unwrap_or_default().to_string())
+      .collect();
+    let starts: Vec<bool> =
+      segments.iter().enumerate().map(|(index, segment)| index == 0 || segments[index - 1] != *segment).collect();
+    self.current_operation_index = jumped_index(action, self.current_operation_index, starts.len(), page)
+      .or_else(|| section_index(action, self.current_operation_index, &starts))?;
+    state.active_operation_index = self.current_operation_index;
+    Some(Action::Update)
+  }
+
+  /// Expands or collapses the selected group, or collapses the group of the selected operation on `Back`.
+  fn toggle_group(&mut self, collapse_parent: bool) {
+    let state = self.state.read().unwrap();
+    let nodes = self.tree(&state);
+    let rows = self.rows(&nodes, 0);
+    let Some(row) = rows.get(self.row_index) else {
+      return;
+    };
+    match row {
+      Row::Group(_, node) if !collapse_parent || self.expanded.contains(&node.key) => {
+        let key = node.key.clone();
+        if !self.expanded.remove(&key) {
+          self.expanded.insert(key);
+        }
+      },
+      Row::Group(depth, _) | Row::Operation(depth, _) if collapse_parent => {
+        let parent = rows[..self.row_index]
+          .iter()
+          .rposition(|row| matches!(row, Row::Group(parent_depth, _) if parent_depth < depth));
+        if let Some((parent, Row::Group(_, node))) = parent.map(|parent| (parent, &rows[parent])) {
+          self.expanded.remove(&node.key);
+          self.row_index = parent;
+        }
+      },
+      _ => {},
+    }
+  }
+
+  fn operation_line<'a>(
+    state: &State,
+    marked: Option<&OperationKey>,
+    method: &str,
+    path: &str,
+    indent: usize,
+  ) -> Line<'a> {
+    let operation = OperationKey::new(method, path);
+    let saved_requests = state.workspace.saved_requests_for(&operation).count();
+    let exercised = match state.workspace.is_exercised(&operation) {
+      true => Span::styled("✓", Color::LightGreen),
+      false => Span::raw(" "),
+    };
+    let bookmarked = match state.workspace.is_bookmarked(&operation) {
+      true => Span::styled("★", Color::LightYellow),
+      false => Span::raw(" "),
+    };
+    let mut line = Line::from(vec![
+      exercised,
+      bookmarked,
+      Span::raw("  ".repeat(indent)),
+      Span::styled(format!(" {:7}", method), theme::current().method(method)),
+      Span::styled(format!(" {:7}", path), Color::White),
+    ]);
+    if saved_requests > 0 {
+      line.spans.push(Span::styled(format!(" [{saved_requests}]"), Style::default().dim()));
+    }
+    if state.workspace.note(&operation).is_some() {
+      line.spans.push(Span::styled(" ✎", Style::default().dim()));
+    }
+    if let Some(source) = state.operation_source(path, method) {
+      line.spans.push(Span::styled(format!(" ‹{source}›"), Color::Cyan));
+    }
+    if marked == Some(&operation) {
+      line.spans.push(Span::styled(" ⇄", Color::LightMagenta));
+    }
+    line
   }
 
   fn border_style(&self) -> Style {
     match self.focused {
       true => self.focused_border_style,
-      false => Style::default(),
+      false => theme::current().border,
     }
   }
 
@@ -39,17 +309,39 @@ impl ApisPane {
       false => BorderType::Plain,
     }
   }
-
-  fn method_color(method: &str) -> Color {
-    match method {
-      "GET" => Color::LightCyan,
-      "POST" => Color::LightBlue,
-      "PUT" => Color::LightYellow,
-      "DELETE" => Color::LightRed,
-      _ => Color::Gray,
+}
+/// Keys of the groups the operation is listed in, from the root of the tree.
+fn groups_of(nodes: &[PathNode], operation: usize) -> Option<Vec<String>> {
+  nodes.iter().find_map(|node| {
+    if node.operations.contains(&operation) {
+      return Some(vec![node.key.clone()]);
     }
+    groups_of(&node.children, operation).map(|mut groups| {
+      groups.insert(0, node.key.clone());
+      groups
+    })
+  })
+}
+
+fn collect_keys(nodes: &[PathNode], keys: &mut Vec<String>) {
+  for node in nodes {
+    keys.push(node.key.clone());
+    collect_keys(&node.children, keys);
   }
 }
+
+/// Operations of the group and of the groups under it.
+fn count(node: &PathNode) -> usize {
+  node.operations.len() + node.children.iter().map(count).sum::<usize>()
+}
+
+/// Frame of the spinner shown while the spec loads, turning with the clock as the page is rendered.
+fn spinner_frame() -> &'static str {
+  const FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+  let elapsed = std::time::UNIX_EPOCH.elapsed().unwrap_or_default();
+  FRAMES[(elapsed.as_millis() / 100) as usize % FRAMES.len()]
+}
+
 impl Pane for ApisPane {
   fn init(&mut self) -> Result<()> {
     Ok(())
@@ -72,17 +364,118 @@ impl Pane for ApisPane {
     }
   }
 
-  fn handle_key_events(&mut self, _key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
-    Ok(None)
+  fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
+    if let Some(editor) = self.filter_editor.as_mut() {
+      match key.code {
+        KeyCode::Enter => {
+          self.filter_editor = None;
+          let query = self.state.read().unwrap().operation_query.clone();
+          if let Err(error) = query.parse::<OperationFilter>() {
+            return Ok(Some(EventResponse::Stop(Action::Notify(ToastLevel::Error, error.to_string()))));
+          }
+          return Ok(Some(EventResponse::Stop(Action::Render)));
+        },
+        KeyCode::Esc => {
+          self.filter_editor = None;
+          self.set_filter(String::default());
+        },
+        _ => {
+          editor.input(key);
+          let query = editor.lines().concat();
+          self.set_filter(query);
+        },
+      }
+      return Ok(Some(EventResponse::Stop(Action::Update)));
+    }
+    match key.code {
+      KeyCode::Char(' ') => {
+        let active = self.state.read().unwrap().active_operation_key();
+        self.marked = match active {
+          active if active == self.marked => None,
+          active => active,
+        };
+        Ok(Some(EventResponse::Stop(Action::Render)))
+      },
+      KeyCode::Char('=') if self.marked.is_some() => {
+        let active = self.state.read().unwrap().active_operation_key();
+        match (self.marked.clone(), active) {
+          (Some(marked), Some(active)) if marked != active => {
+            Ok(Some(EventResponse::Stop(Action::CompareOperations(marked, active))))
+          },
+          _ => Ok(Some(EventResponse::Stop(Action::Render))),
+        }
+      },
+      KeyCode::Char('/') => {
+        self.start_editing_filter();
+        Ok(Some(EventResponse::Stop(Action::Render)))
+      },
+      KeyCode::Char('u') => {
+        let mut state = self.state.write().unwrap();
+        state.untested_only = !state.untested_only;
+        state.active_operation_index = 0;
+        Ok(Some(EventResponse::Stop(Action::Update)))
+      },
+      KeyCode::Char('d') => {
+        self.view = match self.view {
+          View::List => View::ByPath,
+          View::ByPath => View::Outline,
+          View::Outline => View::List,
+        };
+        if self.view == View::Outline {
+          // the outline lists the operations of every tag
+          self.list_every_tag();
+          return Ok(Some(EventResponse::Stop(Action::Update)));
+        }
+        if self.grouped() {
+          self.sync_rows();
+        }
+        Ok(Some(EventResponse::Stop(Action::Render)))
+      },
+      _ => Ok(None),
+    }
   }
 
-  #[allow(unused_variables)]
   fn handle_mouse_events(&mut self, mouse: MouseEvent) -> Result<Option<EventResponse<Action>>> {
-    Ok(None)
+    let Some(index) = list_item_at(self.list_area, self.list_offset, mouse) else {
+      return Ok(Some(EventResponse::Stop(Action::Render)));
+    };
+    if self.grouped() {
+      let rows_len = {
+        let state = self.state.read().unwrap();
+        self.rows(&self.tree(&state), 0).len()
+      };
+      if index >= rows_len {
+        return Ok(Some(EventResponse::Stop(Action::Render)));
+      }
+      self.row_index = index;
+      return Ok(Some(EventResponse::Stop(self.select_row(0).unwrap_or(Action::Render))));
+    }
+    let mut state = self.state.write().unwrap();
+    if index >= state.operations_len() {
+      return Ok(Some(EventResponse::Stop(Action::Render)));
+    }
+    self.current_operation_index = index;
+    state.active_operation_index = index;
+    Ok(Some(EventResponse::Stop(Action::Update)))
   }
 
   fn update(&mut self, action: Action) -> Result<Option<Action>> {
     match action {
+      Action::Down if self.grouped() => return Ok(self.select_row(1)),
+      Action::Up if self.grouped() => {
+        let rows_len = {
+          let state = self.state.read().unwrap();
+          self.rows(&self.tree(&state), 0).len()
+        };
+        return Ok(self.select_row(rows_len.saturating_sub(1)));
+      },
+      Action::Go | Action::Submit if self.grouped() => self.toggle_group(false),
+      Action::Back if self.grouped() => self.toggle_group(true),
+      Action::ExpandAll => self.expand_all(true),
+      Action::CollapseAll => self.expand_all(false),
+      Action::PageDown | Action::PageUp | Action::Top | Action::Bottom | Action::NextSection | Action::PrevSection => {
+        return Ok(self.jump(&action))
+      },
       Action::Down => {
         let mut state = self.state.write().unwrap();
         let operations_len = state.operations_len();
@@ -104,8 +497,10 @@ impl Pane for ApisPane {
       },
       Action::Submit => {},
       Action::Update => {
-        let state = self.state.read().unwrap();
-        self.current_operation_index = state.active_operation_index;
+        self.current_operation_index = self.state.read().unwrap().active_operation_index;
+        if self.grouped() {
+          self.sync_rows();
+        }
       },
       _ => {},
     }
@@ -115,34 +510,113 @@ impl Pane for ApisPane {
 
   fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
     let state = self.state.read().unwrap();
-    let items = state.openapi_spec.operations().filter_map(|operation| {
-      if let Some(active_tag) = &state.active_tag_name {
-        if !operation.2.tags.contains(active_tag) {
-          return None;
-        }
-      }
-      let (path, method, _operation) = operation;
-      Some(Line::from(vec![
-        Span::styled(format!(" {:7}", method.as_str()), Self::method_color(method.as_str())),
-        Span::styled(format!(" {:7}", path), Color::White),
-      ]))
-    });
+    if state.loading {
+      let spinner = Paragraph::new(Line::from(vec![
+        Span::styled(spinner_frame(), theme::current().focused_border),
+        Span::styled(format!(" Loading {}…", state.openapi_path), Style::default().dim()),
+      ]));
+      frame.render_widget(
+        spinner.block(
+          Block::default()
+            .title("APIs")
+            .borders(Borders::ALL)
+            .border_style(self.border_style())
+            .border_type(self.border_type())
+            .padding(Padding::horizontal(1)),
+        ),
+        area,
+      );
+      return Ok(());
+    }
+    // only the items shown are drawn, the list of a large spec being too long to draw whole on every frame
+    let page = usize::from(area.height.saturating_sub(2));
+    let (items, selected, items_len): (Vec<Line<'_>>, usize, usize) = match self.grouped() {
+      true => {
+        let operations: Vec<(String, String)> = state.operations().map(|(path, method, _)| (path, method)).collect();
+        let nodes = self.tree(&state);
+        let rows = self.rows(&nodes, 0);
+        self.list_offset = window_offset(self.list_offset, self.row_index, rows.len(), page);
+        let rows_len = rows.len();
+        let items = rows
+          .into_iter()
+          .skip(self.list_offset)
+          .take(page)
+          .map(|row| {
+            match row {
+              Row::Group(depth, node) => {
+                let marker = match self.expanded.contains(&node.key) {
+                  true => "▾",
+                  false => "▸",
+                };
+                let operations_len = count(node);
+                Line::from(vec![
+                  Span::raw(format!(" {}{marker} ", "  ".repeat(depth))),
+                  Span::styled(node.label.clone(), Color::White),
+                  Span::styled(format!(" ({operations_len})"), Style::default().dim()),
+                ])
+              },
+              Row::Operation(depth, index) => {
+                let (path, method) = &operations[index];
+                Self::operation_line(&state, self.marked.as_ref(), method, path, depth)
+              },
+            }
+          })
+          .collect();
+        (items, self.row_index, rows_len)
+      },
+      false => {
+        let operations_len = state.operations_len();
+        self.list_offset = window_offset(self.list_offset, self.current_operation_index, operations_len, page);
+        let items = state
+          .operations()
+          .skip(self.list_offset)
+          .take(page)
+          .map(|(path, method, _)| Self::operation_line(&state, self.marked.as_ref(), &method, &path, 0))
+          .collect();
+        (items, self.current_operation_index, operations_len)
+      },
+    };
 
     let list = List::new(items)
       .block(Block::default().borders(Borders::ALL))
       .highlight_symbol(symbols::scrollbar::HORIZONTAL.end)
       .highlight_spacing(HighlightSpacing::Always)
-      .highlight_style(Style::default().add_modifier(Modifier::BOLD));
-    let mut list_state = ListState::default().with_selected(Some(self.current_operation_index));
+      .highlight_style(theme::current().selection);
+    let mut list_state = ListState::default().with_selected(selected.checked_sub(self.list_offset));
 
     frame.render_stateful_widget(list, area, &mut list_state);
-    let active_tag = format!("[{}]", state.active_tag_name.clone().unwrap_or(String::from("ALL")));
+    self.list_area = area.inner(&Margin { horizontal: 1, vertical: 1 });
+
+    let mut active_tag = format!("[{}]", state.active_tag_name.clone().unwrap_or(String::from("ALL")));
+    match self.view {
+      View::List => {},
+      View::ByPath => active_tag.push_str(" by path"),
+      View::Outline => active_tag.push_str(" outline"),
+    }
+    if state.favorites_only {
+      active_tag.push_str(" ★");
+    }
+    let (exercised, total) = state.coverage(state.active_tag_name.as_deref());
+    let mut coverage = format!("{exercised}/{total} tested ({}%)", (exercised * 100).checked_div(total).unwrap_or(0));
+    if state.untested_only {
+      coverage.push_str(", untested only");
+    }
+    let filter_style = match state.operation_query.parse::<OperationFilter>() {
+      Ok(_) => Style::default().fg(Color::LightYellow),
+      Err(_) => Style::default().fg(Color::LightRed),
+    };
+    let filter = match (&self.filter_editor, state.operation_query.is_empty()) {
+      (None, true) => Line::default(),
+      (_, _) => Line::from(vec![Span::raw("/ "), Span::styled(state.operation_query.clone(), filter_style)]),
+    };
     frame.render_widget(
       Block::default()
         .title("APIs")
+        .title(Line::styled(coverage, Style::default().dim()))
         .borders(Borders::ALL)
         .border_style(self.border_style())
         .border_type(self.border_type())
+        .title_bottom(filter.left_aligned())
         .title_bottom(
           Line::from(format!("{} of {}", self.current_operation_index.saturating_add(1), state.operations_len()))
             .right_aligned(),
@@ -150,6 +624,51 @@ impl Pane for ApisPane {
         .title(Line::styled(active_tag, Style::default().add_modifier(Modifier::ITALIC)).right_aligned()),
       area,
     );
+    if let Some(editor) = self.filter_editor.as_mut() {
+      // over the bottom border, past `/ `, leaving room for the count of the operations
+      let x = area.x.saturating_add(3);
+      let width = area.width.saturating_sub(3).saturating_mul(2) / 3;
+      let editor_area = Rect { x, y: area.bottom().saturating_sub(1), width, height: 1 };
+      editor.set_style(filter_style);
+      frame.render_widget(Clear, editor_area);
+      frame.render_widget(editor.widget(), editor_area);
+    }
+    let page = usize::from(self.list_area.height);
+    render_scrollbar(frame, area.inner(&Margin { horizontal: 0, vertical: 1 }), items_len, selected, page);
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+  use serde_json::json;
+
+  use super::*;
+  use crate::pages::home::ParsedSpec;
+
+  #[test]
+  fn test_jump() -> Result<()> {
+    let paths: serde_json::Map<String, serde_json::Value> =
+      (0..25).map(|index| (format!("/pets/{index:02}"), json!({ "get": { "responses": {} } }))).collect();
+    let document = json!({ "openapi": "3.0.0", "info": { "title": "Petstore", "version": "1.0.0" }, "paths": paths });
+    let mut state = State::default();
+    state.set_spec(ParsedSpec { spec: serde_json::from_value(document.clone()).unwrap(), document });
+    let mut pane = ApisPane::new(Arc::new(RwLock::new(state)), true, Style::default());
+    pane.list_area = Rect::new(1, 1, 40, 10);
+
+    let mut jump = |action: Action| -> Result<usize> {
+      assert_eq!(pane.update(action)?, Some(Action::Update));
+      assert_eq!(pane.state.read().unwrap().active_operation_index, pane.current_operation_index);
+      Ok(pane.current_operation_index)
+    };
+    assert_eq!(jump(Action::PageDown)?, 10);
+    assert_eq!(jump(Action::PageDown)?, 20);
+    assert_eq!(jump(Action::PageDown)?, 24);
+    assert_eq!(jump(Action::PageUp)?, 14);
+    assert_eq!(jump(Action::Top)?, 0);
+    assert_eq!(jump(Action::PageUp)?, 0);
+    assert_eq!(jump(Action::Bottom)?, 24);
     Ok(())
   }
 }
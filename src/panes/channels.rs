@@ -0,0 +1,163 @@
+use std::sync::{Arc, RwLock};
+
+use color_eyre::eyre::Result;
+use crossterm::event::MouseEvent;
+use ratatui::{
+  prelude::*,
+  widgets::{block::*, *},
+};
+
+use crate::{
+  action::Action,
+  pages::asyncapi::AsyncApiState,
+  panes::{jumped_index, list_item_at, render_scrollbar, Pane},
+  theme,
+  tui::{EventResponse, Frame},
+};
+
+/// Lists the operations on the channels of an AsyncAPI document, and the channels without operations.
+pub struct ChannelsPane {
+  focused: bool,
+  focused_border_style: Style,
+  state: Arc<RwLock<AsyncApiState>>,
+  /// Area the operations were last listed in, and the first one shown, to find the operation clicked.
+  list_area: Rect,
+  list_offset: usize,
+}
+
+impl ChannelsPane {
+  pub fn new(state: Arc<RwLock<AsyncApiState>>, focused: bool, focused_border_style: Style) -> Self {
+    Self { focused, focused_border_style, state, list_area: Rect::default(), list_offset: 0 }
+  }
+
+  fn select(&mut self, index: usize) -> Option<Action> {
+    let mut state = self.state.write().unwrap();
+    let len = state.spec.operations.len();
+    (len > 0).then(|| {
+      state.operation_index = index % len;
+      Action::Update
+    })
+  }
+
+  fn border_style(&self) -> Style {
+    match self.focused {
+      true => self.focused_border_style,
+      false => theme::current().border,
+    }
+  }
+
+  fn border_type(&self) -> BorderType {
+    match self.focused {
+      true => BorderType::Thick,
+      false => BorderType::Plain,
+    }
+  }
+}
+
+/// Style of the action, sending ones apart from receiving ones.
+fn action_style(action: &str) -> Style {
+  match action {
+    "publish" | "send" => Style::default().fg(Color::LightYellow),
+    "subscribe" | "receive" => Style::default().fg(Color::LightBlue),
+    _ => Style::default().dim(),
+  }
+}
+
+impl Pane for ChannelsPane {
+  fn focus(&mut self) -> Result<()> {
+    self.focused = true;
+    Ok(())
+  }
+
+  fn unfocus(&mut self) -> Result<()> {
+    self.focused = false;
+    Ok(())
+  }
+
+  fn height_constraint(&self) -> Constraint {
+    Constraint::Fill(1)
+  }
+
+  fn handle_mouse_events(&mut self, mouse: MouseEvent) -> Result<Option<EventResponse<Action>>> {
+    let len = self.state.read().unwrap().spec.operations.len();
+    let index = list_item_at(self.list_area, self.list_offset, mouse).filter(|index| *index < len);
+    let action = index.and_then(|index| self.select(index));
+    Ok(Some(EventResponse::Stop(action.unwrap_or(Action::Render))))
+  }
+
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    let (index, len) = {
+      let state = self.state.read().unwrap();
+      (state.operation_index, state.spec.operations.len())
+    };
+    let selected = match action {
+      Action::Down => index.saturating_add(1),
+      Action::Up => index.saturating_add(len.saturating_sub(1)),
+      Action::PageDown | Action::PageUp | Action::Top | Action::Bottom => {
+        match jumped_index(&action, index, len, usize::from(self.list_area.height)) {
+          Some(selected) => selected,
+          None => return Ok(None),
+        }
+      },
+      Action::Submit => return Ok(Some(Action::FocusNext)),
+      _ => return Ok(None),
+    };
+    Ok(self.select(selected))
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    let state = self.state.read().unwrap();
+    let operations = &state.spec.operations;
+    let width = operations.iter().filter_map(|operation| operation.action.as_ref()).map(String::len).max();
+    let items = operations.iter().map(|operation| {
+      let action = operation.action.as_deref().unwrap_or("channel");
+      let mut spans = vec![
+        Span::styled(format!(" {action:width$}", width = width.unwrap_or_default().max(7)), action_style(action)),
+        Span::styled(format!(" {}", operation.channel), Color::White),
+      ];
+      if let Some(id) = &operation.id {
+        spans.push(Span::styled(format!(" {id}"), Style::default().dim()));
+      }
+      if operation.messages.len() > 1 {
+        spans.push(Span::styled(format!(" [{}]", operation.messages.len()), Style::default().dim()));
+      }
+      Line::from(spans)
+    });
+    let mut list_state = ListState::default().with_selected((!operations.is_empty()).then_some(state.operation_index));
+    let inner = area.inner(&Margin { horizontal: 1, vertical: 1 });
+    frame.render_stateful_widget(
+      List::new(items)
+        .highlight_symbol(symbols::scrollbar::HORIZONTAL.end)
+        .highlight_spacing(HighlightSpacing::Always)
+        .highlight_style(theme::current().selection),
+      inner,
+      &mut list_state,
+    );
+    self.list_area = inner;
+    self.list_offset = list_state.offset();
+    render_scrollbar(frame, area, operations.len(), state.operation_index, usize::from(inner.height));
+
+    let title = match state.spec.title.is_empty() {
+      true => String::from("Channels"),
+      false => format!("Channels of {} {}", state.spec.title, state.spec.version),
+    };
+    frame.render_widget(
+      Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(self.border_style())
+        .border_type(self.border_type())
+        .title_bottom(
+          Line::from(format!(
+            "{} of {}",
+            state.operation_index.saturating_add(1).min(operations.len()),
+            operations.len()
+          ))
+          .right_aligned(),
+        )
+        .title_bottom(Line::from(format!("AsyncAPI {}", state.spec.asyncapi)).style(Style::default().dim())),
+      area,
+    );
+    Ok(())
+  }
+}
@@ -0,0 +1,287 @@
+use std::{
+  collections::{BTreeMap, BTreeSet},
+  sync::{Arc, RwLock},
+};
+
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent};
+use ratatui::{
+  prelude::*,
+  widgets::{block::*, *},
+};
+
+use crate::{
+  action::Action,
+  pages::home::State,
+  panes::{jumped_index, list_item_at, section_index, Pane},
+  theme,
+  tui::{EventResponse, Frame},
+  workspace::{SavedRequest, ScriptTarget},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Row {
+  /// Folder path and depth.
+  Folder(String, usize),
+  /// Index into the saved requests and depth.
+  Request(usize, usize),
+}
+
+/// Browses the saved requests grouped by folder and sends them with the active environment.
+pub struct CollectionsPane {
+  focused: bool,
+  focused_border_style: Style,
+  state: Arc<RwLock<State>>,
+  row_index: usize,
+  collapsed: BTreeSet<String>,
+  /// Rows of the tree as last drawn, scrolled by `list_offset`, mapping clicks to folders and requests.
+  list_area: Rect,
+  list_offset: usize,
+}
+
+impl CollectionsPane {
+  pub fn new(state: Arc<RwLock<State>>, focused: bool, focused_border_style: Style) -> Self {
+    Self {
+      focused,
+      focused_border_style,
+      state,
+      row_index: 0,
+      collapsed: BTreeSet::default(),
+      list_area: Rect::default(),
+      list_offset: 0,
+    }
+  }
+
+  fn border_style(&self) -> Style {
+    match self.focused {
+      true => self.focused_border_style,
+      false => theme::current().border,
+    }
+  }
+
+  fn border_type(&self) -> BorderType {
+    match self.focused {
+      true => BorderType::Thick,
+      false => BorderType::Plain,
+    }
+  }
+
+  fn rows(&self, saved_requests: &[SavedRequest]) -> Vec<Row> {
+    rows(saved_requests, &self.collapsed)
+  }
+
+  fn selected_row(&self) -> Option<Row> {
+    let state = self.state.read().unwrap();
+    self.rows(&state.workspace.saved_requests).into_iter().nth(self.row_index)
+  }
+
+  fn delete_selected(&mut self) -> Result<()> {
+    let Some(Row::Request(index, _)) = self.selected_row() else {
+      return Ok(());
+    };
+    let mut state = self.state.write().unwrap();
+    state.workspace.saved_requests.remove(index);
+    state.workspace.save(&state.openapi_path)?;
+    let rows_len = self.rows(&state.workspace.saved_requests).len();
+    self.row_index = self.row_index.min(rows_len.saturating_sub(1));
+    Ok(())
+  }
+}
+
+/// Flattens the saved requests into folder and request rows, requests without a folder come first and the
+/// content of collapsed folders is skipped.
+fn rows(saved_requests: &[SavedRequest], collapsed: &BTreeSet<String>) -> Vec<Row> {
+  let mut folders: BTreeMap<Vec<&str>, Vec<usize>> = BTreeMap::new();
+  for (index, saved) in saved_requests.iter().enumerate() {
+    let segments = saved.folder.as_deref().unwrap_or_default().split('/').filter(|segment| !segment.is_empty());
+    folders.entry(segments.collect()).or_default().push(index);
+  }
+  let mut rows = vec![];
+  let mut listed_folders = BTreeSet::new();
+  for (segments, indexes) in folders {
+    let mut hidden = false;
+    for depth in 0..segments.len() {
+      let path = segments[..=depth].join("/");
+      if !hidden && listed_folders.insert(path.clone()) {
+        rows.push(Row::Folder(path.clone(), depth));
+      }
+      hidden = hidden || collapsed.contains(&path);
+    }
+    if !hidden {
+      rows.extend(indexes.into_iter().map(|index| Row::Request(index, segments.len())));
+    }
+  }
+  rows
+}
+
+impl Pane for CollectionsPane {
+  fn init(&mut self) -> Result<()> {
+    Ok(())
+  }
+
+  fn focus(&mut self) -> Result<()> {
+    self.focused = true;
+    Ok(())
+  }
+
+  fn unfocus(&mut self) -> Result<()> {
+    self.focused = false;
+    Ok(())
+  }
+
+  fn height_constraint(&self) -> Constraint {
+    match self.focused {
+      true => Constraint::Fill(3),
+      false => Constraint::Fill(1),
+    }
+  }
+
+  fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
+    match key.code {
+      KeyCode::Char('d') => {
+        self.delete_selected()?;
+        Ok(Some(EventResponse::Stop(Action::Render)))
+      },
+      KeyCode::Char('A') => {
+        match self.selected_row() {
+          Some(Row::Request(index, _)) => Ok(Some(EventResponse::Stop(Action::ShowAssertions(index)))),
+          _ => Ok(None),
+        }
+      },
+      KeyCode::Char('t') => {
+        let target = match self.selected_row() {
+          Some(Row::Folder(path, _)) => ScriptTarget::Folder(path),
+          Some(Row::Request(index, _)) => {
+            let state = self.state.read().unwrap();
+            match state.workspace.saved_requests[index].operation.clone() {
+              Some(operation) => ScriptTarget::Operation(operation),
+              None => return Ok(None),
+            }
+          },
+          None => return Ok(None),
+        };
+        Ok(Some(EventResponse::Stop(Action::ShowScripts(target))))
+      },
+      _ => Ok(None),
+    }
+  }
+
+  fn handle_mouse_events(&mut self, mouse: MouseEvent) -> Result<Option<EventResponse<Action>>> {
+    let rows_len = {
+      let state = self.state.read().unwrap();
+      self.rows(&state.workspace.saved_requests).len()
+    };
+    if let Some(index) = list_item_at(self.list_area, self.list_offset, mouse).filter(|index| *index < rows_len) {
+      self.row_index = index;
+    }
+    Ok(Some(EventResponse::Stop(Action::Render)))
+  }
+
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    let rows_len = {
+      let state = self.state.read().unwrap();
+      self.rows(&state.workspace.saved_requests).len()
+    };
+    match action {
+      Action::Down if rows_len > 0 => self.row_index = self.row_index.saturating_add(1) % rows_len,
+      Action::Up if rows_len > 0 => self.row_index = self.row_index.saturating_add(rows_len - 1) % rows_len,
+      Action::PageDown | Action::PageUp | Action::Top | Action::Bottom | Action::NextSection | Action::PrevSection => {
+        // sections are the folders
+        let starts: Vec<bool> = {
+          let state = self.state.read().unwrap();
+          self.rows(&state.workspace.saved_requests).iter().map(|row| matches!(row, Row::Folder(..))).collect()
+        };
+        let page = usize::from(self.list_area.height);
+        let index = jumped_index(&action, self.row_index, rows_len, page)
+          .or_else(|| section_index(&action, self.row_index, &starts));
+        self.row_index = index.unwrap_or(self.row_index);
+      },
+      Action::Submit => {
+        match self.selected_row() {
+          Some(Row::Folder(path, _)) if !self.collapsed.remove(&path) => {
+            self.collapsed.insert(path);
+          },
+          Some(Row::Request(index, _)) => return Ok(Some(Action::SendSavedRequest(index))),
+          _ => {},
+        }
+      },
+      _ => {},
+    }
+    Ok(None)
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    let state = self.state.read().unwrap();
+    let rows = self.rows(&state.workspace.saved_requests);
+    let items = rows.iter().map(|row| {
+      match row {
+        Row::Folder(path, depth) => {
+          let symbol = if self.collapsed.contains(path) { "▸" } else { "▾" };
+          let name = path.rsplit('/').next().unwrap_or_default();
+          Line::from(Span::styled(format!(" {}{symbol} {name}", "  ".repeat(*depth)), Style::default().dim()))
+        },
+        Row::Request(index, depth) => {
+          let saved = &state.workspace.saved_requests[*index];
+          Line::from(vec![
+            Span::raw(format!(" {}", "  ".repeat(*depth))),
+            Span::styled(format!("{:7}", saved.request.method), theme::current().method(&saved.request.method)),
+            Span::styled(saved.name.as_str(), Color::White),
+          ])
+        },
+      }
+    });
+
+    let list = List::new(items)
+      .block(Block::default().borders(Borders::ALL))
+      .highlight_symbol(symbols::scrollbar::HORIZONTAL.end)
+      .highlight_spacing(HighlightSpacing::Always)
+      .highlight_style(theme::current().selection);
+    let mut list_state = ListState::default().with_selected((!rows.is_empty()).then_some(self.row_index));
+    frame.render_stateful_widget(list, area, &mut list_state);
+    self.list_area = area.inner(&Margin { horizontal: 1, vertical: 1 });
+    self.list_offset = list_state.offset();
+
+    let position = match rows.is_empty() {
+      true => String::from("s to save a request"),
+      false => format!("{} of {}", self.row_index.saturating_add(1), rows.len()),
+    };
+    frame.render_widget(
+      Block::default()
+        .title("Collections")
+        .borders(Borders::ALL)
+        .border_style(self.border_style())
+        .border_type(self.border_type())
+        .title_bottom(Line::from(position).right_aligned()),
+      area,
+    );
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_rows() {
+    let saved = |folder: Option<&str>| SavedRequest { folder: folder.map(String::from), ..SavedRequest::default() };
+    let saved_requests = vec![saved(Some("pets/admin")), saved(None), saved(Some("pets")), saved(Some("store"))];
+    assert_eq!(rows(&saved_requests, &BTreeSet::default()), vec![
+      Row::Request(1, 0),
+      Row::Folder(String::from("pets"), 0),
+      Row::Request(2, 1),
+      Row::Folder(String::from("pets/admin"), 1),
+      Row::Request(0, 2),
+      Row::Folder(String::from("store"), 0),
+      Row::Request(3, 1),
+    ]);
+    assert_eq!(rows(&saved_requests, &BTreeSet::from([String::from("pets")])), vec![
+      Row::Request(1, 0),
+      Row::Folder(String::from("pets"), 0),
+      Row::Folder(String::from("store"), 0),
+      Row::Request(3, 1),
+    ]);
+  }
+}
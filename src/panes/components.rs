@@ -0,0 +1,362 @@
+use std::sync::{Arc, RwLock};
+
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent};
+use ratatui::{
+  prelude::*,
+  widgets::{block::*, *},
+};
+use serde_json::Value;
+
+use crate::{
+  action::Action,
+  components::schema_viewer::SchemaViewer,
+  pages::home::State,
+  panes::{list_item_at, Pane},
+  theme,
+  tui::{EventResponse, Frame},
+  webhook,
+};
+
+/// Sections of `components`, and the `webhooks` of 3.1 specs, by key and tab title.
+const SECTIONS: [(&str, &str); 6] = [
+  ("schemas", "Schemas"),
+  ("parameters", "Parameters"),
+  ("responses", "Responses"),
+  ("headers", "Headers"),
+  ("securitySchemes", "Security Schemes"),
+  ("webhooks", "Webhooks"),
+];
+const WEBHOOKS_SECTION_INDEX: usize = 5;
+
+/// Browses the `components` of the spec, independently of the operations using them.
+pub struct ComponentsPane {
+  focused: bool,
+  focused_border_style: Style,
+  state: Arc<RwLock<State>>,
+
+  section_index: usize,
+  component_index: usize,
+  /// Name and definition of the component shown instead of the list, once opened with go.
+  opened: Option<(String, Value)>,
+  schema_viewer: SchemaViewer,
+  /// Area the components were last listed in, and the first one shown, to find the component clicked.
+  list_area: Rect,
+  list_offset: usize,
+}
+
+impl ComponentsPane {
+  pub fn new(state: Arc<RwLock<State>>, focused: bool, focused_border_style: Style) -> Self {
+    Self {
+      focused,
+      focused_border_style,
+      section_index: 0,
+      component_index: 0,
+      opened: None,
+      schema_viewer: SchemaViewer::from(state.clone()),
+      state,
+      list_area: Rect::default(),
+      list_offset: 0,
+    }
+  }
+
+  fn border_style(&self) -> Style {
+    match self.focused {
+      true => self.focused_border_style,
+      false => theme::current().border,
+    }
+  }
+
+  fn border_type(&self) -> BorderType {
+    match self.focused {
+      true => BorderType::Thick,
+      false => BorderType::Plain,
+    }
+  }
+
+  /// Components of the selected section, by name, the payload schemas for webhooks.
+  fn components(&self) -> Vec<(String, Value)> {
+    let state = self.state.read().unwrap();
+    if self.section_index == WEBHOOKS_SECTION_INDEX {
+      let webhooks = webhook::webhooks(&state.openapi_document);
+      return webhooks.into_iter().map(|webhook| (webhook.label(), webhook.schema)).collect();
+    }
+    let section =
+      state.openapi_document.get("components").and_then(|components| components.get(SECTIONS[self.section_index].0));
+    section
+      .and_then(Value::as_object)
+      .into_iter()
+      .flatten()
+      .map(|(name, value)| (name.clone(), value.clone()))
+      .collect()
+  }
+
+  fn open(&mut self) -> Result<()> {
+    if let Some((name, definition)) = self.components().into_iter().nth(self.component_index) {
+      if let Some(webhook) = self.selected_webhook() {
+        let descriptions = [webhook.summary, webhook.description];
+        self.schema_viewer.set_description(Some(descriptions.into_iter().flatten().collect::<Vec<_>>().join("\n\n")));
+      }
+      self.schema_viewer.set(&definition)?;
+      self.opened = Some((name, definition));
+    }
+    Ok(())
+  }
+
+  fn selected_webhook(&self) -> Option<webhook::Webhook> {
+    if self.section_index != WEBHOOKS_SECTION_INDEX {
+      return None;
+    }
+    webhook::webhooks(&self.state.read().unwrap().openapi_document).into_iter().nth(self.component_index)
+  }
+
+  /// Schema shown, or selected in the schemas list.
+  fn selected_schema(&self) -> Option<String> {
+    if let Some(schema) = self.schema_viewer.schema_path().last() {
+      return Some(schema.clone());
+    }
+    match &self.opened {
+      Some((name, _)) => (self.section_index == 0).then(|| name.clone()),
+      None if self.section_index == 0 => self.components().into_iter().nth(self.component_index).map(|(name, _)| name),
+      None => None,
+    }
+  }
+
+  fn close(&mut self) {
+    self.opened = None;
+    self.schema_viewer.clear();
+  }
+
+  fn path_line(&self) -> Line<'_> {
+    let Some((name, _)) = &self.opened else {
+      return Line::default();
+    };
+    match self.schema_viewer.schema_path().is_empty() {
+      true => Line::from(format!("[ {name} ]")),
+      false => self.schema_viewer.trail(name),
+    }
+  }
+}
+
+impl Pane for ComponentsPane {
+  fn init(&mut self) -> Result<()> {
+    Ok(())
+  }
+
+  fn focus(&mut self) -> Result<()> {
+    self.focused = true;
+    Ok(())
+  }
+
+  fn unfocus(&mut self) -> Result<()> {
+    self.focused = false;
+    Ok(())
+  }
+
+  fn height_constraint(&self) -> Constraint {
+    match self.focused {
+      true => Constraint::Fill(3),
+      false => Constraint::Fill(1),
+    }
+  }
+
+  fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
+    match key.code {
+      KeyCode::Char('J') | KeyCode::Char('Y')
+        if [0, WEBHOOKS_SECTION_INDEX].contains(&self.section_index)
+          || !self.schema_viewer.schema_path().is_empty() =>
+      {
+        let Some((root, _)) = &self.opened else {
+          return Ok(None);
+        };
+        let action: fn(String, Box<Value>) -> Action = match key.code {
+          KeyCode::Char('J') => Action::ExportSchema,
+          _ => Action::ShowTypes,
+        };
+        let shown = self.schema_viewer.shown(root);
+        Ok(shown.map(|(name, schema)| EventResponse::Stop(action(name, Box::new(schema)))))
+      },
+      KeyCode::Char('X') if self.opened.is_some() => {
+        self.schema_viewer.toggle_extensions()?;
+        Ok(Some(EventResponse::Stop(Action::Render)))
+      },
+      KeyCode::Char('y') if self.opened.is_some() => {
+        Ok(self.schema_viewer.link().map(|link| EventResponse::Stop(Action::Copy(link))))
+      },
+      KeyCode::Char('m') if self.opened.is_some() => {
+        Ok(self.schema_viewer.link().map(|link| EventResponse::Stop(Action::OpenUrl(link))))
+      },
+      KeyCode::Char('i') => {
+        let Some(webhook) = self.selected_webhook() else {
+          return Ok(None);
+        };
+        let payload = serde_json::to_string_pretty(&webhook.payload)?;
+        Ok(Some(EventResponse::Stop(Action::ShowPayload(webhook.label(), payload))))
+      },
+      KeyCode::Char('U') => {
+        match self.selected_schema() {
+          Some(schema) => Ok(Some(EventResponse::Stop(Action::ShowUsages(schema)))),
+          None => Ok(None),
+        }
+      },
+      _ => Ok(None),
+    }
+  }
+
+  fn handle_mouse_events(&mut self, mouse: MouseEvent) -> Result<Option<EventResponse<Action>>> {
+    if self.opened.is_none() {
+      let components_len = self.components().len();
+      if let Some(index) = list_item_at(self.list_area, self.list_offset, mouse).filter(|index| *index < components_len)
+      {
+        self.component_index = index;
+      }
+    }
+    Ok(Some(EventResponse::Stop(Action::Render)))
+  }
+
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    match action {
+      Action::Down if self.opened.is_some() => self.schema_viewer.down(),
+      Action::Up if self.opened.is_some() => self.schema_viewer.up(),
+      Action::PageDown | Action::PageUp | Action::Top | Action::Bottom if self.opened.is_some() => {
+        self.schema_viewer.jump(&action)
+      },
+      Action::Down => {
+        let components_len = self.components().len().max(1);
+        self.component_index = self.component_index.saturating_add(1) % components_len;
+      },
+      Action::Up => {
+        let components_len = self.components().len().max(1);
+        self.component_index = self.component_index.saturating_add(components_len - 1) % components_len;
+      },
+      Action::Tab(index) if (index as usize) < SECTIONS.len() => {
+        self.section_index = index as usize;
+        self.component_index = 0;
+        self.close();
+      },
+      Action::Go if self.opened.is_some() => self.schema_viewer.go()?,
+      Action::NextBranch if self.opened.is_some() => self.schema_viewer.next_branch()?,
+      Action::Go | Action::Submit => self.open()?,
+      Action::BackTo(depth) => {
+        if let Some((_, definition)) = &self.opened {
+          let definition = definition.clone();
+          self.schema_viewer.back_to(depth, &definition)?;
+        }
+      },
+      Action::Back => {
+        match &self.opened {
+          Some((_, definition)) if !self.schema_viewer.schema_path().is_empty() => {
+            let definition = definition.clone();
+            self.schema_viewer.back(&definition)?;
+          },
+          _ => self.close(),
+        }
+      },
+      _ => {},
+    }
+    Ok(None)
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    let inner = area.inner(&Margin { horizontal: 1, vertical: 1 });
+    frame.render_widget(
+      Tabs::new(SECTIONS.iter().map(|(_, title)| Span::styled(*title, Style::default().dim())))
+        .highlight_style(theme::current().active_tab)
+        .select(self.section_index),
+      inner,
+    );
+    let mut inner = inner.inner(&Margin { horizontal: 1, vertical: 1 });
+    inner.height = inner.height.saturating_add(1);
+
+    let components = self.components();
+    match self.opened {
+      Some(_) => self.schema_viewer.render_widget(frame, inner),
+      None => {
+        let items = components.iter().map(|(name, _)| Line::from(format!(" {name}")));
+        let mut list_state =
+          ListState::default().with_selected((!components.is_empty()).then_some(self.component_index));
+        frame.render_stateful_widget(
+          List::new(items)
+            .highlight_symbol(symbols::scrollbar::HORIZONTAL.end)
+            .highlight_spacing(HighlightSpacing::Always)
+            .highlight_style(theme::current().selection),
+          inner,
+          &mut list_state,
+        );
+        self.list_area = inner;
+        self.list_offset = list_state.offset();
+      },
+    }
+
+    frame.render_widget(
+      Block::default()
+        .title("Components")
+        .borders(Borders::ALL)
+        .border_style(self.border_style())
+        .border_type(self.border_type())
+        .title_bottom(
+          self.path_line().style(Style::default().fg(Color::White).dim().add_modifier(Modifier::ITALIC)).left_aligned(),
+        )
+        .title_bottom(
+          Line::from(format!(
+            "{} of {}",
+            self.component_index.saturating_add(1).min(components.len()),
+            components.len()
+          ))
+          .right_aligned(),
+        ),
+      area,
+    );
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+  use serde_json::json;
+
+  use super::*;
+
+  #[test]
+  fn test_browse_components() -> Result<()> {
+    let document = json!({ "components": {
+      "schemas": {
+        "Error": { "type": "object", "properties": { "message": { "type": "string" } } },
+        "Pet": { "type": "object", "properties": { "name": { "type": "string" } } }
+      },
+      "parameters": { "limit": { "name": "limit", "in": "query", "schema": { "type": "integer" } } }
+    } });
+    let mut state = State::default();
+    state.openapi_document = document;
+    let mut pane = ComponentsPane::new(Arc::new(RwLock::new(state)), true, Style::default());
+    let names = |pane: &ComponentsPane| pane.components().into_iter().map(|(name, _)| name).collect::<Vec<_>>();
+    let usages = |pane: &mut ComponentsPane| {
+      match pane.handle_key_events(KeyEvent::from(KeyCode::Char('U'))) {
+        Ok(Some(EventResponse::Stop(Action::ShowUsages(schema)))) => Some(schema),
+        _ => None,
+      }
+    };
+    assert_eq!(names(&pane), vec!["Error", "Pet"]);
+    pane.update(Action::Down)?;
+    assert_eq!(usages(&mut pane).as_deref(), Some("Pet"));
+    pane.update(Action::Down)?;
+    assert_eq!(pane.component_index, 0);
+
+    pane.update(Action::Up)?;
+    pane.update(Action::Submit)?;
+    assert_eq!(pane.opened.as_ref().map(|(name, _)| name.as_str()), Some("Pet"));
+    assert_eq!(pane.path_line(), Line::from("[ Pet ]"));
+    assert_eq!(usages(&mut pane).as_deref(), Some("Pet"));
+    pane.update(Action::Back)?;
+    assert!(pane.opened.is_none());
+
+    // other sections list their components, without usages
+    pane.update(Action::Tab(1))?;
+    assert_eq!(names(&pane), vec!["limit"]);
+    assert_eq!(usages(&mut pane), None);
+    pane.update(Action::Tab(3))?;
+    assert!(names(&pane).is_empty());
+    Ok(())
+  }
+}
@@ -0,0 +1,178 @@
+use std::sync::{Arc, RwLock};
+
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent};
+use ratatui::{
+  prelude::*,
+  widgets::{block::*, *},
+};
+
+use crate::{
+  action::Action,
+  client::Exchange,
+  pages::home::State,
+  panes::{jumped_index, list_item_at, Pane},
+  theme,
+  toast::ToastLevel,
+  tui::{EventResponse, Frame},
+};
+
+/// Lists the sent requests, newest first, to inspect their responses or send them again.
+pub struct HistoryPane {
+  focused: bool,
+  focused_border_style: Style,
+  state: Arc<RwLock<State>>,
+  /// Index counted from the newest entry.
+  entry_index: usize,
+  /// Where the exchanges were last drawn and the first one shown, which tell the exchange a click lands on.
+  list_area: Rect,
+  list_offset: usize,
+}
+
+impl HistoryPane {
+  pub fn new(state: Arc<RwLock<State>>, focused: bool, focused_border_style: Style) -> Self {
+    Self { focused, focused_border_style, state, entry_index: 0, list_area: Rect::default(), list_offset: 0 }
+  }
+
+  fn border_style(&self) -> Style {
+    match self.focused {
+      true => self.focused_border_style,
+      false => theme::current().border,
+    }
+  }
+
+  fn border_type(&self) -> BorderType {
+    match self.focused {
+      true => BorderType::Thick,
+      false => BorderType::Plain,
+    }
+  }
+
+  fn selected(&self) -> Option<Exchange> {
+    self.state.read().unwrap().history.iter().rev().nth(self.entry_index).cloned()
+  }
+
+  fn time(exchange: &Exchange) -> String {
+    chrono::DateTime::parse_from_rfc3339(&exchange.started_at)
+      .map(|started_at| started_at.with_timezone(&chrono::Local).format("%m-%d %H:%M:%S").to_string())
+      .unwrap_or_default()
+  }
+}
+
+impl Pane for HistoryPane {
+  fn init(&mut self) -> Result<()> {
+    Ok(())
+  }
+
+  fn focus(&mut self) -> Result<()> {
+    self.focused = true;
+    Ok(())
+  }
+
+  fn unfocus(&mut self) -> Result<()> {
+    self.focused = false;
+    Ok(())
+  }
+
+  fn height_constraint(&self) -> Constraint {
+    match self.focused {
+      true => Constraint::Fill(3),
+      false => Constraint::Fill(1),
+    }
+  }
+
+  fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
+    match key.code {
+      KeyCode::Char('r') => {
+        let Some(exchange) = self.selected() else {
+          return Ok(None);
+        };
+        let action = match self.state.read().unwrap().sent_request(&exchange) {
+          Ok(request) => Action::SendHttpRequest(Box::new(request), exchange.operation),
+          Err(error) => Action::Notify(ToastLevel::Error, error),
+        };
+        Ok(Some(EventResponse::Stop(action)))
+      },
+      KeyCode::Char('d') => {
+        Ok(self.selected().map(|exchange| EventResponse::Stop(Action::ShowResponseDiff(Box::new(exchange)))))
+      },
+      _ => Ok(None),
+    }
+  }
+
+  fn handle_mouse_events(&mut self, mouse: MouseEvent) -> Result<Option<EventResponse<Action>>> {
+    let history_len = self.state.read().unwrap().history.len();
+    if let Some(index) = list_item_at(self.list_area, self.list_offset, mouse).filter(|index| *index < history_len) {
+      self.entry_index = index;
+    }
+    Ok(Some(EventResponse::Stop(Action::Render)))
+  }
+
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    let history_len = self.state.read().unwrap().history.len();
+    match action {
+      Action::Down if history_len > 0 => self.entry_index = self.entry_index.saturating_add(1) % history_len,
+      Action::Up if history_len > 0 => {
+        self.entry_index = self.entry_index.saturating_add(history_len - 1) % history_len
+      },
+      Action::PageDown | Action::PageUp | Action::Top | Action::Bottom => {
+        let page = usize::from(self.list_area.height);
+        self.entry_index = jumped_index(&action, self.entry_index, history_len, page).unwrap_or(self.entry_index);
+      },
+      Action::Submit => {
+        if let Some(exchange) = self.selected() {
+          return Ok(Some(Action::ShowExchange(Box::new(exchange))));
+        }
+      },
+      _ => {},
+    }
+    Ok(None)
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    let state = self.state.read().unwrap();
+    let items = state.history.iter().rev().map(|exchange| {
+      Line::from(vec![
+        Span::styled(format!(" {} ", Self::time(exchange)), Style::default().dim()),
+        Span::styled(format!("{} ", exchange.response.status), theme::current().status_code(exchange.response.status)),
+        Span::styled(format!("{:7}", exchange.request.method), Color::White),
+        Span::raw(exchange.request.path.as_str()),
+        Span::styled(format!(" {} ms", exchange.elapsed_ms), Style::default().dim()),
+      ])
+    });
+
+    let list = List::new(items)
+      .block(Block::default().borders(Borders::ALL))
+      .highlight_symbol(symbols::scrollbar::HORIZONTAL.end)
+      .highlight_spacing(HighlightSpacing::Always)
+      .highlight_style(theme::current().selection);
+    let mut list_state = ListState::default().with_selected((!state.history.is_empty()).then_some(self.entry_index));
+    frame.render_stateful_widget(list, area, &mut list_state);
+    self.list_area = area.inner(&Margin { horizontal: 1, vertical: 1 });
+    self.list_offset = list_state.offset();
+
+    const ARROW: &str = symbols::scrollbar::HORIZONTAL.end;
+    let hints = match self.focused {
+      true => format!("[enter {ARROW} inspect] [r {ARROW} re-run]"),
+      false => String::default(),
+    };
+    frame.render_widget(
+      Block::default()
+        .title("History")
+        .borders(Borders::ALL)
+        .border_style(self.border_style())
+        .border_type(self.border_type())
+        .title_bottom(Line::from(hints).style(Style::default().dim()).left_aligned())
+        .title_bottom(
+          Line::from(format!(
+            "{} of {}",
+            self.entry_index.saturating_add(1).min(state.history.len()),
+            state.history.len()
+          ))
+          .right_aligned(),
+        ),
+      area,
+    );
+    Ok(())
+  }
+}
@@ -0,0 +1,218 @@
+use std::{
+  collections::BTreeMap,
+  sync::{Arc, RwLock},
+};
+
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use oas3::Schema;
+use ratatui::{
+  prelude::*,
+  widgets::{block::*, *},
+};
+use serde_json::Value;
+
+use crate::{
+  action::Action,
+  asyncapi::{AsyncApi, Message},
+  components::schema_viewer::SchemaViewer,
+  pages::asyncapi::AsyncApiState,
+  panes::Pane,
+  theme,
+  tui::{EventResponse, Frame},
+};
+
+/// Shows the messages of the selected operation, one at a time, with the schema of their payload or headers.
+pub struct MessagesPane {
+  focused: bool,
+  focused_border_style: Style,
+  state: Arc<RwLock<AsyncApiState>>,
+  message_index: usize,
+  /// Whether the headers of the message are shown instead of its payload.
+  headers_shown: bool,
+  schema_viewer: SchemaViewer,
+}
+
+impl MessagesPane {
+  pub fn new(state: Arc<RwLock<AsyncApiState>>, focused: bool, focused_border_style: Style) -> Self {
+    let components = components(&state.read().unwrap().spec);
+    Self {
+      focused,
+      focused_border_style,
+      state,
+      message_index: 0,
+      headers_shown: false,
+      schema_viewer: SchemaViewer::new(components),
+    }
+  }
+
+  fn message(&self) -> Option<Message> {
+    let state = self.state.read().unwrap();
+    state.active_operation().and_then(|operation| operation.messages.get(self.message_index)).cloned()
+  }
+
+  /// Schema of the payload or of the headers of the message.
+  fn shown(&self, message: &Message) -> Value {
+    match self.headers_shown {
+      true => message.headers.clone().unwrap_or_default(),
+      false => message.payload.clone(),
+    }
+  }
+
+  /// Shows the selected message, described by its operation and channel.
+  fn show(&mut self) -> Result<()> {
+    self.schema_viewer.clear();
+    let descriptions: Vec<String> = {
+      let state = self.state.read().unwrap();
+      let Some(operation) = state.active_operation() else {
+        return Ok(());
+      };
+      let message = operation.messages.get(self.message_index);
+      let mut descriptions = vec![operation.summary.clone(), operation.description.clone()];
+      if message.is_none() {
+        descriptions.push(operation.channel_description.clone());
+      }
+      if let Some(message) = message {
+        descriptions.extend([message.title.clone(), message.summary.clone(), message.description.clone()]);
+        descriptions.push(message.content_type.as_ref().map(|content_type| format!("Content type: `{content_type}`")));
+      }
+      descriptions.into_iter().flatten().collect()
+    };
+    self.schema_viewer.set_description(Some(descriptions.join("\n\n")));
+    match self.message() {
+      Some(message) => self.schema_viewer.set(self.shown(&message)),
+      None => self.schema_viewer.set(Value::Null),
+    }
+  }
+
+  fn border_style(&self) -> Style {
+    match self.focused {
+      true => self.focused_border_style,
+      false => theme::current().border,
+    }
+  }
+
+  fn border_type(&self) -> BorderType {
+    match self.focused {
+      true => BorderType::Thick,
+      false => BorderType::Plain,
+    }
+  }
+}
+
+/// Component schemas of the document, those valid as OpenAPI schemas, which the viewer goes into.
+fn components(spec: &AsyncApi) -> BTreeMap<String, Schema> {
+  let schemas = spec.schemas.iter();
+  schemas
+    .filter_map(|(name, schema)| serde_json::from_value(schema.clone()).ok().map(|schema| (name.clone(), schema)))
+    .collect()
+}
+
+impl Pane for MessagesPane {
+  fn init(&mut self) -> Result<()> {
+    self.show()
+  }
+
+  fn focus(&mut self) -> Result<()> {
+    self.focused = true;
+    Ok(())
+  }
+
+  fn unfocus(&mut self) -> Result<()> {
+    self.focused = false;
+    Ok(())
+  }
+
+  fn height_constraint(&self) -> Constraint {
+    Constraint::Fill(1)
+  }
+
+  fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
+    match key.code {
+      KeyCode::Char('i') if self.message().is_some_and(|message| message.headers.is_some()) => {
+        self.headers_shown = !self.headers_shown;
+        self.show()?;
+        Ok(Some(EventResponse::Stop(Action::Render)))
+      },
+      _ => Ok(None),
+    }
+  }
+
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    let Some(message) = self.message() else {
+      if action == Action::Update {
+        self.message_index = 0;
+        self.show()?;
+      }
+      return Ok(None);
+    };
+    match action {
+      Action::Update => {
+        self.message_index = 0;
+        self.headers_shown = false;
+        self.show()?;
+      },
+      Action::Tab(index) => {
+        let len = self.state.read().unwrap().active_operation().map_or(0, |operation| operation.messages.len());
+        if (index as usize) < len {
+          self.message_index = index as usize;
+          self.headers_shown = false;
+          self.show()?;
+        }
+      },
+      Action::Down => self.schema_viewer.down(),
+      Action::Up => self.schema_viewer.up(),
+      Action::PageDown | Action::PageUp | Action::Top | Action::Bottom => self.schema_viewer.jump(&action),
+      Action::Go => self.schema_viewer.go()?,
+      Action::NextBranch => self.schema_viewer.next_branch()?,
+      Action::Back => self.schema_viewer.back(self.shown(&message))?,
+      Action::BackTo(depth) => self.schema_viewer.back_to(depth, self.shown(&message))?,
+      _ => return Ok(None),
+    }
+    Ok(Some(Action::Render))
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    let inner = area.inner(&Margin { horizontal: 1, vertical: 1 });
+    let names: Vec<String> = {
+      let state = self.state.read().unwrap();
+      let messages = state.active_operation().map(|operation| operation.messages.as_slice()).unwrap_or_default();
+      messages.iter().map(|message| message.name.clone()).collect()
+    };
+    frame.render_widget(
+      Tabs::new(names.iter().map(|name| Span::styled(name.as_str(), Style::default().dim())))
+        .highlight_style(theme::current().active_tab)
+        .select(self.message_index),
+      inner,
+    );
+    let mut inner = inner.inner(&Margin { horizontal: 1, vertical: 1 });
+    inner.height = inner.height.saturating_add(1);
+    match names.is_empty() {
+      true => frame.render_widget(Paragraph::new("No messages on this channel.").style(Style::default().dim()), inner),
+      false => self.schema_viewer.render_widget(frame, inner),
+    }
+
+    let root = names.get(self.message_index).cloned().unwrap_or_default();
+    let title = match self.headers_shown {
+      true => "Message Headers",
+      false => "Message Payload",
+    };
+    frame.render_widget(
+      Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(self.border_style())
+        .border_type(self.border_type())
+        .title_bottom(
+          self
+            .schema_viewer
+            .trail(&root)
+            .style(Style::default().fg(Color::White).dim().add_modifier(Modifier::ITALIC))
+            .left_aligned(),
+        )
+        .title_bottom(self.schema_viewer.position_line().right_aligned()),
+      area,
+    );
+    Ok(())
+  }
+}
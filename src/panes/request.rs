@@ -1,26 +1,121 @@
-use std::sync::{Arc, RwLock};
+use std::{
+  cell::RefCell,
+  collections::{BTreeMap, BTreeSet},
+  rc::Rc,
+  sync::{Arc, RwLock},
+};
 
 use color_eyre::eyre::Result;
-use crossterm::event::{KeyEvent, MouseEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent};
 use oas3::{spec::RequestBody, Schema};
 use ratatui::{
   prelude::*,
   widgets::{block::*, *},
 };
+use tui_textarea::{CursorMove, TextArea};
 
 use crate::{
   action::Action,
-  components::schema_viewer::SchemaViewer,
+  body_form::{self, Field, FieldKind},
+  callback,
+  components::{example_picker::ExamplePicker, schema_viewer::SchemaViewer},
+  extensions, link, markdown,
+  mock::{self, NamedExample},
+  multipart::{self, FormPart},
+  negotiation::{self, ContentTypes},
   pages::home::State,
   panes::Pane,
-  tui::{EventResponse, Frame},
+  secrets,
+  security::{self, SecurityScheme, SecuritySource},
+  theme,
+  toast::ToastLevel,
+  tui::{self, EventResponse, Frame},
+  undo::UndoHistory,
+  wire,
+  workspace::{HeaderOverride, RequestDraft},
 };
 
+/// Tabs of the pane, the parameters, headers and bodies listing their items in a row below.
+const SECTIONS: [&str; 5] = ["Params", "Headers", "Body", "Auth", "Docs"];
+const PARAMS_SECTION: usize = 0;
+const HEADERS_SECTION: usize = 1;
+const BODY_SECTION: usize = 2;
+const AUTH_SECTION: usize = 3;
+const DOCS_SECTION: usize = 4;
+
+/// Item selected in a section without items, or without any, such as the auth and docs.
+const NO_ITEM: usize = usize::MAX;
+
 pub struct RequestType {
   location: String,
   media_type: String,
   schema: Schema,
   title: String,
+  description: Option<String>,
+  required: bool,
+  deprecated: bool,
+  /// `default` of the schema, shown next to the title.
+  default: Option<String>,
+  /// `example` of the parameter or its schema, shown next to the title when there is no default.
+  example: Option<String>,
+}
+
+impl RequestType {
+  /// Title of the tab with its badges: `*` when required, struck through when deprecated, and its default or
+  /// example value.
+  fn tab(&self, color: Color) -> Line<'_> {
+    const MAX_VALUE_WIDTH: usize = 16;
+    let shorten = |value: &str| {
+      match value.chars().count() > MAX_VALUE_WIDTH {
+        true => format!("{}…", value.chars().take(MAX_VALUE_WIDTH - 1).collect::<String>()),
+        false => value.to_string(),
+      }
+    };
+    let mut title = Style::default().fg(color);
+    if self.deprecated {
+      title = title.add_modifier(Modifier::CROSSED_OUT);
+    }
+    let mut spans = vec![Span::styled(self.title.as_str(), title)];
+    if self.required {
+      spans.push(Span::styled("*", Style::default().fg(Color::LightRed)));
+    }
+    match (&self.default, &self.example) {
+      (Some(default), _) => spans.push(Span::styled(format!(" ={}", shorten(default)), Style::default().fg(color))),
+      (None, Some(example)) => {
+        spans.push(Span::styled(format!(" e.g. {}", shorten(example)), Style::default().fg(color).italic()))
+      },
+      (None, None) => {},
+    }
+    if !self.media_type.is_empty() {
+      spans.push(Span::styled(format!(" [{}]", self.media_type), Style::default().fg(color)));
+    }
+    Line::from(spans).dim()
+  }
+
+  /// Section of the pane the parameter, body or callback is listed in.
+  fn section(&self) -> usize {
+    match self.location.as_str() {
+      "body" | "callback" => BODY_SECTION,
+      "header" => HEADERS_SECTION,
+      _ => PARAMS_SECTION,
+    }
+  }
+}
+
+/// JSON body of the selected tab edited as a form, along with what it was listed from.
+struct JsonForm {
+  /// Draft key, selected tab, draft value and expanded fields the form was listed from.
+  source: (Option<String>, usize, Option<String>, BTreeSet<String>),
+  /// Schema of the body, `None` when it is not a JSON object.
+  schema: Option<serde_json::Value>,
+  /// The draft, or else the example of the operation, `None` when it is not JSON.
+  body: Option<serde_json::Value>,
+  fields: Vec<Field>,
+}
+
+/// Tells why a field of the body cannot be edited, the draft not being JSON.
+fn invalid_form_body() -> Action {
+  Action::Notify(ToastLevel::Error, String::from("The body is not valid JSON, ctrl-f edits it raw"))
 }
 
 #[derive(Default)]
@@ -30,8 +125,31 @@ pub struct RequestPane {
   state: Arc<RwLock<State>>,
 
   schemas: Vec<RequestType>,
+  section_index: usize,
+  /// Selected schema, or the default headers past the schemas, `NO_ITEM` when the section has none.
   schemas_index: usize,
+  /// Lines the docs are scrolled by.
+  docs_offset: u16,
   schema_viewer: SchemaViewer,
+  /// Named examples of the selected body.
+  example_picker: ExamplePicker,
+  header_index: usize,
+  /// Value of the selected default header being edited, `None` when not editing.
+  header_editor: Option<TextArea<'static>>,
+  /// Draft value of the selected parameter or body being edited, `None` when not editing.
+  value_editor: Option<TextArea<'static>>,
+  /// Selected part of a multipart body.
+  part_index: usize,
+  /// Whether JSON object bodies are edited as a form of their properties rather than as raw text.
+  json_form: bool,
+  /// Selected field of a JSON body edited as a form.
+  field_index: usize,
+  /// JSONPaths of the objects and arrays whose fields are listed in the form.
+  expanded_fields: BTreeSet<String>,
+  /// Form of the selected body, listed anew only once the draft, the selected tab or the expanded fields change.
+  json_form_cache: RefCell<Option<Rc<JsonForm>>>,
+  /// Changes of the drafts by draft key, undone with `ctrl-z`.
+  draft_history: BTreeMap<String, UndoHistory<RequestDraft>>,
 }
 
 impl RequestPane {
@@ -40,16 +158,956 @@ impl RequestPane {
       focused,
       focused_border_style,
       schemas: Vec::default(),
-      schemas_index: 0,
+      section_index: PARAMS_SECTION,
+      schemas_index: NO_ITEM,
+      docs_offset: 0,
       schema_viewer: SchemaViewer::from(state.clone()),
+      example_picker: ExamplePicker::default(),
+      header_index: 0,
+      header_editor: None,
+      value_editor: None,
+      part_index: 0,
+      json_form: false,
+      field_index: 0,
+      expanded_fields: BTreeSet::default(),
+      json_form_cache: RefCell::default(),
+      draft_history: BTreeMap::default(),
       state,
     }
   }
 
+  /// Default headers of the active environment as sent for the active operation, listed in the headers section after
+  /// the header parameters.
+  fn default_headers(&self) -> Vec<(String, Option<String>)> {
+    let state = self.state.read().unwrap();
+    state.active_operation_key().map(|operation| state.default_headers(&operation)).unwrap_or_default()
+  }
+
+  fn headers_tab_selected(&self) -> bool {
+    self.section_index == HEADERS_SECTION
+      && self.schemas_index == self.schemas.len()
+      && !self.default_headers().is_empty()
+  }
+
+  /// Items of the section, as indexes of their schemas, the default headers past them.
+  fn section_items(&self, section: usize) -> Vec<usize> {
+    let mut items: Vec<usize> =
+      (0..self.schemas.len()).filter(|index| self.schemas[*index].section() == section).collect();
+    if section == HEADERS_SECTION && !self.default_headers().is_empty() {
+      items.push(self.schemas.len());
+    }
+    items
+  }
+
+  /// Selects the section and its first item.
+  fn select_section(&mut self, section: usize) -> Result<()> {
+    self.section_index = section;
+    self.schemas_index = self.section_items(section).first().copied().unwrap_or(NO_ITEM);
+    self.docs_offset = 0;
+    self.init_schema()
+  }
+
+  /// Selects the next item of the section, or the previous one when not `forward`, wrapping around.
+  fn cycle_item(&mut self, forward: bool) -> Result<()> {
+    let items = self.section_items(self.section_index);
+    let Some(position) = items.iter().position(|index| *index == self.schemas_index) else {
+      return Ok(());
+    };
+    let position = match forward {
+      true => (position + 1) % items.len(),
+      false => (position + items.len() - 1) % items.len(),
+    };
+    self.schemas_index = items[position];
+    self.init_schema()
+  }
+
+  fn selected_header(&self) -> Option<(String, Option<String>)> {
+    if !self.headers_tab_selected() {
+      return None;
+    }
+    self.default_headers().into_iter().nth(self.header_index)
+  }
+
+  fn set_header_override(&mut self, value: Option<Option<String>>) -> Result<()> {
+    let Some((name, _)) = self.selected_header() else {
+      return Ok(());
+    };
+    let mut state = self.state.write().unwrap();
+    let Some(operation) = state.active_operation_key() else {
+      return Ok(());
+    };
+    match value {
+      Some(value) => state.workspace.set_header_override(HeaderOverride { operation, name, value }),
+      None => state.workspace.clear_header_override(&operation, &name),
+    }
+    state.workspace.save(&state.openapi_path)?;
+    Ok(())
+  }
+
+  fn start_editing_header(&mut self) {
+    if let Some((_, value)) = self.selected_header() {
+      let mut editor = TextArea::new(vec![value.unwrap_or_default()]);
+      editor.set_cursor_line_style(Style::default());
+      editor.move_cursor(CursorMove::End);
+      self.header_editor = Some(editor);
+    }
+  }
+
+  fn body_selected(&self) -> bool {
+    self.schemas.get(self.schemas_index).is_some_and(|request_type| request_type.location == "body")
+  }
+
+  /// Whether the selected body is a `multipart/form-data` or urlencoded one, edited field by field.
+  fn form_selected(&self) -> bool {
+    self.body_selected()
+      && self.schemas.get(self.schemas_index).is_some_and(|request_type| multipart::is_form(&request_type.media_type))
+  }
+
+  /// Parts of the selected form body, from its schema, with their draft values.
+  fn form_parts(&self) -> Vec<FormPart> {
+    let Some(request_type) = self.schemas.get(self.schemas_index).filter(|_| self.form_selected()) else {
+      return vec![];
+    };
+    let schema = serde_json::to_value(&request_type.schema).unwrap_or_default();
+    let state = self.state.read().unwrap();
+    let draft = state.active_draft();
+    multipart::parts(&request_type.media_type, &schema)
+      .into_iter()
+      .map(|part| {
+        let value = draft.and_then(|draft| draft.part(&part.name)).map(String::from).unwrap_or_default();
+        FormPart { value, ..part }
+      })
+      .collect()
+  }
+
+  /// The selected body as edited as a form, its schema serialized and its draft parsed anew only once the draft, the
+  /// selected tab or the expanded fields change.
+  fn json_form(&self) -> Rc<JsonForm> {
+    let source =
+      (self.state.read().unwrap().draft_key(), self.schemas_index, self.draft_value(), self.expanded_fields.clone());
+    let mut cache = self.json_form_cache.borrow_mut();
+    if let Some(form) = cache.as_ref().filter(|form| form.source == source) {
+      return form.clone();
+    }
+    let schema = self.json_object_schema();
+    let body = schema.as_ref().and_then(|_| self.form_body(source.2.clone()));
+    let fields = match (&schema, &body) {
+      (Some(schema), Some(body)) => {
+        body_form::fields(schema, &self.state.read().unwrap().openapi_document, body, &self.expanded_fields)
+      },
+      _ => vec![],
+    };
+    let form = Rc::new(JsonForm { source, schema, body, fields });
+    *cache = Some(form.clone());
+    form
+  }
+
+  /// JSON schema of the selected body, when it is a JSON object edited property by property.
+  fn json_object_schema(&self) -> Option<serde_json::Value> {
+    let request_type = self.schemas.get(self.schemas_index).filter(|_| self.body_selected())?;
+    if !request_type.media_type.contains("json") {
+      return None;
+    }
+    let schema = serde_json::to_value(&request_type.schema).ok()?;
+    body_form::is_object(&schema, &self.state.read().unwrap().openapi_document).then_some(schema)
+  }
+
+  /// Whether the selected body is a JSON object, which can be edited property by property.
+  fn json_object_selected(&self) -> bool {
+    self.json_form().schema.is_some()
+  }
+
+  /// Whether the selected body is edited as a form of the properties of its schema.
+  fn json_form_selected(&self) -> bool {
+    self.json_form && self.json_object_selected()
+  }
+
+  /// Body edited as a form: the draft, or else the example of the operation, `None` when it is not JSON.
+  fn form_body(&self, draft: Option<String>) -> Option<serde_json::Value> {
+    let body = match draft {
+      Some(body) => body,
+      None => self.state.read().unwrap().active_request().and_then(|request| request.body).unwrap_or_default(),
+    };
+    match body.trim().is_empty() {
+      true => Some(serde_json::Value::Object(serde_json::Map::new())),
+      false => serde_json::from_str(&body).ok(),
+    }
+  }
+
+  fn selected_field(&self) -> Option<Field> {
+    self.json_form().fields.get(self.field_index).cloned()
+  }
+
+  /// Saves the body edited as a form to the draft, serialized to JSON, telling when the draft is not JSON instead.
+  fn change_form_body(&mut self, change: impl FnOnce(&mut serde_json::Value)) -> Result<Option<Action>> {
+    let Some(mut body) = self.json_form().body.clone() else {
+      return Ok(Some(invalid_form_body()));
+    };
+    change(&mut body);
+    self.set_draft_value(Some(serde_json::to_string_pretty(&body)?))?;
+    self.field_index = self.field_index.min(self.json_form().fields.len().saturating_sub(1));
+    Ok(None)
+  }
+
+  /// Sets the selected field to the value typed for it, telling why when the value does not fit its type.
+  fn apply_field(&mut self, text: &str) -> Result<Option<Action>> {
+    let Some(field) = self.selected_field() else {
+      return Ok(None);
+    };
+    match field.parse(text) {
+      Ok(value) => self.change_form_body(|body| body_form::set(body, &field.path, value)),
+      Err(error) => Ok(Some(Action::Notify(ToastLevel::Error, format!("{}: {error}", field.label)))),
+    }
+  }
+
+  /// Toggles a boolean, cycles a choice, or expands and collapses an object or array.
+  fn toggle_field(&mut self) -> Result<()> {
+    let Some(field) = self.selected_field() else {
+      return Ok(());
+    };
+    if field.is_container() {
+      let key = field.key();
+      if field.value.is_none() {
+        // an object or array left out of the body is added empty to list its fields
+        let empty = match field.kind {
+          FieldKind::Array => serde_json::Value::Array(vec![]),
+          _ => serde_json::Value::Object(serde_json::Map::new()),
+        };
+        self.change_form_body(|body| body_form::set(body, &field.path, Some(empty)))?;
+      } else if self.expanded_fields.remove(&key) {
+        return Ok(());
+      }
+      self.expanded_fields.insert(key);
+      return Ok(());
+    }
+    if let Some(value) = field.toggled() {
+      self.change_form_body(|body| body_form::set(body, &field.path, Some(value)))?;
+    }
+    Ok(())
+  }
+
+  /// Appends an item to the selected array, listing its items.
+  fn add_field_item(&mut self) -> Result<()> {
+    let Some(field) = self.selected_field() else {
+      return Ok(());
+    };
+    self.change_form_body(|body| body_form::add_item(body, &field))?;
+    self.expanded_fields.insert(field.key());
+    Ok(())
+  }
+
+  /// Leaves the selected field out of the body, or removes the selected item from its array.
+  fn remove_field(&mut self) -> Result<()> {
+    let Some(field) = self.selected_field() else {
+      return Ok(());
+    };
+    self.change_form_body(|body| body_form::set(body, &field.path, None))?;
+    Ok(())
+  }
+
+  fn selected_part(&self) -> Option<FormPart> {
+    self.form_parts().into_iter().nth(self.part_index)
+  }
+
+  fn form_lines(&self) -> Vec<Line<'_>> {
+    self
+      .form_parts()
+      .into_iter()
+      .map(|part| {
+        let kind = match part.file {
+          true => "file",
+          false => "text",
+        };
+        let value = match part.value.is_empty() {
+          true => Span::styled("not sent", Style::default().dim().italic()),
+          false => Span::styled(part.value, Style::default().fg(Color::LightGreen)),
+        };
+        Line::from(vec![
+          Span::styled(format!("{}: ", part.name), Style::default().fg(self.location_color("body"))),
+          Span::styled(format!("[{kind}] "), Style::default().dim()),
+          value,
+        ])
+      })
+      .collect()
+  }
+
+  /// Whether the selected tab is a parameter or body the draft values are edited for, callbacks are only shown.
+  fn value_selected(&self) -> bool {
+    self.schemas.get(self.schemas_index).is_some_and(|request_type| request_type.location != "callback")
+  }
+
+  fn content_types(&self) -> ContentTypes {
+    let state = self.state.read().unwrap();
+    state
+      .active_operation_value()
+      .map(|operation| ContentTypes::of(operation, &state.openapi_document))
+      .unwrap_or_default()
+  }
+
+  /// Changes the draft of the active operation, which is dropped when nothing is left in it.
+  fn update_draft(&mut self, change: impl FnOnce(&mut RequestDraft)) -> Result<()> {
+    let mut state = self.state.write().unwrap();
+    let Some(key) = state.draft_key() else {
+      return Ok(());
+    };
+    let draft = state.workspace.drafts.entry(key.clone()).or_default();
+    let before = draft.clone();
+    change(draft);
+    if *draft != before {
+      self.draft_history.entry(key.clone()).or_default().record(before);
+    }
+    if draft.is_empty() {
+      state.workspace.drafts.remove(&key);
+    }
+    state.workspace.save(&state.openapi_path)?;
+    Ok(())
+  }
+
+  /// Restores the draft of the active operation as it was before its last change, or with `redo` as the last undone
+  /// change left it, `false` when there is no such change.
+  fn undo_draft(&mut self, redo: bool) -> Result<bool> {
+    let mut state = self.state.write().unwrap();
+    let Some(key) = state.draft_key() else {
+      return Ok(false);
+    };
+    let Some(history) = self.draft_history.get_mut(&key) else {
+      return Ok(false);
+    };
+    let current = state.workspace.drafts.get(&key).cloned().unwrap_or_default();
+    let restored = match redo {
+      true => history.redo(current),
+      false => history.undo(current),
+    };
+    let Some(restored) = restored else {
+      return Ok(false);
+    };
+    match restored.is_empty() {
+      true => state.workspace.drafts.remove(&key),
+      false => state.workspace.drafts.insert(key, restored),
+    };
+    state.workspace.save(&state.openapi_path)?;
+    Ok(true)
+  }
+
+  /// Sends the next media type of the request body, and selects the body tab of that media type.
+  fn cycle_content_type(&mut self) -> Result<()> {
+    let media_types = self.content_types().request;
+    let current = self.state.read().unwrap().active_draft().and_then(|draft| draft.content_type.clone());
+    let content_type = negotiation::next(&media_types, Some(current.as_ref().unwrap_or(&media_types[0])), false);
+    self.update_draft(|draft| draft.content_type.clone_from(&content_type))?;
+    let tab = self.schemas.iter().position(|request_type| {
+      request_type.location == "body" && Some(&request_type.media_type) == content_type.as_ref()
+    });
+    if let Some(tab) = tab {
+      self.section_index = BODY_SECTION;
+      self.schemas_index = tab;
+      self.init_schema()?;
+    }
+    Ok(())
+  }
+
+  /// Asks for the next media type of the responses, or for any again after the last one.
+  fn cycle_accept(&mut self) -> Result<()> {
+    let media_types = self.content_types().response;
+    let current = self.state.read().unwrap().active_draft().and_then(|draft| draft.accept.clone());
+    let accept = negotiation::next(&media_types, current.as_deref(), true);
+    self.update_draft(|draft| draft.accept = accept)
+  }
+
+  /// Content type sent and accepted, for operations that have several to choose from.
+  fn negotiation_line(&self) -> Line<'_> {
+    let content_types = self.content_types();
+    let draft = self.state.read().unwrap().active_draft().cloned().unwrap_or_default();
+    let mut spans = vec![];
+    if content_types.request.len() > 1 {
+      let content_type = draft.content_type.unwrap_or_else(|| content_types.request[0].clone());
+      spans.push(Span::styled(" Content-Type: ", Style::default().dim()));
+      spans.push(Span::styled(content_type, Style::default().fg(self.location_color("body"))));
+    }
+    if content_types.response.len() > 1 {
+      let accept = draft.accept.unwrap_or_else(|| String::from("*/*"));
+      spans.push(Span::styled(" Accept: ", Style::default().dim()));
+      spans.push(Span::styled(accept, Style::default().fg(self.location_color("header"))));
+    }
+    if !spans.is_empty() {
+      spans.push(Span::raw(" "));
+    }
+    Line::from(spans)
+  }
+
+  /// Named examples of the media type of the selected body.
+  fn body_examples(&self) -> Vec<NamedExample> {
+    let Some(request_type) = self.schemas.get(self.schemas_index).filter(|_| self.body_selected()) else {
+      return vec![];
+    };
+    let state = self.state.read().unwrap();
+    let document = &state.openapi_document;
+    let body = state.active_operation_value().and_then(|operation| operation.get("requestBody"));
+    let media = body.and_then(|body| {
+      mock::resolve(body, document)
+        .pointer(&format!("/content/{}", request_type.media_type.replace('~', "~0").replace('/', "~1")))
+    });
+    media.map(|media| mock::media_examples(media, document)).unwrap_or_default()
+  }
+
+  /// Edits the body starting from the selected example, to be applied like any edit.
+  fn start_editing_example(&mut self) {
+    if let Some(example) = self.example_picker.selected() {
+      let mut editor = TextArea::new(example.text().lines().map(String::from).collect());
+      editor.set_cursor_line_style(Style::default());
+      self.value_editor = Some(editor);
+    }
+  }
+
+  fn draft_value(&self) -> Option<String> {
+    if self.form_selected() {
+      return self.selected_part().map(|part| part.value).filter(|value| !value.is_empty());
+    }
+    let request_type = self.schemas.get(self.schemas_index)?;
+    let state = self.state.read().unwrap();
+    let draft = state.active_draft()?;
+    match request_type.location.as_str() {
+      "body" => draft.body.clone(),
+      location => draft.parameter(location, &request_type.title).map(String::from),
+    }
+  }
+
+  /// Value of the spec the selected parameter is sent with when the draft has none.
+  fn seed_value(&self) -> Option<String> {
+    let request_type = self.schemas.get(self.schemas_index)?;
+    self.state.read().unwrap().parameter_seed(&request_type.location, &request_type.title)
+  }
+
+  /// Edits the draft value of the selected tab, the body starts from the example of the operation.
+  fn start_editing_value(&mut self) {
+    let Some(request_type) = self.schemas.get(self.schemas_index) else {
+      return;
+    };
+    if self.form_selected() && self.selected_part().is_none() {
+      return;
+    }
+    if self.json_form_selected() {
+      if let Some(field) = self.selected_field() {
+        let mut editor = TextArea::new(vec![field.text()]);
+        editor.set_cursor_line_style(Style::default());
+        editor.move_cursor(CursorMove::End);
+        self.value_editor = Some(editor);
+      }
+      return;
+    }
+    let value = match self.draft_value() {
+      Some(value) => value,
+      None if request_type.location == "body" => {
+        self.state.read().unwrap().active_request().and_then(|request| request.body).unwrap_or_default()
+      },
+      None => self.seed_value().unwrap_or_default(),
+    };
+    let mut editor = TextArea::new(value.lines().map(String::from).collect());
+    editor.set_cursor_line_style(Style::default());
+    if request_type.location != "body" || self.form_selected() {
+      editor.move_cursor(CursorMove::End);
+    }
+    self.value_editor = Some(editor);
+  }
+
+  /// Completes the path of the file part being edited.
+  fn complete_part_path(&mut self) {
+    let Some(editor) = self.value_editor.as_mut() else {
+      return;
+    };
+    let mut completed = TextArea::new(vec![multipart::complete_path(&editor.lines().concat())]);
+    completed.set_cursor_line_style(Style::default());
+    completed.move_cursor(CursorMove::End);
+    *editor = completed;
+  }
+
+  /// Saves the edited value to the draft of the active operation, `None` resets it.
+  fn set_draft_value(&mut self, value: Option<String>) -> Result<()> {
+    let Some(request_type) = self.schemas.get(self.schemas_index) else {
+      return Ok(());
+    };
+    let (location, title, media_type) =
+      (request_type.location.clone(), request_type.title.clone(), request_type.media_type.clone());
+    if let Some(part) = self.selected_part().filter(|_| self.form_selected()) {
+      return self.update_draft(|draft| {
+        match value {
+          Some(value) => draft.set_part(FormPart { value, ..part }),
+          None => draft.clear_part(&part.name),
+        }
+        draft.content_type = (!draft.form.is_empty()).then_some(media_type);
+      });
+    }
+    self.update_draft(|draft| {
+      match (location.as_str(), value) {
+        ("body", body) => {
+          draft.content_type = body.as_ref().map(|_| media_type).filter(|media| !media.is_empty());
+          draft.body = body;
+        },
+        (location, Some(value)) => draft.set_parameter(location, &title, value),
+        (location, None) => draft.clear_parameter(location, &title),
+      }
+    })
+  }
+
+  fn value_height(&self, height: u16) -> u16 {
+    if !self.value_selected() {
+      return 0;
+    }
+    if self.form_selected() {
+      let parts = u16::try_from(self.form_parts().len()).unwrap_or(u16::MAX);
+      return parts.max(1).min(height / 2).saturating_add(1);
+    }
+    if self.json_form_selected() {
+      let fields = u16::try_from(self.json_form().fields.len()).unwrap_or(u16::MAX);
+      return fields.max(1).min(height / 2).saturating_add(1);
+    }
+    if !self.body_selected() {
+      return 2;
+    }
+    if self.value_editor.is_some() {
+      return height / 2;
+    }
+    let lines = self.draft_value().map_or(1, |body| body.lines().count().max(1));
+    u16::try_from(lines).unwrap_or(u16::MAX).min(height / 2).saturating_add(1)
+  }
+
+  fn draw_value(&self, frame: &mut Frame<'_>, area: Rect) {
+    let block = Block::default().borders(Borders::BOTTOM).border_style(Style::default().dim());
+    if self.form_selected() {
+      self.draw_form(frame, block.inner(area));
+      frame.render_widget(block, area);
+      return;
+    }
+    if self.json_form_selected() {
+      self.draw_fields(frame, block.inner(area));
+      frame.render_widget(block, area);
+      return;
+    }
+    if let Some(editor) = self.value_editor.as_ref() {
+      frame.render_widget(editor.widget(), block.inner(area));
+      frame.render_widget(block, area);
+      return;
+    }
+    let paragraph = match self.draft_value() {
+      Some(value) if self.body_selected() => Paragraph::new(value).style(Style::default().fg(Color::LightGreen)),
+      Some(value) => {
+        Paragraph::new(Line::from(vec![
+          Span::styled("= ", Style::default().dim()),
+          Span::styled(value, Style::default().fg(Color::LightGreen)),
+        ]))
+      },
+      None => {
+        match self.seed_value().filter(|_| !self.body_selected()) {
+          Some(value) => {
+            Paragraph::new(Line::from(vec![
+              Span::styled("= ", Style::default().dim()),
+              Span::styled(value, Style::default().fg(Color::LightGreen).dim()),
+              Span::styled("  from the spec", Style::default().dim().italic()),
+            ]))
+          },
+          None => Paragraph::new("no draft value").style(Style::default().dim().italic()),
+        }
+      },
+    };
+    frame.render_widget(paragraph.block(block), area);
+  }
+
+  fn draw_form(&self, frame: &mut Frame<'_>, area: Rect) {
+    let lines = self.form_lines();
+    if lines.is_empty() {
+      frame.render_widget(Paragraph::new("no parts").style(Style::default().dim().italic()), area);
+      return;
+    }
+    let mut list_state = ListState::default().with_selected(Some(self.part_index));
+    frame.render_stateful_widget(
+      List::new(lines).highlight_symbol(symbols::scrollbar::HORIZONTAL.end).highlight_spacing(HighlightSpacing::Always),
+      area,
+      &mut list_state,
+    );
+    if let (Some(editor), Some(part)) = (self.value_editor.as_ref(), self.selected_part()) {
+      // past the highlight symbol, the name and the kind of the part
+      let offset = u16::try_from(part.name.len() + 2 + "[file] ".len() + 1).unwrap_or(u16::MAX);
+      let row = u16::try_from(self.part_index.saturating_sub(list_state.offset())).unwrap_or(u16::MAX);
+      let mut editor_area = area;
+      editor_area.y = editor_area.y.saturating_add(row);
+      editor_area.height = 1;
+      editor_area.x = editor_area.x.saturating_add(offset);
+      editor_area.width = editor_area.width.saturating_sub(offset);
+      frame.render_widget(Clear, editor_area);
+      frame.render_widget(editor.widget(), editor_area);
+    }
+  }
+
+  /// Field of a JSON body as listed in the form, up to its value: indented below its parent, marked as expanded or
+  /// collapsed, with its name, whether it is required and its type.
+  fn field_prefix(&self, field: &Field) -> Vec<Span<'static>> {
+    let marker = match (field.is_container(), field.expanded) {
+      (true, true) => "▾ ",
+      (true, false) => "▸ ",
+      (false, _) => "  ",
+    };
+    let mut spans = vec![
+      Span::raw(format!("{}{marker}", "  ".repeat(field.depth))),
+      Span::styled(field.label.clone(), Style::default().fg(self.location_color("body"))),
+    ];
+    if field.required {
+      spans.push(Span::styled("*", Style::default().fg(Color::LightRed)));
+    }
+    spans.push(Span::styled(": ", Style::default().fg(self.location_color("body"))));
+    spans.push(Span::styled(format!("[{}] ", field.kind.name()), Style::default().dim()));
+    spans
+  }
+
+  fn draw_fields(&self, frame: &mut Frame<'_>, area: Rect) {
+    let form = self.json_form();
+    let fields = &form.fields;
+    if fields.is_empty() {
+      let empty = match form.body {
+        Some(_) => "no fields",
+        None => "the body is not valid JSON, ctrl-f edits it raw",
+      };
+      frame.render_widget(Paragraph::new(empty).style(Style::default().dim().italic()), area);
+      return;
+    }
+    let lines: Vec<Line> = fields
+      .iter()
+      .map(|field| {
+        let mut spans = self.field_prefix(field);
+        spans.push(match field.display() {
+          Some(value) if field.is_container() => Span::styled(value, Style::default().dim()),
+          Some(value) => Span::styled(value, Style::default().fg(Color::LightGreen)),
+          None => Span::styled("not sent", Style::default().dim().italic()),
+        });
+        Line::from(spans)
+      })
+      .collect();
+    let mut list_state = ListState::default().with_selected(Some(self.field_index));
+    frame.render_stateful_widget(
+      List::new(lines).highlight_symbol(symbols::scrollbar::HORIZONTAL.end).highlight_spacing(HighlightSpacing::Always),
+      area,
+      &mut list_state,
+    );
+    if let (Some(editor), Some(field)) = (self.value_editor.as_ref(), fields.get(self.field_index)) {
+      // past the highlight symbol and the name and type of the field
+      let offset = u16::try_from(Line::from(self.field_prefix(field)).width() + 1).unwrap_or(u16::MAX);
+      let row = u16::try_from(self.field_index.saturating_sub(list_state.offset())).unwrap_or(u16::MAX);
+      let mut editor_area = area;
+      editor_area.y = editor_area.y.saturating_add(row);
+      editor_area.height = 1;
+      editor_area.x = editor_area.x.saturating_add(offset);
+      editor_area.width = editor_area.width.saturating_sub(offset);
+      frame.render_widget(Clear, editor_area);
+      frame.render_widget(editor.widget(), editor_area);
+    }
+  }
+
+  /// The request of the operation as it will be sent, redrawn as its inputs are edited.
+  fn draw_preview(&self, frame: &mut Frame<'_>, area: Rect) {
+    let request = self.state.read().unwrap().preview_request();
+    let lines: Vec<Line> = match request {
+      Some(request) => {
+        let mut body = false;
+        wire::preview(&request)
+          .into_iter()
+          .enumerate()
+          .map(|(index, line)| {
+            if index == 0 {
+              let url = line.strip_prefix(&request.method).unwrap_or(&line).to_string();
+              return Line::from(vec![
+                Span::styled(request.method.clone(), theme::current().method(&request.method)),
+                Span::raw(url),
+              ]);
+            }
+            body = body || line.is_empty();
+            match line.split_once(": ").filter(|_| !body) {
+              Some((name, value)) => {
+                Line::from(vec![
+                  Span::styled(format!("{name}: "), Style::default().fg(self.location_color("header"))),
+                  Span::raw(value.to_string()),
+                ])
+              },
+              None => Line::styled(line, Style::default().fg(Color::LightGreen)),
+            }
+          })
+          .collect()
+      },
+      None => vec![Line::styled("No operation selected", Style::default().dim().italic())],
+    };
+    frame.render_widget(
+      Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+        Block::default()
+          .title("Preview")
+          .title_bottom(Line::from("scripts, plugins and signing apply when sent").style(Style::default().dim()))
+          .borders(Borders::ALL)
+          .border_style(Style::default().dim()),
+      ),
+      area,
+    );
+  }
+
+  fn hint_line(&self) -> Line<'_> {
+    const ARROW: &str = symbols::scrollbar::HORIZONTAL.end;
+    if self.json_form_selected() {
+      let field = self.selected_field();
+      return match self.value_editor.is_some() {
+        true => Line::from(format!("[enter {ARROW} apply] [esc {ARROW} cancel]")),
+        false if self.focused => {
+          let mut hint = format!("[i {ARROW} edit field] [d {ARROW} remove field]");
+          match field.as_ref().map(|field| &field.kind) {
+            Some(FieldKind::Object) => hint.push_str(&format!(" [space {ARROW} expand]")),
+            Some(FieldKind::Array) => {
+              hint.push_str(&format!(" [space {ARROW} expand] [a {ARROW} add item]"));
+            },
+            Some(FieldKind::Boolean | FieldKind::Choice(_)) => {
+              hint.push_str(&format!(" [space {ARROW} toggle]"));
+            },
+            _ => {},
+          }
+          hint.push_str(&format!(" [ctrl-f {ARROW} raw]"));
+          Line::from(hint)
+        },
+        false => Line::default(),
+      };
+    }
+    if self.form_selected() {
+      return match (self.value_editor.is_some(), self.selected_part().is_some_and(|part| part.file)) {
+        (true, true) => Line::from(format!("[tab {ARROW} complete path] [enter {ARROW} apply] [esc {ARROW} cancel]")),
+        (true, false) => Line::from(format!("[enter {ARROW} apply] [esc {ARROW} cancel]")),
+        (false, _) if self.focused => Line::from(format!("[i {ARROW} edit field] [u {ARROW} reset field]")),
+        (false, _) => Line::default(),
+      };
+    }
+    if self.headers_tab_selected() {
+      return match self.header_editor.is_some() {
+        true => Line::from(format!("[enter {ARROW} apply] [esc {ARROW} cancel]")),
+        false => Line::from(format!("[i {ARROW} edit] [d {ARROW} remove/restore] [u {ARROW} reset to default]")),
+      };
+    }
+    match (self.value_editor.is_some(), self.body_selected()) {
+      (true, true) => Line::from(format!("[ctrl-s {ARROW} apply] [ctrl-z/y {ARROW} undo/redo] [esc {ARROW} cancel]")),
+      (true, false) => Line::from(format!("[enter {ARROW} apply] [esc {ARROW} cancel]")),
+      _ if self.focused && !self.example_picker.is_empty() => {
+        Line::from(format!(
+          "[i {ARROW} edit value] [u {ARROW} reset value] [] / [ {ARROW} example] [I {ARROW} edit from example]"
+        ))
+      },
+      _ if self.focused && self.json_object_selected() => {
+        Line::from(format!("[i {ARROW} edit value] [u {ARROW} reset value] [ctrl-f {ARROW} form]"))
+      },
+      _ if self.focused && self.value_selected() => {
+        Line::from(format!("[i {ARROW} edit value] [u {ARROW} reset value]"))
+      },
+      _ => Line::default(),
+    }
+  }
+
+  fn headers_lines(&self) -> Vec<Line<'_>> {
+    let state = self.state.read().unwrap();
+    let operation = state.active_operation_key();
+    self
+      .default_headers()
+      .into_iter()
+      .map(|(name, value)| {
+        let overridden =
+          operation.as_ref().is_some_and(|operation| state.workspace.header_override(operation, &name).is_some());
+        let name = Span::styled(format!("{name}: "), Style::default().fg(self.location_color("header")));
+        match value {
+          None => Line::from(vec![name.crossed_out().dim(), Span::styled("removed", Style::default().dim().italic())]),
+          Some(value) if overridden => {
+            Line::from(vec![name, Span::styled(value, Style::default().fg(Color::LightGreen))])
+          },
+          Some(value) => Line::from(vec![name, Span::styled(value, Style::default().dim())]),
+        }
+      })
+      .collect()
+  }
+
+  /// Security requirements of the operation, alternatives separated by `or`, each scheme with the header of the
+  /// environment sending its credential.
+  /// Whether the credentials of a scheme are configured, along with the line telling how it is sent. Credentials
+  /// sent as parameters, or answering a challenge of the server, cannot be checked and count as configured.
+  fn credential_status(scheme: Option<&SecurityScheme>, headers: &[(String, Option<String>)]) -> (bool, Line<'static>) {
+    let Some(scheme) = scheme else {
+      return (
+        false,
+        Line::styled("    ✗ not defined in the components of the spec", Style::default().fg(Color::LightRed)),
+      );
+    };
+    match scheme.header("").map(|(header, _)| header) {
+      Some(header) => {
+        match headers.iter().find(|(name, _)| name.eq_ignore_ascii_case(&header)) {
+          Some((name, Some(value))) => {
+            (true, Line::styled(format!("    ✓ {name}: {value}"), Style::default().fg(Color::LightGreen)))
+          },
+          Some((name, None)) => {
+            (
+              false,
+              Line::styled(format!("    ✗ {name} removed for this operation"), Style::default().fg(Color::LightRed)),
+            )
+          },
+          None => {
+            (
+              false,
+              Line::styled(
+                format!("    ✗ no {header} header in the active environment"),
+                Style::default().fg(Color::LightYellow),
+              ),
+            )
+          },
+        }
+      },
+      None if matches!(scheme, SecurityScheme::Http { scheme } if scheme == "digest") => {
+        (true, Line::styled("    answered to the challenge of the server by saved requests", Style::default().dim()))
+      },
+      None => (true, Line::styled("    sent as a parameter of the request", Style::default().dim())),
+    }
+  }
+
+  /// Whether the security requirements of the active operation are met, `None` when it has none.
+  fn auth_met(&self) -> Option<bool> {
+    let state = self.state.read().unwrap();
+    let (path, method, _) = state.active_operation()?;
+    let requirements = security::operation_security(&state.openapi_document, &path, &method);
+    if requirements.is_empty() {
+      return None;
+    }
+    let schemes = security::security_schemes(&state.openapi_document);
+    let headers = state.active_operation_key().map(|operation| state.default_headers(&operation)).unwrap_or_default();
+    Some(security::requirements_met(&requirements, |name| Self::credential_status(schemes.get(name), &headers).0))
+  }
+
+  /// Effective security requirements of the operation, where they are declared, and whether the credentials of each
+  /// scheme are configured.
+  fn auth_lines(&self) -> Vec<Line<'static>> {
+    let state = self.state.read().unwrap();
+    let Some((path, method, _)) = state.active_operation() else {
+      return vec![];
+    };
+    let document = &state.openapi_document;
+    let requirements = security::operation_security(document, &path, &method);
+    let source = match security::security_source(document, &path, &method) {
+      SecuritySource::Operation if requirements.is_empty() && document.get("security").is_some() => {
+        "The operation opts out of the global security"
+      },
+      SecuritySource::Operation if document.get("security").is_some() => {
+        "Declared by the operation, overriding the global security"
+      },
+      SecuritySource::Operation => "Declared by the operation",
+      SecuritySource::Global => "Global security of the spec applies",
+      SecuritySource::None => "Neither the operation nor the spec declare security",
+    };
+    let mut lines = vec![Line::styled(source, Style::default().dim().italic())];
+    if requirements.is_empty() {
+      lines.push(Line::styled("No security requirements, the operation is public", Style::default().dim().italic()));
+      return lines;
+    }
+    if requirements.len() > 1 {
+      lines.push(Line::styled(
+        format!("Any one of {} alternatives, each with all of its schemes", requirements.len()),
+        Style::default().dim(),
+      ));
+    }
+    lines.push(Line::default());
+    let schemes = security::security_schemes(document);
+    let headers = state.active_operation_key().map(|operation| state.default_headers(&operation)).unwrap_or_default();
+    for (index, requirement) in requirements.iter().enumerate() {
+      if index > 0 {
+        lines.push(Line::styled("or", Style::default().dim()));
+      }
+      if requirement.is_empty() {
+        lines.push(Line::styled("✓ anonymously", Style::default().fg(Color::LightGreen)));
+        continue;
+      }
+      let statuses: Vec<(bool, Line<'static>)> =
+        requirement.keys().map(|name| Self::credential_status(schemes.get(name), &headers)).collect();
+      let names = requirement.keys().cloned().collect::<Vec<_>>().join(" and ");
+      lines.push(match statuses.iter().all(|(configured, _)| *configured) {
+        true => Line::styled(format!("✓ {names}"), Style::default().fg(Color::LightGreen).bold()),
+        false => Line::styled(format!("✗ {names}"), Style::default().fg(Color::LightRed).bold()),
+      });
+      for ((name, scopes), (_, status)) in requirement.iter().zip(statuses) {
+        let kind = match schemes.get(name) {
+          Some(SecurityScheme::ApiKey { name, location }) => format!("API key `{name}` in {location}"),
+          Some(SecurityScheme::Http { scheme }) => format!("HTTP {scheme}"),
+          Some(SecurityScheme::OAuth2) => String::from("OAuth 2.0"),
+          Some(SecurityScheme::OpenIdConnect) => String::from("OpenID Connect"),
+          None => String::from("undefined scheme"),
+        };
+        let mut spans = vec![
+          Span::styled(format!("  {name} "), Style::default().fg(self.location_color("header")).bold()),
+          Span::styled(kind, Style::default().dim()),
+        ];
+        if !scopes.is_empty() {
+          spans.push(Span::styled(format!("  scopes: {}", scopes.join(", ")), Style::default().dim().italic()));
+        }
+        lines.push(Line::from(spans));
+        lines.push(status);
+      }
+    }
+    lines
+  }
+
+  /// Summary, description, note and references of the operation.
+  fn docs_lines(&self) -> Vec<Line<'static>> {
+    let state = self.state.read().unwrap();
+    let Some(operation) = state.active_operation_value() else {
+      return vec![];
+    };
+    let text = |key: &str| operation.get(key).and_then(serde_json::Value::as_str).map(String::from);
+    let mut lines = vec![];
+    if let Some(summary) = text("summary") {
+      lines.push(Line::styled(summary, Style::default().bold()));
+    }
+    let mut details = vec![];
+    if let Some(operation_id) = text("operationId") {
+      details.push(Span::styled(operation_id, Style::default().dim()));
+    }
+    if let Some(tags) = operation.get("tags").and_then(serde_json::Value::as_array).filter(|tags| !tags.is_empty()) {
+      let tags: Vec<&str> = tags.iter().filter_map(serde_json::Value::as_str).collect();
+      details.push(Span::styled(format!("  tags: {}", tags.join(", ")), Style::default().dim()));
+    }
+    if operation.get("deprecated").and_then(serde_json::Value::as_bool).unwrap_or_default() {
+      details.push(Span::styled("  deprecated", Style::default().fg(Color::LightRed)));
+    }
+    if !details.is_empty() {
+      lines.push(Line::from(details));
+    }
+    if let Some(note) = state.active_operation_key().and_then(|key| state.workspace.note(&key).map(String::from)) {
+      lines.push(Line::default());
+      lines.extend(note.lines().map(|line| Line::styled(format!("✎ {line}"), Style::default().fg(Color::LightYellow))));
+    }
+    if let Some(description) = text("description") {
+      lines.push(Line::default());
+      lines.extend(markdown::render(&description).into_iter().map(|line| {
+        Line::from(line.spans.into_iter().map(|(style, text)| Span::styled(text, style)).collect::<Vec<_>>())
+      }));
+    }
+    let extensions = extensions::lines(&extensions::of(operation), self.schema_viewer.extensions_expanded());
+    if !extensions.is_empty() {
+      lines.push(Line::default());
+      lines.extend(
+        extensions
+          .into_iter()
+          .map(|line| Line::from(line.into_iter().map(|(style, text)| Span::styled(text, style)).collect::<Vec<_>>())),
+      );
+    }
+    if let Some(url) = operation.pointer("/externalDocs/url").and_then(serde_json::Value::as_str) {
+      lines.push(Line::default());
+      lines.push(Line::from(vec![
+        Span::styled("External docs: ", Style::default().dim()),
+        Span::styled(url.to_string(), Style::default().fg(Color::LightBlue).underlined()),
+      ]));
+    }
+    if lines.is_empty() {
+      lines.push(Line::styled("The operation is not documented", Style::default().dim().italic()));
+    }
+    lines
+  }
+
   fn border_style(&self) -> Style {
     match self.focused {
       true => self.focused_border_style,
-      false => Style::default(),
+      false => theme::current().border,
     }
   }
 
@@ -73,6 +1131,9 @@ impl RequestPane {
     if status.starts_with("body") {
       return Color::LightYellow;
     }
+    if status.starts_with("callback") {
+      return Color::LightGreen;
+    }
     Color::default()
   }
 
@@ -82,9 +1143,8 @@ impl RequestPane {
       if let Some((_path, _method, operation)) = state.active_operation() {
         let mut schemas: Vec<RequestType> = vec![];
         if let Some(request_body) = &operation.request_body {
+          let request_body = request_body.resolve(&state.openapi_spec).unwrap_or(RequestBody::default());
           schemas = request_body
-            .resolve(&state.openapi_spec)
-            .unwrap_or(RequestBody::default())
             .content
             .iter()
             .filter_map(|(media_type, media)| {
@@ -94,6 +1154,11 @@ impl RequestPane {
                   media_type: media_type.to_string(),
                   title: schema.title.clone().unwrap_or("Body".to_string()),
                   schema,
+                  description: request_body.description.clone(),
+                  required: request_body.required.unwrap_or_default(),
+                  deprecated: false,
+                  default: None,
+                  example: None,
                 })
               })
             })
@@ -102,19 +1167,52 @@ impl RequestPane {
         schemas.extend(
           operation.parameters.iter().filter_map(|parameter| parameter.resolve(&state.openapi_spec).ok()).map(
             |parameter| {
+              let schema = parameter.schema.as_ref().and_then(|schema| serde_json::to_value(schema).ok());
+              let schema_value = |key: &str| schema.as_ref().and_then(|schema| schema.get(key)).cloned();
               RequestType {
                 location: parameter.location,
                 media_type: parameter.param_type.unwrap_or_default(),
                 schema: parameter.schema.unwrap_or_default(),
                 title: parameter.name,
+                description: parameter.description,
+                required: parameter.required.unwrap_or_default(),
+                deprecated: parameter.deprecated.unwrap_or_default(),
+                default: schema_value("default").map(link::text_of),
+                example: parameter.example.or_else(|| schema_value("example")).map(link::text_of),
               }
             },
           ),
         );
+        // callbacks follow, shown like request bodies of the requests the API sends back
+        let callbacks = state
+          .active_operation_value()
+          .map(|operation| callback::callbacks(operation, &state.openapi_document))
+          .unwrap_or_default();
+        schemas.extend(callbacks.into_iter().map(|callback| {
+          RequestType {
+            location: String::from("callback"),
+            media_type: callback.media_type.clone().unwrap_or_default(),
+            schema: serde_json::from_value(callback.schema.clone()).unwrap_or_default(),
+            title: callback.title(),
+            description: Some(callback.describe()),
+            required: false,
+            deprecated: false,
+            default: None,
+            example: None,
+          }
+        }));
         self.schemas = schemas;
       }
     }
+    self.header_index = 0;
+    self.header_editor = None;
+    self.value_editor = None;
+    self.part_index = 0;
+    self.field_index = 0;
+    self.expanded_fields.clear();
+    self.example_picker.set(self.body_examples());
     if let Some(request_type) = self.schemas.get(self.schemas_index) {
+      self.schema_viewer.set_description(request_type.description.clone());
       self.schema_viewer.set(request_type.schema.clone())?;
     } else {
       self.schema_viewer.clear();
@@ -123,7 +1221,7 @@ impl RequestPane {
   }
 
   fn legend_line(&self) -> Line {
-    if self.schema_viewer.schema_path().is_empty() {
+    let mut line = if self.schema_viewer.schema_path().is_empty() {
       Line::from(vec![
         Span::raw("["),
         Span::styled("Body".to_string(), self.location_color("body")),
@@ -133,7 +1231,6 @@ impl RequestPane {
         Span::styled("Query".to_string(), self.location_color("query")),
         Span::raw("/"),
         Span::styled("Header".to_string(), self.location_color("header")),
-        Span::raw("]"),
       ])
     } else {
       Line::from(vec![
@@ -145,27 +1242,80 @@ impl RequestPane {
         Span::styled("Q".to_string(), self.location_color("query")),
         Span::raw("/"),
         Span::styled("H".to_string(), self.location_color("header")),
-        Span::raw("]"),
       ])
+    };
+    if self.schemas.iter().any(|request_type| request_type.location == "callback") {
+      let callback = match self.schema_viewer.schema_path().is_empty() {
+        true => "Callback",
+        false => "C",
+      };
+      line.spans.push(Span::raw("/"));
+      line.spans.push(Span::styled(callback.to_string(), self.location_color("callback")));
     }
+    line.spans.push(Span::raw("]"));
+    line
   }
 
-  fn nested_schema_path_line(&self) -> Line {
-    let schema_path = self.schema_viewer.schema_path();
-    if schema_path.is_empty() {
-      return Line::default();
+  /// Trail of the schemas gone to, from the shown parameter, body or callback.
+  fn trail_line(&self) -> Line<'static> {
+    let root = self.schemas.get(self.schemas_index).map(|request_type| request_type.title.as_str());
+    self.schema_viewer.trail(root.unwrap_or_default())
+  }
+
+  /// Draws the selected parameter, body or callback with its value, or the default headers.
+  fn draw_item(&mut self, frame: &mut Frame<'_>, inner: Rect) -> Result<()> {
+    if self.headers_tab_selected() {
+      let mut list_state = ListState::default().with_selected(Some(self.header_index));
+      frame.render_stateful_widget(
+        List::new(self.headers_lines())
+          .highlight_symbol(symbols::scrollbar::HORIZONTAL.end)
+          .highlight_spacing(HighlightSpacing::Always),
+        inner,
+        &mut list_state,
+      );
+      if let Some(editor) = self.header_editor.as_ref() {
+        let name_width = self.selected_header().map_or(0, |(name, _)| name.len() + 2);
+        let mut editor_area = inner;
+        editor_area.y = editor_area.y.saturating_add(self.header_index.try_into()?);
+        editor_area.height = 1;
+        editor_area.x = editor_area.x.saturating_add((name_width + 1).try_into()?);
+        editor_area.width = editor_area.width.saturating_sub((name_width + 1).try_into()?);
+        frame.render_widget(Clear, editor_area);
+        frame.render_widget(editor.widget(), editor_area);
+      }
+    } else {
+      let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+          Constraint::Length(self.value_height(inner.height)),
+          Constraint::Length(self.example_picker.height(inner.height / 3)),
+          Constraint::Fill(1),
+        ])
+        .split(inner);
+      if !layout[0].is_empty() {
+        self.draw_value(frame, layout[0]);
+      }
+      if !layout[1].is_empty() {
+        self.example_picker.render_widget(frame, layout[1]);
+      }
+      self.schema_viewer.render_widget(frame, layout[2]);
     }
-    let mut line = String::from("[ ");
-    line.push_str(&schema_path.join(" > "));
-    line.push_str(" ]");
-    Line::from(line)
+    Ok(())
   }
 }
 
 impl Pane for RequestPane {
   fn init(&mut self) -> Result<()> {
     self.init_schema()?;
-    Ok(())
+    self.select_section(self.section_index)
+  }
+
+  fn unsaved_edit(&self) -> Option<String> {
+    match (&self.value_editor, &self.header_editor) {
+      (Some(_), _) => Some(String::from("The value being edited in the request pane")),
+      (None, Some(_)) => Some(String::from("The header being edited in the request pane")),
+      (None, None) => None,
+    }
   }
 
   fn focus(&mut self) -> Result<()> {
@@ -175,11 +1325,13 @@ impl Pane for RequestPane {
 
   fn unfocus(&mut self) -> Result<()> {
     self.focused = false;
+    self.header_editor = None;
+    self.value_editor = None;
     Ok(())
   }
 
   fn height_constraint(&self) -> Constraint {
-    if self.schemas.get(self.schemas_index).is_none() {
+    if self.state.read().unwrap().active_operation().is_none() {
       return Constraint::Max(2);
     }
     match self.focused {
@@ -188,8 +1340,141 @@ impl Pane for RequestPane {
     }
   }
 
-  fn handle_key_events(&mut self, _key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
-    Ok(None)
+  fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
+    // bodies span several lines, parameters and the parts and fields of a body edited as a form one
+    let json_form = self.json_form_selected();
+    let multiline = self.body_selected() && !self.form_selected() && !json_form;
+    let file_part = self.form_selected() && self.selected_part().is_some_and(|part| part.file);
+    if let Some(editor) = self.value_editor.as_mut() {
+      match key.code {
+        KeyCode::Tab if file_part => self.complete_part_path(),
+        KeyCode::Enter if json_form => {
+          let value = editor.lines().concat();
+          // the editor stays open on a value that does not fit the field
+          if let Some(error) = self.apply_field(&value)? {
+            return Ok(Some(EventResponse::Stop(error)));
+          }
+          self.value_editor = None;
+        },
+        KeyCode::Enter if !multiline => {
+          let value = editor.lines().concat();
+          self.value_editor = None;
+          self.set_draft_value(Some(value))?;
+        },
+        KeyCode::Char('s') if multiline && key.modifiers.contains(KeyModifiers::CONTROL) => {
+          let value = editor.lines().join("\n");
+          self.value_editor = None;
+          self.set_draft_value(Some(value))?;
+        },
+        KeyCode::Esc => self.value_editor = None,
+        KeyCode::Char('z') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+          editor.undo();
+        },
+        KeyCode::Char('y') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+          editor.redo();
+        },
+        _ => {
+          editor.input(key);
+        },
+      }
+      return Ok(Some(EventResponse::Stop(Action::Render)));
+    }
+    if key.modifiers.contains(KeyModifiers::CONTROL) && matches!(key.code, KeyCode::Char('z') | KeyCode::Char('y')) {
+      // ctrl-z suspends the app when there is nothing to undo
+      if self.undo_draft(key.code == KeyCode::Char('y'))? {
+        return Ok(Some(EventResponse::Stop(Action::Render)));
+      }
+    }
+    if let (KeyCode::Char('y') | KeyCode::Char('m'), Some(link)) = (key.code, self.schema_viewer.link()) {
+      return Ok(Some(EventResponse::Stop(match key.code {
+        KeyCode::Char('y') => Action::Copy(link),
+        _ => Action::OpenUrl(link),
+      })));
+    }
+    if key.code == KeyCode::Char('X') {
+      self.schema_viewer.toggle_extensions()?;
+      return Ok(Some(EventResponse::Stop(Action::Render)));
+    }
+    if let (KeyCode::Char('U'), Some(schema)) = (key.code, self.schema_viewer.schema_path().last()) {
+      return Ok(Some(EventResponse::Stop(Action::ShowUsages(schema.clone()))));
+    }
+    if let (KeyCode::Char('J') | KeyCode::Char('Y'), Some(request_type)) =
+      (key.code, self.schemas.get(self.schemas_index))
+    {
+      let action: fn(String, Box<serde_json::Value>) -> Action = match key.code {
+        KeyCode::Char('J') => Action::ExportSchema,
+        _ => Action::ShowTypes,
+      };
+      let root = format!("{}-{}", request_type.location, request_type.title);
+      let shown = self.schema_viewer.shown(&root);
+      return Ok(shown.map(|(name, schema)| EventResponse::Stop(action(name, Box::new(schema)))));
+    }
+    if self.header_editor.is_none() {
+      let cycled = match key.code {
+        KeyCode::Char('C') if self.content_types().request.len() > 1 => self.cycle_content_type().map(|_| true)?,
+        KeyCode::Char('A') if self.content_types().response.len() > 1 => self.cycle_accept().map(|_| true)?,
+        _ => false,
+      };
+      if cycled {
+        return Ok(Some(EventResponse::Stop(Action::Render)));
+      }
+    }
+    if key.code == KeyCode::Char('f') && key.modifiers.contains(KeyModifiers::CONTROL) && self.json_object_selected() {
+      self.json_form = !self.json_form;
+      self.field_index = 0;
+      return Ok(Some(EventResponse::Stop(Action::Render)));
+    }
+    let Some(editor) = self.header_editor.as_mut() else {
+      if !self.headers_tab_selected() {
+        match key.code {
+          KeyCode::Char(' ' | 'a' | 'd' | 'i') if json_form && self.json_form().body.is_none() => {
+            return Ok(Some(EventResponse::Stop(invalid_form_body())));
+          },
+          KeyCode::Char(' ') if json_form => self.toggle_field()?,
+          KeyCode::Char('a')
+            if json_form && self.selected_field().is_some_and(|field| field.kind == FieldKind::Array) =>
+          {
+            self.add_field_item()?
+          },
+          KeyCode::Char('d') if json_form => self.remove_field()?,
+          KeyCode::Char('i') if self.value_selected() => self.start_editing_value(),
+          KeyCode::Char('u') if self.value_selected() => self.set_draft_value(None)?,
+          KeyCode::Char(']') if !self.example_picker.is_empty() => self.example_picker.next(),
+          KeyCode::Char('[') if !self.example_picker.is_empty() => self.example_picker.previous(),
+          KeyCode::Char('I') if !self.example_picker.is_empty() => self.start_editing_example(),
+          _ => return Ok(None),
+        }
+        return Ok(Some(EventResponse::Stop(Action::Render)));
+      }
+      match key.code {
+        KeyCode::Char('i') => self.start_editing_header(),
+        KeyCode::Char('d') => {
+          let removed = self.selected_header().is_some_and(|(_, value)| value.is_none());
+          self.set_header_override(if removed { None } else { Some(None) })?;
+        },
+        KeyCode::Char('u') => self.set_header_override(None)?,
+        _ => return Ok(None),
+      }
+      return Ok(Some(EventResponse::Stop(Action::Render)));
+    };
+    match key.code {
+      KeyCode::Enter => {
+        let value = editor.lines().concat();
+        self.header_editor = None;
+        self.set_header_override(Some(Some(value)))?;
+      },
+      KeyCode::Esc => self.header_editor = None,
+      KeyCode::Char('z') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+        editor.undo();
+      },
+      KeyCode::Char('y') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+        editor.redo();
+      },
+      _ => {
+        editor.input(key);
+      },
+    }
+    Ok(Some(EventResponse::Stop(Action::Render)))
   }
 
   #[allow(unused_variables)]
@@ -197,11 +1482,46 @@ impl Pane for RequestPane {
     Ok(None)
   }
 
+  fn handle_paste_events(&mut self, text: String) -> Result<Option<EventResponse<Action>>> {
+    let multiline = self.body_selected() && !self.form_selected() && !self.json_form_selected();
+    let (editor, multiline) = match (self.value_editor.as_mut(), self.header_editor.as_mut()) {
+      (Some(editor), _) => (editor, multiline),
+      (None, Some(editor)) => (editor, false),
+      (None, None) => return Ok(None),
+    };
+    editor.insert_str(tui::pasted(&text, multiline));
+    Ok(Some(EventResponse::Stop(Action::Render)))
+  }
+
   fn update(&mut self, action: Action) -> Result<Option<Action>> {
     match action {
       Action::Update => {
-        self.schemas_index = 0;
+        // the spec may have been reloaded under the same draft
+        self.json_form_cache = RefCell::default();
+        self.schemas_index = NO_ITEM;
         self.init_schema()?;
+        self.select_section(self.section_index)?;
+      },
+      Action::Down if self.section_index == DOCS_SECTION => self.docs_offset = self.docs_offset.saturating_add(1),
+      Action::Up if self.section_index == DOCS_SECTION => self.docs_offset = self.docs_offset.saturating_sub(1),
+      Action::Top if self.section_index == DOCS_SECTION => self.docs_offset = 0,
+      Action::Down if self.form_selected() => {
+        self.part_index = self.part_index.saturating_add(1).min(self.form_parts().len().saturating_sub(1));
+      },
+      Action::Up if self.form_selected() => {
+        self.part_index = self.part_index.saturating_sub(1);
+      },
+      Action::Down if self.json_form_selected() => {
+        self.field_index = self.field_index.saturating_add(1).min(self.json_form().fields.len().saturating_sub(1));
+      },
+      Action::Up if self.json_form_selected() => {
+        self.field_index = self.field_index.saturating_sub(1);
+      },
+      Action::Down if self.headers_tab_selected() => {
+        self.header_index = self.header_index.saturating_add(1).min(self.default_headers().len().saturating_sub(1));
+      },
+      Action::Up if self.headers_tab_selected() => {
+        self.header_index = self.header_index.saturating_sub(1);
       },
       Action::Down => {
         self.schema_viewer.down();
@@ -209,16 +1529,26 @@ impl Pane for RequestPane {
       Action::Up => {
         self.schema_viewer.up();
       },
-      Action::Tab(index) if index < self.schemas.len().try_into()? => {
-        self.schemas_index = index.try_into()?;
-        self.init_schema()?;
+      Action::PageDown | Action::PageUp | Action::Top | Action::Bottom
+        if !self.form_selected() && !self.json_form_selected() && !self.headers_tab_selected() =>
+      {
+        self.schema_viewer.jump(&action)
       },
+      Action::Tab(index) if (index as usize) < SECTIONS.len() => self.select_section(index as usize)?,
+      Action::NextSection => self.cycle_item(true)?,
+      Action::PrevSection => self.cycle_item(false)?,
       Action::Go => self.schema_viewer.go()?,
+      Action::NextBranch => self.schema_viewer.next_branch()?,
       Action::Back => {
         if let Some(request_type) = self.schemas.get(self.schemas_index) {
           self.schema_viewer.back(request_type.schema.clone())?;
         }
       },
+      Action::BackTo(depth) => {
+        if let Some(request_type) = self.schemas.get(self.schemas_index) {
+          self.schema_viewer.back_to(depth, request_type.schema.clone())?;
+        }
+      },
       _ => {},
     }
 
@@ -226,44 +1556,314 @@ impl Pane for RequestPane {
   }
 
   fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    let area = match self.state.read().unwrap().request_preview {
+      true => {
+        let layout = Layout::default()
+          .direction(Direction::Horizontal)
+          .constraints([Constraint::Fill(3), Constraint::Fill(2)])
+          .split(area);
+        self.draw_preview(frame, layout[1]);
+        layout[0]
+      },
+      false => area,
+    };
     let inner_margin: Margin = Margin { horizontal: 1, vertical: 1 };
 
     let inner = area.inner(&inner_margin);
 
+    let sections = SECTIONS.iter().enumerate().map(|(section, title)| {
+      let title = match self.section_items(section).len() {
+        0 => title.to_string(),
+        count => format!("{title} ({count})"),
+      };
+      let title = match (section == AUTH_SECTION).then(|| self.auth_met()).flatten() {
+        Some(true) => format!("{title} ✓"),
+        Some(false) => format!("{title} ✗"),
+        None => title,
+      };
+      Span::styled(title, Style::default().dim())
+    });
     frame.render_widget(
-      Tabs::new(self.schemas.iter().map(|item| {
-        let mut title = item.title.clone();
-        if !item.media_type.is_empty() {
-          title.push_str(format!(" [{}]", item.media_type).as_str());
-        }
-        Span::styled(title, Style::default().fg(self.location_color(item.location.as_str()))).dim()
-      }))
-      .highlight_style(Style::default().add_modifier(Modifier::BOLD | Modifier::UNDERLINED).not_dim())
-      .select(self.schemas_index),
+      Tabs::new(sections).highlight_style(theme::current().active_tab).select(self.section_index),
       inner,
     );
 
     let inner_margin: Margin = Margin { horizontal: 1, vertical: 1 };
     let mut inner = inner.inner(&inner_margin);
     inner.height = inner.height.saturating_add(1);
-    self.schema_viewer.render_widget(frame, inner);
+    let items = self.section_items(self.section_index);
+    if self.section_index == AUTH_SECTION {
+      frame.render_widget(Paragraph::new(self.auth_lines()).wrap(Wrap { trim: false }), inner);
+    } else if self.section_index == DOCS_SECTION {
+      let lines = self.docs_lines();
+      self.docs_offset = self.docs_offset.min(u16::try_from(lines.len().saturating_sub(1)).unwrap_or(u16::MAX));
+      frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }).scroll((self.docs_offset, 0)), inner);
+    } else if items.is_empty() {
+      let empty = match self.section_index {
+        PARAMS_SECTION => "No path, query or cookie parameters",
+        HEADERS_SECTION => "No header parameters nor default headers",
+        _ => "No request body nor callbacks",
+      };
+      frame.render_widget(Paragraph::new(empty).style(Style::default().dim().italic()), inner);
+    } else {
+      let headers_tab = Span::styled("Default Headers", Style::default().fg(self.location_color("header"))).dim();
+      let tabs = items.iter().map(|index| {
+        match self.schemas.get(*index) {
+          Some(item) => item.tab(self.location_color(item.location.as_str())),
+          None => Line::from(headers_tab.clone()),
+        }
+      });
+      let selected = items.iter().position(|index| *index == self.schemas_index).unwrap_or_default();
+      frame.render_widget(Tabs::new(tabs).highlight_style(theme::current().active_tab).select(selected), inner);
+      let inner = Rect { y: inner.y.saturating_add(2), height: inner.height.saturating_sub(2), ..inner };
+      self.draw_item(frame, inner)?;
+    }
 
+    let secrets = match self.state.read().unwrap().reveal_secrets {
+      true => Line::styled("secrets revealed", Style::default().fg(Color::LightRed)),
+      false => Line::styled(format!("secrets {}", secrets::MASK), Style::default().dim()),
+    };
     frame.render_widget(
       Block::default()
         .title("Request")
+        .title(self.negotiation_line())
+        .title(secrets.right_aligned())
         .borders(Borders::ALL)
         .border_style(self.border_style())
         .border_type(self.border_type())
         .title_bottom(self.legend_line().style(Style::default().dim()).right_aligned())
+        .title_bottom(self.schema_viewer.position_line().style(Style::default().dim()).right_aligned())
         .title_bottom(
           self
-            .nested_schema_path_line()
+            .trail_line()
             .style(Style::default().fg(Color::White).dim().add_modifier(Modifier::ITALIC))
             .left_aligned(),
-        ),
+        )
+        .title_bottom(self.hint_line().style(Style::default().dim()).left_aligned()),
       area,
     );
 
     Ok(())
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+  use serde_json::json;
+
+  use super::*;
+  use crate::{
+    pages::home::ParsedSpec,
+    workspace::{Environment, OperationKey},
+  };
+
+  fn pane(default_headers: &[(&str, &str)]) -> RequestPane {
+    let document = json!({
+      "openapi": "3.0.0",
+      "info": { "title": "Petstore", "version": "1.0.0" },
+      "paths": {
+        "/pets": {
+          "get": {
+            "operationId": "listPets",
+            "parameters": [
+              { "name": "limit", "in": "query", "required": true, "schema": { "type": "integer", "default": 20 } },
+              { "name": "status", "in": "query", "deprecated": true, "example": "available", "schema": { "type": "string" } },
+              { "name": "X-Request-Id", "in": "header", "schema": { "type": "string" } }
+            ],
+            "responses": { "200": { "description": "The pets" } }
+          },
+          "post": {
+            "operationId": "addPet",
+            "requestBody": {
+              "content": {
+                "application/json": {
+                  "schema": { "type": "object", "properties": { "name": { "type": "string" } } }
+                }
+              }
+            },
+            "responses": { "201": { "description": "Added" } }
+          },
+          "put": {
+            "operationId": "updatePetForm",
+            "requestBody": {
+              "content": {
+                "application/x-www-form-urlencoded": {
+                  "schema": {
+                    "type": "object",
+                    "properties": { "name": { "type": "string" }, "photo": { "type": "string", "format": "binary" } }
+                  }
+                }
+              }
+            },
+            "responses": { "200": { "description": "Updated" } }
+          }
+        }
+      }
+    });
+    let mut state = State::default();
+    state.set_spec(ParsedSpec { spec: serde_json::from_value(document.clone()).unwrap(), document });
+    state.workspace.environments.push(Environment {
+      name: String::from("dev"),
+      headers: default_headers.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect(),
+      ..Environment::default()
+    });
+    state.workspace.active_environment = Some(String::from("dev"));
+    let mut pane = RequestPane::new(Arc::new(RwLock::new(state)), true, Style::default());
+    select_operation(&mut pane, "listPets");
+    pane
+  }
+
+  fn select_operation(pane: &mut RequestPane, operation_id: &str) {
+    {
+      let mut state = pane.state.write().unwrap();
+      let index =
+        state.operations().position(|(_, _, operation)| operation.operation_id.as_deref() == Some(operation_id));
+      state.active_operation_index = index.unwrap();
+    }
+    pane.update(Action::Update).unwrap();
+  }
+
+  fn selected(pane: &RequestPane) -> Option<&str> {
+    pane.schemas.get(pane.schemas_index).map(|request_type| request_type.title.as_str())
+  }
+
+  #[test]
+  fn test_selection_across_operations() {
+    let mut pane = pane(&[]);
+    assert_eq!((pane.section_index, selected(&pane)), (PARAMS_SECTION, Some("limit")));
+    pane.update(Action::NextSection).unwrap();
+    assert_eq!(selected(&pane), Some("status"));
+    pane.update(Action::NextSection).unwrap();
+    assert_eq!(selected(&pane), Some("limit"));
+    pane.update(Action::PrevSection).unwrap();
+    assert_eq!(selected(&pane), Some("status"));
+
+    pane.update(Action::Tab(HEADERS_SECTION as u32)).unwrap();
+    assert_eq!(selected(&pane), Some("X-Request-Id"));
+    assert!(!pane.headers_tab_selected());
+
+    // the section stays selected as another operation is, with its first item, or none
+    select_operation(&mut pane, "addPet");
+    assert_eq!((pane.section_index, pane.schemas_index), (HEADERS_SECTION, NO_ITEM));
+    pane.update(Action::NextSection).unwrap();
+    assert_eq!(pane.schemas_index, NO_ITEM);
+    pane.update(Action::Tab(BODY_SECTION as u32)).unwrap();
+    assert_eq!((selected(&pane), pane.body_selected()), (Some("Body"), true));
+
+    select_operation(&mut pane, "listPets");
+    assert_eq!((pane.section_index, pane.schemas_index), (BODY_SECTION, NO_ITEM));
+    pane.update(Action::Tab(AUTH_SECTION as u32)).unwrap();
+    assert_eq!(pane.schemas_index, NO_ITEM);
+  }
+
+  #[test]
+  fn test_default_headers_only() {
+    let mut pane = pane(&[("X-Tenant", "acme"), ("X-Trace", "on")]);
+    select_operation(&mut pane, "addPet");
+    pane.update(Action::Tab(HEADERS_SECTION as u32)).unwrap();
+    assert_eq!(pane.schemas_index, pane.schemas.len());
+    assert!(pane.headers_tab_selected());
+    assert_eq!(pane.selected_header(), Some((String::from("X-Tenant"), Some(String::from("acme")))));
+    pane.update(Action::Down).unwrap();
+    assert_eq!(pane.selected_header(), Some((String::from("X-Trace"), Some(String::from("on")))));
+    // the default headers are the only item of the section
+    pane.update(Action::NextSection).unwrap();
+    assert!(pane.headers_tab_selected());
+
+    select_operation(&mut pane, "listPets");
+    assert_eq!(selected(&pane), Some("X-Request-Id"));
+    pane.update(Action::NextSection).unwrap();
+    assert!(pane.headers_tab_selected());
+    pane.update(Action::NextSection).unwrap();
+    assert_eq!(selected(&pane), Some("X-Request-Id"));
+  }
+
+  #[test]
+  fn test_tab_badges() {
+    let pane = pane(&[]);
+    let tab = |title: &str| {
+      let request_type = pane.schemas.iter().find(|request_type| request_type.title == title).unwrap();
+      let line = request_type.tab(Color::White);
+      let crossed = line.spans[0].style.add_modifier.contains(Modifier::CROSSED_OUT);
+      (line.spans.iter().map(|span| span.content.as_ref()).collect::<String>(), crossed)
+    };
+    assert_eq!(tab("limit"), (String::from("limit* =20"), false));
+    assert_eq!(tab("status"), (String::from("status e.g. available"), true));
+    assert_eq!(tab("X-Request-Id"), (String::from("X-Request-Id"), false));
+  }
+
+  #[test]
+  fn test_urlencoded_form() -> Result<()> {
+    let mut pane = pane(&[]);
+    select_operation(&mut pane, "updatePetForm");
+    pane.update(Action::Tab(BODY_SECTION as u32))?;
+    assert!(pane.form_selected());
+    let parts = pane.form_parts();
+    assert_eq!(parts.iter().map(|part| (part.name.as_str(), part.file)).collect::<Vec<_>>(), vec![
+      ("name", false),
+      ("photo", false)
+    ]);
+
+    pane.handle_key_events(KeyEvent::from(KeyCode::Char('i')))?;
+    pane.handle_paste_events(String::from("Rex & co"))?;
+    pane.handle_key_events(KeyEvent::from(KeyCode::Enter))?;
+    let request = pane.state.read().unwrap().active_request().unwrap();
+    assert_eq!(request.header("Content-Type"), Some("application/x-www-form-urlencoded"));
+    assert_eq!(request.body.as_deref(), Some("name=Rex%20%26%20co"));
+    assert!(request.form.is_empty());
+    Ok(())
+  }
+
+  #[test]
+  fn test_docs_note() {
+    let pane = pane(&[]);
+    let docs = |pane: &RequestPane| -> Vec<String> { pane.docs_lines().iter().map(ToString::to_string).collect() };
+    assert!(docs(&pane).iter().all(|line| !line.starts_with('✎')));
+    pane
+      .state
+      .write()
+      .unwrap()
+      .workspace
+      .set_note(&OperationKey::new("get", "/pets"), "returns 200 on failure\nretry later");
+    let lines = docs(&pane);
+    assert_eq!(lines.iter().filter(|line| line.starts_with('✎')).collect::<Vec<_>>(), vec![
+      "✎ returns 200 on failure",
+      "✎ retry later"
+    ]);
+  }
+
+  #[test]
+  fn test_paste() -> Result<()> {
+    let mut pane = pane(&[]);
+    let draft = |pane: &RequestPane| pane.state.read().unwrap().active_draft().cloned().unwrap_or_default();
+    assert!(pane.handle_paste_events(String::from("ignored"))?.is_none());
+    // pasted into a parameter on its line
+    pane.handle_key_events(KeyEvent::from(KeyCode::Char('i')))?;
+    pane.handle_paste_events(String::from("5\r\n0"))?;
+    pane.handle_key_events(KeyEvent::from(KeyCode::Enter))?;
+    assert_eq!(draft(&pane).parameter("query", "limit"), Some("2050"));
+
+    // pasted into the body verbatim, line breaks included
+    select_operation(&mut pane, "addPet");
+    pane.state.write().unwrap().workspace.drafts.entry(String::from("addPet")).or_default().body = Some(String::new());
+    pane.update(Action::Tab(BODY_SECTION as u32))?;
+    pane.handle_key_events(KeyEvent::from(KeyCode::Char('i')))?;
+    pane.handle_paste_events(String::from("{\r\n  \"name\": \"Rex\"\r}"))?;
+    pane.handle_key_events(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL))?;
+    assert_eq!(draft(&pane).body.as_deref(), Some("{\n  \"name\": \"Rex\"\n}"));
+    Ok(())
+  }
+
+  #[test]
+  fn test_tab_out_of_range() {
+    let mut pane = pane(&[]);
+    pane.update(Action::NextSection).unwrap();
+    for index in [SECTIONS.len() as u32, 9, u32::MAX] {
+      pane.update(Action::Tab(index)).unwrap();
+      assert_eq!((pane.section_index, selected(&pane)), (PARAMS_SECTION, Some("status")));
+    }
+    pane.update(Action::Tab(DOCS_SECTION as u32)).unwrap();
+    assert_eq!((pane.section_index, pane.schemas_index), (DOCS_SECTION, NO_ITEM));
+  }
+}
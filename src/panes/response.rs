@@ -1,7 +1,7 @@
 use std::sync::{Arc, RwLock};
 
 use color_eyre::eyre::Result;
-use crossterm::event::{KeyEvent, MouseEvent};
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent};
 use oas3::Schema;
 use ratatui::{
   prelude::*,
@@ -10,9 +10,15 @@ use ratatui::{
 
 use crate::{
   action::Action,
-  components::schema_viewer::SchemaViewer,
+  assertion::TestResult,
+  client::Exchange,
+  components::{example_picker::ExamplePicker, schema_viewer::SchemaViewer},
+  link::{self, Link},
+  mock::{self, NamedExample},
   pages::home::State,
   panes::Pane,
+  queue::{Outcome, Queued},
+  theme,
   tui::{EventResponse, Frame},
 };
 
@@ -20,6 +26,7 @@ pub struct ResponseType {
   status: String,
   media_type: String,
   schema: Schema,
+  description: Option<String>,
 }
 
 #[derive(Default)]
@@ -29,8 +36,17 @@ pub struct ResponsePane {
   state: Arc<RwLock<State>>,
 
   schemas: Vec<ResponseType>,
+  /// Selects the tests tab, after the schemas, when it is their count, then the links tab and the queue tab.
   schemas_index: usize,
   schema_viewer: SchemaViewer,
+  /// Examples of the selected response, declared or generated from its schema.
+  example_picker: ExamplePicker,
+  /// Links of the responses of the active operation to other operations.
+  links: Vec<Link>,
+  link_index: usize,
+  queue_index: usize,
+  /// Id of the queued request the selected one is compared with.
+  marked: Option<usize>,
 }
 
 impl ResponsePane {
@@ -41,6 +57,11 @@ impl ResponsePane {
       schemas: Vec::default(),
       schemas_index: 0,
       schema_viewer: SchemaViewer::from(state.clone()),
+      example_picker: ExamplePicker::default(),
+      links: Vec::default(),
+      link_index: 0,
+      queue_index: 0,
+      marked: None,
       state,
     }
   }
@@ -48,7 +69,7 @@ impl ResponsePane {
   fn border_style(&self) -> Style {
     match self.focused {
       true => self.focused_border_style,
-      false => Style::default(),
+      false => theme::current().border,
     }
   }
 
@@ -59,31 +80,193 @@ impl ResponsePane {
     }
   }
 
-  fn status_color(&self, status: &str) -> Color {
-    if status.starts_with('2') || status.starts_with("default") {
-      return Color::LightCyan;
-    }
-    if status.starts_with('3') {
-      return Color::LightBlue;
-    }
-    if status.starts_with('4') {
-      return Color::LightYellow;
+  fn tests_selected(&self) -> bool {
+    self.schemas_index == self.schemas.len()
+  }
+
+  /// Examples of the media type of the selected response, the one generated from its schema last.
+  fn response_examples(&self) -> Vec<NamedExample> {
+    let Some(response_type) = self.schemas.get(self.schemas_index) else {
+      return vec![];
+    };
+    let state = self.state.read().unwrap();
+    let document = &state.openapi_document;
+    let response = state
+      .active_operation_value()
+      .and_then(|operation| operation.get("responses"))
+      .and_then(|responses| responses.get(&response_type.status));
+    let pointer = format!("/content/{}", response_type.media_type.replace('~', "~0").replace('/', "~1"));
+    let media = response.and_then(|response| mock::resolve(response, document).pointer(&pointer));
+    media.map(|media| mock::preview_examples(media, document)).unwrap_or_default()
+  }
+
+  fn links_selected(&self) -> bool {
+    self.schemas_index == self.schemas.len() + 1 && !self.links.is_empty()
+  }
+
+  fn queue_tab(&self) -> usize {
+    self.schemas.len() + 1 + usize::from(!self.links.is_empty())
+  }
+
+  fn queue_selected(&self) -> bool {
+    self.schemas_index == self.queue_tab()
+  }
+
+  fn queued(&self, index: usize) -> Option<Queued> {
+    self.state.read().unwrap().queue.entries().get(index).cloned()
+  }
+
+  /// Exchange of the selected request of the queue, once its response came back.
+  fn show_queued(&self) -> Option<Action> {
+    match self.queued(self.queue_index)?.outcome {
+      Outcome::Received(exchange) => Some(Action::ShowExchange(exchange)),
+      _ => None,
     }
-    if status.starts_with('5') {
-      return Color::LightRed;
+  }
+
+  /// Compares the response of the marked request of the queue with the one of the selected request.
+  fn compare_queued(&self) -> Option<Action> {
+    let state = self.state.read().unwrap();
+    let entries = state.queue.entries();
+    let marked = entries.iter().find(|entry| Some(entry.id) == self.marked)?;
+    let selected = entries.get(self.queue_index).filter(|selected| selected.id != marked.id)?;
+    match (&marked.outcome, &selected.outcome) {
+      (Outcome::Received(marked), Outcome::Received(selected)) => {
+        Some(Action::CompareExchanges(marked.clone(), selected.clone()))
+      },
+      _ => None,
     }
-    Color::default()
   }
 
-  fn nested_schema_path_line(&self) -> Line {
-    let schema_path = self.schema_viewer.schema_path();
-    if schema_path.is_empty() {
-      return Line::default();
+  /// Each request of the queue with its status, or the time it has been in flight, the marked one with a diamond.
+  fn queue_items(&self) -> Vec<ListItem<'static>> {
+    let state = self.state.read().unwrap();
+    let theme = theme::current();
+    state
+      .queue
+      .entries()
+      .iter()
+      .map(|entry| {
+        let mark = match Some(entry.id) == self.marked {
+          true => Span::styled("◆ ", Style::default().fg(Color::LightMagenta)),
+          false => Span::raw("  "),
+        };
+        let request = Span::raw(format!("{} {}", entry.method, entry.path));
+        let line = match &entry.outcome {
+          Outcome::Pending => {
+            vec![
+              mark,
+              Span::styled("⟳   ", Style::default().fg(Color::LightYellow)),
+              request,
+              Span::styled(format!("  {:.1} s", entry.sent_at.elapsed().as_secs_f32()), Style::default().dim()),
+            ]
+          },
+          Outcome::Received(exchange) => {
+            vec![
+              mark,
+              Span::styled(format!("{} ", exchange.response.status), theme.status_code(exchange.response.status)),
+              request,
+              Span::styled(format!("  {} ms", exchange.elapsed_ms), Style::default().dim()),
+            ]
+          },
+          Outcome::Failed(error) => {
+            vec![
+              mark,
+              Span::styled("✗   ", Style::default().fg(Color::LightRed)),
+              request,
+              Span::styled(format!("  {}", error.replace('\n', " ")), Style::default().fg(Color::LightRed).dim()),
+            ]
+          },
+        };
+        ListItem::new(Line::from(line))
+      })
+      .collect()
+  }
+
+  /// Last exchange of the active operation, whose response the values of the links are taken from.
+  fn exchange(&self) -> Option<Exchange> {
+    let state = self.state.read().unwrap();
+    let operation = state.active_operation_key()?;
+    state.history.iter().rev().find(|exchange| exchange.operation.as_ref() == Some(&operation)).cloned()
+  }
+
+  /// Selects the target of the selected link, with the values of its parameters taken from the last response.
+  fn follow_link(&self) -> Option<Action> {
+    let link = self.links.get(self.link_index)?;
+    let target = link.target.clone()?;
+    let draft = link::draft(link, self.exchange().as_ref(), &self.state.read().unwrap().openapi_document);
+    Some(Action::FollowLink(target, Box::new(draft)))
+  }
+
+  /// Each link with its target, then the values of its parameters for the last response, or their expressions when
+  /// there is none.
+  fn link_items(&self) -> Vec<ListItem<'_>> {
+    let exchange = self.exchange();
+    self
+      .links
+      .iter()
+      .map(|link| {
+        let target = match &link.target {
+          Some(target) => Span::raw(target.to_string()),
+          None => Span::styled("unknown operation", Style::default().fg(Color::LightRed)),
+        };
+        let mut lines = vec![Line::from(vec![
+          Span::styled(format!("{} ", link.status), theme::current().status(&link.status)),
+          Span::styled(link.name.clone(), Style::default().bold()),
+          Span::raw(" → "),
+          target,
+        ])];
+        let body = link.request_body.iter().map(|body| (String::from("body"), body));
+        for (name, expression) in link.parameters.iter().map(|(name, value)| (name.clone(), value)).chain(body) {
+          let value = exchange.as_ref().and_then(|exchange| link::evaluate(expression, exchange)).map(link::text_of);
+          let value = match value {
+            Some(value) => Span::styled(value.replace('\n', " "), Style::default().fg(Color::LightGreen)),
+            None => Span::styled(link::text_of(expression.clone()), Style::default().dim()),
+          };
+          lines.push(Line::from(vec![Span::raw(format!("    {name} = ")), value]));
+        }
+        if let Some(description) = link.description.as_deref().and_then(|description| description.lines().next()) {
+          lines.push(Line::styled(format!("    {description}"), Style::default().dim()));
+        }
+        ListItem::new(lines)
+      })
+      .collect()
+  }
+
+  /// Results of the assertions checked against the last response.
+  fn tests(&self) -> Vec<TestResult> {
+    self.state.read().unwrap().history.last().map(|exchange| exchange.tests.clone()).unwrap_or_default()
+  }
+
+  fn test_lines(tests: &[TestResult]) -> Vec<Line<'_>> {
+    if tests.is_empty() {
+      return vec![Line::styled("No assertions were checked against the last response.", Style::default().dim())];
     }
-    let mut line = String::from("[ ");
-    line.push_str(&schema_path.join(" > "));
-    line.push_str(" ]");
-    Line::from(line)
+    tests
+      .iter()
+      .map(|test| {
+        let (symbol, color) = match test.passed {
+          true => ("✓", Color::LightGreen),
+          false => ("✗", Color::LightRed),
+        };
+        let actual = match (&test.actual, test.passed) {
+          (_, true) => String::default(),
+          (Some(actual), false) => format!("  got {actual}"),
+          (None, false) => String::from("  missing"),
+        };
+        Line::from(vec![
+          Span::styled(format!("{symbol} "), color),
+          Span::raw(test.assertion.as_str()),
+          Span::styled(actual, Style::default().dim()),
+        ])
+      })
+      .collect()
+  }
+
+  /// Trail of the schemas gone to, from the shown response.
+  fn trail_line(&self) -> Line<'static> {
+    let root = self.schemas.get(self.schemas_index).map(|response| format!("response {}", response.status));
+    self.schema_viewer.trail(&root.unwrap_or_default())
   }
 
   fn init_schema(&mut self) -> Result<()> {
@@ -100,15 +283,27 @@ impl ResponsePane {
               .iter()
               .filter_map(|(media_type, media)| {
                 media.schema(&state.openapi_spec).map_or(None, |schema| {
-                  Some(ResponseType { status: key.clone(), media_type: media_type.to_string(), schema })
+                  Some(ResponseType {
+                    status: key.clone(),
+                    media_type: media_type.to_string(),
+                    schema,
+                    description: response.description.clone(),
+                  })
                 })
               })
               .collect::<Vec<ResponseType>>()
           })
           .collect();
       }
+      self.links = state
+        .active_operation_value()
+        .map(|operation| link::links(operation, &state.openapi_document))
+        .unwrap_or_default();
     }
+    self.link_index = self.link_index.min(self.links.len().saturating_sub(1));
+    self.example_picker.set(self.response_examples());
     if let Some(response_type) = self.schemas.get(self.schemas_index) {
+      self.schema_viewer.set_description(response_type.description.clone());
       self.schema_viewer.set(response_type.schema.clone())?;
     } else {
       self.schema_viewer.clear();
@@ -134,7 +329,11 @@ impl Pane for ResponsePane {
   }
 
   fn height_constraint(&self) -> Constraint {
-    if self.schemas.get(self.schemas_index).is_none() {
+    if self.schemas.get(self.schemas_index).is_none()
+      && (!self.tests_selected() || self.tests().is_empty())
+      && !self.links_selected()
+      && !self.queue_selected()
+    {
       return Constraint::Max(2);
     }
 
@@ -144,8 +343,49 @@ impl Pane for ResponsePane {
     }
   }
 
-  fn handle_key_events(&mut self, _key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
-    Ok(None)
+  fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
+    if key.code == KeyCode::Enter && self.links_selected() {
+      return Ok(self.follow_link().map(EventResponse::Stop));
+    }
+    if self.queue_selected() {
+      return match key.code {
+        KeyCode::Enter => Ok(self.show_queued().map(EventResponse::Stop)),
+        KeyCode::Char(' ') => {
+          let id = self.queued(self.queue_index).map(|entry| entry.id);
+          self.marked = if self.marked == id { None } else { id };
+          Ok(Some(EventResponse::Stop(Action::Render)))
+        },
+        KeyCode::Char('d') => Ok(self.compare_queued().map(EventResponse::Stop)),
+        _ => Ok(None),
+      };
+    }
+    match (key.code, self.schema_viewer.schema_path().last()) {
+      (KeyCode::Char('X'), _) => {
+        self.schema_viewer.toggle_extensions()?;
+        Ok(Some(EventResponse::Stop(Action::Render)))
+      },
+      (KeyCode::Char(']'), _) if !self.example_picker.is_empty() => {
+        self.example_picker.next();
+        Ok(Some(EventResponse::Stop(Action::Render)))
+      },
+      (KeyCode::Char('['), _) if !self.example_picker.is_empty() => {
+        self.example_picker.previous();
+        Ok(Some(EventResponse::Stop(Action::Render)))
+      },
+      (KeyCode::Char('U'), Some(schema)) => Ok(Some(EventResponse::Stop(Action::ShowUsages(schema.clone())))),
+      (KeyCode::Char('y'), _) => Ok(self.schema_viewer.link().map(|link| EventResponse::Stop(Action::Copy(link)))),
+      (KeyCode::Char('m'), _) => Ok(self.schema_viewer.link().map(|link| EventResponse::Stop(Action::OpenUrl(link)))),
+      (KeyCode::Char('J') | KeyCode::Char('Y'), _) => {
+        let action: fn(String, Box<serde_json::Value>) -> Action = match key.code {
+          KeyCode::Char('J') => Action::ExportSchema,
+          _ => Action::ShowTypes,
+        };
+        let root = self.schemas.get(self.schemas_index).map(|response| format!("response-{}", response.status));
+        let shown = root.and_then(|root| self.schema_viewer.shown(&root));
+        Ok(shown.map(|(name, schema)| EventResponse::Stop(action(name, Box::new(schema)))))
+      },
+      _ => Ok(None),
+    }
   }
 
   #[allow(unused_variables)]
@@ -159,22 +399,48 @@ impl Pane for ResponsePane {
         self.schemas_index = 0;
         self.init_schema()?;
       },
+      Action::Down if self.links_selected() => {
+        self.link_index = self.link_index.saturating_add(1).min(self.links.len().saturating_sub(1));
+      },
+      Action::Up if self.links_selected() => {
+        self.link_index = self.link_index.saturating_sub(1);
+      },
+      Action::Go if self.links_selected() => return Ok(self.follow_link()),
+      Action::Down if self.queue_selected() => {
+        let count = self.state.read().unwrap().queue.entries().len();
+        self.queue_index = self.queue_index.saturating_add(1).min(count.saturating_sub(1));
+      },
+      Action::Up if self.queue_selected() => {
+        self.queue_index = self.queue_index.saturating_sub(1);
+      },
+      Action::Go if self.queue_selected() => return Ok(self.show_queued()),
       Action::Down => {
         self.schema_viewer.down();
       },
       Action::Up => {
         self.schema_viewer.up();
       },
-      Action::Tab(index) if index < self.schemas.len().try_into()? => {
+      Action::PageDown | Action::PageUp | Action::Top | Action::Bottom
+        if !self.links_selected() && !self.queue_selected() =>
+      {
+        self.schema_viewer.jump(&action)
+      },
+      Action::Tab(index) if index <= self.queue_tab().try_into()? => {
         self.schemas_index = index.try_into()?;
         self.init_schema()?;
       },
       Action::Go => self.schema_viewer.go()?,
+      Action::NextBranch => self.schema_viewer.next_branch()?,
       Action::Back => {
         if let Some(response_type) = self.schemas.get(self.schemas_index) {
           self.schema_viewer.back(response_type.schema.clone())?;
         }
       },
+      Action::BackTo(depth) => {
+        if let Some(response_type) = self.schemas.get(self.schemas_index) {
+          self.schema_viewer.back_to(depth, response_type.schema.clone())?;
+        }
+      },
       _ => {},
     }
 
@@ -185,14 +451,41 @@ impl Pane for ResponsePane {
     let inner_margin: Margin = Margin { horizontal: 1, vertical: 1 };
 
     let inner = area.inner(&inner_margin);
-    frame.render_widget(
-      Tabs::new(self.schemas.iter().map(|resp| {
+    let tests = self.tests();
+    let failed = tests.iter().filter(|test| !test.passed).count();
+    let tests_tab = match (tests.is_empty(), failed) {
+      (true, _) => Span::styled("Tests", Style::default().dim()),
+      (false, 0) => {
+        Span::styled(format!("Tests {}/{}", tests.len(), tests.len()), Style::default().fg(Color::LightGreen).dim())
+      },
+      (false, failed) => {
         Span::styled(
-          format!("{} [{}]", resp.status, resp.media_type),
-          Style::default().fg(self.status_color(resp.status.as_str())).dim(),
+          format!("Tests {}/{}", tests.len() - failed, tests.len()),
+          Style::default().fg(Color::LightRed).dim(),
         )
-      }))
-      .highlight_style(Style::default().add_modifier(Modifier::BOLD | Modifier::UNDERLINED).not_dim())
+      },
+    };
+    let queue = self.state.read().unwrap().queue.entries().len();
+    let queue_tab = match self.state.read().unwrap().queue.pending() {
+      0 => Span::styled(format!("Queue {queue}"), Style::default().dim()),
+      pending => Span::styled(format!("Queue {queue} ⇅{pending}"), Style::default().fg(Color::LightYellow).dim()),
+    };
+    frame.render_widget(
+      Tabs::new(
+        self
+          .schemas
+          .iter()
+          .map(|resp| {
+            Span::styled(format!("{} [{}]", resp.status, resp.media_type), theme::current().status(&resp.status).dim())
+          })
+          .chain([tests_tab])
+          .chain(
+            (!self.links.is_empty())
+              .then(|| Span::styled(format!("Links {}", self.links.len()), Style::default().dim())),
+          )
+          .chain([queue_tab]),
+      )
+      .highlight_style(theme::current().active_tab)
       .select(self.schemas_index),
       inner,
     );
@@ -200,7 +493,52 @@ impl Pane for ResponsePane {
     let inner_margin: Margin = Margin { horizontal: 1, vertical: 1 };
     let mut inner = inner.inner(&inner_margin);
     inner.height = inner.height.saturating_add(1);
-    self.schema_viewer.render_widget(frame, inner);
+    if self.queue_selected() {
+      self.queue_index = self.queue_index.min(queue.saturating_sub(1));
+      let items = self.queue_items();
+      if items.is_empty() {
+        let empty = Line::styled("No request was sent during this session.", Style::default().dim());
+        frame.render_widget(Paragraph::new(empty), inner);
+      } else {
+        let mut list_state = ListState::default().with_selected(Some(self.queue_index));
+        let list = List::new(items)
+          .highlight_symbol(symbols::scrollbar::HORIZONTAL.end)
+          .highlight_spacing(HighlightSpacing::Always);
+        frame.render_stateful_widget(list, inner, &mut list_state);
+      }
+    } else if self.links_selected() {
+      let mut list_state = ListState::default().with_selected(Some(self.link_index));
+      let list = List::new(self.link_items())
+        .highlight_symbol(symbols::scrollbar::HORIZONTAL.end)
+        .highlight_spacing(HighlightSpacing::Always);
+      frame.render_stateful_widget(list, inner, &mut list_state);
+    } else if self.tests_selected() {
+      frame.render_widget(Paragraph::new(Self::test_lines(&tests)), inner);
+    } else {
+      let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+          Constraint::Length(self.example_picker.height(match self.focused {
+            true => inner.height / 2,
+            false => inner.height / 3,
+          })),
+          Constraint::Fill(1),
+        ])
+        .split(inner);
+      if !layout[0].is_empty() {
+        self.example_picker.render_widget(frame, layout[0]);
+      }
+      self.schema_viewer.render_widget(frame, layout[1]);
+    }
+    const ARROW: &str = symbols::scrollbar::HORIZONTAL.end;
+    let hint = match (self.links_selected(), self.example_picker.is_empty()) {
+      _ if self.queue_selected() && self.focused => {
+        Line::from(format!("[enter {ARROW} show] [space {ARROW} mark] [d {ARROW} compare with marked]"))
+      },
+      (true, _) => Line::from(format!("[enter {ARROW} follow]")),
+      (false, false) if self.focused => Line::from(format!("[] / [ {ARROW} example]")),
+      _ => Line::default(),
+    };
 
     frame.render_widget(
       Block::default()
@@ -210,10 +548,12 @@ impl Pane for ResponsePane {
         .border_type(self.border_type())
         .title_bottom(
           self
-            .nested_schema_path_line()
+            .trail_line()
             .style(Style::default().fg(Color::White).dim().add_modifier(Modifier::ITALIC))
             .left_aligned(),
-        ),
+        )
+        .title_bottom(hint.style(Style::default().dim()).left_aligned())
+        .title_bottom(self.schema_viewer.position_line().style(Style::default().dim()).right_aligned()),
       area,
     );
     Ok(())
@@ -1,4 +1,7 @@
-use std::sync::{Arc, RwLock};
+use std::{
+  collections::BTreeSet,
+  sync::{Arc, RwLock},
+};
 
 use color_eyre::eyre::Result;
 use crossterm::event::{KeyEvent, MouseEvent};
@@ -9,8 +12,11 @@ use ratatui::{
 
 use crate::{
   action::Action,
+  markdown,
   pages::home::State,
-  panes::Pane,
+  panes::{jumped_index, list_item_at, section_index, Pane},
+  tags::TagNode,
+  theme,
   tui::{EventResponse, Frame},
 };
 
@@ -20,17 +26,58 @@ pub struct TagsPane {
   focused_border_style: Style,
   state: Arc<RwLock<State>>,
   current_tag_index: usize,
+  /// Keys of the tag groups whose tags are listed.
+  expanded: BTreeSet<String>,
+  /// Inside of the block the tags were last drawn in, and how far they were scrolled, see `list_item_at`.
+  list_area: Rect,
+  list_offset: usize,
 }
 
 impl TagsPane {
   pub fn new(state: Arc<RwLock<State>>, focused: bool, focused_border_style: Style) -> Self {
-    Self { focused, focused_border_style, state, current_tag_index: 0 }
+    Self {
+      focused,
+      focused_border_style,
+      state,
+      current_tag_index: 0,
+      expanded: BTreeSet::default(),
+      list_area: Rect::default(),
+      list_offset: 0,
+    }
+  }
+
+  /// Listed nodes, those of collapsed groups left out, with their depth.
+  fn rows<'a>(&self, nodes: &'a [TagNode], depth: usize) -> Vec<(usize, &'a TagNode)> {
+    let mut rows = vec![];
+    for node in nodes {
+      rows.push((depth, node));
+      if self.expanded.contains(&node.key) {
+        rows.extend(self.rows(&node.children, depth + 1));
+      }
+    }
+    rows
+  }
+
+  /// The tag with its share of exercised operations and the first line of its description.
+  fn tag_line<'a>(state: &State, name: String, tag: Option<&str>, description: Option<&str>) -> Line<'a> {
+    let (exercised, total) = state.coverage(tag);
+    let percentage = (exercised * 100).checked_div(total).unwrap_or(0);
+    let style = match percentage {
+      100 => Style::default().fg(Color::LightGreen),
+      _ => Style::default().dim(),
+    };
+    let mut spans = vec![Span::raw(name), Span::styled(format!(" {exercised}/{total} {percentage}%"), style)];
+    if let Some(line) = description.and_then(|description| markdown::render(description).into_iter().next()) {
+      spans.push(Span::raw("  "));
+      spans.extend(line.spans.into_iter().map(|(style, text)| Span::styled(text, style.dim())));
+    }
+    Line::from(spans)
   }
 
   fn border_style(&self) -> Style {
     match self.focused {
       true => self.focused_border_style,
-      false => Style::default(),
+      false => theme::current().border,
     }
   }
 
@@ -41,19 +88,82 @@ impl TagsPane {
     }
   }
 
+  /// Follows the active tag when it was changed outside of the pane, expanding the groups it is listed in.
+  fn sync_active_tag(&mut self) {
+    let state = self.state.read().unwrap();
+    let Some(name) = &state.active_tag_name else {
+      self.current_tag_index = 0;
+      return;
+    };
+    if let Some(path) = path(&state.tag_tree, name) {
+      self.expanded.extend(path.into_iter().map(|node| node.key.clone()));
+    }
+    self.current_tag_index =
+      self.rows(&state.tag_tree, 0).iter().position(|(_, node)| &node.name() == name).map_or(0, |index| index + 1);
+  }
+
   fn update_active_tag(&mut self) {
     let mut state = self.state.write().unwrap();
-    if self.current_tag_index > 0 {
-      if let Some(tag) = state.openapi_spec.tags.get(self.current_tag_index - 1) {
-        state.active_tag_name = Some(tag.name.clone());
-        state.active_operation_index = 0;
-      }
-    } else {
-      state.active_tag_name = None;
-      state.active_operation_index = 0;
+    let name = match self.current_tag_index {
+      0 => None,
+      index => self.rows(&state.tag_tree, 0).get(index - 1).map(|(_, node)| node.name()),
+    };
+    state.active_tag_name = name;
+    state.active_operation_index = 0;
+  }
+
+  /// Expands or collapses the selected group.
+  fn toggle_group(&mut self) {
+    let state = self.state.read().unwrap();
+    let rows = self.rows(&state.tag_tree, 0);
+    let Some((_, node)) = self.current_tag_index.checked_sub(1).and_then(|index| rows.get(index)) else {
+      return;
+    };
+    if node.children.is_empty() {
+      return;
     }
+    let key = node.key.clone();
+    if !self.expanded.remove(&key) {
+      self.expanded.insert(key);
+    }
+  }
+
+  /// Collapses the group of the selected tag and selects it.
+  fn collapse_group(&mut self) -> bool {
+    let state = self.state.read().unwrap();
+    let rows = self.rows(&state.tag_tree, 0);
+    let Some(index) = self.current_tag_index.checked_sub(1).filter(|index| *index < rows.len()) else {
+      return false;
+    };
+    let (depth, node) = rows[index];
+    if self.expanded.contains(&node.key) {
+      let key = node.key.clone();
+      self.expanded.remove(&key);
+      return true;
+    }
+    let Some(parent) = rows[..index].iter().rposition(|(parent_depth, _)| *parent_depth < depth) else {
+      return false;
+    };
+    let key = rows[parent].1.key.clone();
+    self.expanded.remove(&key);
+    self.current_tag_index = parent + 1;
+    true
   }
 }
+
+/// Groups the node of the name is listed under, from the root of the tree.
+fn path<'a>(nodes: &'a [TagNode], name: &str) -> Option<Vec<&'a TagNode>> {
+  nodes.iter().find_map(|node| {
+    if node.name() == name {
+      return Some(vec![]);
+    }
+    path(&node.children, name).map(|mut path| {
+      path.insert(0, node);
+      path
+    })
+  })
+}
+
 impl Pane for TagsPane {
   fn init(&mut self) -> Result<()> {
     Ok(())
@@ -80,9 +190,18 @@ impl Pane for TagsPane {
     Ok(None)
   }
 
-  #[allow(unused_variables)]
   fn handle_mouse_events(&mut self, mouse: MouseEvent) -> Result<Option<EventResponse<Action>>> {
-    Ok(None)
+    let tags_list_len = {
+      let state = self.state.read().unwrap();
+      self.rows(&state.tag_tree, 0).len().saturating_add(1)
+    };
+    let Some(index) = list_item_at(self.list_area, self.list_offset, mouse).filter(|index| *index < tags_list_len)
+    else {
+      return Ok(Some(EventResponse::Stop(Action::Render)));
+    };
+    self.current_tag_index = index;
+    self.update_active_tag();
+    Ok(Some(EventResponse::Stop(Action::Update)))
   }
 
   fn update(&mut self, action: Action) -> Result<Option<Action>> {
@@ -90,10 +209,8 @@ impl Pane for TagsPane {
       Action::Down => {
         {
           let state = self.state.read().unwrap();
-          let tags_list_len = state.openapi_spec.tags.len().saturating_add(1);
-          if tags_list_len > 0 {
-            self.current_tag_index = self.current_tag_index.saturating_add(1) % tags_list_len;
-          }
+          let tags_list_len = self.rows(&state.tag_tree, 0).len().saturating_add(1);
+          self.current_tag_index = self.current_tag_index.saturating_add(1) % tags_list_len;
         }
         self.update_active_tag();
         return Ok(Some(Action::Update));
@@ -101,15 +218,34 @@ impl Pane for TagsPane {
       Action::Up => {
         {
           let state = self.state.read().unwrap();
-          let tags_list_len = state.openapi_spec.tags.len().saturating_add(1);
-          if tags_list_len > 0 {
-            self.current_tag_index = self.current_tag_index.saturating_add(tags_list_len - 1) % tags_list_len;
-          }
+          let tags_list_len = self.rows(&state.tag_tree, 0).len().saturating_add(1);
+          self.current_tag_index = self.current_tag_index.saturating_add(tags_list_len - 1) % tags_list_len;
+        }
+        self.update_active_tag();
+        return Ok(Some(Action::Update));
+      },
+      Action::PageDown | Action::PageUp | Action::Top | Action::Bottom | Action::NextSection | Action::PrevSection => {
+        // sections are the top level tags and groups, after the entry listing all operations
+        let starts: Vec<bool> = {
+          let state = self.state.read().unwrap();
+          let rows = self.rows(&state.tag_tree, 0);
+          std::iter::once(true).chain(rows.iter().map(|(depth, _)| *depth == 0)).collect()
+        };
+        let page = usize::from(self.list_area.height);
+        let index = jumped_index(&action, self.current_tag_index, starts.len(), page)
+          .or_else(|| section_index(&action, self.current_tag_index, &starts));
+        if let Some(index) = index {
+          self.current_tag_index = index;
+          self.update_active_tag();
+          return Ok(Some(Action::Update));
         }
+      },
+      Action::Go | Action::Submit => self.toggle_group(),
+      Action::Back if self.collapse_group() => {
         self.update_active_tag();
         return Ok(Some(Action::Update));
       },
-      Action::Submit => {},
+      Action::Update => self.sync_active_tag(),
       _ => {},
     }
 
@@ -118,24 +254,33 @@ impl Pane for TagsPane {
 
   fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
     let state = self.state.read().unwrap();
-    let mut items: Vec<Line<'_>> = state
-      .openapi_spec
-      .tags
+    let rows = self.rows(&state.tag_tree, 0);
+    let mut items: Vec<Line<'_>> = rows
       .iter()
-      .map(|tag| Line::from(vec![Span::styled(format!(" {}", tag.name), Style::default())]))
+      .map(|(depth, node)| {
+        let marker = match (node.children.is_empty(), self.expanded.contains(&node.key)) {
+          (true, _) => "  ",
+          (false, true) => "▾ ",
+          (false, false) => "▸ ",
+        };
+        let name = format!(" {}{marker}{}", "  ".repeat(*depth), node.label);
+        Self::tag_line(&state, name, Some(&node.name()), node.description.as_deref())
+      })
       .collect();
 
-    items.insert(0, Line::styled(" [ALL]", Style::default()));
+    items.insert(0, Self::tag_line(&state, String::from(" [ALL]"), None, None));
 
     let list = List::new(items)
       .block(Block::default().borders(Borders::ALL))
       .highlight_symbol(symbols::scrollbar::HORIZONTAL.end)
       .highlight_spacing(HighlightSpacing::Always)
-      .highlight_style(Style::default().add_modifier(Modifier::BOLD));
+      .highlight_style(theme::current().selection);
     let mut list_state = ListState::default().with_selected(Some(self.current_tag_index));
 
     frame.render_stateful_widget(list, area, &mut list_state);
-    let items_len = state.openapi_spec.tags.len() + 1;
+    self.list_area = area.inner(&Margin { horizontal: 1, vertical: 1 });
+    self.list_offset = list_state.offset();
+    let items_len = rows.len() + 1;
     frame.render_widget(
       Block::default()
         .title("Tags")
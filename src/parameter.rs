@@ -0,0 +1,366 @@
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+use crate::mock;
+
+/// Styles of the parameters of an operation by location and name.
+pub type ParameterStyles = BTreeMap<(String, String), ParameterStyle>;
+
+/// `style` of a parameter, how an array or object value is written in the URL or header.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Style {
+  #[default]
+  Form,
+  Simple,
+  Label,
+  Matrix,
+  SpaceDelimited,
+  PipeDelimited,
+  DeepObject,
+}
+
+/// Serialization of the values of a parameter, by its `style` and `explode`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParameterStyle {
+  pub style: Style,
+  pub explode: bool,
+  /// The schema is an array or an object, values of other parameters are sent as is even when they look like JSON.
+  pub structured: bool,
+  /// `allowReserved`, reserved characters of path values are then sent unencoded.
+  pub allow_reserved: bool,
+}
+
+/// Value entered for a parameter: JSON arrays and objects of structured parameters are serialized by the style,
+/// anything else is sent as is.
+enum Shape {
+  Scalar(String),
+  List(Vec<String>),
+  Pairs(Vec<(String, String)>),
+}
+
+fn scalar(value: &Value) -> String {
+  match value {
+    Value::String(text) => text.clone(),
+    Value::Null => String::default(),
+    value => value.to_string(),
+  }
+}
+
+fn shape(value: &str) -> Shape {
+  let trimmed = value.trim();
+  if !trimmed.starts_with(['[', '{']) {
+    return Shape::Scalar(value.to_string());
+  }
+  match serde_json::from_str::<Value>(trimmed) {
+    Ok(Value::Array(items)) => Shape::List(items.iter().map(scalar).collect()),
+    Ok(Value::Object(members)) => {
+      Shape::Pairs(members.iter().map(|(key, value)| (key.clone(), scalar(value))).collect())
+    },
+    _ => Shape::Scalar(value.to_string()),
+  }
+}
+
+impl Shape {
+  fn map(self, encode: impl Fn(&str) -> String) -> Self {
+    match self {
+      Self::Scalar(value) => Self::Scalar(encode(&value)),
+      Self::List(items) => Self::List(items.iter().map(|item| encode(item)).collect()),
+      Self::Pairs(pairs) => Self::Pairs(pairs.iter().map(|(key, value)| (encode(key), encode(value))).collect()),
+    }
+  }
+}
+
+/// Whether the schema of the parameter is an array or an object.
+fn is_structured(parameter: &Value, document: &Value) -> bool {
+  let Some(schema) = parameter.get("schema").map(|schema| mock::resolve(schema, document)) else {
+    return false;
+  };
+  let types = match schema.get("type") {
+    Some(Value::Array(types)) => types.iter().filter_map(Value::as_str).collect(),
+    Some(Value::String(kind)) => vec![kind.as_str()],
+    _ => vec![],
+  };
+  types.iter().any(|kind| matches!(*kind, "array" | "object"))
+    || schema.get("items").is_some()
+    || schema.get("properties").is_some()
+}
+
+/// Percent-encodes a path value, keeping the reserved characters when they are allowed and the `{{name}}`
+/// references substituted later.
+fn encode_path(value: &str, allow_reserved: bool) -> String {
+  let encode = |text: &str| {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+      match byte {
+        b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => encoded.push(byte as char),
+        b':' | b'/' | b'?' | b'#' | b'[' | b']' | b'@' | b'!' | b'$' | b'&' | b'\'' | b'(' | b')' | b'*' | b'+'
+        | b',' | b';' | b'='
+          if allow_reserved =>
+        {
+          encoded.push(byte as char)
+        },
+        _ => encoded.push_str(&format!("%{byte:02X}")),
+      }
+    }
+    encoded
+  };
+  let mut encoded = String::with_capacity(value.len());
+  let mut rest = value;
+  while let Some(start) = rest.find("{{") {
+    let Some(end) = rest[start..].find("}}").map(|end| start + end + 2) else {
+      break;
+    };
+    encoded.push_str(&encode(&rest[..start]));
+    encoded.push_str(&rest[start..end]);
+    rest = &rest[end..];
+  }
+  encoded.push_str(&encode(rest));
+  encoded
+}
+
+/// Keys and values of an object one after the other, e.g. `R,100,G,200`.
+fn flatten(pairs: &[(String, String)]) -> Vec<String> {
+  pairs.iter().flat_map(|(key, value)| [key.clone(), value.clone()]).collect()
+}
+
+impl ParameterStyle {
+  /// Style of parameters declaring none, `form` in the query and `simple` in the path and headers.
+  pub fn default_for(location: &str) -> Self {
+    match location {
+      "path" | "header" => Self { style: Style::Simple, explode: false, ..Self::default() },
+      _ => Self { style: Style::Form, explode: true, ..Self::default() },
+    }
+  }
+
+  /// Style of the parameter object, `explode` defaulting to whether it is `form`.
+  pub fn of(parameter: &Value, document: &Value) -> Self {
+    let location = parameter.get("in").and_then(Value::as_str).unwrap_or_default();
+    let style = match parameter.get("style").and_then(Value::as_str) {
+      Some("form") => Style::Form,
+      Some("simple") => Style::Simple,
+      Some("label") => Style::Label,
+      Some("matrix") => Style::Matrix,
+      Some("spaceDelimited") => Style::SpaceDelimited,
+      Some("pipeDelimited") => Style::PipeDelimited,
+      Some("deepObject") => Style::DeepObject,
+      _ => Self::default_for(location).style,
+    };
+    let explode = parameter.get("explode").and_then(Value::as_bool).unwrap_or(style == Style::Form);
+    let structured = is_structured(parameter, document);
+    let allow_reserved = parameter.get("allowReserved").and_then(Value::as_bool).unwrap_or_default();
+    Self { style, explode, structured, allow_reserved }
+  }
+
+  fn shape(&self, value: &str) -> Shape {
+    match self.structured {
+      true => shape(value),
+      false => Shape::Scalar(value.to_string()),
+    }
+  }
+
+  fn delimiter(&self) -> &'static str {
+    match self.style {
+      Style::SpaceDelimited => " ",
+      Style::PipeDelimited => "|",
+      _ => ",",
+    }
+  }
+
+  /// Query pairs of the value of a query parameter, encoded by `HttpRequest::query_string`.
+  pub fn query(&self, name: &str, value: &str) -> Vec<(String, String)> {
+    match self.shape(value) {
+      Shape::Scalar(value) => vec![(name.to_string(), value)],
+      Shape::List(items) if self.explode => items.into_iter().map(|item| (name.to_string(), item)).collect(),
+      Shape::List(items) => vec![(name.to_string(), items.join(self.delimiter()))],
+      Shape::Pairs(pairs) if self.style == Style::DeepObject => {
+        pairs.into_iter().map(|(key, value)| (format!("{name}[{key}]"), value)).collect()
+      },
+      Shape::Pairs(pairs) if self.explode => pairs,
+      Shape::Pairs(pairs) => vec![(name.to_string(), flatten(&pairs).join(self.delimiter()))],
+    }
+  }
+
+  /// Text of the value of a header parameter, prefixed with `.` by `label` and `;name=` by `matrix`.
+  pub fn text(&self, name: &str, value: &str) -> String {
+    self.styled(name, self.shape(value))
+  }
+
+  /// Text of the value of a path parameter, see `text`, with the values percent-encoded.
+  pub fn path(&self, name: &str, value: &str) -> String {
+    self.styled(name, self.shape(value).map(|value| encode_path(value, self.allow_reserved)))
+  }
+
+  fn styled(&self, name: &str, shape: Shape) -> String {
+    let matrix = |value: &str| {
+      match value.is_empty() {
+        true => format!(";{name}"),
+        false => format!(";{name}={value}"),
+      }
+    };
+    match (shape, self.style, self.explode) {
+      (Shape::Scalar(value), Style::Matrix, _) => matrix(&value),
+      (Shape::Scalar(value), Style::Label, _) => format!(".{value}"),
+      (Shape::Scalar(value), _, _) => value,
+      (Shape::List(items), Style::Matrix, true) => items.iter().map(|item| matrix(item)).collect(),
+      (Shape::List(items), Style::Matrix, false) => matrix(&items.join(",")),
+      (Shape::List(items), Style::Label, true) => items.iter().map(|item| format!(".{item}")).collect(),
+      (Shape::List(items), Style::Label, false) => format!(".{}", items.join(",")),
+      (Shape::List(items), _, _) => items.join(","),
+      (Shape::Pairs(pairs), Style::Matrix, true) => {
+        pairs.iter().map(|(key, value)| format!(";{key}={value}")).collect()
+      },
+      (Shape::Pairs(pairs), Style::Matrix, false) => matrix(&flatten(&pairs).join(",")),
+      (Shape::Pairs(pairs), Style::Label, true) => pairs.iter().map(|(key, value)| format!(".{key}={value}")).collect(),
+      (Shape::Pairs(pairs), Style::Label, false) => format!(".{}", flatten(&pairs).join(",")),
+      (Shape::Pairs(pairs), _, true) => {
+        pairs.iter().map(|(key, value)| format!("{key}={value}")).collect::<Vec<_>>().join(",")
+      },
+      (Shape::Pairs(pairs), _, false) => flatten(&pairs).join(","),
+    }
+  }
+}
+
+/// Parameters of the operation by location and name, those of its path item included and overridden by its own.
+pub fn parameters<'a>(document: &'a Value, path: &str, method: &str) -> BTreeMap<(String, String), &'a Value> {
+  let item = document.get("paths").and_then(|paths| paths.get(path));
+  let operation = item.and_then(|item| item.get(method.to_lowercase()));
+  let parameters = [item, operation]
+    .into_iter()
+    .flatten()
+    .filter_map(|value| value.get("parameters").and_then(Value::as_array))
+    .flatten()
+    .map(|parameter| mock::resolve(parameter, document));
+  parameters
+    .map(|parameter| {
+      let text = |key: &str| parameter.get(key).and_then(Value::as_str).unwrap_or_default().to_string();
+      ((text("in"), text("name")), parameter)
+    })
+    .collect()
+}
+
+/// Styles of the parameters of the operation.
+pub fn styles(document: &Value, path: &str, method: &str) -> ParameterStyles {
+  let parameters = parameters(document, path, method);
+  parameters.into_iter().map(|(key, parameter)| (key, ParameterStyle::of(parameter, document))).collect()
+}
+
+/// Value a parameter starts from: the `default` of its schema, its `example` or first of its `examples`, the
+/// `example` of its schema, or the first value of its enum or of the enum of its items.
+pub fn seed(parameter: &Value, document: &Value) -> Option<String> {
+  let schema = parameter.get("schema").map(|schema| mock::resolve(schema, document));
+  let items = schema.and_then(|schema| schema.get("items")).map(|items| mock::resolve(items, document));
+  let first = |schema: Option<&Value>| schema?.get("enum")?.as_array()?.first().cloned();
+  let first_example = || {
+    let example = parameter.get("examples")?.as_object()?.values().next()?;
+    mock::resolve(example, document).get("value").cloned()
+  };
+  let value = schema
+    .and_then(|schema| schema.get("default"))
+    .or_else(|| parameter.get("example"))
+    .cloned()
+    .or_else(first_example)
+    .or_else(|| schema.and_then(|schema| schema.get("example")).cloned())
+    .or_else(|| first(schema))
+    .or_else(|| first(items).map(|item| Value::Array(vec![item])))?;
+  Some(scalar(&value))
+}
+
+/// Values the path and required parameters of the operation start from, by location and name, see `seed`.
+pub fn seeds(document: &Value, path: &str, method: &str) -> BTreeMap<(String, String), String> {
+  let parameters = parameters(document, path, method).into_iter().filter(|((location, _), parameter)| {
+    location == "path" || parameter.get("required").and_then(Value::as_bool).unwrap_or_default()
+  });
+  parameters.filter_map(|(key, parameter)| seed(parameter, document).map(|value| (key, value))).collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+  use serde_json::json;
+
+  use super::*;
+
+  #[test]
+  fn test_styles() {
+    let style = |style, explode| ParameterStyle { style, explode, structured: true, allow_reserved: false };
+    let array = r#"["blue", "black"]"#;
+    let object = r#"{ "B": 100, "G": 200 }"#;
+    let text = |parameter: ParameterStyle, value| parameter.text("color", value);
+    assert_eq!(text(style(Style::Simple, false), array), "blue,black");
+    assert_eq!(text(style(Style::Simple, true), object), "B=100,G=200");
+    assert_eq!(text(style(Style::Simple, false), object), "B,100,G,200");
+    assert_eq!(text(style(Style::Label, false), "blue"), ".blue");
+    assert_eq!(text(style(Style::Label, true), array), ".blue.black");
+    assert_eq!(text(style(Style::Label, false), object), ".B,100,G,200");
+    assert_eq!(text(style(Style::Matrix, false), array), ";color=blue,black");
+    assert_eq!(text(style(Style::Matrix, true), array), ";color=blue;color=black");
+    assert_eq!(text(style(Style::Matrix, true), object), ";B=100;G=200");
+    assert_eq!(text(style(Style::Simple, false), "[not json"), "[not json");
+
+    let query = |parameter: ParameterStyle, value| {
+      let pairs = parameter.query("color", value);
+      pairs.into_iter().map(|(key, value)| format!("{key}={value}")).collect::<Vec<_>>().join("&")
+    };
+    assert_eq!(query(style(Style::Form, true), array), "color=blue&color=black");
+    assert_eq!(query(style(Style::Form, false), array), "color=blue,black");
+    assert_eq!(query(style(Style::Form, true), object), "B=100&G=200");
+    assert_eq!(query(style(Style::Form, false), object), "color=B,100,G,200");
+    assert_eq!(query(style(Style::SpaceDelimited, false), array), "color=blue black");
+    assert_eq!(query(style(Style::PipeDelimited, false), array), "color=blue|black");
+    assert_eq!(query(style(Style::DeepObject, true), object), "color[B]=100&color[G]=200");
+
+    // values of string parameters are sent as is, even when they look like JSON
+    let string = ParameterStyle { structured: false, ..style(Style::Form, true) };
+    assert_eq!(query(string, r#"{"a":1}"#), r#"color={"a":1}"#);
+    assert_eq!(string.text("color", array), array);
+
+    let document = json!({ "paths": { "/pets/{id}": {
+      "parameters": [{ "name": "id", "in": "path", "style": "matrix", "explode": true, "allowReserved": true }],
+      "get": { "parameters": [
+        { "name": "tags", "in": "query", "style": "pipeDelimited", "schema": { "type": "array" } },
+        { "$ref": "#/components/parameters/Filter" }
+      ] }
+    } }, "components": {
+      "parameters": {
+        "Filter": { "name": "filter", "in": "query", "style": "deepObject", "schema": { "$ref": "#/components/schemas/Filter" } }
+      },
+      "schemas": { "Filter": { "type": ["object", "null"] } }
+    } });
+    let styles = styles(&document, "/pets/{id}", "GET");
+    let key = |location: &str, name: &str| (location.to_string(), name.to_string());
+    let declared = |style, explode| ParameterStyle { style, explode, ..ParameterStyle::default() };
+    assert_eq!(styles[&key("path", "id")], ParameterStyle { allow_reserved: true, ..declared(Style::Matrix, true) });
+    assert_eq!(styles[&key("query", "tags")], style(Style::PipeDelimited, false));
+    assert_eq!(styles[&key("query", "filter")], style(Style::DeepObject, false));
+    assert_eq!(ParameterStyle::of(&json!({ "in": "query" }), &Value::Null), declared(Style::Form, true));
+  }
+
+  #[test]
+  fn test_path_values() {
+    let simple = ParameterStyle::default_for("path");
+    assert_eq!(simple.path("id", "a/b c"), "a%2Fb%20c");
+    assert_eq!(simple.path("id", "{{petId}}/1"), "{{petId}}%2F1");
+    let reserved = ParameterStyle { allow_reserved: true, ..simple };
+    assert_eq!(reserved.path("id", "a/b c"), "a/b%20c");
+    let matrix = ParameterStyle { style: Style::Matrix, structured: true, ..simple };
+    assert_eq!(matrix.path("id", r#"["a/b", "c"]"#), ";id=a%2Fb,c");
+  }
+
+  #[test]
+  fn test_seeds() {
+    let document = json!({ "paths": { "/pets/{id}": { "get": { "parameters": [
+      { "name": "id", "in": "path", "required": true, "schema": { "type": "integer", "example": 7 } },
+      { "name": "limit", "in": "query", "required": true, "schema": { "type": "integer", "default": 20 }, "example": 5 },
+      { "name": "status", "in": "query", "required": true, "schema": { "$ref": "#/components/schemas/Status" } },
+      { "name": "tags", "in": "query", "required": true, "schema": { "type": "array", "items": { "enum": ["dog"] } } },
+      { "name": "sort", "in": "query", "examples": { "newest": { "value": "-created" } } },
+      { "name": "X-Trace", "in": "header", "required": true }
+    ] } } }, "components": { "schemas": { "Status": { "type": "string", "enum": ["available", "sold"] } } } });
+    let seeds = seeds(&document, "/pets/{id}", "get");
+    let seeds: Vec<(&str, &str)> = seeds.iter().map(|((_, name), value)| (name.as_str(), value.as_str())).collect();
+    assert_eq!(seeds, vec![("id", "7"), ("limit", "20"), ("status", "available"), ("tags", "[\"dog\"]")]);
+    let sort = document.pointer("/paths/~1pets~1{id}/get/parameters/4").unwrap();
+    assert_eq!(seed(sort, &document).as_deref(), Some("-created"));
+  }
+}
@@ -0,0 +1,85 @@
+/// Group of the operations under a path prefix, e.g. `/users` for `/users` and `/users/{id}`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PathNode {
+  /// Path prefix of the group, unique among the nodes of the tree.
+  pub key: String,
+  /// Segments of the prefix below the parent group, e.g. `/{id}`.
+  pub label: String,
+  /// Indexes of the operations of this exact path.
+  pub operations: Vec<usize>,
+  pub children: Vec<PathNode>,
+}
+
+/// Operations of the paths, given in order, grouped by path segments. Groups with no operation of their own and a
+/// single group under them are merged, e.g. `/api/v1` instead of `/api` then `/v1`.
+pub fn tree(paths: &[String]) -> Vec<PathNode> {
+  let mut nodes = vec![];
+  for (index, path) in paths.iter().enumerate() {
+    let segments: Vec<&str> = path.split('/').filter(|segment| !segment.is_empty()).collect();
+    if segments.is_empty() {
+      let node = child(&mut nodes, "/", "/");
+      node.operations.push(index);
+      continue;
+    }
+    let mut children = &mut nodes;
+    let mut key = String::new();
+    for (depth, segment) in segments.iter().enumerate() {
+      key.push('/');
+      key.push_str(segment);
+      let node = child(children, &key, &format!("/{segment}"));
+      if depth == segments.len() - 1 {
+        node.operations.push(index);
+      }
+      children = &mut node.children;
+    }
+  }
+  nodes.into_iter().map(merge).collect()
+}
+
+/// Node of the key among the nodes, added when missing.
+fn child<'a>(nodes: &'a mut Vec<PathNode>, key: &str, label: &str) -> &'a mut PathNode {
+  let index = match nodes.iter().position(|node| node.key == key) {
+    Some(index) => index,
+    None => {
+      nodes.push(PathNode { key: key.to_string(), label: label.to_string(), ..PathNode::default() });
+      nodes.len() - 1
+    },
+  };
+  &mut nodes[index]
+}
+
+fn merge(mut node: PathNode) -> PathNode {
+  while node.operations.is_empty() && node.children.len() == 1 {
+    let child = node.children.remove(0);
+    node = PathNode { label: format!("{}{}", node.label, child.label), ..child };
+  }
+  node.children = node.children.into_iter().map(merge).collect();
+  node
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_tree() {
+    let paths: Vec<String> = ["/api/v1/users", "/api/v1/users/{id}", "/api/v1/users", "/api/v1/pets/{id}/photos", "/"]
+      .iter()
+      .map(|path| path.to_string())
+      .collect();
+    let nodes = tree(&paths);
+    assert_eq!(nodes.len(), 2);
+    assert_eq!((nodes[0].key.as_str(), nodes[0].label.as_str()), ("/api/v1", "/api/v1"));
+    let users = &nodes[0].children[0];
+    assert_eq!((users.label.as_str(), users.operations.clone()), ("/users", vec![0, 2]));
+    assert_eq!((users.children[0].label.as_str(), users.children[0].operations.clone()), ("/{id}", vec![1]));
+    let pets = &nodes[0].children[1];
+    assert_eq!(
+      (pets.key.as_str(), pets.label.as_str(), pets.operations.clone()),
+      ("/api/v1/pets/{id}/photos", "/pets/{id}/photos", vec![3])
+    );
+    assert_eq!((nodes[1].label.as_str(), nodes[1].operations.clone()), ("/", vec![4]));
+  }
+}
@@ -0,0 +1,212 @@
+use std::{collections::BTreeMap, path::Path, sync::Arc};
+
+use color_eyre::eyre::{eyre, Result};
+use wasmi::{core::TrapCode, Config, Engine, Linker, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+use crate::{client::HttpResponse, config::PluginConfig, request::HttpRequest};
+
+/// Export of request mutators, run for every request.
+const MUTATE: &str = "mutate";
+/// Export of auth signers, run for the requests of operations using one of the plugin auth schemes.
+const SIGN: &str = "sign";
+/// Export of response body renderers, run for the responses of the plugin content types.
+const RENDER: &str = "render";
+/// Instructions a call may run before it is aborted, so a looping plugin doesn't hang the request.
+const FUEL: u64 = 100_000_000;
+/// Bytes of linear memory an instance may grow to.
+const MEMORY: usize = 64 * 1024 * 1024;
+
+/// WebAssembly module declared in the `plugins` config.
+///
+/// Modules export their `memory` and an `alloc(len) -> ptr` function the input is written to, along with any of
+/// `mutate`, `sign` and `render`. These take the input as `(ptr, len)` and return the output location packed as
+/// `ptr << 32 | len`, an empty output leaves the input unchanged. Requests are passed as JSON and returned as JSON,
+/// responses are passed as JSON and rendered as text.
+pub struct Plugin {
+  pub name: String,
+  config: PluginConfig,
+  engine: Engine,
+  module: Module,
+}
+
+impl Plugin {
+  /// Loads a `.wasm` module, or its `.wat` text format.
+  pub fn load(name: &str, config: &PluginConfig, config_dir: &Path) -> Result<Self> {
+    let wasm = wat::parse_file(config_dir.join(&config.path))?;
+    Self::from_bytes(name, config, &wasm)
+  }
+
+  pub fn from_bytes(name: &str, config: &PluginConfig, wasm: &[u8]) -> Result<Self> {
+    let mut engine_config = Config::default();
+    engine_config.consume_fuel(true);
+    let engine = Engine::new(&engine_config);
+    let module = Module::new(&engine, wasm)?;
+    Ok(Self { name: name.to_string(), config: config.clone(), engine, module })
+  }
+
+  fn exports(&self, name: &str) -> bool {
+    self.module.exports().any(|export| export.name() == name)
+  }
+
+  /// Calls the export with the input in a fresh instance, `None` when the output is empty.
+  ///
+  /// Every call gets its own fuel budget and memory cap.
+  fn call(&self, export: &str, input: &[u8]) -> Result<Option<Vec<u8>>> {
+    let mut store = Store::new(&self.engine, StoreLimitsBuilder::new().memory_size(MEMORY).build());
+    store.limiter(|limits| limits);
+    store.set_fuel(FUEL).map_err(wasmi::Error::from)?;
+    let instance = Linker::<StoreLimits>::new(&self.engine)
+      .instantiate(&mut store, &self.module)?
+      .start(&mut store)
+      .map_err(trap)?;
+    let memory = instance.get_memory(&store, "memory").ok_or_else(|| eyre!("`memory` is not exported"))?;
+    let length = i32::try_from(input.len())?;
+    let pointer = instance.get_typed_func::<i32, i32>(&store, "alloc")?.call(&mut store, length).map_err(trap)?;
+    memory.write(&mut store, usize::try_from(pointer)?, input).map_err(wasmi::Error::from)?;
+    let packed =
+      instance.get_typed_func::<(i32, i32), i64>(&store, export)?.call(&mut store, (pointer, length)).map_err(trap)?;
+    let (pointer, length) = ((packed as u64 >> 32) as usize, (packed as u64 & u64::from(u32::MAX)) as usize);
+    if length == 0 {
+      return Ok(None);
+    }
+    let mut output = vec![0; length];
+    memory.read(&store, pointer, &mut output).map_err(wasmi::Error::from)?;
+    Ok(Some(output))
+  }
+
+  fn transform(&self, export: &str, request: HttpRequest) -> Result<HttpRequest> {
+    match self.call(export, &serde_json::to_vec(&request)?)? {
+      Some(output) => Ok(serde_json::from_slice(&output)?),
+      None => Ok(request),
+    }
+  }
+}
+
+/// The error of a plugin call, spelling out an exhausted fuel budget.
+fn trap(error: wasmi::Error) -> color_eyre::eyre::Report {
+  match error.as_trap_code() {
+    Some(TrapCode::OutOfFuel) => eyre!("exceeded its budget of {FUEL} instructions"),
+    _ => error.into(),
+  }
+}
+
+/// Plugins loaded from the config, shared with the tasks sending requests.
+#[derive(Clone, Default)]
+pub struct Plugins(Arc<Vec<Plugin>>);
+
+impl Plugins {
+  /// Loads the plugins of the config, those that fail to load are logged and skipped.
+  pub fn load(configs: &BTreeMap<String, PluginConfig>, config_dir: &Path) -> Self {
+    let plugins = configs
+      .iter()
+      .filter_map(|(name, config)| {
+        Plugin::load(name, config, config_dir)
+          .map_err(|error| log::error!("Failed to load the `{name}` plugin: {error:?}"))
+          .ok()
+      })
+      .collect();
+    Self(Arc::new(plugins))
+  }
+
+  /// Runs the request mutators, then the signers of the given auth schemes.
+  pub fn prepare(&self, mut request: HttpRequest, auth_schemes: &[String]) -> Result<HttpRequest> {
+    // the Digest credentials are not handed to plugins
+    let digest = request.digest.clone();
+    for plugin in self.0.iter().filter(|plugin| plugin.exports(MUTATE)) {
+      request = plugin.transform(MUTATE, request).map_err(|error| eyre!("Plugin `{}`: {error}", plugin.name))?;
+    }
+    let signers = self.0.iter().filter(|plugin| {
+      plugin.exports(SIGN) && plugin.config.auth_schemes.iter().any(|scheme| auth_schemes.contains(scheme))
+    });
+    for plugin in signers {
+      request = plugin.transform(SIGN, request).map_err(|error| eyre!("Plugin `{}`: {error}", plugin.name))?;
+    }
+    request.digest = digest;
+    Ok(request)
+  }
+
+  /// The body as rendered by the first plugin handling the response content type.
+  pub fn render(&self, response: &HttpResponse) -> Option<String> {
+    let content_type = response.header("Content-Type").unwrap_or_default();
+    let plugin = self.0.iter().find(|plugin| {
+      plugin.exports(RENDER) && plugin.config.content_types.iter().any(|prefix| content_type.starts_with(prefix))
+    })?;
+    let rendered = serde_json::to_vec(response).map_err(Into::into).and_then(|input| plugin.call(RENDER, &input));
+    match rendered {
+      Ok(output) => output.map(|output| String::from_utf8_lossy(&output).to_string()),
+      Err(error) => Some(format!("Plugin `{}` failed to render the body: {error}", plugin.name)),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  const ECHO: &str = r#"
+    (module
+      (memory (export "memory") 1)
+      (global $next (mut i32) (i32.const 1024))
+      (data (i32.const 0) "rendered")
+      (func (export "alloc") (param $length i32) (result i32)
+        (global.get $next)
+        (global.set $next (i32.add (global.get $next) (local.get $length))))
+      (func (export "sign") (param $pointer i32) (param $length i32) (result i64)
+        (i64.or
+          (i64.shl (i64.extend_i32_u (local.get $pointer)) (i64.const 32))
+          (i64.extend_i32_u (local.get $length))))
+      (func (export "render") (param i32 i32) (result i64)
+        (i64.const 8)))
+  "#;
+
+  #[test]
+  fn test_plugins() {
+    let config = PluginConfig {
+      auth_schemes: vec![String::from("orgSignature")],
+      content_types: vec![String::from("application/x-protobuf")],
+      ..PluginConfig::default()
+    };
+    let plugin = Plugin::from_bytes("echo", &config, &wat::parse_str(ECHO).unwrap()).unwrap();
+    let plugins = Plugins(Arc::new(vec![plugin]));
+
+    let request = HttpRequest { method: String::from("GET"), path: String::from("/pets"), ..HttpRequest::default() };
+    assert_eq!(plugins.prepare(request.clone(), &[String::from("orgSignature")]).unwrap(), request);
+
+    let response = HttpResponse {
+      headers: vec![(String::from("Content-Type"), String::from("application/x-protobuf"))],
+      ..HttpResponse::default()
+    };
+    assert_eq!(plugins.render(&response).as_deref(), Some("rendered"));
+    assert_eq!(plugins.render(&HttpResponse::default()), None);
+  }
+
+  #[test]
+  fn test_plugin_limits() {
+    let looping = r#"
+      (module
+        (memory (export "memory") 1)
+        (func (export "alloc") (param i32) (result i32)
+          (i32.const 0))
+        (func (export "mutate") (param i32 i32) (result i64)
+          (loop $forever (br $forever))
+          (i64.const 0)))
+    "#;
+    let plugin = Plugin::from_bytes("looping", &PluginConfig::default(), &wat::parse_str(looping).unwrap()).unwrap();
+    let plugins = Plugins(Arc::new(vec![plugin]));
+    let error = plugins.prepare(HttpRequest::default(), &[]).unwrap_err();
+    assert_eq!(error.to_string(), format!("Plugin `looping`: exceeded its budget of {FUEL} instructions"));
+
+    let greedy = r#"
+      (module
+        (memory (export "memory") 2048)
+        (func (export "alloc") (param i32) (result i32)
+          (i32.const 0))
+        (func (export "mutate") (param i32 i32) (result i64)
+          (i64.const 0)))
+    "#;
+    let plugin = Plugin::from_bytes("greedy", &PluginConfig::default(), &wat::parse_str(greedy).unwrap()).unwrap();
+    assert!(plugin.call(MUTATE, b"{}").is_err());
+  }
+}
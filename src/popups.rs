@@ -0,0 +1,81 @@
+use color_eyre::eyre::Result;
+use crossterm::event::KeyEvent;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+
+use crate::{
+  action::Action,
+  tui::{EventResponse, Frame},
+};
+
+pub mod assertion;
+pub mod diff;
+pub mod environment;
+pub mod extraction;
+pub mod fuzz;
+pub mod help;
+pub mod lint;
+pub mod load;
+pub mod message;
+pub mod note;
+pub mod operation_compare;
+pub mod pagination;
+pub mod palette;
+pub mod payload;
+pub mod quit;
+pub mod registry;
+pub mod replay;
+pub mod response;
+pub mod response_diff;
+pub mod save;
+pub mod script;
+pub mod server;
+pub mod smoke;
+pub mod snippet;
+pub mod spec_info;
+pub mod stats;
+pub mod types;
+pub mod usage;
+pub mod workflow;
+
+pub trait Popup {
+  #[allow(unused_variables)]
+  fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
+    Ok(None)
+  }
+
+  #[allow(unused_variables)]
+  fn handle_paste_events(&mut self, text: String) -> Result<Option<EventResponse<Action>>> {
+    Ok(None)
+  }
+
+  #[allow(unused_variables)]
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    Ok(None)
+  }
+
+  /// Whether the popup changes on its own, e.g. with a running clock, and is rendered on every frame.
+  fn animating(&self) -> bool {
+    false
+  }
+
+  fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<()>;
+}
+
+pub fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+  let vertical_layout = Layout::default()
+    .direction(Direction::Vertical)
+    .constraints([
+      Constraint::Percentage((100 - percent_y) / 2),
+      Constraint::Percentage(percent_y),
+      Constraint::Percentage((100 - percent_y) / 2),
+    ])
+    .split(area);
+  Layout::default()
+    .direction(Direction::Horizontal)
+    .constraints([
+      Constraint::Percentage((100 - percent_x) / 2),
+      Constraint::Percentage(percent_x),
+      Constraint::Percentage((100 - percent_x) / 2),
+    ])
+    .split(vertical_layout[1])[1]
+}
@@ -0,0 +1,88 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+  prelude::*,
+  widgets::{block::*, *},
+};
+use tui_textarea::TextArea;
+
+use crate::{
+  action::Action,
+  popups::Popup,
+  tui::{self, EventResponse, Frame},
+};
+
+/// Edits the assertions of a saved request, one per line.
+pub struct AssertionPopup {
+  saved_index: usize,
+  name: String,
+  editor: TextArea<'static>,
+}
+
+impl AssertionPopup {
+  pub fn new(saved_index: usize, name: String, assertions: &[String]) -> Self {
+    let mut editor = TextArea::new(assertions.to_vec());
+    editor.set_cursor_line_style(Style::default());
+    editor.set_placeholder_text("$statusCode == 200");
+    Self { saved_index, name, editor }
+  }
+
+  fn assertions(&self) -> Vec<String> {
+    self.editor.lines().iter().map(|line| line.trim()).filter(|line| !line.is_empty()).map(String::from).collect()
+  }
+}
+
+impl Popup for AssertionPopup {
+  fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
+    let response = match key.code {
+      KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+        EventResponse::Stop(Action::SaveAssertions(self.saved_index, self.assertions()))
+      },
+      KeyCode::Esc => EventResponse::Stop(Action::ClosePopup),
+      _ => {
+        self.editor.input(key);
+        EventResponse::Stop(Action::Render)
+      },
+    };
+    Ok(Some(response))
+  }
+
+  fn handle_paste_events(&mut self, text: String) -> Result<Option<EventResponse<Action>>> {
+    self.editor.insert_str(tui::pasted(&text, true));
+    Ok(Some(EventResponse::Stop(Action::Render)))
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    let inner = area.inner(&Margin { horizontal: 1, vertical: 1 });
+    let layout = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints([Constraint::Fill(1), Constraint::Max(3)])
+      .split(inner);
+
+    frame.render_widget(self.editor.widget(), layout[0]);
+    frame.render_widget(
+      Paragraph::new(vec![
+        Line::from("$statusCode, header:<name> or a JSONPath such as $.items, followed by `length` to count"),
+        Line::from("==, !=, <, <=, >, >= against a number or text, nothing to check that the value exists"),
+      ])
+      .style(Style::default().dim())
+      .block(Block::default().borders(Borders::TOP).border_style(Style::default().dim())),
+      layout[1],
+    );
+
+    const ARROW: &str = symbols::scrollbar::HORIZONTAL.end;
+    frame.render_widget(
+      Block::default()
+        .title(format!("Assertions: {}", self.name))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double)
+        .title_bottom(
+          Line::from(format!("[ctrl-s {ARROW} save] [esc {ARROW} cancel]"))
+            .style(Style::default().dim())
+            .right_aligned(),
+        ),
+      area,
+    );
+    Ok(())
+  }
+}
@@ -0,0 +1,177 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+  prelude::*,
+  widgets::{block::*, *},
+};
+use tui_textarea::TextArea;
+
+use crate::{
+  action::Action,
+  diff::{Change, ChangeKind},
+  popups::Popup,
+  theme,
+  tui::{self, EventResponse, Frame},
+};
+
+/// Asks for a previous version of the spec, then lists the changes from it to the loaded one.
+pub struct DiffPopup {
+  editor: TextArea<'static>,
+  /// Path of the previous version and the changes, once compared.
+  compared: Option<(String, Vec<Change>)>,
+  /// Lists the breaking changes only.
+  breaking_only: bool,
+  change_index: usize,
+}
+
+impl DiffPopup {
+  pub fn new() -> Self {
+    let mut editor = TextArea::default();
+    editor.set_cursor_line_style(Style::default());
+    editor.set_placeholder_text("path of the previous version, e.g. openapi.v1.yaml");
+    Self { editor, compared: None, breaking_only: false, change_index: 0 }
+  }
+
+  pub fn with_changes(path: String, changes: Vec<Change>) -> Self {
+    Self { compared: Some((path, changes)), ..Self::new() }
+  }
+
+  fn listed(&self) -> Vec<&Change> {
+    let changes = self.compared.iter().flat_map(|(_, changes)| changes.iter());
+    changes.filter(|change| !self.breaking_only || change.breaking).collect()
+  }
+
+  fn change_line(change: &Change) -> Line<'_> {
+    let (symbol, color) = match change.kind {
+      ChangeKind::Added => ("+", Color::LightGreen),
+      ChangeKind::Removed => ("-", Color::LightRed),
+      ChangeKind::Changed => ("~", Color::LightYellow),
+    };
+    let mut spans = vec![Span::styled(format!("{symbol} "), color)];
+    if change.breaking {
+      spans.push(Span::styled("breaking ", Style::default().fg(Color::LightRed).bold()));
+    }
+    spans.push(Span::raw(change.location.as_str()));
+    spans.push(Span::styled(format!("  {}", change.message), Style::default().dim()));
+    Line::from(spans)
+  }
+}
+
+impl Default for DiffPopup {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl Popup for DiffPopup {
+  fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
+    if self.compared.is_none() {
+      let response = match key.code {
+        KeyCode::Enter => {
+          let path = self.editor.lines().concat().trim().to_string();
+          match path.is_empty() {
+            true => EventResponse::Stop(Action::Render),
+            false => EventResponse::Stop(Action::DiffSpec(path)),
+          }
+        },
+        KeyCode::Esc => EventResponse::Stop(Action::ClosePopup),
+        _ => {
+          self.editor.input(key);
+          EventResponse::Stop(Action::Render)
+        },
+      };
+      return Ok(Some(response));
+    }
+    let response = match key.code {
+      KeyCode::Char('b') | KeyCode::Char('B') => {
+        self.breaking_only = !self.breaking_only;
+        self.change_index = 0;
+        EventResponse::Stop(Action::Render)
+      },
+      KeyCode::Esc | KeyCode::Char('q') => EventResponse::Stop(Action::ClosePopup),
+      _ => return Ok(None),
+    };
+    Ok(Some(response))
+  }
+
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    let listed_len = self.listed().len().max(1);
+    match action {
+      Action::Down => self.change_index = self.change_index.saturating_add(1) % listed_len,
+      Action::Up => self.change_index = self.change_index.saturating_add(listed_len - 1) % listed_len,
+      _ => {},
+    }
+    Ok(None)
+  }
+
+  fn handle_paste_events(&mut self, text: String) -> Result<Option<EventResponse<Action>>> {
+    if self.compared.is_some() {
+      return Ok(None);
+    }
+    self.editor.insert_str(tui::pasted(&text, false));
+    Ok(Some(EventResponse::Stop(Action::Render)))
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    const ARROW: &str = symbols::scrollbar::HORIZONTAL.end;
+    let inner = area.inner(&Margin { horizontal: 1, vertical: 1 });
+    let Some((path, changes)) = &self.compared else {
+      let input = Block::default().borders(Borders::BOTTOM).border_style(Style::default().dim());
+      let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Fill(1)])
+        .split(inner);
+      frame.render_widget(self.editor.widget(), input.inner(layout[0]));
+      frame.render_widget(input, layout[0]);
+      frame.render_widget(
+        Block::default().title("Diff").borders(Borders::ALL).border_type(BorderType::Double).title_bottom(
+          Line::from(format!("[enter {ARROW} compare] [esc {ARROW} cancel]"))
+            .style(Style::default().dim())
+            .right_aligned(),
+        ),
+        area,
+      );
+      return Ok(());
+    };
+
+    let layout = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints([Constraint::Max(2), Constraint::Fill(1)])
+      .split(inner);
+    let breaking = changes.iter().filter(|change| change.breaking).count();
+    frame.render_widget(
+      Paragraph::new(Line::from(vec![
+        Span::styled(format!("{path} {ARROW} loaded spec"), Style::default().bold()),
+        Span::styled(format!("  {} changes  ", changes.len()), Style::default().dim()),
+        Span::styled(format!("{breaking} breaking"), match breaking {
+          0 => Style::default().fg(Color::LightGreen),
+          _ => Style::default().fg(Color::LightRed),
+        }),
+      ])),
+      layout[0],
+    );
+    let items: Vec<Line<'_>> = self.listed().into_iter().map(Self::change_line).collect();
+    let mut list_state = ListState::default().with_selected((!items.is_empty()).then_some(self.change_index));
+    frame.render_stateful_widget(
+      List::new(items)
+        .highlight_symbol(symbols::scrollbar::HORIZONTAL.end)
+        .highlight_spacing(HighlightSpacing::Always)
+        .highlight_style(theme::current().selection),
+      layout[1],
+      &mut list_state,
+    );
+    let filter = match self.breaking_only {
+      true => "all changes",
+      false => "breaking only",
+    };
+    frame.render_widget(
+      Block::default().title("Diff").borders(Borders::ALL).border_type(BorderType::Double).title_bottom(
+        Line::from(format!("[j/k {ARROW} change] [b {ARROW} {filter}] [esc {ARROW} close]"))
+          .style(Style::default().dim())
+          .right_aligned(),
+      ),
+      area,
+    );
+    Ok(())
+  }
+}
@@ -0,0 +1,109 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+  prelude::*,
+  widgets::{block::*, *},
+};
+
+use crate::{
+  action::Action,
+  popups::Popup,
+  tui::{EventResponse, Frame},
+  workspace::Environment,
+};
+
+/// Picks the active environment, the first entry disables variable substitution.
+pub struct EnvironmentPopup {
+  environments: Vec<Environment>,
+  /// Index into `environments` plus one, `0` is no environment.
+  index: usize,
+}
+
+impl EnvironmentPopup {
+  pub fn new(environments: Vec<Environment>, active: Option<&str>) -> Self {
+    let index = active
+      .and_then(|active| environments.iter().position(|environment| environment.name == active))
+      .map_or(0, |index| index + 1);
+    Self { environments, index }
+  }
+
+  fn selected(&self) -> Option<&Environment> {
+    self.index.checked_sub(1).and_then(|index| self.environments.get(index))
+  }
+}
+
+impl Popup for EnvironmentPopup {
+  fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
+    let response = match key.code {
+      KeyCode::Enter => {
+        EventResponse::Stop(Action::SelectEnvironment(self.selected().map(|environment| environment.name.clone())))
+      },
+      KeyCode::Esc | KeyCode::Char('q') => EventResponse::Stop(Action::ClosePopup),
+      _ => return Ok(None),
+    };
+    Ok(Some(response))
+  }
+
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    let len = self.environments.len() + 1;
+    match action {
+      Action::Down => self.index = self.index.saturating_add(1) % len,
+      Action::Up => self.index = self.index.saturating_add(len - 1) % len,
+      _ => {},
+    }
+    Ok(None)
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    let inner = area.inner(&Margin { horizontal: 1, vertical: 1 });
+    let layout = Layout::default()
+      .direction(Direction::Horizontal)
+      .constraints([Constraint::Fill(1), Constraint::Fill(2)])
+      .split(inner);
+
+    let names = std::iter::once("No environment").chain(self.environments.iter().map(|env| env.name.as_str()));
+    let mut list_state = ListState::default().with_selected(Some(self.index));
+    frame.render_stateful_widget(
+      List::new(names)
+        .highlight_symbol(symbols::scrollbar::HORIZONTAL.end)
+        .highlight_spacing(HighlightSpacing::Always)
+        .block(Block::default().borders(Borders::RIGHT).border_style(Style::default().dim())),
+      layout[0],
+      &mut list_state,
+    );
+
+    let variables: Vec<Line> = self
+      .selected()
+      .map(|environment| {
+        let mut lines: Vec<Line> = environment
+          .variables
+          .iter()
+          .map(|(name, value)| {
+            Line::from(vec![Span::styled(format!("{name} = "), Style::default().dim()), Span::raw(value.as_str())])
+          })
+          .collect();
+        let network = environment.network.as_ref().map(|network| network.settings()).unwrap_or_default();
+        if !network.is_empty() {
+          lines.push(Line::default());
+          lines.push(Line::styled("Network", Style::default().bold()));
+          lines.extend(network.into_iter().map(|(name, value)| {
+            Line::from(vec![Span::styled(format!("{name}: "), Style::default().dim()), Span::raw(value)])
+          }));
+        }
+        lines
+      })
+      .unwrap_or_default();
+    frame.render_widget(Paragraph::new(variables).block(Block::default().padding(Padding::horizontal(1))), layout[1]);
+
+    const ARROW: &str = symbols::scrollbar::HORIZONTAL.end;
+    frame.render_widget(
+      Block::default().title("Environments").borders(Borders::ALL).border_type(BorderType::Double).title_bottom(
+        Line::from(format!("[j/k {ARROW} environment] [enter {ARROW} activate] [esc {ARROW} close]"))
+          .style(Style::default().dim())
+          .right_aligned(),
+      ),
+      area,
+    );
+    Ok(())
+  }
+}
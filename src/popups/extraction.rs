@@ -0,0 +1,156 @@
+use std::collections::BTreeMap;
+
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+  prelude::*,
+  widgets::{block::*, *},
+};
+use tui_textarea::TextArea;
+
+use crate::{
+  action::Action,
+  popups::Popup,
+  tui::{self, EventResponse, Frame},
+  workspace::{Extraction, OperationKey},
+};
+
+/// Lists the extraction rules of an operation, which copy response values into variables for the next requests.
+pub struct ExtractionPopup {
+  operation: OperationKey,
+  extractions: Vec<Extraction>,
+  /// Current values of the extracted variables.
+  values: BTreeMap<String, String>,
+  index: usize,
+  /// New rule being written as `variable = source`, `None` when not adding one.
+  editor: Option<TextArea<'static>>,
+}
+
+impl ExtractionPopup {
+  pub fn new(operation: OperationKey, extractions: Vec<Extraction>, values: BTreeMap<String, String>) -> Self {
+    Self { operation, extractions, values, index: 0, editor: None }
+  }
+
+  fn parse(&self, line: &str) -> Option<Extraction> {
+    let (variable, source) = line.split_once('=')?;
+    let (variable, source) = (variable.trim(), source.trim());
+    if variable.is_empty() || source.is_empty() {
+      return None;
+    }
+    Some(Extraction { operation: self.operation.clone(), variable: variable.to_string(), source: source.to_string() })
+  }
+}
+
+impl Popup for ExtractionPopup {
+  fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
+    if let Some(editor) = self.editor.as_mut() {
+      match key.code {
+        KeyCode::Enter => {
+          let line = editor.lines().concat();
+          self.editor = None;
+          if let Some(extraction) = self.parse(&line) {
+            return Ok(Some(EventResponse::Stop(Action::AddExtraction(extraction))));
+          }
+        },
+        KeyCode::Esc => self.editor = None,
+        _ => {
+          editor.input(key);
+        },
+      }
+      return Ok(Some(EventResponse::Stop(Action::Render)));
+    }
+    let response = match key.code {
+      KeyCode::Char('a') => {
+        let mut editor = TextArea::default();
+        editor.set_cursor_line_style(Style::default());
+        editor.set_placeholder_text("created_id = $.id");
+        self.editor = Some(editor);
+        EventResponse::Stop(Action::Render)
+      },
+      KeyCode::Char('d') => {
+        let Some(extraction) = self.extractions.get(self.index) else {
+          return Ok(None);
+        };
+        EventResponse::Stop(Action::RemoveExtraction(extraction.clone()))
+      },
+      KeyCode::Esc | KeyCode::Char('q') => EventResponse::Stop(Action::ClosePopup),
+      _ => return Ok(None),
+    };
+    Ok(Some(response))
+  }
+
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    let len = self.extractions.len().max(1);
+    match action {
+      Action::Down => self.index = self.index.saturating_add(1) % len,
+      Action::Up => self.index = self.index.saturating_add(len - 1) % len,
+      _ => {},
+    }
+    Ok(None)
+  }
+
+  fn handle_paste_events(&mut self, text: String) -> Result<Option<EventResponse<Action>>> {
+    let Some(editor) = self.editor.as_mut() else {
+      return Ok(None);
+    };
+    editor.insert_str(tui::pasted(&text, false));
+    Ok(Some(EventResponse::Stop(Action::Render)))
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    let inner = area.inner(&Margin { horizontal: 1, vertical: 1 });
+    let layout = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints([Constraint::Max(2), Constraint::Fill(1), Constraint::Max(2)])
+      .split(inner);
+
+    frame.render_widget(
+      Paragraph::new(Line::from(vec![
+        Span::styled("Responses of ", Style::default().dim()),
+        Span::raw(self.operation.to_string()),
+        Span::styled(" set the variables below, use them as {{variable}}", Style::default().dim()),
+      ])),
+      layout[0],
+    );
+
+    let items = self.extractions.iter().map(|extraction| {
+      let value = match self.values.get(&extraction.variable) {
+        Some(value) => Span::styled(format!("  = {value}"), Style::default().fg(Color::LightGreen)),
+        None => Span::styled("  not extracted yet", Style::default().dim().italic()),
+      };
+      Line::from(vec![
+        Span::raw(extraction.variable.as_str()),
+        Span::styled(format!(" {} ", symbols::line::VERTICAL), Style::default().dim()),
+        Span::styled(extraction.source.as_str(), Style::default().fg(Color::LightCyan)),
+        value,
+      ])
+    });
+    let mut list_state = ListState::default().with_selected((!self.extractions.is_empty()).then_some(self.index));
+    frame.render_stateful_widget(
+      List::new(items).highlight_symbol(symbols::scrollbar::HORIZONTAL.end).highlight_spacing(HighlightSpacing::Always),
+      layout[1],
+      &mut list_state,
+    );
+
+    if let Some(editor) = self.editor.as_ref() {
+      let input = Block::default().borders(Borders::TOP).border_style(Style::default().dim());
+      frame.render_widget(editor.widget(), input.inner(layout[2]));
+      frame.render_widget(input, layout[2]);
+    }
+
+    const ARROW: &str = symbols::scrollbar::HORIZONTAL.end;
+    let hints = match self.editor.is_some() {
+      true => format!("[variable = $.json.path | header:Name] [enter {ARROW} add] [esc {ARROW} cancel]"),
+      false => format!("[j/k {ARROW} rule] [a {ARROW} add] [d {ARROW} delete] [esc {ARROW} close]"),
+    };
+    frame.render_widget(
+      Block::default()
+        .title("Extract Variables")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double)
+        .title_bottom(Line::from(hints).style(Style::default().dim()).right_aligned()),
+      area,
+    );
+    Ok(())
+  }
+}
@@ -0,0 +1,197 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+  prelude::*,
+  widgets::{block::*, *},
+};
+
+use crate::{
+  action::Action,
+  fuzz::{Case, CaseKind, CaseResult},
+  popups::Popup,
+  report::{TestCase, TestReport, TestSuite},
+  tui::{EventResponse, Frame},
+  workspace::OperationKey,
+};
+
+/// Fires the fuzz cases of an operation and lists those the server failed or answered outside of its spec.
+pub struct FuzzPopup {
+  operation: OperationKey,
+  cases: Vec<Case>,
+  results: Vec<Option<CaseResult>>,
+  started: bool,
+  /// Lists every case instead of the failed ones only.
+  show_all: bool,
+  case_index: usize,
+}
+
+impl FuzzPopup {
+  pub fn new(operation: OperationKey, cases: Vec<Case>) -> Self {
+    let results = vec![None; cases.len()];
+    Self { operation, cases, results, started: false, show_all: true, case_index: 0 }
+  }
+
+  fn running(&self) -> bool {
+    self.started && self.results.iter().any(Option::is_none)
+  }
+
+  /// Indexes of the listed cases.
+  fn listed(&self) -> Vec<usize> {
+    (0..self.cases.len())
+      .filter(|index| self.show_all || self.results[*index].as_ref().is_some_and(CaseResult::failed))
+      .collect()
+  }
+
+  fn report(&self) -> TestReport {
+    let name = format!("fuzz {} {}", self.operation.method, self.operation.path);
+    let cases = self
+      .cases
+      .iter()
+      .zip(self.results.iter())
+      .map(|(case, result)| TestCase::from_case(case.description.clone(), result.as_ref(), false))
+      .collect();
+    TestReport { name: name.replace(' ', "-"), suites: vec![TestSuite { name, cases }] }
+  }
+
+  fn summary(&self) -> Line<'_> {
+    let results: Vec<&CaseResult> = self.results.iter().flatten().collect();
+    let server_errors = results.iter().filter(|result| result.server_error()).count();
+    let violations = results.iter().filter(|result| result.failed() && !result.server_error()).count();
+    let dim = Style::default().dim();
+    Line::from(vec![
+      Span::styled(format!("{} {}", self.operation.method, self.operation.path), Style::default().bold()),
+      Span::styled(format!("  {}/{} sent  ", results.len(), self.cases.len()), dim),
+      Span::styled(format!("{server_errors} server errors"), match server_errors {
+        0 => dim,
+        _ => Style::default().fg(Color::LightRed),
+      }),
+      Span::styled("  ", dim),
+      Span::styled(format!("{violations} other findings"), match violations {
+        0 => dim,
+        _ => Style::default().fg(Color::LightYellow),
+      }),
+    ])
+  }
+
+  fn case_line(&self, index: usize) -> Line<'_> {
+    let case = &self.cases[index];
+    let (symbol, style) = match &self.results[index] {
+      None if self.running() => ("…", Style::default().fg(Color::LightBlue)),
+      None => ("·", Style::default().dim()),
+      Some(result) if result.server_error() => ("✗", Style::default().fg(Color::LightRed)),
+      Some(result) if result.failed() => ("!", Style::default().fg(Color::LightYellow)),
+      Some(_) => ("✓", Style::default().fg(Color::LightGreen)),
+    };
+    let kind = match case.kind {
+      CaseKind::Valid => "valid",
+      CaseKind::Boundary => "boundary",
+      CaseKind::Invalid => "invalid",
+    };
+    let mut spans = vec![
+      Span::styled(format!("{symbol} "), style),
+      Span::styled(format!("{kind:9}"), Style::default().dim()),
+      Span::raw(case.description.as_str()),
+    ];
+    if let Some(exchange) = self.results[index].as_ref().and_then(|result| result.exchange.as_ref()) {
+      spans.push(Span::styled(format!("  {}", exchange.response.status), style));
+      spans.push(Span::styled(format!("  {} ms", exchange.elapsed_ms), Style::default().dim()));
+    }
+    Line::from(spans)
+  }
+
+  fn detail_lines(&self) -> Vec<Line<'_>> {
+    let Some(Some(result)) = self.listed().get(self.case_index).map(|index| &self.results[*index]) else {
+      return vec![];
+    };
+    let mut lines = vec![];
+    let problem = Style::default().fg(Color::LightRed);
+    lines.extend(result.error.iter().map(|error| Line::styled(error.as_str(), problem)));
+    lines.extend(result.problems.iter().map(|found| Line::styled(found.as_str(), problem)));
+    if let Some(exchange) = &result.exchange {
+      lines.push(Line::styled(
+        format!("{} {}", exchange.request.method, exchange.request.full_url()),
+        Style::default().dim(),
+      ));
+      if let Some(body) = exchange.request.body.as_ref().filter(|body| !body.is_empty()) {
+        lines.extend(body.lines().map(|line| Line::styled(line.to_string(), Style::default().dim())));
+      }
+      lines.push(Line::default());
+      lines.extend(exchange.response.body.lines().map(|line| Line::raw(line.to_string())));
+    }
+    lines
+  }
+}
+
+impl Popup for FuzzPopup {
+  fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
+    let response = match key.code {
+      KeyCode::Enter if !self.running() && !self.cases.is_empty() => {
+        self.results = vec![None; self.cases.len()];
+        self.started = true;
+        EventResponse::Stop(Action::RunFuzz)
+      },
+      KeyCode::Char('a') | KeyCode::Char('A') => {
+        self.show_all = !self.show_all;
+        self.case_index = 0;
+        EventResponse::Stop(Action::Render)
+      },
+      KeyCode::Char('e') if self.started => EventResponse::Stop(Action::ExportReport(Box::new(self.report()))),
+      KeyCode::Esc | KeyCode::Char('q') => EventResponse::Stop(Action::ClosePopup),
+      _ => return Ok(None),
+    };
+    Ok(Some(response))
+  }
+
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    let listed_len = self.listed().len().max(1);
+    match action {
+      Action::Down => self.case_index = self.case_index.saturating_add(1) % listed_len,
+      Action::Up => self.case_index = self.case_index.saturating_add(listed_len - 1) % listed_len,
+      Action::FuzzCaseFinished(index, result) if index < self.results.len() => {
+        self.results[index] = Some(*result);
+      },
+      _ => {},
+    }
+    Ok(None)
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    let inner = area.inner(&Margin { horizontal: 1, vertical: 1 });
+    let layout = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints([Constraint::Max(2), Constraint::Fill(1), Constraint::Fill(1)])
+      .split(inner);
+
+    frame.render_widget(Paragraph::new(self.summary()), layout[0]);
+    let items: Vec<Line<'_>> = self.listed().into_iter().map(|index| self.case_line(index)).collect();
+    let mut list_state = ListState::default().with_selected((!items.is_empty()).then_some(self.case_index));
+    frame.render_stateful_widget(
+      List::new(items).highlight_symbol(symbols::scrollbar::HORIZONTAL.end).highlight_spacing(HighlightSpacing::Always),
+      layout[1],
+      &mut list_state,
+    );
+    frame.render_widget(
+      Paragraph::new(self.detail_lines())
+        .wrap(Wrap { trim: false })
+        .block(Block::default().borders(Borders::TOP).border_style(Style::default().dim())),
+      layout[2],
+    );
+
+    const ARROW: &str = symbols::scrollbar::HORIZONTAL.end;
+    let filter = match self.show_all {
+      true => "findings only",
+      false => "all cases",
+    };
+    frame.render_widget(
+      Block::default().title("Fuzz").borders(Borders::ALL).border_type(BorderType::Double).title_bottom(
+        Line::from(format!(
+          "[j/k {ARROW} case] [a {ARROW} {filter}] [enter {ARROW} run] [e {ARROW} export] [esc {ARROW} close]"
+        ))
+        .style(Style::default().dim())
+        .right_aligned(),
+      ),
+      area,
+    );
+    Ok(())
+  }
+}
@@ -0,0 +1,80 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+  prelude::*,
+  widgets::{block::*, *},
+};
+
+use crate::{
+  action::Action,
+  popups::Popup,
+  tui::{EventResponse, Frame},
+};
+
+/// Keys of a scope of the keymap, described.
+pub struct HelpSection {
+  pub title: String,
+  /// Keys bound to each action, and what the action does.
+  pub bindings: Vec<(Vec<String>, String)>,
+}
+
+/// Lists the keys bound in the focused pane and on the page, as configured.
+pub struct HelpPopup {
+  sections: Vec<HelpSection>,
+  line_offset: usize,
+}
+
+impl HelpPopup {
+  pub fn new(sections: Vec<HelpSection>) -> Self {
+    Self { sections, line_offset: 0 }
+  }
+
+  fn rows(&self) -> Vec<Row<'_>> {
+    let mut rows = vec![];
+    for section in self.sections.iter().filter(|section| !section.bindings.is_empty()) {
+      if !rows.is_empty() {
+        rows.push(Row::new(vec![Cell::default()]));
+      }
+      rows.push(Row::new(vec![Cell::from(section.title.as_str()).style(Style::default().bold().underlined())]));
+      rows.extend(section.bindings.iter().map(|(keys, description)| {
+        Row::new(vec![
+          Cell::from(keys.join(", ")).style(Style::default().fg(Color::LightCyan)),
+          Cell::from(description.as_str()),
+        ])
+      }));
+    }
+    rows
+  }
+}
+
+impl Popup for HelpPopup {
+  fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
+    match key.code {
+      KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('?') => Ok(Some(EventResponse::Stop(Action::ClosePopup))),
+      _ => Ok(None),
+    }
+  }
+
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    match action {
+      Action::Down => self.line_offset = self.line_offset.saturating_add(1).min(self.rows().len().saturating_sub(1)),
+      Action::Up => self.line_offset = self.line_offset.saturating_sub(1),
+      _ => {},
+    }
+    Ok(None)
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    const ARROW: &str = symbols::scrollbar::HORIZONTAL.end;
+    let inner = area.inner(&Margin { horizontal: 2, vertical: 1 });
+    let rows: Vec<Row<'_>> = self.rows().into_iter().skip(self.line_offset).collect();
+    frame.render_widget(Table::new(rows, [Constraint::Max(24), Constraint::Fill(1)]).column_spacing(2), inner);
+    frame.render_widget(
+      Block::default().title("Keys").borders(Borders::ALL).border_type(BorderType::Double).title_bottom(
+        Line::from(format!("[j/k {ARROW} scroll] [esc {ARROW} close]")).style(Style::default().dim()).right_aligned(),
+      ),
+      area,
+    );
+    Ok(())
+  }
+}
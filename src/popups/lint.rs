@@ -0,0 +1,118 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+  prelude::*,
+  widgets::{block::*, *},
+};
+
+use crate::{
+  action::Action,
+  lint::{Finding, Severity},
+  popups::Popup,
+  theme,
+  tui::{EventResponse, Frame},
+};
+
+/// Lists the lint findings of the spec, enter jumps to the operation of the selected finding.
+pub struct LintPopup {
+  findings: Vec<Finding>,
+  finding_index: usize,
+}
+
+impl LintPopup {
+  pub fn new(findings: Vec<Finding>) -> Self {
+    Self { findings, finding_index: 0 }
+  }
+
+  fn severity_style(severity: Severity) -> Style {
+    match severity {
+      Severity::Error => Style::default().fg(Color::LightRed),
+      Severity::Warning => Style::default().fg(Color::LightYellow),
+      Severity::Info => Style::default().fg(Color::LightBlue),
+    }
+  }
+
+  fn summary(&self) -> Line<'_> {
+    let count = |severity| self.findings.iter().filter(|finding| finding.severity == severity).count();
+    if self.findings.is_empty() {
+      return Line::styled("No findings, the spec follows every rule.", Style::default().fg(Color::LightGreen));
+    }
+    Line::from(vec![
+      Span::styled(format!("{} errors", count(Severity::Error)), Self::severity_style(Severity::Error)),
+      Span::styled("  ", Style::default()),
+      Span::styled(format!("{} warnings", count(Severity::Warning)), Self::severity_style(Severity::Warning)),
+      Span::styled("  ", Style::default()),
+      Span::styled(format!("{} hints", count(Severity::Info)), Self::severity_style(Severity::Info)),
+    ])
+  }
+
+  fn finding_line(finding: &Finding) -> Line<'_> {
+    let symbol = match finding.severity {
+      Severity::Error => "✗",
+      Severity::Warning => "!",
+      Severity::Info => "i",
+    };
+    Line::from(vec![
+      Span::styled(format!("{symbol} "), Self::severity_style(finding.severity)),
+      Span::raw(finding.message.as_str()),
+      Span::styled(format!("  {}", finding.rule), Style::default().dim()),
+    ])
+  }
+}
+
+impl Popup for LintPopup {
+  fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
+    let response = match key.code {
+      KeyCode::Enter => {
+        match self.findings.get(self.finding_index).and_then(|finding| finding.operation.clone()) {
+          Some(operation) => EventResponse::Stop(Action::SelectOperation(operation)),
+          None => return Ok(Some(EventResponse::Stop(Action::Render))),
+        }
+      },
+      KeyCode::Esc | KeyCode::Char('q') => EventResponse::Stop(Action::ClosePopup),
+      _ => return Ok(None),
+    };
+    Ok(Some(response))
+  }
+
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    let findings_len = self.findings.len().max(1);
+    match action {
+      Action::Down => self.finding_index = self.finding_index.saturating_add(1) % findings_len,
+      Action::Up => self.finding_index = self.finding_index.saturating_add(findings_len - 1) % findings_len,
+      _ => {},
+    }
+    Ok(None)
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    let inner = area.inner(&Margin { horizontal: 1, vertical: 1 });
+    let layout = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints([Constraint::Max(2), Constraint::Fill(1)])
+      .split(inner);
+
+    frame.render_widget(Paragraph::new(self.summary()), layout[0]);
+    let items: Vec<Line<'_>> = self.findings.iter().map(Self::finding_line).collect();
+    let mut list_state = ListState::default().with_selected((!items.is_empty()).then_some(self.finding_index));
+    frame.render_stateful_widget(
+      List::new(items)
+        .highlight_symbol(symbols::scrollbar::HORIZONTAL.end)
+        .highlight_spacing(HighlightSpacing::Always)
+        .highlight_style(theme::current().selection),
+      layout[1],
+      &mut list_state,
+    );
+
+    const ARROW: &str = symbols::scrollbar::HORIZONTAL.end;
+    frame.render_widget(
+      Block::default().title("Diagnostics").borders(Borders::ALL).border_type(BorderType::Double).title_bottom(
+        Line::from(format!("[j/k {ARROW} finding] [enter {ARROW} go to operation] [esc {ARROW} close]"))
+          .style(Style::default().dim())
+          .right_aligned(),
+      ),
+      area,
+    );
+    Ok(())
+  }
+}
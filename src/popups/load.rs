@@ -0,0 +1,219 @@
+use std::time::{Duration, Instant};
+
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+  prelude::*,
+  widgets::{block::*, *},
+};
+
+use crate::{
+  action::Action,
+  load::{Sample, Summary, DEFAULT_CONCURRENCY, DEFAULT_REQUESTS},
+  popups::Popup,
+  theme,
+  tui::{EventResponse, Frame},
+};
+
+/// Largest number of requests or concurrent requests that can be typed.
+const MAX_COUNT: usize = 100_000;
+
+/// Fires the request of the active operation many times and shows the throughput, latencies and errors as the
+/// responses come in.
+pub struct LoadPopup {
+  /// Method and path of the request, as shown in the title.
+  target: String,
+  requests: usize,
+  concurrency: usize,
+  /// Edits the concurrency instead of the number of requests.
+  concurrency_selected: bool,
+  samples: Vec<Sample>,
+  started_at: Option<Instant>,
+  /// Time the last sample was received at, once every request was sent.
+  finished_at: Option<Instant>,
+}
+
+impl LoadPopup {
+  pub fn new(target: String) -> Self {
+    Self {
+      target,
+      requests: DEFAULT_REQUESTS,
+      concurrency: DEFAULT_CONCURRENCY,
+      concurrency_selected: false,
+      samples: Vec::default(),
+      started_at: None,
+      finished_at: None,
+    }
+  }
+
+  fn running(&self) -> bool {
+    self.started_at.is_some() && self.finished_at.is_none()
+  }
+
+  fn elapsed(&self) -> Duration {
+    match (self.started_at, self.finished_at) {
+      (Some(started_at), Some(finished_at)) => finished_at - started_at,
+      (Some(started_at), None) => started_at.elapsed(),
+      _ => Duration::ZERO,
+    }
+  }
+
+  fn selected_count(&mut self) -> &mut usize {
+    match self.concurrency_selected {
+      true => &mut self.concurrency,
+      false => &mut self.requests,
+    }
+  }
+
+  fn setting_spans(&self) -> Vec<Span<'_>> {
+    let style = |selected: bool| {
+      match selected && !self.running() {
+        true => theme::current().selection,
+        false => Style::default().bold(),
+      }
+    };
+    vec![
+      Span::styled("Requests ", Style::default().dim()),
+      Span::styled(format!(" {} ", self.requests), style(!self.concurrency_selected)),
+      Span::styled("   Concurrency ", Style::default().dim()),
+      Span::styled(format!(" {} ", self.concurrency), style(self.concurrency_selected)),
+    ]
+  }
+
+  fn summary_lines(&self, summary: &Summary) -> Vec<Line<'_>> {
+    let row = |label: &str, value: String| {
+      Line::from(vec![Span::styled(format!("{label:14}"), Style::default().dim()), Span::raw(value)])
+    };
+    let error_style = match summary.errors {
+      0 => Style::default().fg(Color::LightGreen),
+      _ => Style::default().fg(Color::LightRed),
+    };
+    let mut lines = vec![
+      row("Throughput", format!("{:.1} req/s", summary.rps)),
+      Line::from(vec![
+        Span::styled(format!("{:14}", "Errors"), Style::default().dim()),
+        Span::styled(format!("{} ({:.1}%)", summary.errors, summary.error_rate * 100.0), error_style),
+      ]),
+      row("Elapsed", format!("{:.1} s", self.elapsed().as_secs_f64())),
+      Line::default(),
+      row("Latency min", format!("{} ms", summary.min_ms)),
+      row("        mean", format!("{} ms", summary.mean_ms)),
+      row("        p50", format!("{} ms", summary.p50_ms)),
+      row("        p90", format!("{} ms", summary.p90_ms)),
+      row("        p95", format!("{} ms", summary.p95_ms)),
+      row("        p99", format!("{} ms", summary.p99_ms)),
+      row("        max", format!("{} ms", summary.max_ms)),
+      Line::default(),
+    ];
+    lines.extend(summary.statuses.iter().map(|(status, count)| {
+      Line::from(vec![
+        Span::styled(format!("{:14}", format!("Status {status}")), theme::current().status_code(*status)),
+        Span::raw(count.to_string()),
+      ])
+    }));
+    if let Some(error) = self.samples.iter().rev().find_map(|sample| sample.error.as_ref()) {
+      lines.push(Line::styled(format!("Last error: {error}"), Style::default().fg(Color::LightRed)));
+    }
+    lines
+  }
+}
+
+impl Popup for LoadPopup {
+  fn animating(&self) -> bool {
+    self.running()
+  }
+
+  fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
+    let response = match key.code {
+      KeyCode::Enter if !self.running() && self.requests > 0 => {
+        self.samples.clear();
+        self.started_at = Some(Instant::now());
+        self.finished_at = None;
+        EventResponse::Stop(Action::RunLoadTest(self.requests, self.concurrency.max(1)))
+      },
+      KeyCode::Tab | KeyCode::BackTab if !self.running() => {
+        self.concurrency_selected = !self.concurrency_selected;
+        EventResponse::Stop(Action::Render)
+      },
+      KeyCode::Char(digit @ '0'..='9') if !self.running() => {
+        let count = self.selected_count();
+        *count = (*count * 10 + digit.to_digit(10).unwrap_or_default() as usize).min(MAX_COUNT);
+        EventResponse::Stop(Action::Render)
+      },
+      KeyCode::Backspace if !self.running() => {
+        *self.selected_count() /= 10;
+        EventResponse::Stop(Action::Render)
+      },
+      KeyCode::Esc | KeyCode::Char('q') => EventResponse::Stop(Action::ClosePopup),
+      _ => return Ok(None),
+    };
+    Ok(Some(response))
+  }
+
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    if let Action::LoadSample(sample) = action {
+      if self.running() {
+        self.samples.push(*sample);
+        if self.samples.len() >= self.requests {
+          self.finished_at = Some(Instant::now());
+        }
+      }
+    }
+    Ok(None)
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    let inner = area.inner(&Margin { horizontal: 2, vertical: 1 });
+    let layout = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints([Constraint::Length(2), Constraint::Length(2), Constraint::Fill(1), Constraint::Length(4)])
+      .split(inner);
+
+    frame.render_widget(Paragraph::new(Line::from(self.setting_spans())), layout[0]);
+    let progress = self.samples.len() as f64 / self.requests.max(1) as f64;
+    frame.render_widget(
+      Gauge::default()
+        .gauge_style(Style::default().fg(Color::LightBlue))
+        .label(format!("{}/{}", self.samples.len(), self.requests))
+        .ratio(progress.min(1.0)),
+      Rect { height: 1, ..layout[1] },
+    );
+    let summary = Summary::of(&self.samples, self.elapsed());
+    match self.started_at {
+      Some(_) => frame.render_widget(Paragraph::new(self.summary_lines(&summary)), layout[2]),
+      None => {
+        frame.render_widget(
+          Paragraph::new("Type the number of requests, tab to the concurrency, and press enter to fire them.")
+            .style(Style::default().dim()),
+          layout[2],
+        )
+      },
+    }
+    let latencies: Vec<u64> = self.samples.iter().map(|sample| sample.elapsed_ms).collect();
+    let shown = latencies.len().saturating_sub(usize::from(layout[3].width));
+    frame.render_widget(
+      Sparkline::default()
+        .block(Block::default().borders(Borders::TOP).border_style(Style::default().dim()).title("Latency"))
+        .style(Style::default().fg(Color::LightBlue))
+        .data(&latencies[shown..]),
+      layout[3],
+    );
+
+    const ARROW: &str = symbols::scrollbar::HORIZONTAL.end;
+    frame.render_widget(
+      Block::default()
+        .title(format!("Load Test: {}", self.target))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double)
+        .title_bottom(
+          Line::from(format!(
+            "[0-9 {ARROW} count] [tab {ARROW} requests/concurrency] [enter {ARROW} fire] [esc {ARROW} stop and close]"
+          ))
+          .style(Style::default().dim())
+          .right_aligned(),
+        ),
+      area,
+    );
+    Ok(())
+  }
+}
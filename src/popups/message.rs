@@ -0,0 +1,48 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+  prelude::*,
+  widgets::{block::*, *},
+};
+
+use crate::{
+  action::Action,
+  popups::Popup,
+  tui::{EventResponse, Frame},
+};
+
+pub struct MessagePopup {
+  title: String,
+  message: String,
+}
+
+impl MessagePopup {
+  pub fn new(title: impl Into<String>, message: impl Into<String>) -> Self {
+    Self { title: title.into(), message: message.into() }
+  }
+}
+
+impl Popup for MessagePopup {
+  fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
+    match key.code {
+      KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => Ok(Some(EventResponse::Stop(Action::ClosePopup))),
+      _ => Ok(None),
+    }
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    const ARROW: &str = symbols::scrollbar::HORIZONTAL.end;
+    frame.render_widget(
+      Paragraph::new(self.message.as_str()).wrap(Wrap { trim: false }).block(
+        Block::default()
+          .title(self.title.as_str())
+          .borders(Borders::ALL)
+          .border_type(BorderType::Double)
+          .padding(Padding::uniform(1))
+          .title_bottom(Line::from(format!("[enter {ARROW} close]")).style(Style::default().dim()).right_aligned()),
+      ),
+      area,
+    );
+    Ok(())
+  }
+}
@@ -0,0 +1,68 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+  prelude::*,
+  widgets::{block::*, *},
+};
+use tui_textarea::TextArea;
+
+use crate::{
+  action::Action,
+  popups::Popup,
+  tui::{self, EventResponse, Frame},
+  workspace::OperationKey,
+};
+
+/// Edits the note of an operation, saving an empty one removes it.
+pub struct NotePopup {
+  operation: OperationKey,
+  editor: TextArea<'static>,
+}
+
+impl NotePopup {
+  pub fn new(operation: OperationKey, note: Option<&str>) -> Self {
+    let mut editor = TextArea::new(note.unwrap_or_default().lines().map(String::from).collect());
+    editor.set_cursor_line_style(Style::default());
+    editor.set_placeholder_text("e.g. returns 200 with an error body on failure");
+    Self { operation, editor }
+  }
+}
+
+impl Popup for NotePopup {
+  fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
+    let response = match key.code {
+      KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+        EventResponse::Stop(Action::SaveNote(self.operation.clone(), self.editor.lines().join("\n")))
+      },
+      KeyCode::Esc => EventResponse::Stop(Action::ClosePopup),
+      _ => {
+        self.editor.input(key);
+        EventResponse::Stop(Action::Render)
+      },
+    };
+    Ok(Some(response))
+  }
+
+  fn handle_paste_events(&mut self, text: String) -> Result<Option<EventResponse<Action>>> {
+    self.editor.insert_str(tui::pasted(&text, true));
+    Ok(Some(EventResponse::Stop(Action::Render)))
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    const ARROW: &str = symbols::scrollbar::HORIZONTAL.end;
+    frame.render_widget(self.editor.widget(), area.inner(&Margin { horizontal: 1, vertical: 1 }));
+    frame.render_widget(
+      Block::default()
+        .title(format!("Note: {}", self.operation))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double)
+        .title_bottom(
+          Line::from(format!("[ctrl-s {ARROW} save] [esc {ARROW} cancel]"))
+            .style(Style::default().dim())
+            .right_aligned(),
+        ),
+      area,
+    );
+    Ok(())
+  }
+}
@@ -0,0 +1,144 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+  prelude::*,
+  widgets::{block::*, *},
+};
+
+use crate::{
+  action::Action,
+  operation_compare::{self, SECTIONS},
+  popups::Popup,
+  theme,
+  tui::{EventResponse, Frame},
+  workspace::OperationKey,
+};
+
+/// Shows two operations side by side, their parameters, request bodies, responses and security, what differs
+/// highlighted.
+pub struct OperationComparePopup {
+  left: OperationKey,
+  right: OperationKey,
+  rows: Vec<operation_compare::Row>,
+  differences_only: bool,
+  row_index: usize,
+}
+
+impl OperationComparePopup {
+  pub fn new(left: OperationKey, right: OperationKey, rows: Vec<operation_compare::Row>) -> Self {
+    Self { left, right, rows, differences_only: false, row_index: 0 }
+  }
+
+  fn shown(&self) -> Vec<&operation_compare::Row> {
+    self.rows.iter().filter(|row| !self.differences_only || row.differs()).collect()
+  }
+
+  fn operation_cell(operation: &OperationKey) -> Cell<'_> {
+    Cell::from(Line::from(vec![
+      Span::styled(format!("{} ", operation.method), theme::current().method(&operation.method)),
+      Span::styled(operation.path.as_str(), Style::default().bold()),
+    ]))
+  }
+
+  fn row<'a>(row: &'a operation_compare::Row, section: Option<usize>) -> Row<'a> {
+    let value = |value: &'a Option<String>| {
+      match value {
+        Some(value) if row.differs() => Cell::from(value.as_str()).style(Style::default().fg(Color::LightYellow)),
+        Some(value) => Cell::from(value.as_str()).style(Style::default().dim()),
+        None => Cell::from("—").style(Style::default().fg(Color::LightRed)),
+      }
+    };
+    let name = Style::default().fg(match row.differs() {
+      true => Color::White,
+      false => Color::Gray,
+    });
+    let section = section.map_or(String::default(), |section| SECTIONS[section].to_string());
+    Row::new(vec![
+      Cell::from(section).style(Style::default().fg(Color::LightBlue)),
+      Cell::from(row.name.as_str()).style(name),
+      value(&row.left),
+      value(&row.right),
+    ])
+  }
+}
+
+impl Popup for OperationComparePopup {
+  fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
+    let response = match key.code {
+      KeyCode::Char('d') => {
+        self.differences_only = !self.differences_only;
+        self.row_index = 0;
+        EventResponse::Stop(Action::Render)
+      },
+      KeyCode::Char('s') => {
+        std::mem::swap(&mut self.left, &mut self.right);
+        for row in self.rows.iter_mut() {
+          std::mem::swap(&mut row.left, &mut row.right);
+        }
+        EventResponse::Stop(Action::Render)
+      },
+      KeyCode::Esc | KeyCode::Char('q') => EventResponse::Stop(Action::ClosePopup),
+      _ => return Ok(None),
+    };
+    Ok(Some(response))
+  }
+
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    let rows_len = self.shown().len().max(1);
+    match action {
+      Action::Down => self.row_index = self.row_index.saturating_add(1) % rows_len,
+      Action::Up => self.row_index = self.row_index.saturating_add(rows_len - 1) % rows_len,
+      Action::Top => self.row_index = 0,
+      Action::Bottom => self.row_index = rows_len - 1,
+      _ => {},
+    }
+    Ok(None)
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    let inner = area.inner(&Margin { horizontal: 1, vertical: 1 });
+    let shown = self.shown();
+    let differences = self.rows.iter().filter(|row| row.differs()).count();
+    // the section is named on its first row only
+    let rows: Vec<Row<'_>> = shown
+      .iter()
+      .enumerate()
+      .map(|(index, row)| {
+        let first = index == 0 || shown[index - 1].section != row.section;
+        Self::row(row, first.then_some(row.section))
+      })
+      .collect();
+    let widths = [Constraint::Length(13), Constraint::Fill(2), Constraint::Fill(2), Constraint::Fill(2)];
+    let header = Row::new(vec![
+      Cell::from(format!("{differences} differ")).style(Style::default().dim()),
+      Cell::default(),
+      Self::operation_cell(&self.left),
+      Self::operation_cell(&self.right),
+    ]);
+    let mut table_state = TableState::default().with_selected((!rows.is_empty()).then_some(self.row_index));
+    frame.render_stateful_widget(
+      Table::new(rows, widths)
+        .header(header.bottom_margin(1))
+        .highlight_symbol(symbols::scrollbar::HORIZONTAL.end)
+        .highlight_spacing(HighlightSpacing::Always)
+        .highlight_style(theme::current().selection),
+      inner,
+      &mut table_state,
+    );
+
+    const ARROW: &str = symbols::scrollbar::HORIZONTAL.end;
+    let differences_only = match self.differences_only {
+      true => "all",
+      false => "differences only",
+    };
+    frame.render_widget(
+      Block::default().title("Compare Operations").borders(Borders::ALL).border_type(BorderType::Double).title_bottom(
+        Line::from(format!("[j/k {ARROW} scroll] [d {ARROW} {differences_only}] [s {ARROW} swap] [esc {ARROW} close]"))
+          .style(Style::default().dim())
+          .right_aligned(),
+      ),
+      area,
+    );
+    Ok(())
+  }
+}
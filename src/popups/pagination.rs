@@ -0,0 +1,128 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+  prelude::*,
+  widgets::{block::*, *},
+};
+use serde_json::Value;
+
+use crate::{
+  action::Action,
+  pagination::Page,
+  popups::Popup,
+  theme,
+  tui::{EventResponse, Frame},
+};
+
+/// Pages of a list request as they are followed, with the items of every page concatenated.
+pub struct PaginationPopup {
+  target: String,
+  pages: Vec<Page>,
+  /// The items of the pages, pretty printed as one array.
+  items: String,
+  scroll: u16,
+}
+
+impl PaginationPopup {
+  pub fn new(target: String) -> Self {
+    Self { target, pages: vec![], items: String::from("[]"), scroll: 0 }
+  }
+
+  fn item_count(&self) -> usize {
+    self.pages.iter().map(|page| page.items.len()).sum()
+  }
+
+  fn summary_line(&self) -> Line<'_> {
+    let counts = format!("{} items across {} pages", self.item_count(), self.pages.len());
+    let ended = self.pages.last().and_then(|page| page.end.as_deref().map(|end| (page.status, end)));
+    let (end, color) = match ended {
+      Some((Some(200..=299), end)) => (format!(", {end}"), Color::LightGreen),
+      Some((_, end)) => (format!(", {end}"), Color::LightRed),
+      None => (String::from(", following…"), Color::LightYellow),
+    };
+    Line::from(vec![Span::raw(counts), Span::styled(end, Style::default().fg(color))])
+  }
+
+  fn page_lines(&self) -> Vec<Line<'_>> {
+    self
+      .pages
+      .iter()
+      .enumerate()
+      .map(|(index, page)| {
+        let status = match page.status {
+          Some(status) => Span::styled(format!("{status} "), theme::current().status_code(status)),
+          None => Span::styled("✗   ", Style::default().fg(Color::LightRed)),
+        };
+        Line::from(vec![
+          Span::styled(format!("{:>3}. ", index + 1), Style::default().dim()),
+          status,
+          Span::raw(format!("{:>4} items  ", page.items.len())),
+          Span::styled(format!("{:>5} ms  ", page.elapsed_ms), Style::default().dim()),
+          Span::raw(page.url.as_str()),
+        ])
+      })
+      .collect()
+  }
+}
+
+impl Popup for PaginationPopup {
+  fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
+    match key.code {
+      KeyCode::Char('y') => Ok(Some(EventResponse::Stop(Action::Copy(self.items.clone())))),
+      KeyCode::Esc | KeyCode::Char('q') => Ok(Some(EventResponse::Stop(Action::ClosePopup))),
+      _ => Ok(None),
+    }
+  }
+
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    match action {
+      Action::PageFetched(page) => {
+        self.pages.push(*page);
+        let items: Vec<Value> = self.pages.iter().flat_map(|page| page.items.iter().cloned()).collect();
+        self.items = serde_json::to_string_pretty(&items)?;
+      },
+      Action::Down => self.scroll = self.scroll.saturating_add(1),
+      Action::Up => self.scroll = self.scroll.saturating_sub(1),
+      Action::PageDown => self.scroll = self.scroll.saturating_add(10),
+      Action::PageUp => self.scroll = self.scroll.saturating_sub(10),
+      Action::Top => self.scroll = 0,
+      _ => {},
+    }
+    Ok(None)
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    const ARROW: &str = symbols::scrollbar::HORIZONTAL.end;
+    let block = Block::default()
+      .title(format!("Pagination: {}", self.target))
+      .borders(Borders::ALL)
+      .border_type(BorderType::Double)
+      .padding(Padding::horizontal(1))
+      .title_bottom(
+        Line::from(format!("[j/k {ARROW} scroll items] [y {ARROW} copy items] [esc {ARROW} stop and close]"))
+          .style(Style::default().dim())
+          .right_aligned(),
+      );
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    let pages_height = u16::try_from(self.pages.len()).unwrap_or(u16::MAX).min(inner.height / 3).max(1);
+    let layout = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints([
+        Constraint::Length(1),
+        Constraint::Length(pages_height),
+        Constraint::Length(1),
+        Constraint::Fill(1),
+      ])
+      .split(inner);
+    frame.render_widget(Paragraph::new(self.summary_line()), layout[0]);
+    // the latest pages stay in view
+    let hidden = self.pages.len().saturating_sub(usize::from(pages_height));
+    frame.render_widget(
+      Paragraph::new(self.page_lines()).scroll((u16::try_from(hidden).unwrap_or(u16::MAX), 0)),
+      layout[1],
+    );
+    frame.render_widget(Paragraph::new(self.items.as_str()).scroll((self.scroll, 0)), layout[3]);
+    Ok(())
+  }
+}
@@ -0,0 +1,129 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+  prelude::*,
+  widgets::{block::*, *},
+};
+use tui_textarea::TextArea;
+
+use crate::{
+  action::Action,
+  palette::{self, Command},
+  popups::Popup,
+  theme,
+  tui::{self, EventResponse, Frame},
+};
+
+/// Runs any command found by typing part of its title, its key shown next to it.
+pub struct PalettePopup {
+  commands: Vec<Command>,
+  editor: TextArea<'static>,
+  command_index: usize,
+}
+
+impl PalettePopup {
+  pub fn new(commands: Vec<Command>) -> Self {
+    let mut editor = TextArea::default();
+    editor.set_cursor_line_style(Style::default());
+    editor.set_placeholder_text("type a command, e.g. env, curl or an operationId");
+    Self { commands, editor, command_index: 0 }
+  }
+
+  fn query(&self) -> String {
+    self.editor.lines().concat()
+  }
+
+  fn matches(&self) -> Vec<&Command> {
+    palette::matches(&self.commands, &self.query())
+  }
+
+  fn select(&mut self, forward: bool) {
+    let len = self.matches().len().max(1);
+    self.command_index = match forward {
+      true => (self.command_index + 1) % len,
+      false => (self.command_index + len - 1) % len,
+    };
+  }
+}
+
+impl Popup for PalettePopup {
+  fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
+    let control = key.modifiers.contains(KeyModifiers::CONTROL);
+    match key.code {
+      KeyCode::Down | KeyCode::Tab => self.select(true),
+      KeyCode::Char('n') if control => self.select(true),
+      KeyCode::Up | KeyCode::BackTab => self.select(false),
+      KeyCode::Char('p') if control => self.select(false),
+      KeyCode::Enter => {
+        let action = self.matches().get(self.command_index).map(|command| command.action.clone());
+        return Ok(Some(EventResponse::Stop(match action {
+          Some(action) => Action::RunCommand(Box::new(action)),
+          None => Action::Render,
+        })));
+      },
+      KeyCode::Esc => return Ok(Some(EventResponse::Stop(Action::ClosePopup))),
+      _ => {
+        if self.editor.input(key) {
+          self.command_index = 0;
+        }
+      },
+    }
+    Ok(Some(EventResponse::Stop(Action::Render)))
+  }
+
+  fn handle_paste_events(&mut self, text: String) -> Result<Option<EventResponse<Action>>> {
+    if self.editor.insert_str(tui::pasted(&text, false)) {
+      self.command_index = 0;
+    }
+    Ok(Some(EventResponse::Stop(Action::Render)))
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    const ARROW: &str = symbols::scrollbar::HORIZONTAL.end;
+    let inner = area.inner(&Margin { horizontal: 1, vertical: 1 });
+    let layout = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints([Constraint::Length(2), Constraint::Fill(1)])
+      .split(inner);
+    let input = Block::default().borders(Borders::BOTTOM).border_style(Style::default().dim());
+    frame.render_widget(self.editor.widget(), input.inner(layout[0]));
+    frame.render_widget(input, layout[0]);
+
+    let matches = self.matches();
+    let width = usize::from(layout[1].width).saturating_sub(2);
+    let items: Vec<Line<'_>> = matches
+      .iter()
+      .map(|command| {
+        let key = command.key.as_deref().unwrap_or_default();
+        let padding = width.saturating_sub(command.title.chars().count() + key.chars().count());
+        Line::from(vec![
+          Span::raw(command.title.as_str()),
+          Span::raw(" ".repeat(padding)),
+          Span::styled(key, Style::default().fg(Color::LightCyan).dim()),
+        ])
+      })
+      .collect();
+    let mut list_state = ListState::default().with_selected((!items.is_empty()).then_some(self.command_index));
+    frame.render_stateful_widget(
+      List::new(items)
+        .highlight_symbol(ARROW)
+        .highlight_spacing(HighlightSpacing::Always)
+        .highlight_style(theme::current().selection),
+      layout[1],
+      &mut list_state,
+    );
+    frame.render_widget(
+      Block::default()
+        .title(format!("Commands ({}/{})", matches.len(), self.commands.len()))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double)
+        .title_bottom(
+          Line::from(format!("[↑/↓ {ARROW} command] [enter {ARROW} run] [esc {ARROW} close]"))
+            .style(Style::default().dim())
+            .right_aligned(),
+        ),
+      area,
+    );
+    Ok(())
+  }
+}
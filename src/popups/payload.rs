@@ -0,0 +1,73 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+  prelude::*,
+  widgets::{block::*, *},
+};
+
+use crate::{
+  action::Action,
+  popups::Popup,
+  tui::{EventResponse, Frame},
+};
+
+/// Shows the example payload of a webhook, to copy it or write it to a file for testing a receiver.
+pub struct PayloadPopup {
+  name: String,
+  payload: String,
+  line_offset: u16,
+}
+
+impl PayloadPopup {
+  pub fn new(name: String, payload: String) -> Self {
+    Self { name, payload, line_offset: 0 }
+  }
+}
+
+impl Popup for PayloadPopup {
+  fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
+    let response = match key.code {
+      KeyCode::Char('y') | KeyCode::Char('Y') => EventResponse::Stop(Action::Copy(self.payload.clone())),
+      KeyCode::Char('e') => {
+        EventResponse::Stop(Action::ExportTypes(self.name.clone(), String::from("json"), self.payload.clone()))
+      },
+      KeyCode::Esc | KeyCode::Char('q') => EventResponse::Stop(Action::ClosePopup),
+      _ => return Ok(None),
+    };
+    Ok(Some(response))
+  }
+
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    match action {
+      Action::Down => {
+        let lines_len = u16::try_from(self.payload.lines().count().saturating_sub(1)).unwrap_or(u16::MAX);
+        self.line_offset = self.line_offset.saturating_add(1).min(lines_len);
+      },
+      Action::Up => {
+        self.line_offset = self.line_offset.saturating_sub(1);
+      },
+      _ => {},
+    }
+    Ok(None)
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    let inner = area.inner(&Margin { horizontal: 1, vertical: 1 });
+    frame.render_widget(Paragraph::new(self.payload.as_str()).scroll((self.line_offset, 0)), inner);
+
+    const ARROW: &str = symbols::scrollbar::HORIZONTAL.end;
+    frame.render_widget(
+      Block::default()
+        .title(format!("Example Payload: {}", self.name))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double)
+        .title_bottom(
+          Line::from(format!("[j/k {ARROW} scroll] [y {ARROW} copy] [e {ARROW} export] [esc {ARROW} close]"))
+            .style(Style::default().dim())
+            .right_aligned(),
+        ),
+      area,
+    );
+    Ok(())
+  }
+}
@@ -0,0 +1,58 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+  prelude::*,
+  widgets::{block::*, *},
+};
+
+use crate::{
+  action::Action,
+  popups::Popup,
+  tui::{EventResponse, Frame},
+};
+
+/// Asks before quitting with the unsaved edits or requests in flight it would discard.
+pub struct QuitPopup {
+  work: Vec<String>,
+}
+
+impl QuitPopup {
+  pub fn new(work: Vec<String>) -> Self {
+    Self { work }
+  }
+}
+
+impl Popup for QuitPopup {
+  fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
+    let response = match key.code {
+      KeyCode::Char('y') | KeyCode::Enter => EventResponse::Stop(Action::ConfirmQuit(false)),
+      KeyCode::Char('a') => EventResponse::Stop(Action::ConfirmQuit(true)),
+      KeyCode::Char('n') | KeyCode::Esc | KeyCode::Char('q') => EventResponse::Stop(Action::ClosePopup),
+      _ => return Ok(None),
+    };
+    Ok(Some(response))
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    const ARROW: &str = symbols::scrollbar::HORIZONTAL.end;
+    let mut lines = vec![Line::from("Quitting now discards:"), Line::default()];
+    lines
+      .extend(self.work.iter().map(|work| Line::styled(format!("• {work}"), Style::default().fg(Color::LightYellow))));
+    frame.render_widget(
+      Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+        Block::default()
+          .title("Quit?")
+          .borders(Borders::ALL)
+          .border_type(BorderType::Double)
+          .padding(Padding::uniform(1))
+          .title_bottom(
+            Line::from(format!("[y {ARROW} quit] [a {ARROW} quit, never ask for this spec] [n {ARROW} stay]"))
+              .style(Style::default().dim())
+              .right_aligned(),
+          ),
+      ),
+      area,
+    );
+    Ok(())
+  }
+}
@@ -0,0 +1,115 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+  prelude::*,
+  widgets::{block::*, *},
+};
+
+use crate::{
+  action::Action,
+  popups::Popup,
+  registry::RegistryApi,
+  tui::{EventResponse, Frame},
+};
+
+/// Picks an API version among those of the configured registries, loading its spec in place of the one shown.
+#[derive(Default)]
+pub struct RegistryPopup {
+  /// `None` while the registries are listed.
+  apis: Option<Vec<RegistryApi>>,
+  /// Registries that could not be listed.
+  errors: Vec<String>,
+  index: usize,
+}
+
+impl RegistryPopup {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  fn lines(&self) -> Vec<Line<'_>> {
+    let errors = self.errors.iter().map(|error| Line::styled(error.as_str(), Style::default().fg(Color::LightRed)));
+    match &self.apis {
+      None => vec![Line::styled("Listing the registries…", Style::default().dim())],
+      Some(apis) if apis.is_empty() => {
+        errors.chain(std::iter::once(Line::styled("No APIs in the registries.", Style::default().dim()))).collect()
+      },
+      Some(_) => errors.collect(),
+    }
+  }
+}
+
+impl Popup for RegistryPopup {
+  fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
+    let response = match key.code {
+      KeyCode::Enter => {
+        match self.apis.as_ref().and_then(|apis| apis.get(self.index)) {
+          Some(api) => EventResponse::Stop(Action::LoadSpec(api.url.clone())),
+          None => EventResponse::Stop(Action::Render),
+        }
+      },
+      KeyCode::Esc | KeyCode::Char('q') => EventResponse::Stop(Action::ClosePopup),
+      _ => return Ok(None),
+    };
+    Ok(Some(response))
+  }
+
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    let len = self.apis.as_ref().map_or(0, Vec::len).max(1);
+    match action {
+      Action::Down => self.index = self.index.saturating_add(1) % len,
+      Action::Up => self.index = self.index.saturating_add(len - 1) % len,
+      Action::RegistryListed(apis, errors) => {
+        self.apis = Some(apis);
+        self.errors = errors;
+        self.index = 0;
+      },
+      _ => {},
+    }
+    Ok(None)
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    let inner = area.inner(&Margin { horizontal: 1, vertical: 1 });
+    let messages = self.lines();
+    let layout = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints([Constraint::Length(messages.len().try_into()?), Constraint::Fill(1)])
+      .split(inner);
+    frame.render_widget(Paragraph::new(messages).block(Block::default().padding(Padding::horizontal(1))), layout[0]);
+
+    let apis = self.apis.as_deref().unwrap_or_default();
+    let width = apis.iter().map(|api| api.name.chars().count()).max().unwrap_or_default();
+    let items = apis.iter().map(|api| {
+      let mut spans = vec![
+        Span::raw(format!("{:width$}  ", api.name)),
+        Span::styled(api.version.clone().unwrap_or_default(), Style::default().fg(Color::LightGreen)),
+        Span::styled(format!(" ‹{}›", api.registry), Style::default().fg(Color::Cyan)),
+      ];
+      if let Some(description) = &api.description {
+        spans.push(Span::styled(format!("  {description}"), Style::default().dim()));
+      }
+      Line::from(spans)
+    });
+    let mut list_state = ListState::default().with_selected(Some(self.index).filter(|_| !apis.is_empty()));
+    frame.render_stateful_widget(
+      List::new(items)
+        .highlight_symbol(symbols::scrollbar::HORIZONTAL.end)
+        .highlight_spacing(HighlightSpacing::Always)
+        .highlight_style(Style::default().add_modifier(Modifier::BOLD)),
+      layout[1],
+      &mut list_state,
+    );
+
+    const ARROW: &str = symbols::scrollbar::HORIZONTAL.end;
+    frame.render_widget(
+      Block::default().title("Registries").borders(Borders::ALL).border_type(BorderType::Double).title_bottom(
+        Line::from(format!("[j/k {ARROW} API] [enter {ARROW} open] [esc {ARROW} close]"))
+          .style(Style::default().dim())
+          .right_aligned(),
+      ),
+      area,
+    );
+    Ok(())
+  }
+}
@@ -0,0 +1,138 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+  prelude::*,
+  widgets::{block::*, *},
+};
+
+use crate::{
+  action::Action,
+  popups::Popup,
+  request::HttpRequest,
+  secrets, theme,
+  tui::{EventResponse, Frame},
+  workspace::{Environment, SavedRequest},
+};
+
+/// Lists requests imported from HAR captures and replays the selected one against an environment.
+pub struct ReplayPopup {
+  entries: Vec<SavedRequest>,
+  environments: Vec<Environment>,
+  entry_index: usize,
+  /// Index into `environments` plus one, `0` replays the request as captured.
+  environment_index: usize,
+  reveal_secrets: bool,
+  /// The selected entry resolved against the selected environment, resolved again only as either changes since the
+  /// keyring references of the environment are read from the keyring.
+  resolved_request: Option<HttpRequest>,
+}
+
+impl ReplayPopup {
+  pub fn new(entries: Vec<SavedRequest>, environments: Vec<Environment>, reveal_secrets: bool) -> Self {
+    let mut popup =
+      Self { entries, environments, entry_index: 0, environment_index: 0, reveal_secrets, resolved_request: None };
+    popup.resolve();
+    popup
+  }
+
+  fn environment(&self) -> Option<&Environment> {
+    self.environment_index.checked_sub(1).and_then(|index| self.environments.get(index))
+  }
+
+  fn resolve(&mut self) {
+    self.resolved_request = self.entries.get(self.entry_index).map(|entry| entry.resolve(self.environment()));
+  }
+}
+
+impl Popup for ReplayPopup {
+  fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
+    let response = match key.code {
+      KeyCode::Enter => {
+        let Some(request) = self.resolved_request.clone() else {
+          return Ok(None);
+        };
+        let operation = self.entries[self.entry_index].operation.clone();
+        EventResponse::Stop(Action::SendHttpRequest(Box::new(request), operation))
+      },
+      KeyCode::Esc | KeyCode::Char('q') => EventResponse::Stop(Action::ClosePopup),
+      _ => return Ok(None),
+    };
+    Ok(Some(response))
+  }
+
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    let environments_len = self.environments.len() + 1;
+    match action {
+      Action::Down if !self.entries.is_empty() => {
+        self.entry_index = self.entry_index.saturating_add(1) % self.entries.len();
+      },
+      Action::Up if !self.entries.is_empty() => {
+        self.entry_index = self.entry_index.saturating_add(self.entries.len() - 1) % self.entries.len();
+      },
+      Action::FocusNext => self.environment_index = self.environment_index.saturating_add(1) % environments_len,
+      Action::FocusPrev => {
+        self.environment_index = self.environment_index.saturating_add(environments_len - 1) % environments_len
+      },
+      _ => return Ok(None),
+    }
+    self.resolve();
+    Ok(None)
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    let inner = area.inner(&Margin { horizontal: 1, vertical: 1 });
+    let layout = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints([Constraint::Max(2), Constraint::Fill(1), Constraint::Max(2)])
+      .split(inner);
+
+    let environments = std::iter::once("As captured").chain(self.environments.iter().map(|env| env.name.as_str()));
+    frame.render_widget(
+      Tabs::new(environments.map(|name| Span::styled(name, Style::default().dim())))
+        .highlight_style(theme::current().active_tab)
+        .select(self.environment_index),
+      layout[0],
+    );
+
+    let items = self.entries.iter().map(|entry| {
+      let operation = match &entry.operation {
+        Some(operation) => Span::styled(format!(" {operation}"), Style::default().dim()),
+        None => Span::styled(" unmatched", Style::default().fg(Color::LightYellow).dim()),
+      };
+      let folder = entry.folder.as_deref().unwrap_or_default();
+      Line::from(vec![
+        Span::styled(format!("{folder}: "), Style::default().dim()),
+        Span::raw(entry.name.as_str()),
+        operation,
+      ])
+    });
+    let mut list_state = ListState::default().with_selected(Some(self.entry_index));
+    frame.render_stateful_widget(
+      List::new(items).highlight_symbol(symbols::scrollbar::HORIZONTAL.end).highlight_spacing(HighlightSpacing::Always),
+      layout[1],
+      &mut list_state,
+    );
+
+    if let Some(request) = &self.resolved_request {
+      let request = match self.reveal_secrets {
+        true => request.clone(),
+        false => secrets::mask_request(request, &[]),
+      };
+      frame.render_widget(
+        Paragraph::new(format!("{} {}", request.method, request.full_url())).style(Style::default().dim()),
+        layout[2],
+      );
+    }
+
+    const ARROW: &str = symbols::scrollbar::HORIZONTAL.end;
+    frame.render_widget(
+      Block::default().title("Replay HAR").borders(Borders::ALL).border_type(BorderType::Double).title_bottom(
+        Line::from(format!("[h/l {ARROW} environment] [j/k {ARROW} entry] [enter {ARROW} send] [esc {ARROW} close]"))
+          .style(Style::default().dim())
+          .right_aligned(),
+      ),
+      area,
+    );
+    Ok(())
+  }
+}
@@ -0,0 +1,480 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+  prelude::*,
+  widgets::{block::*, *},
+};
+
+use crate::{
+  action::Action,
+  cache::{self, CacheHit, Revalidation},
+  charset::{self, CHARSETS},
+  client::Exchange,
+  json_path::{self, Segment},
+  popups::Popup,
+  rate_limit::RateLimit,
+  response_diff::DocumentedStatus,
+  script::LogLine,
+  spool::BodyWindow,
+  theme, timestamps,
+  tui::{EventResponse, Frame},
+  xml,
+};
+
+pub struct ResponsePopup {
+  exchange: Exchange,
+  body: String,
+  /// Highlights the body as XML.
+  xml: bool,
+  wrap: bool,
+  line_offset: usize,
+  /// Lines shown by the last draw, scrolled by a page.
+  page: usize,
+  /// Lines of the body read from the file it was spooled to, as they are scrolled to.
+  window: Option<BodyWindow>,
+  /// Shows the script log instead of the response.
+  show_log: bool,
+  /// Quota told by the rate limit headers, counted from when the response was received.
+  rate_limit: Option<RateLimit>,
+  /// Charset the body was decoded with, when it was not UTF-8.
+  decoded_as: Option<&'static str>,
+  /// Charset chosen instead of the declared one.
+  charset: Option<&'static str>,
+  /// How the operation of the exchange documents the status, `None` without an operation.
+  documented: Option<DocumentedStatus>,
+  /// Path of the node of each line of a JSON body, the one of the top line copied as a JSONPath or jq path.
+  paths: Vec<Vec<Segment>>,
+  /// Annotates the timestamps of the body with the local time.
+  local_times: bool,
+}
+
+impl ResponsePopup {
+  /// Shows the body as rendered by a plugin when given, pretty printed when it is JSON or XML, long lines wrapped
+  /// with `wrap`.
+  pub fn new(exchange: Exchange, rendered: Option<String>, wrap: bool) -> Self {
+    let response = &exchange.response;
+    let xml = rendered.is_none()
+      && (response.header("Content-Type").is_some_and(xml::is_xml) || response.body.trim_start().starts_with("<?xml"));
+    let window = response.spooled.as_ref().and_then(|spooled| BodyWindow::open(spooled).ok());
+    let paths = match (&window, &rendered) {
+      (None, None) if !xml => Self::paths(&response.body),
+      _ => vec![],
+    };
+    let body = match window {
+      Some(_) => String::default(),
+      None => rendered.unwrap_or_else(|| Self::pretty(&response.body, xml)),
+    };
+    let received_at = chrono::DateTime::parse_from_rfc3339(&exchange.started_at)
+      .map_or(chrono::Utc::now().timestamp(), |started_at| {
+        started_at.timestamp() + (exchange.elapsed_ms / 1000) as i64
+      });
+    let rate_limit = RateLimit::of(response, received_at);
+    let decoded_as = response
+      .raw_body
+      .as_ref()
+      .map(|raw_body| charset::decode(raw_body, response.header("Content-Type").and_then(charset::of)).1);
+    Self {
+      exchange,
+      body,
+      xml,
+      wrap,
+      line_offset: 0,
+      page: 0,
+      window,
+      show_log: false,
+      rate_limit,
+      decoded_as,
+      charset: None,
+      documented: None,
+      paths,
+      local_times: false,
+    }
+  }
+
+  /// Annotates the timestamps of the body with the local time, toggled with `t`.
+  pub fn with_local_times(mut self, local_times: bool) -> Self {
+    self.local_times = local_times;
+    self
+  }
+
+  /// Tells below the status whether the operation documents it, and how.
+  pub fn with_documented_status(mut self, documented: Option<DocumentedStatus>) -> Self {
+    self.documented = documented;
+    self
+  }
+
+  fn pretty(body: &str, xml: bool) -> String {
+    match serde_json::from_str::<serde_json::Value>(body) {
+      _ if xml => xml::pretty(body).unwrap_or(body.to_string()),
+      Ok(json) => serde_json::to_string_pretty(&json).unwrap_or(body.to_string()),
+      Err(_) => body.to_string(),
+    }
+  }
+
+  fn paths(body: &str) -> Vec<Vec<Segment>> {
+    serde_json::from_str(body).map(|json| json_path::line_paths(&json)).unwrap_or_default()
+  }
+
+  /// Path of the node of the body line at the top of the view, the first one while the head is in view.
+  fn top_path(&self) -> Option<&[Segment]> {
+    if self.show_log {
+      return None;
+    }
+    self.paths.get(self.line_offset.saturating_sub(self.head_lines().len())).map(Vec::as_slice)
+  }
+
+  /// The timestamp, or epoch number, of the body line at the top of the view converted to the local time and epoch
+  /// milliseconds, or UTC, while local times are shown.
+  fn top_time(&self) -> Option<String> {
+    if !self.local_times || self.show_log || self.xml {
+      return None;
+    }
+    let line = self.body.lines().nth(self.line_offset.saturating_sub(self.head_lines().len()))?;
+    timestamps::convert(line, &chrono::Local)
+  }
+
+  /// Decodes the body again with the next charset, the declared one after the last, when it was not UTF-8.
+  fn next_charset(&mut self) {
+    let response = &mut self.exchange.response;
+    let Some(raw_body) = response.raw_body.as_ref() else {
+      return;
+    };
+    self.charset = match self.charset {
+      None => CHARSETS.first().copied(),
+      Some(charset) => {
+        CHARSETS.iter().position(|other| *other == charset).and_then(|index| CHARSETS.get(index + 1)).copied()
+      },
+    };
+    let declared = response.header("Content-Type").and_then(charset::of);
+    let (body, decoded_as) = charset::decode(raw_body, self.charset.or(declared));
+    self.body = Self::pretty(&body, self.xml);
+    if !self.xml {
+      self.paths = Self::paths(&body);
+    }
+    self.decoded_as = Some(decoded_as);
+    response.body = body;
+  }
+
+  fn lines(&self) -> Vec<Line<'_>> {
+    if self.show_log {
+      return self.log_lines();
+    }
+    let mut lines = self.head_lines();
+    match self.xml {
+      true => {
+        lines.extend(self.body.lines().map(|line| {
+          Line::from(
+            xml::highlight(line).into_iter().map(|(style, text)| Span::styled(text, style)).collect::<Vec<_>>(),
+          )
+        }))
+      },
+      false => {
+        // the line whose path `p` copies
+        let cursor = (!self.paths.is_empty()).then(|| self.line_offset.saturating_sub(lines.len()));
+        lines.extend(self.body.lines().enumerate().map(|(index, line)| {
+          let mut line = match Some(index) == cursor {
+            true => Line::styled(line, theme::current().selection),
+            false => Line::from(line),
+          };
+          if self.local_times {
+            let text = line.spans.first().map(|span| span.content.as_ref()).unwrap_or_default();
+            let times = timestamps::find(text);
+            if !times.is_empty() {
+              let local = times.iter().map(|(_, time)| timestamps::local(time, &chrono::Local));
+              line.spans.push(Span::styled(
+                format!("  ⏱ {}", local.collect::<Vec<_>>().join(", ")),
+                Style::default().fg(Color::LightBlue).dim(),
+              ));
+            }
+          }
+          line
+        }))
+      },
+    }
+    lines
+  }
+
+  /// Lines of the redirects, the status and the headers shown above the body.
+  fn head_lines(&self) -> Vec<Line<'_>> {
+    let response = &self.exchange.response;
+    let mut status_line = vec![
+      Span::styled(
+        format!("{} {}", response.status, response.status_text),
+        theme::current().status_code(response.status).bold(),
+      ),
+      Span::styled(format!("  {}  {} ms", response.http_version, self.exchange.elapsed_ms), Style::default().dim()),
+    ];
+    if let Some(rate_limit) = self.rate_limit {
+      let style = match rate_limit.remaining == Some(0) || response.status == 429 {
+        true => Style::default().fg(Color::LightRed),
+        false => Style::default().fg(Color::LightBlue),
+      };
+      status_line.push(Span::styled(format!("  {rate_limit}"), style));
+    }
+    if let Some(decoded_as) = self.decoded_as {
+      let chosen = match self.charset {
+        Some(_) => " (chosen)",
+        None => "",
+      };
+      status_line.push(Span::styled(format!("  {decoded_as}{chosen}"), Style::default().fg(Color::LightYellow)));
+    }
+    match response.cache_hit {
+      Some(CacheHit::Fresh) => status_line.push(Span::styled("  ⚡ from cache", Style::default().fg(Color::LightCyan))),
+      Some(CacheHit::Revalidated) => {
+        status_line.push(Span::styled("  ⚡ from cache, revalidated", Style::default().fg(Color::LightCyan)))
+      },
+      None => {},
+    }
+    if let Some(spooled) = &response.spooled {
+      status_line
+        .push(Span::styled(format!("  {:.1} MB on disk", spooled.len as f64 / 1_048_576.0), Style::default().dim()));
+    }
+    // the redirect chain leading to the response
+    let mut lines: Vec<Line<'_>> = response
+      .redirects
+      .iter()
+      .map(|redirect| {
+        Line::from(vec![
+          Span::styled(format!("{} ", redirect.status), theme::current().status_code(redirect.status)),
+          Span::styled(redirect.url.as_str(), Style::default().dim()),
+          Span::styled("  Location: ", Style::default().dim()),
+          Span::raw(redirect.location.as_str()),
+        ])
+      })
+      .collect();
+    lines.push(Line::from(status_line));
+    let summary = |description: &str| description.lines().next().unwrap_or_default().to_string();
+    let documented = match &self.documented {
+      Some(DocumentedStatus::Listed { key, description }) if *key == response.status.to_string() => {
+        Some((format!("✓ documented: {}", summary(description)), Color::LightGreen))
+      },
+      Some(DocumentedStatus::Listed { key, description }) => {
+        Some((format!("✓ documented as {key}: {}", summary(description)), Color::LightGreen))
+      },
+      Some(DocumentedStatus::Default { description }) => {
+        Some((format!("~ only the default response: {}", summary(description)), Color::LightYellow))
+      },
+      Some(DocumentedStatus::Undocumented) => {
+        Some((format!("✗ {} is not documented by the operation", response.status), Color::LightRed))
+      },
+      None => None,
+    };
+    if let Some((text, color)) = documented {
+      lines.push(Line::styled(text, Style::default().fg(color)));
+    }
+    let revalidation = match cache::revalidation(&self.exchange.request, response) {
+      Some(Revalidation::NotModified) => {
+        Some((String::from("✓ 304 Not Modified, the validator still matches"), Color::LightGreen))
+      },
+      Some(Revalidation::Ignored) => {
+        Some((String::from("✗ full response sent again though the validator still matches"), Color::LightRed))
+      },
+      Some(Revalidation::Modified(validator)) => {
+        Some((format!("↻ modified since, new {validator}"), Color::LightYellow))
+      },
+      Some(Revalidation::Unvalidated) => {
+        Some((String::from("✗ full response sent again without any validator"), Color::LightRed))
+      },
+      None => None,
+    };
+    if let Some((text, color)) = revalidation {
+      lines.push(Line::styled(text, Style::default().fg(color)));
+    }
+    let (storable, caching) = cache::analysis(&self.exchange.request, response);
+    if !caching.is_empty() {
+      let verdict = match storable {
+        true => Span::styled("cacheable: ", Style::default().fg(Color::LightBlue)),
+        false => Span::styled("not cacheable: ", Style::default().fg(Color::LightYellow)),
+      };
+      lines.push(Line::from(vec![verdict, Span::styled(caching.join(" · "), Style::default().dim())]));
+    }
+    lines.extend(response.headers.iter().map(|(key, value)| {
+      Line::from(vec![Span::styled(format!("{key}: "), Style::default().dim()), Span::raw(value.as_str())])
+    }));
+    lines.push(Line::default());
+    lines
+  }
+
+  /// The line, or the last one when there are fewer, a spooled body being indexed only up to the line.
+  fn clamp_line(&mut self, line: usize) -> Result<usize> {
+    let head = self.head_lines().len();
+    match self.window.as_mut() {
+      Some(window) if !self.show_log && line >= head => {
+        match window.has_line(line - head)? {
+          true => Ok(line),
+          false => Ok((head + window.line_count()?).saturating_sub(1)),
+        }
+      },
+      Some(_) if !self.show_log => Ok(line),
+      _ => Ok(line.min(self.lines().len().saturating_sub(1))),
+    }
+  }
+
+  fn log_lines(&self) -> Vec<Line<'_>> {
+    if self.exchange.log.is_empty() {
+      return vec![Line::styled("No script output, t attaches scripts to the operation.", Style::default().dim())];
+    }
+    self
+      .exchange
+      .log
+      .iter()
+      .map(|line| {
+        match line {
+          LogLine::Output(text) => Line::raw(text.as_str()),
+          LogLine::Passed(message) => Line::styled(format!("✓ {message}"), Style::default().fg(Color::LightGreen)),
+          LogLine::Failed(message) => Line::styled(format!("✗ {message}"), Style::default().fg(Color::LightRed)),
+        }
+      })
+      .collect()
+  }
+
+  fn select_tab(&mut self, show_log: bool) {
+    self.show_log = show_log;
+    self.line_offset = 0;
+  }
+}
+
+impl Popup for ResponsePopup {
+  fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
+    let response = match key.code {
+      KeyCode::Char('y') | KeyCode::Char('Y') => {
+        let response = &self.exchange.response;
+        match &response.spooled {
+          Some(spooled) => EventResponse::Stop(Action::Copy(spooled.path.display().to_string())),
+          None => EventResponse::Stop(Action::Copy(response.body.clone())),
+        }
+      },
+      KeyCode::Char('p') | KeyCode::Char('P') => {
+        let Some(path) = self.top_path() else {
+          return Ok(None);
+        };
+        match key.code {
+          KeyCode::Char('p') => EventResponse::Stop(Action::Copy(json_path::json_path(path))),
+          _ => EventResponse::Stop(Action::Copy(json_path::jq_path(path))),
+        }
+      },
+      KeyCode::Char('d') => EventResponse::Stop(Action::ShowResponseDiff(Box::new(self.exchange.clone()))),
+      KeyCode::Char('e') => EventResponse::Stop(Action::ShowExampleDiff(Box::new(self.exchange.clone()))),
+      KeyCode::Char('t') if !self.xml && self.window.is_none() => {
+        self.local_times = !self.local_times;
+        EventResponse::Stop(Action::Render)
+      },
+      KeyCode::Char('r') if !cache::conditional_headers(&self.exchange.response).is_empty() => {
+        EventResponse::Stop(Action::Revalidate(Box::new(self.exchange.clone())))
+      },
+      KeyCode::Char('c') if self.exchange.response.raw_body.is_some() => {
+        self.next_charset();
+        EventResponse::Stop(Action::Render)
+      },
+      KeyCode::Tab | KeyCode::BackTab => {
+        self.select_tab(!self.show_log);
+        EventResponse::Stop(Action::Render)
+      },
+      KeyCode::Esc | KeyCode::Char('q') => EventResponse::Stop(Action::ClosePopup),
+      _ => return Ok(None),
+    };
+    Ok(Some(response))
+  }
+
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    match action {
+      Action::Down => self.line_offset = self.clamp_line(self.line_offset + 1)?,
+      Action::Up => self.line_offset = self.line_offset.saturating_sub(1),
+      Action::PageDown => self.line_offset = self.clamp_line(self.line_offset + self.page.max(1))?,
+      Action::PageUp => self.line_offset = self.line_offset.saturating_sub(self.page.max(1)),
+      Action::Top => self.line_offset = 0,
+      Action::Bottom => self.line_offset = self.clamp_line(usize::MAX - 1)?,
+      Action::Tab(index) if index < 2 => self.select_tab(index == 1),
+      _ => {},
+    }
+    Ok(None)
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    const ARROW: &str = symbols::scrollbar::HORIZONTAL.end;
+    let request = &self.exchange.request;
+    let block = Block::default()
+      .title(format!("Response: {} {}", request.method, request.full_url()))
+      .borders(Borders::ALL)
+      .border_type(BorderType::Double)
+      .padding(Padding::horizontal(1))
+      .title_bottom(
+        Line::from(format!(
+          "[j/k {ARROW} scroll] [tab {ARROW} log] [y {ARROW} copy {}]{} [d/e {ARROW} diff previous/example]{}{}{} [esc {ARROW} close]",
+          match self.exchange.response.spooled {
+            Some(_) => "body path",
+            None => "body",
+          },
+          match self.paths.is_empty() {
+            true => String::default(),
+            false => format!(" [p/P {ARROW} copy JSONPath/jq path]"),
+          },
+          match self.exchange.response.raw_body {
+            Some(_) => format!(" [c {ARROW} charset]"),
+            None => String::default(),
+          },
+          match cache::conditional_headers(&self.exchange.response).is_empty() {
+            true => String::default(),
+            false => format!(" [r {ARROW} revalidate]"),
+          },
+          match self.xml || self.window.is_some() {
+            true => String::default(),
+            false => format!(" [t {ARROW} local times]"),
+          }
+        ))
+        .style(Style::default().dim())
+        .right_aligned(),
+      )
+      .title_bottom(
+        Line::from(vec![
+          Span::styled(self.top_path().map(json_path::json_path).unwrap_or_default(), Style::default().fg(Color::LightCyan)),
+          Span::styled(
+            self.top_time().map(|time| format!("  ⏱ {time}")).unwrap_or_default(),
+            Style::default().fg(Color::LightBlue),
+          ),
+        ])
+        .left_aligned(),
+      );
+    let layout = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints([Constraint::Max(2), Constraint::Fill(1)])
+      .split(block.inner(area));
+    frame.render_widget(block, area);
+
+    let failed = self.exchange.log.iter().any(|line| matches!(line, LogLine::Failed(_)));
+    let log_style = match failed {
+      true => Style::default().fg(Color::LightRed).dim(),
+      false => Style::default().dim(),
+    };
+    frame.render_widget(
+      Tabs::new(vec![
+        Span::styled("Response", Style::default().dim()),
+        Span::styled(format!("Log ({})", self.exchange.log.len()), log_style),
+      ])
+      .highlight_style(theme::current().active_tab)
+      .select(usize::from(self.show_log)),
+      layout[0],
+    );
+    self.page = usize::from(layout[1].height);
+    let first = self.line_offset.saturating_sub(self.head_lines().len());
+    let body_lines = match self.window.as_mut().filter(|_| !self.show_log) {
+      // only the lines in view are read from the spooled body
+      Some(window) => Some(window.lines(first, self.page)?),
+      None => None,
+    };
+    let mut paragraph = match body_lines {
+      Some(body_lines) => {
+        let mut lines = self.head_lines();
+        lines.drain(..self.line_offset.min(lines.len()));
+        lines.extend(body_lines.into_iter().map(Line::from));
+        Paragraph::new(lines)
+      },
+      None => Paragraph::new(self.lines()).scroll((u16::try_from(self.line_offset).unwrap_or(u16::MAX), 0)),
+    };
+    if self.wrap {
+      paragraph = paragraph.wrap(Wrap { trim: false });
+    }
+    frame.render_widget(paragraph, layout[1]);
+    Ok(())
+  }
+}
@@ -0,0 +1,199 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+  prelude::*,
+  widgets::{block::*, *},
+};
+use serde_json::Value;
+
+use crate::{
+  action::Action,
+  client::Exchange,
+  diff::ChangeKind,
+  popups::Popup,
+  response_diff::{self, FieldChange, LineChange},
+  theme,
+  tui::{EventResponse, Frame},
+};
+
+/// Compares the previous response of an operation with the latest one, line by line or field by field of their JSON
+/// bodies, or a response with the example the spec declares for it.
+pub struct ResponseDiffPopup {
+  title: String,
+  /// What is compared, the old side first.
+  compared: Vec<Line<'static>>,
+  lines: Vec<LineChange>,
+  /// `None` when either body is not JSON.
+  fields: Option<Vec<FieldChange>>,
+  show_fields: bool,
+  line_offset: u16,
+}
+
+impl ResponseDiffPopup {
+  pub fn new(previous: Exchange, latest: Exchange) -> Self {
+    let lines = response_diff::lines(&response_diff::text(&previous.response), &response_diff::text(&latest.response));
+    let fields = response_diff::fields(&previous.response.body, &latest.response.body);
+    let request = &latest.request;
+    Self {
+      title: format!("Response Diff: {} {}", request.method, request.path),
+      compared: vec![Self::exchange_line("previous", &previous), Self::exchange_line("latest", &latest)],
+      lines,
+      fields,
+      show_fields: false,
+      line_offset: 0,
+    }
+  }
+
+  /// Compares the body of the response with the example declared for it, their fields by name and type only.
+  pub fn with_example(media_type: &str, example: &Value, exchange: &Exchange) -> Self {
+    let pretty = |value: &Value| serde_json::to_string_pretty(value).unwrap_or_default();
+    let example_text = match example {
+      Value::String(text) => text.clone(),
+      example => pretty(example),
+    };
+    let body = serde_json::from_str::<Value>(&exchange.response.body).ok();
+    let body_text = body.as_ref().map_or(exchange.response.body.clone(), pretty);
+    let fields = body.filter(|_| !example.is_string()).map(|body| response_diff::shapes(example, &body));
+    let request = &exchange.request;
+    Self {
+      title: format!("Response vs Example: {} {}", request.method, request.path),
+      compared: vec![
+        Line::from(vec![
+          Span::styled(format!("{:9}", "example"), Style::default().dim()),
+          Span::raw(format!("{media_type}  ")),
+          Span::styled("+ undocumented, - missing from the response, ~ of another type", Style::default().dim()),
+        ]),
+        Self::exchange_line("response", exchange),
+      ],
+      lines: response_diff::lines(&example_text, &body_text),
+      fields,
+      show_fields: true,
+      line_offset: 0,
+    }
+  }
+
+  fn exchange_line(label: &'static str, exchange: &Exchange) -> Line<'static> {
+    let response = &exchange.response;
+    Line::from(vec![
+      Span::styled(format!("{label:9}"), Style::default().dim()),
+      Span::styled(format!("{} ", response.status), theme::current().status_code(response.status)),
+      Span::raw(exchange.started_at.clone()),
+      Span::styled(format!("  {} ms", exchange.elapsed_ms), Style::default().dim()),
+    ])
+  }
+
+  fn line_lines(&self) -> Vec<Line<'_>> {
+    if self.lines.iter().all(|line| matches!(line, LineChange::Same(_))) {
+      return vec![Line::styled("The responses are the same.", Style::default().fg(Color::LightGreen))];
+    }
+    self
+      .lines
+      .iter()
+      .map(|line| {
+        match line {
+          LineChange::Same(text) => Line::styled(format!("  {text}"), Style::default().dim()),
+          LineChange::Removed(text) => Line::styled(format!("- {text}"), Style::default().fg(Color::LightRed)),
+          LineChange::Added(text) => Line::styled(format!("+ {text}"), Style::default().fg(Color::LightGreen)),
+        }
+      })
+      .collect()
+  }
+
+  fn field_lines(&self) -> Vec<Line<'_>> {
+    let Some(fields) = &self.fields else {
+      return vec![Line::styled("The bodies are not both JSON, compare them by lines.", Style::default().dim())];
+    };
+    if fields.is_empty() {
+      return vec![Line::styled("The bodies have the same fields.", Style::default().fg(Color::LightGreen))];
+    }
+    let value = |value: &Option<Value>| value.as_ref().map(|value| value.to_string()).unwrap_or_default();
+    fields
+      .iter()
+      .map(|field| {
+        let (symbol, color, change) = match field.kind {
+          ChangeKind::Added => ('+', Color::LightGreen, value(&field.new)),
+          ChangeKind::Removed => ('-', Color::LightRed, value(&field.old)),
+          ChangeKind::Changed => ('~', Color::LightYellow, format!("{} → {}", value(&field.old), value(&field.new))),
+        };
+        Line::from(vec![
+          Span::styled(format!("{symbol} {} ", field.path), color),
+          Span::styled(change, Style::default().dim()),
+        ])
+      })
+      .collect()
+  }
+
+  fn shown_lines(&self) -> Vec<Line<'_>> {
+    match self.show_fields {
+      true => self.field_lines(),
+      false => self.line_lines(),
+    }
+  }
+
+  fn select_tab(&mut self, show_fields: bool) {
+    self.show_fields = show_fields;
+    self.line_offset = 0;
+  }
+}
+
+impl Popup for ResponseDiffPopup {
+  fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
+    let response = match key.code {
+      KeyCode::Tab | KeyCode::BackTab => {
+        self.select_tab(!self.show_fields);
+        EventResponse::Stop(Action::Render)
+      },
+      KeyCode::Esc | KeyCode::Char('q') => EventResponse::Stop(Action::ClosePopup),
+      _ => return Ok(None),
+    };
+    Ok(Some(response))
+  }
+
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    match action {
+      Action::Down => {
+        let lines_len = u16::try_from(self.shown_lines().len().saturating_sub(1)).unwrap_or(u16::MAX);
+        self.line_offset = self.line_offset.saturating_add(1).min(lines_len);
+      },
+      Action::Up => self.line_offset = self.line_offset.saturating_sub(1),
+      Action::Top => self.line_offset = 0,
+      Action::Tab(index) if index < 2 => self.select_tab(index == 1),
+      _ => {},
+    }
+    Ok(None)
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    const ARROW: &str = symbols::scrollbar::HORIZONTAL.end;
+    let block = Block::default()
+      .title(self.title.as_str())
+      .borders(Borders::ALL)
+      .border_type(BorderType::Double)
+      .padding(Padding::horizontal(1))
+      .title_bottom(
+        Line::from(format!("[j/k {ARROW} scroll] [tab {ARROW} lines/fields] [esc {ARROW} close]"))
+          .style(Style::default().dim())
+          .right_aligned(),
+      );
+    let layout = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints([Constraint::Length(3), Constraint::Max(2), Constraint::Fill(1)])
+      .split(block.inner(area));
+    frame.render_widget(block, area);
+
+    frame.render_widget(Paragraph::new(self.compared.clone()), layout[0]);
+    let changed = self.lines.iter().filter(|line| !matches!(line, LineChange::Same(_))).count();
+    let fields = self.fields.as_ref().map_or(String::from("Fields"), |fields| format!("Fields ({})", fields.len()));
+    frame.render_widget(
+      Tabs::new(vec![
+        Span::styled(format!("Lines ({changed})"), Style::default().dim()),
+        Span::styled(fields, Style::default().dim()),
+      ])
+      .highlight_style(theme::current().active_tab)
+      .select(usize::from(self.show_fields)),
+      layout[1],
+    );
+    frame.render_widget(Paragraph::new(self.shown_lines()).scroll((self.line_offset, 0)), layout[2]);
+    Ok(())
+  }
+}
@@ -0,0 +1,81 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+  prelude::*,
+  widgets::{block::*, *},
+};
+use tui_textarea::{CursorMove, TextArea};
+
+use crate::{
+  action::Action,
+  popups::Popup,
+  tui::{self, EventResponse, Frame},
+};
+
+/// Names the request being saved, `folder/name` puts it in a folder and `/` nests folders.
+pub struct SavePopup {
+  editor: TextArea<'static>,
+  /// Existing folders, listed as a reminder.
+  folders: Vec<String>,
+}
+
+impl SavePopup {
+  pub fn new(name: String, folders: Vec<String>) -> Self {
+    let mut editor = TextArea::new(vec![name]);
+    editor.set_cursor_line_style(Style::default());
+    editor.move_cursor(CursorMove::End);
+    Self { editor, folders }
+  }
+}
+
+impl Popup for SavePopup {
+  fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
+    let response = match key.code {
+      KeyCode::Enter => {
+        let name = self.editor.lines().concat().trim().trim_matches('/').to_string();
+        if name.is_empty() {
+          return Ok(Some(EventResponse::Stop(Action::Render)));
+        }
+        EventResponse::Stop(Action::SaveRequest(name))
+      },
+      KeyCode::Esc => EventResponse::Stop(Action::ClosePopup),
+      _ => {
+        self.editor.input(key);
+        EventResponse::Stop(Action::Render)
+      },
+    };
+    Ok(Some(response))
+  }
+
+  fn handle_paste_events(&mut self, text: String) -> Result<Option<EventResponse<Action>>> {
+    self.editor.insert_str(tui::pasted(&text, false));
+    Ok(Some(EventResponse::Stop(Action::Render)))
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    let inner = area.inner(&Margin { horizontal: 1, vertical: 1 });
+    let layout = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints([Constraint::Length(2), Constraint::Fill(1)])
+      .split(inner);
+
+    let input = Block::default().borders(Borders::BOTTOM).border_style(Style::default().dim());
+    frame.render_widget(self.editor.widget(), input.inner(layout[0]));
+    frame.render_widget(input, layout[0]);
+
+    let folders = std::iter::once(Line::styled("Folders", Style::default().bold().dim()))
+      .chain(self.folders.iter().map(|folder| Line::styled(format!("  {folder}/"), Style::default().dim())));
+    frame.render_widget(Paragraph::new(folders.collect::<Vec<_>>()), layout[1]);
+
+    const ARROW: &str = symbols::scrollbar::HORIZONTAL.end;
+    frame.render_widget(
+      Block::default().title("Save Request").borders(Borders::ALL).border_type(BorderType::Double).title_bottom(
+        Line::from(format!("[folder/name] [enter {ARROW} save] [esc {ARROW} cancel]"))
+          .style(Style::default().dim())
+          .right_aligned(),
+      ),
+      area,
+    );
+    Ok(())
+  }
+}
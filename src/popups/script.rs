@@ -0,0 +1,116 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+  prelude::*,
+  widgets::{block::*, *},
+};
+use tui_textarea::TextArea;
+
+use crate::{
+  action::Action,
+  popups::Popup,
+  theme,
+  tui::{self, EventResponse, Frame},
+  workspace::{ScriptTarget, Scripts},
+};
+
+const STAGES: [&str; 2] = ["Pre-request", "Post-response"];
+
+/// Edits the pre-request and post-response scripts of an operation or a collection folder.
+pub struct ScriptPopup {
+  target: ScriptTarget,
+  editors: [TextArea<'static>; 2],
+  stage_index: usize,
+}
+
+impl ScriptPopup {
+  pub fn new(target: ScriptTarget, scripts: Scripts) -> Self {
+    let editor = |source: &str, placeholder: &str| {
+      let mut editor = TextArea::new(source.lines().map(String::from).collect());
+      editor.set_cursor_line_style(Style::default());
+      editor.set_placeholder_text(placeholder);
+      editor
+    };
+    Self {
+      target,
+      editors: [
+        editor(
+          &scripts.pre_request,
+          "request.headers.set_header(\"X-Signature\", hmac_sha256(vars.secret, request.body));",
+        ),
+        editor(&scripts.post_response, "assert(response.status == 200, \"status is 200\");"),
+      ],
+      stage_index: 0,
+    }
+  }
+
+  fn scripts(&self) -> Scripts {
+    let [pre_request, post_response] = &self.editors;
+    Scripts { pre_request: pre_request.lines().join("\n"), post_response: post_response.lines().join("\n") }
+  }
+}
+
+impl Popup for ScriptPopup {
+  fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
+    let response = match key.code {
+      KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+        EventResponse::Stop(Action::SaveScripts(self.target.clone(), self.scripts()))
+      },
+      KeyCode::Tab | KeyCode::BackTab => {
+        self.stage_index = (self.stage_index + 1) % STAGES.len();
+        EventResponse::Stop(Action::Render)
+      },
+      KeyCode::Esc => EventResponse::Stop(Action::ClosePopup),
+      _ => {
+        self.editors[self.stage_index].input(key);
+        EventResponse::Stop(Action::Render)
+      },
+    };
+    Ok(Some(response))
+  }
+
+  fn handle_paste_events(&mut self, text: String) -> Result<Option<EventResponse<Action>>> {
+    self.editors[self.stage_index].insert_str(tui::pasted(&text, true));
+    Ok(Some(EventResponse::Stop(Action::Render)))
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    let inner = area.inner(&Margin { horizontal: 1, vertical: 1 });
+    let layout = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints([Constraint::Max(2), Constraint::Fill(1), Constraint::Max(3)])
+      .split(inner);
+
+    frame.render_widget(
+      Tabs::new(STAGES.iter().map(|stage| Span::styled(*stage, Style::default().dim())))
+        .highlight_style(theme::current().active_tab)
+        .select(self.stage_index),
+      layout[0],
+    );
+    frame.render_widget(self.editors[self.stage_index].widget(), layout[1]);
+    frame.render_widget(
+      Paragraph::new(vec![
+        Line::from("request, response (status, headers, body, json), vars"),
+        Line::from("print, assert(ok, message), sha256, hmac_sha256(key, text), base64, timestamp, now"),
+      ])
+      .style(Style::default().dim())
+      .block(Block::default().borders(Borders::TOP).border_style(Style::default().dim())),
+      layout[2],
+    );
+
+    const ARROW: &str = symbols::scrollbar::HORIZONTAL.end;
+    frame.render_widget(
+      Block::default()
+        .title(format!("Scripts: {}", self.target))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double)
+        .title_bottom(
+          Line::from(format!("[tab {ARROW} stage] [ctrl-s {ARROW} save] [esc {ARROW} cancel]"))
+            .style(Style::default().dim())
+            .right_aligned(),
+        ),
+      area,
+    );
+    Ok(())
+  }
+}
@@ -0,0 +1,115 @@
+use std::collections::BTreeMap;
+
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+  prelude::*,
+  widgets::{block::*, *},
+};
+
+use crate::{
+  action::Action,
+  popups::Popup,
+  server::Server,
+  tui::{EventResponse, Frame},
+};
+
+/// Chooses the values of the active server variables, cycling through their `enum` choices.
+pub struct ServerPopup {
+  server: Server,
+  values: BTreeMap<String, String>,
+  variable_index: usize,
+}
+
+impl ServerPopup {
+  pub fn new(server: Server, values: BTreeMap<String, String>) -> Self {
+    Self { server, values, variable_index: 0 }
+  }
+
+  fn value(&self, index: usize) -> &str {
+    let variable = &self.server.variables[index];
+    self.values.get(&variable.name).unwrap_or(&variable.default)
+  }
+
+  fn cycle_choice(&mut self, forward: bool) {
+    let Some(variable) = self.server.variables.get(self.variable_index) else {
+      return;
+    };
+    if variable.choices.is_empty() {
+      return;
+    }
+    let len = variable.choices.len();
+    let current = variable.choices.iter().position(|choice| choice == self.value(self.variable_index)).unwrap_or(0);
+    let next = if forward { (current + 1) % len } else { (current + len - 1) % len };
+    self.values.insert(variable.name.clone(), variable.choices[next].clone());
+  }
+}
+
+impl Popup for ServerPopup {
+  fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
+    let response = match key.code {
+      KeyCode::Enter => EventResponse::Stop(Action::SetServerVariables(self.values.clone())),
+      KeyCode::Esc | KeyCode::Char('q') => EventResponse::Stop(Action::ClosePopup),
+      _ => return Ok(None),
+    };
+    Ok(Some(response))
+  }
+
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    let len = self.server.variables.len().max(1);
+    match action {
+      Action::Down => self.variable_index = self.variable_index.saturating_add(1) % len,
+      Action::Up => self.variable_index = self.variable_index.saturating_add(len - 1) % len,
+      Action::FocusNext => self.cycle_choice(true),
+      Action::FocusPrev => self.cycle_choice(false),
+      _ => {},
+    }
+    Ok(None)
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    let inner = area.inner(&Margin { horizontal: 1, vertical: 1 });
+    let layout = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints([Constraint::Max(2), Constraint::Fill(1)])
+      .split(inner);
+
+    frame.render_widget(
+      Paragraph::new(Line::from(vec![
+        Span::raw(self.server.expand(&self.values)),
+        Span::styled(format!("  {}", self.server.description.as_deref().unwrap_or_default()), Style::default().dim()),
+      ])),
+      layout[0],
+    );
+
+    let items = self.server.variables.iter().enumerate().map(|(index, variable)| {
+      let choices = if variable.choices.is_empty() { String::from("free value") } else { variable.choices.join(" | ") };
+      Line::from(vec![
+        Span::styled(format!("{} = ", variable.name), Style::default().dim()),
+        Span::styled(self.value(index).to_string(), Style::default().fg(Color::LightGreen)),
+        Span::styled(format!("  [{choices}]"), Style::default().dim()),
+        Span::styled(
+          variable.description.as_ref().map(|description| format!("  {description}")).unwrap_or_default(),
+          Style::default().dim(),
+        ),
+      ])
+    });
+    let mut list_state = ListState::default().with_selected(Some(self.variable_index));
+    frame.render_stateful_widget(
+      List::new(items).highlight_symbol(symbols::scrollbar::HORIZONTAL.end).highlight_spacing(HighlightSpacing::Always),
+      layout[1],
+      &mut list_state,
+    );
+
+    const ARROW: &str = symbols::scrollbar::HORIZONTAL.end;
+    frame.render_widget(
+      Block::default().title("Server Variables").borders(Borders::ALL).border_type(BorderType::Double).title_bottom(
+        Line::from(format!("[j/k {ARROW} variable] [h/l {ARROW} value] [enter {ARROW} apply] [esc {ARROW} close]"))
+          .style(Style::default().dim())
+          .right_aligned(),
+      ),
+      area,
+    );
+    Ok(())
+  }
+}
@@ -0,0 +1,280 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+  prelude::*,
+  widgets::{block::*, *},
+};
+
+use crate::{
+  action::Action,
+  fuzz::CaseResult,
+  popups::Popup,
+  report::{TestCase, TestReport, TestSuite},
+  theme,
+  tui::{EventResponse, Frame},
+  workspace::OperationKey,
+};
+
+/// Sends every listed `GET` operation with example inputs and shows a pass/fail grid of their responses.
+pub struct SmokePopup {
+  operations: Vec<OperationKey>,
+  results: Vec<Option<CaseResult>>,
+  tag: Option<String>,
+  environment: Option<String>,
+  started: bool,
+  row_index: usize,
+}
+
+impl SmokePopup {
+  pub fn new(operations: Vec<OperationKey>, tag: Option<String>, environment: Option<String>) -> Self {
+    let results = vec![None; operations.len()];
+    Self { operations, results, tag, environment, started: false, row_index: 0 }
+  }
+
+  fn running(&self) -> bool {
+    self.started && self.results.iter().any(Option::is_none)
+  }
+
+  /// A 2xx status with a response matching the spec.
+  fn passed(result: &CaseResult) -> bool {
+    !result.failed() && result.exchange.as_ref().is_some_and(|exchange| exchange.response.status / 100 == 2)
+  }
+
+  /// Whether the response matches the responses the spec documents, a server error telling nothing of its schema.
+  fn schema_valid(result: &CaseResult) -> bool {
+    result.problems.iter().all(|problem| problem.starts_with("server error"))
+  }
+
+  fn report(&self) -> TestReport {
+    let cases = self
+      .operations
+      .iter()
+      .zip(self.results.iter())
+      .map(|(operation, result)| {
+        TestCase::from_case(format!("{} {}", operation.method, operation.path), result.as_ref(), true)
+      })
+      .collect();
+    TestReport {
+      name: String::from("smoke-tests"),
+      suites: vec![TestSuite { name: String::from("smoke tests"), cases }],
+    }
+  }
+
+  fn summary(&self) -> Line<'_> {
+    let results: Vec<&CaseResult> = self.results.iter().flatten().collect();
+    let passed = results.iter().filter(|result| Self::passed(result)).count();
+    let failed = results.len() - passed;
+    let scope = match &self.tag {
+      Some(tag) => format!("GET operations tagged {tag}"),
+      None => String::from("all GET operations"),
+    };
+    let environment = self.environment.as_deref().unwrap_or("no environment");
+    Line::from(vec![
+      Span::styled(format!("{scope} on {environment}"), Style::default().bold()),
+      Span::styled(format!("  {}/{} sent  ", results.len(), self.operations.len()), Style::default().dim()),
+      Span::styled(format!("{passed} passed  "), Style::default().fg(Color::LightGreen)),
+      Span::styled(format!("{failed} failed"), match failed {
+        0 => Style::default().dim(),
+        _ => Style::default().fg(Color::LightRed),
+      }),
+    ])
+  }
+
+  fn row(&self, index: usize) -> Row<'_> {
+    let operation = &self.operations[index];
+    let name = Cell::from(format!("{} {}", operation.method, operation.path));
+    let Some(result) = &self.results[index] else {
+      let (symbol, style) = match self.running() {
+        true => ("…", Style::default().fg(Color::LightBlue)),
+        false => ("·", Style::default().dim()),
+      };
+      return Row::new(vec![Cell::from(symbol).style(style), name]);
+    };
+    let Some(exchange) = &result.exchange else {
+      return Row::new(vec![
+        Cell::from("✗").style(Style::default().fg(Color::LightRed)),
+        name,
+        Cell::from("error").style(Style::default().fg(Color::LightRed)),
+      ]);
+    };
+    let status_style = match exchange.response.status {
+      200..=299 => Style::default().fg(Color::LightGreen),
+      300..=399 => Style::default().fg(Color::LightBlue),
+      400..=499 => Style::default().fg(Color::LightYellow),
+      _ => Style::default().fg(Color::LightRed),
+    };
+    let schema = match Self::schema_valid(result) {
+      true => Cell::from("valid").style(Style::default().fg(Color::LightGreen)),
+      false => Cell::from("invalid").style(Style::default().fg(Color::LightRed)),
+    };
+    let symbol = match Self::passed(result) {
+      true => Cell::from("✓").style(Style::default().fg(Color::LightGreen)),
+      false => Cell::from("✗").style(Style::default().fg(Color::LightRed)),
+    };
+    Row::new(vec![
+      symbol,
+      name,
+      Cell::from(format!("{}xx {}", exchange.response.status / 100, exchange.response.status)).style(status_style),
+      schema,
+      Cell::from(format!("{} ms", exchange.elapsed_ms)).style(Style::default().dim()),
+    ])
+  }
+
+  fn detail_lines(&self) -> Vec<Line<'_>> {
+    let Some(Some(result)) = self.results.get(self.row_index) else {
+      return vec![];
+    };
+    let problem = Style::default().fg(Color::LightRed);
+    let mut lines: Vec<Line<'_>> = result.error.iter().map(|error| Line::styled(error.as_str(), problem)).collect();
+    lines.extend(result.problems.iter().map(|found| Line::styled(found.as_str(), problem)));
+    if let Some(exchange) = &result.exchange {
+      lines.push(Line::styled(
+        format!("{} {}", exchange.request.method, exchange.request.full_url()),
+        Style::default().dim(),
+      ));
+    }
+    lines
+  }
+}
+
+impl Popup for SmokePopup {
+  fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
+    let response = match key.code {
+      KeyCode::Enter if !self.running() && !self.operations.is_empty() => {
+        self.results = vec![None; self.operations.len()];
+        self.started = true;
+        EventResponse::Stop(Action::RunSmokeTests)
+      },
+      KeyCode::Char('e') if self.started => EventResponse::Stop(Action::ExportReport(Box::new(self.report()))),
+      KeyCode::Esc | KeyCode::Char('q') => EventResponse::Stop(Action::ClosePopup),
+      _ => return Ok(None),
+    };
+    Ok(Some(response))
+  }
+
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    let rows_len = self.operations.len().max(1);
+    match action {
+      Action::Down => self.row_index = self.row_index.saturating_add(1) % rows_len,
+      Action::Up => self.row_index = self.row_index.saturating_add(rows_len - 1) % rows_len,
+      Action::SmokeTestFinished(index, result) if index < self.results.len() => {
+        self.results[index] = Some(*result);
+      },
+      _ => {},
+    }
+    Ok(None)
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    let inner = area.inner(&Margin { horizontal: 1, vertical: 1 });
+    let layout = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints([Constraint::Max(2), Constraint::Fill(3), Constraint::Fill(1)])
+      .split(inner);
+
+    frame.render_widget(Paragraph::new(self.summary()), layout[0]);
+    let rows: Vec<Row<'_>> = (0..self.operations.len()).map(|index| self.row(index)).collect();
+    let mut table_state = TableState::default().with_selected((!rows.is_empty()).then_some(self.row_index));
+    frame.render_stateful_widget(
+      Table::new(rows, [
+        Constraint::Length(1),
+        Constraint::Fill(1),
+        Constraint::Length(8),
+        Constraint::Length(8),
+        Constraint::Length(9),
+      ])
+      .header(Row::new(vec!["", "Operation", "Status", "Schema", "Latency"]).style(Style::default().dim()))
+      .highlight_symbol(symbols::scrollbar::HORIZONTAL.end)
+      .highlight_spacing(HighlightSpacing::Always)
+      .highlight_style(theme::current().selection),
+      layout[1],
+      &mut table_state,
+    );
+    frame.render_widget(
+      Paragraph::new(self.detail_lines())
+        .wrap(Wrap { trim: false })
+        .block(Block::default().borders(Borders::TOP).border_style(Style::default().dim())),
+      layout[2],
+    );
+
+    const ARROW: &str = symbols::scrollbar::HORIZONTAL.end;
+    frame.render_widget(
+      Block::default().title("Smoke Tests").borders(Borders::ALL).border_type(BorderType::Double).title_bottom(
+        Line::from(format!("[j/k {ARROW} operation] [enter {ARROW} run] [e {ARROW} export] [esc {ARROW} close]"))
+          .style(Style::default().dim())
+          .right_aligned(),
+      ),
+      area,
+    );
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+  use serde_json::json;
+
+  use super::*;
+  use crate::{
+    client::{Exchange, HttpResponse},
+    fuzz,
+  };
+
+  #[test]
+  fn test_classification() {
+    let document = json!({
+      "paths": {
+        "/pets/{petId}": {
+          "get": {
+            "responses": {
+              "200": {
+                "description": "The pet",
+                "content": {
+                  "application/json": {
+                    "schema": { "type": "object", "required": ["name"], "properties": { "name": { "type": "string" } } }
+                  }
+                }
+              },
+              "404": { "description": "Not found" }
+            }
+          }
+        }
+      }
+    });
+    let result = |status: u16, body: &str| {
+      let response = HttpResponse {
+        status,
+        headers: vec![(String::from("Content-Type"), String::from("application/json"))],
+        body: body.to_string(),
+        ..HttpResponse::default()
+      };
+      CaseResult {
+        problems: fuzz::problems(&document, "/pets/{petId}", "GET", &response),
+        exchange: Some(Exchange { response, ..Exchange::default() }),
+        error: None,
+      }
+    };
+    let classify = |result: &CaseResult| (SmokePopup::passed(result), SmokePopup::schema_valid(result));
+
+    assert_eq!(classify(&result(200, r#"{ "name": "Rex" }"#)), (true, true));
+    assert_eq!(classify(&result(200, r#"{ "id": 1 }"#)), (false, false));
+    assert_eq!(classify(&result(200, "Rex")), (false, false));
+    // documented, but not a success
+    assert_eq!(classify(&result(404, "")), (false, true));
+    assert_eq!(classify(&result(301, "")), (false, false));
+    assert_eq!(classify(&result(503, "")), (false, false));
+    let unreachable = CaseResult { error: Some(String::from("Connection refused")), ..CaseResult::default() };
+    assert!(!SmokePopup::passed(&unreachable));
+
+    let mut popup = SmokePopup::new(
+      vec![OperationKey::new("GET", "/pets/{petId}"), OperationKey::new("GET", "/pets")],
+      None,
+      Some(String::from("dev")),
+    );
+    popup.update(Action::SmokeTestFinished(0, Box::new(result(200, r#"{ "name": "Rex" }"#)))).unwrap();
+    popup.update(Action::SmokeTestFinished(1, Box::new(result(404, "")))).unwrap();
+    let summary: String = popup.summary().spans.iter().map(|span| span.content.as_ref()).collect();
+    assert_eq!(summary, "all GET operations on dev  2/2 sent  1 passed  1 failed");
+  }
+}
@@ -0,0 +1,93 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+  prelude::*,
+  widgets::{block::*, *},
+};
+use strum::IntoEnumIterator;
+
+use crate::{
+  action::Action,
+  popups::Popup,
+  request::HttpRequest,
+  snippets::Language,
+  theme,
+  tui::{EventResponse, Frame},
+};
+
+pub struct SnippetPopup {
+  request: HttpRequest,
+  languages: Vec<Language>,
+  language_index: usize,
+  code: String,
+  line_offset: u16,
+}
+
+impl SnippetPopup {
+  pub fn new(request: HttpRequest) -> Self {
+    let languages: Vec<Language> = Language::iter().collect();
+    let code = languages[0].render(&request);
+    Self { request, languages, language_index: 0, code, line_offset: 0 }
+  }
+
+  fn select_language(&mut self, index: usize) {
+    self.language_index = index % self.languages.len();
+    self.code = self.languages[self.language_index].render(&self.request);
+    self.line_offset = 0;
+  }
+}
+
+impl Popup for SnippetPopup {
+  fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
+    let response = match key.code {
+      KeyCode::Char('y') | KeyCode::Char('Y') => EventResponse::Stop(Action::Copy(self.code.clone())),
+      KeyCode::Esc | KeyCode::Char('q') => EventResponse::Stop(Action::ClosePopup),
+      _ => return Ok(None),
+    };
+    Ok(Some(response))
+  }
+
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    match action {
+      Action::FocusNext => self.select_language(self.language_index.saturating_add(1)),
+      Action::FocusPrev => self.select_language(self.language_index.saturating_add(self.languages.len() - 1)),
+      Action::Tab(index) if index < self.languages.len().try_into()? => self.select_language(index.try_into()?),
+      Action::Down => {
+        let lines_len = u16::try_from(self.code.lines().count().saturating_sub(1)).unwrap_or(u16::MAX);
+        self.line_offset = self.line_offset.saturating_add(1).min(lines_len);
+      },
+      Action::Up => {
+        self.line_offset = self.line_offset.saturating_sub(1);
+      },
+      _ => {},
+    }
+    Ok(None)
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    let inner = area.inner(&Margin { horizontal: 1, vertical: 1 });
+    let layout = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints([Constraint::Max(2), Constraint::Fill(1)])
+      .split(inner);
+
+    frame.render_widget(
+      Tabs::new(self.languages.iter().map(|language| Span::styled(language.to_string(), Style::default().dim())))
+        .highlight_style(theme::current().active_tab)
+        .select(self.language_index),
+      layout[0],
+    );
+    frame.render_widget(Paragraph::new(self.code.as_str()).scroll((self.line_offset, 0)), layout[1]);
+
+    const ARROW: &str = symbols::scrollbar::HORIZONTAL.end;
+    frame.render_widget(
+      Block::default().title("Code Snippet").borders(Borders::ALL).border_type(BorderType::Double).title_bottom(
+        Line::from(format!("[h/l {ARROW} language] [y {ARROW} copy] [esc {ARROW} close]"))
+          .style(Style::default().dim())
+          .right_aligned(),
+      ),
+      area,
+    );
+    Ok(())
+  }
+}
@@ -0,0 +1,127 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+  prelude::*,
+  widgets::{block::*, *},
+};
+
+use crate::{
+  action::Action,
+  popups::Popup,
+  spec_info::{Link, SpecInfo},
+  tui::{EventResponse, Frame},
+};
+
+/// Version, contact, license and documentation of the spec, along with its tags and their `externalDocs`, the tags of
+/// the selected operation marked.
+pub struct SpecInfoPopup {
+  info: SpecInfo,
+  operation_tags: Vec<String>,
+  scroll: u16,
+}
+
+impl SpecInfoPopup {
+  pub fn new(info: SpecInfo, operation_tags: Vec<String>) -> Self {
+    Self { info, operation_tags, scroll: 0 }
+  }
+
+  fn heading(title: &str) -> Line<'_> {
+    Line::styled(title, Style::default().bold().fg(Color::LightBlue))
+  }
+
+  fn field(label: &'static str, value: String) -> Line<'static> {
+    Line::from(vec![Span::styled(format!("  {label:<10}"), Style::default().dim()), Span::raw(value)])
+  }
+
+  fn link_text(link: &Link) -> String {
+    match &link.description {
+      Some(description) => format!("{} ({description})", link.url),
+      None => link.url.clone(),
+    }
+  }
+
+  fn lines(&self) -> Vec<Line<'static>> {
+    let info = &self.info;
+    let mut lines = vec![Self::heading("API"), Self::field("Title", info.title.clone())];
+    lines.push(Self::field("Version", info.version.clone()));
+    if let Some(license) = &info.license {
+      let name = license.description.clone().unwrap_or_default();
+      lines.push(Self::field("License", format!("{name} {}", license.url).trim_end().to_string()));
+    }
+    if let Some(terms) = &info.terms_of_service {
+      lines.push(Self::field("Terms", terms.clone()));
+    }
+    if let Some(docs) = &info.external_docs {
+      lines.push(Self::field("Docs", Self::link_text(docs)));
+    }
+    lines.extend([Line::default(), Self::heading("Contact")]);
+    match &info.contact {
+      Some(contact) => {
+        let fields = [("Name", &contact.name), ("Email", &contact.email), ("Url", &contact.url)];
+        lines.extend(fields.into_iter().filter_map(|(label, value)| Some(Self::field(label, value.clone()?))));
+      },
+      None => lines.push(Line::styled("  The spec names no contact.", Style::default().dim())),
+    }
+    lines.extend([Line::default(), Self::heading("Tags")]);
+    if info.tags.is_empty() {
+      lines.push(Line::styled("  No tags.", Style::default().dim()));
+    }
+    for tag in &info.tags {
+      let marker = match self.operation_tags.contains(&tag.name) {
+        true => Span::styled("● ", Style::default().fg(Color::LightGreen)),
+        false => Span::raw("  "),
+      };
+      lines.push(Line::from(vec![
+        Span::raw("  "),
+        marker,
+        Span::styled(tag.name.clone(), Style::default().bold()),
+        Span::styled(format!("  {} operations", tag.operations), Style::default().dim()),
+      ]));
+      if let Some(description) = tag.description.as_deref().and_then(|description| description.lines().next()) {
+        lines.push(Line::styled(format!("      {description}"), Style::default().dim()));
+      }
+      if let Some(docs) = &tag.external_docs {
+        lines.push(Line::styled(format!("      {}", Self::link_text(docs)), Style::default().fg(Color::LightCyan)));
+      }
+    }
+    lines
+  }
+}
+
+impl Popup for SpecInfoPopup {
+  fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
+    match key.code {
+      KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter => Ok(Some(EventResponse::Stop(Action::ClosePopup))),
+      _ => Ok(None),
+    }
+  }
+
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    match action {
+      Action::Down => self.scroll = self.scroll.saturating_add(1),
+      Action::Up => self.scroll = self.scroll.saturating_sub(1),
+      Action::Top => self.scroll = 0,
+      _ => {},
+    }
+    Ok(None)
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    let inner = area.inner(&Margin { horizontal: 2, vertical: 1 });
+    frame.render_widget(Paragraph::new(self.lines()).wrap(Wrap { trim: false }).scroll((self.scroll, 0)), inner);
+
+    const ARROW: &str = symbols::scrollbar::HORIZONTAL.end;
+    frame.render_widget(
+      Block::default()
+        .title("Spec Info")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double)
+        .title_bottom(Line::styled("● tag of the selected operation", Style::default().dim()).left_aligned())
+        .title_bottom(
+          Line::from(format!("[j/k {ARROW} scroll] [esc {ARROW} close]")).style(Style::default().dim()).right_aligned(),
+        ),
+      area,
+    );
+    Ok(())
+  }
+}
@@ -0,0 +1,137 @@
+use std::collections::BTreeMap;
+
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+  prelude::*,
+  widgets::{block::*, *},
+};
+
+use crate::{
+  action::Action,
+  popups::Popup,
+  stats::Stats,
+  tui::{EventResponse, Frame},
+};
+
+/// Width of the bar of the largest count of a section.
+const BAR_WIDTH: usize = 20;
+
+/// Dashboard of the counts of the spec: operations by method and tag, gaps in their documentation and security
+/// scheme usage.
+pub struct StatsPopup {
+  stats: Stats,
+  scroll: u16,
+}
+
+impl StatsPopup {
+  pub fn new(stats: Stats) -> Self {
+    Self { stats, scroll: 0 }
+  }
+
+  fn heading(title: &str) -> Line<'_> {
+    Line::styled(title, Style::default().bold().fg(Color::LightBlue))
+  }
+
+  /// Count out of the operations, red when some are concerned.
+  fn gap_line(&self, label: &'static str, count: usize) -> Line<'static> {
+    let style = match count {
+      0 => Style::default().fg(Color::LightGreen),
+      _ => Style::default().fg(Color::LightRed),
+    };
+    Line::from(vec![
+      Span::raw(format!("  {label:<24}")),
+      Span::styled(format!("{count:>5}"), style),
+      Span::styled(format!(" of {}", self.stats.operations), Style::default().dim()),
+    ])
+  }
+
+  /// A line per count, with a bar relative to the largest one.
+  fn bar_lines(counts: &BTreeMap<String, usize>) -> Vec<Line<'static>> {
+    let max = counts.values().max().copied().unwrap_or_default().max(1);
+    let width = counts.keys().map(|name| name.chars().count()).max().unwrap_or_default();
+    counts
+      .iter()
+      .map(|(name, count)| {
+        Line::from(vec![
+          Span::raw(format!("  {name:<width$} ")),
+          Span::styled(format!("{count:>5} "), Color::White),
+          Span::styled("█".repeat(count * BAR_WIDTH / max), Color::LightBlue),
+        ])
+      })
+      .collect()
+  }
+
+  fn overview(&self) -> Vec<Line<'static>> {
+    let stats = &self.stats;
+    let mut lines = vec![
+      Self::heading("Overview"),
+      Line::from(format!("  {:<24}{:>5}", "Operations", stats.operations)),
+      Line::from(format!("  {:<24}{:>5}", "Schemas", stats.schemas)),
+      Line::from(format!("  {:<24}{:>5}", "Deprecated", stats.deprecated)),
+      Line::default(),
+      Self::heading("Missing"),
+      self.gap_line("Description or summary", stats.missing_descriptions),
+      self.gap_line("operationId", stats.missing_operation_ids),
+      self.gap_line("Examples", stats.missing_examples),
+      self.gap_line("Tags", stats.untagged),
+      self.gap_line("Security", stats.unsecured),
+      Line::default(),
+      Self::heading("Methods"),
+    ];
+    lines.extend(Self::bar_lines(&stats.methods));
+    lines
+  }
+
+  fn usage(&self) -> Vec<Line<'static>> {
+    let mut lines = vec![Self::heading("Security schemes")];
+    match self.stats.security.is_empty() {
+      true => lines.push(Line::styled("  No security schemes declared.", Style::default().dim())),
+      false => lines.extend(Self::bar_lines(&self.stats.security)),
+    }
+    lines.extend([Line::default(), Self::heading("Tags")]);
+    match self.stats.tags.is_empty() {
+      true => lines.push(Line::styled("  No tagged operations.", Style::default().dim())),
+      false => lines.extend(Self::bar_lines(&self.stats.tags)),
+    }
+    lines
+  }
+}
+
+impl Popup for StatsPopup {
+  fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
+    match key.code {
+      KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter => Ok(Some(EventResponse::Stop(Action::ClosePopup))),
+      _ => Ok(None),
+    }
+  }
+
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    match action {
+      Action::Down => self.scroll = self.scroll.saturating_add(1),
+      Action::Up => self.scroll = self.scroll.saturating_sub(1),
+      Action::Top => self.scroll = 0,
+      _ => {},
+    }
+    Ok(None)
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    let inner = area.inner(&Margin { horizontal: 2, vertical: 1 });
+    let layout = Layout::default()
+      .direction(Direction::Horizontal)
+      .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+      .split(inner);
+    frame.render_widget(Paragraph::new(self.overview()).scroll((self.scroll, 0)), layout[0]);
+    frame.render_widget(Paragraph::new(self.usage()).scroll((self.scroll, 0)), layout[1]);
+
+    const ARROW: &str = symbols::scrollbar::HORIZONTAL.end;
+    frame.render_widget(
+      Block::default().title("Statistics").borders(Borders::ALL).border_type(BorderType::Double).title_bottom(
+        Line::from(format!("[j/k {ARROW} scroll] [esc {ARROW} close]")).style(Style::default().dim()).right_aligned(),
+      ),
+      area,
+    );
+    Ok(())
+  }
+}
@@ -0,0 +1,104 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+  prelude::*,
+  widgets::{block::*, *},
+};
+
+use crate::{
+  action::Action,
+  codegen::Language,
+  popups::Popup,
+  theme,
+  tui::{EventResponse, Frame},
+};
+
+/// Shows the TypeScript declarations and Rust structs generated for a schema, to copy them or write them to a file.
+pub struct TypesPopup {
+  name: String,
+  codes: Vec<(Language, String)>,
+  language_index: usize,
+  line_offset: u16,
+}
+
+impl TypesPopup {
+  pub fn new(name: String, codes: Vec<(Language, String)>) -> Self {
+    Self { name, codes, language_index: 0, line_offset: 0 }
+  }
+
+  fn code(&self) -> &str {
+    self.codes.get(self.language_index).map(|(_, code)| code.as_str()).unwrap_or_default()
+  }
+
+  fn select_language(&mut self, index: usize) {
+    self.language_index = index % self.codes.len().max(1);
+    self.line_offset = 0;
+  }
+}
+
+impl Popup for TypesPopup {
+  fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
+    let response = match key.code {
+      KeyCode::Char('y') | KeyCode::Char('Y') => EventResponse::Stop(Action::Copy(self.code().to_string())),
+      KeyCode::Char('e') => {
+        let Some((language, code)) = self.codes.get(self.language_index) else {
+          return Ok(None);
+        };
+        EventResponse::Stop(Action::ExportTypes(self.name.clone(), language.extension().to_string(), code.clone()))
+      },
+      KeyCode::Esc | KeyCode::Char('q') => EventResponse::Stop(Action::ClosePopup),
+      _ => return Ok(None),
+    };
+    Ok(Some(response))
+  }
+
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    match action {
+      Action::FocusNext => self.select_language(self.language_index.saturating_add(1)),
+      Action::FocusPrev => self.select_language(self.language_index.saturating_add(self.codes.len().saturating_sub(1))),
+      Action::Tab(index) if index < self.codes.len().try_into()? => self.select_language(index.try_into()?),
+      Action::Down => {
+        let lines_len = u16::try_from(self.code().lines().count().saturating_sub(1)).unwrap_or(u16::MAX);
+        self.line_offset = self.line_offset.saturating_add(1).min(lines_len);
+      },
+      Action::Up => {
+        self.line_offset = self.line_offset.saturating_sub(1);
+      },
+      _ => {},
+    }
+    Ok(None)
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    let inner = area.inner(&Margin { horizontal: 1, vertical: 1 });
+    let layout = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints([Constraint::Max(2), Constraint::Fill(1)])
+      .split(inner);
+
+    frame.render_widget(
+      Tabs::new(self.codes.iter().map(|(language, _)| Span::styled(language.to_string(), Style::default().dim())))
+        .highlight_style(theme::current().active_tab)
+        .select(self.language_index),
+      layout[0],
+    );
+    frame.render_widget(Paragraph::new(self.code()).scroll((self.line_offset, 0)), layout[1]);
+
+    const ARROW: &str = symbols::scrollbar::HORIZONTAL.end;
+    frame.render_widget(
+      Block::default()
+        .title(format!("Types: {}", self.name))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double)
+        .title_bottom(
+          Line::from(format!(
+            "[h/l {ARROW} language] [j/k {ARROW} scroll] [y {ARROW} copy] [e {ARROW} export] [esc {ARROW} close]"
+          ))
+          .style(Style::default().dim())
+          .right_aligned(),
+        ),
+      area,
+    );
+    Ok(())
+  }
+}
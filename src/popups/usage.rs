@@ -0,0 +1,103 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+  prelude::*,
+  widgets::{block::*, *},
+};
+
+use crate::{
+  action::Action,
+  popups::Popup,
+  theme,
+  tui::{EventResponse, Frame},
+  usage::Usage,
+};
+
+/// Lists the operations using a schema, enter jumps to the selected one.
+pub struct UsagePopup {
+  schema: String,
+  usages: Vec<Usage>,
+  usage_index: usize,
+}
+
+impl UsagePopup {
+  pub fn new(schema: String, usages: Vec<Usage>) -> Self {
+    Self { schema, usages, usage_index: 0 }
+  }
+
+  fn usage_line(usage: &Usage) -> Line<'_> {
+    let mut spans = vec![
+      Span::styled(format!("{:7} ", usage.operation.method), Style::default().bold()),
+      Span::raw(usage.operation.path.as_str()),
+      Span::styled(format!("  {}", usage.location), Style::default().fg(Color::LightBlue)),
+    ];
+    if let Some(via) = &usage.via {
+      spans.push(Span::styled(format!("  via {via}"), Style::default().dim()));
+    }
+    Line::from(spans)
+  }
+}
+
+impl Popup for UsagePopup {
+  fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
+    let response = match key.code {
+      KeyCode::Enter => {
+        match self.usages.get(self.usage_index) {
+          Some(usage) => EventResponse::Stop(Action::SelectOperation(usage.operation.clone())),
+          None => EventResponse::Stop(Action::ClosePopup),
+        }
+      },
+      KeyCode::Esc | KeyCode::Char('q') => EventResponse::Stop(Action::ClosePopup),
+      _ => return Ok(None),
+    };
+    Ok(Some(response))
+  }
+
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    let usages_len = self.usages.len().max(1);
+    match action {
+      Action::Down => self.usage_index = self.usage_index.saturating_add(1) % usages_len,
+      Action::Up => self.usage_index = self.usage_index.saturating_add(usages_len - 1) % usages_len,
+      _ => {},
+    }
+    Ok(None)
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    let inner = area.inner(&Margin { horizontal: 1, vertical: 1 });
+    let layout = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints([Constraint::Max(2), Constraint::Fill(1)])
+      .split(inner);
+
+    let operations = self.usages.iter().map(|usage| &usage.operation).collect::<std::collections::BTreeSet<_>>().len();
+    frame.render_widget(
+      Paragraph::new(Line::from(vec![
+        Span::styled(self.schema.as_str(), Style::default().bold()),
+        Span::styled(format!("  used {} times by {operations} operations", self.usages.len()), Style::default().dim()),
+      ])),
+      layout[0],
+    );
+    let items: Vec<Line<'_>> = self.usages.iter().map(Self::usage_line).collect();
+    let mut list_state = ListState::default().with_selected((!items.is_empty()).then_some(self.usage_index));
+    frame.render_stateful_widget(
+      List::new(items)
+        .highlight_symbol(symbols::scrollbar::HORIZONTAL.end)
+        .highlight_spacing(HighlightSpacing::Always)
+        .highlight_style(theme::current().selection),
+      layout[1],
+      &mut list_state,
+    );
+
+    const ARROW: &str = symbols::scrollbar::HORIZONTAL.end;
+    frame.render_widget(
+      Block::default().title("Usages").borders(Borders::ALL).border_type(BorderType::Double).title_bottom(
+        Line::from(format!("[j/k {ARROW} usage] [enter {ARROW} go to operation] [esc {ARROW} close]"))
+          .style(Style::default().dim())
+          .right_aligned(),
+      ),
+      area,
+    );
+    Ok(())
+  }
+}
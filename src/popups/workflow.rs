@@ -0,0 +1,205 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+  prelude::*,
+  widgets::{block::*, *},
+};
+
+use crate::{
+  action::Action,
+  popups::Popup,
+  report::{TestReport, TestSuite},
+  theme,
+  tui::{EventResponse, Frame},
+  workflow::{StepOutcome, Workflow},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum StepStatus {
+  Pending,
+  Running,
+  Finished(Box<StepOutcome>),
+}
+
+/// Runs the loaded workflows and shows how each of their steps went.
+pub struct WorkflowPopup {
+  workflows: Vec<Workflow>,
+  workflow_index: usize,
+  step_index: usize,
+  statuses: Vec<StepStatus>,
+}
+
+impl WorkflowPopup {
+  pub fn new(workflows: Vec<Workflow>) -> Self {
+    let mut popup = Self { workflows, workflow_index: 0, step_index: 0, statuses: vec![] };
+    popup.select_workflow(0);
+    popup
+  }
+
+  fn workflow(&self) -> Option<&Workflow> {
+    self.workflows.get(self.workflow_index)
+  }
+
+  fn running(&self) -> bool {
+    self.statuses.contains(&StepStatus::Running)
+  }
+
+  fn select_workflow(&mut self, index: usize) {
+    if self.running() || self.workflows.is_empty() {
+      return;
+    }
+    self.workflow_index = index % self.workflows.len();
+    self.step_index = 0;
+    self.statuses = vec![StepStatus::Pending; self.workflow().map_or(0, |workflow| workflow.steps.len())];
+  }
+
+  fn report(&self) -> Option<TestReport> {
+    let workflow = self.workflow()?;
+    let outcomes: Vec<_> = self
+      .statuses
+      .iter()
+      .map(|status| {
+        match status {
+          StepStatus::Finished(outcome) => Some(*outcome.clone()),
+          _ => None,
+        }
+      })
+      .collect();
+    Some(TestReport {
+      name: format!("workflow-{}", workflow.name),
+      suites: vec![TestSuite::workflow(workflow, &outcomes)],
+    })
+  }
+
+  fn step_line(&self, index: usize) -> Line<'_> {
+    let step = &self.workflows[self.workflow_index].steps[index];
+    let (symbol, style) = match &self.statuses[index] {
+      StepStatus::Pending => ("·", Style::default().dim()),
+      StepStatus::Running => ("…", Style::default().fg(Color::LightBlue)),
+      StepStatus::Finished(outcome) if outcome.passed() => ("✓", Style::default().fg(Color::LightGreen)),
+      StepStatus::Finished(_) => ("✗", Style::default().fg(Color::LightRed)),
+    };
+    let mut spans = vec![
+      Span::styled(format!("{symbol} "), style),
+      Span::raw(step.id.as_str()),
+      Span::styled(format!("  {}", step.operation), Style::default().dim()),
+    ];
+    if let StepStatus::Finished(outcome) = &self.statuses[index] {
+      if let Some(exchange) = &outcome.exchange {
+        spans.push(Span::styled(format!("  {}", exchange.response.status), style));
+        spans.push(Span::styled(format!("  {} ms", exchange.elapsed_ms), Style::default().dim()));
+      }
+    }
+    Line::from(spans)
+  }
+
+  fn detail_lines(&self) -> Vec<Line<'_>> {
+    let Some(StepStatus::Finished(outcome)) = self.statuses.get(self.step_index) else {
+      return vec![];
+    };
+    let mut lines = vec![];
+    if let Some(exchange) = &outcome.exchange {
+      lines.push(Line::styled(
+        format!("{} {}", exchange.request.method, exchange.request.full_url()),
+        Style::default().dim(),
+      ));
+    }
+    let problem = Style::default().fg(Color::LightRed);
+    lines.extend(outcome.error.iter().map(|error| Line::styled(error.as_str(), problem)));
+    lines.extend(outcome.failures.iter().map(|failure| Line::styled(format!("failed: {failure}"), problem)));
+    lines.extend(outcome.extracted.iter().map(|(variable, value)| {
+      Line::from(vec![
+        Span::styled(format!("{variable} = "), Style::default().dim()),
+        Span::styled(value.as_str(), Style::default().fg(Color::LightGreen)),
+      ])
+    }));
+    lines
+  }
+}
+
+impl Popup for WorkflowPopup {
+  fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<EventResponse<Action>>> {
+    let response = match key.code {
+      KeyCode::Enter if !self.running() && self.workflow().is_some_and(|workflow| !workflow.steps.is_empty()) => {
+        self.select_workflow(self.workflow_index);
+        self.statuses[0] = StepStatus::Running;
+        EventResponse::Stop(Action::RunWorkflow(self.workflow_index))
+      },
+      KeyCode::Char('e') if self.statuses.iter().any(|status| matches!(status, StepStatus::Finished(_))) => {
+        match self.report() {
+          Some(report) => EventResponse::Stop(Action::ExportReport(Box::new(report))),
+          None => return Ok(None),
+        }
+      },
+      KeyCode::Esc | KeyCode::Char('q') => EventResponse::Stop(Action::ClosePopup),
+      _ => return Ok(None),
+    };
+    Ok(Some(response))
+  }
+
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    let steps_len = self.statuses.len().max(1);
+    match action {
+      Action::Down => self.step_index = self.step_index.saturating_add(1) % steps_len,
+      Action::Up => self.step_index = self.step_index.saturating_add(steps_len - 1) % steps_len,
+      Action::FocusNext => self.select_workflow(self.workflow_index.saturating_add(1)),
+      Action::FocusPrev => {
+        self.select_workflow(self.workflow_index.saturating_add(self.workflows.len().saturating_sub(1)))
+      },
+      Action::WorkflowStepFinished(index, outcome) if index < self.statuses.len() => {
+        let passed = outcome.passed();
+        self.statuses[index] = StepStatus::Finished(outcome);
+        if let Some(next) = self.statuses.get_mut(index + 1).filter(|_| passed) {
+          *next = StepStatus::Running;
+        }
+        self.step_index = index;
+      },
+      _ => {},
+    }
+    Ok(None)
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    let inner = area.inner(&Margin { horizontal: 1, vertical: 1 });
+    let layout = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints([Constraint::Max(2), Constraint::Max(2), Constraint::Fill(1), Constraint::Fill(1)])
+      .split(inner);
+
+    frame.render_widget(
+      Tabs::new(self.workflows.iter().map(|workflow| Span::styled(workflow.name.as_str(), Style::default().dim())))
+        .highlight_style(theme::current().active_tab)
+        .select(self.workflow_index),
+      layout[0],
+    );
+    let description = self.workflow().and_then(|workflow| workflow.description.clone()).unwrap_or_default();
+    frame.render_widget(Paragraph::new(description).style(Style::default().dim()), layout[1]);
+
+    let items: Vec<Line<'_>> = (0..self.statuses.len()).map(|index| self.step_line(index)).collect();
+    let mut list_state = ListState::default().with_selected((!items.is_empty()).then_some(self.step_index));
+    frame.render_stateful_widget(
+      List::new(items).highlight_symbol(symbols::scrollbar::HORIZONTAL.end).highlight_spacing(HighlightSpacing::Always),
+      layout[2],
+      &mut list_state,
+    );
+    frame.render_widget(
+      Paragraph::new(self.detail_lines())
+        .wrap(Wrap { trim: false })
+        .block(Block::default().borders(Borders::TOP).border_style(Style::default().dim())),
+      layout[3],
+    );
+
+    const ARROW: &str = symbols::scrollbar::HORIZONTAL.end;
+    frame.render_widget(
+      Block::default().title("Workflow Runner").borders(Borders::ALL).border_type(BorderType::Double).title_bottom(
+        Line::from(format!(
+          "[h/l {ARROW} workflow] [j/k {ARROW} step] [enter {ARROW} run] [e {ARROW} export] [esc {ARROW} close]"
+        ))
+        .style(Style::default().dim())
+        .right_aligned(),
+      ),
+      area,
+    );
+    Ok(())
+  }
+}
@@ -0,0 +1,124 @@
+use std::time::Instant;
+
+use crate::client::Exchange;
+
+/// Finished requests beyond this count are dropped, oldest first.
+const MAX_FINISHED: usize = 50;
+
+/// Outcome of a queued request, pending until its response or error comes back.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+  Pending,
+  Received(Box<Exchange>),
+  Failed(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct Queued {
+  pub id: usize,
+  pub method: String,
+  pub path: String,
+  pub sent_at: Instant,
+  pub outcome: Outcome,
+}
+
+/// Requests sent during this session, in the order they were sent, each sent without waiting for the others.
+#[derive(Debug, Default)]
+pub struct Queue {
+  entries: Vec<Queued>,
+  next_id: usize,
+}
+
+impl Queue {
+  /// Adds a pending request and returns the id its outcome is reported with.
+  pub fn push(&mut self, method: &str, path: &str) -> usize {
+    let id = self.next_id;
+    self.next_id += 1;
+    self.entries.push(Queued {
+      id,
+      method: method.to_uppercase(),
+      path: path.to_string(),
+      sent_at: Instant::now(),
+      outcome: Outcome::Pending,
+    });
+    self.trim();
+    id
+  }
+
+  pub fn receive(&mut self, id: usize, exchange: Exchange) {
+    self.finish(id, Outcome::Received(Box::new(exchange)));
+  }
+
+  pub fn fail(&mut self, id: usize, error: String) {
+    self.finish(id, Outcome::Failed(error));
+  }
+
+  fn finish(&mut self, id: usize, outcome: Outcome) {
+    if let Some(entry) = self.entries.iter_mut().find(|entry| entry.id == id) {
+      entry.outcome = outcome;
+    }
+    self.trim();
+  }
+
+  /// Drops the oldest finished requests beyond the limit, pending ones are kept whatever their age.
+  fn trim(&mut self) {
+    let mut excess = self.entries.iter().filter(|entry| entry.outcome != Outcome::Pending).count();
+    excess = excess.saturating_sub(MAX_FINISHED);
+    self.entries.retain(|entry| {
+      if excess > 0 && entry.outcome != Outcome::Pending {
+        excess -= 1;
+        return false;
+      }
+      true
+    });
+  }
+
+  pub fn pending(&self) -> usize {
+    self.entries.iter().filter(|entry| entry.outcome == Outcome::Pending).count()
+  }
+
+  pub fn entries(&self) -> &[Queued] {
+    &self.entries
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.entries.is_empty()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+  use crate::client::HttpResponse;
+
+  #[test]
+  fn test_queue() {
+    let mut queue = Queue::default();
+    let pets = queue.push("get", "/pets");
+    let owners = queue.push("get", "/owners");
+    let orders = queue.push("post", "/orders");
+    assert_eq!(queue.pending(), 3);
+
+    let exchange =
+      Exchange { response: HttpResponse { status: 200, ..HttpResponse::default() }, ..Exchange::default() };
+    queue.receive(owners, exchange.clone());
+    queue.fail(orders, String::from("connection refused"));
+    assert_eq!(queue.pending(), 1);
+    let outcomes = queue.entries().iter().map(|entry| (entry.method.as_str(), &entry.outcome)).collect::<Vec<_>>();
+    assert_eq!(outcomes, vec![
+      ("GET", &Outcome::Pending),
+      ("GET", &Outcome::Received(Box::new(exchange))),
+      ("POST", &Outcome::Failed(String::from("connection refused")))
+    ]);
+
+    for _ in 0..MAX_FINISHED {
+      let id = queue.push("get", "/pets");
+      queue.fail(id, String::from("timed out"));
+    }
+    assert_eq!(queue.entries().len(), MAX_FINISHED + 1);
+    assert_eq!(queue.entries()[0].id, pets);
+    assert_eq!(queue.entries()[1].path, "/pets");
+  }
+}
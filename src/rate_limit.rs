@@ -0,0 +1,145 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use color_eyre::eyre::Result;
+
+use crate::{
+  client::{Exchange, HttpResponse},
+  request::HttpRequest,
+  script::{self, Hooks},
+  workspace::OperationKey,
+};
+
+/// Longest pause waited for a rate limit to reset, past it runs go on and get the 429s.
+const MAX_WAIT: Duration = Duration::from_secs(300);
+/// Times a request answered with a 429 is sent again once the limit reset.
+const MAX_RETRIES: usize = 3;
+/// Reset values past this are Unix timestamps rather than seconds to wait, as GitHub sends them.
+const TIMESTAMP_THRESHOLD: u64 = 1_000_000_000;
+
+/// Quota told by the `X-RateLimit-*`, `RateLimit-*` and `Retry-After` headers of a response.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RateLimit {
+  pub limit: Option<u64>,
+  pub remaining: Option<u64>,
+  /// Seconds until the quota resets, from when the response was received.
+  pub reset: Option<u64>,
+  /// Seconds the server asks to wait before the next request.
+  pub retry_after: Option<u64>,
+}
+
+impl RateLimit {
+  /// The quota of the response, `None` when it has none of the headers. `now` is the Unix time the reset and retry
+  /// dates are counted from.
+  pub fn of(response: &HttpResponse, now: i64) -> Option<Self> {
+    let number = |names: &[&str]| {
+      names.iter().find_map(|name| response.header(name)).and_then(|value| value.trim().parse::<u64>().ok())
+    };
+    let reset = number(&["X-RateLimit-Reset", "RateLimit-Reset"]).map(|reset| {
+      match reset > TIMESTAMP_THRESHOLD {
+        true => reset.saturating_sub(now.try_into().unwrap_or_default()),
+        false => reset,
+      }
+    });
+    let retry_after = response.header("Retry-After").and_then(|value| {
+      let value = value.trim();
+      value.parse::<u64>().ok().or_else(|| {
+        let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+        Some(date.timestamp().saturating_sub(now).max(0).try_into().unwrap_or_default())
+      })
+    });
+    let rate_limit = Self {
+      limit: number(&["X-RateLimit-Limit", "RateLimit-Limit"]),
+      remaining: number(&["X-RateLimit-Remaining", "RateLimit-Remaining"]),
+      reset,
+      retry_after,
+    };
+    (rate_limit != Self::default()).then_some(rate_limit)
+  }
+
+  /// Pause before the next request, when the response was a 429 or the quota is used up.
+  pub fn wait(&self, status: u16) -> Option<Duration> {
+    let seconds = match status {
+      429 | 503 if self.retry_after.is_some() => self.retry_after,
+      429 => self.reset,
+      _ if self.remaining == Some(0) => self.reset.or(self.retry_after),
+      _ => None,
+    }?;
+    Some(Duration::from_secs(seconds.max(1)))
+  }
+}
+
+impl std::fmt::Display for RateLimit {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let mut parts = vec![];
+    match (self.remaining, self.limit) {
+      (Some(remaining), Some(limit)) => parts.push(format!("quota {remaining}/{limit}")),
+      (Some(remaining), None) => parts.push(format!("quota {remaining} left")),
+      _ => {},
+    }
+    if let Some(reset) = self.reset {
+      parts.push(format!("resets in {reset}s"));
+    }
+    if let Some(retry_after) = self.retry_after {
+      parts.push(format!("retry after {retry_after}s"));
+    }
+    write!(f, "{}", parts.join(", "))
+  }
+}
+
+/// Sends the request as `script::exchange` does for runs of many requests: it waits for the quota to reset once it is
+/// used up, and sends the request again when it was answered with a 429.
+pub async fn exchange(
+  operation: Option<OperationKey>,
+  request: HttpRequest,
+  hooks: &Hooks,
+  variables: &BTreeMap<String, String>,
+) -> Result<Exchange> {
+  let mut retries = 0;
+  loop {
+    let exchange = script::exchange(operation.clone(), request.clone(), hooks, variables).await?;
+    let response = &exchange.response;
+    let wait = RateLimit::of(response, chrono::Utc::now().timestamp()).and_then(|limit| limit.wait(response.status));
+    let Some(wait) = wait.filter(|wait| *wait <= MAX_WAIT) else {
+      return Ok(exchange);
+    };
+    log::info!("Rate limited by {} {}, waiting {}s", request.method, request.path, wait.as_secs());
+    tokio::time::sleep(wait).await;
+    if response.status != 429 || retries == MAX_RETRIES {
+      return Ok(exchange);
+    }
+    retries += 1;
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  fn response(status: u16, headers: &[(&str, &str)]) -> HttpResponse {
+    let headers = headers.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect();
+    HttpResponse { status, headers, ..HttpResponse::default() }
+  }
+
+  #[test]
+  fn test_of() {
+    let now = 1_700_000_000;
+    let github = response(200, &[
+      ("x-ratelimit-limit", "60"),
+      ("x-ratelimit-remaining", "0"),
+      ("x-ratelimit-reset", "1700000042"),
+    ]);
+    let rate_limit = RateLimit::of(&github, now).unwrap();
+    assert_eq!(rate_limit, RateLimit { limit: Some(60), remaining: Some(0), reset: Some(42), retry_after: None });
+    assert_eq!(rate_limit.to_string(), "quota 0/60, resets in 42s");
+    assert_eq!(rate_limit.wait(200), Some(Duration::from_secs(42)));
+
+    let retry = response(429, &[("Retry-After", "Tue, 14 Nov 2023 22:13:40 GMT"), ("RateLimit-Remaining", "3")]);
+    let rate_limit = RateLimit::of(&retry, now).unwrap();
+    assert_eq!(rate_limit.retry_after, Some(20));
+    assert_eq!(rate_limit.wait(429), Some(Duration::from_secs(20)));
+    assert_eq!(rate_limit.wait(200), None);
+    assert_eq!(RateLimit::of(&response(200, &[("Content-Type", "text/plain")]), now), None);
+  }
+}
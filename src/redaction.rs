@@ -0,0 +1,194 @@
+use color_eyre::eyre::Result;
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::{client::Exchange, multipart::FormPart, request::HttpRequest};
+
+/// Shown in place of redacted values.
+pub const REDACTED: &str = "[redacted]";
+
+/// Rules of the `redaction` section of the config, applied to what leaves the app: the history, HAR exports, logs
+/// and code snippets, e.g. `{ headers: ["X-Customer-Id"], fields: ["email"], patterns: ["\\d{16}"] }`.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct RedactionConfig {
+  /// Headers whose values are redacted, case insensitive.
+  #[serde(default)]
+  pub headers: Vec<String>,
+  /// Fields redacted at any depth of JSON bodies, along with the query parameters and form fields of the same name,
+  /// case insensitive.
+  #[serde(default)]
+  pub fields: Vec<String>,
+  /// Regular expressions whose matches are redacted anywhere in the url, the headers and the bodies.
+  #[serde(default)]
+  pub patterns: Vec<String>,
+}
+
+/// The redaction rules of the config, with their patterns compiled.
+#[derive(Clone, Debug, Default)]
+pub struct Redactor {
+  headers: Vec<String>,
+  fields: Vec<String>,
+  patterns: Vec<Regex>,
+}
+
+impl Redactor {
+  pub fn new(config: &RedactionConfig) -> Result<Self> {
+    Ok(Self {
+      headers: config.headers.iter().map(|name| name.to_lowercase()).collect(),
+      fields: config.fields.iter().map(|name| name.to_lowercase()).collect(),
+      patterns: config.patterns.iter().map(|pattern| Regex::new(pattern)).collect::<Result<_, _>>()?,
+    })
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.headers.is_empty() && self.fields.is_empty() && self.patterns.is_empty()
+  }
+
+  fn is_field(&self, name: &str) -> bool {
+    self.fields.contains(&name.to_lowercase())
+  }
+
+  fn text(&self, text: &str) -> String {
+    self.patterns.iter().fold(text.to_string(), |text, pattern| pattern.replace_all(&text, REDACTED).into_owned())
+  }
+
+  fn pairs(&self, pairs: &[(String, String)], names: &[String]) -> Vec<(String, String)> {
+    pairs
+      .iter()
+      .map(|(name, value)| {
+        match names.contains(&name.to_lowercase()) {
+          true => (name.clone(), REDACTED.to_string()),
+          false => (name.clone(), self.text(value)),
+        }
+      })
+      .collect()
+  }
+
+  fn json(&self, value: &mut Value) {
+    match value {
+      Value::Object(object) => {
+        for (name, value) in object.iter_mut() {
+          match self.is_field(name) {
+            true => *value = Value::String(REDACTED.to_string()),
+            false => self.json(value),
+          }
+        }
+      },
+      Value::Array(items) => items.iter_mut().for_each(|item| self.json(item)),
+      _ => {},
+    }
+  }
+
+  /// Redacts the fields of a JSON body, keeping it pretty printed when it was, then the matches of the patterns.
+  fn body(&self, body: &str) -> String {
+    let body = match serde_json::from_str::<Value>(body) {
+      Ok(mut json) if !self.fields.is_empty() => {
+        self.json(&mut json);
+        match body.contains('\n') {
+          true => serde_json::to_string_pretty(&json),
+          false => serde_json::to_string(&json),
+        }
+        .unwrap_or_else(|_| body.to_string())
+      },
+      _ => body.to_string(),
+    };
+    self.text(&body)
+  }
+
+  pub fn request(&self, request: &HttpRequest) -> HttpRequest {
+    if self.is_empty() {
+      return request.clone();
+    }
+    let form = request
+      .form
+      .iter()
+      .map(|part| {
+        match self.is_field(&part.name) && !part.file {
+          true => FormPart { value: REDACTED.to_string(), ..part.clone() },
+          false => FormPart { value: self.text(&part.value), ..part.clone() },
+        }
+      })
+      .collect();
+    HttpRequest {
+      base_url: self.text(&request.base_url),
+      path: self.text(&request.path),
+      query: self.pairs(&request.query, &self.fields),
+      headers: self.pairs(&request.headers, &self.headers),
+      body: request.body.as_deref().map(|body| self.body(body)),
+      form,
+      ..request.clone()
+    }
+  }
+
+  /// Redacts the request like [`Redactor::request`], and the headers and body of the response.
+  pub fn exchange(&self, exchange: &Exchange) -> Exchange {
+    if self.is_empty() {
+      return exchange.clone();
+    }
+    let mut redacted = exchange.clone();
+    redacted.request = self.request(&exchange.request);
+    redacted.response.headers = self.pairs(&exchange.response.headers, &self.headers);
+    redacted.response.body = self.body(&exchange.response.body);
+    redacted.response.raw_body = None;
+    redacted
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+  use crate::client::HttpResponse;
+
+  #[test]
+  fn test_redactor() {
+    let config = RedactionConfig {
+      headers: vec![String::from("X-Customer-Id")],
+      fields: vec![String::from("email")],
+      patterns: vec![String::from(r"\b\d{4}-\d{4}-\d{4}-\d{4}\b")],
+    };
+    let redactor = Redactor::new(&config).unwrap();
+    let exchange = Exchange {
+      request: HttpRequest {
+        method: String::from("post"),
+        base_url: String::from("https://shop.example.com"),
+        path: String::from("/cards/4111-1111-1111-1111"),
+        query: vec![(String::from("email"), String::from("ann@example.com"))],
+        headers: vec![
+          (String::from("x-customer-id"), String::from("42")),
+          (String::from("Accept"), String::from("application/json")),
+        ],
+        body: Some(String::from(
+          "{\n  \"Email\": \"ann@example.com\",\n  \"tags\": [{ \"email\": \"bob@example.com\" }]\n}",
+        )),
+        ..HttpRequest::default()
+      },
+      response: HttpResponse {
+        status: 200,
+        headers: vec![(String::from("X-Customer-Id"), String::from("42"))],
+        body: String::from("{\"card\":\"4111-1111-1111-1111\",\"name\":\"Ann\"}"),
+        ..HttpResponse::default()
+      },
+      ..Exchange::default()
+    };
+    let redacted = redactor.exchange(&exchange);
+    assert_eq!(redacted.request.path, "/cards/[redacted]");
+    assert_eq!(redacted.request.query, vec![(String::from("email"), String::from(REDACTED))]);
+    assert_eq!(redacted.request.headers, vec![
+      (String::from("x-customer-id"), String::from(REDACTED)),
+      (String::from("Accept"), String::from("application/json"))
+    ]);
+    assert_eq!(
+      redacted.request.body.as_deref(),
+      Some("{\n  \"Email\": \"[redacted]\",\n  \"tags\": [\n    {\n      \"email\": \"[redacted]\"\n    }\n  ]\n}")
+    );
+    assert_eq!(redacted.response.headers, vec![(String::from("X-Customer-Id"), String::from(REDACTED))]);
+    assert_eq!(redacted.response.body, "{\"card\":\"[redacted]\",\"name\":\"Ann\"}");
+
+    assert_eq!(Redactor::default().exchange(&exchange), exchange);
+    let invalid = RedactionConfig { patterns: vec![String::from("(")], ..RedactionConfig::default() };
+    assert!(Redactor::new(&invalid).is_err());
+  }
+}
@@ -0,0 +1,76 @@
+use std::sync::{Arc, Mutex, RwLock};
+
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+
+/// Redirects followed when neither `--max-redirects` nor the config file tell otherwise, as browsers do.
+pub const DEFAULT_MAX_REDIRECTS: usize = 10;
+
+lazy_static! {
+  static ref MAX_REDIRECTS: RwLock<usize> = RwLock::new(DEFAULT_MAX_REDIRECTS);
+}
+
+/// Largest number of redirects followed by a request, 0 when they are not followed.
+pub fn max() -> usize {
+  *MAX_REDIRECTS.read().unwrap()
+}
+
+pub fn set_max(max_redirects: usize) {
+  *MAX_REDIRECTS.write().unwrap() = max_redirects;
+}
+
+/// Redirect followed on the way to a response.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Redirect {
+  pub status: u16,
+  /// Url answered with the redirect.
+  pub url: String,
+  /// Url the `Location` header pointed to, resolved against the url.
+  pub location: String,
+}
+
+/// The redirect from the last of the urls requested so far to the next one, `None` when it is not followed as the
+/// redirects before it reached the maximum.
+pub fn follow(previous: &[reqwest::Url], status: u16, next: &reqwest::Url, max_redirects: usize) -> Option<Redirect> {
+  let url = previous.last()?;
+  // the first url was requested, not redirected to
+  (previous.len() <= max_redirects).then(|| Redirect { status, url: url.to_string(), location: next.to_string() })
+}
+
+/// Policy following up to `max` redirects, recording them in the chain, and handing back the redirect response itself
+/// once there are more.
+pub fn policy(chain: Arc<Mutex<Vec<Redirect>>>) -> reqwest::redirect::Policy {
+  let max_redirects = max();
+  reqwest::redirect::Policy::custom(move |attempt| {
+    match follow(attempt.previous(), attempt.status().as_u16(), attempt.url(), max_redirects) {
+      Some(redirect) => {
+        chain.lock().unwrap().push(redirect);
+        attempt.follow()
+      },
+      None => attempt.stop(),
+    }
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_follow() {
+    let url = |text: &str| reqwest::Url::parse(text).unwrap();
+    let previous = [url("http://example.com/pets"), url("https://example.com/pets")];
+    assert_eq!(
+      follow(&previous[..1], 301, &previous[1], 1),
+      Some(Redirect {
+        status: 301,
+        url: String::from("http://example.com/pets"),
+        location: String::from("https://example.com/pets"),
+      })
+    );
+    assert_eq!(follow(&previous, 302, &url("https://example.com/v2/pets"), 1), None);
+    assert_eq!(follow(&previous[..1], 301, &previous[1], 0), None);
+  }
+}
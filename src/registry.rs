@@ -0,0 +1,227 @@
+use std::collections::BTreeMap;
+
+use color_eyre::eyre::{eyre, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::config::RegistryConfig;
+
+/// How a registry lists its APIs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RegistryKind {
+  /// JSON document listing the APIs and the urls of their versions, see [`parse_index`].
+  #[default]
+  Index,
+  /// SwaggerHub registry API, e.g. `https://api.swaggerhub.com/apis/<owner>`.
+  SwaggerHub,
+  /// Apicurio Registry, by its base url, e.g. `http://localhost:8080`.
+  Apicurio,
+}
+
+/// Version of an API of a registry, with the url of its spec.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegistryApi {
+  /// Name of the registry in the config.
+  pub registry: String,
+  pub name: String,
+  pub version: Option<String>,
+  pub description: Option<String>,
+  pub url: String,
+}
+
+/// APIs of the registries, along with the errors of those that could not be listed.
+pub async fn list(registries: &BTreeMap<String, RegistryConfig>) -> (Vec<RegistryApi>, Vec<String>) {
+  let (mut apis, mut errors) = (vec![], vec![]);
+  for (name, registry) in registries {
+    let listed = match registry.kind {
+      RegistryKind::Index => get_json(&registry.url).await.map(|index| parse_index(name, &registry.url, &index)),
+      RegistryKind::SwaggerHub => get_json(&registry.url).await.map(|listing| parse_swaggerhub(name, &listing)),
+      RegistryKind::Apicurio => list_apicurio(name, &registry.url).await,
+    };
+    match listed {
+      Ok(listed) => apis.extend(listed),
+      Err(error) => {
+        log::error!("Failed to list the APIs of the registry {name}: {error:?}");
+        errors.push(format!("{name}: {error}"));
+      },
+    }
+  }
+  (apis, errors)
+}
+
+async fn get_json(url: &str) -> Result<Value> {
+  let response = reqwest::Client::new().get(url).header(reqwest::header::ACCEPT, "application/json").send().await?;
+  if !response.status().is_success() {
+    return Err(eyre!("{url} answered {}", response.status()));
+  }
+  Ok(serde_json::from_slice(&response.bytes().await?)?)
+}
+
+/// APIs of an index, `{ "apis": [{ "name": …, "description": …, "versions": [{ "version": …, "url": … }] }] }`, an API
+/// of a single version giving its `url` directly. Urls are relative to the index.
+pub fn parse_index(registry: &str, index_url: &str, index: &Value) -> Vec<RegistryApi> {
+  let text = |value: &Value, key: &str| value.get(key).and_then(Value::as_str).map(String::from);
+  let apis = index.get("apis").unwrap_or(index).as_array().into_iter().flatten();
+  apis
+    .flat_map(|api| {
+      let versions: Vec<&Value> = match api.get("versions").and_then(Value::as_array) {
+        Some(versions) => versions.iter().collect(),
+        None => vec![api],
+      };
+      versions.into_iter().filter_map(move |version| {
+        Some(RegistryApi {
+          registry: registry.to_string(),
+          name: text(api, "name")?,
+          version: text(version, "version"),
+          description: text(api, "description"),
+          url: join_url(index_url, &text(version, "url")?),
+        })
+      })
+    })
+    .collect()
+}
+
+/// APIs of a SwaggerHub listing, in the APIs.json format, their spec url and version among their `properties`.
+pub fn parse_swaggerhub(registry: &str, listing: &Value) -> Vec<RegistryApi> {
+  let apis = listing.get("apis").and_then(Value::as_array).into_iter().flatten();
+  apis
+    .filter_map(|api| {
+      let properties = api.get("properties").and_then(Value::as_array)?;
+      let property = |kind: &str, key: &str| {
+        properties
+          .iter()
+          .find(|property| property.get("type").and_then(Value::as_str) == Some(kind))
+          .and_then(|property| property.get(key)?.as_str())
+          .map(String::from)
+      };
+      Some(RegistryApi {
+        registry: registry.to_string(),
+        name: api.get("name")?.as_str()?.to_string(),
+        version: property("X-Version", "value"),
+        description: api.get("description").and_then(Value::as_str).map(String::from),
+        url: property("Swagger", "url")?,
+      })
+    })
+    .collect()
+}
+
+/// OpenAPI artifacts of an Apicurio Registry, each version of them.
+async fn list_apicurio(registry: &str, base_url: &str) -> Result<Vec<RegistryApi>> {
+  let api = format!("{}/apis/registry/v2", base_url.trim_end_matches('/'));
+  let search = get_json(&format!("{api}/search/artifacts?limit=500")).await?;
+  let mut apis = vec![];
+  for artifact in search.get("artifacts").and_then(Value::as_array).into_iter().flatten() {
+    if artifact.get("type").and_then(Value::as_str).is_some_and(|kind| kind != "OPENAPI") {
+      continue;
+    }
+    let Some(id) = artifact.get("id").and_then(Value::as_str) else {
+      continue;
+    };
+    let group = artifact.get("groupId").and_then(Value::as_str).unwrap_or("default");
+    let artifact_url = format!("{api}/groups/{group}/artifacts/{id}");
+    let versions = get_json(&format!("{artifact_url}/versions")).await?;
+    apis.extend(parse_apicurio_versions(registry, &artifact_url, artifact, &versions));
+  }
+  Ok(apis)
+}
+
+/// Versions of an Apicurio artifact, from the listing of its versions.
+pub fn parse_apicurio_versions(
+  registry: &str,
+  artifact_url: &str,
+  artifact: &Value,
+  versions: &Value,
+) -> Vec<RegistryApi> {
+  let text = |value: &Value, key: &str| value.get(key).and_then(Value::as_str).map(String::from);
+  let name = text(artifact, "name").or_else(|| text(artifact, "id")).unwrap_or_default();
+  let versions = versions.get("versions").and_then(Value::as_array).into_iter().flatten();
+  versions
+    .filter_map(|version| {
+      let number = text(version, "version")?;
+      Some(RegistryApi {
+        registry: registry.to_string(),
+        name: name.clone(),
+        url: format!("{artifact_url}/versions/{number}"),
+        version: Some(number),
+        description: text(artifact, "description"),
+      })
+    })
+    .collect()
+}
+
+/// The url relative to the base one, e.g. an index.
+fn join_url(base: &str, url: &str) -> String {
+  if url.contains("://") {
+    return url.to_string();
+  }
+  let (scheme, rest) = base.split_once("://").unwrap_or(("https", base));
+  if url.starts_with('/') {
+    let host = rest.split('/').next().unwrap_or_default();
+    return format!("{scheme}://{host}{url}");
+  }
+  let directory = rest.rsplit_once('/').map_or(rest, |(directory, _)| directory);
+  format!("{scheme}://{directory}/{url}")
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+  use serde_json::json;
+
+  use super::*;
+
+  fn api(name: &str, version: Option<&str>, url: &str) -> RegistryApi {
+    RegistryApi {
+      registry: String::from("team"),
+      name: name.to_string(),
+      version: version.map(String::from),
+      description: None,
+      url: url.to_string(),
+    }
+  }
+
+  #[test]
+  fn test_parse_index() {
+    let index = json!({ "apis": [
+      { "name": "Petstore", "versions": [
+        { "version": "1.0.0", "url": "petstore/1.0.0.yaml" },
+        { "version": "2.0.0", "url": "/specs/petstore/2.0.0.yaml" }
+      ] },
+      { "name": "Billing", "url": "https://billing.example.com/openapi.json" },
+      { "name": "Unreachable" }
+    ] });
+    assert_eq!(parse_index("team", "https://specs.example.com/registry/index.json", &index), vec![
+      api("Petstore", Some("1.0.0"), "https://specs.example.com/registry/petstore/1.0.0.yaml"),
+      api("Petstore", Some("2.0.0"), "https://specs.example.com/specs/petstore/2.0.0.yaml"),
+      api("Billing", None, "https://billing.example.com/openapi.json"),
+    ]);
+  }
+
+  #[test]
+  fn test_parse_swaggerhub() {
+    let listing = json!({ "apis": [
+      { "name": "Petstore", "properties": [
+        { "type": "Swagger", "url": "https://api.swaggerhub.com/apis/acme/petstore/1.0.0" },
+        { "type": "X-Version", "value": "1.0.0" }
+      ] },
+      { "name": "Draft", "properties": [] }
+    ] });
+    assert_eq!(parse_swaggerhub("team", &listing), vec![api(
+      "Petstore",
+      Some("1.0.0"),
+      "https://api.swaggerhub.com/apis/acme/petstore/1.0.0"
+    )]);
+  }
+
+  #[test]
+  fn test_parse_apicurio_versions() {
+    let artifact = json!({ "id": "petstore", "type": "OPENAPI" });
+    let versions = json!({ "versions": [{ "version": "1" }, { "version": "2" }] });
+    let url = "http://localhost:8080/apis/registry/v2/groups/default/artifacts/petstore";
+    assert_eq!(parse_apicurio_versions("team", url, &artifact, &versions), vec![
+      api("petstore", Some("1"), &format!("{url}/versions/1")),
+      api("petstore", Some("2"), &format!("{url}/versions/2")),
+    ]);
+  }
+}
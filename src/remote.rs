@@ -0,0 +1,182 @@
+use std::path::{Path, PathBuf};
+
+use color_eyre::eyre::{eyre, Result};
+use reqwest::{header, StatusCode};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{cli::Cli, utils::get_config_dir};
+
+/// Validators of a cached spec, sent back to fetch it only once it changed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheEntry {
+  pub url: String,
+  pub etag: Option<String>,
+  pub last_modified: Option<String>,
+}
+
+pub fn is_url(path: &str) -> bool {
+  path.starts_with("http://") || path.starts_with("https://")
+}
+
+/// Replaces the urls of the specs of the arguments with the paths of their cached copies.
+pub async fn resolve_args(args: &mut Cli) -> Result<()> {
+  let offline = args.offline;
+  for spec in args.specs_mut() {
+    *spec = resolve(spec, offline).await?;
+  }
+  Ok(())
+}
+
+/// Path of the spec: the path itself, or for a url its copy cached under the config directory, fetched again when it
+/// changed. Offline, or when the server cannot be reached, the cached copy is used as is.
+pub async fn resolve(path: &str, offline: bool) -> Result<String> {
+  if !is_url(path) {
+    return Ok(path.to_string());
+  }
+  let cached = cache_dir().join(cache_name(path));
+  let entry = read_entry(&cached);
+  if offline {
+    return match entry.is_some() && cached.exists() {
+      true => Ok(cached.display().to_string()),
+      false => Err(eyre!("{path} is not cached yet, load it once without --offline")),
+    };
+  }
+  match fetch(path, &cached, entry.filter(|_| cached.exists())).await {
+    Ok(_) => Ok(cached.display().to_string()),
+    Err(error) if cached.exists() => {
+      log::warn!("Failed to fetch {path}, using the cached copy: {error:?}");
+      Ok(cached.display().to_string())
+    },
+    Err(error) => Err(error),
+  }
+}
+
+/// Fetches the spec of the cached copy at the path again, `true` when it changed on the server since. Specs not given by
+/// url are left alone.
+pub async fn refresh(path: &str) -> Result<bool> {
+  let cached = Path::new(path);
+  match read_entry(cached) {
+    Some(entry) => fetch(&entry.url.clone(), cached, Some(entry)).await,
+    None => Ok(false),
+  }
+}
+
+/// Fetches the spec into the cache, unless the cached copy with the entry is still current, `true` when the copy
+/// changed.
+async fn fetch(url: &str, cached: &Path, entry: Option<CacheEntry>) -> Result<bool> {
+  let mut request = reqwest::Client::new().get(url);
+  if let Some(entry) = &entry {
+    if let Some(etag) = &entry.etag {
+      request = request.header(header::IF_NONE_MATCH, etag);
+    }
+    if let Some(last_modified) = &entry.last_modified {
+      request = request.header(header::IF_MODIFIED_SINCE, last_modified);
+    }
+  }
+  let response = request.send().await?;
+  if response.status() == StatusCode::NOT_MODIFIED && entry.is_some() {
+    log::info!("{url} is unchanged since cached");
+    return Ok(false);
+  }
+  if !response.status().is_success() {
+    return Err(eyre!("Failed to fetch {url}: {}", response.status()));
+  }
+  let validator = |name| response.headers().get(name).and_then(|value| value.to_str().ok()).map(String::from);
+  let entry =
+    CacheEntry { url: url.to_string(), etag: validator(header::ETAG), last_modified: validator(header::LAST_MODIFIED) };
+  let body = response.bytes().await?;
+  // servers sending no validators send the spec whole every time
+  let changed = !std::fs::read(cached).is_ok_and(|previous| previous == body);
+  std::fs::create_dir_all(cache_dir())?;
+  if changed {
+    std::fs::write(cached, body)?;
+    log::info!("Cached {url} at {}", cached.display());
+  }
+  std::fs::write(entry_path(cached), serde_json::to_string_pretty(&entry)?)?;
+  Ok(changed)
+}
+
+/// Url of the spec whose cached copy is at the path, `None` for specs not given by url.
+pub fn source_url(path: &str) -> Option<String> {
+  read_entry(Path::new(path)).map(|entry| entry.url)
+}
+
+/// Directory of the cached copies of the specs given by url, and of the specs merged from several.
+pub fn cache_dir() -> PathBuf {
+  get_config_dir().join("cache")
+}
+
+/// File name of the cached copy of the url, readable and keeping its extension, with a hash of the whole url so that
+/// urls differing by their scheme or query are cached apart, e.g. `example.com_api_openapi-fb9f3bc6.yaml`.
+fn cache_name(url: &str) -> String {
+  let location = url.split_once("://").map_or(url, |(_, location)| location);
+  let location = location.split(['?', '#']).next().unwrap_or_default().trim_end_matches('/');
+  let name: String =
+    location.chars().map(|c| if c.is_ascii_alphanumeric() || c == '.' || c == '-' { c } else { '_' }).collect();
+  let hash = &hex::encode(Sha256::digest(url))[..8];
+  let extension = Path::new(&name).extension().and_then(|extension| extension.to_str()).map(str::to_lowercase);
+  match extension.as_deref() {
+    Some(extension @ ("json" | "yaml" | "yml")) => {
+      format!("{}-{hash}.{extension}", &name[..name.len() - extension.len() - 1])
+    },
+    _ => format!("{name}-{hash}.json"),
+  }
+}
+
+fn entry_path(cached: &Path) -> PathBuf {
+  let mut path = cached.as_os_str().to_owned();
+  path.push(".cache.json");
+  PathBuf::from(path)
+}
+
+fn read_entry(cached: &Path) -> Option<CacheEntry> {
+  let text = std::fs::read_to_string(entry_path(cached)).ok()?;
+  serde_json::from_str(&text).ok()
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_cache_name() {
+    let readable = |url: &str| {
+      let name = cache_name(url);
+      let (stem, extension) = name.rsplit_once('.').unwrap();
+      format!("{}.{extension}", stem.rsplit_once('-').unwrap().0)
+    };
+    assert_eq!(readable("https://example.com/api/openapi.yaml"), "example.com_api_openapi.yaml");
+    assert_eq!(readable("http://localhost:8080/v3/api-docs?group=public"), "localhost_8080_v3_api-docs.json");
+    assert_eq!(readable("https://example.com/specs/"), "example.com_specs.json");
+    assert_ne!(
+      cache_name("http://localhost:8080/v3/api-docs?group=public"),
+      cache_name("http://localhost:8080/v3/api-docs?group=internal")
+    );
+    assert_ne!(cache_name("https://example.com/openapi.json"), cache_name("http://example.com/openapi.json"));
+    assert_eq!(cache_name("https://example.com/openapi.json"), cache_name("https://example.com/openapi.json"));
+    assert!(is_url("https://example.com/openapi.json"));
+    assert!(!is_url("specs/openapi.json"));
+  }
+
+  #[test]
+  fn test_source_url() -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("openapi-tui-remote-{}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    let cached = dir.join("example.com_openapi-fb9f3bc6.yaml");
+    let local = dir.join("openapi.yaml").display().to_string();
+    let entry = CacheEntry { url: String::from("https://example.com/openapi.yaml"), ..CacheEntry::default() };
+    std::fs::write(entry_path(&cached), serde_json::to_string(&entry)?)?;
+    let source = source_url(&cached.display().to_string());
+    // specs not given by url are never fetched
+    let refreshed = tokio::runtime::Runtime::new()?.block_on(refresh(&local));
+    std::fs::remove_dir_all(&dir)?;
+    assert_eq!(source.as_deref(), Some("https://example.com/openapi.yaml"));
+    assert_eq!(source_url(&local), None);
+    assert!(!refreshed?);
+    Ok(())
+  }
+}
@@ -0,0 +1,279 @@
+use std::path::PathBuf;
+
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+  client::Exchange,
+  export::export_path,
+  fuzz::CaseResult,
+  workflow::{StepOutcome, Workflow},
+  xml::escape,
+};
+
+/// Results of test runs, written as JUnit XML or JSON for CI.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TestReport {
+  pub name: String,
+  pub suites: Vec<TestSuite>,
+}
+
+/// Workflow, smoke test, fuzz or assertion run.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TestSuite {
+  pub name: String,
+  pub cases: Vec<TestCase>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TestCase {
+  pub name: String,
+  pub status: TestStatus,
+  pub time_ms: u64,
+  /// Failures, or the error that kept the request from being sent.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub messages: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TestStatus {
+  #[default]
+  Passed,
+  Failed,
+  /// The request could not be sent.
+  Error,
+  /// Not run, e.g. the steps after a failed workflow step.
+  Skipped,
+}
+
+impl TestCase {
+  fn new(name: String, exchange: Option<&Exchange>, error: Option<&String>, failures: Vec<String>) -> Self {
+    let (status, messages) = match error {
+      Some(error) => (TestStatus::Error, vec![error.clone()]),
+      None if failures.is_empty() => (TestStatus::Passed, failures),
+      None => (TestStatus::Failed, failures),
+    };
+    Self { name, status, time_ms: exchange.map_or(0, |exchange| exchange.elapsed_ms), messages }
+  }
+
+  fn skipped(name: String) -> Self {
+    Self { name, status: TestStatus::Skipped, ..Self::default() }
+  }
+
+  /// Case of a smoke test or fuzz run, smoke tests also expect a 2xx status.
+  pub fn from_case(name: String, result: Option<&CaseResult>, expect_success: bool) -> Self {
+    let Some(result) = result else {
+      return Self::skipped(name);
+    };
+    let mut failures = result.problems.clone();
+    let status = result.exchange.as_ref().map(|exchange| exchange.response.status);
+    if let Some(status) = status.filter(|status| expect_success && status / 100 != 2 && *status < 500) {
+      failures.insert(0, format!("expected a 2xx status, got {status}"));
+    }
+    Self::new(name, result.exchange.as_ref(), result.error.as_ref(), failures)
+  }
+
+  /// Case of a saved request, failing with its failed assertions.
+  pub fn from_exchange(name: String, exchange: &Result<Exchange, String>) -> Self {
+    match exchange {
+      Ok(exchange) => {
+        let failures = exchange
+          .tests
+          .iter()
+          .filter(|test| !test.passed)
+          .map(|test| {
+            match &test.actual {
+              Some(actual) => format!("{}, got {actual}", test.assertion),
+              None => format!("{}, missing", test.assertion),
+            }
+          })
+          .collect();
+        Self::new(name, Some(exchange), None, failures)
+      },
+      Err(error) => Self::new(name, None, Some(error), vec![]),
+    }
+  }
+}
+
+impl TestSuite {
+  /// Steps of the workflow, those without an outcome were skipped.
+  pub fn workflow(workflow: &Workflow, outcomes: &[Option<StepOutcome>]) -> Self {
+    let cases = workflow
+      .steps
+      .iter()
+      .zip(outcomes)
+      .map(|(step, outcome)| {
+        let name = format!("{} ({})", step.id, step.operation);
+        match outcome {
+          Some(outcome) => {
+            TestCase::new(name, outcome.exchange.as_ref(), outcome.error.as_ref(), outcome.failures.clone())
+          },
+          None => TestCase::skipped(name),
+        }
+      })
+      .collect();
+    Self { name: format!("workflow: {}", workflow.name), cases }
+  }
+
+  fn count(&self, status: TestStatus) -> usize {
+    self.cases.iter().filter(|case| case.status == status).count()
+  }
+
+  fn time_ms(&self) -> u64 {
+    self.cases.iter().map(|case| case.time_ms).sum()
+  }
+}
+
+impl TestReport {
+  pub fn passed(&self) -> bool {
+    self.suites.iter().all(|suite| suite.count(TestStatus::Failed) + suite.count(TestStatus::Error) == 0)
+  }
+
+  /// One line per suite, e.g. `smoke tests: 12 passed, 1 failed`.
+  pub fn summary(&self) -> Vec<String> {
+    self
+      .suites
+      .iter()
+      .map(|suite| {
+        let counts = [
+          (TestStatus::Passed, "passed"),
+          (TestStatus::Failed, "failed"),
+          (TestStatus::Error, "errors"),
+          (TestStatus::Skipped, "skipped"),
+        ];
+        let counts: Vec<String> = counts
+          .iter()
+          .map(|(status, label)| (suite.count(*status), label))
+          .filter(|(count, label)| *count > 0 || **label == "passed")
+          .map(|(count, label)| format!("{count} {label}"))
+          .collect();
+        format!("{}: {}", suite.name, counts.join(", "))
+      })
+      .collect()
+  }
+
+  pub fn to_junit(&self) -> String {
+    let seconds = |time_ms: u64| format!("{:.3}", time_ms as f64 / 1000.0);
+    let count = |status| self.suites.iter().map(|suite| suite.count(status)).sum::<usize>();
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+      "<testsuites name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{}\">\n",
+      escape(&self.name),
+      self.suites.iter().map(|suite| suite.cases.len()).sum::<usize>(),
+      count(TestStatus::Failed),
+      count(TestStatus::Error),
+      count(TestStatus::Skipped),
+      seconds(self.suites.iter().map(TestSuite::time_ms).sum()),
+    ));
+    for suite in self.suites.iter() {
+      xml.push_str(&format!(
+        "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{}\">\n",
+        escape(&suite.name),
+        suite.cases.len(),
+        suite.count(TestStatus::Failed),
+        suite.count(TestStatus::Error),
+        suite.count(TestStatus::Skipped),
+        seconds(suite.time_ms()),
+      ));
+      for case in suite.cases.iter() {
+        let attributes = format!(
+          "name=\"{}\" classname=\"{}\" time=\"{}\"",
+          escape(&case.name),
+          escape(&suite.name),
+          seconds(case.time_ms)
+        );
+        let element = match case.status {
+          TestStatus::Passed => None,
+          TestStatus::Failed => Some("failure"),
+          TestStatus::Error => Some("error"),
+          TestStatus::Skipped => Some("skipped"),
+        };
+        match element {
+          None => xml.push_str(&format!("    <testcase {attributes}/>\n")),
+          Some("skipped") => xml.push_str(&format!("    <testcase {attributes}>\n      <skipped/>\n    </testcase>\n")),
+          Some(element) => {
+            let message = case.messages.first().map(String::as_str).unwrap_or_default();
+            xml.push_str(&format!(
+              "    <testcase {attributes}>\n      <{element} message=\"{}\">{}</{element}>\n    </testcase>\n",
+              escape(message),
+              escape(&case.messages.join("\n")),
+            ));
+          },
+        }
+      }
+      xml.push_str("  </testsuite>\n");
+    }
+    xml.push_str("</testsuites>\n");
+    xml
+  }
+
+  /// Writes the report as `<name>.junit.xml` and `<name>.json` in the current directory.
+  pub fn export(&self) -> Result<Vec<PathBuf>> {
+    let junit = export_path(&self.name, "junit.xml");
+    std::fs::write(&junit, self.to_junit())?;
+    let json = export_path(&self.name, "json");
+    std::fs::write(&json, serde_json::to_string_pretty(self)?)?;
+    Ok(vec![junit, json])
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+  use crate::client::HttpResponse;
+
+  #[test]
+  fn test_report() {
+    let result = |status: u16, problems: Vec<String>| {
+      CaseResult {
+        exchange: Some(Exchange {
+          elapsed_ms: 1500,
+          response: HttpResponse { status, ..HttpResponse::default() },
+          ..Exchange::default()
+        }),
+        problems,
+        error: None,
+      }
+    };
+    let report = TestReport {
+      name: String::from("smoke-tests"),
+      suites: vec![TestSuite {
+        name: String::from("smoke tests"),
+        cases: vec![
+          TestCase::from_case(String::from("GET /pets"), Some(&result(200, vec![])), true),
+          TestCase::from_case(String::from("GET /pets/{id}"), Some(&result(404, vec![])), true),
+          TestCase::from_case(
+            String::from("GET /store"),
+            Some(&result(200, vec![String::from("$.id: is <missing>")])),
+            true,
+          ),
+          TestCase::from_case(String::from("GET /users"), None, true),
+        ],
+      }],
+    };
+    assert!(!report.passed());
+    assert_eq!(report.summary(), vec![String::from("smoke tests: 1 passed, 2 failed, 1 skipped")]);
+    assert_eq!(
+      report.to_junit(),
+      r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="smoke-tests" tests="4" failures="2" errors="0" skipped="1" time="4.500">
+  <testsuite name="smoke tests" tests="4" failures="2" errors="0" skipped="1" time="4.500">
+    <testcase name="GET /pets" classname="smoke tests" time="1.500"/>
+    <testcase name="GET /pets/{id}" classname="smoke tests" time="1.500">
+      <failure message="expected a 2xx status, got 404">expected a 2xx status, got 404</failure>
+    </testcase>
+    <testcase name="GET /store" classname="smoke tests" time="1.500">
+      <failure message="$.id: is &lt;missing&gt;">$.id: is &lt;missing&gt;</failure>
+    </testcase>
+    <testcase name="GET /users" classname="smoke tests" time="0.000">
+      <skipped/>
+    </testcase>
+  </testsuite>
+</testsuites>
+"#
+    );
+  }
+}
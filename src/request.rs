@@ -0,0 +1,228 @@
+use std::collections::BTreeMap;
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use oas3::{
+  spec::{Operation, RequestBody},
+  Spec,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+  digest,
+  multipart::{self, FormPart},
+  network::Network,
+};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HttpRequest {
+  pub method: String,
+  pub base_url: String,
+  pub path: String,
+  pub query: Vec<(String, String)>,
+  pub headers: Vec<(String, String)>,
+  pub body: Option<String>,
+  /// Parts of a `multipart/form-data` body, sent instead of the body when there are some.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub form: Vec<FormPart>,
+  /// Credentials answering a Digest challenge when the server responds with a 401, never saved with the request.
+  #[serde(skip)]
+  pub digest: Option<digest::Credentials>,
+  /// Network settings of the environment the request is sent with, never saved with the request.
+  #[serde(skip)]
+  pub network: Option<Network>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum Auth {
+  Bearer {
+    token: String,
+  },
+  Basic {
+    username: String,
+    password: String,
+  },
+  ApiKey {
+    name: String,
+    value: String,
+    location: String,
+  },
+  /// Answers the Digest challenge of the server, the request being sent again once it responded with a 401.
+  Digest {
+    username: String,
+    password: String,
+  },
+}
+
+impl Auth {
+  pub fn apply(&self, request: &mut HttpRequest) {
+    match self {
+      Auth::Bearer { token } => request.set_header("Authorization", format!("Bearer {token}")),
+      Auth::Basic { username, password } => {
+        request.set_header("Authorization", format!("Basic {}", STANDARD.encode(format!("{username}:{password}"))))
+      },
+      Auth::ApiKey { name, value, location } if location == "query" => {
+        request.query.retain(|(key, _)| key != name);
+        request.query.push((name.clone(), value.clone()));
+      },
+      Auth::ApiKey { name, value, .. } => request.set_header(name, value.clone()),
+      Auth::Digest { username, password } => {
+        request.digest = Some(digest::Credentials { username: username.clone(), password: password.clone() })
+      },
+    }
+  }
+}
+
+impl HttpRequest {
+  pub fn from_operation(spec: &Spec, base_url: &str, path: &str, method: &str, operation: &Operation) -> Self {
+    let mut request = Self {
+      method: method.to_uppercase(),
+      base_url: base_url.trim_end_matches('/').to_string(),
+      path: path.to_string(),
+      ..Self::default()
+    };
+
+    for parameter in operation.parameters.iter().filter_map(|parameter| parameter.resolve(spec).ok()) {
+      if !parameter.required.unwrap_or_default() {
+        continue;
+      }
+      match parameter.location.as_str() {
+        "query" => request.query.push((parameter.name, String::default())),
+        "header" => request.headers.push((parameter.name, String::default())),
+        _ => {},
+      }
+    }
+
+    if let Some(request_body) = &operation.request_body {
+      let request_body = request_body.resolve(spec).unwrap_or(RequestBody::default());
+      if let Some((media_type, media)) = request_body.content.iter().next() {
+        request.headers.push((String::from("Content-Type"), media_type.clone()));
+        if multipart::is_multipart(media_type) {
+          let schema = media.schema(spec).ok().and_then(|schema| serde_json::to_value(schema).ok());
+          request.form = schema.map(|schema| multipart::parts(media_type, &schema)).unwrap_or_default();
+          return request;
+        }
+        let example = media
+          .schema(spec)
+          .ok()
+          .and_then(|schema| serde_json::to_value(schema).ok())
+          .and_then(|schema| schema.get("example").cloned());
+        request.body = match example {
+          Some(serde_json::Value::String(example)) => Some(example),
+          Some(example) => serde_json::to_string_pretty(&example).ok(),
+          None if media_type.contains("json") => Some(String::from("{}")),
+          None => Some(String::default()),
+        };
+      }
+    }
+    request
+  }
+
+  pub fn header(&self, name: &str) -> Option<&str> {
+    self.headers.iter().find(|(key, _)| key.eq_ignore_ascii_case(name)).map(|(_, value)| value.as_str())
+  }
+
+  pub fn set_header(&mut self, name: &str, value: String) {
+    self.headers.retain(|(key, _)| !key.eq_ignore_ascii_case(name));
+    self.headers.push((name.to_string(), value));
+  }
+
+  pub fn url(&self) -> String {
+    format!("{}{}", self.base_url, self.path)
+  }
+
+  pub fn query_string(&self) -> String {
+    if self.query.is_empty() {
+      return String::default();
+    }
+    let query = self
+      .query
+      .iter()
+      .map(|(key, value)| format!("{}={}", encode_component(key), encode_component(value)))
+      .collect::<Vec<_>>()
+      .join("&");
+    format!("?{query}")
+  }
+
+  pub fn full_url(&self) -> String {
+    format!("{}{}", self.url(), self.query_string())
+  }
+
+  /// Replaces `{{name}}` references in every part of the request.
+  pub fn substitute(&self, variables: &BTreeMap<String, String>) -> Self {
+    let pairs = |pairs: &[(String, String)]| {
+      pairs.iter().map(|(key, value)| (substitute(key, variables), substitute(value, variables))).collect()
+    };
+    Self {
+      method: self.method.clone(),
+      base_url: substitute(&self.base_url, variables),
+      path: substitute(&self.path, variables),
+      query: pairs(&self.query),
+      headers: pairs(&self.headers),
+      body: self.body.as_ref().map(|body| substitute(body, variables)),
+      form: self
+        .form
+        .iter()
+        .map(|part| FormPart { value: substitute(&part.value, variables), ..part.clone() })
+        .collect(),
+      digest: self.digest.as_ref().map(|credentials| {
+        digest::Credentials {
+          username: substitute(&credentials.username, variables),
+          password: substitute(&credentials.password, variables),
+        }
+      }),
+      network: self.network.clone(),
+    }
+  }
+}
+
+/// Replaces `{{name}}` references with the given variables, leaving unknown references untouched.
+pub fn substitute(text: &str, variables: &BTreeMap<String, String>) -> String {
+  let mut result = text.to_string();
+  // Variables may reference each other, resolve a few levels deep.
+  for _ in 0..4 {
+    let mut replaced = result.clone();
+    for (name, value) in variables.iter() {
+      replaced = replaced.replace(format!("{{{{{name}}}}}").as_str(), value);
+    }
+    if replaced == result {
+      break;
+    }
+    result = replaced;
+  }
+  result
+}
+
+pub fn encode_component(value: &str) -> String {
+  let mut encoded = String::with_capacity(value.len());
+  for byte in value.bytes() {
+    match byte {
+      b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => encoded.push(byte as char),
+      _ => encoded.push_str(format!("%{:02X}", byte).as_str()),
+    }
+  }
+  encoded
+}
+
+pub fn decode_component(value: &str) -> String {
+  let bytes = value.as_bytes();
+  let mut decoded = Vec::with_capacity(bytes.len());
+  let mut index = 0;
+  while index < bytes.len() {
+    let escaped = bytes
+      .get(index + 1..index + 3)
+      .and_then(|hex| std::str::from_utf8(hex).ok())
+      .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+    match (bytes[index], escaped) {
+      (b'%', Some(byte)) => {
+        decoded.push(byte);
+        index += 3;
+        continue;
+      },
+      (b'+', _) => decoded.push(b' '),
+      (byte, _) => decoded.push(byte),
+    }
+    index += 1;
+  }
+  String::from_utf8_lossy(&decoded).to_string()
+}
@@ -0,0 +1,320 @@
+use serde_json::Value;
+
+use crate::{client::HttpResponse, diff::ChangeKind, mock::resolve};
+
+/// Lines past which the line diff no longer looks for the longest common subsequence, which keeps it quadratic in
+/// small bodies only.
+const MAX_LINES: usize = 2000;
+
+/// Line of the line diff of two responses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LineChange {
+  Same(String),
+  Removed(String),
+  Added(String),
+}
+
+/// Difference between the JSON bodies of two responses, at a path such as `$.items[0].status`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+  pub kind: ChangeKind,
+  pub path: String,
+  pub old: Option<Value>,
+  pub new: Option<Value>,
+}
+
+/// Status line, headers and body of the response, the body pretty printed when it is JSON, as compared line by line.
+pub fn text(response: &HttpResponse) -> String {
+  let mut lines = vec![format!("{} {}", response.status, response.status_text)];
+  lines.extend(response.headers.iter().map(|(key, value)| format!("{key}: {value}")));
+  lines.push(String::default());
+  match serde_json::from_str::<Value>(&response.body) {
+    Ok(json) => lines.push(serde_json::to_string_pretty(&json).unwrap_or(response.body.clone())),
+    Err(_) => lines.push(response.body.clone()),
+  }
+  lines.join("\n")
+}
+
+/// Lines of the old text followed by those of the new one, the lines they share kept once.
+pub fn lines(old: &str, new: &str) -> Vec<LineChange> {
+  let old: Vec<&str> = old.lines().collect();
+  let new: Vec<&str> = new.lines().collect();
+  let prefix = old.iter().zip(new.iter()).take_while(|(old, new)| old == new).count();
+  let suffix = old[prefix..].iter().rev().zip(new[prefix..].iter().rev()).take_while(|(old, new)| old == new).count();
+  let (old_middle, new_middle) = (&old[prefix..old.len() - suffix], &new[prefix..new.len() - suffix]);
+
+  let mut changes: Vec<LineChange> = old[..prefix].iter().map(|line| LineChange::Same(line.to_string())).collect();
+  match old_middle.len().max(new_middle.len()) > MAX_LINES {
+    true => {
+      changes.extend(old_middle.iter().map(|line| LineChange::Removed(line.to_string())));
+      changes.extend(new_middle.iter().map(|line| LineChange::Added(line.to_string())));
+    },
+    false => changes.extend(common_subsequence(old_middle, new_middle)),
+  }
+  changes.extend(old[old.len() - suffix..].iter().map(|line| LineChange::Same(line.to_string())));
+  changes
+}
+
+/// Changes between the lines, from the table of the longest common subsequences of their ends.
+fn common_subsequence(old: &[&str], new: &[&str]) -> Vec<LineChange> {
+  let mut lengths = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+  for i in (0..old.len()).rev() {
+    for j in (0..new.len()).rev() {
+      lengths[i][j] = match old[i] == new[j] {
+        true => lengths[i + 1][j + 1] + 1,
+        false => lengths[i + 1][j].max(lengths[i][j + 1]),
+      };
+    }
+  }
+  let (mut i, mut j) = (0, 0);
+  let mut changes = vec![];
+  while i < old.len() || j < new.len() {
+    if i < old.len() && j < new.len() && old[i] == new[j] {
+      changes.push(LineChange::Same(old[i].to_string()));
+      (i, j) = (i + 1, j + 1);
+    } else if j < new.len() && (i == old.len() || lengths[i][j + 1] >= lengths[i + 1][j]) {
+      changes.push(LineChange::Added(new[j].to_string()));
+      j += 1;
+    } else {
+      changes.push(LineChange::Removed(old[i].to_string()));
+      i += 1;
+    }
+  }
+  changes
+}
+
+/// Fields added, removed or changed from the old JSON body to the new one, `None` when either is not JSON.
+pub fn fields(old: &str, new: &str) -> Option<Vec<FieldChange>> {
+  let old = serde_json::from_str(old).ok()?;
+  let new = serde_json::from_str(new).ok()?;
+  let mut changes = vec![];
+  compare(String::from("$"), &old, &new, &mut changes);
+  Some(changes)
+}
+
+/// Fields only in the example, only in the actual body, or of another JSON type in each, their values left aside as
+/// those of examples are made up. Arrays are compared by their first items, at paths such as `$.items[].status`.
+pub fn shapes(example: &Value, actual: &Value) -> Vec<FieldChange> {
+  let mut changes = vec![];
+  compare_shape(String::from("$"), example, actual, &mut changes);
+  changes
+}
+
+fn compare_shape(path: String, example: &Value, actual: &Value, changes: &mut Vec<FieldChange>) {
+  match (example, actual) {
+    (Value::Object(example), Value::Object(actual)) => {
+      for (key, example_value) in example {
+        match actual.get(key) {
+          Some(actual_value) => compare_shape(format!("{path}.{key}"), example_value, actual_value, changes),
+          None => {
+            let old = Some(example_value.clone());
+            changes.push(FieldChange { kind: ChangeKind::Removed, path: format!("{path}.{key}"), old, new: None });
+          },
+        }
+      }
+      for (key, actual_value) in actual.iter().filter(|(key, _)| !example.contains_key(*key)) {
+        let new = Some(actual_value.clone());
+        changes.push(FieldChange { kind: ChangeKind::Added, path: format!("{path}.{key}"), old: None, new });
+      }
+    },
+    (Value::Array(example), Value::Array(actual)) => {
+      if let (Some(example), Some(actual)) = (example.first(), actual.first()) {
+        compare_shape(format!("{path}[]"), example, actual, changes);
+      }
+    },
+    (example, actual) if std::mem::discriminant(example) != std::mem::discriminant(actual) => {
+      let (old, new) = (Some(example.clone()), Some(actual.clone()));
+      changes.push(FieldChange { kind: ChangeKind::Changed, path, old, new });
+    },
+    _ => {},
+  }
+}
+
+/// How an operation documents the status of a response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DocumentedStatus {
+  /// Under the status itself or its range, e.g. `4XX`, the key it is listed under given with the description.
+  Listed {
+    key: String,
+    description: String,
+  },
+  /// Only by the `default` response.
+  Default {
+    description: String,
+  },
+  Undocumented,
+}
+
+/// Response documented for the status: that of the status, of its range, e.g. `4XX`, or else the `default` one, along
+/// with the key it is documented under.
+fn documented_response<'a>(operation: &'a Value, document: &'a Value, status: u16) -> Option<(String, &'a Value)> {
+  let responses = operation.get("responses")?;
+  let range = format!("{}XX", status / 100);
+  [status.to_string(), range, String::from("default")].into_iter().find_map(|key| {
+    let response = responses.get(&key).or_else(|| responses.get(key.to_lowercase()))?;
+    Some((key, resolve(response, document)))
+  })
+}
+
+pub fn documented_status(operation: &Value, document: &Value, status: u16) -> DocumentedStatus {
+  let Some((key, response)) = documented_response(operation, document, status) else {
+    return DocumentedStatus::Undocumented;
+  };
+  let description = response.get("description").and_then(Value::as_str).unwrap_or_default().trim().to_string();
+  match key.as_str() {
+    "default" => DocumentedStatus::Default { description },
+    _ => DocumentedStatus::Listed { key, description },
+  }
+}
+
+/// Example declared for the status and content type of a response, along with its media type: that of the media
+/// type or its schema, `None` when the spec gives none.
+pub fn declared_example(
+  operation: &Value,
+  document: &Value,
+  status: u16,
+  content_type: Option<&str>,
+) -> Option<(String, Value)> {
+  let (_, response) = documented_response(operation, document, status)?;
+  let content = response.get("content")?.as_object()?;
+  let essence = content_type.and_then(|content_type| content_type.split(';').next()).map(str::trim);
+  let (media_type, media) = essence
+    .and_then(|essence| content.iter().find(|(media_type, _)| media_type.eq_ignore_ascii_case(essence)))
+    .or_else(|| content.iter().next())?;
+  let first_example = media.get("examples").and_then(Value::as_object).and_then(|examples| examples.values().next());
+  let schema = media.get("schema").map(|schema| resolve(schema, document));
+  let example = media
+    .get("example")
+    .or_else(|| first_example.and_then(|example| resolve(example, document).get("value")))
+    .or_else(|| schema.and_then(|schema| schema.get("example")))?;
+  Some((media_type.clone(), example.clone()))
+}
+
+fn compare(path: String, old: &Value, new: &Value, changes: &mut Vec<FieldChange>) {
+  let change = |kind, path, old: Option<&Value>, new: Option<&Value>| {
+    FieldChange { kind, path, old: old.cloned(), new: new.cloned() }
+  };
+  match (old, new) {
+    (Value::Object(old), Value::Object(new)) => {
+      for (key, old_value) in old {
+        match new.get(key) {
+          Some(new_value) => compare(format!("{path}.{key}"), old_value, new_value, changes),
+          None => changes.push(change(ChangeKind::Removed, format!("{path}.{key}"), Some(old_value), None)),
+        }
+      }
+      for (key, new_value) in new.iter().filter(|(key, _)| !old.contains_key(*key)) {
+        changes.push(change(ChangeKind::Added, format!("{path}.{key}"), None, Some(new_value)));
+      }
+    },
+    (Value::Array(old), Value::Array(new)) => {
+      for index in 0..old.len().max(new.len()) {
+        let item_path = format!("{path}[{index}]");
+        match (old.get(index), new.get(index)) {
+          (Some(old_item), Some(new_item)) => compare(item_path, old_item, new_item, changes),
+          (old_item, new_item) => {
+            let kind = match old_item {
+              Some(_) => ChangeKind::Removed,
+              None => ChangeKind::Added,
+            };
+            changes.push(change(kind, item_path, old_item, new_item));
+          },
+        }
+      }
+    },
+    (old, new) if old != new => changes.push(change(ChangeKind::Changed, path, Some(old), Some(new))),
+    _ => {},
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+  use serde_json::json;
+
+  use super::*;
+
+  #[test]
+  fn test_lines() {
+    let changes = lines("a\nb\nc\nd", "a\nc\nx\nd");
+    assert_eq!(changes, vec![
+      LineChange::Same(String::from("a")),
+      LineChange::Removed(String::from("b")),
+      LineChange::Same(String::from("c")),
+      LineChange::Added(String::from("x")),
+      LineChange::Same(String::from("d")),
+    ]);
+    assert_eq!(lines("a", "a"), vec![LineChange::Same(String::from("a"))]);
+  }
+
+  #[test]
+  fn test_fields() {
+    let old = r#"{"id": 1, "status": "available", "tags": ["a", "b"], "owner": {"name": "Ann"}}"#;
+    let new = r#"{"id": 1, "status": "sold", "tags": ["a"], "owner": {"name": "Ann", "age": 3}}"#;
+    let changes = fields(old, new).unwrap();
+    let listed: Vec<(ChangeKind, &str)> = changes.iter().map(|change| (change.kind, change.path.as_str())).collect();
+    assert_eq!(listed, vec![
+      (ChangeKind::Added, "$.owner.age"),
+      (ChangeKind::Changed, "$.status"),
+      (ChangeKind::Removed, "$.tags[1]"),
+    ]);
+    assert_eq!(changes[1].old, Some(json!("available")));
+    assert_eq!(changes[1].new, Some(json!("sold")));
+    assert_eq!(fields("not json", "{}"), None);
+  }
+
+  #[test]
+  fn test_shapes() {
+    let example = json!({ "id": 1, "name": "Rex", "tags": [{ "name": "a" }] });
+    let actual = json!({ "id": "7", "tags": [{ "name": "b", "color": "red" }], "status": "sold" });
+    let changes = shapes(&example, &actual);
+    let listed: Vec<(ChangeKind, &str)> = changes.iter().map(|change| (change.kind, change.path.as_str())).collect();
+    assert_eq!(listed, vec![
+      (ChangeKind::Changed, "$.id"),
+      (ChangeKind::Removed, "$.name"),
+      (ChangeKind::Added, "$.tags[].color"),
+      (ChangeKind::Added, "$.status"),
+    ]);
+  }
+
+  #[test]
+  fn test_declared_example() {
+    let document = json!({ "components": { "schemas": { "Pet": { "type": "object", "example": { "id": 1 } } } } });
+    let operation = json!({ "responses": {
+      "200": { "content": {
+        "application/xml": { "example": "<pet/>" },
+        "application/json": { "schema": { "$ref": "#/components/schemas/Pet" } }
+      } },
+      "4XX": { "content": { "application/json": { "examples": { "missing": { "value": { "error": "missing" } } } } } }
+    } });
+    let example = |status, content_type| declared_example(&operation, &document, status, content_type);
+    assert_eq!(
+      example(200, Some("application/json; charset=utf-8")),
+      Some((String::from("application/json"), json!({ "id": 1 })))
+    );
+    assert_eq!(example(404, None), Some((String::from("application/json"), json!({ "error": "missing" }))));
+    assert_eq!(example(500, None), None);
+  }
+
+  #[test]
+  fn test_documented_status() {
+    let document = json!({ "components": { "responses": { "NotFound": { "description": "Pet not found" } } } });
+    let operation = json!({ "responses": {
+      "200": { "description": "Successful operation\n\nThe pet." },
+      "4xx": { "$ref": "#/components/responses/NotFound" },
+    } });
+    let status = |status| documented_status(&operation, &document, status);
+    assert_eq!(status(200), DocumentedStatus::Listed {
+      key: String::from("200"),
+      description: String::from("Successful operation\n\nThe pet.")
+    });
+    assert_eq!(status(404), DocumentedStatus::Listed {
+      key: String::from("4XX"),
+      description: String::from("Pet not found")
+    });
+    assert_eq!(status(500), DocumentedStatus::Undocumented);
+    let operation = json!({ "responses": { "default": { "description": "Unexpected error" } } });
+    assert_eq!(documented_status(&operation, &document, 500), DocumentedStatus::Default {
+      description: String::from("Unexpected error")
+    });
+  }
+}
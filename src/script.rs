@@ -0,0 +1,362 @@
+use std::{
+  collections::BTreeMap,
+  sync::{Arc, Mutex},
+  time::{Duration, Instant},
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use color_eyre::eyre::{eyre, Result};
+use hmac::{Hmac, Mac};
+use rhai::{Array, Dynamic, Engine, Map, Scope};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{
+  assertion,
+  cache::Cache,
+  cassette::Player,
+  client::{self, Exchange, HttpResponse},
+  network::Network,
+  plugin::Plugins,
+  redaction::Redactor,
+  request::HttpRequest,
+  signing::Signing,
+  wire,
+  workspace::{OperationKey, Scripts},
+};
+
+/// Operations a script may run before it is stopped, e.g. as it loops forever.
+const MAX_OPERATIONS: u64 = 5_000_000;
+/// Depth of nested function calls a script may reach before it is stopped.
+const MAX_CALL_LEVELS: usize = 64;
+/// Time a script may run before it is stopped, whatever the operations it ran.
+const TIMEOUT: Duration = Duration::from_secs(5);
+/// Bytes of a string a script may build before it is stopped, e.g. as it keeps doubling one, large enough for the
+/// bodies of the requests and responses it is given.
+const MAX_STRING_SIZE: usize = 64 * 1024 * 1024;
+/// Items of an array, and properties of a map, a script may build before it is stopped.
+const MAX_COLLECTION_SIZE: usize = 1_000_000;
+
+/// Line written by the scripts of a request, shown in the log tab of its response.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "message", rename_all = "lowercase")]
+pub enum LogLine {
+  /// Output of `print` and `debug`.
+  Output(String),
+  Passed(String),
+  /// Failed assertion, or error of a post-response script.
+  Failed(String),
+}
+
+/// Scripts and plugins a request goes through.
+#[derive(Clone, Default)]
+pub struct Hooks {
+  /// Scripts of the folders the request was saved in, then of its operation.
+  pub scripts: Vec<Scripts>,
+  pub plugins: Plugins,
+  /// Security schemes of the operation, which select the plugin signers.
+  pub auth_schemes: Vec<String>,
+  /// Cassette answering instead of the network.
+  pub player: Option<Arc<Player>>,
+  /// Assertions of the saved request, checked once the post-response scripts ran.
+  pub assertions: Vec<String>,
+  /// Values masked in the logged wire traffic.
+  pub secret_values: Vec<String>,
+  /// Redaction rules of the config, applied to the logged wire traffic.
+  pub redactor: Redactor,
+  /// Client-side cache answering repeated GETs, when enabled.
+  pub cache: Option<Cache>,
+  /// Signature of the active environment, computed once the plugins prepared the request.
+  pub signing: Option<Signing>,
+  /// Proxy, certificates, timeout and host overrides of the active environment.
+  pub network: Option<Network>,
+}
+
+/// Runs the pre-request scripts, which may change the request and set variables, the plugins and the signing of the
+/// environment, sends the request, then runs the post-response scripts on its response.
+///
+/// Scripts see the request as `request`, with `method`, `base_url`, `path`, `query`, `headers`, as `[name, value]`
+/// pairs so that repeated headers are kept, and `body`, the response as `response`, with `status`, `headers`, `body`
+/// and the parsed `json`, and the variables as `vars`. Scripts run on a blocking thread and are stopped once they run
+/// too long.
+pub async fn exchange(
+  operation: Option<OperationKey>,
+  mut request: HttpRequest,
+  hooks: &Hooks,
+  variables: &BTreeMap<String, String>,
+) -> Result<Exchange> {
+  let log = Arc::new(Mutex::new(vec![]));
+  let mut vars = variables.clone();
+  for script in hooks.scripts.iter().filter(|script| !script.pre_request.trim().is_empty()) {
+    let mut scope = Scope::new();
+    scope.push("request", request_map(&request));
+    scope.push("vars", strings_map(&vars));
+    let scope = run(&log, scope, &script.pre_request).await.map_err(|error| eyre!("Pre-request script: {error}"))?;
+    if let Some(map) = scope.get_value::<Map>("request") {
+      apply_request_map(&mut request, &map);
+    }
+    vars = scope.get_value::<Map>("vars").map(|map| map_strings(&map)).unwrap_or(vars);
+  }
+
+  let mut request = hooks.plugins.prepare(request, &hooks.auth_schemes)?;
+  if let Some(signing) = &hooks.signing {
+    signing.sign(&mut request, &vars, chrono::Utc::now())?;
+  }
+  request.network.clone_from(&hooks.network);
+  let mut exchange = match &hooks.player {
+    Some(player) => player.play(operation, request, &vars)?,
+    None => {
+      let exchange = match &hooks.cache {
+        Some(cache) => cache.exchange(operation, request).await?,
+        None => client::exchange(operation, request).await?,
+      };
+      wire::log(&hooks.redactor.exchange(&exchange), &hooks.secret_values);
+      exchange
+    },
+  };
+  for script in hooks.scripts.iter().filter(|script| !script.post_response.trim().is_empty()) {
+    let mut scope = Scope::new();
+    scope.push("request", request_map(&exchange.request));
+    scope.push("response", response_map(&exchange.response));
+    scope.push("vars", strings_map(&vars));
+    match run(&log, scope, &script.post_response).await {
+      Ok(scope) => vars = scope.get_value::<Map>("vars").map(|map| map_strings(&map)).unwrap_or(vars),
+      Err(error) => log.lock().unwrap().push(LogLine::Failed(format!("Post-response script: {error}"))),
+    }
+  }
+  exchange.log = std::mem::take(&mut *log.lock().unwrap());
+  exchange.tests =
+    hooks.assertions.iter().map(|assertion| assertion::evaluate(assertion, &exchange.response)).collect();
+  exchange.variables = vars.into_iter().filter(|(name, value)| variables.get(name) != Some(value)).collect();
+  Ok(exchange)
+}
+
+/// Runs the script on a blocking thread, so that a script running long does not hold up the other requests, with the
+/// scope it leaves.
+async fn run(log: &Arc<Mutex<Vec<LogLine>>>, mut scope: Scope<'static>, script: &str) -> Result<Scope<'static>> {
+  let log = log.clone();
+  let script = script.to_string();
+  tokio::task::spawn_blocking(move || {
+    engine(log).run_with_scope(&mut scope, &script).map_err(|error| eyre!("{error}"))?;
+    Ok(scope)
+  })
+  .await?
+}
+
+/// Engine running a script, stopped once it runs more than `MAX_OPERATIONS` operations, nests more than
+/// `MAX_CALL_LEVELS` calls or runs longer than `TIMEOUT` since the engine was made.
+fn engine(log: Arc<Mutex<Vec<LogLine>>>) -> Engine {
+  let mut engine = Engine::new();
+  engine.set_max_operations(MAX_OPERATIONS);
+  engine.set_max_call_levels(MAX_CALL_LEVELS);
+  engine.set_max_string_size(MAX_STRING_SIZE);
+  engine.set_max_array_size(MAX_COLLECTION_SIZE);
+  engine.set_max_map_size(MAX_COLLECTION_SIZE);
+  let started = Instant::now();
+  engine.on_progress(move |operations| {
+    (operations % 1024 == 0 && started.elapsed() > TIMEOUT)
+      .then(|| Dynamic::from(format!("Script timed out after {} s", TIMEOUT.as_secs())))
+  });
+  let output = log.clone();
+  engine.on_print(move |text| output.lock().unwrap().push(LogLine::Output(text.to_string())));
+  let output = log.clone();
+  engine.on_debug(move |text, _, _| output.lock().unwrap().push(LogLine::Output(text.to_string())));
+  engine.register_fn("assert", move |passed: bool, message: &str| {
+    let message = message.to_string();
+    log.lock().unwrap().push(if passed { LogLine::Passed(message) } else { LogLine::Failed(message) });
+  });
+  engine.register_fn("sha256", |text: &str| hex::encode(Sha256::digest(text)));
+  engine.register_fn("hmac_sha256", |key: &str, message: &str| {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(message.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+  });
+  engine.register_fn("base64", |text: &str| STANDARD.encode(text));
+  engine.register_fn("timestamp", || chrono::Utc::now().timestamp());
+  engine.register_fn("now", || chrono::Utc::now().to_rfc3339());
+  engine.register_fn("header", |headers: &mut Array, name: &str| {
+    headers
+      .iter()
+      .filter_map(pair)
+      .find(|(header, _)| header.eq_ignore_ascii_case(name))
+      .map_or(Dynamic::UNIT, |(_, value)| Dynamic::from(value))
+  });
+  engine.register_fn("set_header", |headers: &mut Array, name: &str, value: &str| {
+    let position =
+      headers.iter().position(|header| pair(header).is_some_and(|(header, _)| header.eq_ignore_ascii_case(name)));
+    remove_header(headers, name);
+    headers.insert(position.unwrap_or(headers.len()), pair_of(name, value));
+  });
+  engine.register_fn("remove_header", remove_header);
+  engine
+}
+
+/// Removes every pair of the header, whatever the case of its name.
+fn remove_header(headers: &mut Array, name: &str) {
+  headers.retain(|header| !pair(header).is_some_and(|(header, _)| header.eq_ignore_ascii_case(name)));
+}
+
+/// Name and value of a `[name, value]` pair.
+fn pair(value: &Dynamic) -> Option<(String, String)> {
+  let pair = value.read_lock::<Array>()?;
+  match pair.as_slice() {
+    [name, value] => {
+      Some((name.clone().into_string().ok()?, value.clone().into_string().unwrap_or_else(|_| value.to_string())))
+    },
+    _ => None,
+  }
+}
+
+fn pair_of(name: &str, value: &str) -> Dynamic {
+  Dynamic::from(vec![Dynamic::from(name.to_string()), Dynamic::from(value.to_string())])
+}
+
+fn strings_map<'a>(pairs: impl IntoIterator<Item = (&'a String, &'a String)>) -> Map {
+  pairs.into_iter().map(|(name, value)| (name.into(), Dynamic::from(value.clone()))).collect()
+}
+
+/// Unit values remove the entry, other values are turned into text.
+fn map_strings(map: &Map) -> BTreeMap<String, String> {
+  map
+    .iter()
+    .filter(|(_, value)| !value.is_unit())
+    .map(|(name, value)| (name.to_string(), value.clone().into_string().unwrap_or_else(|_| value.to_string())))
+    .collect()
+}
+
+fn request_map(request: &HttpRequest) -> Map {
+  let pairs = |pairs: &[(String, String)]| strings_map(pairs.iter().map(|(name, value)| (name, value)));
+  Map::from([
+    ("method".into(), Dynamic::from(request.method.clone())),
+    ("base_url".into(), Dynamic::from(request.base_url.clone())),
+    ("path".into(), Dynamic::from(request.path.clone())),
+    ("query".into(), Dynamic::from(pairs(&request.query))),
+    (
+      "headers".into(),
+      Dynamic::from(request.headers.iter().map(|(name, value)| pair_of(name, value)).collect::<Array>()),
+    ),
+    ("body".into(), request.body.clone().map_or(Dynamic::UNIT, Dynamic::from)),
+  ])
+}
+
+fn response_map(response: &HttpResponse) -> Map {
+  let json = serde_json::from_str::<serde_json::Value>(&response.body)
+    .ok()
+    .and_then(|json| rhai::serde::to_dynamic(json).ok())
+    .unwrap_or(Dynamic::UNIT);
+  Map::from([
+    ("status".into(), Dynamic::from(i64::from(response.status))),
+    ("headers".into(), Dynamic::from(strings_map(response.headers.iter().map(|(name, value)| (name, value))))),
+    ("body".into(), Dynamic::from(response.body.clone())),
+    ("json".into(), json),
+  ])
+}
+
+fn apply_request_map(request: &mut HttpRequest, map: &Map) {
+  let text = |name: &str| map.get(name).and_then(|value| value.clone().into_string().ok());
+  if let Some(method) = text("method") {
+    request.method = method.to_uppercase();
+  }
+  if let Some(base_url) = text("base_url") {
+    request.base_url = base_url;
+  }
+  if let Some(path) = text("path") {
+    request.path = path;
+  }
+  if let Some(query) = map.get("query").and_then(|value| value.clone().try_cast::<Map>()) {
+    request.query = merge_pairs(&request.query, map_strings(&query));
+  }
+  if let Some(headers) = map.get("headers").and_then(|value| value.clone().try_cast::<Array>()) {
+    request.headers = headers.iter().filter_map(pair).collect();
+  }
+  request.body = text("body");
+}
+
+/// Pairs with the values of the map, keeping the order of the pairs the map still has and adding the new ones last.
+fn merge_pairs(pairs: &[(String, String)], mut values: BTreeMap<String, String>) -> Vec<(String, String)> {
+  let mut merged: Vec<_> =
+    pairs.iter().filter_map(|(name, _)| values.remove(name).map(|value| (name.clone(), value))).collect();
+  merged.extend(values);
+  merged
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_scripts() {
+    let log = Arc::new(Mutex::new(vec![]));
+    let engine = engine(log.clone());
+    let mut request = HttpRequest {
+      method: String::from("POST"),
+      path: String::from("/pets"),
+      headers: vec![
+        (String::from("Content-Type"), String::from("application/json")),
+        (String::from("X-Old"), String::default()),
+        (String::from("X-Tag"), String::from("a")),
+      ],
+      body: Some(String::from("{}")),
+      ..HttpRequest::default()
+    };
+    let mut scope = Scope::new();
+    scope.push("request", request_map(&request));
+    scope.push("vars", strings_map(&BTreeMap::from([(String::from("secret"), String::from("key"))])));
+    engine
+      .run_with_scope(
+        &mut scope,
+        r#"
+          request.headers.set_header("X-Signature", hmac_sha256(vars.secret, request.body));
+          request.headers.set_header("content-type", request.headers.header("Content-Type") + "; charset=utf-8");
+          request.headers.remove_header("X-Old");
+          request.headers.push(["X-Tag", "b"]);
+          vars.signed = true;
+          print("signed " + request.path);
+          assert(request.method == "POST", "method is POST");
+        "#,
+      )
+      .unwrap();
+    apply_request_map(&mut request, &scope.get_value::<Map>("request").unwrap());
+    assert_eq!(request.headers, vec![
+      (String::from("content-type"), String::from("application/json; charset=utf-8")),
+      (String::from("X-Tag"), String::from("a")),
+      (String::from("X-Signature"), String::from("a777724d943eb48dc69bca8a4a6d57a04db3f9ec7e1de4e581e860265bdf3032")),
+      (String::from("X-Tag"), String::from("b")),
+    ]);
+    assert_eq!(map_strings(&scope.get_value::<Map>("vars").unwrap())["signed"], "true");
+    assert_eq!(*log.lock().unwrap(), vec![
+      LogLine::Output(String::from("signed /pets")),
+      LogLine::Passed(String::from("method is POST")),
+    ]);
+
+    let response =
+      HttpResponse { status: 404, body: String::from(r#"{ "items": [{ "id": 7 }] }"#), ..Default::default() };
+    let mut scope = Scope::new();
+    scope.push("response", response_map(&response));
+    engine
+      .run_with_scope(&mut scope, r#"assert(response.json.items[0].id == 7 && response.status == 200, "found")"#)
+      .unwrap();
+    assert_eq!(log.lock().unwrap().last(), Some(&LogLine::Failed(String::from("found"))));
+  }
+
+  #[test]
+  fn test_runaway_scripts() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let log = Arc::new(Mutex::new(vec![]));
+    let looping = runtime.block_on(run(&log, Scope::new(), "loop {}"));
+    assert!(looping.unwrap_err().to_string().contains("Too many operations"));
+    let recursing = runtime.block_on(run(&log, Scope::new(), "fn down(n) { down(n + 1) } down(0)"));
+    assert!(recursing.is_err());
+    let doubling = runtime.block_on(run(&log, Scope::new(), r#"let text = "x"; loop { text += text; }"#));
+    assert!(doubling.unwrap_err().to_string().contains("Length of string too large"));
+    let growing = runtime.block_on(run(&log, Scope::new(), "let items = [0]; loop { items += items; }"));
+    assert!(growing.unwrap_err().to_string().contains("Size of array/BLOB too large"));
+
+    let mut scope = Scope::new();
+    scope.push("vars", Map::new());
+    let scope = runtime.block_on(run(&log, scope, "vars.done = true;")).unwrap();
+    assert_eq!(map_strings(&scope.get_value::<Map>("vars").unwrap())["done"], "true");
+  }
+}
@@ -0,0 +1,321 @@
+use std::{collections::BTreeMap, io::BufRead};
+
+use color_eyre::eyre::Result;
+
+use crate::{
+  client::Exchange,
+  request::{Auth, HttpRequest},
+};
+
+const KEYRING_SERVICE: &str = env!("CARGO_PKG_NAME");
+
+/// Variable values starting with this prefix are read from the OS keyring, e.g. `keyring:petstore-token`.
+pub const KEYRING_PREFIX: &str = "keyring:";
+
+/// Shown in place of secret values until they are revealed.
+pub const MASK: &str = "•••";
+
+/// Secret values shorter than this are not masked on screen, e.g. a tenant id `1` or `dev` would mask unrelated text.
+/// Requests written to disk reference every secret whatever its length.
+const MIN_SECRET_LENGTH: usize = 6;
+
+const SECRET_NAME_PARTS: [&str; 9] =
+  ["authorization", "token", "secret", "password", "passwd", "apikey", "api_key", "api-key", "cookie"];
+
+pub fn store(name: &str, secret: &str) -> Result<()> {
+  keyring::Entry::new(KEYRING_SERVICE, name)?.set_password(secret)?;
+  Ok(())
+}
+
+pub fn load(name: &str) -> Result<String> {
+  Ok(keyring::Entry::new(KEYRING_SERVICE, name)?.get_password()?)
+}
+
+/// Reads the secret from the first line of stdin, so it does not end up in the shell history, and stores it.
+pub fn store_from_stdin(name: &str) -> Result<()> {
+  let mut secret = String::new();
+  std::io::stdin().lock().read_line(&mut secret)?;
+  store(name, secret.trim_end_matches(['\r', '\n']))
+}
+
+pub fn is_keyring_reference(value: &str) -> bool {
+  value.starts_with(KEYRING_PREFIX)
+}
+
+/// Whether a header, parameter or variable name usually carries a credential, e.g. `Authorization` or `client_secret`.
+pub fn is_secret_name(name: &str) -> bool {
+  let name = name.to_lowercase();
+  SECRET_NAME_PARTS.iter().any(|part| name.contains(part))
+}
+
+/// Values referencing a variable, e.g. `Bearer {{authToken}}`, are templates rather than secrets.
+fn mask_value(value: &str) -> String {
+  if value.contains("{{") {
+    value.to_string()
+  } else {
+    MASK.to_string()
+  }
+}
+
+fn mask_pairs(pairs: &mut [(String, String)]) {
+  for (_, value) in pairs.iter_mut().filter(|(name, _)| is_secret_name(name)) {
+    *value = mask_value(value);
+  }
+}
+
+/// Whether the character continues a word, which a whole value is not preceded or followed by.
+fn is_word_character(character: char) -> bool {
+  character.is_alphanumeric() || character == '_' || character == '-'
+}
+
+/// Replaces the whole occurrences of `from` in the text, e.g. in `Bearer s3cr3t` or `"s3cr3t"` but not in
+/// `s3cr3tive`.
+fn replace_whole(text: &str, from: &str, to: &str) -> String {
+  let mut replaced = String::with_capacity(text.len());
+  let mut end = 0;
+  for (start, _) in text.match_indices(from) {
+    let before = text[..start].chars().next_back();
+    let after = text[start + from.len()..].chars().next();
+    if start < end || before.is_some_and(is_word_character) || after.is_some_and(is_word_character) {
+      continue;
+    }
+    replaced.push_str(&text[end..start]);
+    replaced.push_str(to);
+    end = start + from.len();
+  }
+  replaced.push_str(&text[end..]);
+  replaced
+}
+
+/// Rewrites every text of the request a secret may show up in: its url, query, headers, body and form parts.
+fn replace_everywhere(request: &mut HttpRequest, replace: impl Fn(&str) -> String) {
+  let replace = |text: &mut String| *text = replace(text);
+  replace(&mut request.base_url);
+  replace(&mut request.path);
+  request.query.iter_mut().chain(request.headers.iter_mut()).for_each(|(_, value)| replace(value));
+  if let Some(body) = request.body.as_mut() {
+    replace(body);
+  }
+  request.form.iter_mut().for_each(|part| replace(&mut part.value));
+}
+
+/// Masks secret headers and query parameters, along with the whole occurrences of the given secret values.
+pub fn mask_request(request: &HttpRequest, secret_values: &[String]) -> HttpRequest {
+  let mut masked = request.clone();
+  mask_pairs(&mut masked.headers);
+  mask_pairs(&mut masked.query);
+  for secret in secret_values.iter().filter(|secret| secret.chars().count() >= MIN_SECRET_LENGTH) {
+    replace_everywhere(&mut masked, |text| replace_whole(text, secret, MASK));
+  }
+  masked
+}
+
+/// Masks the request like [`mask_request`] and the secret response headers, e.g. `Set-Cookie`.
+pub fn mask_exchange(exchange: &Exchange, secret_values: &[String]) -> Exchange {
+  let mut masked = exchange.clone();
+  masked.request = mask_request(&exchange.request, secret_values);
+  mask_pairs(&mut masked.response.headers);
+  masked
+}
+
+pub fn mask_auth(auth: &Auth) -> Auth {
+  match auth {
+    Auth::Bearer { token } => Auth::Bearer { token: mask_value(token) },
+    Auth::Basic { username, password } => Auth::Basic { username: username.clone(), password: mask_value(password) },
+    Auth::ApiKey { name, value, location } => {
+      Auth::ApiKey { name: name.clone(), value: mask_value(value), location: location.clone() }
+    },
+    Auth::Digest { username, password } => Auth::Digest { username: username.clone(), password: mask_value(password) },
+  }
+}
+
+/// Masks the values of secret variables, keyring references are kept since they only name the secret.
+pub fn mask_variables(variables: &BTreeMap<String, String>) -> BTreeMap<String, String> {
+  variables
+    .iter()
+    .map(|(name, value)| {
+      let value = if is_secret_name(name) && !is_keyring_reference(value) { mask_value(value) } else { value.clone() };
+      (name.clone(), value)
+    })
+    .collect()
+}
+
+/// Puts the `{{name}}` references of the secret variables back in place of every occurrence of their values, however
+/// short, so the request can be written to disk and resolved again later. Longer secrets are referenced first, so a
+/// secret holding a shorter one is referenced whole.
+pub fn reference_secrets(request: &HttpRequest, secret_variables: &BTreeMap<String, String>) -> HttpRequest {
+  let mut referenced = request.clone();
+  let mut secrets: Vec<_> = secret_variables.iter().filter(|(_, secret)| !secret.is_empty()).collect();
+  secrets.sort_by_key(|(_, secret)| std::cmp::Reverse(secret.len()));
+  for (name, secret) in secrets {
+    let reference = format!("{{{{{name}}}}}");
+    replace_everywhere(&mut referenced, |text| text.replace(secret.as_str(), &reference));
+  }
+  referenced
+}
+
+/// Replaces keyring references with the stored secrets, references that cannot be read are kept as is.
+pub fn resolve(variables: &mut BTreeMap<String, String>) {
+  resolve_with(variables, load);
+}
+
+/// Replaces keyring references with the secrets read by `load`, references that cannot be read are kept as is.
+pub fn resolve_with(variables: &mut BTreeMap<String, String>, load: impl Fn(&str) -> Result<String>) {
+  for (variable, value) in variables.iter_mut() {
+    let Some(name) = value.strip_prefix(KEYRING_PREFIX) else {
+      continue;
+    };
+    match load(name) {
+      Ok(secret) => *value = secret,
+      Err(error) => log::error!("Failed to read `{name}` from the keyring for `{variable}`: {error:?}"),
+    }
+  }
+}
+
+/// Variables with their keyring references resolved, along with the variables they were resolved from, so that the
+/// keyring, which may block or prompt, is read again only once those change, e.g. as another environment is activated.
+#[derive(Debug, Default)]
+pub struct ResolvedVariables {
+  unresolved: BTreeMap<String, String>,
+  resolved: BTreeMap<String, String>,
+}
+
+impl ResolvedVariables {
+  /// The variables with their keyring references resolved by `load`, read again only when they differ from the
+  /// variables last resolved.
+  pub fn resolve(
+    &mut self,
+    unresolved: BTreeMap<String, String>,
+    load: impl Fn(&str) -> Result<String>,
+  ) -> &BTreeMap<String, String> {
+    if unresolved != self.unresolved {
+      let mut resolved = unresolved.clone();
+      resolve_with(&mut resolved, load);
+      self.unresolved = unresolved;
+      self.resolved = resolved;
+    }
+    &self.resolved
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_resolve_keeps_plain_values() {
+    let mut variables = BTreeMap::from([(String::from("baseUrl"), String::from("https://example.com"))]);
+    resolve(&mut variables);
+    assert_eq!(variables["baseUrl"], "https://example.com");
+    assert!(is_keyring_reference("keyring:token"));
+    assert!(!is_keyring_reference("token"));
+  }
+
+  #[test]
+  fn test_resolve_keyring_references() {
+    let loads = std::cell::Cell::new(0);
+    let load = |name: &str| {
+      loads.set(loads.get() + 1);
+      match name {
+        "petstore-token" => Ok(String::from("s3cr3t-token")),
+        _ => Err(color_eyre::eyre::eyre!("No entry for `{name}`")),
+      }
+    };
+    let unresolved = BTreeMap::from([
+      (String::from("authToken"), String::from("keyring:petstore-token")),
+      (String::from("baseUrl"), String::from("https://example.com")),
+      (String::from("missing"), String::from("keyring:missing")),
+    ]);
+    let mut resolved = ResolvedVariables::default();
+    assert_eq!(
+      resolved.resolve(unresolved.clone(), load),
+      &BTreeMap::from([
+        (String::from("authToken"), String::from("s3cr3t-token")),
+        (String::from("baseUrl"), String::from("https://example.com")),
+        (String::from("missing"), String::from("keyring:missing")),
+      ])
+    );
+    assert_eq!(loads.get(), 2);
+    resolved.resolve(unresolved.clone(), load);
+    assert_eq!(loads.get(), 2);
+
+    let mut switched = unresolved;
+    switched.remove("missing");
+    assert_eq!(resolved.resolve(switched, load)["authToken"], "s3cr3t-token");
+    assert_eq!(loads.get(), 3);
+  }
+
+  #[test]
+  fn test_mask_request() {
+    let request = HttpRequest {
+      method: String::from("GET"),
+      base_url: String::from("https://example.com"),
+      path: String::from("/pets"),
+      query: vec![(String::from("api_key"), String::from("abc")), (String::from("limit"), String::from("10"))],
+      headers: vec![
+        (String::from("Authorization"), String::from("Bearer {{authToken}}")),
+        (String::from("X-Tenant"), String::from("s3cr3t")),
+      ],
+      body: Some(String::from("{\"tenant\":\"s3cr3t\"}")),
+      form: vec![],
+      digest: None,
+      network: None,
+    };
+    let masked = mask_request(&request, &[String::from("s3cr3t")]);
+    assert_eq!(masked.query, vec![
+      (String::from("api_key"), MASK.to_string()),
+      (String::from("limit"), String::from("10"))
+    ]);
+    assert_eq!(masked.header("Authorization"), Some("Bearer {{authToken}}"));
+    assert_eq!(masked.header("X-Tenant"), Some(MASK));
+    assert_eq!(masked.body.as_deref(), Some("{\"tenant\":\"•••\"}"));
+
+    let referenced = reference_secrets(&request, &BTreeMap::from([(String::from("tenant"), String::from("s3cr3t"))]));
+    assert_eq!(referenced.header("X-Tenant"), Some("{{tenant}}"));
+  }
+
+  #[test]
+  fn test_reference_short_secrets() {
+    let request = HttpRequest {
+      method: String::from("POST"),
+      path: String::from("/cards/4711/unlock"),
+      headers: vec![(String::from("X-Pin"), String::from("4711")), (String::from("X-Key"), String::from("ab-4711-cd"))],
+      form: vec![crate::multipart::FormPart { name: String::from("pin"), value: String::from("4711"), file: false }],
+      ..HttpRequest::default()
+    };
+    let secrets = BTreeMap::from([
+      (String::from("pin"), String::from("4711")),
+      (String::from("key"), String::from("ab-4711-cd")),
+      (String::from("empty"), String::default()),
+    ]);
+    let referenced = reference_secrets(&request, &secrets);
+    assert_eq!(referenced.path, "/cards/{{pin}}/unlock");
+    assert_eq!(referenced.header("X-Pin"), Some("{{pin}}"));
+    assert_eq!(referenced.header("X-Key"), Some("{{key}}"));
+    assert_eq!(referenced.form[0].value, "{{pin}}");
+    // too short to be masked on screen, though never written to disk
+    assert_eq!(mask_request(&request, &[String::from("4711")]).path, "/cards/4711/unlock");
+  }
+
+  #[test]
+  fn test_mask_request_whole_values() {
+    let request = HttpRequest {
+      method: String::from("POST"),
+      base_url: String::from("https://dev.example.com/v1"),
+      path: String::from("/tenants/1/users"),
+      headers: vec![(String::from("X-Signature"), String::from("sig=s3cr3t-token; v=1"))],
+      body: Some(String::from("{\"id\":1,\"note\":\"s3cr3t-tokens rotate\",\"key\":\"s3cr3t-token\"}")),
+      ..HttpRequest::default()
+    };
+    let secrets = [String::from("1"), String::from("dev"), String::from("s3cr3t-token")];
+    let masked = mask_request(&request, &secrets);
+    assert_eq!(masked.base_url, "https://dev.example.com/v1");
+    assert_eq!(masked.path, "/tenants/1/users");
+    assert_eq!(masked.header("X-Signature"), Some("sig=•••; v=1"));
+    assert_eq!(masked.body.as_deref(), Some("{\"id\":1,\"note\":\"s3cr3t-tokens rotate\",\"key\":\"•••\"}"));
+    assert_eq!(replace_whole("aaaa", "aa", "b"), "aaaa");
+    assert_eq!(replace_whole("aa aa", "aa", "b"), "b b");
+  }
+}
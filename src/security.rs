@@ -0,0 +1,140 @@
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SecurityScheme {
+  ApiKey { name: String, location: String },
+  Http { scheme: String },
+  OAuth2,
+  OpenIdConnect,
+}
+
+impl SecurityScheme {
+  pub fn from_value(value: &Value) -> Option<Self> {
+    let text = |key: &str| value.get(key).and_then(Value::as_str).unwrap_or_default().to_string();
+    match value.get("type").and_then(Value::as_str)? {
+      "apiKey" => Some(Self::ApiKey { name: text("name"), location: text("in") }),
+      "http" => Some(Self::Http { scheme: text("scheme").to_lowercase() }),
+      "oauth2" => Some(Self::OAuth2),
+      "openIdConnect" => Some(Self::OpenIdConnect),
+      _ => None,
+    }
+  }
+
+  /// Header name and value template used to send the credential, `None` if the credential does not
+  /// travel in a header.
+  pub fn header(&self, credential: &str) -> Option<(String, String)> {
+    match self {
+      Self::ApiKey { name, location } if location == "header" => Some((name.clone(), credential.to_string())),
+      Self::ApiKey { .. } => None,
+      Self::Http { scheme } if scheme == "basic" => {
+        Some((String::from("Authorization"), format!("Basic {credential}")))
+      },
+      // answers a challenge of the server, see `Auth::Digest`
+      Self::Http { scheme } if scheme == "digest" => None,
+      Self::Http { .. } | Self::OAuth2 | Self::OpenIdConnect => {
+        Some((String::from("Authorization"), format!("Bearer {credential}")))
+      },
+    }
+  }
+}
+
+pub fn security_schemes(document: &Value) -> BTreeMap<String, SecurityScheme> {
+  document
+    .pointer("/components/securitySchemes")
+    .and_then(Value::as_object)
+    .map(|schemes| {
+      schemes
+        .iter()
+        .filter_map(|(name, scheme)| SecurityScheme::from_value(scheme).map(|scheme| (name.clone(), scheme)))
+        .collect()
+    })
+    .unwrap_or_default()
+}
+
+/// Where the security requirements applying to an operation are declared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecuritySource {
+  /// The operation declares its own requirements, overriding the global ones, `[]` making it public.
+  Operation,
+  /// The operation inherits the global requirements of the spec.
+  Global,
+  /// Neither the operation nor the spec declare requirements.
+  None,
+}
+
+fn operation<'a>(document: &'a Value, path: &str, method: &str) -> Option<&'a Value> {
+  document.get("paths").and_then(|paths| paths.get(path)).and_then(|item| item.get(method.to_lowercase()))
+}
+
+pub fn security_source(document: &Value, path: &str, method: &str) -> SecuritySource {
+  if operation(document, path, method).is_some_and(|operation| operation.get("security").is_some()) {
+    SecuritySource::Operation
+  } else if document.get("security").is_some() {
+    SecuritySource::Global
+  } else {
+    SecuritySource::None
+  }
+}
+
+/// Security requirements of an operation, falling back to the global requirements when the operation
+/// does not override them.
+pub fn operation_security(document: &Value, path: &str, method: &str) -> Vec<BTreeMap<String, Vec<String>>> {
+  operation(document, path, method)
+    .and_then(|operation| operation.get("security"))
+    .or_else(|| document.get("security"))
+    .and_then(|security| serde_json::from_value(security.clone()).ok())
+    .unwrap_or_default()
+}
+
+/// The first security scheme of an operation that can be sent as a header.
+pub fn operation_auth_scheme(document: &Value, path: &str, method: &str) -> Option<(String, SecurityScheme)> {
+  let schemes = security_schemes(document);
+  operation_security(document, path, method).into_iter().flat_map(|requirement| requirement.into_keys()).find_map(
+    |name| schemes.get(&name).filter(|scheme| scheme.header("").is_some()).map(|scheme| (name.clone(), scheme.clone())),
+  )
+}
+
+/// Whether the requirements are met: no requirement at all, or one of the alternatives with a credential configured
+/// for each of its schemes.
+pub fn requirements_met(requirements: &[BTreeMap<String, Vec<String>>], configured: impl Fn(&str) -> bool) -> bool {
+  requirements.is_empty() || requirements.iter().any(|requirement| requirement.keys().all(|name| configured(name)))
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+  use serde_json::json;
+
+  use super::*;
+
+  #[test]
+  fn test_operation_security() {
+    let document = json!({
+      "security": [{ "api_key": [] }],
+      "paths": {
+        "/pets": {
+          "get": {},
+          "post": { "security": [{ "oauth": ["write:pets"], "api_key": [] }, { "basic": [] }] },
+          "options": { "security": [] },
+        },
+      },
+    });
+    assert_eq!(security_source(&document, "/pets", "GET"), SecuritySource::Global);
+    assert_eq!(security_source(&document, "/pets", "post"), SecuritySource::Operation);
+    assert_eq!(security_source(&document, "/pets", "options"), SecuritySource::Operation);
+    assert_eq!(security_source(&json!({ "paths": { "/pets": { "get": {} } } }), "/pets", "get"), SecuritySource::None);
+
+    let requirements = operation_security(&document, "/pets", "get");
+    assert_eq!(requirements, vec![BTreeMap::from([(String::from("api_key"), vec![])])]);
+    assert!(operation_security(&document, "/pets", "options").is_empty());
+
+    let requirements = operation_security(&document, "/pets", "post");
+    assert_eq!(requirements[0]["oauth"], vec![String::from("write:pets")]);
+    assert!(requirements_met(&requirements, |name| name == "basic"));
+    assert!(!requirements_met(&requirements, |name| name == "api_key"));
+    assert!(requirements_met(&requirements, |name| name != "basic"));
+    assert!(requirements_met(&[], |_| false));
+  }
+}
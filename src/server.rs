@@ -0,0 +1,90 @@
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ServerVariable {
+  pub name: String,
+  pub default: String,
+  /// Allowed values, any value is allowed when empty.
+  pub choices: Vec<String>,
+  pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Server {
+  /// Url template, e.g. `https://{region}.example.com/v1`.
+  pub url: String,
+  pub description: Option<String>,
+  pub variables: Vec<ServerVariable>,
+}
+
+impl Server {
+  pub fn from_value(value: &Value) -> Option<Self> {
+    let text = |value: &Value, key: &str| value.get(key).and_then(Value::as_str).map(String::from);
+    let variables = value
+      .get("variables")
+      .and_then(Value::as_object)
+      .map(|variables| {
+        variables
+          .iter()
+          .map(|(name, variable)| {
+            ServerVariable {
+              name: name.clone(),
+              default: text(variable, "default").unwrap_or_default(),
+              choices: variable
+                .get("enum")
+                .and_then(Value::as_array)
+                .map(|choices| choices.iter().filter_map(Value::as_str).map(String::from).collect())
+                .unwrap_or_default(),
+              description: text(variable, "description"),
+            }
+          })
+          .collect()
+      })
+      .unwrap_or_default();
+    Some(Self { url: text(value, "url")?, description: text(value, "description"), variables })
+  }
+
+  /// Replaces `{name}` variables of the url with the chosen values, falling back to their defaults.
+  pub fn expand(&self, values: &BTreeMap<String, String>) -> String {
+    self.variables.iter().fold(self.url.clone(), |url, variable| {
+      let value = values.get(&variable.name).unwrap_or(&variable.default);
+      url.replace(format!("{{{}}}", variable.name).as_str(), value)
+    })
+  }
+}
+
+/// Servers listed in a `servers` array, e.g. the root one of the document.
+pub fn servers(value: Option<&Value>) -> Vec<Server> {
+  value
+    .and_then(Value::as_array)
+    .map(|servers| servers.iter().filter_map(Server::from_value).collect())
+    .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_expand() {
+    let document = serde_json::json!({
+      "servers": [{
+        "url": "https://{region}.example.com:{port}/v1",
+        "variables": {
+          "region": { "default": "eu", "enum": ["eu", "us"] },
+          "port": { "default": "443" }
+        }
+      }]
+    });
+    let servers = servers(document.get("servers"));
+    assert_eq!(servers[0].variables[1].choices, vec![String::from("eu"), String::from("us")]);
+    assert_eq!(
+      servers[0].expand(&BTreeMap::from([(String::from("region"), String::from("us"))])),
+      "https://us.example.com:443/v1"
+    );
+  }
+}
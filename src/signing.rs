@@ -0,0 +1,181 @@
+use std::collections::BTreeMap;
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use color_eyre::eyre::Result;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256, Sha512};
+
+use crate::request::{self, HttpRequest};
+
+/// HMAC signature of the requests sent with an environment, computed over a template of their parts and sent in a
+/// header, e.g. for partner APIs signing `method + path + timestamp`.
+///
+/// The template and the header value reference the parts of the request as `{method}`, `{path}`, `{query}`,
+/// `{host}`, `{body}` and `{body_sha256}`, the time as `{timestamp}`, `{timestamp_ms}` and `{date}`, and the header
+/// value the signature as `{signature}`. The template and the secret may reference variables as `{{name}}`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Signing {
+  pub template: String,
+  pub secret: String,
+  #[serde(default)]
+  pub algorithm: Algorithm,
+  #[serde(default)]
+  pub encoding: Encoding,
+  #[serde(default = "default_header")]
+  pub header: String,
+  /// Value of the header, e.g. `HMAC {timestamp}:{signature}`.
+  #[serde(default = "default_value")]
+  pub value: String,
+  /// Header the timestamp is sent in as well, e.g. `X-Timestamp`.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub timestamp_header: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Algorithm {
+  #[default]
+  HmacSha256,
+  HmacSha512,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Encoding {
+  #[default]
+  Hex,
+  Base64,
+}
+
+fn default_header() -> String {
+  String::from("X-Signature")
+}
+
+fn default_value() -> String {
+  String::from("{signature}")
+}
+
+impl Signing {
+  /// Sets the signature header of the request, as sent at `now`.
+  pub fn sign(
+    &self,
+    request: &mut HttpRequest,
+    variables: &BTreeMap<String, String>,
+    now: chrono::DateTime<chrono::Utc>,
+  ) -> Result<()> {
+    let url = reqwest::Url::parse(&request.full_url())?;
+    let body = request.body.clone().unwrap_or_default();
+    let mut parts = vec![
+      ("method", request.method.to_uppercase()),
+      ("path", url.path().to_string()),
+      ("query", url.query().unwrap_or_default().to_string()),
+      ("host", url.host_str().unwrap_or_default().to_string()),
+      ("body_sha256", hex::encode(Sha256::digest(&body))),
+      ("body", body),
+      ("timestamp_ms", now.timestamp_millis().to_string()),
+      ("timestamp", now.timestamp().to_string()),
+      ("date", now.to_rfc2822()),
+    ];
+    let message = fill(&request::substitute(&self.template, variables), &parts);
+    let secret = request::substitute(&self.secret, variables);
+    let signature = match self.algorithm {
+      Algorithm::HmacSha256 => {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+        mac.update(message.as_bytes());
+        mac.finalize().into_bytes().to_vec()
+      },
+      Algorithm::HmacSha512 => {
+        let mut mac = Hmac::<Sha512>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+        mac.update(message.as_bytes());
+        mac.finalize().into_bytes().to_vec()
+      },
+    };
+    let signature = match self.encoding {
+      Encoding::Hex => hex::encode(signature),
+      Encoding::Base64 => STANDARD.encode(signature),
+    };
+    parts.push(("signature", signature));
+    request.set_header(&self.header, fill(&self.value, &parts));
+    if let Some(timestamp_header) = &self.timestamp_header {
+      request.set_header(timestamp_header, now.timestamp().to_string());
+    }
+    Ok(())
+  }
+}
+
+/// Replaces the `{name}` placeholders of the template, leaving the `{{name}}` variable references untouched.
+fn fill(template: &str, parts: &[(&str, String)]) -> String {
+  let mut filled = String::new();
+  let mut rest = template;
+  while let Some(start) = rest.find('{') {
+    filled.push_str(&rest[..start]);
+    rest = &rest[start..];
+    if let Some(reference) = rest.strip_prefix("{{") {
+      filled.push_str("{{");
+      rest = reference;
+      continue;
+    }
+    let part = rest[1..].find('}').and_then(|end| {
+      let name = &rest[1..end + 1];
+      parts.iter().find(|(part, _)| *part == name).map(|(_, value)| (value, end + 2))
+    });
+    match part {
+      Some((value, len)) => {
+        filled.push_str(value);
+        rest = &rest[len..];
+      },
+      None => {
+        filled.push('{');
+        rest = &rest[1..];
+      },
+    }
+  }
+  filled.push_str(rest);
+  filled
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_sign() {
+    let signing: Signing = serde_json::from_str(
+      r#"{ "template": "{method}\n{path}\n{timestamp}", "secret": "{{partnerSecret}}", "timestamp_header": "X-Timestamp" }"#,
+    )
+    .unwrap();
+    let mut request = HttpRequest {
+      method: String::from("get"),
+      base_url: String::from("https://api.example.com/v1"),
+      path: String::from("/pets"),
+      query: vec![(String::from("limit"), String::from("10"))],
+      ..HttpRequest::default()
+    };
+    let variables = BTreeMap::from([(String::from("partnerSecret"), String::from("key"))]);
+    let now = chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+    signing.sign(&mut request, &variables, now).unwrap();
+    let mut mac = Hmac::<Sha256>::new_from_slice(b"key").unwrap();
+    mac.update(b"GET\n/v1/pets\n1700000000");
+    assert_eq!(request.header("X-Signature"), Some(hex::encode(mac.finalize().into_bytes()).as_str()));
+    assert_eq!(request.header("X-Timestamp"), Some("1700000000"));
+
+    let signing = Signing {
+      encoding: Encoding::Base64,
+      header: String::from("Authorization"),
+      value: String::from("HMAC {timestamp}:{signature}"),
+      ..signing
+    };
+    signing.sign(&mut request, &variables, now).unwrap();
+    assert!(request.header("Authorization").unwrap().starts_with("HMAC 1700000000:"));
+    assert!(request.header("Authorization").unwrap().ends_with('='));
+  }
+
+  #[test]
+  fn test_fill() {
+    let parts = [("method", String::from("GET"))];
+    assert_eq!(fill("{method} {unknown} {{method}} {", &parts), "GET {unknown} {{method}} {");
+  }
+}
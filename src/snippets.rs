@@ -0,0 +1,348 @@
+use strum::{Display, EnumIter};
+
+use crate::{multipart::FormPart, request::HttpRequest};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Display, EnumIter)]
+pub enum Language {
+  #[default]
+  #[strum(to_string = "cURL")]
+  Curl,
+  #[strum(to_string = "Python")]
+  Python,
+  #[strum(to_string = "JavaScript")]
+  JavaScript,
+  #[strum(to_string = "Go")]
+  Go,
+  #[strum(to_string = "Rust")]
+  Rust,
+}
+
+impl Language {
+  pub fn render(&self, request: &HttpRequest) -> String {
+    match self {
+      Language::Curl => curl(request),
+      Language::Python => python(request),
+      Language::JavaScript => javascript(request),
+      Language::Go => go(request),
+      Language::Rust => rust(request),
+    }
+  }
+}
+
+fn quote(value: &str) -> String {
+  serde_json::to_string(value).unwrap_or_default()
+}
+
+fn shell_quote(value: &str) -> String {
+  format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+fn is_multipart(request: &HttpRequest) -> bool {
+  !request.form.is_empty()
+}
+
+/// Headers of the request, without the content type of multipart bodies that every client sets along with the
+/// boundary.
+fn headers(request: &HttpRequest) -> impl Iterator<Item = &(String, String)> {
+  request.headers.iter().filter(|(key, _)| !(is_multipart(request) && key.eq_ignore_ascii_case("Content-Type")))
+}
+
+fn form_parts(request: &HttpRequest) -> impl Iterator<Item = &FormPart> {
+  request.form.iter().filter(|part| !part.value.is_empty())
+}
+
+/// Body of the request, unless it is sent as multipart form parts.
+fn body(request: &HttpRequest) -> Option<&String> {
+  request.body.as_ref().filter(|_| !is_multipart(request))
+}
+
+fn curl(request: &HttpRequest) -> String {
+  let mut lines = vec![format!("curl -X {} {}", request.method, shell_quote(&request.full_url()))];
+  for (key, value) in headers(request) {
+    lines.push(format!("  -H {}", shell_quote(&format!("{key}: {value}"))));
+  }
+  for part in form_parts(request) {
+    let value = if part.file { format!("@{}", part.value) } else { part.value.clone() };
+    lines.push(format!("  -F {}", shell_quote(&format!("{}={value}", part.name))));
+  }
+  if let Some(body) = body(request) {
+    lines.push(format!("  --data-raw {}", shell_quote(body)));
+  }
+  lines.join(" \\\n")
+}
+
+fn python(request: &HttpRequest) -> String {
+  let mut code = String::from("import requests\n\n");
+  code.push_str(format!("url = {}\n", quote(&request.url())).as_str());
+  let mut arguments = vec![quote(&request.method), String::from("url")];
+  if !request.query.is_empty() {
+    code.push_str("params = {\n");
+    for (key, value) in request.query.iter() {
+      code.push_str(format!("    {}: {},\n", quote(key), quote(value)).as_str());
+    }
+    code.push_str("}\n");
+    arguments.push(String::from("params=params"));
+  }
+  if headers(request).next().is_some() {
+    code.push_str("headers = {\n");
+    for (key, value) in headers(request) {
+      code.push_str(format!("    {}: {},\n", quote(key), quote(value)).as_str());
+    }
+    code.push_str("}\n");
+    arguments.push(String::from("headers=headers"));
+  }
+  if is_multipart(request) {
+    code.push_str("files = [\n");
+    for part in form_parts(request) {
+      let value = if part.file { format!("open({}, \"rb\")", quote(&part.value)) } else { quote(&part.value) };
+      let file_name = if part.file { quote(&part.value) } else { String::from("None") };
+      code.push_str(format!("    ({}, ({file_name}, {value})),\n", quote(&part.name)).as_str());
+    }
+    code.push_str("]\n");
+    arguments.push(String::from("files=files"));
+  }
+  if let Some(body) = body(request) {
+    code.push_str(format!("payload = {}\n", quote(body)).as_str());
+    arguments.push(String::from("data=payload"));
+  }
+  code.push_str(format!("\nresponse = requests.request({})\n", arguments.join(", ")).as_str());
+  code.push_str("print(response.status_code)\nprint(response.text)\n");
+  code
+}
+
+fn javascript(request: &HttpRequest) -> String {
+  let mut code = String::new();
+  if form_parts(request).any(|part| part.file) {
+    code.push_str("import { openAsBlob } from \"node:fs\";\n\n");
+  }
+  if is_multipart(request) {
+    code.push_str("const form = new FormData();\n");
+    for part in form_parts(request) {
+      let value = if part.file {
+        format!("await openAsBlob({}), {}", quote(&part.value), quote(&part.value))
+      } else {
+        quote(&part.value)
+      };
+      code.push_str(format!("form.append({}, {value});\n", quote(&part.name)).as_str());
+    }
+    code.push('\n');
+  }
+  code.push_str(format!("const response = await fetch({}, {{\n", quote(&request.full_url())).as_str());
+  code.push_str(format!("  method: {},\n", quote(&request.method)).as_str());
+  if headers(request).next().is_some() {
+    code.push_str("  headers: {\n");
+    for (key, value) in headers(request) {
+      code.push_str(format!("    {}: {},\n", quote(key), quote(value)).as_str());
+    }
+    code.push_str("  },\n");
+  }
+  if is_multipart(request) {
+    code.push_str("  body: form,\n");
+  } else if let Some(body) = body(request) {
+    code.push_str(format!("  body: {},\n", quote(body)).as_str());
+  }
+  code.push_str("});\n\nconsole.log(response.status);\nconsole.log(await response.text());\n");
+  code
+}
+
+fn go(request: &HttpRequest) -> String {
+  let multipart = is_multipart(request);
+  let files = form_parts(request).any(|part| part.file);
+  let mut code = String::from("package main\n\nimport (\n");
+  if multipart {
+    code.push_str("\t\"bytes\"\n");
+  }
+  code.push_str("\t\"fmt\"\n\t\"io\"\n");
+  if multipart {
+    code.push_str("\t\"mime/multipart\"\n");
+  }
+  code.push_str("\t\"net/http\"\n");
+  if files {
+    code.push_str("\t\"os\"\n\t\"path/filepath\"\n");
+  }
+  if body(request).is_some() {
+    code.push_str("\t\"strings\"\n");
+  }
+  code.push_str(")\n\nfunc main() {\n");
+  let body = if multipart {
+    code.push_str("\tbody := &bytes.Buffer{}\n\twriter := multipart.NewWriter(body)\n");
+    for part in form_parts(request) {
+      if part.file {
+        code.push_str(
+          format!("\tif err := addFile(writer, {}, {}); err != nil {{\n", quote(&part.name), quote(&part.value))
+            .as_str(),
+        );
+        code.push_str("\t\tpanic(err)\n\t}\n");
+      } else {
+        code.push_str(format!("\twriter.WriteField({}, {})\n", quote(&part.name), quote(&part.value)).as_str());
+      }
+    }
+    code.push_str("\twriter.Close()\n");
+    "body"
+  } else {
+    match body(request) {
+      Some(body) => {
+        code.push_str(format!("\tbody := strings.NewReader({})\n", quote(body)).as_str());
+        "body"
+      },
+      None => "nil",
+    }
+  };
+  code.push_str(
+    format!("\treq, err := http.NewRequest({}, {}, {body})\n", quote(&request.method), quote(&request.full_url()))
+      .as_str(),
+  );
+  code.push_str("\tif err != nil {\n\t\tpanic(err)\n\t}\n");
+  for (key, value) in headers(request) {
+    code.push_str(format!("\treq.Header.Set({}, {})\n", quote(key), quote(value)).as_str());
+  }
+  if multipart {
+    code.push_str("\treq.Header.Set(\"Content-Type\", writer.FormDataContentType())\n");
+  }
+  code.push_str("\n\tres, err := http.DefaultClient.Do(req)\n\tif err != nil {\n\t\tpanic(err)\n\t}\n");
+  code.push_str("\tdefer res.Body.Close()\n\n\tdata, _ := io.ReadAll(res.Body)\n");
+  code.push_str("\tfmt.Println(res.Status)\n\tfmt.Println(string(data))\n}\n");
+  if files {
+    code.push_str("\nfunc addFile(writer *multipart.Writer, name string, path string) error {\n");
+    code.push_str("\tfile, err := os.Open(path)\n\tif err != nil {\n\t\treturn err\n\t}\n\tdefer file.Close()\n\n");
+    code.push_str(
+      "\tpart, err := writer.CreateFormFile(name, filepath.Base(path))\n\tif err != nil {\n\t\treturn err\n\t}\n",
+    );
+    code.push_str("\t_, err = io.Copy(part, file)\n\treturn err\n}\n");
+  }
+  code
+}
+
+fn rust(request: &HttpRequest) -> String {
+  let files = form_parts(request).any(|part| part.file);
+  // reading the files of a form fails with an I/O error rather than a reqwest one
+  let error = if files { "Box<dyn std::error::Error>" } else { "reqwest::Error" };
+  let mut code = format!("fn main() -> Result<(), {error}> {{\n");
+  code.push_str("    let client = reqwest::blocking::Client::new();\n");
+  if is_multipart(request) {
+    code.push_str("    let form = reqwest::blocking::multipart::Form::new()");
+    for part in form_parts(request) {
+      if part.file {
+        code.push_str(format!("\n        .file({:?}, {:?})?", part.name, part.value).as_str());
+      } else {
+        code.push_str(format!("\n        .text({:?}, {:?})", part.name, part.value).as_str());
+      }
+    }
+    code.push_str(";\n");
+  }
+  code.push_str("    let response = client\n");
+  code.push_str(format!("        .request(reqwest::Method::{}, {:?})\n", request.method, request.url()).as_str());
+  if !request.query.is_empty() {
+    let query =
+      request.query.iter().map(|(key, value)| format!("({:?}, {:?})", key, value)).collect::<Vec<_>>().join(", ");
+    code.push_str(format!("        .query(&[{query}])\n").as_str());
+  }
+  for (key, value) in headers(request) {
+    code.push_str(format!("        .header({:?}, {:?})\n", key, value).as_str());
+  }
+  if is_multipart(request) {
+    code.push_str("        .multipart(form)\n");
+  }
+  if let Some(body) = body(request) {
+    let mut hashes = String::from("#");
+    while body.contains(format!("\"{hashes}").as_str()) {
+      hashes.push('#');
+    }
+    code.push_str(format!("        .body(r{hashes}\"{body}\"{hashes})\n").as_str());
+  }
+  code.push_str("        .send()?;\n\n");
+  code.push_str("    println!(\"{}\", response.status());\n");
+  code.push_str("    println!(\"{}\", response.text()?);\n");
+  code.push_str("    Ok(())\n}\n");
+  code
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  fn request() -> HttpRequest {
+    HttpRequest {
+      method: String::from("POST"),
+      base_url: String::from("https://petstore.swagger.io/v2"),
+      path: String::from("/pet"),
+      query: vec![(String::from("dry run"), String::from("yes"))],
+      headers: vec![(String::from("Content-Type"), String::from("application/json"))],
+      body: Some(String::from(r#"{"name": "doggie"}"#)),
+      form: vec![],
+      digest: None,
+      network: None,
+    }
+  }
+
+  #[test]
+  fn test_curl() {
+    assert_eq!(
+      Language::Curl.render(&request()),
+      "curl -X POST 'https://petstore.swagger.io/v2/pet?dry%20run=yes' \\\n  -H 'Content-Type: application/json' \\\n  \
+       --data-raw '{\"name\": \"doggie\"}'"
+    );
+  }
+
+  fn multipart_request() -> HttpRequest {
+    let mut request = request();
+    request.form =
+      vec![FormPart { name: String::from("name"), value: String::from("doggie"), file: false }, FormPart {
+        name: String::from("photo"),
+        value: String::from("dog.png"),
+        file: true,
+      }];
+    request
+  }
+
+  #[test]
+  fn test_curl_multipart() {
+    let request = multipart_request();
+    assert_eq!(
+      Language::Curl.render(&request),
+      "curl -X POST 'https://petstore.swagger.io/v2/pet?dry%20run=yes' \\\n  -F 'name=doggie' \\\n  -F 'photo=@dog.png'"
+    );
+  }
+
+  #[test]
+  fn test_multipart() {
+    let request = multipart_request();
+    let python = Language::Python.render(&request);
+    assert!(python
+      .contains("    (\"name\", (None, \"doggie\")),\n    (\"photo\", (\"dog.png\", open(\"dog.png\", \"rb\"))),\n"));
+    assert!(python.contains("requests.request(\"POST\", url, params=params, files=files)"));
+    let javascript = Language::JavaScript.render(&request);
+    assert!(javascript.contains(
+      "form.append(\"name\", \"doggie\");\nform.append(\"photo\", await openAsBlob(\"dog.png\"), \"dog.png\");"
+    ));
+    assert!(javascript.contains("  body: form,\n"));
+    let go = Language::Go.render(&request);
+    assert!(go.contains(
+      "\twriter.WriteField(\"name\", \"doggie\")\n\tif err := addFile(writer, \"photo\", \"dog.png\"); err != nil {"
+    ));
+    assert!(go.contains("req.Header.Set(\"Content-Type\", writer.FormDataContentType())"));
+    let rust = Language::Rust.render(&request);
+    assert!(rust.contains("Form::new()\n        .text(\"name\", \"doggie\")\n        .file(\"photo\", \"dog.png\")?;"));
+    assert!(rust.contains("        .multipart(form)\n        .send()?;"));
+    for code in [python, javascript, go, rust] {
+      assert!(!code.contains("application/json") && !code.contains("doggie\\\""), "{code}");
+    }
+  }
+
+  #[test]
+  fn test_rust_raw_string_delimiter() {
+    let mut request = request();
+    request.body = Some(String::from(r##"{"tag": "a"#b"}"##));
+    let code = Language::Rust.render(&request);
+    assert!(code.contains(r###".body(r##"{"tag": "a"#b"}"##)"###));
+  }
+
+  #[test]
+  fn test_python_escapes_body() {
+    let code = Language::Python.render(&request());
+    assert!(code.contains(r#"payload = "{\"name\": \"doggie\"}""#));
+    assert!(code.contains("response = requests.request(\"POST\", url, params=params, headers=headers, data=payload)"));
+  }
+}
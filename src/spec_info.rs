@@ -0,0 +1,144 @@
+use serde_json::Value;
+
+/// A link of the spec, e.g. its `externalDocs` or the url of its license.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Link {
+  pub url: String,
+  pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Contact {
+  pub name: Option<String>,
+  pub email: Option<String>,
+  pub url: Option<String>,
+}
+
+/// A tag of the spec, with the operations tagged with it and its own `externalDocs`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TagInfo {
+  pub name: String,
+  pub description: Option<String>,
+  pub external_docs: Option<Link>,
+  pub operations: usize,
+}
+
+/// Who owns the API and where it is documented, from the `info` of the spec and its tags.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SpecInfo {
+  pub title: String,
+  pub version: String,
+  pub description: Option<String>,
+  pub terms_of_service: Option<String>,
+  pub contact: Option<Contact>,
+  /// Name of the license as its description, with its url or else its SPDX identifier, if any.
+  pub license: Option<Link>,
+  pub external_docs: Option<Link>,
+  /// Declared tags in order, followed by the ones only used by operations.
+  pub tags: Vec<TagInfo>,
+}
+
+fn text(value: &Value, key: &str) -> Option<String> {
+  value.get(key).and_then(Value::as_str).filter(|text| !text.is_empty()).map(String::from)
+}
+
+fn link(value: Option<&Value>) -> Option<Link> {
+  let value = value?;
+  Some(Link { url: text(value, "url")?, description: text(value, "description") })
+}
+
+impl SpecInfo {
+  pub fn new(document: &Value) -> Self {
+    let info = document.get("info").unwrap_or(&Value::Null);
+    let contact = info
+      .get("contact")
+      .map(|contact| Contact { name: text(contact, "name"), email: text(contact, "email"), url: text(contact, "url") });
+    let license = info.get("license").and_then(|license| {
+      let url = text(license, "url").or_else(|| text(license, "identifier")).unwrap_or_default();
+      Some(Link { url, description: Some(text(license, "name")?) })
+    });
+    let mut tags: Vec<TagInfo> = document
+      .get("tags")
+      .and_then(Value::as_array)
+      .into_iter()
+      .flatten()
+      .filter_map(|tag| {
+        Some(TagInfo {
+          name: text(tag, "name")?,
+          description: text(tag, "description"),
+          external_docs: link(tag.get("externalDocs")),
+          operations: 0,
+        })
+      })
+      .collect();
+    let items = document.get("paths").and_then(Value::as_object).into_iter().flat_map(|paths| paths.values());
+    let operations = items.filter_map(Value::as_object).flat_map(|item| item.values());
+    for name in operations.filter_map(|operation| operation.get("tags")?.as_array()).flatten().filter_map(Value::as_str)
+    {
+      match tags.iter_mut().find(|tag| tag.name == name) {
+        Some(tag) => tag.operations += 1,
+        None => tags.push(TagInfo { name: name.to_string(), operations: 1, ..TagInfo::default() }),
+      }
+    }
+    Self {
+      title: text(info, "title").unwrap_or_default(),
+      version: text(info, "version").unwrap_or_default(),
+      description: text(info, "description"),
+      terms_of_service: text(info, "termsOfService"),
+      contact: contact.filter(|contact| contact != &Contact::default()),
+      license,
+      external_docs: link(document.get("externalDocs")),
+      tags,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+  use serde_json::json;
+
+  use super::*;
+
+  #[test]
+  fn test_spec_info() {
+    let document = json!({
+      "info": {
+        "title": "Petstore",
+        "version": "1.2.0",
+        "contact": { "name": "Pets team", "email": "pets@example.com" },
+        "license": { "name": "MIT", "identifier": "MIT" },
+      },
+      "externalDocs": { "url": "https://docs.example.com" },
+      "tags": [
+        { "name": "pets", "description": "Everything about pets", "externalDocs": { "url": "https://wiki.example.com/pets", "description": "Runbook" } },
+        { "name": "store" },
+      ],
+      "paths": {
+        "/pets": {
+          "get": { "tags": ["pets"] },
+          "post": { "tags": ["pets", "admin"] },
+          "parameters": [],
+        },
+      },
+    });
+    let info = SpecInfo::new(&document);
+    assert_eq!(info.version, "1.2.0");
+    assert_eq!(
+      info.contact,
+      Some(Contact { name: Some(String::from("Pets team")), email: Some(String::from("pets@example.com")), url: None })
+    );
+    assert_eq!(info.license, Some(Link { url: String::from("MIT"), description: Some(String::from("MIT")) }));
+    assert_eq!(info.external_docs, Some(Link { url: String::from("https://docs.example.com"), description: None }));
+    let tags = info.tags.iter().map(|tag| (tag.name.as_str(), tag.operations)).collect::<Vec<_>>();
+    assert_eq!(tags, vec![("pets", 2), ("store", 0), ("admin", 1)]);
+    assert_eq!(
+      info.tags[0].external_docs,
+      Some(Link { url: String::from("https://wiki.example.com/pets"), description: Some(String::from("Runbook")) })
+    );
+
+    let bare = SpecInfo::new(&json!({ "info": { "title": "Bare", "version": "1", "contact": {} } }));
+    assert_eq!(bare.contact, None);
+    assert_eq!(bare.license, None);
+  }
+}
@@ -0,0 +1,189 @@
+use std::{
+  fs::{self, File},
+  io::{BufRead, BufReader, BufWriter, Seek, SeekFrom, Write},
+  path::PathBuf,
+  sync::atomic::{AtomicUsize, Ordering},
+};
+
+use color_eyre::eyre::Result;
+
+/// Bodies larger than this are written to a file as they are received instead of being kept in memory.
+pub const THRESHOLD: usize = 8 * 1024 * 1024;
+
+/// Head of a spooled body kept in memory, for the assertions, extractions and scripts reading the body.
+pub const HEAD: usize = 1024 * 1024;
+
+/// Lines between two offsets indexed in a spooled body, so that the index stays small for millions of lines.
+const STRIDE: usize = 256;
+
+static SPOOLED: AtomicUsize = AtomicUsize::new(0);
+
+/// Body written to a file as it was received.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SpooledBody {
+  pub path: PathBuf,
+  pub len: u64,
+}
+
+/// Directory of the bodies spooled during this session, removed on exit.
+pub fn directory() -> PathBuf {
+  std::env::temp_dir().join(format!("openapi-tui-bodies-{}", std::process::id()))
+}
+
+pub fn clean_up() {
+  let _ = fs::remove_dir_all(directory());
+}
+
+/// Body being received, kept in memory until it grows past the threshold and written to a file from then on.
+#[derive(Default)]
+pub struct Spool {
+  head: Vec<u8>,
+  file: Option<(PathBuf, BufWriter<File>)>,
+  len: u64,
+}
+
+impl Spool {
+  pub fn write(&mut self, chunk: &[u8]) -> Result<()> {
+    self.len += chunk.len() as u64;
+    if let Some((_, file)) = self.file.as_mut() {
+      file.write_all(chunk)?;
+      return Ok(());
+    }
+    self.head.extend_from_slice(chunk);
+    if self.head.len() > THRESHOLD {
+      fs::create_dir_all(directory())?;
+      let path = directory().join(format!("{}.body", SPOOLED.fetch_add(1, Ordering::Relaxed)));
+      let mut file = BufWriter::new(File::create(&path)?);
+      file.write_all(&self.head)?;
+      self.head.truncate(HEAD);
+      self.file = Some((path, file));
+    }
+    Ok(())
+  }
+
+  /// The body when it stayed small, its head along with the file it was written to otherwise.
+  pub fn finish(self) -> Result<(Vec<u8>, Option<SpooledBody>)> {
+    match self.file {
+      Some((path, mut file)) => {
+        file.flush()?;
+        Ok((self.head, Some(SpooledBody { path, len: self.len })))
+      },
+      None => Ok((self.head, None)),
+    }
+  }
+}
+
+/// Lines of a spooled body read on demand, the body being indexed only as far as it was scrolled.
+pub struct BodyWindow {
+  reader: BufReader<File>,
+  /// Offsets of every `STRIDE`th line.
+  checkpoints: Vec<u64>,
+  /// Lines indexed so far, all of them once `complete`.
+  lines: usize,
+  /// Offset the indexing stopped at.
+  end: u64,
+  complete: bool,
+}
+
+impl BodyWindow {
+  pub fn open(body: &SpooledBody) -> Result<Self> {
+    let reader = BufReader::new(File::open(&body.path)?);
+    Ok(Self { reader, checkpoints: vec![], lines: 0, end: 0, complete: false })
+  }
+
+  fn index_to(&mut self, line: usize) -> Result<()> {
+    if self.complete || line < self.lines {
+      return Ok(());
+    }
+    self.reader.seek(SeekFrom::Start(self.end))?;
+    let mut buffer = vec![];
+    while self.lines <= line {
+      if self.lines.is_multiple_of(STRIDE) && self.checkpoints.len() == self.lines / STRIDE {
+        self.checkpoints.push(self.end);
+      }
+      buffer.clear();
+      let read = self.reader.read_until(b'\n', &mut buffer)?;
+      if read == 0 {
+        self.complete = true;
+        break;
+      }
+      self.end += read as u64;
+      self.lines += 1;
+    }
+    Ok(())
+  }
+
+  /// Whether the body has the line, indexing it up to the line.
+  pub fn has_line(&mut self, line: usize) -> Result<bool> {
+    self.index_to(line)?;
+    Ok(line < self.lines)
+  }
+
+  /// Number of lines of the body, indexing all of it.
+  pub fn line_count(&mut self) -> Result<usize> {
+    self.index_to(usize::MAX - 1)?;
+    Ok(self.lines)
+  }
+
+  /// Up to `count` lines from the `first` one, decoded as UTF-8.
+  pub fn lines(&mut self, first: usize, count: usize) -> Result<Vec<String>> {
+    if !self.has_line(first)? {
+      return Ok(vec![]);
+    }
+    self.reader.seek(SeekFrom::Start(self.checkpoints[first / STRIDE]))?;
+    let mut buffer = vec![];
+    for _ in 0..first % STRIDE {
+      self.reader.read_until(b'\n', &mut buffer)?;
+    }
+    let mut lines = vec![];
+    while lines.len() < count {
+      buffer.clear();
+      if self.reader.read_until(b'\n', &mut buffer)? == 0 {
+        break;
+      }
+      let line = String::from_utf8_lossy(&buffer);
+      lines.push(line.trim_end_matches(['\n', '\r']).to_string());
+    }
+    Ok(lines)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_spool() {
+    let mut spool = Spool::default();
+    spool.write(b"{\"id\":1}\n").unwrap();
+    let (head, spooled) = spool.finish().unwrap();
+    assert_eq!((head.as_slice(), spooled), (&b"{\"id\":1}\n"[..], None));
+
+    let mut spool = Spool::default();
+    let mut len = 0;
+    for id in 0.. {
+      let line = format!("{{\"id\":{id}}}\n");
+      spool.write(line.as_bytes()).unwrap();
+      len += line.len();
+      if len > THRESHOLD + STRIDE * 10 {
+        break;
+      }
+    }
+    let (head, spooled) = spool.finish().unwrap();
+    let spooled = spooled.unwrap();
+    assert_eq!(head.len(), HEAD);
+    assert_eq!(spooled.len, len as u64);
+    assert_eq!(fs::metadata(&spooled.path).unwrap().len(), len as u64);
+
+    let mut window = BodyWindow::open(&spooled).unwrap();
+    assert_eq!(window.lines(600, 2).unwrap(), vec![r#"{"id":600}"#, r#"{"id":601}"#]);
+    assert_eq!(window.lines(3, 1).unwrap(), vec![r#"{"id":3}"#]);
+    let lines = window.line_count().unwrap();
+    assert!(window.has_line(lines - 1).unwrap());
+    assert!(!window.has_line(lines).unwrap());
+    assert_eq!(window.lines(lines - 1, 5).unwrap(), vec![format!("{{\"id\":{}}}", lines - 1)]);
+    fs::remove_file(&spooled.path).unwrap();
+  }
+}
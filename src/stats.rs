@@ -0,0 +1,151 @@
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+use crate::mock::{resolve, METHODS};
+
+/// Counts giving a quick overview of the health of a spec.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Stats {
+  pub operations: usize,
+  /// Operations by uppercase method, e.g. `GET`.
+  pub methods: BTreeMap<String, usize>,
+  /// Operations by tag, an operation counting once for each of its tags.
+  pub tags: BTreeMap<String, usize>,
+  pub untagged: usize,
+  /// Component schemas of the document.
+  pub schemas: usize,
+  /// Operations with neither a description nor a summary.
+  pub missing_descriptions: usize,
+  pub missing_operation_ids: usize,
+  /// Operations without an example in any of the media types of their request body and responses.
+  pub missing_examples: usize,
+  pub deprecated: usize,
+  /// Operations requiring each security scheme, the declared schemes no operation requires counted as 0.
+  pub security: BTreeMap<String, usize>,
+  /// Operations without any security requirement, of their own or global.
+  pub unsecured: usize,
+}
+
+impl Stats {
+  pub fn new(document: &Value) -> Self {
+    let mut stats = Self::default();
+    let schemes = document.pointer("/components/securitySchemes").and_then(Value::as_object).into_iter().flatten();
+    stats.security = schemes.map(|(name, _)| (name.clone(), 0)).collect();
+    stats.schemas =
+      document.pointer("/components/schemas").and_then(Value::as_object).map_or(0, |schemas| schemas.len());
+    let paths = document.get("paths").and_then(Value::as_object).into_iter().flatten();
+    for (_, item) in paths {
+      let item = resolve(item, document);
+      for method in METHODS {
+        if let Some(operation) = item.get(method) {
+          stats.count(document, method, operation);
+        }
+      }
+    }
+    stats
+  }
+
+  fn count(&mut self, document: &Value, method: &str, operation: &Value) {
+    let text = |name: &str| operation.get(name).and_then(Value::as_str).is_some_and(|text| !text.trim().is_empty());
+    self.operations += 1;
+    *self.methods.entry(method.to_uppercase()).or_default() += 1;
+    let tags: Vec<&str> =
+      operation.get("tags").and_then(Value::as_array).into_iter().flatten().filter_map(Value::as_str).collect();
+    match tags.is_empty() {
+      true => self.untagged += 1,
+      false => tags.into_iter().for_each(|tag| *self.tags.entry(tag.to_string()).or_default() += 1),
+    }
+    self.missing_descriptions += usize::from(!text("description") && !text("summary"));
+    self.missing_operation_ids += usize::from(!text("operationId"));
+    self.missing_examples += usize::from(!has_example(document, operation));
+    self.deprecated += usize::from(operation.get("deprecated").and_then(Value::as_bool).unwrap_or_default());
+
+    let requirements = operation.get("security").or_else(|| document.get("security")).and_then(Value::as_array);
+    let mut schemes: Vec<&String> = requirements
+      .into_iter()
+      .flatten()
+      .filter_map(Value::as_object)
+      .flat_map(|requirement| requirement.keys())
+      .collect();
+    schemes.sort();
+    schemes.dedup();
+    match schemes.is_empty() {
+      true => self.unsecured += 1,
+      false => schemes.into_iter().for_each(|scheme| *self.security.entry(scheme.clone()).or_default() += 1),
+    }
+  }
+}
+
+/// Whether a media type of the request body or of a response of the operation has an example, of its own or of its
+/// schema.
+fn has_example(document: &Value, operation: &Value) -> bool {
+  let request_body = operation.get("requestBody").into_iter();
+  let responses =
+    operation.get("responses").and_then(Value::as_object).into_iter().flat_map(|responses| responses.values());
+  request_body.chain(responses).any(|body| {
+    let content = resolve(body, document).get("content").and_then(Value::as_object).into_iter().flatten();
+    content.map(|(_, media_type)| media_type).any(|media_type| {
+      let schema = media_type.get("schema").map(|schema| resolve(schema, document));
+      ["example", "examples"]
+        .iter()
+        .any(|key| media_type.get(key).is_some() || schema.is_some_and(|schema| schema.get(key).is_some()))
+    })
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+  use serde_json::json;
+
+  use super::*;
+
+  #[test]
+  fn test_stats() {
+    let document = json!({
+      "openapi": "3.0.0",
+      "security": [{ "apiKey": [] }],
+      "paths": {
+        "/pets": {
+          "get": {
+            "tags": ["pets"],
+            "operationId": "listPets",
+            "summary": "List pets",
+            "responses": { "200": { "content": { "application/json": {
+              "schema": { "$ref": "#/components/schemas/Pets" }
+            } } } }
+          },
+          "post": {
+            "tags": ["pets", "admin"],
+            "deprecated": true,
+            "security": [{ "oauth": ["write"] }, { "apiKey": [] }],
+            "requestBody": { "content": { "application/json": { "example": { "name": "Rex" } } } },
+            "responses": { "201": { "description": "Created" } }
+          }
+        },
+        "/health": {
+          "get": { "security": [], "description": "Health", "responses": { "204": { "description": "Alive" } } }
+        }
+      },
+      "components": {
+        "schemas": { "Pets": { "type": "array", "example": [] }, "Pet": { "type": "object" } },
+        "securitySchemes": { "apiKey": { "type": "apiKey" }, "oauth": { "type": "oauth2" }, "basic": { "type": "http" } }
+      }
+    });
+    let stats = Stats::new(&document);
+    assert_eq!(stats, Stats {
+      operations: 3,
+      methods: BTreeMap::from([(String::from("GET"), 2), (String::from("POST"), 1)]),
+      tags: BTreeMap::from([(String::from("admin"), 1), (String::from("pets"), 2)]),
+      untagged: 1,
+      schemas: 2,
+      missing_descriptions: 1,
+      missing_operation_ids: 2,
+      missing_examples: 1,
+      deprecated: 1,
+      security: BTreeMap::from([(String::from("apiKey"), 2), (String::from("basic"), 0), (String::from("oauth"), 1)]),
+      unsecured: 1,
+    });
+  }
+}
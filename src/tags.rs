@@ -0,0 +1,260 @@
+use serde_json::Value;
+
+use crate::{mock::METHODS, path_tree::PathNode};
+
+/// Separators of hierarchical tag names, e.g. `users/admin` is listed under `users`.
+pub const SEPARATORS: [char; 2] = ['/', ':'];
+/// Stands for the tag of the operations without tags.
+pub const UNTAGGED: &str = "(untagged)";
+
+/// Tag, or group of tags, of the tags pane.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TagNode {
+  /// Path of the node in the tree, unique among its nodes.
+  pub key: String,
+  pub label: String,
+  /// Name of the tag, `None` for groups that are not a tag themselves.
+  pub tag: Option<String>,
+  pub description: Option<String>,
+  /// The tag and those listed under it, whose operations the node lists.
+  pub tags: Vec<String>,
+  pub children: Vec<TagNode>,
+}
+
+impl TagNode {
+  /// Name the node is selected by, its tag or else its key.
+  pub fn name(&self) -> String {
+    self.tag.clone().unwrap_or_else(|| self.key.clone())
+  }
+
+  fn collect_tags(&mut self) {
+    let mut tags: Vec<String> = self.tag.iter().cloned().collect();
+    for child in self.children.iter_mut() {
+      child.collect_tags();
+      tags.extend(child.tags.iter().cloned());
+    }
+    self.tags = tags;
+  }
+}
+
+/// Whether the operation of the tags is listed under one of the given tags, [`UNTAGGED`] listing those without tags.
+pub fn is_tagged_with(operation_tags: &[String], tags: &[String]) -> bool {
+  match operation_tags.is_empty() {
+    true => tags.iter().any(|tag| tag == UNTAGGED),
+    false => operation_tags.iter().any(|tag| tags.contains(tag)),
+  }
+}
+
+/// Tags of the spec, in the groups of `x-tagGroups` when it has them, or else nested by the separators of their names,
+/// followed by [`UNTAGGED`] when some operations have no tags.
+pub fn tree(document: &Value) -> Vec<TagNode> {
+  let declared: Vec<(String, Option<String>)> = document
+    .get("tags")
+    .and_then(Value::as_array)
+    .into_iter()
+    .flatten()
+    .filter_map(|tag| {
+      let name = tag.get("name").and_then(Value::as_str)?;
+      Some((name.to_string(), tag.get("description").and_then(Value::as_str).map(String::from)))
+    })
+    .collect();
+  let description =
+    |name: &str| declared.iter().find(|(tag, _)| tag == name).and_then(|(_, description)| description.clone());
+  let leaf = |key: String, name: &str| {
+    TagNode {
+      key,
+      label: name.to_string(),
+      tag: Some(name.to_string()),
+      description: description(name),
+      ..TagNode::default()
+    }
+  };
+
+  let mut nodes = vec![];
+  if let Some(groups) = document.get("x-tagGroups").and_then(Value::as_array) {
+    let mut grouped = vec![];
+    for group in groups {
+      let Some(name) = group.get("name").and_then(Value::as_str) else {
+        continue;
+      };
+      let tags: Vec<&str> =
+        group.get("tags").and_then(Value::as_array).into_iter().flatten().filter_map(Value::as_str).collect();
+      grouped.extend(tags.iter().map(|tag| tag.to_string()));
+      nodes.push(TagNode {
+        key: name.to_string(),
+        label: name.to_string(),
+        children: tags.iter().map(|tag| leaf(format!("{name}/{tag}"), tag)).collect(),
+        ..TagNode::default()
+      });
+    }
+    // tags left out of the groups are listed after them
+    nodes.extend(declared.iter().filter(|(name, _)| !grouped.contains(name)).map(|(name, _)| leaf(name.clone(), name)));
+  } else {
+    for (name, _) in declared.iter() {
+      insert(&mut nodes, name, 0, leaf(name.clone(), name));
+    }
+  }
+  let untagged = document.get("paths").and_then(Value::as_object).into_iter().flatten().any(|(_, item)| {
+    METHODS.iter().filter_map(|method| item.get(*method)).any(|operation| {
+      match operation.get("tags").and_then(Value::as_array) {
+        Some(tags) => tags.is_empty(),
+        None => true,
+      }
+    })
+  });
+  if untagged {
+    nodes.push(TagNode {
+      key: UNTAGGED.to_string(),
+      label: UNTAGGED.to_string(),
+      tag: Some(UNTAGGED.to_string()),
+      ..TagNode::default()
+    });
+  }
+  for node in nodes.iter_mut() {
+    node.collect_tags();
+  }
+  nodes
+}
+
+/// Inserts the tag under the nodes of the segments of its name from `start`.
+fn insert(nodes: &mut Vec<TagNode>, name: &str, start: usize, tag: TagNode) {
+  let Some(end) = name[start..].find(SEPARATORS).map(|end| start + end) else {
+    let label = name[start..].to_string();
+    match nodes.iter_mut().find(|node| node.key == name) {
+      // a group made for the tags under this one becomes the tag
+      Some(node) => {
+        node.tag = tag.tag;
+        node.description = tag.description;
+      },
+      None => nodes.push(TagNode { label, ..tag }),
+    }
+    return;
+  };
+  let key = &name[..end];
+  let index = match nodes.iter().position(|node| node.key == key) {
+    Some(index) => index,
+    None => {
+      nodes.push(TagNode { key: key.to_string(), label: name[start..end].to_string(), ..TagNode::default() });
+      nodes.len() - 1
+    },
+  };
+  insert(&mut nodes[index].children, name, end + 1, tag);
+}
+
+/// Node of the tree selected by the name, a tag or the key of a group.
+pub fn find<'a>(nodes: &'a [TagNode], name: &str) -> Option<&'a TagNode> {
+  nodes.iter().find_map(|node| {
+    match node.tag.as_deref() == Some(name) || (node.tag.is_none() && node.key == name) {
+      true => Some(node),
+      false => find(&node.children, name),
+    }
+  })
+}
+
+/// Operations, given by their tags in order, listed under the nodes of their tags as the groups of a tree, followed by
+/// the tags the tree lacks, e.g. those the spec does not declare. Nodes without operations under them are left out.
+pub fn outline(nodes: &[TagNode], operation_tags: &[Vec<String>]) -> Vec<PathNode> {
+  let mut outline: Vec<PathNode> = nodes.iter().filter_map(|node| outline_node(node, operation_tags)).collect();
+  let mut undeclared: Vec<&String> = vec![];
+  for tag in operation_tags.iter().flatten() {
+    if find(nodes, tag).is_none() && !undeclared.contains(&tag) {
+      undeclared.push(tag);
+    }
+  }
+  outline.extend(undeclared.into_iter().filter_map(|tag| {
+    let node = TagNode { key: tag.clone(), label: tag.clone(), tag: Some(tag.clone()), ..TagNode::default() };
+    outline_node(&node, operation_tags)
+  }));
+  outline
+}
+
+fn outline_node(node: &TagNode, operation_tags: &[Vec<String>]) -> Option<PathNode> {
+  let operations: Vec<usize> = match &node.tag {
+    Some(tag) => {
+      (0..operation_tags.len())
+        .filter(|index| is_tagged_with(&operation_tags[*index], std::slice::from_ref(tag)))
+        .collect()
+    },
+    None => vec![],
+  };
+  let children: Vec<PathNode> = node.children.iter().filter_map(|child| outline_node(child, operation_tags)).collect();
+  // keys apart from those of the path groups, both kept in the same set of expanded groups
+  (!operations.is_empty() || !children.is_empty())
+    .then(|| PathNode { key: format!("tag:{}", node.key), label: node.label.clone(), operations, children })
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+  use serde_json::json;
+
+  use super::*;
+
+  fn labels(nodes: &[TagNode]) -> Vec<String> {
+    nodes
+      .iter()
+      .map(|node| {
+        match node.children.is_empty() {
+          true => node.label.clone(),
+          false => format!("{} ({})", node.label, labels(&node.children).join(", ")),
+        }
+      })
+      .collect()
+  }
+
+  #[test]
+  fn test_tree() {
+    let document = json!({
+      "tags": [
+        { "name": "users/admin", "description": "Administration" },
+        { "name": "users", "description": "Users" },
+        { "name": "users/admin/audit" },
+        { "name": "pets" }
+      ],
+      "paths": { "/pets": { "get": { "tags": ["pets"] }, "post": {} } }
+    });
+    let nodes = tree(&document);
+    assert_eq!(labels(&nodes), vec!["users (admin (audit))", "pets", UNTAGGED]);
+    assert!(is_tagged_with(&[], &nodes[2].tags));
+    assert!(!is_tagged_with(&[String::from("pets")], &nodes[2].tags));
+    assert_eq!(nodes[0].tag.as_deref(), Some("users"));
+    assert_eq!(nodes[0].description.as_deref(), Some("Users"));
+    assert_eq!(nodes[0].tags, vec!["users", "users/admin", "users/admin/audit"]);
+    assert_eq!(find(&nodes, "users/admin").and_then(|node| node.description.as_deref()), Some("Administration"));
+
+    let document = json!({
+      "tags": [{ "name": "pets", "description": "Pets" }, { "name": "store" }, { "name": "misc" }],
+      "x-tagGroups": [{ "name": "Shop", "tags": ["pets", "store"] }]
+    });
+    let nodes = tree(&document);
+    assert_eq!(labels(&nodes), vec!["Shop (pets, store)", "misc"]);
+    assert_eq!(nodes[0].children[0].description.as_deref(), Some("Pets"));
+    assert_eq!(
+      find(&nodes, "Shop").map(|node| node.tags.clone()),
+      Some(vec![String::from("pets"), String::from("store")])
+    );
+  }
+
+  #[test]
+  fn test_outline() {
+    let document = json!({
+      "tags": [{ "name": "users" }, { "name": "users/admin" }, { "name": "pets" }, { "name": "store" }],
+      "paths": { "/pets": { "get": { "tags": ["pets"] }, "post": {} } }
+    });
+    let operation_tags: Vec<Vec<String>> = [vec!["pets"], vec![], vec!["users/admin", "pets"], vec!["legacy"]]
+      .into_iter()
+      .map(|tags| tags.into_iter().map(String::from).collect())
+      .collect();
+    let outline = outline(&tree(&document), &operation_tags);
+    let groups: Vec<(&str, &str, Vec<usize>)> =
+      outline.iter().map(|node| (node.key.as_str(), node.label.as_str(), node.operations.clone())).collect();
+    assert_eq!(groups, vec![
+      ("tag:users", "users", vec![]),
+      ("tag:pets", "pets", vec![0, 2]),
+      ("tag:(untagged)", UNTAGGED, vec![1]),
+      ("tag:legacy", "legacy", vec![3]),
+    ]);
+    assert_eq!(outline[0].children[0].label, "admin");
+    assert_eq!(outline[0].children[0].operations, vec![2]);
+  }
+}
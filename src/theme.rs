@@ -0,0 +1,316 @@
+use std::{
+  collections::BTreeMap,
+  path::Path,
+  str::FromStr,
+  sync::{RwLock, RwLockReadGuard},
+};
+
+use color_eyre::eyre::{eyre, Result, WrapErr};
+use lazy_static::lazy_static;
+use ratatui::{
+  buffer::Buffer,
+  style::{Color, Modifier, Style, Stylize},
+};
+use serde::Deserialize;
+use syntect::highlighting::ThemeSet;
+
+lazy_static! {
+  static ref THEME: RwLock<Theme> = RwLock::new(Theme::dark());
+}
+
+/// Theme in use, the dark one until another is set.
+pub fn current() -> RwLockReadGuard<'static, Theme> {
+  THEME.read().unwrap()
+}
+
+pub fn set(theme: Theme) {
+  *THEME.write().unwrap() = theme;
+}
+
+/// Colors and styles of the interface.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+  /// Syntect theme highlighting the schemas, one of its defaults, e.g. `Solarized (dark)`.
+  pub syntax: String,
+  pub border: Style,
+  pub focused_border: Style,
+  /// Selected item of lists and tables.
+  pub selection: Style,
+  /// Active tab of panes and popups.
+  pub active_tab: Style,
+  /// Colors of the methods in upper case, `OTHER` for the methods not listed.
+  pub methods: BTreeMap<String, Color>,
+  /// Colors of the status classes, `2XX` to `5XX`.
+  pub statuses: BTreeMap<String, Color>,
+  /// Colors are dropped from the whole screen, methods and statuses told apart by modifiers instead.
+  pub monochrome: bool,
+}
+
+/// Theme file, every key optional and applied over the built-in theme it is `based_on`, the dark one by default.
+#[derive(Debug, Default, Deserialize)]
+struct ThemeFile {
+  based_on: Option<String>,
+  syntax: Option<String>,
+  border: Option<String>,
+  focused_border: Option<String>,
+  selection: Option<String>,
+  active_tab: Option<String>,
+  #[serde(default)]
+  methods: BTreeMap<String, String>,
+  #[serde(default)]
+  statuses: BTreeMap<String, String>,
+}
+
+impl Theme {
+  pub const BUILT_IN: [&'static str; 3] = ["dark", "light", "mono"];
+
+  pub fn dark() -> Self {
+    Self {
+      syntax: String::from("Solarized (dark)"),
+      border: Style::default(),
+      focused_border: Style::default().fg(Color::LightGreen),
+      selection: Style::default().add_modifier(Modifier::BOLD),
+      active_tab: Style::default().add_modifier(Modifier::BOLD | Modifier::UNDERLINED).not_dim(),
+      methods: colors(&[
+        ("GET", Color::LightCyan),
+        ("POST", Color::LightBlue),
+        ("PUT", Color::LightYellow),
+        ("DELETE", Color::LightRed),
+        ("OTHER", Color::Gray),
+      ]),
+      statuses: colors(&[
+        ("2XX", Color::LightCyan),
+        ("3XX", Color::LightBlue),
+        ("4XX", Color::LightYellow),
+        ("5XX", Color::LightRed),
+      ]),
+      monochrome: false,
+    }
+  }
+
+  /// Darker colors, readable on light terminals.
+  pub fn light() -> Self {
+    Self {
+      syntax: String::from("Solarized (light)"),
+      border: Style::default().fg(Color::DarkGray),
+      focused_border: Style::default().fg(Color::Green),
+      selection: Style::default().add_modifier(Modifier::BOLD | Modifier::REVERSED),
+      active_tab: Style::default().fg(Color::Blue).add_modifier(Modifier::BOLD | Modifier::UNDERLINED).not_dim(),
+      methods: colors(&[
+        ("GET", Color::Blue),
+        ("POST", Color::Green),
+        ("PUT", Color::Magenta),
+        ("DELETE", Color::Red),
+        ("OTHER", Color::DarkGray),
+      ]),
+      statuses: colors(&[("2XX", Color::Green), ("3XX", Color::Blue), ("4XX", Color::Magenta), ("5XX", Color::Red)]),
+      monochrome: false,
+    }
+  }
+
+  /// No colors at all, for monochrome terminals and color blind users, cues left to borders, modifiers and text.
+  pub fn mono() -> Self {
+    Self {
+      syntax: String::from("Solarized (dark)"),
+      border: Style::default(),
+      focused_border: Style::default().add_modifier(Modifier::BOLD),
+      selection: Style::default().add_modifier(Modifier::BOLD | Modifier::REVERSED),
+      active_tab: Style::default().add_modifier(Modifier::BOLD | Modifier::UNDERLINED | Modifier::REVERSED).not_dim(),
+      methods: BTreeMap::new(),
+      statuses: BTreeMap::new(),
+      monochrome: true,
+    }
+  }
+
+  pub fn built_in(name: &str) -> Option<Self> {
+    match name {
+      "dark" | "default" => Some(Self::dark()),
+      "light" => Some(Self::light()),
+      "mono" => Some(Self::mono()),
+      _ => None,
+    }
+  }
+
+  /// Theme of the config, a built-in name or a TOML file relative to the config directory, the dark one if none.
+  pub fn load(name: Option<&str>, config_dir: &Path) -> Result<Self> {
+    let Some(name) = name else {
+      return Ok(Self::dark());
+    };
+    if let Some(theme) = Self::built_in(name) {
+      return Ok(theme);
+    }
+    let path = config_dir.join(name);
+    let file: ThemeFile = config::Config::builder()
+      .add_source(config::File::from(path.as_path()).format(config::FileFormat::Toml))
+      .build()
+      .and_then(config::Config::try_deserialize)
+      .wrap_err_with(|| format!("Could not load the theme `{}`", path.display()))?;
+    Self::from_file(file)
+  }
+
+  fn from_file(file: ThemeFile) -> Result<Self> {
+    let based_on = file.based_on.as_deref().unwrap_or("dark");
+    let mut theme = Self::built_in(based_on)
+      .ok_or_else(|| eyre!("Unknown theme `{based_on}`, expected one of {}", Self::BUILT_IN.join(", ")))?;
+    if let Some(syntax) = file.syntax {
+      if !ThemeSet::load_defaults().themes.contains_key(&syntax) {
+        return Err(eyre!("Unknown syntax theme `{syntax}`"));
+      }
+      theme.syntax = syntax;
+    }
+    for (style, text) in [
+      (&mut theme.border, file.border),
+      (&mut theme.focused_border, file.focused_border),
+      (&mut theme.selection, file.selection),
+      (&mut theme.active_tab, file.active_tab),
+    ] {
+      if let Some(text) = text {
+        *style = parse_style(&text)?;
+      }
+    }
+    for (colors, overrides) in [(&mut theme.methods, file.methods), (&mut theme.statuses, file.statuses)] {
+      for (key, text) in overrides {
+        let color = Color::from_str(&text).map_err(|_| eyre!("Unknown color `{text}`"))?;
+        colors.insert(key.to_uppercase(), color);
+      }
+    }
+    Ok(theme)
+  }
+
+  /// Style of the method, its color, or when monochrome bold for the methods changing data, underlined too for
+  /// `DELETE`.
+  pub fn method(&self, method: &str) -> Style {
+    let method = method.to_uppercase();
+    if self.monochrome {
+      return match method.as_str() {
+        "GET" | "HEAD" | "OPTIONS" | "TRACE" => Style::default(),
+        "DELETE" => Style::default().bold().underlined(),
+        _ => Style::default().bold(),
+      };
+    }
+    let color = self.methods.get(&method).or_else(|| self.methods.get("OTHER"));
+    Style::default().fg(color.copied().unwrap_or_default())
+  }
+
+  /// Style of the status code or range, e.g. `404` or `4XX`, `default` responses being successes. Its color, or when
+  /// monochrome italic for redirects, bold for client errors and underlined too for server errors.
+  pub fn status(&self, status: &str) -> Style {
+    let class = match status {
+      "default" => String::from("2XX"),
+      status => format!("{}XX", status.chars().next().unwrap_or_default()),
+    };
+    if self.monochrome {
+      return match class.as_str() {
+        "3XX" => Style::default().italic(),
+        "4XX" => Style::default().bold(),
+        "5XX" => Style::default().bold().underlined(),
+        _ => Style::default(),
+      };
+    }
+    Style::default().fg(self.statuses.get(&class).copied().unwrap_or_default())
+  }
+
+  pub fn status_code(&self, status: u16) -> Style {
+    self.status(&status.to_string())
+  }
+}
+
+/// Drops the colors of every cell of the drawn frame, those of the syntax highlighting and of the other cues drawn
+/// with fixed colors among them, leaving their modifiers.
+pub fn strip_colors(buffer: &mut Buffer) {
+  for cell in buffer.content.iter_mut() {
+    cell.set_fg(Color::Reset).set_bg(Color::Reset);
+  }
+}
+
+fn colors(colors: &[(&str, Color)]) -> BTreeMap<String, Color> {
+  colors.iter().map(|(key, color)| (key.to_string(), *color)).collect()
+}
+
+/// Style of modifiers and colors, e.g. `bold light green on black` or `underlined #ff8800`.
+pub fn parse_style(text: &str) -> Result<Style> {
+  let mut style = Style::default();
+  let mut colors = [String::default(), String::default()];
+  let mut background = false;
+  for word in text.split_whitespace() {
+    let modifier = match word.to_lowercase().as_str() {
+      "bold" => Modifier::BOLD,
+      "dim" => Modifier::DIM,
+      "italic" => Modifier::ITALIC,
+      "underlined" => Modifier::UNDERLINED,
+      "reversed" => Modifier::REVERSED,
+      "crossed-out" => Modifier::CROSSED_OUT,
+      "on" => {
+        background = true;
+        continue;
+      },
+      _ => {
+        colors[usize::from(background)].push_str(word);
+        continue;
+      },
+    };
+    style = style.add_modifier(modifier);
+  }
+  let [foreground, background] = colors.map(|text| {
+    (!text.is_empty()).then(|| Color::from_str(&text).map_err(|_| eyre!("Unknown color `{text}`"))).transpose()
+  });
+  if let Some(color) = foreground? {
+    style = style.fg(color);
+  }
+  if let Some(color) = background? {
+    style = style.bg(color);
+  }
+  Ok(style)
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_theme() {
+    assert_eq!(
+      parse_style("bold light green on black").unwrap(),
+      Style::default().fg(Color::LightGreen).bg(Color::Black).bold()
+    );
+    assert_eq!(parse_style("underlined #ff8800").unwrap(), Style::default().fg(Color::Rgb(255, 136, 0)).underlined());
+    assert!(parse_style("bold shiny").is_err());
+
+    let theme = Theme::dark();
+    assert_eq!(theme.method("patch"), Style::default().fg(Color::Gray));
+    assert_eq!(theme.status("default"), Style::default().fg(Color::LightCyan));
+    assert_eq!(theme.status_code(404), Style::default().fg(Color::LightYellow));
+
+    let theme = Theme::mono();
+    assert_eq!(theme.method("get"), Style::default());
+    assert_eq!(theme.method("DELETE"), Style::default().bold().underlined());
+    assert_eq!(theme.status_code(302), Style::default().italic());
+    assert_eq!(theme.status_code(503), Style::default().bold().underlined());
+    let mut buffer = Buffer::empty(ratatui::layout::Rect::new(0, 0, 4, 1));
+    buffer.set_string(0, 0, "GET", Style::default().fg(Color::LightCyan).on_black().bold());
+    strip_colors(&mut buffer);
+    let cell = buffer.get(0, 0);
+    assert_eq!((cell.fg, cell.bg, cell.modifier), (Color::Reset, Color::Reset, Modifier::BOLD));
+
+    let directory = std::env::temp_dir().join(format!("openapi-tui-theme-{}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+    std::fs::write(
+      directory.join("mine.toml"),
+      "based_on = \"light\"\nfocused_border = \"bold yellow\"\n[methods]\npatch = \"cyan\"\n",
+    )
+    .unwrap();
+    std::fs::write(directory.join("broken.toml"), "based_on = \"neon\"\n").unwrap();
+    let theme = Theme::load(Some("mine.toml"), &directory).unwrap();
+    let broken = Theme::load(Some("broken.toml"), &directory);
+    std::fs::remove_dir_all(&directory).unwrap();
+    assert_eq!(theme.focused_border, Style::default().fg(Color::Yellow).bold());
+    assert_eq!(theme.method("PATCH"), Style::default().fg(Color::Cyan));
+    assert_eq!(theme.method("GET"), Style::default().fg(Color::Blue));
+    assert_eq!(theme.syntax, "Solarized (light)");
+    assert!(broken.is_err());
+    assert_eq!(Theme::load(Some("light"), &directory).unwrap(), Theme::light());
+    assert!(Theme::load(Some("mono"), &directory).unwrap().monochrome);
+  }
+}
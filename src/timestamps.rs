@@ -0,0 +1,89 @@
+use std::ops::Range;
+
+use chrono::{DateTime, FixedOffset, TimeZone, Utc};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+  /// RFC 3339 timestamps with an offset, e.g. `2024-01-05T10:00:00Z` or `2024-01-05 10:00:00.250+02:00`.
+  static ref TIMESTAMP: Regex =
+    Regex::new(r"\d{4}-\d{2}-\d{2}[Tt ]\d{2}:\d{2}:\d{2}(\.\d+)?([Zz]|[+-]\d{2}:\d{2})").unwrap();
+}
+
+/// Epoch seconds from 2001 to 2286, along with epoch milliseconds over the same years.
+const SECONDS: Range<i64> = 1_000_000_000..10_000_000_000;
+const MILLISECONDS: Range<i64> = 1_000_000_000_000..10_000_000_000_000;
+
+/// Timestamps of the text along with where they are.
+pub fn find(text: &str) -> Vec<(Range<usize>, DateTime<FixedOffset>)> {
+  TIMESTAMP
+    .find_iter(text)
+    .filter_map(|found| Some((found.range(), DateTime::parse_from_rfc3339(found.as_str()).ok()?)))
+    .collect()
+}
+
+/// The time in the zone, e.g. `2024-01-05 11:00:00 +01:00`.
+pub fn local<Tz: TimeZone>(time: &DateTime<FixedOffset>, zone: &Tz) -> String
+where
+  Tz::Offset: std::fmt::Display,
+{
+  time.with_timezone(zone).format("%Y-%m-%d %H:%M:%S %:z").to_string()
+}
+
+/// Time of an epoch number, read as milliseconds or seconds by its magnitude, `None` when it is neither.
+pub fn epoch(number: i64) -> Option<DateTime<Utc>> {
+  match number {
+    _ if SECONDS.contains(&number) => DateTime::from_timestamp(number, 0),
+    _ if MILLISECONDS.contains(&number) => DateTime::from_timestamp_millis(number),
+    _ => None,
+  }
+}
+
+/// Number a line of a pretty printed JSON body holds as its value, e.g. `1704448800` for `"at": 1704448800,`.
+fn number(line: &str) -> Option<i64> {
+  let value = line.rsplit_once(':').map_or(line, |(_, value)| value);
+  value.trim().trim_end_matches(',').parse().ok()
+}
+
+/// Conversions of the first timestamp of the line, to the zone and to epoch milliseconds, or of an epoch number it
+/// holds to UTC and the zone.
+pub fn convert<Tz: TimeZone>(line: &str, zone: &Tz) -> Option<String>
+where
+  Tz::Offset: std::fmt::Display,
+{
+  if let Some((_, time)) = find(line).into_iter().next() {
+    return Some(format!("{} · {} ms since epoch", local(&time, zone), time.timestamp_millis()));
+  }
+  let time = epoch(number(line)?)?;
+  Some(format!("{} · {}", time.to_rfc3339(), local(&time.fixed_offset(), zone)))
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_convert() {
+    let zone = FixedOffset::east_opt(3600).unwrap();
+    let line = r#"  "between": "2024-01-05T10:00:00Z and 2024-01-06 08:30:00.5-05:00","#;
+    let found = find(line);
+    assert_eq!(found.iter().map(|(range, _)| &line[range.clone()]).collect::<Vec<_>>(), vec![
+      "2024-01-05T10:00:00Z",
+      "2024-01-06 08:30:00.5-05:00"
+    ]);
+    assert_eq!(local(&found[1].1, &zone), "2024-01-06 14:30:00 +01:00");
+    assert_eq!(convert(line, &zone).as_deref(), Some("2024-01-05 11:00:00 +01:00 · 1704448800000 ms since epoch"));
+    assert_eq!(
+      convert(r#"  "at": 1704448800000,"#, &zone).as_deref(),
+      Some("2024-01-05T10:00:00+00:00 · 2024-01-05 11:00:00 +01:00")
+    );
+    assert_eq!(
+      convert("  1704448800", &zone).as_deref(),
+      Some("2024-01-05T10:00:00+00:00 · 2024-01-05 11:00:00 +01:00")
+    );
+    assert_eq!(convert(r#"  "count": 42,"#, &zone), None);
+    assert_eq!(find("2024-01-05T10:00:00"), vec![]);
+  }
+}
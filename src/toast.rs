@@ -0,0 +1,85 @@
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ToastLevel {
+  Info,
+  Success,
+  Error,
+}
+
+/// Transient notification, shown for a while in a corner of the page.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Toast {
+  pub level: ToastLevel,
+  pub message: String,
+  shown_at: Instant,
+}
+
+/// Toasts being shown, oldest first.
+#[derive(Debug, Default)]
+pub struct Toasts {
+  toasts: Vec<Toast>,
+}
+
+impl Toasts {
+  /// How long toasts are shown, errors twice as long.
+  const DURATION: Duration = Duration::from_secs(3);
+  /// Toasts shown at once, the oldest ones dropped first.
+  const MAX_SHOWN: usize = 3;
+
+  pub fn push(&mut self, level: ToastLevel, message: impl Into<String>) {
+    self.push_at(level, message, Instant::now());
+  }
+
+  fn push_at(&mut self, level: ToastLevel, message: impl Into<String>, now: Instant) {
+    self.toasts.push(Toast { level, message: message.into(), shown_at: now });
+    if self.toasts.len() > Self::MAX_SHOWN {
+      self.toasts.remove(0);
+    }
+  }
+
+  /// Removes the toasts shown long enough, `true` when some were.
+  pub fn expire(&mut self, now: Instant) -> bool {
+    let shown = self.toasts.len();
+    self.toasts.retain(|toast| {
+      let duration = match toast.level {
+        ToastLevel::Error => Self::DURATION * 2,
+        _ => Self::DURATION,
+      };
+      now.duration_since(toast.shown_at) < duration
+    });
+    self.toasts.len() != shown
+  }
+
+  pub fn shown(&self) -> &[Toast] {
+    &self.toasts
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_toasts() {
+    let start = Instant::now();
+    let mut toasts = Toasts::default();
+    toasts.push_at(ToastLevel::Error, "request failed: timeout", start);
+    toasts.push_at(ToastLevel::Success, "copied to clipboard", start + Duration::from_secs(1));
+    let messages = |toasts: &Toasts| toasts.shown().iter().map(|toast| toast.message.clone()).collect::<Vec<_>>();
+    assert!(!toasts.expire(start + Duration::from_secs(2)));
+    assert!(toasts.expire(start + Duration::from_secs(4)));
+    assert_eq!(messages(&toasts), vec!["request failed: timeout"]);
+    assert!(toasts.expire(start + Duration::from_secs(6)));
+    assert!(toasts.shown().is_empty());
+
+    for index in 0..5 {
+      toasts.push_at(ToastLevel::Info, format!("toast {index}"), start);
+    }
+    assert_eq!(messages(&toasts), vec!["toast 2", "toast 3", "toast 4"]);
+  }
+}
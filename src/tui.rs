@@ -47,6 +47,16 @@ pub enum EventResponse<T> {
   Continue(T),
   Stop(T),
 }
+
+/// Pasted text with its line breaks as `\n`, which terminals may send as `\r`, or joined on one line for single-line
+/// inputs.
+pub fn pasted(text: &str, multiline: bool) -> String {
+  let text = text.replace("\r\n", "\n").replace('\r', "\n");
+  match multiline {
+    true => text,
+    false => text.lines().collect(),
+  }
+}
 pub struct Tui {
   pub terminal: ratatui::Terminal<Backend<IO>>,
   pub task: JoinHandle<()>,
@@ -0,0 +1,67 @@
+/// Changes kept to be undone, the oldest forgotten past it.
+const LIMIT: usize = 100;
+
+/// Values a changing value had, to undo its changes and redo the undone ones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UndoHistory<T> {
+  undone: Vec<T>,
+  redone: Vec<T>,
+}
+
+impl<T> Default for UndoHistory<T> {
+  fn default() -> Self {
+    Self { undone: Vec::default(), redone: Vec::default() }
+  }
+}
+
+impl<T> UndoHistory<T> {
+  /// Records the value before a change, the undone changes can no longer be redone.
+  pub fn record(&mut self, before: T) {
+    self.undone.push(before);
+    if self.undone.len() > LIMIT {
+      self.undone.remove(0);
+    }
+    self.redone.clear();
+  }
+
+  /// The value before the last change, the current one being kept to redo it.
+  pub fn undo(&mut self, current: T) -> Option<T> {
+    let before = self.undone.pop()?;
+    self.redone.push(current);
+    Some(before)
+  }
+
+  /// The value the last undone change left, the current one being kept to undo it again.
+  pub fn redo(&mut self, current: T) -> Option<T> {
+    let after = self.redone.pop()?;
+    self.undone.push(current);
+    Some(after)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_undo_history() {
+    let mut history = UndoHistory::default();
+    history.record("a");
+    history.record("ab");
+    assert_eq!(history.undo("abc"), Some("ab"));
+    assert_eq!(history.undo("ab"), Some("a"));
+    assert_eq!(history.undo("a"), None);
+    assert_eq!(history.redo("a"), Some("ab"));
+    history.record("ab");
+    assert_eq!(history.redo("abd"), None);
+    assert_eq!(history.undo("abd"), Some("ab"));
+
+    let mut history = UndoHistory::default();
+    for value in 0..=LIMIT {
+      history.record(value);
+    }
+    assert_eq!(history.undone.first(), Some(&1));
+  }
+}
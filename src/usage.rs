@@ -0,0 +1,166 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde_json::Value;
+
+use crate::{
+  mock::{self, METHODS},
+  workspace::OperationKey,
+};
+
+const SCHEMA_REF_PREFIX: &str = "#/components/schemas/";
+/// Nesting of references to parameters, request bodies or responses that are followed.
+const MAX_DEPTH: usize = 8;
+
+/// Part of an operation that uses a schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Usage {
+  pub operation: OperationKey,
+  /// e.g. `query parameter limit`, `request body` or `response 200`.
+  pub location: String,
+  /// Schema through which the part uses it, `None` when it references it directly.
+  pub via: Option<String>,
+}
+
+/// Operations whose parameters, request body or responses reference the component schema, directly or through other
+/// schemas.
+pub fn usages(document: &Value, schema: &str) -> Vec<Usage> {
+  let dependents = dependents(document, schema);
+  let mut usages = vec![];
+  let paths = document.get("paths").and_then(Value::as_object).into_iter().flatten();
+  for (path, item) in paths {
+    for method in METHODS {
+      let Some(operation) = item.get(method) else {
+        continue;
+      };
+      let key = OperationKey::new(method, path);
+      for (location, part) in parts(item, operation, document) {
+        let referenced = references(part, document, 0);
+        let via = match referenced.contains(schema) {
+          true => None,
+          false => {
+            let Some(via) = referenced.iter().find(|name| dependents.contains(*name)) else {
+              continue;
+            };
+            Some(via.clone())
+          },
+        };
+        usages.push(Usage { operation: key.clone(), location, via });
+      }
+    }
+  }
+  usages
+}
+
+/// Parameters, request body and responses of the operation, by location.
+fn parts<'a>(item: &'a Value, operation: &'a Value, document: &'a Value) -> Vec<(String, &'a Value)> {
+  let mut parts: Vec<(String, &Value)> = [item, operation]
+    .into_iter()
+    .filter_map(|value| value.get("parameters").and_then(Value::as_array))
+    .flatten()
+    .map(|parameter| {
+      let resolved = mock::resolve(parameter, document);
+      let location = resolved.get("in").and_then(Value::as_str).unwrap_or("unknown");
+      let name = resolved.get("name").and_then(Value::as_str).unwrap_or_default();
+      (format!("{location} parameter {name}"), parameter)
+    })
+    .collect();
+  parts.extend(operation.get("requestBody").map(|body| (String::from("request body"), body)));
+  let responses = operation.get("responses").and_then(Value::as_object).into_iter().flatten();
+  parts.extend(responses.map(|(status, response)| (format!("response {status}"), response)));
+  parts
+}
+
+/// Names of the schemas referenced in the value, following references to other components.
+fn references(value: &Value, document: &Value, depth: usize) -> BTreeSet<String> {
+  let mut names = BTreeSet::new();
+  match value {
+    Value::Object(object) => {
+      for (key, nested) in object {
+        match (key.as_str(), nested.as_str()) {
+          ("$ref", Some(reference)) => {
+            match reference.strip_prefix(SCHEMA_REF_PREFIX) {
+              Some(name) => {
+                names.insert(name.to_string());
+              },
+              // one reference at a time, the names of the schemas along the way are collected too
+              None if depth < MAX_DEPTH => {
+                names.extend(
+                  mock::target(value, document)
+                    .map(|target| references(target, document, depth + 1))
+                    .unwrap_or_default(),
+                )
+              },
+              None => {},
+            }
+          },
+          _ => names.extend(references(nested, document, depth)),
+        }
+      }
+    },
+    Value::Array(items) => items.iter().for_each(|item| names.extend(references(item, document, depth))),
+    _ => {},
+  }
+  names
+}
+
+/// Component schemas that reference the schema, directly or through each other.
+fn dependents(document: &Value, schema: &str) -> BTreeSet<String> {
+  let schemas = document.pointer("/components/schemas").and_then(Value::as_object).into_iter().flatten();
+  let graph: BTreeMap<&String, BTreeSet<String>> =
+    schemas.map(|(name, definition)| (name, references(definition, document, 0))).collect();
+  let mut dependents = BTreeSet::new();
+  let mut pending = vec![schema.to_string()];
+  while let Some(name) = pending.pop() {
+    for (dependent, referenced) in graph.iter() {
+      if referenced.contains(&name) && dependents.insert(dependent.to_string()) {
+        pending.push(dependent.to_string());
+      }
+    }
+  }
+  dependents.remove(schema);
+  dependents
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+  use serde_json::json;
+
+  use super::*;
+
+  #[test]
+  fn test_usages() {
+    let document = json!({
+      "paths": {
+        "/owners": {
+          "get": { "responses": { "200": { "$ref": "#/components/responses/Owners" } } }
+        },
+        "/pets": {
+          "parameters": [{ "in": "query", "name": "like", "schema": { "$ref": "#/components/schemas/Pet" } }],
+          "post": {
+            "requestBody": { "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Pet" } } } },
+            "responses": { "201": {}, "400": { "content": { "application/json": { "schema": { "type": "string" } } } } }
+          }
+        }
+      },
+      "components": {
+        "schemas": {
+          "Pet": { "type": "object" },
+          "Owner": { "properties": { "pets": { "items": { "$ref": "#/components/schemas/Pet" } } } }
+        },
+        "responses": {
+          "Owners": { "content": { "application/json": { "schema": { "items": { "$ref": "#/components/schemas/Owner" } } } } }
+        }
+      }
+    });
+    let usages: Vec<(String, String, Option<String>)> = usages(&document, "Pet")
+      .into_iter()
+      .map(|usage| (usage.operation.to_string(), usage.location, usage.via))
+      .collect();
+    assert_eq!(usages, vec![
+      (String::from("GET /owners"), String::from("response 200"), Some(String::from("Owner"))),
+      (String::from("POST /pets"), String::from("query parameter like"), None),
+      (String::from("POST /pets"), String::from("request body"), None),
+    ]);
+  }
+}
@@ -1,6 +1,9 @@
-use std::path::PathBuf;
+use std::{
+  path::PathBuf,
+  process::{Command, Stdio},
+};
 
-use color_eyre::eyre::Result;
+use color_eyre::eyre::{eyre, Result};
 use directories::ProjectDirs;
 use lazy_static::lazy_static;
 use tracing::error;
@@ -33,11 +36,7 @@ pub fn initialize_panic_handler() -> Result<()> {
     .into_hooks();
   eyre_hook.install()?;
   std::panic::set_hook(Box::new(move |panic_info| {
-    if let Ok(mut t) = crate::tui::Tui::new() {
-      if let Err(r) = t.exit() {
-        error!("Unable to exit Terminal: {:?}", r);
-      }
-    }
+    restore_terminal();
 
     #[cfg(not(debug_assertions))]
     {
@@ -54,8 +53,9 @@ pub fn initialize_panic_handler() -> Result<()> {
       print_msg(file_path, &meta).expect("human-panic: printing error message to console failed");
       eprintln!("{}", panic_hook.panic_report(panic_info)); // prints color-eyre stack trace to stderr
     }
-    let msg = format!("{}", panic_hook.panic_report(panic_info));
-    log::error!("Error: {}", strip_ansi_escapes::strip_str(msg));
+    let msg = strip_ansi_escapes::strip_str(format!("{}", panic_hook.panic_report(panic_info)));
+    log::error!("Error: {}", msg);
+    write_crash_report(&msg);
 
     #[cfg(debug_assertions)]
     {
@@ -72,6 +72,23 @@ pub fn initialize_panic_handler() -> Result<()> {
   Ok(())
 }
 
+/// Leaves the raw mode, alternate screen, mouse capture and bracketed paste the app may have left the terminal in.
+pub fn restore_terminal() {
+  if let Ok(t) = crate::tui::Tui::new() {
+    if let Err(r) = t.mouse(true).paste(true).exit() {
+      error!("Unable to exit Terminal: {:?}", r);
+    }
+  }
+}
+
+/// Writes the crash report of the error and tells where it is.
+pub fn write_crash_report(error: &str) {
+  match crate::crash::write(error) {
+    Ok(path) => eprintln!("A crash report was written to {}", path.display()),
+    Err(report_error) => error!("Unable to write the crash report: {:?}", report_error),
+  }
+}
+
 pub fn get_data_dir() -> PathBuf {
   let directory = if let Some(s) = DATA_FOLDER.clone() {
     s
@@ -94,24 +111,47 @@ pub fn get_config_dir() -> PathBuf {
   directory
 }
 
-pub fn initialize_logging() -> Result<()> {
-  let directory = get_data_dir();
-  std::fs::create_dir_all(directory.clone())?;
-  let log_path = directory.join(LOG_FILE.clone());
-  let log_file = std::fs::File::create(log_path)?;
-  std::env::set_var(
-    "RUST_LOG",
-    std::env::var("RUST_LOG")
-      .or_else(|_| std::env::var(LOG_ENV.clone()))
-      .unwrap_or_else(|_| format!("{}=info", env!("CARGO_CRATE_NAME"))),
-  );
-  let file_subscriber = tracing_subscriber::fmt::layer()
-    .with_file(true)
-    .with_line_number(true)
-    .with_writer(log_file)
-    .with_target(false)
-    .with_ansi(false)
-    .with_filter(tracing_subscriber::filter::EnvFilter::from_default_env());
+/// Logs to the log file of the data directory, or as JSON lines to the given file. A `verbosity` of 1 logs debug
+/// events and 2 trace events, the raw requests and responses among them, otherwise the level comes from the
+/// environment.
+pub fn initialize_logging(log_file: Option<&str>, verbosity: u8) -> Result<()> {
+  let level = match verbosity {
+    0 => None,
+    1 => Some("debug"),
+    _ => Some("trace"),
+  };
+  std::env::set_var("RUST_LOG", match level {
+    Some(level) => format!("{}={level}", env!("CARGO_CRATE_NAME")),
+    None => {
+      std::env::var("RUST_LOG")
+        .or_else(|_| std::env::var(LOG_ENV.clone()))
+        .unwrap_or_else(|_| format!("{}=info", env!("CARGO_CRATE_NAME")))
+    },
+  });
+  let file_subscriber = match log_file {
+    Some(path) => {
+      tracing_subscriber::fmt::layer()
+        .json()
+        .with_file(true)
+        .with_line_number(true)
+        .with_writer(std::fs::File::create(path)?)
+        .with_filter(tracing_subscriber::filter::EnvFilter::from_default_env())
+        .boxed()
+    },
+    None => {
+      let directory = get_data_dir();
+      std::fs::create_dir_all(directory.clone())?;
+      let log_path = directory.join(LOG_FILE.clone());
+      tracing_subscriber::fmt::layer()
+        .with_file(true)
+        .with_line_number(true)
+        .with_writer(std::fs::File::create(log_path)?)
+        .with_target(false)
+        .with_ansi(false)
+        .with_filter(tracing_subscriber::filter::EnvFilter::from_default_env())
+        .boxed()
+    },
+  };
   tracing_subscriber::registry().with(file_subscriber).with(ErrorLayer::default()).init();
   Ok(())
 }
@@ -142,6 +182,24 @@ macro_rules! trace_dbg {
     };
 }
 
+/// Opens the url in the default browser of the system, only web and mail links are opened.
+pub fn open_in_browser(url: &str) -> Result<()> {
+  if !["http://", "https://", "mailto:"].iter().any(|scheme| url.starts_with(scheme)) {
+    return Err(eyre!("Only http, https and mailto links can be opened, not `{url}`"));
+  }
+  let mut command = match std::env::consts::OS {
+    "macos" => Command::new("open"),
+    "windows" => {
+      let mut command = Command::new("cmd");
+      command.args(["/C", "start", ""]);
+      command
+    },
+    _ => Command::new("xdg-open"),
+  };
+  command.arg(url).stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null()).spawn()?;
+  Ok(())
+}
+
 pub fn version() -> String {
   let author = clap::crate_authors!();
 
@@ -0,0 +1,94 @@
+use serde_json::Value;
+
+use crate::mock::{self, METHODS};
+
+/// Operation of the `webhooks` of a 3.1 spec, a request the API sends to the receivers registered for it.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Webhook {
+  pub name: String,
+  pub method: String,
+  pub summary: Option<String>,
+  pub description: Option<String>,
+  /// Media type of the payload, JSON when the webhook has several.
+  pub media_type: Option<String>,
+  /// Schema of the payload, `Null` when the webhook has no request body.
+  pub schema: Value,
+  /// Example payload, for testing a receiver.
+  pub payload: Value,
+}
+
+impl Webhook {
+  /// Name, followed by the method when it is not the usual `POST`.
+  pub fn label(&self) -> String {
+    match self.method.as_str() {
+      "POST" => self.name.clone(),
+      method => format!("{} {method}", self.name),
+    }
+  }
+}
+
+pub fn webhooks(document: &Value) -> Vec<Webhook> {
+  let mut webhooks = vec![];
+  for (name, item) in document.get("webhooks").and_then(Value::as_object).into_iter().flatten() {
+    let item = mock::resolve(item, document);
+    for method in METHODS {
+      let Some(operation) = item.get(method) else {
+        continue;
+      };
+      let media = request_media(operation, document);
+      webhooks.push(Webhook {
+        name: name.clone(),
+        method: method.to_uppercase(),
+        summary: operation.get("summary").and_then(Value::as_str).map(String::from),
+        description: operation.get("description").and_then(Value::as_str).map(String::from),
+        media_type: media.map(|(media_type, _)| media_type.clone()),
+        schema: media.and_then(|(_, media)| media.get("schema")).cloned().unwrap_or_default(),
+        payload: media.map_or(Value::Null, |(_, media)| mock::media_example(media, document)),
+      });
+    }
+  }
+  webhooks
+}
+
+/// Media type and media of the request body of the operation, the JSON one when it has several.
+pub fn request_media<'a>(operation: &'a Value, document: &'a Value) -> Option<(&'a String, &'a Value)> {
+  let content = operation
+    .get("requestBody")
+    .map(|body| mock::resolve(body, document))
+    .and_then(|body| body.get("content"))
+    .and_then(Value::as_object)?;
+  content.iter().find(|(media_type, _)| media_type.contains("json")).or_else(|| content.iter().next())
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+  use serde_json::json;
+
+  use super::*;
+
+  #[test]
+  fn test_webhooks() {
+    let document = json!({
+      "webhooks": {
+        "newPet": { "post": {
+          "summary": "A pet was added",
+          "requestBody": { "content": {
+            "text/plain": { "schema": { "type": "string" } },
+            "application/json": { "schema": { "$ref": "#/components/schemas/Pet" } }
+          } }
+        } },
+        "ping": { "get": {} }
+      },
+      "components": { "schemas": { "Pet": {
+        "properties": { "id": { "type": "integer" }, "name": { "type": "string", "example": "Rex" } }
+      } } }
+    });
+    let webhooks = webhooks(&document);
+    assert_eq!(webhooks.iter().map(Webhook::label).collect::<Vec<_>>(), vec!["newPet", "ping GET"]);
+    assert_eq!(webhooks[0].media_type.as_deref(), Some("application/json"));
+    assert_eq!(webhooks[0].schema, json!({ "$ref": "#/components/schemas/Pet" }));
+    assert_eq!(webhooks[0].payload, json!({ "id": 0, "name": "Rex" }));
+    assert_eq!(webhooks[1].payload, Value::Null);
+  }
+}
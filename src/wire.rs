@@ -0,0 +1,126 @@
+use crate::{client::Exchange, request::HttpRequest, secrets};
+
+/// Logs the exchange sent, and at the trace level its raw request and response, secrets masked.
+pub fn log(exchange: &Exchange, secret_values: &[String]) {
+  let request = &exchange.request;
+  tracing::info!(
+    method = request.method,
+    url = request.url(),
+    status = exchange.response.status,
+    elapsed_ms = exchange.elapsed_ms,
+    "request sent"
+  );
+  if tracing::enabled!(tracing::Level::TRACE) {
+    let masked = secrets::mask_exchange(exchange, secret_values);
+    tracing::trace!(wire = dump(&masked), "request sent on the wire");
+  }
+}
+
+/// The request and response of the exchange as on the wire, prefixed with `>` and `<` like `curl --verbose`.
+pub fn dump(exchange: &Exchange) -> String {
+  let (request, response) = (&exchange.request, &exchange.response);
+  let url = request.full_url();
+  let authority = url.split_once("://").map_or(url.as_str(), |(_, rest)| rest);
+  let (host, target) = authority.find('/').map_or((authority, "/"), |index| authority.split_at(index));
+  let mut lines = vec![format!("{} {target} {}", request.method, response.http_version), format!("Host: {host}")];
+  lines.extend(request.headers.iter().map(|(name, value)| format!("{name}: {value}")));
+  lines.push(String::default());
+  lines.extend(body_lines(request));
+  let mut dump: Vec<String> = lines.into_iter().map(|line| format!("> {line}").trim_end().to_string()).collect();
+  let mut lines = vec![format!("{} {} {}", response.http_version, response.status, response.status_text)];
+  lines.extend(response.headers.iter().map(|(name, value)| format!("{name}: {value}")));
+  lines.push(String::default());
+  lines.extend(response.body.lines().map(String::from));
+  dump.extend(lines.into_iter().map(|line| format!("< {line}").trim_end().to_string()));
+  dump.join("\n")
+}
+
+/// Lines of the body of the request, the parts of a form as `[field name: value]` or `[file name: path]`.
+fn body_lines(request: &HttpRequest) -> Vec<String> {
+  match request.form.is_empty() {
+    true => request.body.iter().flat_map(|body| body.lines()).map(String::from).collect(),
+    false => {
+      request
+        .form
+        .iter()
+        .map(|part| {
+          let kind = if part.file { "file" } else { "field" };
+          format!("[{kind} {}: {}]", part.name, part.value)
+        })
+        .collect()
+    },
+  }
+}
+
+/// The request as it is about to be sent: its method and full url, query string included, its headers and its body.
+pub fn preview(request: &HttpRequest) -> Vec<String> {
+  let mut lines = vec![format!("{} {}", request.method, request.full_url())];
+  lines.extend(request.headers.iter().map(|(name, value)| format!("{name}: {value}")));
+  if request.body.is_some() || !request.form.is_empty() {
+    lines.push(String::default());
+    lines.extend(body_lines(request));
+  }
+  lines
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+  use crate::{client::HttpResponse, request::HttpRequest};
+
+  #[test]
+  fn test_dump() {
+    let exchange = Exchange {
+      request: HttpRequest {
+        method: String::from("POST"),
+        base_url: String::from("https://petstore.example.com/v2"),
+        path: String::from("/pet"),
+        query: vec![(String::from("dry run"), String::from("true"))],
+        headers: vec![
+          (String::from("Content-Type"), String::from("application/json")),
+          (String::from("Authorization"), String::from("Bearer abc")),
+        ],
+        body: Some(String::from("{\n  \"name\": \"doggie\"\n}")),
+        ..HttpRequest::default()
+      },
+      response: HttpResponse {
+        status: 201,
+        status_text: String::from("Created"),
+        http_version: String::from("HTTP/1.1"),
+        headers: vec![(String::from("Set-Cookie"), String::from("session=42"))],
+        body: String::from("{\"id\": 10}"),
+        ..HttpResponse::default()
+      },
+      ..Exchange::default()
+    };
+    assert_eq!(
+      dump(&secrets::mask_exchange(&exchange, &[])),
+      [
+        "> POST /v2/pet?dry%20run=true HTTP/1.1",
+        "> Host: petstore.example.com",
+        "> Content-Type: application/json",
+        "> Authorization: •••",
+        ">",
+        "> {",
+        ">   \"name\": \"doggie\"",
+        "> }",
+        "< HTTP/1.1 201 Created",
+        "< Set-Cookie: •••",
+        "<",
+        "< {\"id\": 10}",
+      ]
+      .join("\n")
+    );
+    assert_eq!(preview(&secrets::mask_request(&exchange.request, &[])), vec![
+      "POST https://petstore.example.com/v2/pet?dry%20run=true",
+      "Content-Type: application/json",
+      "Authorization: •••",
+      "",
+      "{",
+      "  \"name\": \"doggie\"",
+      "}",
+    ]);
+  }
+}
@@ -0,0 +1,163 @@
+use std::{collections::BTreeMap, path::Path};
+
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+  assertion::check,
+  client::{Exchange, HttpResponse},
+  extract, rate_limit,
+  request::HttpRequest,
+  script::{Hooks, LogLine},
+  workspace::OperationKey,
+};
+
+/// Step along with its request and the hooks it goes through, `None` when its operation is not in the spec.
+pub type PreparedStep = (Step, Option<(HttpRequest, OperationKey)>, Hooks);
+
+/// Ordered sequence of operations run one after the other, loosely modelled on Arazzo workflows.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Workflow {
+  pub name: String,
+  #[serde(default)]
+  pub description: Option<String>,
+  pub steps: Vec<Step>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Step {
+  pub id: String,
+  /// Operation id, or method and path, e.g. `POST /pet`.
+  pub operation: String,
+  /// Parameter values by name, they may reference variables, e.g. `{{created_id}}`.
+  #[serde(default)]
+  pub parameters: BTreeMap<String, String>,
+  /// Body text, a mapping or a list is sent as JSON.
+  #[serde(default)]
+  pub body: Option<Value>,
+  /// Variables set from the response, e.g. `created_id: $.id`, as in the extraction rules of operations.
+  #[serde(default)]
+  pub extract: BTreeMap<String, String>,
+  /// Conditions the response must meet for the workflow to go on, e.g. `$statusCode == 200` or
+  /// `$.status == available`, checked as the assertions of saved requests. Without any, a 2xx status is expected.
+  #[serde(default)]
+  pub success: Vec<String>,
+}
+
+impl Step {
+  pub fn body_text(&self) -> Option<String> {
+    match self.body.as_ref()? {
+      Value::String(text) => Some(text.clone()),
+      body => serde_json::to_string_pretty(body).ok(),
+    }
+  }
+
+  /// Describes the criteria the response fails, an empty list means the step succeeded.
+  pub fn failures(&self, response: &HttpResponse) -> Vec<String> {
+    if self.success.is_empty() {
+      return match response.status {
+        200..=299 => vec![],
+        status => vec![format!("expected a 2xx status, got {status}")],
+      };
+    }
+    self.success.iter().filter(|criterion| !check(criterion, response)).cloned().collect()
+  }
+}
+
+/// What running a step produced, sent back to the runner as each step finishes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StepOutcome {
+  pub exchange: Option<Exchange>,
+  pub extracted: BTreeMap<String, String>,
+  /// Unmet success criteria and missing extractions.
+  pub failures: Vec<String>,
+  /// Why the request could not be sent.
+  pub error: Option<String>,
+}
+
+impl StepOutcome {
+  pub fn passed(&self) -> bool {
+    self.error.is_none() && self.failures.is_empty()
+  }
+}
+
+/// Sends the step request, resolved with the variables extracted by the previous steps, through the scripts of its
+/// operation and the plugins, paced by the rate limit of the server, then checks the response and adds its extractions
+/// to the variables.
+pub async fn run_step(
+  step: &Step,
+  request: Option<(HttpRequest, OperationKey)>,
+  hooks: &Hooks,
+  variables: &mut BTreeMap<String, String>,
+) -> StepOutcome {
+  let Some((request, operation)) = request else {
+    return StepOutcome {
+      error: Some(format!("operation `{}` not found in the spec", step.operation)),
+      ..StepOutcome::default()
+    };
+  };
+  let exchange = match rate_limit::exchange(Some(operation), request.substitute(variables), hooks, variables).await {
+    Ok(exchange) => exchange,
+    Err(error) => return StepOutcome { error: Some(error.to_string()), ..StepOutcome::default() },
+  };
+  let mut failures = step.failures(&exchange.response);
+  failures.extend(exchange.log.iter().filter_map(|line| {
+    match line {
+      LogLine::Failed(message) => Some(message.clone()),
+      _ => None,
+    }
+  }));
+  let mut extracted = exchange.variables.clone();
+  for (variable, source) in step.extract.iter() {
+    match extract::extract(source, &exchange.response) {
+      Some(value) => {
+        extracted.insert(variable.clone(), value);
+      },
+      None => failures.push(format!("`{source}` not found for {variable}")),
+    }
+  }
+  variables.extend(extracted.clone());
+  StepOutcome { exchange: Some(exchange), extracted, failures, error: None }
+}
+
+pub fn load(path: &Path) -> Result<Workflow> {
+  Ok(serde_yaml::from_reader(std::fs::File::open(path)?)?)
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_workflow() {
+    let workflow: Workflow = serde_yaml::from_str(
+      r#"
+name: Adopt a pet
+steps:
+  - id: create
+    operation: addPet
+    body: { name: rex }
+    extract: { created_id: $.id }
+    success: ["$statusCode == 200", "$.name == 'rex'"]
+  - id: fetch
+    operation: GET /pet/{petId}
+    parameters: { petId: "{{created_id}}" }
+"#,
+    )
+    .unwrap();
+    assert_eq!(workflow.steps[0].body_text().as_deref(), Some("{\n  \"name\": \"rex\"\n}"));
+    assert_eq!(workflow.steps[1].parameters["petId"], "{{created_id}}");
+
+    let response =
+      HttpResponse { status: 200, body: String::from(r#"{ "id": 7, "name": "rex" }"#), ..Default::default() };
+    assert_eq!(workflow.steps[0].failures(&response), Vec::<String>::new());
+    assert!(check("$.id >= 7", &response));
+    assert!(!check("$.id < 7", &response));
+    assert!(!check("$.missing", &response));
+    let response = HttpResponse { status: 404, ..Default::default() };
+    assert_eq!(workflow.steps[1].failures(&response), vec![String::from("expected a 2xx status, got 404")]);
+  }
+}
@@ -0,0 +1,751 @@
+use std::{
+  collections::{BTreeMap, BTreeSet},
+  path::{Path, PathBuf},
+};
+
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+  export::http::BASE_URL_VARIABLE,
+  multipart::{self, FormPart},
+  network::Network,
+  parameter::{ParameterStyle, ParameterStyles},
+  request::{Auth, HttpRequest},
+  secrets,
+  signing::Signing,
+};
+
+const WORKSPACE_FOLDER: &str = ".openapi-tui";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct OperationKey {
+  pub method: String,
+  pub path: String,
+}
+
+impl OperationKey {
+  pub fn new(method: &str, path: &str) -> Self {
+    Self { method: method.to_uppercase(), path: path.to_string() }
+  }
+}
+
+impl std::fmt::Display for OperationKey {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{} {}", self.method, self.path)
+  }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SavedRequest {
+  pub name: String,
+  /// Spec operation exercised by this request, `None` when it could not be matched.
+  pub operation: Option<OperationKey>,
+  pub request: HttpRequest,
+  /// Folder path, nested folders are separated by `/`.
+  #[serde(default)]
+  pub folder: Option<String>,
+  #[serde(default)]
+  pub auth: Option<Auth>,
+  /// Checked against every response, e.g. `$statusCode == 200`, `$.items length > 0` or `header:ETag`.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub assertions: Vec<String>,
+}
+
+impl SavedRequest {
+  /// The request as it is sent: auth applied, and when an environment is given its `baseUrl` replaces the
+  /// server of requests matched to an operation and its variables are substituted.
+  pub fn resolve(&self, environment: Option<&Environment>) -> HttpRequest {
+    self.resolve_with(environment, secrets::load)
+  }
+
+  /// The request as it is sent, the keyring references of the environment read by `load`.
+  pub fn resolve_with(&self, environment: Option<&Environment>, load: impl Fn(&str) -> Result<String>) -> HttpRequest {
+    let mut request = self.request.clone();
+    if let Some(auth) = &self.auth {
+      auth.apply(&mut request);
+    }
+    let Some(environment) = environment else {
+      return request;
+    };
+    environment.apply_headers(&mut request);
+    if let Some(base_url) = environment.variables.get(BASE_URL_VARIABLE).filter(|_| self.operation.is_some()) {
+      request.base_url = base_url.trim_end_matches('/').to_string();
+    }
+    let mut variables = environment.variables.clone();
+    secrets::resolve_with(&mut variables, load);
+    request.substitute(&variables)
+  }
+}
+
+/// Named set of variables, e.g. `dev` or `staging`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Environment {
+  pub name: String,
+  #[serde(default)]
+  pub variables: BTreeMap<String, String>,
+  /// Headers added to every request sent with this environment, e.g. `X-Tenant-Id`.
+  #[serde(default)]
+  pub headers: BTreeMap<String, String>,
+  /// Signature of every request sent with this environment.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub signing: Option<Signing>,
+  /// Proxy, certificates, timeout and host overrides of every request sent with this environment.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub network: Option<Network>,
+}
+
+impl Environment {
+  /// Adds the default headers the request does not set itself.
+  pub fn apply_headers(&self, request: &mut HttpRequest) {
+    for (name, value) in self.headers.iter() {
+      if request.header(name).is_none() {
+        request.set_header(name, value.clone());
+      }
+    }
+  }
+}
+
+/// Change to a default environment header for a single operation.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HeaderOverride {
+  pub operation: OperationKey,
+  pub name: String,
+  /// Value sent instead of the default one, `None` removes the header.
+  pub value: Option<String>,
+}
+
+/// Rule copying a value of the responses of an operation into a variable, e.g. `created_id = $.id`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Extraction {
+  pub operation: OperationKey,
+  pub variable: String,
+  /// `header:<name>` or a JSONPath into the JSON body, e.g. `$.items[0].id`.
+  pub source: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DraftParameter {
+  /// `path`, `query` or `header`.
+  pub location: String,
+  pub name: String,
+  pub value: String,
+}
+
+/// Parameter values and body entered for an operation, restored whenever the operation is selected again.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RequestDraft {
+  pub parameters: Vec<DraftParameter>,
+  pub body: Option<String>,
+  /// Media type of the body, when the operation accepts several.
+  pub content_type: Option<String>,
+  /// Media type of the response asked for with the `Accept` header, when the operation returns several.
+  pub accept: Option<String>,
+  /// Values of the parts of a multipart body, or of the fields of an urlencoded one.
+  pub form: Vec<FormPart>,
+}
+
+impl RequestDraft {
+  pub fn parameter(&self, location: &str, name: &str) -> Option<&str> {
+    self
+      .parameters
+      .iter()
+      .find(|parameter| parameter.location == location && parameter.name == name)
+      .map(|parameter| parameter.value.as_str())
+  }
+
+  pub fn set_parameter(&mut self, location: &str, name: &str, value: String) {
+    self.clear_parameter(location, name);
+    self.parameters.push(DraftParameter { location: location.to_string(), name: name.to_string(), value });
+  }
+
+  pub fn clear_parameter(&mut self, location: &str, name: &str) {
+    self.parameters.retain(|parameter| parameter.location != location || parameter.name != name);
+  }
+
+  pub fn part(&self, name: &str) -> Option<&str> {
+    self.form.iter().find(|part| part.name == name).map(|part| part.value.as_str())
+  }
+
+  pub fn set_part(&mut self, part: FormPart) {
+    self.clear_part(&part.name);
+    self.form.push(part);
+  }
+
+  pub fn clear_part(&mut self, name: &str) {
+    self.form.retain(|part| part.name != name);
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.parameters.is_empty()
+      && self.body.is_none()
+      && self.content_type.is_none()
+      && self.accept.is_none()
+      && self.form.is_empty()
+  }
+
+  /// Fills the request built from the operation with the draft values, `{name}` path segments included. Array and
+  /// object values are serialized by the styles of their parameters.
+  pub fn apply(&self, request: &mut HttpRequest, styles: &ParameterStyles) {
+    for DraftParameter { location, name, value } in self.parameters.iter() {
+      let style =
+        styles.get(&(location.clone(), name.clone())).copied().unwrap_or_else(|| ParameterStyle::default_for(location));
+      match location.as_str() {
+        "path" => request.path = request.path.replace(format!("{{{name}}}").as_str(), &style.path(name, value)),
+        "query" => {
+          let index = request.query.iter().position(|(key, _)| key == name).unwrap_or(request.query.len());
+          request.query.retain(|(key, _)| key != name);
+          let index = index.min(request.query.len());
+          request.query.splice(index..index, style.query(name, value));
+        },
+        "header" => request.set_header(name, style.text(name, value)),
+        _ => {},
+      }
+    }
+    if let Some(content_type) = &self.content_type {
+      request.set_header("Content-Type", content_type.clone());
+    }
+    if let Some(accept) = &self.accept {
+      request.set_header("Accept", accept.clone());
+    }
+    if let Some(body) = &self.body {
+      request.body = Some(body.clone());
+    }
+    // urlencoded forms are sent as the body they encode to
+    if request.header("Content-Type").is_some_and(multipart::is_urlencoded) && !self.form.is_empty() {
+      request.body = Some(multipart::urlencode(&self.form));
+      return;
+    }
+    for part in self.form.iter() {
+      match request.form.iter_mut().find(|existing| existing.name == part.name) {
+        Some(existing) => existing.clone_from(part),
+        None => request.form.push(part.clone()),
+      }
+    }
+  }
+}
+
+/// Rhai scripts run before a request is sent and after its response is received, see `script`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Scripts {
+  pub pre_request: String,
+  pub post_response: String,
+}
+
+impl Scripts {
+  pub fn is_empty(&self) -> bool {
+    self.pre_request.trim().is_empty() && self.post_response.trim().is_empty()
+  }
+}
+
+/// What scripts are attached to, an operation or a folder of the collections.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScriptTarget {
+  Operation(OperationKey),
+  Folder(String),
+}
+
+impl std::fmt::Display for ScriptTarget {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      ScriptTarget::Operation(operation) => write!(f, "{operation}"),
+      ScriptTarget::Folder(folder) => write!(f, "{folder}/"),
+    }
+  }
+}
+
+/// Sizes of the home panes, changed by growing or shrinking the focused one.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PaneSizes {
+  /// Weights added to the groups of panes of the layouts, by layout name and in the order of their groups.
+  pub groups: BTreeMap<String, Vec<i16>>,
+  /// Weights added to the heights of the panes, in their order.
+  pub heights: Vec<i16>,
+}
+
+impl PaneSizes {
+  const GROUPS: std::ops::RangeInclusive<i16> = -60..=120;
+  const GROUP_STEP: i16 = 5;
+  /// Weight of a group of panes for each unit of its size, leaving room for the steps it is resized by.
+  pub const GROUP_WEIGHT: u16 = 20;
+  const HEIGHTS: std::ops::RangeInclusive<i16> = -2..=12;
+
+  /// Grows or shrinks the pane, its group in the layout and its height in the group.
+  pub fn resize(&mut self, layout: &str, group: usize, index: usize, grow: bool) {
+    let groups = self.groups.entry(layout.to_string()).or_default();
+    if groups.len() <= group {
+      groups.resize(group + 1, 0);
+    }
+    let width = groups[group] + if grow { Self::GROUP_STEP } else { -Self::GROUP_STEP };
+    groups[group] = width.clamp(*Self::GROUPS.start(), *Self::GROUPS.end());
+    if self.heights.len() <= index {
+      self.heights.resize(index + 1, 0);
+    }
+    let height = self.heights[index] + if grow { 1 } else { -1 };
+    self.heights[index] = height.clamp(*Self::HEIGHTS.start(), *Self::HEIGHTS.end());
+  }
+
+  /// Weight of the group of the layout, that of its size with the one added, at least a step.
+  pub fn group(&self, layout: &str, group: usize, size: u16) -> u16 {
+    let added = self.groups.get(layout).and_then(|groups| groups.get(group)).copied().unwrap_or_default();
+    let weight = i32::from(size.saturating_mul(Self::GROUP_WEIGHT)) + i32::from(added);
+    u16::try_from(weight).unwrap_or_default().max(Self::GROUP_STEP.unsigned_abs())
+  }
+
+  /// Weight of the height of the pane, the weight it asks for with the one added, at least 1.
+  pub fn height(&self, index: usize, weight: u16) -> u16 {
+    let added = self.heights.get(index).copied().unwrap_or_default();
+    u16::try_from(i32::from(weight) + i32::from(added)).unwrap_or_default().max(1)
+  }
+}
+
+/// Where the spec was left on exit, restored when it is opened again.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Session {
+  /// Tag the operations were filtered by.
+  pub tag: Option<String>,
+  /// Whether only the bookmarked operations were listed.
+  pub favorites_only: bool,
+  pub operation: Option<OperationKey>,
+  /// Index of the focused home pane.
+  pub focused_pane: usize,
+  pub fullscreen_pane: Option<usize>,
+}
+
+/// Per-spec state kept next to the spec in `.openapi-tui/<spec file name>.json`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Workspace {
+  pub saved_requests: Vec<SavedRequest>,
+  pub environments: Vec<Environment>,
+  /// Name of the environment whose variables are substituted in sent requests.
+  pub active_environment: Option<String>,
+  /// Index of the spec server used as base url.
+  pub active_server: usize,
+  /// Chosen server variable values by name, variables without a value use their default.
+  pub server_variables: BTreeMap<String, String>,
+  pub header_overrides: Vec<HeaderOverride>,
+  /// Request drafts by operation id, or by method and path for operations without one.
+  pub drafts: BTreeMap<String, RequestDraft>,
+  pub extractions: Vec<Extraction>,
+  /// Scripts of operations by method and path.
+  pub operation_scripts: BTreeMap<String, Scripts>,
+  /// Scripts of collection folders by folder path, they also run for the requests of nested folders.
+  pub folder_scripts: BTreeMap<String, Scripts>,
+  /// Operations a request was sent for, by hand or by a runner, by method and path.
+  pub exercised_operations: BTreeSet<String>,
+  /// Operations bookmarked as favorites, by method and path.
+  pub bookmarked_operations: BTreeSet<String>,
+  /// Notes on operations, by method and path.
+  pub notes: BTreeMap<String, String>,
+  pub pane_sizes: PaneSizes,
+  /// Name of the layout of the config the panes are arranged as.
+  pub active_layout: Option<String>,
+  pub session: Session,
+  /// Quits without asking even with unsaved edits or requests in flight.
+  pub skip_quit_confirmation: bool,
+}
+
+impl Workspace {
+  pub fn path(openapi_path: &str) -> PathBuf {
+    let openapi_path = Path::new(openapi_path);
+    let file_name = openapi_path.file_name().unwrap_or_default().to_string_lossy();
+    openapi_path.parent().unwrap_or(Path::new(".")).join(WORKSPACE_FOLDER).join(format!("{file_name}.json"))
+  }
+
+  /// The `.env` file discovered next to the spec.
+  pub fn dotenv_path(openapi_path: &str) -> PathBuf {
+    Path::new(openapi_path).parent().unwrap_or(Path::new(".")).join(".env")
+  }
+
+  pub fn load(openapi_path: &str) -> Result<Self> {
+    let path = Self::path(openapi_path);
+    if !path.exists() {
+      return Ok(Self::default());
+    }
+    Ok(serde_json::from_reader(std::fs::File::open(path)?)?)
+  }
+
+  pub fn save(&self, openapi_path: &str) -> Result<()> {
+    let path = Self::path(openapi_path);
+    if let Some(parent) = path.parent() {
+      std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+    Ok(())
+  }
+
+  /// Marks the operation as exercised, `true` when it was not yet.
+  pub fn exercise(&mut self, operation: &OperationKey) -> bool {
+    self.exercised_operations.insert(operation.to_string())
+  }
+
+  /// Bookmarks the operation, or removes its bookmark, `true` when it is now bookmarked.
+  pub fn toggle_bookmark(&mut self, operation: &OperationKey) -> bool {
+    let key = operation.to_string();
+    if self.bookmarked_operations.remove(&key) {
+      return false;
+    }
+    self.bookmarked_operations.insert(key)
+  }
+
+  pub fn is_bookmarked(&self, operation: &OperationKey) -> bool {
+    self.bookmarked_operations.contains(&operation.to_string())
+  }
+
+  pub fn note(&self, operation: &OperationKey) -> Option<&str> {
+    self.notes.get(&operation.to_string()).map(String::as_str)
+  }
+
+  /// Sets the note of the operation, trimmed, a blank one removes it.
+  pub fn set_note(&mut self, operation: &OperationKey, note: &str) {
+    match note.trim() {
+      "" => self.notes.remove(&operation.to_string()),
+      note => self.notes.insert(operation.to_string(), note.to_string()),
+    };
+  }
+
+  pub fn is_exercised(&self, operation: &OperationKey) -> bool {
+    self.exercised_operations.contains(&operation.to_string())
+  }
+
+  /// Adds the environment, or merges its variables and headers into those of an existing environment with the same
+  /// name, its values overriding the ones of the same keys and the others being kept.
+  pub fn merge_environment(&mut self, environment: Environment) {
+    match self.environments.iter_mut().find(|existing| existing.name == environment.name) {
+      Some(existing) => {
+        existing.variables.extend(environment.variables);
+        existing.headers.extend(environment.headers);
+      },
+      None => self.environments.push(environment),
+    }
+  }
+
+  /// Adds the imported requests, each replacing a saved request of the same folder, name, method and url so that
+  /// importing a collection again updates its requests, and returns how many were replaced.
+  ///
+  /// Only the requests saved before the import are replaced, so duplicates within the collection are all kept, and
+  /// the replaced requests keep their assertions.
+  pub fn import_requests(&mut self, saved_requests: Vec<SavedRequest>) -> usize {
+    let before = self.saved_requests.len();
+    let mut replaced = 0;
+    for saved in saved_requests {
+      let same = self.saved_requests[..before].iter_mut().find(|existing| {
+        existing.folder == saved.folder
+          && existing.name == saved.name
+          && existing.request.method == saved.request.method
+          && existing.request.url() == saved.request.url()
+      });
+      match same {
+        Some(existing) => {
+          let mut assertions = std::mem::take(&mut existing.assertions);
+          for assertion in saved.assertions {
+            if !assertions.contains(&assertion) {
+              assertions.push(assertion);
+            }
+          }
+          *existing = SavedRequest { assertions, ..saved };
+          replaced += 1;
+        },
+        None => self.saved_requests.push(saved),
+      }
+    }
+    replaced
+  }
+
+  pub fn header_override(&self, operation: &OperationKey, name: &str) -> Option<&HeaderOverride> {
+    self.header_overrides.iter().find(|header_override| {
+      &header_override.operation == operation && header_override.name.eq_ignore_ascii_case(name)
+    })
+  }
+
+  /// Replaces the override of the same operation and header, if any.
+  pub fn set_header_override(&mut self, header_override: HeaderOverride) {
+    self.clear_header_override(&header_override.operation, &header_override.name);
+    self.header_overrides.push(header_override);
+  }
+
+  pub fn clear_header_override(&mut self, operation: &OperationKey, name: &str) {
+    self.header_overrides.retain(|header_override| {
+      &header_override.operation != operation || !header_override.name.eq_ignore_ascii_case(name)
+    });
+  }
+
+  /// Reads the variables of a `.env` file without touching the process environment.
+  pub fn load_dotenv(path: &Path) -> Result<BTreeMap<String, String>> {
+    let mut variables = BTreeMap::new();
+    for item in dotenvy::from_path_iter(path)? {
+      let (name, value) = item?;
+      variables.insert(name, value);
+    }
+    Ok(variables)
+  }
+
+  pub fn scripts(&self, target: &ScriptTarget) -> Scripts {
+    let scripts = match target {
+      ScriptTarget::Operation(operation) => self.operation_scripts.get(&operation.to_string()),
+      ScriptTarget::Folder(folder) => self.folder_scripts.get(folder),
+    };
+    scripts.cloned().unwrap_or_default()
+  }
+
+  /// Replaces the scripts of the target, empty scripts are removed.
+  pub fn set_scripts(&mut self, target: &ScriptTarget, scripts: Scripts) {
+    let (all, key) = match target {
+      ScriptTarget::Operation(operation) => (&mut self.operation_scripts, operation.to_string()),
+      ScriptTarget::Folder(folder) => (&mut self.folder_scripts, folder.clone()),
+    };
+    match scripts.is_empty() {
+      true => all.remove(&key),
+      false => all.insert(key, scripts),
+    };
+  }
+
+  /// Scripts run for a request, those of the outermost folder first and those of the operation last.
+  pub fn request_scripts(&self, operation: Option<&OperationKey>, folder: Option<&str>) -> Vec<Scripts> {
+    let segments: Vec<&str> = folder.unwrap_or_default().split('/').filter(|segment| !segment.is_empty()).collect();
+    let folders = (1..=segments.len()).filter_map(|depth| self.folder_scripts.get(&segments[..depth].join("/")));
+    let operation = operation.and_then(|operation| self.operation_scripts.get(&operation.to_string()));
+    folders.chain(operation).cloned().collect()
+  }
+
+  pub fn saved_requests_for(&self, operation: &OperationKey) -> impl Iterator<Item = &SavedRequest> {
+    let operation = operation.clone();
+    self.saved_requests.iter().filter(move |saved| saved.operation.as_ref() == Some(&operation))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_bookmarks() {
+    let mut workspace = Workspace::default();
+    let operation = OperationKey::new("get", "/pets/{petId}");
+    assert!(workspace.toggle_bookmark(&operation));
+    assert!(workspace.is_bookmarked(&OperationKey::new("GET", "/pets/{petId}")));
+    assert!(!workspace.is_bookmarked(&OperationKey::new("DELETE", "/pets/{petId}")));
+    assert!(!workspace.toggle_bookmark(&operation));
+    assert!(workspace.bookmarked_operations.is_empty());
+
+    workspace.set_note(&operation, "returns 200 on failure\n");
+    assert_eq!(workspace.note(&operation), Some("returns 200 on failure"));
+    workspace.set_note(&operation, " \n");
+    assert_eq!(workspace.note(&operation), None);
+  }
+
+  #[test]
+  fn test_import_requests() {
+    let saved = |name: &str, path: &str, body: &str| {
+      SavedRequest {
+        name: name.to_string(),
+        operation: None,
+        request: HttpRequest {
+          method: String::from("POST"),
+          base_url: String::from("{{baseUrl}}"),
+          path: path.to_string(),
+          body: Some(body.to_string()),
+          ..HttpRequest::default()
+        },
+        folder: None,
+        auth: None,
+        assertions: vec![],
+      }
+    };
+    let mut workspace = Workspace::default();
+    assert_eq!(workspace.import_requests(vec![saved("addPet", "/pet", "{}"), saved("addUser", "/user", "{}")]), 0);
+    let replaced = workspace.import_requests(vec![
+      saved("addPet", "/pet", r#"{"name":"rex"}"#),
+      saved("addPet", "/pets", "{}"),
+      saved("addUser", "/user", "{}"),
+    ]);
+    assert_eq!(replaced, 2);
+    let names: Vec<(&str, &str, Option<&str>)> = workspace
+      .saved_requests
+      .iter()
+      .map(|saved| (saved.name.as_str(), saved.request.path.as_str(), saved.request.body.as_deref()))
+      .collect();
+    assert_eq!(names, vec![
+      ("addPet", "/pet", Some(r#"{"name":"rex"}"#)),
+      ("addUser", "/user", Some("{}")),
+      ("addPet", "/pets", Some("{}")),
+    ]);
+
+    // the duplicates of a collection are all imported
+    let mut workspace = Workspace::default();
+    assert_eq!(workspace.import_requests(vec![saved("addPet", "/pet", "{}"), saved("addPet", "/pet", "{}")]), 0);
+    assert_eq!(workspace.saved_requests.len(), 2);
+
+    // requests of other folders are not replaced
+    let mut workspace = Workspace::default();
+    workspace
+      .import_requests(vec![SavedRequest { folder: Some(String::from("pets")), ..saved("addPet", "/pet", "{}") }]);
+    assert_eq!(workspace.import_requests(vec![saved("addPet", "/pet", "{}")]), 0);
+    assert_eq!(workspace.saved_requests.len(), 2);
+    let replaced = workspace.import_requests(vec![SavedRequest {
+      folder: Some(String::from("pets")),
+      ..saved("addPet", "/pet", r#"{"name":"rex"}"#)
+    }]);
+    assert_eq!(replaced, 1);
+    assert_eq!(workspace.saved_requests[0].request.body.as_deref(), Some(r#"{"name":"rex"}"#));
+
+    // the assertions of the replaced requests are kept
+    let mut workspace = Workspace::default();
+    workspace.import_requests(vec![SavedRequest {
+      assertions: vec![String::from("status == 201")],
+      ..saved("addPet", "/pet", "{}")
+    }]);
+    workspace.import_requests(vec![SavedRequest {
+      assertions: vec![String::from("status == 201"), String::from("body.id != null")],
+      ..saved("addPet", "/pet", r#"{"name":"rex"}"#)
+    }]);
+    workspace.import_requests(vec![saved("addPet", "/pet", r#"{"name":"rex"}"#)]);
+    assert_eq!(workspace.saved_requests[0].assertions, vec![
+      String::from("status == 201"),
+      String::from("body.id != null")
+    ]);
+  }
+
+  #[test]
+  fn test_resolve_saved_request() {
+    let saved = SavedRequest {
+      name: String::from("getPet"),
+      operation: Some(OperationKey::new("GET", "/pets/{petId}")),
+      request: HttpRequest {
+        method: String::from("GET"),
+        base_url: String::from("https://captured.example.com"),
+        path: String::from("/pets/{{petId}}"),
+        headers: vec![(String::from("Authorization"), String::from("Bearer {{authToken}}"))],
+        ..HttpRequest::default()
+      },
+      folder: None,
+      auth: None,
+      assertions: vec![],
+    };
+    let environment = Environment {
+      name: String::from("dev"),
+      variables: BTreeMap::from([
+        (String::from("baseUrl"), String::from("https://dev.example.com/")),
+        (String::from("petId"), String::from("42")),
+        (String::from("authToken"), String::from("keyring:petstore-token")),
+      ]),
+      ..Environment::default()
+    };
+    let load = |name: &str| {
+      match name {
+        "petstore-token" => Ok(String::from("s3cr3t-token")),
+        _ => Err(color_eyre::eyre::eyre!("No entry for `{name}`")),
+      }
+    };
+
+    let request = saved.resolve_with(Some(&environment), load);
+    assert_eq!(request.base_url, "https://dev.example.com");
+    assert_eq!(request.path, "/pets/42");
+    assert_eq!(request.header("Authorization"), Some("Bearer s3cr3t-token"));
+    assert_eq!(saved.resolve_with(None, load), saved.request);
+  }
+
+  #[test]
+  fn test_pane_sizes() {
+    let mut sizes = PaneSizes::default();
+    sizes.resize("default", 1, 7, true);
+    sizes.resize("default", 1, 7, true);
+    assert_eq!(sizes.group("default", 1, 3), 70);
+    assert_eq!(sizes.group("default", 0, 1), 20);
+    assert_eq!(sizes.group("reading", 1, 2), 40);
+    assert_eq!(sizes.height(7, 3), 5);
+    assert_eq!(sizes.height(0, 3), 3);
+    for _ in 0..20 {
+      sizes.resize("default", 0, 0, false);
+    }
+    assert_eq!(sizes.group("default", 0, 1), 5);
+    assert_eq!(sizes.height(0, 3), 1);
+    let sizes: PaneSizes = serde_json::from_str("{}").unwrap();
+    assert_eq!(sizes, PaneSizes::default());
+  }
+
+  #[test]
+  fn test_header_overrides() {
+    let environment = Environment {
+      name: String::from("dev"),
+      headers: BTreeMap::from([
+        (String::from("User-Agent"), String::from("openapi-tui")),
+        (String::from("X-Tenant-Id"), String::from("acme")),
+      ]),
+      ..Environment::default()
+    };
+    let mut request =
+      HttpRequest { headers: vec![(String::from("x-tenant-id"), String::from("other"))], ..HttpRequest::default() };
+    environment.apply_headers(&mut request);
+    assert_eq!(request.header("X-Tenant-Id"), Some("other"));
+    assert_eq!(request.header("User-Agent"), Some("openapi-tui"));
+
+    let operation = OperationKey::new("get", "/pets");
+    let mut workspace = Workspace::default();
+    workspace.set_header_override(HeaderOverride {
+      operation: operation.clone(),
+      name: String::from("User-Agent"),
+      value: None,
+    });
+    workspace.set_header_override(HeaderOverride {
+      operation: operation.clone(),
+      name: String::from("user-agent"),
+      value: Some(String::from("curl")),
+    });
+    assert_eq!(workspace.header_overrides.len(), 1);
+    assert_eq!(
+      workspace.header_override(&operation, "USER-AGENT").and_then(|header| header.value.as_deref()),
+      Some("curl")
+    );
+    workspace.clear_header_override(&operation, "User-Agent");
+    assert!(workspace.header_overrides.is_empty());
+  }
+
+  #[test]
+  fn test_apply_draft() {
+    let mut draft = RequestDraft::default();
+    draft.set_parameter("path", "petId", String::from("42"));
+    draft.set_parameter("query", "verbose", String::from("true"));
+    draft.set_parameter("query", "verbose", String::from("false"));
+    draft.set_parameter("query", "tags", String::from(r#"["dog", "cat"]"#));
+    draft.body = Some(String::from("{\"name\":\"rex\"}"));
+    draft.accept = Some(String::from("application/xml"));
+    draft.set_part(FormPart { name: String::from("file"), value: String::from("pet.png"), file: true });
+    let mut request = HttpRequest {
+      path: String::from("/pet/{petId}"),
+      query: vec![(String::from("verbose"), String::default())],
+      form: vec![FormPart { name: String::from("file"), file: true, ..FormPart::default() }],
+      ..HttpRequest::default()
+    };
+    let tags = ParameterStyle { structured: true, ..ParameterStyle::default_for("query") };
+    draft.apply(&mut request, &ParameterStyles::from([((String::from("query"), String::from("tags")), tags)]));
+    assert_eq!(request.path, "/pet/42");
+    assert_eq!(request.query_string(), "?verbose=false&tags=dog&tags=cat");
+    assert_eq!(request.body.as_deref(), Some("{\"name\":\"rex\"}"));
+    assert_eq!(request.header("Accept"), Some("application/xml"));
+    assert_eq!(request.form[0].value, "pet.png");
+
+    let mut draft =
+      RequestDraft { content_type: Some(String::from("application/x-www-form-urlencoded")), ..RequestDraft::default() };
+    draft.set_part(FormPart { name: String::from("status"), value: String::from("sold out"), file: false });
+    let mut request = HttpRequest::default();
+    draft.apply(&mut request, &ParameterStyles::default());
+    assert_eq!(request.body.as_deref(), Some("status=sold%20out"));
+    assert!(request.form.is_empty());
+
+    let mut draft = RequestDraft::default();
+    draft.set_parameter("path", "name", String::from("dogs/rex"));
+    draft.set_parameter("query", "filter", String::from(r#"{"a":1}"#));
+    let mut request = HttpRequest { path: String::from("/pets/{name}"), ..HttpRequest::default() };
+    draft.apply(&mut request, &ParameterStyles::default());
+    assert_eq!(request.path, "/pets/dogs%2Frex");
+    assert_eq!(request.query, vec![(String::from("filter"), String::from(r#"{"a":1}"#))]);
+  }
+}
@@ -0,0 +1,504 @@
+use ratatui::prelude::*;
+use serde_json::Value;
+
+use crate::mock;
+
+const SCHEMA_REF_PREFIX: &str = "#/components/schemas/";
+const DECLARATION: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>";
+
+/// Whether the media type is an XML one, e.g. `application/xml`, `text/xml` or `application/soap+xml`.
+pub fn is_xml(media_type: &str) -> bool {
+  let essence = media_type.split(';').next().unwrap_or_default().trim();
+  essence.ends_with("/xml") || essence.ends_with("+xml")
+}
+
+/// Example document of the schema, shaped by its `xml` annotations: names and prefixes of elements, namespaces,
+/// attributes and wrapped arrays.
+pub fn example(schema: &Value, document: &Value) -> String {
+  let value = mock::example(schema, document, 0);
+  let resolved = mock::resolve(schema, document);
+  let xml = resolved.get("xml");
+  let component =
+    schema.get("$ref").and_then(Value::as_str).and_then(|reference| reference.strip_prefix(SCHEMA_REF_PREFIX));
+  let name = annotation(xml, "name").or(component).unwrap_or("root");
+  let mut out = format!("{DECLARATION}\n");
+  match (&value, resolved.get("items")) {
+    // arrays at the root are always wrapped in an element
+    (Value::Array(items), Some(items_schema)) => {
+      out.push_str(&format!("<{}{}>\n", qualified(name, xml), namespace(xml)));
+      let item_xml = mock::resolve(items_schema, document).get("xml");
+      let item_name = annotation(item_xml, "name").unwrap_or(name);
+      for item in items {
+        element(&mut out, &qualified(item_name, item_xml), item_xml, items_schema, item, document, 1);
+      }
+      out.push_str(&format!("</{}>\n", qualified(name, xml)));
+    },
+    _ => element(&mut out, &qualified(name, xml), xml, schema, &value, document, 0),
+  }
+  out
+}
+
+fn annotation<'a>(xml: Option<&'a Value>, key: &str) -> Option<&'a str> {
+  xml.and_then(|xml| xml.get(key)).and_then(Value::as_str)
+}
+
+/// `xml` annotations of the schema, those of the schema it references when it has none of its own.
+fn annotations<'a>(schema: &'a Value, document: &'a Value) -> Option<&'a Value> {
+  schema.get("xml").or_else(|| mock::resolve(schema, document).get("xml"))
+}
+
+fn qualified(name: &str, xml: Option<&Value>) -> String {
+  match annotation(xml, "prefix") {
+    Some(prefix) => format!("{prefix}:{name}"),
+    None => name.to_string(),
+  }
+}
+
+/// `xmlns` attribute of the namespace of the annotations, with a leading space.
+fn namespace(xml: Option<&Value>) -> String {
+  match (annotation(xml, "namespace"), annotation(xml, "prefix")) {
+    (Some(namespace), Some(prefix)) => format!(" xmlns:{prefix}=\"{}\"", escape(namespace)),
+    (Some(namespace), None) => format!(" xmlns=\"{}\"", escape(namespace)),
+    _ => String::default(),
+  }
+}
+
+/// Properties of the object schema, those of its `allOf` members included.
+fn properties<'a>(schema: &'a Value, document: &'a Value) -> Vec<(&'a String, &'a Value)> {
+  let schema = mock::resolve(schema, document);
+  let mut properties: Vec<(&String, &Value)> =
+    schema.get("properties").and_then(Value::as_object).into_iter().flatten().collect();
+  for member in schema.get("allOf").and_then(Value::as_array).into_iter().flatten() {
+    properties.extend(self::properties(member, document));
+  }
+  properties
+}
+
+fn text(value: &Value) -> String {
+  match value {
+    Value::String(text) => escape(text),
+    value => escape(&value.to_string()),
+  }
+}
+
+fn element(
+  out: &mut String,
+  name: &str,
+  xml: Option<&Value>,
+  schema: &Value,
+  value: &Value,
+  document: &Value,
+  depth: usize,
+) {
+  let indent = "  ".repeat(depth);
+  let Value::Object(members) = value else {
+    match value {
+      Value::Null => out.push_str(&format!("{indent}<{name}{}/>\n", namespace(xml))),
+      value => out.push_str(&format!("{indent}<{name}{}>{}</{name}>\n", namespace(xml), text(value))),
+    }
+    return;
+  };
+  let properties = properties(schema, document);
+  let mut attributes = namespace(xml);
+  let mut children = vec![];
+  for (property_name, property) in properties {
+    let Some(member) = members.get(property_name) else {
+      continue;
+    };
+    let property_xml = annotations(property, document);
+    let property_name = annotation(property_xml, "name").unwrap_or(property_name);
+    match property_xml.and_then(|xml| xml.get("attribute")).and_then(Value::as_bool) {
+      Some(true) => attributes.push_str(&format!(" {}=\"{}\"", qualified(property_name, property_xml), text(member))),
+      _ => children.push((property_name, property, property_xml, member)),
+    }
+  }
+  if children.is_empty() {
+    out.push_str(&format!("{indent}<{name}{attributes}/>\n"));
+    return;
+  }
+  out.push_str(&format!("{indent}<{name}{attributes}>\n"));
+  for (property_name, property, property_xml, member) in children {
+    let items_schema = mock::resolve(property, document).get("items");
+    let (Value::Array(items), Some(items_schema)) = (member, items_schema) else {
+      element(out, &qualified(property_name, property_xml), property_xml, property, member, document, depth + 1);
+      continue;
+    };
+    let item_xml = annotations(items_schema, document);
+    let item_name = qualified(annotation(item_xml, "name").unwrap_or(property_name), item_xml);
+    let wrapped = property_xml.and_then(|xml| xml.get("wrapped")).and_then(Value::as_bool).unwrap_or_default();
+    let item_depth = depth + 1 + usize::from(wrapped);
+    let wrapper = qualified(property_name, property_xml);
+    if wrapped {
+      out.push_str(&format!("{indent}  <{wrapper}{}>\n", namespace(property_xml)));
+    }
+    for item in items {
+      element(out, &item_name, item_xml, items_schema, item, document, item_depth);
+    }
+    if wrapped {
+      out.push_str(&format!("{indent}  </{wrapper}>\n"));
+    }
+  }
+  out.push_str(&format!("{indent}</{name}>\n"));
+}
+
+/// The text escaped for the content and attribute values of XML and HTML documents.
+pub fn escape(text: &str) -> String {
+  text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;").replace('\'', "&apos;")
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token<'a> {
+  Start {
+    name: &'a str,
+    source: &'a str,
+    empty: bool,
+  },
+  End {
+    name: &'a str,
+    source: &'a str,
+  },
+  Text(&'a str),
+  /// Declaration, processing instruction, comment, doctype or CDATA section.
+  Other(&'a str),
+}
+
+/// Line of the byte offset, from 1.
+fn line_of(text: &str, offset: usize) -> usize {
+  text[..offset].matches('\n').count() + 1
+}
+
+fn is_name(name: &str) -> bool {
+  let mut chars = name.chars();
+  chars.next().is_some_and(|first| first.is_alphabetic() || first == '_' || first == ':')
+    && chars.all(|c| c.is_alphanumeric() || matches!(c, '_' | ':' | '-' | '.'))
+}
+
+fn tokens(text: &str) -> Result<Vec<(usize, Token<'_>)>, String> {
+  let mut tokens = vec![];
+  let mut offset = 0;
+  while offset < text.len() {
+    let rest = &text[offset..];
+    let error = |message: &str| Err(format!("line {}: {message}", line_of(text, offset)));
+    if !rest.starts_with('<') {
+      let end = rest.find('<').unwrap_or(rest.len());
+      let content = &rest[..end];
+      if let Some(message) = entities_error(content) {
+        return error(&message);
+      }
+      tokens.push((offset, Token::Text(content)));
+      offset += end;
+      continue;
+    }
+    let delimited =
+      [("<!--", "-->"), ("<![CDATA[", "]]>"), ("<?", "?>")].into_iter().find(|(start, _)| rest.starts_with(start));
+    if let Some((start, end)) = delimited {
+      let Some(length) = rest[start.len()..].find(end) else {
+        return error(&format!("`{start}` is not closed by `{end}`"));
+      };
+      let length = start.len() + length + end.len();
+      tokens.push((offset, Token::Other(&rest[..length])));
+      offset += length;
+      continue;
+    }
+    // the end of the tag, outside of quoted attribute values
+    let mut quote = None;
+    let Some(length) = rest.char_indices().find_map(|(index, c)| {
+      match (quote, c) {
+        (None, '"' | '\'') => quote = Some(c),
+        (Some(open), c) if c == open => quote = None,
+        (None, '>') => return Some(index + 1),
+        _ => {},
+      }
+      None
+    }) else {
+      return error("tag is not closed by `>`");
+    };
+    let source = &rest[..length];
+    offset += length;
+    if source.starts_with("<!") {
+      tokens.push((offset - length, Token::Other(source)));
+      continue;
+    }
+    if let Some(name) = source.strip_prefix("</") {
+      let name = name.trim_end_matches('>').trim();
+      if !is_name(name) {
+        return Err(format!("line {}: invalid closing tag `{source}`", line_of(text, offset - length)));
+      }
+      tokens.push((offset - length, Token::End { name, source }));
+      continue;
+    }
+    let empty = source.ends_with("/>");
+    let inner = source[1..source.len() - if empty { 2 } else { 1 }].trim_end();
+    let name_end = inner.find(|c: char| c.is_whitespace()).unwrap_or(inner.len());
+    let name = &inner[..name_end];
+    if !is_name(name) {
+      return Err(format!("line {}: invalid tag `{source}`", line_of(text, offset - length)));
+    }
+    if let Some(message) = attributes_error(&inner[name_end..]) {
+      return Err(format!("line {}: {message} in `{name}`", line_of(text, offset - length)));
+    }
+    tokens.push((offset - length, Token::Start { name, source, empty }));
+  }
+  Ok(tokens)
+}
+
+/// What is wrong with the `&` references of the text, if anything.
+fn entities_error(text: &str) -> Option<String> {
+  let mut rest = text;
+  while let Some(start) = rest.find('&') {
+    let Some(end) = rest[start..].find(';') else {
+      return Some(String::from("`&` is not escaped as `&amp;`"));
+    };
+    let entity = &rest[start + 1..start + end];
+    let valid = matches!(entity, "amp" | "lt" | "gt" | "quot" | "apos")
+      || entity.strip_prefix("#x").is_some_and(|hex| !hex.is_empty() && hex.chars().all(|c| c.is_ascii_hexdigit()))
+      || entity
+        .strip_prefix('#')
+        .is_some_and(|digits| !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit()));
+    if !valid {
+      return Some(format!("unknown entity `&{entity};`"));
+    }
+    rest = &rest[start + end + 1..];
+  }
+  None
+}
+
+/// What is wrong with the attributes of a start tag, if anything.
+fn attributes_error(attributes: &str) -> Option<String> {
+  let mut names = vec![];
+  let mut rest = attributes.trim_start();
+  while !rest.is_empty() {
+    let Some(equals) = rest.find('=') else {
+      return Some(format!("attribute `{}` has no value", rest.trim()));
+    };
+    let name = rest[..equals].trim();
+    if !is_name(name) {
+      return Some(format!("invalid attribute `{name}`"));
+    }
+    if names.contains(&name) {
+      return Some(format!("duplicate attribute `{name}`"));
+    }
+    names.push(name);
+    let value = rest[equals + 1..].trim_start();
+    let Some(quote) = value.chars().next().filter(|quote| *quote == '"' || *quote == '\'') else {
+      return Some(format!("value of `{name}` is not quoted"));
+    };
+    let Some(end) = value[1..].find(quote) else {
+      return Some(format!("value of `{name}` is not closed"));
+    };
+    let content = &value[1..end + 1];
+    if content.contains('<') {
+      return Some(format!("value of `{name}` contains `<`"));
+    }
+    if let Some(message) = entities_error(content) {
+      return Some(message);
+    }
+    rest = value[end + 2..].trim_start();
+  }
+  None
+}
+
+/// Checks that the text is a well-formed XML document: a single root element, tags closed in order, quoted
+/// attributes and known entities.
+pub fn check(text: &str) -> Result<(), String> {
+  let mut open: Vec<&str> = vec![];
+  let mut roots = 0;
+  for (offset, token) in tokens(text)? {
+    let line = line_of(text, offset);
+    match token {
+      Token::Start { name, empty, .. } => {
+        if open.is_empty() {
+          roots += 1;
+          if roots > 1 {
+            return Err(format!("line {line}: second root element `{name}`"));
+          }
+        }
+        if !empty {
+          open.push(name);
+        }
+      },
+      Token::End { name, .. } => {
+        match open.pop() {
+          Some(expected) if expected == name => {},
+          Some(expected) => return Err(format!("line {line}: `</{name}>` closes `<{expected}>`")),
+          None => return Err(format!("line {line}: `</{name}>` closes no element")),
+        }
+      },
+      Token::Text(content) if open.is_empty() && !content.trim().is_empty() => {
+        return Err(format!("line {line}: text outside of the root element"));
+      },
+      Token::Other(source) if source.starts_with("<![CDATA[") && open.is_empty() => {
+        return Err(format!("line {line}: CDATA section outside of the root element"));
+      },
+      _ => {},
+    }
+  }
+  if let Some(name) = open.last() {
+    return Err(format!("`<{name}>` is not closed"));
+  }
+  if roots == 0 {
+    return Err(String::from("no root element"));
+  }
+  Ok(())
+}
+
+/// The document indented, an element per line and text kept on the line of its element, `None` when it is not
+/// well-formed.
+pub fn pretty(text: &str) -> Option<String> {
+  check(text).ok()?;
+  let tokens: Vec<Token> = tokens(text)
+    .ok()?
+    .into_iter()
+    .map(|(_, token)| token)
+    .filter(|token| !matches!(token, Token::Text(content) if content.trim().is_empty()))
+    .collect();
+  let mut lines = vec![];
+  let mut depth = 0;
+  let mut index = 0;
+  while index < tokens.len() {
+    let indent = "  ".repeat(depth);
+    match (&tokens[index], tokens.get(index + 1), tokens.get(index + 2)) {
+      (Token::Start { source, empty: false, .. }, Some(Token::Text(content)), Some(Token::End { source: end, .. })) => {
+        lines.push(format!("{indent}{source}{}{end}", content.trim()));
+        index += 3;
+        continue;
+      },
+      (Token::Start { source, empty: false, .. }, Some(Token::End { source: end, .. }), _) => {
+        lines.push(format!("{indent}{source}{end}"));
+        index += 2;
+        continue;
+      },
+      (Token::Start { source, empty, .. }, _, _) => {
+        lines.push(format!("{indent}{source}"));
+        depth += usize::from(!empty);
+      },
+      (Token::End { source, .. }, _, _) => {
+        depth = depth.saturating_sub(1);
+        lines.push(format!("{}{source}", "  ".repeat(depth)));
+      },
+      (Token::Text(content), _, _) => lines.push(format!("{indent}{}", content.trim())),
+      (Token::Other(source), _, _) => lines.push(format!("{indent}{source}")),
+    }
+    index += 1;
+  }
+  Some(lines.join("\n"))
+}
+
+/// Spans of a line of XML: tag names, attributes and their values, comments and text.
+pub fn highlight(line: &str) -> Vec<(Style, String)> {
+  let tag = Style::default().fg(Color::LightBlue);
+  let attribute = Style::default().fg(Color::LightCyan);
+  let value = Style::default().fg(Color::LightGreen);
+  let punctuation = Style::default().dim();
+  let mut spans: Vec<(Style, String)> = vec![];
+  let mut push = |style: Style, text: &str| {
+    if !text.is_empty() {
+      spans.push((style, text.to_string()));
+    }
+  };
+  let mut rest = line;
+  while !rest.is_empty() {
+    let Some(start) = rest.find('<') else {
+      push(Style::default(), rest);
+      break;
+    };
+    push(Style::default(), &rest[..start]);
+    rest = &rest[start..];
+    if rest.starts_with("<!--") || rest.starts_with("<?") || rest.starts_with("<!") {
+      let end = rest.find('>').map_or(rest.len(), |end| end + 1);
+      push(punctuation, &rest[..end]);
+      rest = &rest[end..];
+      continue;
+    }
+    let opening = if rest.starts_with("</") { 2 } else { 1 };
+    push(punctuation, &rest[..opening]);
+    rest = &rest[opening..];
+    let name_end = rest.find(|c: char| c.is_whitespace() || c == '>' || c == '/').unwrap_or(rest.len());
+    push(tag, &rest[..name_end]);
+    rest = &rest[name_end..];
+    // attributes up to the end of the tag
+    while !rest.is_empty() && !rest.starts_with('>') && !rest.starts_with("/>") {
+      if let Some(quote) = rest.chars().next().filter(|c| *c == '"' || *c == '\'') {
+        let end = rest[1..].find(quote).map_or(rest.len(), |end| end + 2);
+        push(value, &rest[..end]);
+        rest = &rest[end..];
+        continue;
+      }
+      let end = rest.find(['=', '"', '\'', '>', '/']).unwrap_or(rest.len());
+      let end = end.max(1);
+      let style = if rest.starts_with(['=', '/']) { punctuation } else { attribute };
+      push(style, &rest[..end]);
+      rest = &rest[end..];
+    }
+    let close = if rest.starts_with("/>") { 2 } else { rest.len().min(1) };
+    push(punctuation, &rest[..close]);
+    rest = &rest[close..];
+  }
+  spans
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+  use serde_json::json;
+
+  use super::*;
+
+  #[test]
+  fn test_example() {
+    let document = json!({ "components": { "schemas": {
+      "Pet": {
+        "type": "object",
+        "xml": { "name": "pet", "prefix": "p", "namespace": "https://example.com/pet" },
+        "properties": {
+          "id": { "type": "integer", "example": 7, "xml": { "attribute": true } },
+          "name": { "type": "string", "example": "Rex & co" },
+          "photoUrls": {
+            "type": "array",
+            "xml": { "wrapped": true },
+            "items": { "type": "string", "example": "a.png", "xml": { "name": "photoUrl" } }
+          },
+          "tags": { "type": "array", "items": { "$ref": "#/components/schemas/Tag" } }
+        }
+      },
+      "Tag": { "type": "object", "xml": { "name": "tag" }, "properties": { "name": { "type": "string", "example": "good" } } }
+    } } });
+    let schema = json!({ "$ref": "#/components/schemas/Pet" });
+    assert_eq!(
+      example(&schema, &document),
+      "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<p:pet xmlns:p=\"https://example.com/pet\" id=\"7\">\n  <name>Rex \
+       &amp; co</name>\n  <photoUrls>\n    <photoUrl>a.png</photoUrl>\n  </photoUrls>\n  <tag>\n    \
+       <name>good</name>\n  </tag>\n</p:pet>\n"
+    );
+    assert!(check(&example(&schema, &document)).is_ok());
+    assert!(is_xml("application/soap+xml; charset=utf-8"));
+    assert!(!is_xml("application/json"));
+  }
+
+  #[test]
+  fn test_check() {
+    assert_eq!(check("<a><b></a>"), Err(String::from("line 1: `</a>` closes `<b>`")));
+    assert_eq!(check("<a>\n</a><b/>"), Err(String::from("line 2: second root element `b`")));
+    assert_eq!(check("<a x=1/>"), Err(String::from("line 1: value of `x` is not quoted in `a`")));
+    assert_eq!(check("<a>R&D</a>"), Err(String::from("line 1: `&` is not escaped as `&amp;`")));
+    assert_eq!(check("<a>"), Err(String::from("`<a>` is not closed")));
+    assert_eq!(check("<?xml version=\"1.0\"?>\n<!-- a > b --><a t='x>y'><![CDATA[<raw>]]>&lt;</a>"), Ok(()));
+  }
+
+  #[test]
+  fn test_pretty() {
+    let text = "<?xml version=\"1.0\"?><pet id=\"1\"><name>Rex</name><tags><tag/></tags><empty></empty></pet>";
+    assert_eq!(
+      pretty(text).as_deref(),
+      Some(
+        "<?xml version=\"1.0\"?>\n<pet id=\"1\">\n  <name>Rex</name>\n  <tags>\n    <tag/>\n  </tags>\n  \
+         <empty></empty>\n</pet>"
+      )
+    );
+    assert_eq!(pretty("<a>"), None);
+    let spans = highlight("  <name id=\"1\">Rex</name>");
+    let texts: Vec<&str> = spans.iter().map(|(_, text)| text.as_str()).collect();
+    assert_eq!(texts, vec!["  ", "<", "name", " id", "=", "\"1\"", ">", "Rex", "</", "name", ">"]);
+    assert_eq!(spans[2].0, Style::default().fg(Color::LightBlue));
+  }
+}